@@ -0,0 +1,408 @@
+//! # Geschichtete Konfiguration (`config::layers`)
+//!
+//! Dieses Untermodul von [`crate::config`] setzt die Kernkonfiguration aus mehreren Schichten
+//! zusammen. Jede Schicht muss nur die Werte enthalten, die sie ändern möchte; die übrigen
+//! Werte stammen aus den darunterliegenden Schichten.
+//!
+//! ## Vorrang der Schichten (von niedrig nach hoch):
+//!
+//! 1. [`ConfigLayer::Default`]: Die eingebauten Standardwerte ([`CoreConfig::default()`]).
+//! 2. [`ConfigLayer::System`]: Die systemweite Datei `/etc/novade/core.toml`.
+//! 3. [`ConfigLayer::User`]: Die Datei des Benutzers, z.B. `~/.config/novade/core.toml`.
+//! 4. [`ConfigLayer::Environment`]: Umgebungsvariablen wie `NOVADE_LOG_LEVEL`
+//!    (siehe [`crate::config::loader::env_overrides()`]).
+//! 5. [`ConfigLayer::Runtime`]: Überschreibungen zur Laufzeit ([`ConfigOverrides`]),
+//!    z.B. aus Kommandozeilenargumenten.
+//!
+//! Verschachtelte Tabellen (z.B. `config_version`) werden Wert für Wert zusammengeführt.
+//! Die Werte der Umgebungsvariablen sind Zeichenketten; sie werden in den Typ umgewandelt,
+//! den das Feld in den unteren Schichten hat (z.B. `NOVADE_CONFIG_VERSION__MINOR=2` in eine
+//! Zahl), und bleiben Zeichenketten, wo das Feld eine ist oder nicht gesetzt ist.
+//! Fehlende Dateien werden übersprungen; unlesbare oder ungültige Dateien sind ein Fehler.
+//!
+//! ## Herkunft der Werte:
+//!
+//! [`LayeredConfig`] enthält neben der fertigen [`CoreConfig`] für jeden Wert die Schicht,
+//! aus der er stammt (siehe [`LayeredConfig::source_of()`]), damit z.B. Einstellungsdialoge
+//! anzeigen können, warum ein Wert gilt.
+//!
+//! ## Beispiel:
+//!
+//! ```rust,no_run
+//! use novade_core::config::{ConfigLayer, ConfigOverrides, CoreConfig};
+//!
+//! # fn main() -> novade_core::CoreResult<()> {
+//! let layered = CoreConfig::load(&ConfigOverrides::new().set("log_level", "debug"))?;
+//! assert_eq!(layered.config().log_level, "debug");
+//! assert_eq!(layered.source_of("log_level"), Some(ConfigLayer::Runtime));
+//! println!("Lokalisierung aus Schicht {:?}", layered.source_of("default_locale"));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::validation::{check_unknown_keys, Strictness};
+use crate::config::{loader, CoreConfig, DEFAULT_CORE_CONFIG_FILENAME};
+use crate::error::{CoreError, CoreResult};
+use crate::utils::get_app_config_dir;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Das Verzeichnis der systemweiten Konfiguration.
+pub const SYSTEM_CONFIG_DIR: &str = "/etc/novade";
+
+/// Der Name des Anwendungsverzeichnisses im Konfigurationsverzeichnis des Benutzers.
+pub const USER_CONFIG_DIR_NAME: &str = "novade";
+
+/// Eine Schicht der Konfiguration. Spätere Varianten haben Vorrang vor früheren.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfigLayer {
+    /// Die eingebauten Standardwerte.
+    Default,
+    /// Die systemweite Konfigurationsdatei.
+    System,
+    /// Die Konfigurationsdatei des Benutzers.
+    User,
+    /// Umgebungsvariablen mit dem Präfix `NOVADE_`.
+    Environment,
+    /// Überschreibungen zur Laufzeit.
+    Runtime,
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ConfigLayer::Default => "Standardwerte",
+            ConfigLayer::System => "Systemkonfiguration",
+            ConfigLayer::User => "Benutzerkonfiguration",
+            ConfigLayer::Environment => "Umgebungsvariablen",
+            ConfigLayer::Runtime => "Laufzeit",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Überschreibungen der Konfiguration zur Laufzeit, die Vorrang vor allen Dateien haben.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::config::ConfigOverrides;
+///
+/// let overrides = ConfigOverrides::new()
+///     .set("log_level", "trace")
+///     .set("config_version.minor", 2);
+/// assert!(!overrides.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigOverrides {
+    values: Table,
+}
+
+impl ConfigOverrides {
+    /// Erstellt leere Überschreibungen.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Überschreibt den Wert am Schlüssel `key`. Werte in verschachtelten Tabellen werden mit
+    /// Punkten adressiert (z.B. `"config_version.major"`).
+    pub fn set(mut self, key: &str, value: impl Into<Value>) -> Self {
+        let mut segments: Vec<&str> = key.split('.').collect();
+        let last = segments.pop().unwrap_or_default();
+        let mut table = &mut self.values;
+        for segment in segments {
+            let entry = table.entry(segment.to_string()).or_insert_with(|| Value::Table(Table::new()));
+            if !entry.is_table() {
+                *entry = Value::Table(Table::new());
+            }
+            table = entry.as_table_mut().expect("gerade als Tabelle angelegt");
+        }
+        table.insert(last.to_string(), value.into());
+        self
+    }
+
+    /// Ob keine Werte überschrieben werden.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Eine aus mehreren Schichten zusammengesetzte [`CoreConfig`] mit der Herkunft jedes Wertes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayeredConfig {
+    config: CoreConfig,
+    sources: BTreeMap<String, ConfigLayer>,
+}
+
+impl LayeredConfig {
+    /// Die zusammengesetzte Konfiguration.
+    pub fn config(&self) -> &CoreConfig {
+        &self.config
+    }
+
+    /// Gibt die zusammengesetzte Konfiguration zurück und verwirft die Herkunft der Werte.
+    pub fn into_config(self) -> CoreConfig {
+        self.config
+    }
+
+    /// Die Schicht, aus der der Wert am Schlüssel `key` stammt.
+    ///
+    /// Werte in verschachtelten Tabellen werden mit Punkten adressiert
+    /// (z.B. `"config_version.major"`). Für eine Tabelle selbst (z.B. `"config_version"`) wird
+    /// die höchste Schicht geliefert, die einen ihrer Werte gesetzt hat.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn keine Schicht den Schlüssel gesetzt hat (z.B. ein nicht gesetztes
+    /// `custom_theme_path`).
+    pub fn source_of(&self, key: &str) -> Option<ConfigLayer> {
+        if let Some(layer) = self.sources.get(key) {
+            return Some(*layer);
+        }
+        let prefix = format!("{}.", key);
+        self.sources.iter().filter(|(path, _)| path.starts_with(&prefix)).map(|(_, layer)| *layer).max()
+    }
+
+    /// Alle gesetzten Werte mit ihrer Schicht, sortiert nach Schlüssel.
+    pub fn sources(&self) -> impl Iterator<Item = (&str, ConfigLayer)> {
+        self.sources.iter().map(|(key, layer)| (key.as_str(), *layer))
+    }
+}
+
+impl CoreConfig {
+    /// Lädt die Kernkonfiguration aus den Standardschichten.
+    ///
+    /// Führt die Standardwerte, `/etc/novade/core.toml`, die Datei im Konfigurationsverzeichnis
+    /// des Benutzers (z.B. `~/.config/novade/core.toml`), die `NOVADE_*`-Umgebungsvariablen
+    /// und `overrides` in dieser Reihenfolge zusammen (siehe [`crate::config::layers`]).
+    ///
+    /// # Parameter
+    /// * `overrides`: Überschreibungen zur Laufzeit; [`ConfigOverrides::new()`] für keine.
+    ///
+    /// Unbekannte Schlüssel werden als Warnung geloggt; siehe [`CoreConfig::load_with()`] für
+    /// eine strikte Prüfung.
+    ///
+    /// # Fehler
+    /// Siehe [`CoreConfig::load_layers()`].
+    pub fn load(overrides: &ConfigOverrides) -> CoreResult<LayeredConfig> {
+        Self::load_with(overrides, Strictness::Lenient)
+    }
+
+    /// Wie [`CoreConfig::load()`], prüft die Schichten aber mit der angegebenen Strenge auf
+    /// unbekannte Schlüssel (siehe [`crate::config::validation`]).
+    ///
+    /// # Fehler
+    /// Siehe [`CoreConfig::load_layers()`].
+    pub fn load_with(overrides: &ConfigOverrides, strictness: Strictness) -> CoreResult<LayeredConfig> {
+        let system = Path::new(SYSTEM_CONFIG_DIR).join(DEFAULT_CORE_CONFIG_FILENAME);
+        let user = get_app_config_dir(USER_CONFIG_DIR_NAME).map(|dir| dir.join(DEFAULT_CORE_CONFIG_FILENAME));
+        let environment = loader::env_overrides(CoreConfig::FIELDS);
+        Self::load_layers(&system, user.as_deref(), &environment, overrides, strictness)
+    }
+
+    /// Lädt die Kernkonfiguration aus den angegebenen Dateien.
+    ///
+    /// # Parameter
+    /// * `system`: Die systemweite Konfigurationsdatei.
+    /// * `user`: Die Konfigurationsdatei des Benutzers, falls ermittelbar.
+    /// * `environment`: Die Überschreibungen aus Umgebungsvariablen.
+    /// * `overrides`: Überschreibungen zur Laufzeit.
+    /// * `strictness`: Ob unbekannte Schlüssel in einer Schicht ein Fehler sind.
+    ///
+    /// # Rückgabe
+    /// Die zusammengesetzte Konfiguration mit der Herkunft jedes Wertes. Nicht vorhandene
+    /// Dateien werden übersprungen.
+    ///
+    /// # Fehler
+    /// - `CoreError::ConfigLoadError`, wenn eine vorhandene Datei nicht gelesen werden kann.
+    /// - `CoreError::ConfigParseError`, wenn eine Datei kein gültiges TOML ist oder das
+    ///   Ergebnis nicht zur `CoreConfig` passt.
+    /// - `CoreError::ConfigValidationError`, wenn das Ergebnis [`CoreConfig::validate()`] nicht
+    ///   besteht oder eine Schicht mit [`Strictness::Strict`] unbekannte Schlüssel enthält.
+    pub fn load_layers(
+        system: &Path,
+        user: Option<&Path>,
+        environment: &ConfigOverrides,
+        overrides: &ConfigOverrides,
+        strictness: Strictness,
+    ) -> CoreResult<LayeredConfig> {
+        let defaults = Value::try_from(CoreConfig::default()).map_err(|err| CoreError::SerializationError {
+            format: "TOML".to_string(),
+            message: err.to_string(),
+        })?;
+        let mut merged = Table::new();
+        let mut sources = BTreeMap::new();
+        if let Value::Table(defaults) = defaults {
+            merge(&mut merged, defaults, ConfigLayer::Default, "", &mut sources);
+        }
+        for (layer, path) in [(ConfigLayer::System, Some(system)), (ConfigLayer::User, user)] {
+            let Some(path) = path else {
+                continue;
+            };
+            if let Some(table) = read_layer(path)? {
+                check_unknown_keys(&table, &path.display().to_string(), strictness)?;
+                merge(&mut merged, table, layer, "", &mut sources);
+            }
+        }
+        check_unknown_keys(&environment.values, &ConfigLayer::Environment.to_string(), strictness)?;
+        let environment = coerce_strings(environment.values.clone(), &merged);
+        merge(&mut merged, environment, ConfigLayer::Environment, "", &mut sources);
+        check_unknown_keys(&overrides.values, &ConfigLayer::Runtime.to_string(), strictness)?;
+        merge(&mut merged, overrides.values.clone(), ConfigLayer::Runtime, "", &mut sources);
+
+        let config: CoreConfig = Value::Table(merged).try_into().map_err(|err: toml::de::Error| CoreError::ConfigParseError {
+            format: "TOML".to_string(),
+            message: err.to_string(),
+        })?;
+        config.validate()?;
+        Ok(LayeredConfig { config, sources })
+    }
+}
+
+/// Liest die Datei einer Schicht im Format ihrer Dateiendung; `None`, wenn sie nicht existiert.
+fn read_layer(path: &Path) -> CoreResult<Option<Table>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(CoreError::ConfigLoadError { path: PathBuf::from(path), source: err });
+        }
+    };
+    loader::ConfigFormat::from_path(path).parse::<Table>(&content).map(Some).map_err(|err| match err {
+        CoreError::ConfigParseError { format, message } => {
+            CoreError::ConfigParseError { format, message: format!("{}: {}", path.display(), message) }
+        }
+        err => err,
+    })
+}
+
+/// Wandelt die Zeichenketten in `layer` in den Typ des Wertes am selben Schlüssel in
+/// `template` um. Lässt sich eine Zeichenkette nicht umwandeln, bleibt sie erhalten, damit
+/// das Deserialisieren den Fehler mit dem Feldnamen meldet.
+fn coerce_strings(layer: Table, template: &Table) -> Table {
+    layer
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match (value, template.get(&key)) {
+                (Value::Table(table), Some(Value::Table(template))) => Value::Table(coerce_strings(table, template)),
+                (Value::String(text), Some(Value::Integer(_))) => text.parse().map(Value::Integer).unwrap_or(Value::String(text)),
+                (Value::String(text), Some(Value::Float(_))) => text.parse().map(Value::Float).unwrap_or(Value::String(text)),
+                (Value::String(text), Some(Value::Boolean(_))) => text.parse().map(Value::Boolean).unwrap_or(Value::String(text)),
+                (value, _) => value,
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+/// Führt `layer` in `target` zusammen und vermerkt für jeden gesetzten Wert die Schicht.
+fn merge(target: &mut Table, layer: Table, source: ConfigLayer, prefix: &str, sources: &mut BTreeMap<String, ConfigLayer>) {
+    for (key, value) in layer {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match (target.get_mut(&key), value) {
+            (Some(Value::Table(existing)), Value::Table(table)) => merge(existing, table, source, &path, sources),
+            (_, value) => {
+                // Ein Wert ersetzt alles, was die unteren Schichten unter diesem Schlüssel gesetzt haben.
+                let nested = format!("{}.", path);
+                sources.retain(|key, _| !key.starts_with(&nested));
+                record(&value, source, &path, sources);
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+fn record(value: &Value, source: ConfigLayer, path: &str, sources: &mut BTreeMap<String, ConfigLayer>) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                record(value, source, &format!("{}.{}", path, key), sources);
+            }
+        }
+        _ => {
+            sources.insert(path.to_string(), source);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Version;
+
+    #[test]
+    fn test_layers_are_merged_by_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let system = dir.path().join("system.toml");
+        let user = dir.path().join("user.toml");
+        fs::write(&system, "log_level = \"warn\"\ndefault_locale = \"de-DE\"\nconfig_version = { major = 1, minor = 1, patch = 0 }\n").unwrap();
+        fs::write(&user, "log_level = \"error\"\n[config_version]\nminor = 3\n").unwrap();
+
+        let environment = loader::env_overrides_from(
+            [("NOVADE_DEFAULT_LOCALE".to_string(), "fr-FR".to_string()), ("NOVADE_LOG_LEVEL".to_string(), "warn".to_string())],
+            CoreConfig::FIELDS,
+        );
+        let overrides = ConfigOverrides::new().set("custom_theme_path", "/tmp/themes").set("log_level", "debug");
+        let layered = CoreConfig::load_layers(&system, Some(&user), &environment, &overrides, Strictness::Strict).unwrap();
+        let config = layered.config();
+        assert_eq!((config.log_level.as_str(), config.default_locale.as_str()), ("debug", "fr-FR"));
+        assert_eq!(config.config_version, Version::new(1, 3, 0));
+        assert_eq!(config.custom_theme_path, Some(PathBuf::from("/tmp/themes")));
+
+        assert_eq!(layered.source_of("log_level"), Some(ConfigLayer::Runtime));
+        assert_eq!(layered.source_of("default_locale"), Some(ConfigLayer::Environment));
+        assert_eq!(layered.source_of("config_version.major"), Some(ConfigLayer::System));
+        assert_eq!(layered.source_of("config_version"), Some(ConfigLayer::User));
+        assert_eq!(layered.source_of("custom_theme_path"), Some(ConfigLayer::Runtime));
+        assert_eq!(layered.source_of("unbekannt"), None);
+    }
+
+    #[test]
+    fn test_environment_values_take_the_field_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let environment = loader::env_overrides_from(
+            [
+                ("NOVADE_CUSTOM_THEME_PATH".to_string(), "2024".to_string()),
+                ("NOVADE_CONFIG_VERSION__MINOR".to_string(), "2".to_string()),
+                ("NOVADE_LOG_FILE__ENABLED".to_string(), "true".to_string()),
+            ],
+            CoreConfig::FIELDS,
+        );
+        let none = ConfigOverrides::new();
+        let layered = CoreConfig::load_layers(&dir.path().join("fehlt.toml"), None, &environment, &none, Strictness::Strict).unwrap();
+        let config = layered.config();
+        assert_eq!(config.custom_theme_path, Some(PathBuf::from("2024")));
+        assert_eq!(config.config_version.minor, 2);
+        assert!(config.log_file.enabled);
+
+        // Eine Zeichenkette bleibt eine, auch wenn sie wie ein Wahrheitswert aussieht; sie
+        // scheitert erst an der Prüfung der Locale, nicht am Typ.
+        let locale = loader::env_overrides_from([("NOVADE_DEFAULT_LOCALE".to_string(), "true".to_string())], CoreConfig::FIELDS);
+        let result = CoreConfig::load_layers(&dir.path().join("fehlt.toml"), None, &locale, &none, Strictness::Strict);
+        assert!(matches!(result, Err(CoreError::ConfigValidationError { field, .. }) if field == "default_locale"));
+
+        // Keine Zahl für ein Zahlenfeld: Der Fehler nennt das Feld.
+        let invalid = loader::env_overrides_from([("NOVADE_CONFIG_VERSION__MAJOR".to_string(), "zwei".to_string())], CoreConfig::FIELDS);
+        let result = CoreConfig::load_layers(&dir.path().join("fehlt.toml"), None, &invalid, &none, Strictness::Strict);
+        assert!(matches!(result, Err(CoreError::ConfigParseError { message, .. }) if message.contains("major")));
+    }
+
+    #[test]
+    fn test_missing_files_fall_back_to_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let none = ConfigOverrides::new();
+        let layered = CoreConfig::load_layers(&dir.path().join("fehlt.toml"), None, &none, &none, Strictness::Strict).unwrap();
+        assert_eq!(layered.config(), &CoreConfig::example());
+        assert!(layered.sources().all(|(_, layer)| layer == ConfigLayer::Default));
+        assert_eq!(layered.source_of("custom_theme_path"), None);
+
+        let invalid = CoreConfig::load_layers(&dir.path().join("fehlt.toml"), None, &none, &none.clone().set("log_level", "laut"), Strictness::Lenient);
+        assert!(matches!(invalid, Err(CoreError::ConfigValidationError { .. })));
+
+        let typo = none.clone().set("config_version.majr", 2);
+        assert!(CoreConfig::load_layers(&dir.path().join("fehlt.toml"), None, &none, &typo, Strictness::Lenient).is_ok());
+        let strict = CoreConfig::load_layers(&dir.path().join("fehlt.toml"), None, &none, &typo, Strictness::Strict);
+        assert!(matches!(strict, Err(CoreError::ConfigValidationError { field, .. }) if field == "config_version.majr"));
+    }
+}
@@ -6,14 +6,38 @@
 //! ## Hauptfunktionalität:
 //!
 //! - [`load_config_from_file()`]: Eine generische Funktion, die eine Datei von einem
-//!   gegebenen Pfad liest, ihren Inhalt als TOML interpretiert und versucht,
+//!   gegebenen Pfad liest, ihren Inhalt im passenden [`ConfigFormat`] interpretiert und versucht,
 //!   diesen in einen beliebigen Typ `T` zu deserialisieren, der `serde::Deserialize` implementiert.
+//! - [`ConfigFormat`]: Das Format einer Konfigurationsdatei, gewählt anhand der Dateiendung.
+//! - [`env_overrides()`]: Liest Umgebungsvariablen mit dem Präfix [`ENV_PREFIX`] (`NOVADE_`) als
+//!   [`ConfigOverrides`], damit z.B. Container und CI die Konfiguration ohne Dateien setzen können.
+//!
+//! ## Formate:
+//!
+//! | Dateiendung        | Format | Cargo-Feature |
+//! |--------------------|--------|---------------|
+//! | `.toml` und andere | TOML   | immer         |
+//! | `.json`            | JSON   | `json`        |
+//! | `.yaml`, `.yml`    | YAML   | `yaml`        |
+//!
+//! Ist das Feature eines Formats nicht aktiviert, schlägt das Laden mit einem
+//! `CoreError::ConfigParseError` fehl, der das fehlende Feature nennt.
+//!
+//! ## Umgebungsvariablen:
+//!
+//! Der Name einer Variablen ergibt sich aus dem Feldnamen in Großbuchstaben mit dem Präfix
+//! `NOVADE_` (z.B. `NOVADE_LOG_LEVEL` für `log_level`). Felder verschachtelter Tabellen werden
+//! mit doppeltem Unterstrich getrennt (z.B. `NOVADE_CONFIG_VERSION__MAJOR` für
+//! `config_version.major`). Die Werte werden als Zeichenketten übernommen und erst beim
+//! Zusammenführen der Schichten in den Typ des Feldes umgewandelt (siehe
+//! [`crate::config::layers`]), sodass z.B. `NOVADE_CUSTOM_THEME_PATH=2024` ein Pfad bleibt.
+//! Variablen, die zu keinem bekannten Feld gehören, werden ignoriert.
 //!
 //! ## Fehlerbehandlung:
 //!
 //! Die Ladefunktion gibt spezifische Fehler aus `CoreError` zurück, wie z.B.:
 //! - `CoreError::ConfigLoadError`: Wenn die Datei nicht gelesen werden kann (z.B. nicht vorhanden, keine Berechtigungen).
-//! - `CoreError::ConfigParseError`: Wenn der Inhalt der Datei kein valides TOML ist oder nicht zur Zielstruktur passt.
+//! - `CoreError::ConfigParseError`: Wenn der Inhalt der Datei nicht dem Format entspricht oder nicht zur Zielstruktur passt.
 //!
 //! ## Beispielhafte Verwendung (intern durch `CoreConfig`):
 //!
@@ -31,20 +55,117 @@
 //! Normalerweise wird diese Funktion nicht direkt von außerhalb des `config`-Moduls aufgerufen,
 //! sondern über Methoden wie [`crate::config::CoreConfig::load_from_path()`].
 
+use crate::config::ConfigOverrides;
 use crate::error::{CoreError, CoreResult};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+/// Das Präfix der Umgebungsvariablen, die Konfigurationswerte überschreiben.
+pub const ENV_PREFIX: &str = "NOVADE_";
+
+/// Das Trennzeichen für Felder verschachtelter Tabellen in Umgebungsvariablen.
+const ENV_NESTING_SEPARATOR: &str = "__";
+
+/// Das Format einer Konfigurationsdatei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    /// Benötigt das Cargo-Feature `json`.
+    Json,
+    /// Benötigt das Cargo-Feature `yaml`.
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Wählt das Format anhand der Dateiendung von `path` (Groß-/Kleinschreibung egal).
+    /// Dateien ohne oder mit unbekannter Endung gelten als TOML.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_core::config::loader::ConfigFormat;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(ConfigFormat::from_path(Path::new("core.toml")), ConfigFormat::Toml);
+    /// assert_eq!(ConfigFormat::from_path(Path::new("generiert.JSON")), ConfigFormat::Json);
+    /// assert_eq!(ConfigFormat::from_path(Path::new("core.yml")), ConfigFormat::Yaml);
+    /// assert_eq!(ConfigFormat::from_path(Path::new("core.conf")), ConfigFormat::Toml);
+    /// ```
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path.extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("json") => ConfigFormat::Json,
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Toml,
+        }
+    }
+
+    /// Der Name des Formats, wie er in `CoreError::ConfigParseError::format` erscheint.
+    pub fn name(self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "TOML",
+            ConfigFormat::Json => "JSON",
+            ConfigFormat::Yaml => "YAML",
+        }
+    }
+
+    /// Deserialisiert `content` in diesem Format in den Typ `T`.
+    ///
+    /// # Fehler
+    /// `CoreError::ConfigParseError`, wenn `content` nicht dem Format entspricht, nicht zu `T`
+    /// passt oder das Cargo-Feature des Formats nicht aktiviert ist.
+    pub fn parse<T>(self, content: &str) -> CoreResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let parse_error = |message: String| CoreError::ConfigParseError { format: self.name().to_string(), message };
+        match self {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|err| parse_error(err.to_string())),
+            #[cfg(feature = "json")]
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|err| parse_error(err.to_string())),
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|err| parse_error(err.to_string())),
+            #[allow(unreachable_patterns)]
+            _ => Err(parse_error(self.missing_feature_message())),
+        }
+    }
+
+    /// Serialisiert `value` in diesem Format.
+    ///
+    /// # Fehler
+    /// `CoreError::SerializationError`, wenn `value` sich nicht darstellen lässt oder das
+    /// Cargo-Feature des Formats nicht aktiviert ist.
+    pub fn serialize<T: Serialize>(self, value: &T) -> CoreResult<String> {
+        let serialization_error = |message: String| CoreError::SerializationError { format: self.name().to_string(), message };
+        match self {
+            ConfigFormat::Toml => toml::to_string_pretty(value).map_err(|err| serialization_error(err.to_string())),
+            #[cfg(feature = "json")]
+            ConfigFormat::Json => serde_json::to_string_pretty(value).map_err(|err| serialization_error(err.to_string())),
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => serde_yaml::to_string(value).map_err(|err| serialization_error(err.to_string())),
+            #[allow(unreachable_patterns)]
+            _ => Err(serialization_error(self.missing_feature_message())),
+        }
+    }
+
+    fn missing_feature_message(self) -> String {
+        format!(
+            "Das Format wird nicht unterstützt; novade-core muss mit dem Feature `{}` gebaut werden.",
+            self.name().to_ascii_lowercase()
+        )
+    }
+}
+
 /// Lädt und deserialisiert eine Konfigurationsdatei vom angegebenen Pfad.
 ///
-/// Diese Funktion liest den gesamten Inhalt der Datei am `path`, interpretiert ihn als
-/// TOML-formatierten String und versucht dann, ihn in den Zieltyp `T` zu deserialisieren.
+/// Diese Funktion liest den gesamten Inhalt der Datei am `path`, interpretiert ihn im
+/// anhand der Dateiendung gewählten [`ConfigFormat`] (standardmäßig TOML) und versucht dann,
+/// ihn in den Zieltyp `T` zu deserialisieren.
 /// Der Typ `T` muss das `serde::Deserialize` Trait implementieren.
 ///
 /// # Typparameter
 /// * `T`: Der Typ, in den der Inhalt der Konfigurationsdatei deserialisiert werden soll.
-///        Muss `serde::Deserialize` implementieren.
+///   Muss `serde::Deserialize` implementieren.
 ///
 /// # Parameter
 /// * `path`: Ein Referenz auf den `Path` der zu ladenden Konfigurationsdatei.
@@ -55,20 +176,70 @@ use std::path::Path;
 /// - `Err(CoreError)`: Im Fehlerfall, z.B.:
 ///     - [`CoreError::ConfigLoadError`]: Wenn die Datei nicht gefunden wurde oder nicht gelesen werden konnte.
 ///       Der ursprüngliche `std::io::Error` wird als `source` mitgeführt.
-///     - [`CoreError::ConfigParseError`]: Wenn der Dateiinhalt kein gültiges TOML war oder nicht
-///       zur Struktur von `T` passte. Die Fehlermeldung des TOML-Parsers wird mitgeliefert.
+///     - [`CoreError::ConfigParseError`]: Wenn der Dateiinhalt nicht dem Format entsprach, nicht
+///       zur Struktur von `T` passte oder das Format nicht aktiviert ist. Die Fehlermeldung des
+///       Parsers wird mitgeliefert.
 pub fn load_config_from_file<T>(path: &Path) -> CoreResult<T>
 where
     T: for<'de> Deserialize<'de>, // T muss für jede Lifetime 'de deserialisierbar sein.
 {
-    let content = fs::read_to_string(path).map_err(|err| CoreError::ConfigLoadError {
+    let content = read_config_file(path)?;
+    ConfigFormat::from_path(path).parse(&content)
+}
+
+/// Liest den Inhalt einer Konfigurationsdatei; Fehler werden als `CoreError::ConfigLoadError` gemeldet.
+pub(crate) fn read_config_file(path: &Path) -> CoreResult<String> {
+    fs::read_to_string(path).map_err(|err| CoreError::ConfigLoadError {
         path: path.to_path_buf(), // Klone den Pfad für die Fehlerstruktur.
-        error_message: err.to_string(), // .source zu .error_message und err.to_string()
-    })?;
+        source: err,
+    })
+}
+
+/// Liest die Überschreibungen der Felder `fields` aus den Umgebungsvariablen des Prozesses.
+///
+/// Siehe [`env_overrides_from()`] und die Modul-Dokumentation für die Benennung der Variablen.
+///
+/// # Parameter
+/// * `fields`: Die Felder der obersten Ebene, die überschrieben werden dürfen (z.B. `["log_level"]`).
+pub fn env_overrides(fields: &[&str]) -> ConfigOverrides {
+    env_overrides_from(std::env::vars(), fields)
+}
 
-    toml::from_str(&content).map_err(|err| CoreError::ConfigParseError {
-        format: "TOML".to_string(),
-        message: err.to_string(),
+/// Bildet Variablen mit dem Präfix [`ENV_PREFIX`] auf Überschreibungen der Felder `fields` ab.
+///
+/// # Parameter
+/// * `vars`: Die Variablen als Paare aus Name und Wert, z.B. aus `std::env::vars()`.
+/// * `fields`: Die Felder der obersten Ebene, die überschrieben werden dürfen.
+///
+/// # Rückgabe
+/// Die Überschreibungen mit den Werten als Zeichenketten; Variablen ohne Präfix oder zu
+/// unbekannten Feldern werden ignoriert.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::config::loader::env_overrides_from;
+/// use novade_core::config::ConfigOverrides;
+///
+/// let vars = vec![
+///     ("NOVADE_LOG_LEVEL".to_string(), "debug".to_string()),
+///     ("NOVADE_CONFIG_VERSION__MINOR".to_string(), "2".to_string()),
+///     ("NOVADE_UNBEKANNT".to_string(), "x".to_string()),
+///     ("HOME".to_string(), "/root".to_string()),
+/// ];
+/// let overrides = env_overrides_from(vars, &["log_level", "config_version"]);
+/// assert_eq!(overrides, ConfigOverrides::new().set("log_level", "debug").set("config_version.minor", "2"));
+/// ```
+pub fn env_overrides_from(vars: impl IntoIterator<Item = (String, String)>, fields: &[&str]) -> ConfigOverrides {
+    vars.into_iter().fold(ConfigOverrides::new(), |overrides, (name, value)| {
+        let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+            return overrides;
+        };
+        let key = key.to_lowercase().replace(ENV_NESTING_SEPARATOR, ".");
+        let field = key.split('.').next().unwrap_or_default();
+        if !fields.contains(&field) {
+            return overrides;
+        }
+        overrides.set(&key, value)
     })
 }
 
@@ -124,6 +295,25 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_load_config_by_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("config.json");
+        std::fs::write(&json, r#"{ "name": "Test Name", "count": 42 }"#).unwrap();
+        let yaml = dir.path().join("config.yml");
+        std::fs::write(&yaml, "name: Test Name\ncount: 42\n").unwrap();
+        let expected = TestConfig { name: "Test Name".to_string(), count: 42 };
+
+        for (path, format, enabled) in [(json, "JSON", cfg!(feature = "json")), (yaml, "YAML", cfg!(feature = "yaml"))] {
+            let result: CoreResult<TestConfig> = load_config_from_file(&path);
+            if enabled {
+                assert_eq!(result.unwrap(), expected);
+            } else {
+                assert!(matches!(result, Err(CoreError::ConfigParseError { format: f, message }) if f == format && message.contains("Feature")));
+            }
+        }
+    }
+
     #[test]
     fn test_load_mismatched_structure() {
         let mut temp_file = NamedTempFile::new().unwrap();
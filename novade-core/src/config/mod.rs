@@ -6,16 +6,24 @@
 //! ## Hauptkomponenten:
 //!
 //! - [`CoreConfig`]: Eine Struktur, die die Kernkonfigurationsparameter wie Log-Level,
-//!   Standard-Lokalisierung und Version der Konfigurationsdatei enthält.
+//!   Standard-Lokalisierung, Logdateien ([`LogFileConfig`]) und Version der Konfigurationsdatei enthält.
 //! - [`DEFAULT_CORE_CONFIG_FILENAME`]: Der Standarddateiname ("core.toml"), der für die
 //!   Kernkonfiguration verwendet wird.
 //! - [`loader`]: Ein Untermodul, das die Funktionalität zum Laden von Konfigurationsdateien
-//!   (aktuell TOML) bereitstellt.
+//!   (aktuell TOML) und zum Lesen der `NOVADE_*`-Umgebungsvariablen bereitstellt.
+//! - [`layers`]: Ein Untermodul, das die Konfiguration aus Standardwerten, System- und
+//!   Benutzerdatei sowie Überschreibungen zur Laufzeit zusammensetzt ([`CoreConfig::load()`])
+//!   und die Herkunft jedes Wertes festhält ([`LayeredConfig`]).
+//! - [`validation`]: Ein Untermodul, das Dateien auf unbekannte Schlüssel (z.B. Tippfehler wie
+//!   `log_lvel`) prüft und Korrekturen vorschlägt, wahlweise strikt ([`Strictness`]).
+//! - [`watcher`]: Ein Untermodul mit dem [`ConfigWatcher`], der eine Konfigurationsdatei
+//!   überwacht und Änderungen zur Laufzeit als [`ConfigChanged`] meldet.
 //!
 //! ## Verwendung:
 //!
-//! Die `CoreConfig` kann typischerweise durch Aufruf von [`CoreConfig::load_from_path()`]
-//! geladen werden, wobei der Pfad zu einer TOML-Datei übergeben wird.
+//! Die `CoreConfig` wird typischerweise durch Aufruf von [`CoreConfig::load()`] aus den
+//! Standardschichten geladen. Eine einzelne TOML-Datei lässt sich mit
+//! [`CoreConfig::load_from_path()`] laden.
 //! Für Tests oder Standardwerte kann [`CoreConfig::example()`] verwendet werden.
 //!
 //! ```rust,no_run
@@ -33,12 +41,24 @@
 //! // println!("Log-Level: {}", config.log_level);
 //! ```
 
+pub mod layers;
 pub mod loader;
+pub mod validation;
+pub mod watcher;
 
-use crate::error::CoreResult;
+pub use layers::{ConfigLayer, ConfigOverrides, LayeredConfig};
+pub use validation::{Strictness, UnknownKey};
+pub use watcher::{ConfigChanged, ConfigWatcher, WatchedConfig};
+
+use crate::error::{CoreError, CoreResult};
 use crate::types::Version;
-use serde::Deserialize;
+use crate::utils::write_file_atomically;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing_subscriber::filter::LevelFilter;
+use toml_edit::{DocumentMut, TableLike};
+use tracing_subscriber::EnvFilter;
 
 /// Der Standard-Dateiname für die Kernkonfigurationsdatei von NovaDE.
 ///
@@ -51,45 +71,292 @@ pub const DEFAULT_CORE_CONFIG_FILENAME: &str = "core.toml";
 ///
 /// Diese Struktur wird aus einer Konfigurationsdatei (z.B. TOML) deserialisiert und
 /// enthält grundlegende Einstellungen für die Anwendung.
-#[derive(Deserialize, Debug, PartialEq)]
+///
+/// Jedes Feld ist in der Datei optional; fehlende Felder erhalten ihren Standardwert aus
+/// [`CoreConfig::default()`]. Eine Datei, die nur `log_level = "debug"` enthält, ist daher
+/// eine vollständige Konfiguration.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
 pub struct CoreConfig {
     /// Das zu verwendende globale Log-Level (z.B. "debug", "info", "warn", "error").
     ///
     /// Dieses Level kann durch die `RUST_LOG` Umgebungsvariable überschrieben werden,
     /// falls diese gesetzt ist. Siehe [`novade_core::logging::setup::initialize_logging()`].
+    ///
+    /// Standardwert: `"info"`. Umgebungsvariable: `NOVADE_LOG_LEVEL`.
     pub log_level: String,
 
     /// Die Standard-Lokalisierung für die Anwendung (z.B. "en-US", "de-DE").
     ///
     /// Wird verwendet, wenn keine spezifischere Lokalisierung verfügbar oder eingestellt ist.
+    ///
+    /// Standardwert: `"en-US"`. Umgebungsvariable: `NOVADE_DEFAULT_LOCALE`.
     pub default_locale: String,
 
     /// Die Version der Konfigurationsdatei-Struktur selbst.
     ///
     /// Dies ermöglicht es, bei zukünftigen Änderungen an der Struktur der Konfigurationsdatei
     /// Migrationen oder Kompatibilitätsprüfungen durchzuführen.
+    ///
+    /// Standardwert: `1.0.0`. Umgebungsvariablen: `NOVADE_CONFIG_VERSION__MAJOR`, `NOVADE_CONFIG_VERSION__MINOR`
+    /// und `NOVADE_CONFIG_VERSION__PATCH`.
     pub config_version: Version,
 
     /// Ein optionaler Pfad zu einem benutzerdefinierten Theme-Verzeichnis.
     ///
     /// Wenn gesetzt, kann die UI-Schicht versuchen, Themes von diesem Pfad zu laden.
+    ///
+    /// Standardwert: nicht gesetzt. Umgebungsvariable: `NOVADE_CUSTOM_THEME_PATH`.
     pub custom_theme_path: Option<PathBuf>,
+
+    /// Wohin die Logs ausgegeben werden: nach `stderr`, in das systemd-Journal oder beides.
+    /// Die Logdateien aus [`CoreConfig::log_file`] kommen unabhängig davon hinzu.
+    ///
+    /// Standardwert: `"stderr"`. Umgebungsvariable: `NOVADE_LOG_OUTPUT`.
+    pub log_output: LogOutput,
+
+    /// Das Format der Logs nach `stderr` und in Logdateien: lesbarer Text oder eine JSON-Zeile
+    /// je Eintrag für Aggregationssysteme (siehe [`crate::logging::json`]).
+    ///
+    /// Standardwert: `"text"`. Umgebungsvariable: `NOVADE_LOG_FORMAT`.
+    pub log_format: LogFormat,
+
+    /// Ob und wie zusätzlich in rotierende Logdateien geloggt wird (Tabelle `[log_file]`).
+    ///
+    /// Standardwert: keine Logdateien. Umgebungsvariablen: `NOVADE_LOG_FILE__ENABLED`,
+    /// `NOVADE_LOG_FILE__DIRECTORY`, `NOVADE_LOG_FILE__ROTATION`, `NOVADE_LOG_FILE__MAX_BYTES`
+    /// und `NOVADE_LOG_FILE__MAX_FILES`.
+    pub log_file: LogFileConfig,
+}
+
+/// Die Ausgabe der Logs (siehe [`crate::logging::setup::initialize_logging()`]).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogOutput {
+    /// Nur nach `stderr`.
+    #[default]
+    Stderr,
+    /// Nur in das systemd-Journal, mit strukturierten Feldern. Benötigt das Cargo-Feature
+    /// `journald`; ist das Journal nicht erreichbar, wird stattdessen nach `stderr` geloggt.
+    Journald,
+    /// Nach `stderr` und in das systemd-Journal.
+    Both,
+}
+
+/// Das Format der Logs nach `stderr` und in Logdateien.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Lesbarer Text mit Zeitstempel, Level, Thread und Ziel.
+    #[default]
+    Text,
+    /// Eine JSON-Zeile je Eintrag, mit den Feldern der umgebenden Spans auf oberster Ebene.
+    Json,
+}
+
+/// Wann eine neue Logdatei begonnen wird.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// Mit jedem neuen Tag (Ortszeit).
+    #[default]
+    Daily,
+    /// Sobald die Datei [`LogFileConfig::max_bytes`] überschreiten würde.
+    Size,
+}
+
+/// Die Einstellungen der Logdateien (siehe [`crate::logging::file`]).
+///
+/// Wie bei [`CoreConfig`] ist jedes Feld in der Datei optional.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct LogFileConfig {
+    /// Ob zusätzlich zu `stderr` in Logdateien geloggt wird. Standardwert: `false`.
+    pub enabled: bool,
+
+    /// Das Verzeichnis der Logdateien.
+    ///
+    /// Standardwert: das Unterverzeichnis `logs` im Zustandsverzeichnis von NovaDE
+    /// (z.B. `~/.local/state/novade/logs`, siehe [`crate::utils::get_app_state_dir()`]).
+    pub directory: Option<PathBuf>,
+
+    /// Wann eine neue Logdatei begonnen wird. Standardwert: `"daily"`.
+    pub rotation: LogRotation,
+
+    /// Die Größe in Bytes, ab der bei `rotation = "size"` eine neue Datei begonnen wird.
+    /// Standardwert: 10 MiB.
+    pub max_bytes: u64,
+
+    /// Die Anzahl der Logdateien, die höchstens aufbewahrt werden; ältere werden gelöscht.
+    /// Standardwert: `7`.
+    pub max_files: usize,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self { enabled: false, directory: None, rotation: LogRotation::Daily, max_bytes: 10 * 1024 * 1024, max_files: 7 }
+    }
 }
 
 impl CoreConfig {
+    /// Die Felder der obersten Ebene, die über `NOVADE_*`-Umgebungsvariablen überschrieben
+    /// werden können (siehe [`loader::env_overrides()`]).
+    pub const FIELDS: &'static [&'static str] = &["log_level", "default_locale", "config_version", "custom_theme_path", "log_output", "log_format", "log_file"];
+
+    /// Die Version der Konfigurationsstruktur, die dieser Code liest. Eine `config_version`
+    /// wird nur akzeptiert, wenn sie damit kompatibel ist (siehe [`Version::is_compatible_with()`]).
+    pub const SUPPORTED_CONFIG_VERSION: Version = Version::new(1, 0, 0);
+
     /// Lädt die Kernkonfiguration aus der Datei am angegebenen Pfad.
     ///
-    /// Diese Methode delegiert an [`loader::load_config_from_file()`] und erwartet,
-    /// dass die Datei im TOML-Format vorliegt.
+    /// Das Format (TOML, JSON oder YAML) ergibt sich aus der Dateiendung (siehe
+    /// [`loader::load_config_from_file()`]). Unbekannte Schlüssel werden als Warnung geloggt;
+    /// siehe [`CoreConfig::load_from_path_with()`] für eine strikte Prüfung.
     ///
     /// # Parameter
     /// * `path`: Der Pfad zur Konfigurationsdatei (z.B. `core.toml`).
     ///
     /// # Fehler
-    /// Gibt `CoreError` zurück, wenn die Datei nicht gelesen werden kann (`ConfigLoadError`)
-    /// oder wenn der Inhalt nicht als `CoreConfig` deserialisiert werden kann (`ConfigParseError`).
+    /// Gibt `CoreError` zurück, wenn die Datei nicht gelesen werden kann (`ConfigLoadError`),
+    /// wenn der Inhalt nicht als `CoreConfig` deserialisiert werden kann (`ConfigParseError`)
+    /// oder wenn die Werte [`CoreConfig::validate()`] nicht bestehen (`ConfigValidationError`).
     pub fn load_from_path(path: &Path) -> CoreResult<Self> {
-        loader::load_config_from_file(path)
+        Self::load_from_path_with(path, Strictness::Lenient)
+    }
+
+    /// Lädt die Kernkonfiguration aus der Datei am angegebenen Pfad und prüft sie mit der
+    /// angegebenen Strenge auf unbekannte Schlüssel (siehe [`validation`]).
+    ///
+    /// # Parameter
+    /// * `path`: Der Pfad zur Konfigurationsdatei (z.B. `core.toml`).
+    /// * `strictness`: Ob unbekannte Schlüssel (z.B. Tippfehler wie `log_lvel`) ein Fehler sind.
+    ///
+    /// # Fehler
+    /// Wie [`CoreConfig::load_from_path()`]; mit [`Strictness::Strict`] zusätzlich
+    /// `ConfigValidationError` für unbekannte Schlüssel, mit Korrekturvorschlag.
+    ///
+    /// # Beispiele
+    /// ```no_run
+    /// use novade_core::config::{CoreConfig, Strictness};
+    /// use std::path::Path;
+    ///
+    /// match CoreConfig::load_from_path_with(Path::new("core.toml"), Strictness::Strict) {
+    ///     Ok(config) => println!("Log-Level: {}", config.log_level),
+    ///     // z.B. "core.toml: Unbekannter Schlüssel 'log_lvel' (meinten Sie 'log_level'?)."
+    ///     Err(e) => eprintln!("{}", e),
+    /// }
+    /// ```
+    pub fn load_from_path_with(path: &Path, strictness: Strictness) -> CoreResult<Self> {
+        let content = loader::read_config_file(path)?;
+        let format = loader::ConfigFormat::from_path(path);
+        if let Ok(table) = format.parse::<toml::Table>(&content) {
+            validation::check_unknown_keys(&table, &path.display().to_string(), strictness)?;
+        }
+        let config: CoreConfig = format.parse(&content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Speichert die Konfiguration in die Datei am angegebenen Pfad, im Format ihrer
+    /// Dateiendung (siehe [`loader::ConfigFormat`]).
+    ///
+    /// Existiert eine TOML-Datei bereits und ist gültig, werden nur die Werte ersetzt:
+    /// Kommentare, Formatierung, Reihenfolge und Schlüssel, die `CoreConfig` nicht kennt,
+    /// bleiben erhalten. Andernfalls wird die Datei neu geschrieben. Das Schreiben ist atomar
+    /// (siehe [`crate::utils::write_file_atomically()`]), sodass ein [`ConfigWatcher`] nie
+    /// eine halb geschriebene Datei liest.
+    ///
+    /// # Parameter
+    /// * `path`: Der Pfad zur Konfigurationsdatei (z.B. `core.toml`). Das Verzeichnis muss existieren.
+    ///
+    /// # Fehler
+    /// - `CoreError::SerializationError`, wenn die Konfiguration nicht im Format dargestellt werden
+    ///   kann oder das Format nicht aktiviert ist.
+    /// - `CoreError::ConfigLoadError`, wenn eine vorhandene Datei nicht gelesen werden kann.
+    /// - `CoreError::IoError`, wenn die Datei nicht geschrieben werden kann.
+    ///
+    /// # Beispiele
+    /// ```no_run
+    /// use novade_core::config::CoreConfig;
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("/home/benutzer/.config/novade/core.toml");
+    /// let mut config = CoreConfig::load_from_path(path).unwrap();
+    /// config.log_level = "debug".to_string();
+    /// config.save_to_path(path).unwrap();
+    /// ```
+    pub fn save_to_path(&self, path: &Path) -> CoreResult<()> {
+        let format = loader::ConfigFormat::from_path(path);
+        let serialized = format.serialize(self)?;
+        if format != loader::ConfigFormat::Toml {
+            return write_file_atomically(path, &serialized);
+        }
+        let existing = match std::fs::read_to_string(path) {
+            Ok(existing) => Some(existing),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(CoreError::ConfigLoadError { path: path.to_path_buf(), source: err }),
+        };
+        let documents = existing.and_then(|existing| existing.parse::<DocumentMut>().ok()).zip(serialized.parse::<DocumentMut>().ok());
+        let content = match documents {
+            Some((mut document, updated)) => {
+                update_table(document.as_table_mut(), updated.as_table(), &|key| !Self::FIELDS.contains(&key));
+                document.to_string()
+            }
+            None => serialized,
+        };
+        write_file_atomically(path, &content)
+    }
+
+    /// Prüft die Werte der Konfiguration, die sich nicht schon beim Deserialisieren prüfen lassen.
+    ///
+    /// Das `log_level` muss ein Level (z.B. "debug") oder eine Filterangabe mit Zielen im
+    /// Format von `RUST_LOG` (z.B. "info,novade_domain=debug") sein, die `default_locale` eine
+    /// Locale-ID wie "de-DE" oder "sr-Latn-RS". Die `config_version` muss mit
+    /// [`CoreConfig::SUPPORTED_CONFIG_VERSION`] kompatibel sein, also dieselbe Major-Version
+    /// haben. Für Logdateien müssen mindestens eine Datei aufbewahrt werden und die Größe für
+    /// die Rotation positiv sein.
+    ///
+    /// # Fehler
+    /// `CoreError::ConfigValidationError` mit dem Namen des ungültigen Feldes.
+    pub fn validate(&self) -> CoreResult<()> {
+        let level = self.log_level.trim();
+        let is_level = LevelFilter::from_str(level).is_ok();
+        let is_directive = level.contains('=') && EnvFilter::try_new(level).is_ok();
+        if !is_level && !is_directive {
+            return Err(CoreError::ConfigValidationError {
+                field: "log_level".to_string(),
+                message: format!("'{}' ist kein Log-Level wie \"info\" oder \"info,novade_domain=debug\".", self.log_level),
+            });
+        }
+        if !validation::is_valid_locale(self.default_locale.trim()) {
+            return Err(CoreError::ConfigValidationError {
+                field: "default_locale".to_string(),
+                message: format!("'{}' ist keine Locale-ID wie \"de-DE\" oder \"sr-Latn-RS\".", self.default_locale),
+            });
+        }
+        if !self.config_version.is_compatible_with(&Self::SUPPORTED_CONFIG_VERSION) {
+            return Err(CoreError::ConfigValidationError {
+                field: "config_version".to_string(),
+                message: format!(
+                    "Version {} der Konfiguration wird nicht unterstützt; erwartet wird eine zu {} kompatible Version.",
+                    self.config_version,
+                    Self::SUPPORTED_CONFIG_VERSION
+                ),
+            });
+        }
+        if self.log_file.max_files == 0 {
+            return Err(CoreError::ConfigValidationError {
+                field: "log_file.max_files".to_string(),
+                message: "Es muss mindestens eine Logdatei aufbewahrt werden.".to_string(),
+            });
+        }
+        if self.log_file.max_bytes == 0 {
+            return Err(CoreError::ConfigValidationError {
+                field: "log_file.max_bytes".to_string(),
+                message: "Die Größe für die Rotation der Logdateien muss positiv sein.".to_string(),
+            });
+        }
+        Ok(())
     }
 
     /// Erstellt eine Beispiel-Konfiguration mit Standardwerten.
@@ -98,13 +365,52 @@ impl CoreConfig {
     /// wenn keine Konfigurationsdatei gefunden wird und Standardverhalten gewünscht ist.
     ///
     /// # Rückgabe
-    /// Eine `CoreConfig` Instanz mit vordefinierten Werten (z.B. log_level "info").
+    /// Eine `CoreConfig` Instanz mit vordefinierten Werten (z.B. log_level "info"), identisch
+    /// mit [`CoreConfig::default()`].
     pub fn example() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for CoreConfig {
+    /// Die Standardwerte, die für in der Konfigurationsdatei fehlende Felder gelten.
+    fn default() -> Self {
         Self {
             log_level: "info".to_string(),
             default_locale: "en-US".to_string(),
-            config_version: Version::new(1, 0, 0),
+            config_version: Self::SUPPORTED_CONFIG_VERSION,
             custom_theme_path: None,
+            log_output: LogOutput::Stderr,
+            log_format: LogFormat::Text,
+            log_file: LogFileConfig::default(),
+        }
+    }
+}
+
+/// Übernimmt die Werte aus `updated` in `table`, ohne Kommentare und Formatierung von `table`
+/// zu verändern. Schlüssel, die in `updated` fehlen, werden entfernt, sofern `keep` sie nicht
+/// behalten möchte.
+fn update_table(table: &mut dyn TableLike, updated: &dyn TableLike, keep: &dyn Fn(&str) -> bool) {
+    let removed: Vec<String> =
+        table.iter().map(|(key, _)| key.to_string()).filter(|key| !updated.contains_key(key) && !keep(key)).collect();
+    for key in removed {
+        table.remove(&key);
+    }
+    for (key, item) in updated.iter() {
+        let Some(existing) = table.get_mut(key) else {
+            table.insert(key, item.clone());
+            continue;
+        };
+        match (existing.as_table_like_mut(), item.as_table_like()) {
+            (Some(existing), Some(item)) => update_table(existing, item, &|_| false),
+            _ => match (existing.as_value_mut(), item.as_value()) {
+                (Some(value), Some(new_value)) => {
+                    let decor = value.decor().clone();
+                    *value = new_value.clone();
+                    *value.decor_mut() = decor;
+                }
+                _ => *existing = item.clone(),
+            },
         }
     }
 }
@@ -134,6 +440,19 @@ mod tests {
         assert_eq!(config.custom_theme_path, Some(PathBuf::from("/usr/share/themes/MyTheme")));
     }
 
+    #[test]
+    fn test_load_partial_core_config() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(b"log_level = \"debug\"\n").unwrap();
+
+        let config = CoreConfig::load_from_path(temp_file.path()).unwrap();
+        assert_eq!(config, CoreConfig { log_level: "debug".to_string(), ..CoreConfig::default() });
+        assert_eq!(config.log_output, LogOutput::Stderr);
+
+        temp_file.write_all(b"log_output = \"both\"\n").unwrap();
+        assert_eq!(CoreConfig::load_from_path(temp_file.path()).unwrap().log_output, LogOutput::Both);
+    }
+
     #[test]
     fn test_core_config_example() {
         let example_config = CoreConfig::example();
@@ -142,4 +461,70 @@ mod tests {
         assert_eq!(example_config.config_version, Version::new(1,0,0));
         assert!(example_config.custom_theme_path.is_none());
     }
+
+    #[test]
+    fn test_save_to_path_preserves_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("core.toml");
+        let mut config = CoreConfig::example();
+        config.custom_theme_path = Some(PathBuf::from("/usr/share/themes/Nova"));
+        config.save_to_path(&path).unwrap();
+        assert_eq!(CoreConfig::load_from_path(&path).unwrap(), config);
+
+        let commented = "# Von Hand gepflegt\nlog_level = \"info\" # Standard\ndefault_locale = \"de-DE\"\nexperimentell = true\nconfig_version = { major = 1, minor = 0, patch = 0 }\ncustom_theme_path = \"/alt\"\n";
+        std::fs::write(&path, commented).unwrap();
+        let updated = CoreConfig { log_level: "debug".to_string(), config_version: Version::new(1, 1, 0), ..CoreConfig::example() };
+        updated.save_to_path(&path).unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            saved,
+            "# Von Hand gepflegt\nlog_level = \"debug\" # Standard\ndefault_locale = \"en-US\"\nexperimentell = true\nconfig_version = { major = 1, minor = 1, patch = 0 }\nlog_output = \"stderr\"\nlog_format = \"text\"\n\n\
+             [log_file]\nenabled = false\nrotation = \"daily\"\nmax_bytes = 10485760\nmax_files = 7\n"
+        );
+        assert_eq!(CoreConfig::load_from_path(&path).unwrap(), updated);
+    }
+
+    #[test]
+    fn test_validate_log_level() {
+        let config = |log_level: &str| CoreConfig { log_level: log_level.to_string(), ..CoreConfig::example() };
+        assert!(config("debug").validate().is_ok());
+        assert!(config("info,novade_domain=trace").validate().is_ok());
+        assert!(matches!(config("gesprächig").validate(), Err(CoreError::ConfigValidationError { field, .. }) if field == "log_level"));
+
+        let locale = CoreConfig { default_locale: "deutsch".to_string(), ..CoreConfig::example() };
+        assert!(matches!(locale.validate(), Err(CoreError::ConfigValidationError { field, .. }) if field == "default_locale"));
+
+        let log_file = CoreConfig { log_file: LogFileConfig { max_files: 0, ..LogFileConfig::default() }, ..CoreConfig::example() };
+        assert!(matches!(log_file.validate(), Err(CoreError::ConfigValidationError { field, .. }) if field == "log_file.max_files"));
+    }
+
+    #[test]
+    fn test_incompatible_config_version_is_rejected() {
+        let version = |version: Version| CoreConfig { config_version: version, ..CoreConfig::example() };
+        assert!(version(Version::new(1, 4, 2)).validate().is_ok());
+        assert!(matches!(version(Version::new(2, 0, 0)).validate(), Err(CoreError::ConfigValidationError { field, .. }) if field == "config_version"));
+        assert!(version(Version::new(0, 9, 0)).validate().is_err());
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "config_version = {{ major = 2, minor = 0, patch = 0 }}").unwrap();
+        let result = CoreConfig::load_from_path(temp_file.path());
+        assert!(matches!(result, Err(CoreError::ConfigValidationError { field, .. }) if field == "config_version"));
+    }
+
+    #[test]
+    fn test_load_from_path_with_strictness() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let content = r#"
+            log_level = "debug"
+            log_lvel = "trace"
+            default_locale = "de-DE"
+            config_version = { major = 1, minor = 0, patch = 0 }
+        "#;
+        temp_file.write_all(content.as_bytes()).unwrap();
+
+        assert_eq!(CoreConfig::load_from_path(temp_file.path()).unwrap().log_level, "debug");
+        let strict = CoreConfig::load_from_path_with(temp_file.path(), Strictness::Strict);
+        assert!(matches!(strict, Err(CoreError::ConfigValidationError { field, message })
+            if field == "log_lvel" && message.contains("meinten Sie 'log_level'")));
+    }
 }
@@ -0,0 +1,191 @@
+//! # Prüfung von Konfigurationsdateien (`config::validation`)
+//!
+//! Dieses Untermodul von [`crate::config`] prüft Konfigurationsdateien, bevor ihre Werte
+//! übernommen werden, auf Schlüssel, die die [`CoreConfig`](crate::config::CoreConfig) nicht
+//! kennt. Solche Schlüssel sind meist Tippfehler (z.B. `log_lvel`), die sonst stillschweigend
+//! ignoriert würden; [`UnknownKey`] schlägt dafür den ähnlichsten bekannten Schlüssel vor.
+//!
+//! Wie mit unbekannten Schlüsseln umgegangen wird, bestimmt die [`Strictness`]: Standardmäßig
+//! werden sie als Warnung geloggt, im strikten Modus wird die Datei abgelehnt. Die Werte selbst
+//! prüft [`CoreConfig::validate()`](crate::config::CoreConfig::validate).
+
+use crate::error::{CoreError, CoreResult};
+use std::fmt;
+use toml::{Table, Value};
+
+/// Die bekannten Schlüssel der Kernkonfiguration mit den Schlüsseln verschachtelter Tabellen.
+const SCHEMA: &[(&str, &[&str])] = &[
+    ("log_level", &[]),
+    ("default_locale", &[]),
+    ("config_version", &["major", "minor", "patch", "pre", "build"]),
+    ("custom_theme_path", &[]),
+    ("log_output", &[]),
+    ("log_format", &[]),
+    ("log_file", &["enabled", "directory", "rotation", "max_bytes", "max_files"]),
+];
+
+/// Wie streng Konfigurationsdateien auf unbekannte Schlüssel geprüft werden.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Unbekannte Schlüssel werden als Warnung geloggt und ignoriert.
+    #[default]
+    Lenient,
+    /// Unbekannte Schlüssel sind ein Fehler (wie `#[serde(deny_unknown_fields)]`).
+    Strict,
+}
+
+/// Ein Schlüssel, den die Kernkonfiguration nicht kennt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    /// Der vollständige Schlüssel, verschachtelte Tabellen mit Punkten getrennt
+    /// (z.B. `"config_version.majr"`).
+    pub key: String,
+    /// Der ähnlichste bekannte Schlüssel, falls einer nahe genug liegt.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unbekannter Schlüssel '{}'", self.key)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (meinten Sie '{}'?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sucht in einer geparsten Konfigurationsdatei nach Schlüsseln, die die Kernkonfiguration nicht kennt.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::config::validation::unknown_keys;
+///
+/// let table: toml::Table = "log_lvel = \"debug\"\n[config_version]\nmajor = 1\nminr = 2\n".parse().unwrap();
+/// let unknown = unknown_keys(&table);
+/// assert_eq!(unknown[0].to_string(), "Unbekannter Schlüssel 'config_version.minr' (meinten Sie 'config_version.minor'?)");
+/// assert_eq!(unknown[1].suggestion.as_deref(), Some("log_level"));
+/// ```
+pub fn unknown_keys(table: &Table) -> Vec<UnknownKey> {
+    let mut unknown = Vec::new();
+    for (key, value) in table {
+        let Some((_, nested)) = SCHEMA.iter().find(|(known, _)| known == key) else {
+            unknown.push(UnknownKey { key: key.clone(), suggestion: suggest(key, SCHEMA.iter().map(|(known, _)| *known)) });
+            continue;
+        };
+        let Value::Table(nested_table) = value else {
+            continue;
+        };
+        for nested_key in nested_table.keys().filter(|nested_key| !nested.contains(&nested_key.as_str())) {
+            unknown.push(UnknownKey {
+                key: format!("{}.{}", key, nested_key),
+                suggestion: suggest(nested_key, nested.iter().copied()).map(|suggestion| format!("{}.{}", key, suggestion)),
+            });
+        }
+    }
+    unknown
+}
+
+/// Meldet die unbekannten Schlüssel in `table` je nach `strictness` als Warnung oder Fehler.
+///
+/// # Parameter
+/// * `table`: Die geparste Konfiguration.
+/// * `origin`: Woher die Konfiguration stammt (z.B. der Pfad der Datei), für die Meldungen.
+/// * `strictness`: Ob unbekannte Schlüssel ein Fehler sind.
+///
+/// # Fehler
+/// `CoreError::ConfigValidationError` für den ersten unbekannten Schlüssel, wenn `strictness`
+/// [`Strictness::Strict`] ist. Die Meldung nennt alle unbekannten Schlüssel.
+pub(crate) fn check_unknown_keys(table: &Table, origin: &str, strictness: Strictness) -> CoreResult<()> {
+    let unknown = unknown_keys(table);
+    let Some(first) = unknown.first() else {
+        return Ok(());
+    };
+    if strictness == Strictness::Strict {
+        let descriptions: Vec<String> = unknown.iter().map(ToString::to_string).collect();
+        return Err(CoreError::ConfigValidationError {
+            field: first.key.clone(),
+            message: format!("{}: {}.", origin, descriptions.join("; ")),
+        });
+    }
+    for key in &unknown {
+        tracing::warn!(origin, key = %key.key, suggestion = ?key.suggestion, "{}; der Wert wird ignoriert.", key);
+    }
+    Ok(())
+}
+
+/// Prüft, ob `locale` eine Locale-ID wie "de-DE" oder "sr-Latn-RS" ist: eine Sprache
+/// (2–3 Buchstaben), optional eine Schrift (4 Buchstaben) und optional eine Region
+/// (2 Buchstaben oder 3 Ziffern), getrennt durch `-` oder `_`.
+pub(crate) fn is_valid_locale(locale: &str) -> bool {
+    let is_alpha = |part: &str, lengths: &[usize]| lengths.contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphabetic());
+    let mut parts = locale.split(['-', '_']);
+    if !parts.next().is_some_and(|language| is_alpha(language, &[2, 3])) {
+        return false;
+    }
+    let mut next = parts.next();
+    if next.is_some_and(|script| is_alpha(script, &[4])) {
+        next = parts.next();
+    }
+    let region_ok = next.is_none_or(|region| {
+        is_alpha(region, &[2]) || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit()))
+    });
+    region_ok && parts.next().is_none()
+}
+
+/// Der bekannte Schlüssel, der `key` am ähnlichsten ist, sofern er höchstens ein Drittel
+/// seiner Zeichen (mindestens eines) abweicht.
+fn suggest<'a>(key: &str, known: impl Iterator<Item = &'a str>) -> Option<String> {
+    known
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.chars().count() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Die Levenshtein-Distanz zwischen `a` und `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_keys_are_reported_by_strictness() {
+        let table: Table = "log_level = \"info\"\nthema = \"dunkel\"\ndefault_locle = \"de-DE\"\n".parse().unwrap();
+        let unknown = unknown_keys(&table);
+        assert_eq!(
+            unknown,
+            vec![
+                UnknownKey { key: "default_locle".to_string(), suggestion: Some("default_locale".to_string()) },
+                UnknownKey { key: "thema".to_string(), suggestion: None },
+            ]
+        );
+
+        assert!(check_unknown_keys(&table, "core.toml", Strictness::Lenient).is_ok());
+        let strict = check_unknown_keys(&table, "core.toml", Strictness::Strict);
+        assert!(matches!(strict, Err(CoreError::ConfigValidationError { field, message })
+            if field == "default_locle" && message.contains("meinten Sie 'default_locale'") && message.contains("'thema'")));
+    }
+
+    #[test]
+    fn test_is_valid_locale() {
+        for locale in ["de", "de-DE", "de_AT", "sr-Latn-RS", "es-419"] {
+            assert!(is_valid_locale(locale), "{}", locale);
+        }
+        for locale in ["", "deutsch", "de-DEU", "de-DE-x", "d3-DE"] {
+            assert!(!is_valid_locale(locale), "{}", locale);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! # Neuladen der Konfiguration zur Laufzeit (`config::watcher`)
+//!
+//! Dieses Untermodul von [`crate::config`] überwacht eine Konfigurationsdatei (z.B. `core.toml`)
+//! und lädt sie bei jeder Änderung neu, damit z.B. ein geändertes Log-Level oder ein neuer
+//! Theme-Pfad ohne Neustart wirksam werden.
+//!
+//! ## Hauptkomponenten:
+//!
+//! - [`ConfigWatcher`]: Überwacht eine Datei, parst und validiert sie nach jeder Änderung neu
+//!   und meldet gültige, tatsächlich geänderte Konfigurationen als [`ConfigChanged`].
+//! - [`WatchedConfig`]: Das Trait für Konfigurationstypen, die überwacht werden können
+//!   (implementiert von [`CoreConfig`] und offen für künftige Konfigurationsdateien).
+//!
+//! ## Verhalten bei Fehlern:
+//!
+//! Überwacht wird das Verzeichnis der Datei, da viele Editoren eine Datei beim Speichern durch
+//! eine neue ersetzen. Ist die geänderte Datei vorübergehend nicht lesbar, kein gültiges TOML
+//! oder besteht sie die Validierung nicht, wird eine Warnung geloggt und die bisherige
+//! Konfiguration bleibt gültig; es wird kein Ereignis gemeldet. Leere Dateien gelten als
+//! unvollständig geschrieben und werden ebenfalls übergangen.
+//!
+//! ## Beispiel:
+//!
+//! ```rust,no_run
+//! use novade_core::config::{ConfigWatcher, CoreConfig};
+//! use novade_core::logging::setup::reload_log_level;
+//! use std::path::Path;
+//!
+//! # fn main() -> novade_core::CoreResult<()> {
+//! let watcher: ConfigWatcher<CoreConfig> = ConfigWatcher::watch(Path::new("/etc/novade/core.toml"))?;
+//! for change in watcher.changes() {
+//!     if change.log_level_changed() {
+//!         reload_log_level(&change.current.log_level)?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::{loader, CoreConfig};
+use crate::error::{CoreError, CoreResult};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+
+/// Ein Konfigurationstyp, der von einem [`ConfigWatcher`] überwacht werden kann.
+pub trait WatchedConfig: DeserializeOwned + Clone + PartialEq + Send + 'static {
+    /// Prüft eine neu geladene Konfiguration, bevor sie übernommen wird.
+    ///
+    /// Die Standardimplementierung akzeptiert jede Konfiguration, die sich deserialisieren lässt.
+    fn validate(&self) -> CoreResult<()> {
+        Ok(())
+    }
+}
+
+impl WatchedConfig for CoreConfig {
+    fn validate(&self) -> CoreResult<()> {
+        CoreConfig::validate(self)
+    }
+}
+
+/// Meldet eine geänderte Konfigurationsdatei mit dem vorherigen und dem neuen Stand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChanged<T = CoreConfig> {
+    /// Der Pfad der geänderten Datei.
+    pub path: PathBuf,
+    /// Die bis zur Änderung gültige Konfiguration.
+    pub previous: T,
+    /// Die neu geladene und validierte Konfiguration.
+    pub current: T,
+}
+
+impl ConfigChanged<CoreConfig> {
+    /// Ob sich das Log-Level geändert hat (siehe [`reload_log_level`](crate::logging::setup::reload_log_level)).
+    pub fn log_level_changed(&self) -> bool {
+        self.previous.log_level != self.current.log_level
+    }
+
+    /// Ob sich der Pfad des benutzerdefinierten Theme-Verzeichnisses geändert hat.
+    pub fn theme_path_changed(&self) -> bool {
+        self.previous.custom_theme_path != self.current.custom_theme_path
+    }
+}
+
+/// Überwacht eine Konfigurationsdatei und meldet Änderungen als [`ConfigChanged`].
+///
+/// Die Überwachung endet, sobald der `ConfigWatcher` verworfen wird.
+pub struct ConfigWatcher<T: WatchedConfig = CoreConfig> {
+    // Muss am Leben bleiben, solange überwacht werden soll.
+    _watcher: RecommendedWatcher,
+    current: Arc<Mutex<T>>,
+    changes: Receiver<ConfigChanged<T>>,
+}
+
+impl<T: WatchedConfig> ConfigWatcher<T> {
+    /// Lädt die Konfiguration am Pfad `path` und beginnt, die Datei zu überwachen.
+    ///
+    /// # Parameter
+    /// * `path`: Der Pfad zur Konfigurationsdatei (z.B. `core.toml`).
+    ///
+    /// # Rückgabe
+    /// Der `ConfigWatcher`, oder ein `CoreError`, wenn die Datei anfangs nicht geladen oder
+    /// validiert werden kann bzw. die Überwachung nicht eingerichtet werden kann
+    /// (`CoreError::InitializationError`).
+    pub fn watch(path: &Path) -> CoreResult<Self> {
+        let initial: T = loader::load_config_from_file(path)?;
+        initial.validate()?;
+        let current = Arc::new(Mutex::new(initial));
+        let (sender, changes) = mpsc::channel();
+
+        let watched_path = path.to_path_buf();
+        let file_name = path.file_name().map(|name| name.to_os_string());
+        let state = current.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            // Nur Ereignisse, die die überwachte Datei betreffen; Zugriffe ändern nichts.
+            if event.kind.is_access() || !event.paths.iter().any(|path| path.file_name() == file_name.as_deref()) {
+                return;
+            }
+            let content = match loader::read_config_file(&watched_path) {
+                // Beim Speichern wird die Datei oft erst geleert und dann geschrieben; eine leere
+                // Datei wäre sonst eine gültige Konfiguration aus lauter Standardwerten.
+                Ok(content) if content.trim().is_empty() => return,
+                content => content,
+            };
+            let reloaded = content
+                .and_then(|content| loader::ConfigFormat::from_path(&watched_path).parse::<T>(&content))
+                .and_then(|config| config.validate().map(|_| config));
+            let config = match reloaded {
+                Ok(config) => config,
+                Err(error) => {
+                    tracing::warn!(path = %watched_path.display(), %error, "Geänderte Konfiguration wird nicht übernommen.");
+                    return;
+                }
+            };
+            let mut current = state.lock().unwrap();
+            // Editoren lösen beim Speichern oft mehrere Ereignisse aus; unveränderte Inhalte werden nicht gemeldet.
+            if *current == config {
+                return;
+            }
+            let previous = std::mem::replace(&mut *current, config.clone());
+            tracing::info!(path = %watched_path.display(), "Konfiguration neu geladen.");
+            let _ = sender.send(ConfigChanged { path: watched_path.clone(), previous, current: config });
+        })
+        .map_err(|error| watch_error(path, error))?;
+
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        watcher.watch(directory, RecursiveMode::NonRecursive).map_err(|error| watch_error(path, error))?;
+        Ok(Self { _watcher: watcher, current, changes })
+    }
+
+    /// Die aktuell gültige Konfiguration.
+    pub fn current(&self) -> T {
+        self.current.lock().unwrap().clone()
+    }
+
+    /// Die gemeldeten Änderungen. Das Iterieren über den `Receiver` blockiert bis zur
+    /// nächsten Änderung; `try_recv` bzw. `recv_timeout` blockieren nicht bzw. begrenzt.
+    pub fn changes(&self) -> &Receiver<ConfigChanged<T>> {
+        &self.changes
+    }
+}
+
+fn watch_error(path: &Path, error: notify::Error) -> CoreError {
+    CoreError::InitializationError {
+        component: "config_watcher".to_string(),
+        message: format!("Konfigurationsdatei '{}' kann nicht überwacht werden: {}", path.display(), error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    const CONFIG: &str = r#"
+        log_level = "info"
+        default_locale = "de-DE"
+        config_version = { major = 1, minor = 0, patch = 0 }
+    "#;
+
+    #[test]
+    fn test_config_watcher_reports_valid_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("core.toml");
+        fs::write(&path, CONFIG).unwrap();
+        let watcher: ConfigWatcher = ConfigWatcher::watch(&path).unwrap();
+        assert_eq!(watcher.current().log_level, "info");
+
+        // Ungültige Inhalte werden nicht übernommen.
+        fs::write(&path, CONFIG.replace("\"info\"", "\"gesprächig\"")).unwrap();
+        fs::write(dir.path().join("andere.toml"), CONFIG).unwrap();
+        assert!(watcher.changes().recv_timeout(Duration::from_millis(500)).is_err());
+        assert_eq!(watcher.current().log_level, "info");
+
+        fs::write(&path, CONFIG.replace("\"info\"", "\"debug\"")).unwrap();
+        let change = watcher.changes().recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(change.log_level_changed() && !change.theme_path_changed());
+        assert_eq!((change.previous.log_level.as_str(), change.current.log_level.as_str()), ("info", "debug"));
+        assert_eq!(watcher.current(), change.current);
+    }
+}
@@ -22,6 +22,31 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ## Fehlercodes und Kontext
+//!
+//! Jeder Fehler hat mit [`CoreError::code()`] einen stabilen, maschinenlesbaren Code
+//! (z.B. `"core.config.load"`), nach dem Aufrufer und Log-Auswertungen unterscheiden können,
+//! ohne die (übersetzbare) Meldung zu parsen. Mit [`ResultExt::context()`] lässt sich einem
+//! Fehler die Operation voranstellen, in der er auftrat; der ursprüngliche Fehler bleibt als
+//! [`source()`](std::error::Error::source) erhalten und bestimmt weiterhin den Code;
+//! [`CoreError::root_cause()`] liefert ihn ohne die Kontext-Ebenen. Die Meldung eines Fehlers
+//! mit Quelle enthält die Meldung der Quelle nicht; wer die ganze Kette ausgeben will, folgt
+//! `source()`.
+//!
+//! ```rust
+//! use novade_core::error::{CoreError, CoreResult, ResultExt};
+//!
+//! fn lade_thema() -> CoreResult<()> {
+//!     Err(CoreError::InvalidPathError { path: "~/themes/x".to_string(), message: "fehlt".to_string() })
+//! }
+//!
+//! let err = lade_thema().context("Thema konnte nicht angewendet werden").unwrap_err();
+//! assert_eq!(err.to_string(), "Thema konnte nicht angewendet werden");
+//! assert!(std::error::Error::source(&err).unwrap().to_string().starts_with("Ungültiger oder nicht auflösbarer Pfad"));
+//! assert_eq!(err.code(), "core.invalid_path");
+//! assert!(matches!(err.root_cause(), CoreError::InvalidPathError { .. }));
+//! ```
 
 use thiserror::Error;
 use std::path::PathBuf;
@@ -36,29 +61,34 @@ pub type CoreResult<T> = Result<T, CoreError>;
 ///
 /// Jede Variante repräsentiert eine spezifische Fehlerbedingung. Die `#[error(...)]` Attribute
 /// von `thiserror` werden verwendet, um aussagekräftige Fehlermeldungen zu generieren.
-#[derive(Debug, Error, Clone)]
+#[derive(Debug, Error)]
 pub enum CoreError {
     /// Fehler beim Laden einer Konfigurationsdatei vom Dateisystem.
-    #[error("Konfiguration konnte nicht von Pfad '{path}' geladen werden: {error_message}")]
+    #[error("Konfiguration konnte nicht von Pfad '{path}' geladen werden")]
     ConfigLoadError {
         path: PathBuf,
-        // std::io::Error ist nicht Clone, daher speichern wir die Nachricht.
-        // Das #[source] Attribut kann hier nicht direkt verwendet werden.
-        error_message: String, 
+        #[source]
+        source: std::io::Error,
     },
 
     /// Fehler beim Parsen des Inhalts einer Konfigurationsdatei (z.B. ungültiges TOML).
     #[error("Konfigurationsdatei-Inhalt konnte nicht geparst werden (Format: {format}): {message}")]
     ConfigParseError { format: String, message: String },
 
+    /// Eine Konfiguration ließ sich parsen, enthält aber einen ungültigen Wert.
+    #[error("Ungültiger Wert für Konfigurationsfeld '{field}': {message}")]
+    ConfigValidationError { field: String, message: String },
+
     /// Fehler während der Initialisierung der Logging-Infrastruktur.
     #[error("Fehler bei der Initialisierung des Loggings: {0}")]
     LoggingInitError(String),
 
     /// Ein allgemeiner Ein-/Ausgabe-Fehler ist aufgetreten.
-    /// Die ursprüngliche `std::io::Error` wird in einen String konvertiert, da `std::io::Error` nicht `Clone` ist.
+    /// Der ursprüngliche `std::io::Error` bleibt erhalten, damit Aufrufer z.B. nach
+    /// [`std::io::ErrorKind::NotFound`] unterscheiden können; [`CoreError::io()`] ergänzt
+    /// dabei die betroffene Datei oder Operation.
     #[error("Ein E/A-Fehler ist aufgetreten: {0}")]
-    IoError(String), // Kein #[from] mehr, da wir manuell konvertieren müssen.
+    IoError(#[from] std::io::Error),
 
     /// Fehler bei der Serialisierung von Daten in ein bestimmtes Format (z.B. JSON, TOML).
     #[error("Fehler bei der Serialisierung von Daten (Format: {format}): {message}")]
@@ -79,4 +109,167 @@ pub enum CoreError {
     /// Ein unspezifischer oder nicht anderweitig kategorisierter Fehler innerhalb von `novade-core`.
     #[error("Ein unbekannter Kernfehler ist aufgetreten: {0}")]
     UnknownError(String),
+
+    /// Ein Fehler mit der Beschreibung der Operation, in der er auftrat (siehe [`ResultExt`]).
+    #[error("{context}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<CoreError>,
+    },
+}
+
+/// Klont auch die enthaltenen `std::io::Error`, die selbst nicht `Clone` sind: Die Kopie hat
+/// dieselbe Art ([`std::io::ErrorKind`]) und Meldung, aber weder deren eigene Quelle noch den
+/// Fehlercode des Betriebssystems.
+impl Clone for CoreError {
+    fn clone(&self) -> Self {
+        match self {
+            CoreError::ConfigLoadError { path, source } => {
+                CoreError::ConfigLoadError { path: path.clone(), source: clone_io_error(source) }
+            }
+            CoreError::ConfigParseError { format, message } => {
+                CoreError::ConfigParseError { format: format.clone(), message: message.clone() }
+            }
+            CoreError::ConfigValidationError { field, message } => {
+                CoreError::ConfigValidationError { field: field.clone(), message: message.clone() }
+            }
+            CoreError::LoggingInitError(message) => CoreError::LoggingInitError(message.clone()),
+            CoreError::IoError(error) => CoreError::IoError(clone_io_error(error)),
+            CoreError::SerializationError { format, message } => {
+                CoreError::SerializationError { format: format.clone(), message: message.clone() }
+            }
+            CoreError::DeserializationError { format, message } => {
+                CoreError::DeserializationError { format: format.clone(), message: message.clone() }
+            }
+            CoreError::InvalidPathError { path, message } => {
+                CoreError::InvalidPathError { path: path.clone(), message: message.clone() }
+            }
+            CoreError::InitializationError { component, message } => {
+                CoreError::InitializationError { component: component.clone(), message: message.clone() }
+            }
+            CoreError::UnknownError(message) => CoreError::UnknownError(message.clone()),
+            CoreError::Context { context, source } => CoreError::Context { context: context.clone(), source: source.clone() },
+        }
+    }
+}
+
+/// Eine Kopie von `error` mit derselben Art und Meldung.
+fn clone_io_error(error: &std::io::Error) -> std::io::Error {
+    std::io::Error::new(error.kind(), error.to_string())
+}
+
+impl CoreError {
+    /// Ein [`CoreError::IoError`], dessen Meldung mit `context` beginnt (z.B. der betroffenen
+    /// Datei). Die Art des ursprünglichen Fehlers bleibt erhalten.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_core::error::CoreError;
+    /// use std::io;
+    ///
+    /// let err = CoreError::io("'/etc/novade/core.toml' konnte nicht gelesen werden", io::Error::from(io::ErrorKind::NotFound));
+    /// assert!(matches!(&err, CoreError::IoError(io_err) if io_err.kind() == io::ErrorKind::NotFound));
+    /// assert!(err.to_string().contains("'/etc/novade/core.toml' konnte nicht gelesen werden: "));
+    /// ```
+    pub fn io(context: impl std::fmt::Display, error: std::io::Error) -> CoreError {
+        CoreError::IoError(std::io::Error::new(error.kind(), format!("{}: {}", context, error)))
+    }
+
+    /// Ein stabiler, maschinenlesbarer Code für die Art des Fehlers, z.B. `"core.io"`.
+    ///
+    /// Die Codes ändern sich nicht, wenn Meldungen umformuliert oder übersetzt werden. Bei
+    /// [`CoreError::Context`] ist es der Code des ursprünglichen Fehlers.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CoreError::ConfigLoadError { .. } => "core.config.load",
+            CoreError::ConfigParseError { .. } => "core.config.parse",
+            CoreError::ConfigValidationError { .. } => "core.config.validation",
+            CoreError::LoggingInitError(_) => "core.logging.init",
+            CoreError::IoError(_) => "core.io",
+            CoreError::SerializationError { .. } => "core.serialization",
+            CoreError::DeserializationError { .. } => "core.deserialization",
+            CoreError::InvalidPathError { .. } => "core.invalid_path",
+            CoreError::InitializationError { .. } => "core.initialization",
+            CoreError::UnknownError(_) => "core.unknown",
+            CoreError::Context { .. } => self.root_cause().code(),
+        }
+    }
+
+    /// Der ursprüngliche Fehler ohne die mit [`ResultExt::context()`] hinzugefügten Ebenen.
+    pub fn root_cause(&self) -> &CoreError {
+        let mut error = self;
+        while let CoreError::Context { source, .. } = error {
+            error = source;
+        }
+        error
+    }
+
+    /// Stellt dem Fehler die Beschreibung einer Operation voran.
+    pub fn context(self, context: impl Into<String>) -> CoreError {
+        CoreError::Context { context: context.into(), source: Box::new(self) }
+    }
+}
+
+/// Erweitert [`CoreResult`] um Methoden, die einem Fehler Kontext hinzufügen.
+pub trait ResultExt<T> {
+    /// Stellt einem Fehler die Beschreibung der Operation voran, z.B.
+    /// `"Hintergrundbild konnte nicht geladen werden"`.
+    ///
+    /// # Fehler
+    /// Ein `CoreError::Context` mit dem ursprünglichen Fehler als Quelle.
+    fn context(self, context: impl Into<String>) -> CoreResult<T>;
+
+    /// Wie [`ResultExt::context()`], erzeugt die Beschreibung aber nur im Fehlerfall.
+    ///
+    /// # Fehler
+    /// Ein `CoreError::Context` mit dem ursprünglichen Fehler als Quelle.
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> CoreResult<T>;
+}
+
+impl<T> ResultExt<T> for CoreResult<T> {
+    fn context(self, context: impl Into<String>) -> CoreResult<T> {
+        self.map_err(|error| error.context(context))
+    }
+
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> CoreResult<T> {
+        self.map_err(|error| error.context(context()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_keeps_code_and_source_chain() {
+        let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "keine Berechtigung");
+        let result: CoreResult<()> = Err(CoreError::ConfigLoadError { path: PathBuf::from("/etc/novade/core.toml"), source: io });
+        let err = result.context("Systemkonfiguration").with_context(|| format!("Start von {}", "novade-shell")).unwrap_err();
+
+        assert_eq!(err.code(), "core.config.load");
+        let mut chain = Vec::new();
+        let mut current: Option<&dyn std::error::Error> = Some(&err);
+        while let Some(error) = current {
+            chain.push(error);
+            current = error.source();
+        }
+        // Every message appears exactly once along the chain.
+        let messages: Vec<String> = chain.iter().map(|error| error.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Start von novade-shell",
+                "Systemkonfiguration",
+                "Konfiguration konnte nicht von Pfad '/etc/novade/core.toml' geladen werden",
+                "keine Berechtigung",
+            ]
+        );
+        let io = chain[3].downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io.kind(), std::io::ErrorKind::PermissionDenied);
+
+        let copy = err.clone();
+        assert_eq!(copy.to_string(), err.to_string());
+        assert!(matches!(copy.root_cause(), CoreError::ConfigLoadError { source, .. } if source.kind() == std::io::ErrorKind::PermissionDenied));
+    }
 }
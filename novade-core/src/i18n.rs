@@ -0,0 +1,319 @@
+//! # Übersetzungen in `novade-core`
+//!
+//! Dieses Modul lädt Übersetzungskataloge im [Fluent](https://projectfluent.org)-Format und
+//! stellt das Makro [`t!`](crate::t) bereit, mit dem Fehlermeldungen der Domänenschicht und
+//! Texte der Oberfläche in der Sprache des Benutzers ausgegeben werden.
+//!
+//! ## Hauptkomponenten:
+//!
+//! - [`Catalog`]: Die Meldungen einer Sprache mit ihren Rückfallsprachen, geladen aus
+//!   `*.ftl`-Dateien.
+//! - [`init()`]: Lädt den Katalog für [`CoreConfig::default_locale`] aus den
+//!   [`locale_dirs()`] und macht ihn global verfügbar; [`set_catalog()`] ersetzt ihn, z.B.
+//!   wenn der Benutzer die Sprache wechselt.
+//! - [`t!`](crate::t) und [`translate()`]: Übersetzen einen Schlüssel mit dem globalen Katalog.
+//!
+//! ## Kataloge:
+//!
+//! Die Dateien einer Sprache liegen in einem Unterverzeichnis mit der Locale-ID, z.B.
+//! `~/.local/share/novade/locales/de-DE/shell.ftl`. Gesucht wird in dieser Reihenfolge:
+//! der Sprache selbst (`de-AT`), der Sprache ohne Region (`de`) und zuletzt
+//! [`FALLBACK_LOCALE`]. Fehlt ein Schlüssel überall, wird der Schlüssel selbst ausgegeben,
+//! damit fehlende Übersetzungen auffallen, ohne die Oberfläche zu blockieren.
+//!
+//! Ein Schlüssel mit Punkt (`"fenster.schliessen"`) bezeichnet das Attribut `schliessen` der
+//! Meldung `fenster`. Die Unicode-Isolationszeichen, die Fluent standardmäßig um eingesetzte
+//! Werte legt, sind abgeschaltet, da sie in Logs und einfachen Textfeldern stören.
+//!
+//! ## Verwendung:
+//!
+//! ```rust
+//! use novade_core::i18n::{set_catalog, Catalog};
+//! use novade_core::t;
+//! use std::fs;
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! fs::create_dir(dir.path().join("de")).unwrap();
+//! fs::write(dir.path().join("de/shell.ftl"), "fenster-geoeffnet = { $titel } wurde geöffnet.\n").unwrap();
+//!
+//! set_catalog(Catalog::load("de-DE", &[dir.path().to_path_buf()]).unwrap());
+//! assert_eq!(t!("fenster-geoeffnet", titel = "Terminal"), "Terminal wurde geöffnet.");
+//! assert_eq!(t!("unbekannt"), "unbekannt");
+//! ```
+
+use crate::config::CoreConfig;
+use crate::error::{CoreError, CoreResult};
+use crate::utils::{get_app_data_dir, read_file_to_string};
+use fluent_bundle::FluentResource;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+
+/// Die Sprache, auf die zuletzt zurückgegriffen wird.
+pub const FALLBACK_LOCALE: &str = "en-US";
+
+/// Der Name des Unterverzeichnisses der Datenverzeichnisse, in dem die Kataloge liegen.
+pub const LOCALES_DIR_NAME: &str = "locales";
+
+/// Das systemweite Datenverzeichnis, das mit NovaDE installierte Kataloge enthält.
+const SYSTEM_DATA_DIR: &str = "/usr/share/novade";
+
+/// Die Dateiendung von Fluent-Katalogen.
+const CATALOG_EXTENSION: &str = "ftl";
+
+type Bundle = fluent_bundle::concurrent::FluentBundle<FluentResource>;
+
+/// Die Meldungen einer Sprache und ihrer Rückfallsprachen.
+pub struct Catalog {
+    locale: String,
+    /// Je Sprache der Rückfallkette ein Bundle, in der Reihenfolge der Suche. Sprachen ohne
+    /// Katalogdateien fehlen.
+    bundles: Vec<Bundle>,
+}
+
+impl Catalog {
+    /// Ein Katalog ohne Meldungen; jeder Schlüssel wird unverändert ausgegeben.
+    pub fn empty(locale: &str) -> Self {
+        Self { locale: locale.to_string(), bundles: Vec::new() }
+    }
+
+    /// Lädt die Kataloge für `locale` und ihre Rückfallsprachen aus `dirs`.
+    ///
+    /// Verzeichnisse weiter vorne in `dirs` haben Vorrang: Definieren zwei Verzeichnisse
+    /// dieselbe Meldung, gilt die aus dem früheren. Fehlende Verzeichnisse werden übersprungen;
+    /// Syntaxfehler in einer Datei werden als Warnung geloggt und der lesbare Rest verwendet.
+    ///
+    /// # Parameter
+    /// * `locale`: Die Sprache, z.B. "de-DE" oder "de_AT".
+    /// * `dirs`: Die Verzeichnisse mit einem Unterverzeichnis je Sprache (siehe [`locale_dirs()`]).
+    ///
+    /// # Fehler
+    /// - `CoreError::ConfigValidationError`, wenn `locale` keine gültige Locale-ID ist.
+    /// - Die Fehler von [`read_file_to_string()`], wenn eine Katalogdatei nicht gelesen werden kann.
+    pub fn load(locale: &str, dirs: &[PathBuf]) -> CoreResult<Self> {
+        let requested = parse_locale(locale)?;
+        let mut bundles = Vec::new();
+        for candidate in fallback_chain(&requested) {
+            let mut bundle = Bundle::new_concurrent(vec![candidate.clone()]);
+            bundle.set_use_isolating(false);
+            let mut has_resources = false;
+            // Spätere Ressourcen überschreiben frühere, daher die Verzeichnisse rückwärts.
+            for dir in dirs.iter().rev() {
+                for path in catalog_files(&dir.join(candidate.to_string()))? {
+                    bundle.add_resource_overriding(parse_resource(&path)?);
+                    has_resources = true;
+                }
+            }
+            if has_resources {
+                bundles.push(bundle);
+            }
+        }
+        Ok(Self { locale: requested.to_string(), bundles })
+    }
+
+    /// Die Sprache des Katalogs in kanonischer Schreibweise (z.B. "de-AT").
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Ob der Katalog eine Übersetzung für `key` enthält.
+    pub fn contains(&self, key: &str) -> bool {
+        let (id, attribute) = split_key(key);
+        self.bundles.iter().any(|bundle| {
+            bundle.get_message(id).is_some_and(|message| match attribute {
+                Some(attribute) => message.get_attribute(attribute).is_some(),
+                None => message.value().is_some(),
+            })
+        })
+    }
+
+    /// Übersetzt `key` mit den Werten `args` für die Platzhalter.
+    ///
+    /// Fehlt der Schlüssel in allen Sprachen, wird er selbst zurückgegeben. Fehlende Werte
+    /// für Platzhalter werden als `{$name}` ausgegeben und auf dem Level `DEBUG` geloggt.
+    pub fn translate(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        let (id, attribute) = split_key(key);
+        for bundle in &self.bundles {
+            let Some(message) = bundle.get_message(id) else {
+                continue;
+            };
+            let pattern = match attribute {
+                Some(attribute) => message.get_attribute(attribute).map(|attribute| attribute.value()),
+                None => message.value(),
+            };
+            let Some(pattern) = pattern else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, args, &mut errors).into_owned();
+            if !errors.is_empty() {
+                tracing::debug!(key, locale = %self.locale, ?errors, "Übersetzung unvollständig formatiert.");
+            }
+            return text;
+        }
+        key.to_string()
+    }
+}
+
+impl std::fmt::Debug for Catalog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let locales: Vec<String> = self.bundles.iter().flat_map(|bundle| bundle.locales.iter().map(ToString::to_string)).collect();
+        f.debug_struct("Catalog").field("locale", &self.locale).field("bundles", &locales).finish()
+    }
+}
+
+/// Die Verzeichnisse, in denen nach Katalogen gesucht wird, mit Vorrang in dieser Reihenfolge:
+/// `$XDG_DATA_HOME/novade/locales` (eigene oder nachinstallierte Übersetzungen) und
+/// `/usr/share/novade/locales`.
+pub fn locale_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = get_app_data_dir("novade").map(|dir| dir.join(LOCALES_DIR_NAME)).into_iter().collect();
+    dirs.push(Path::new(SYSTEM_DATA_DIR).join(LOCALES_DIR_NAME));
+    dirs
+}
+
+/// Lädt den Katalog für [`CoreConfig::default_locale`] aus den [`locale_dirs()`] und setzt ihn
+/// als globalen Katalog für [`t!`](crate::t).
+///
+/// # Fehler
+/// Wie [`Catalog::load()`].
+pub fn init(config: &CoreConfig) -> CoreResult<()> {
+    let catalog = Catalog::load(&config.default_locale, &locale_dirs())?;
+    tracing::debug!(?catalog, "Übersetzungskatalog geladen.");
+    set_catalog(catalog);
+    Ok(())
+}
+
+/// Ersetzt den globalen Katalog, z.B. nach einem Sprachwechsel.
+pub fn set_catalog(catalog: Catalog) {
+    *global().write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(catalog);
+}
+
+/// Der globale Katalog. Vor [`init()`] oder [`set_catalog()`] ist er leer.
+pub fn catalog() -> Arc<Catalog> {
+    global().read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Übersetzt `key` mit dem globalen Katalog (siehe [`Catalog::translate()`]). Meist wird
+/// stattdessen [`t!`](crate::t) verwendet.
+pub fn translate(key: &str, args: Option<&FluentArgs>) -> String {
+    catalog().translate(key, args)
+}
+
+fn global() -> &'static RwLock<Arc<Catalog>> {
+    static CATALOG: OnceLock<RwLock<Arc<Catalog>>> = OnceLock::new();
+    CATALOG.get_or_init(|| RwLock::new(Arc::new(Catalog::empty(FALLBACK_LOCALE))))
+}
+
+/// Übersetzt einen Schlüssel mit dem globalen Katalog, optional mit benannten Werten für die
+/// Platzhalter.
+///
+/// ```rust
+/// use novade_core::t;
+///
+/// // Ohne geladenen Katalog wird der Schlüssel ausgegeben.
+/// assert_eq!(t!("fenster-anzahl", anzahl = 3, ort = "Arbeitsfläche 2"), "fenster-anzahl");
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::translate($key, None)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($key, Some(&args))
+    }};
+}
+
+/// Prüft `locale` und bringt es in die kanonische Schreibweise.
+fn parse_locale(locale: &str) -> CoreResult<LanguageIdentifier> {
+    locale.replace('_', "-").parse().map_err(|e| CoreError::ConfigValidationError {
+        field: "default_locale".to_string(),
+        message: format!("'{}' ist keine gültige Locale-ID: {}", locale, e),
+    })
+}
+
+/// Die Sprachen, in denen nacheinander gesucht wird, ohne Doppelte.
+fn fallback_chain(requested: &LanguageIdentifier) -> Vec<LanguageIdentifier> {
+    let language_only = LanguageIdentifier::from_parts(requested.language, None, None, &[]);
+    let fallback: LanguageIdentifier = FALLBACK_LOCALE.parse().expect("FALLBACK_LOCALE ist gültig");
+    let mut chain: Vec<LanguageIdentifier> = Vec::new();
+    for candidate in [requested.clone(), language_only, fallback] {
+        if !chain.contains(&candidate) {
+            chain.push(candidate);
+        }
+    }
+    chain
+}
+
+/// Die `*.ftl`-Dateien in `dir`, sortiert; leer, wenn es das Verzeichnis nicht gibt.
+fn catalog_files(dir: &Path) -> CoreResult<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(CoreError::io(format!("Katalogverzeichnis '{}' kann nicht gelesen werden", dir.display()), e)),
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == CATALOG_EXTENSION))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Liest eine Katalogdatei; Syntaxfehler werden als Warnung geloggt.
+fn parse_resource(path: &Path) -> CoreResult<FluentResource> {
+    let source = read_file_to_string(path)?;
+    Ok(FluentResource::try_new(source).unwrap_or_else(|(resource, errors)| {
+        tracing::warn!(path = %path.display(), ?errors, "Syntaxfehler im Übersetzungskatalog; fehlerhafte Einträge werden ignoriert.");
+        resource
+    }))
+}
+
+/// Teilt einen Schlüssel in die Meldung und optional ein Attribut.
+fn split_key(key: &str) -> (&str, Option<&str>) {
+    match key.split_once('.') {
+        Some((id, attribute)) => (id, Some(attribute)),
+        None => (key, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_follows_the_fallback_chain() {
+        let system = tempfile::tempdir().unwrap();
+        let user = tempfile::tempdir().unwrap();
+        for (dir, locale, source) in [
+            (system.path(), "en-US", "gruss = Hello, { $name }!\nabmelden = Log out\n"),
+            (system.path(), "de", "gruss = Hallo, { $name }!\nfenster = Fenster\n    .schliessen = Schließen\n"),
+            (user.path(), "de-AT", "gruss = Servus, { $name }!\n"),
+            (user.path(), "de", "fenster = Fenster\n    .schliessen = Zumachen\n"),
+        ] {
+            fs::create_dir_all(dir.join(locale)).unwrap();
+            fs::write(dir.join(locale).join("shell.ftl"), source).unwrap();
+        }
+        let dirs = [user.path().to_path_buf(), system.path().to_path_buf()];
+
+        let catalog = Catalog::load("de_AT", &dirs).unwrap();
+        assert_eq!(catalog.locale(), "de-AT");
+        let mut args = FluentArgs::new();
+        args.set("name", "Anna");
+        assert_eq!(catalog.translate("gruss", Some(&args)), "Servus, Anna!");
+        assert_eq!(catalog.translate("fenster.schliessen", None), "Zumachen");
+        assert_eq!(catalog.translate("abmelden", None), "Log out");
+        assert_eq!(catalog.translate("fehlt", None), "fehlt");
+        assert!(catalog.contains("fenster.schliessen"));
+        assert!(!catalog.contains("fenster.minimieren"));
+
+        let german = Catalog::load("de-DE", &dirs).unwrap();
+        assert_eq!(german.translate("gruss", Some(&args)), "Hallo, Anna!");
+
+        assert!(matches!(Catalog::load("kein gültiges locale", &dirs), Err(CoreError::ConfigValidationError { .. })));
+    }
+}
@@ -7,13 +7,22 @@
 //! ## Hauptverantwortlichkeiten:
 //!
 //! - **Grundlegende Datentypen**: Definition von gemeinsamen Typen wie `NovaId`, `Version`,
-//!   `Timestamp` und `ResourceIdentifier`, die systemweit verwendet werden. Siehe [`types`].
+//!   `Timestamp` und `ResourceIdentifier` sowie geometrische Typen wie `Rect`, die systemweit
+//!   verwendet werden. Siehe [`types`].
 //! - **Fehlerbehandlung**: Ein robustes Fehlermanagement durch das `CoreError` Enum und
 //!   den `CoreResult<T>` Typalias. Siehe [`error`].
 //! - **Konfigurationsmanagement**: Laden und Verwalten von Kernkonfigurationen
 //!   (z.B. aus TOML-Dateien). Siehe [`config`].
 //! - **Logging-Infrastruktur**: Initialisierung und Bereitstellung einer flexiblen Logging-Lösung
 //!   basierend auf `tracing`. Siehe [`logging`].
+//! - **Übersetzungen**: Laden von Fluent-Katalogen für die Standardsprache und Übersetzen von
+//!   Meldungen mit dem Makro `t!`. Siehe [`i18n`].
+//! - **Metriken**: Zähler, Messwerte und Histogramme für Leistungsdaten, erfasst über die
+//!   Makros `counter!`, `gauge!` und `histogram!` und ausgegeben über austauschbare Exporter.
+//!   Siehe [`metrics`].
+//! - **Signale**: Ein typisiertes Publish/Subscribe-Primitiv mit synchronen und asynchronen
+//!   Empfängern sowie schwachen Rückrufverbindungen, auf dem Ereignisbusse und
+//!   Änderungsbenachrichtigungen aufbauen. Siehe [`signal`].
 //! - **Allgemeine Dienstprogramme**: Sammlung von Hilfsfunktionen für Pfadmanipulation,
 //!   Dateizugriff und Ermittlung von Anwendungsverzeichnissen. Siehe [`utils`].
 //!
@@ -61,7 +70,10 @@
 // Module werden öffentlich gemacht
 pub mod config;
 pub mod error;
+pub mod i18n;
 pub mod logging;
+pub mod metrics;
+pub mod signal;
 pub mod types;
 pub mod utils;
 
@@ -72,7 +84,7 @@ pub use config::{CoreConfig, DEFAULT_CORE_CONFIG_FILENAME};
 pub use error::{CoreError, CoreResult};
 pub use logging::setup::initialize_logging; // Spezifischer Pfad zur Initialisierungsfunktion
 pub use logging::{debug, error, info, trace, warn, instrument, span, Level, Span}; // Logging-Makros
-pub use types::{NovaId, ResourceIdentifier, Timestamp, Version};
+pub use types::{MonotonicInstant, NovaId, Point, Rect, ResourceIdentifier, Size, Timestamp, Version};
 // utils-Funktionen werden typischerweise spezifisch aufgerufen, z.B. novade_core::utils::resolve_path,
 // daher werden sie hier nicht alle pauschal re-exportiert, es sei denn, es gibt sehr häufig genutzte.
 
@@ -0,0 +1,196 @@
+//! # Rotierende Logdateien (`logging::file`)
+//!
+//! Dieses Untermodul von [`crate::logging`] stellt den [`RollingFileWriter`] bereit, in den
+//! [`initialize_logging()`](crate::logging::setup::initialize_logging) zusätzlich zu `stderr`
+//! schreibt, wenn [`LogFileConfig::enabled`] gesetzt ist.
+//!
+//! ## Dateien und Rotation:
+//!
+//! Jede Logdatei heißt `novade.<Zeitstempel>.log` (z.B. `novade.2026-10-17T08-30-00.log`);
+//! wird in derselben Sekunde mehrfach rotiert, folgt dem Zeitstempel ein Zähler
+//! (`novade.2026-10-17T08-30-00.1.log`). Eine neue Datei wird
+//! beim Start, mit jedem neuen Tag ([`LogRotation::Daily`]) bzw. beim Überschreiten von
+//! [`LogFileConfig::max_bytes`] ([`LogRotation::Size`]) begonnen. Danach werden die ältesten
+//! Dateien gelöscht, bis höchstens [`LogFileConfig::max_files`] übrig sind.
+
+use crate::config::{LogFileConfig, LogRotation};
+use crate::error::{CoreError, CoreResult};
+use crate::utils::get_app_state_dir;
+use chrono::{Local, NaiveDate};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Der Anfang der Namen aller Logdateien.
+pub const LOG_FILE_PREFIX: &str = "novade";
+
+const LOG_FILE_EXTENSION: &str = "log";
+
+/// Schreibt in eine Folge von Logdateien und beginnt nach den Regeln einer [`LogFileConfig`]
+/// neue Dateien.
+#[derive(Debug)]
+pub struct RollingFileWriter {
+    directory: PathBuf,
+    rotation: LogRotation,
+    max_bytes: u64,
+    max_files: usize,
+    file: File,
+    path: PathBuf,
+    size: u64,
+    opened_on: NaiveDate,
+}
+
+impl RollingFileWriter {
+    /// Legt das Logverzeichnis an, falls nötig, und öffnet die erste Logdatei.
+    ///
+    /// # Parameter
+    /// * `config`: Die Einstellungen der Logdateien; `enabled` wird nicht ausgewertet.
+    ///
+    /// # Fehler
+    /// `CoreError::LoggingInitError`, wenn kein Logverzeichnis ermittelt, angelegt oder die
+    /// Datei darin nicht geöffnet werden kann.
+    pub fn new(config: &LogFileConfig) -> CoreResult<Self> {
+        let directory = match &config.directory {
+            Some(directory) => directory.clone(),
+            None => get_app_state_dir(LOG_FILE_PREFIX).map(|dir| dir.join("logs")).ok_or_else(|| {
+                CoreError::LoggingInitError("Kein Verzeichnis für Logdateien gefunden; bitte log_file.directory setzen.".to_string())
+            })?,
+        };
+        fs::create_dir_all(&directory).map_err(|e| {
+            CoreError::LoggingInitError(format!("Logverzeichnis '{}' kann nicht angelegt werden: {}", directory.display(), e))
+        })?;
+        let (file, path) = open_new_file(&directory).map_err(|e| {
+            CoreError::LoggingInitError(format!("Logdatei in '{}' kann nicht geöffnet werden: {}", directory.display(), e))
+        })?;
+        let writer = Self {
+            directory,
+            rotation: config.rotation,
+            max_bytes: config.max_bytes,
+            max_files: config.max_files.max(1),
+            file,
+            path,
+            size: 0,
+            opened_on: Local::now().date_naive(),
+        };
+        writer.remove_old_files();
+        Ok(writer)
+    }
+
+    /// Der Pfad der Datei, in die gerade geschrieben wird.
+    pub fn current_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn needs_rotation(&self, incoming: usize) -> bool {
+        match self.rotation {
+            LogRotation::Daily => Local::now().date_naive() != self.opened_on,
+            // Eine einzelne Zeile, die größer als die Grenze ist, landet in einer eigenen Datei.
+            LogRotation::Size => self.size > 0 && self.size + incoming as u64 > self.max_bytes,
+        }
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let (file, path) = open_new_file(&self.directory)?;
+        self.file = file;
+        self.path = path;
+        self.size = 0;
+        self.opened_on = Local::now().date_naive();
+        self.remove_old_files();
+        Ok(())
+    }
+
+    /// Löscht die ältesten Logdateien, bis höchstens `max_files` übrig sind. Fehler werden
+    /// ignoriert, da das Logging selbst nicht darüber berichten kann.
+    fn remove_old_files(&self) {
+        let Ok(entries) = fs::read_dir(&self.directory) else {
+            return;
+        };
+        let mut files: Vec<((String, u32), PathBuf)> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter_map(|path| log_file_key(&path).map(|key| (key, path)))
+            .collect();
+        files.sort();
+        let excess = files.len().saturating_sub(self.max_files);
+        for (_, old) in files.into_iter().take(excess).filter(|(_, old)| *old != self.path) {
+            let _ = fs::remove_file(old);
+        }
+    }
+}
+
+impl Write for RollingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.needs_rotation(buf.len()) {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Der Zeitstempel und Zähler aus dem Namen einer Logdatei, nach denen die Dateien zeitlich
+/// sortiert werden; `None` für andere Dateien.
+fn log_file_key(path: &Path) -> Option<(String, u32)> {
+    let name = path.file_name()?.to_str()?;
+    let stem = name.strip_prefix(LOG_FILE_PREFIX)?.strip_prefix('.')?.strip_suffix(LOG_FILE_EXTENSION)?.strip_suffix('.')?;
+    match stem.split_once('.') {
+        Some((timestamp, counter)) => Some((timestamp.to_string(), counter.parse().ok()?)),
+        None => Some((stem.to_string(), 0)),
+    }
+}
+
+/// Öffnet eine neue Logdatei mit dem aktuellen Zeitstempel im Namen. Gibt es die Datei schon
+/// (mehrere Rotationen in derselben Sekunde), wird ein Zähler angehängt.
+fn open_new_file(directory: &Path) -> io::Result<(File, PathBuf)> {
+    let timestamp = Local::now().format("%Y-%m-%dT%H-%M-%S");
+    for counter in 0.. {
+        let name = match counter {
+            0 => format!("{}.{}.{}", LOG_FILE_PREFIX, timestamp, LOG_FILE_EXTENSION),
+            n => format!("{}.{}.{}.{}", LOG_FILE_PREFIX, timestamp, n, LOG_FILE_EXTENSION),
+        };
+        let path = directory.join(name);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((file, path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("der Zähler ist unbegrenzt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_rotation_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LogFileConfig {
+            enabled: true,
+            directory: Some(dir.path().to_path_buf()),
+            rotation: LogRotation::Size,
+            max_bytes: 10,
+            max_files: 2,
+        };
+        let mut writer = RollingFileWriter::new(&config).unwrap();
+        let first = writer.current_path().to_path_buf();
+        writer.write_all(b"12345678\n").unwrap();
+        assert_eq!(writer.current_path(), first);
+
+        // Jede weitere Zeile überschreitet die Grenze und beginnt eine neue Datei.
+        writer.write_all(b"abcdefgh\n").unwrap();
+        writer.write_all(b"ABCDEFGH\n").unwrap();
+        writer.flush().unwrap();
+        assert_ne!(writer.current_path(), first);
+
+        let files: Vec<PathBuf> = fs::read_dir(dir.path()).unwrap().map(|entry| entry.unwrap().path()).collect();
+        assert_eq!(files.len(), 2);
+        assert!(!files.contains(&first));
+        assert_eq!(fs::read_to_string(writer.current_path()).unwrap(), "ABCDEFGH\n");
+    }
+}
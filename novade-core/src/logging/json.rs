@@ -0,0 +1,123 @@
+//! # JSON-Ausgabe der Logs (`logging::json`)
+//!
+//! Dieses Untermodul von [`crate::logging`] stellt das Format [`FlatJsonFormat`] bereit, das
+//! [`initialize_logging()`](crate::logging::setup::initialize_logging) bei
+//! `log_format = "json"` verwendet. Jeder Eintrag ist eine Zeile mit einem JSON-Objekt, z.B.:
+//!
+//! ```text
+//! {"level":"INFO","message":"Fenster geöffnet","spans":"session:window","target":"novade_system::window","timestamp":"2026-10-17T08:30:00.123Z","window_id":"…","workspace":2}
+//! ```
+//!
+//! Die Felder aller umgebenden Spans (vom äußersten zum innersten) werden als eigene Felder in
+//! das Objekt übernommen, damit Aggregationssysteme wie Loki oder Elasticsearch direkt nach
+//! ihnen filtern können. Gleichnamige Felder innerer Spans und des Ereignisses überschreiben
+//! die äußeren; `timestamp`, `level`, `target` und `spans` überschreiben alle.
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use std::fmt;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::{JsonFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Formatiert Ereignisse als JSON-Zeilen mit den Feldern der umgebenden Spans auf oberster Ebene.
+///
+/// Muss zusammen mit [`JsonFields`] als Feldformat verwendet werden, damit die Felder der
+/// Spans als JSON vorliegen.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlatJsonFormat;
+
+impl<S> FormatEvent<S, JsonFields> for FlatJsonFormat
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, JsonFields>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let mut record = Map::new();
+        let mut span_names = Vec::new();
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                span_names.push(span.name());
+                if let Some(fields) = span.extensions().get::<FormattedFields<JsonFields>>() {
+                    extend_with_object(&mut record, fields);
+                }
+            }
+        }
+
+        let mut fields = String::new();
+        ctx.format_fields(Writer::new(&mut fields), event)?;
+        extend_with_object(&mut record, &fields);
+
+        let metadata = event.metadata();
+        record.insert("timestamp".to_string(), Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true).into());
+        record.insert("level".to_string(), metadata.level().as_str().into());
+        record.insert("target".to_string(), metadata.target().into());
+        if !span_names.is_empty() {
+            record.insert("spans".to_string(), span_names.join(":").into());
+        }
+        writeln!(writer, "{}", Value::Object(record))
+    }
+}
+
+/// Übernimmt die Felder eines als JSON-Objekt formatierten Feldsatzes in `record`.
+fn extend_with_object(record: &mut Map<String, Value>, json: &str) {
+    if let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(json) {
+        record.extend(fields);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::fmt::MakeWriter;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_span_fields_are_flattened() {
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(buffer.clone())
+            .fmt_fields(JsonFields::new())
+            .event_format(FlatJsonFormat)
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let session = tracing::info_span!("session", user = "anna", workspace = 1);
+            let _session = session.enter();
+            let window = tracing::info_span!("window", workspace = 2);
+            let _window = window.enter();
+            tracing::info!(title = "Terminal", "Fenster geöffnet");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let record: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(record["message"], "Fenster geöffnet");
+        assert_eq!(record["title"], "Terminal");
+        assert_eq!(record["user"], "anna");
+        assert_eq!(record["workspace"], 2);
+        assert_eq!(record["spans"], "session:window");
+        assert_eq!(record["level"], "INFO");
+        assert_eq!(record["target"], module_path!());
+    }
+}
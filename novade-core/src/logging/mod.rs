@@ -8,6 +8,10 @@
 //!
 //! - [`setup`]: Ein Untermodul, das die Funktion [`setup::initialize_logging()`]
 //!   bereitstellt, um das globale Logging-System zu konfigurieren und zu starten.
+//! - [`json`]: Ein Untermodul mit dem [`json::FlatJsonFormat`], das Einträge bei
+//!   `log_format = "json"` als JSON-Zeilen ausgibt.
+//! - [`file`]: Ein Untermodul mit dem [`file::RollingFileWriter`], der optional in rotierende
+//!   Logdateien schreibt (siehe [`crate::config::LogFileConfig`]).
 //! - **Re-exportierte Makros**: Die Standard-Logging-Makros von `tracing`
 //!   (`info!`, `warn!`, `error!`, `debug!`, `trace!`) sowie `span!`, `instrument`, `Level` und `Span`
 //!   werden direkt unter `novade_core::logging` (oder `novade_core::*` bei entsprechendem `pub use`
@@ -61,6 +65,8 @@
 //! // process_data("test");
 //! ```
 
+pub mod file;
+pub mod json;
 pub mod setup;
 
 // Re-Exportiere die wichtigsten Tracing-Makros und Typen für eine einfache Nutzung
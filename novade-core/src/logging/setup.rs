@@ -9,12 +9,16 @@
 //!   konfiguriert. Sie berücksichtigt dabei sowohl die Einstellungen aus der [`CoreConfig`]
 //!   (insbesondere `log_level`) als auch die Umgebungsvariable `RUST_LOG`.
 //!   `RUST_LOG` hat dabei Vorrang vor der Konfiguration.
+//! - [`reload_log_level()`]: Ersetzt das Log-Level zur Laufzeit, z.B. wenn ein
+//!   [`ConfigWatcher`](crate::config::ConfigWatcher) eine geänderte Konfiguration meldet.
 //!
 //! ## Konfigurationsdetails:
 //!
 //! Der `tracing_subscriber` wird wie folgt konfiguriert:
 //! - **Log-Level-Filterung**: Durch `EnvFilter`, der `RUST_LOG` und `core_config.log_level` kombiniert.
-//! - **Ausgabe**: Logs werden nach `stderr` geschrieben.
+//! - **Ausgabe**: Logs werden nach `stderr` und/oder in das systemd-Journal geschrieben
+//!   (`core_config.log_output`), optional zusätzlich in rotierende
+//!   Logdateien ([`crate::logging::file::RollingFileWriter`], konfiguriert über `core_config.log_file`).
 //! - **Span-Events**: `NEW` und `CLOSE` Events für Spans werden protokolliert.
 //! - **Zusatzinformationen**: Thread-IDs, Log-Level und das Ziel (Modulpfad) jeder Nachricht werden angezeigt.
 //!
@@ -24,6 +28,7 @@
 //! - Ein ungültiges Log-Level in der Konfiguration oder `RUST_LOG` angegeben wird.
 //! - Ein globaler Tracing-Subscriber bereits gesetzt wurde (die Funktion verwendet `try_init`,
 //!   um einen Panic in diesem Fall zu vermeiden und stattdessen einen Fehler zurückzugeben).
+//!
 //! In solchen Fällen wird ein [`CoreError::LoggingInitError`] zurückgegeben.
 //!
 //! ## Beispielhafte Verwendung (intern durch `novade_core` oder Anwendungen):
@@ -46,10 +51,23 @@
 //! }
 //! ```
 
-use crate::config::CoreConfig;
+use crate::config::{CoreConfig, LogFormat, LogOutput};
 use crate::error::{CoreError, CoreResult};
-use tracing_subscriber::fmt::format::FmtSpan;
-use tracing_subscriber::EnvFilter;
+use crate::logging::file::RollingFileWriter;
+use crate::logging::json::FlatJsonFormat;
+use std::sync::{Mutex, OnceLock};
+use tracing::Subscriber;
+use tracing_subscriber::fmt::format::{FmtSpan, JsonFields};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter};
+
+/// Ersetzt den Filter des globalen Subscribers; gesetzt von [`initialize_logging()`].
+type FilterReloader = Box<dyn Fn(EnvFilter) -> Result<(), String> + Send + Sync>;
+
+static FILTER_RELOADER: OnceLock<FilterReloader> = OnceLock::new();
 
 /// Initialisiert die globale Logging-Infrastruktur basierend auf der [`CoreConfig`].
 ///
@@ -64,9 +82,17 @@ use tracing_subscriber::EnvFilter;
 /// 3. Wenn beide ungültig sind, schlägt die Initialisierung fehl.
 ///
 /// ## Konfiguration des Subscribers:
-/// - Schreibt Logs nach `stderr`.
+/// - Schreibt Logs je nach `core_config.log_output` nach `stderr`, in das systemd-Journal
+///   (Cargo-Feature `journald`) oder in beide. Ist das Journal nicht nutzbar, wird mit einer
+///   Warnung nach `stderr` geloggt.
+/// - Schreibt, wenn `core_config.log_file.enabled` gesetzt ist,
+///   zusätzlich in rotierende Logdateien (siehe [`crate::logging::file`]).
+/// - Formatiert die Einträge nach `stderr` und in Logdateien je nach `core_config.log_format`
+///   als lesbaren Text oder als JSON-Zeilen, in denen die Felder der umgebenden Spans als
+///   eigene Felder erscheinen (siehe [`crate::logging::json`]).
 /// - Aktiviert Span-Events für `NEW` (beim Erstellen eines Spans) und `CLOSE` (beim Verlassen).
-/// - Fügt Thread-IDs, das Log-Level und das Ziel (Modulpfad) zu jeder Log-Nachricht hinzu.
+/// - Fügt im Textformat Thread-IDs, das Log-Level und das Ziel (Modulpfad) zu jeder
+///   Log-Nachricht hinzu.
 ///
 /// # Parameter
 /// * `core_config`: Eine Referenz auf die [`CoreConfig`], die das Standard-Log-Level
@@ -76,7 +102,8 @@ use tracing_subscriber::EnvFilter;
 /// Gibt ein [`CoreResult<()>`] zurück:
 /// - `Ok(())`: Wenn die Logging-Initialisierung erfolgreich war.
 /// - `Err(CoreError::LoggingInitError)`: Wenn ein Fehler auftritt, z.B. ein ungültiges
-///   Log-Level oder wenn bereits ein globaler Subscriber gesetzt wurde.
+///   Log-Level, ein nicht nutzbares Logverzeichnis oder wenn bereits ein globaler Subscriber
+///   gesetzt wurde.
 pub fn initialize_logging(core_config: &CoreConfig) -> CoreResult<()> {
     // Baue den EnvFilter: Starte mit dem Level aus der Konfiguration (`core_config.log_level`),
     // erlaube aber eine Überschreibung durch die `RUST_LOG` Umgebungsvariable, falls gesetzt.
@@ -89,24 +116,48 @@ pub fn initialize_logging(core_config: &CoreConfig) -> CoreResult<()> {
             ))
         })?;
 
-    // Baue den Subscriber mit den gewünschten Formatierungsoptionen.
-    let subscriber_builder = tracing_subscriber::fmt()
-        .with_env_filter(env_filter) // Wendet den oben erstellten Filter an.
-        .with_writer(std::io::stderr) // Log-Ausgaben gehen nach stderr.
-        .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE) // Protokolliert Erstellung und Schließung von Spans.
-        .with_thread_ids(true) // Fügt die ID des aktuellen Threads zu Log-Einträgen hinzu.
-        .with_level(true) // Fügt das Log-Level (z.B. INFO, DEBUG) zu Log-Einträgen hinzu.
-        .with_target(true); // Fügt das Ziel (Modulpfad) zu Log-Einträgen hinzu.
+    // Der Filter sitzt in einer eigenen, austauschbaren Schicht, damit reload_log_level()
+    // ihn für alle Ausgaben gemeinsam ersetzen kann.
+    let (filter_layer, reload_handle) = reload::Layer::new(env_filter);
+
+    // Das systemd-Journal, falls gewählt. Ist es nicht nutzbar, gehen die Logs stattdessen nach
+    // stderr, damit sie nicht verloren gehen; gewarnt wird, sobald das Logging steht.
+    let (journald_layer, journald_error) = match core_config.log_output {
+        LogOutput::Stderr => (None, None),
+        LogOutput::Journald | LogOutput::Both => match journald_layer() {
+            Ok(layer) => (Some(layer), None),
+            Err(error) => (None, Some(error)),
+        },
+    };
+    let use_stderr = core_config.log_output != LogOutput::Journald || journald_error.is_some();
+
+    // Die Ausgabe nach stderr im gewählten Format.
+    let stderr_layer = use_stderr.then(|| output_layer(core_config.log_format, std::io::stderr, true));
+
+    // Optional dieselben Einträge zusätzlich in rotierende Logdateien, ohne ANSI-Farbcodes.
+    let file_layer = if core_config.log_file.enabled {
+        let writer = RollingFileWriter::new(&core_config.log_file)?;
+        Some(output_layer(core_config.log_format, Mutex::new(writer), false))
+    } else {
+        None
+    };
 
     // Versuche, den konfigurierten Subscriber als globalen Standard für das Tracing-System zu setzen.
     // `try_init` gibt einen Fehler zurück, falls bereits ein globaler Subscriber gesetzt wurde,
     // anstatt zu panicken (wie es `set_global_default` tun würde).
-    subscriber_builder.try_init().map_err(|e| {
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(stderr_layer)
+        .with(file_layer)
+        .with(journald_layer)
+        .try_init()
+        .map_err(|e| {
         CoreError::LoggingInitError(format!(
             "Fehler beim Setzen des globalen Tracing-Subscribers: {}. Möglicherweise wurde initialize_logging bereits aufgerufen.",
             e
         ))
     })?;
+    let _ = FILTER_RELOADER.set(Box::new(move |filter| reload_handle.reload(filter).map_err(|e| e.to_string())));
 
     // Eine erste Log-Nachricht, um zu bestätigen, dass das Logging funktioniert
     // und um das effektiv verwendete Log-Level (implizit durch den Filter) zu signalisieren.
@@ -115,7 +166,95 @@ pub fn initialize_logging(core_config: &CoreConfig) -> CoreResult<()> {
         rust_log_env = %std::env::var("RUST_LOG").unwrap_or_else(|_| "Nicht gesetzt".to_string()),
         "Logging initialisiert. Effektives Log-Level wird durch Konfiguration und RUST_LOG bestimmt."
     );
+    if let Some(error) = journald_error {
+        tracing::warn!(%error, "Das systemd-Journal ist nicht nutzbar; es wird nach stderr geloggt.");
+    }
+
+    Ok(())
+}
+
+/// Eine Ausgabeschicht, die in `writer` schreibt, als lesbarer Text oder als JSON-Zeilen
+/// (siehe [`FlatJsonFormat`]).
+///
+/// Beide Formate protokollieren Erstellung und Schließung von Spans (`NEW` und `CLOSE`).
+/// `ansi` schaltet Farbcodes im Textformat ein, sofern `tracing-subscriber` sie unterstützt.
+fn output_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer()
+            .with_writer(writer)
+            .with_ansi(ansi)
+            .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE) // Protokolliert Erstellung und Schließung von Spans.
+            .with_thread_ids(true) // Fügt die ID des aktuellen Threads zu Log-Einträgen hinzu.
+            .with_level(true) // Fügt das Log-Level (z.B. INFO, DEBUG) zu Log-Einträgen hinzu.
+            .with_target(true) // Fügt das Ziel (Modulpfad) zu Log-Einträgen hinzu.
+            .boxed(),
+        LogFormat::Json => fmt::layer()
+            .with_writer(writer)
+            .with_span_events(FmtSpan::NEW | FmtSpan::CLOSE)
+            .fmt_fields(JsonFields::new())
+            .event_format(FlatJsonFormat)
+            .boxed(),
+    }
+}
+
+/// Die Schicht für das systemd-Journal.
+#[cfg(feature = "journald")]
+type JournaldLayer = tracing_journald::Layer;
+#[cfg(not(feature = "journald"))]
+type JournaldLayer = tracing_subscriber::layer::Identity;
+
+/// Verbindet sich mit dem systemd-Journal.
+///
+/// Die Einträge erscheinen unter dem Bezeichner `novade`. Die Level werden so auf die
+/// syslog-Prioritäten abgebildet, dass `journalctl -p info` die üblichen Meldungen ohne
+/// Debug-Ausgaben zeigt: `ERROR` → err, `WARN` → warning, `INFO` → info, `DEBUG` und
+/// `TRACE` → debug.
+#[cfg(feature = "journald")]
+fn journald_layer() -> Result<JournaldLayer, String> {
+    use tracing_journald::{Priority, PriorityMappings};
+    let mappings = PriorityMappings {
+        error: Priority::Error,
+        warn: Priority::Warning,
+        info: Priority::Informational,
+        debug: Priority::Debug,
+        trace: Priority::Debug,
+    };
+    let layer = tracing_journald::layer().map_err(|e| e.to_string())?;
+    Ok(layer.with_syslog_identifier("novade".to_string()).with_priority_mappings(mappings))
+}
+
+#[cfg(not(feature = "journald"))]
+fn journald_layer() -> Result<JournaldLayer, String> {
+    Err("novade-core wurde ohne das Feature `journald` gebaut".to_string())
+}
 
+/// Ersetzt das Log-Level des mit [`initialize_logging()`] gesetzten Subscribers zur Laufzeit.
+///
+/// Ist `RUST_LOG` gesetzt, behält es wie bei der Initialisierung Vorrang und der Aufruf
+/// ändert nichts.
+///
+/// # Parameter
+/// * `log_level`: Das neue Level oder eine Filterangabe im Format von `RUST_LOG`
+///   (z.B. "debug" oder "info,novade_domain=trace").
+///
+/// # Rückgabe
+/// `Err(CoreError::LoggingInitError)`, wenn das Level ungültig ist oder das Logging nicht mit
+/// [`initialize_logging()`] initialisiert wurde.
+pub fn reload_log_level(log_level: &str) -> CoreResult<()> {
+    let reloader = FILTER_RELOADER.get().ok_or_else(|| {
+        CoreError::LoggingInitError("Das Log-Level kann erst nach initialize_logging geändert werden.".to_string())
+    })?;
+    if std::env::var("RUST_LOG").is_ok_and(|value| EnvFilter::try_new(value).is_ok()) {
+        return Ok(());
+    }
+    let filter = EnvFilter::try_new(log_level)
+        .map_err(|e| CoreError::LoggingInitError(format!("Ungültiges Log-Level '{}': {}", log_level, e)))?;
+    reloader(filter).map_err(|e| CoreError::LoggingInitError(format!("Log-Level konnte nicht geändert werden: {}", e)))?;
+    tracing::info!(log_level, "Log-Level geändert.");
     Ok(())
 }
 
@@ -131,7 +270,7 @@ mod tests {
             log_level: log_level.to_string(),
             default_locale: "en-US".to_string(),
             config_version: Version::new(1, 0, 0),
-            custom_theme_path: None,
+            ..CoreConfig::default()
         }
     }
 
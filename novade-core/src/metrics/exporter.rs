@@ -0,0 +1,149 @@
+//! # Ausgabe von Metriken (`metrics::exporter`)
+//!
+//! Dieses Untermodul von [`crate::metrics`] gibt Momentaufnahmen der Metriken aus. Ein Exporter
+//! implementiert [`MetricsExporter`] und wird mit
+//! [`MetricsRegistry::export()`](crate::metrics::MetricsRegistry::export) aufgerufen, z.B.
+//! periodisch aus der Hauptschleife.
+//!
+//! ## Hauptkomponenten:
+//!
+//! - [`LogSummaryExporter`]: Schreibt eine Zusammenfassung der Metriken in das Log.
+//! - [`render_prometheus()`]: Erzeugt das Textformat von Prometheus. Die Systemschicht bietet
+//!   es über HTTP an, damit Prometheus die Werte abfragen kann.
+
+use crate::error::CoreResult;
+use crate::metrics::{MetricSnapshot, MetricValue};
+use std::fmt::Write;
+
+/// Gibt Momentaufnahmen von Metriken aus.
+pub trait MetricsExporter: Send + Sync {
+    /// Gibt die Metriken aus.
+    ///
+    /// # Parameter
+    /// * `metrics`: Die Momentaufnahmen, nach Namen sortiert.
+    ///
+    /// # Fehler
+    /// Abhängig vom Exporter, z.B. wenn das Ziel nicht erreichbar ist.
+    fn export(&self, metrics: &[MetricSnapshot]) -> CoreResult<()>;
+}
+
+/// Schreibt je Metrik einen Eintrag auf dem Level `INFO` mit dem Ziel `novade_core::metrics`.
+///
+/// Histogramme werden mit Anzahl, Summe und Mittelwert zusammengefasst.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogSummaryExporter;
+
+impl MetricsExporter for LogSummaryExporter {
+    fn export(&self, metrics: &[MetricSnapshot]) -> CoreResult<()> {
+        for metric in metrics {
+            match &metric.value {
+                MetricValue::Counter(value) => {
+                    tracing::info!(target: "novade_core::metrics", metric = %metric.name, value, "Zähler");
+                }
+                MetricValue::Gauge(value) => {
+                    tracing::info!(target: "novade_core::metrics", metric = %metric.name, value, "Messwert");
+                }
+                MetricValue::Histogram(histogram) => {
+                    let mean = if histogram.count > 0 { histogram.sum / histogram.count as f64 } else { 0.0 };
+                    tracing::info!(
+                        target: "novade_core::metrics",
+                        metric = %metric.name,
+                        count = histogram.count,
+                        sum = histogram.sum,
+                        mean,
+                        "Histogramm"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Erzeugt das Textformat von Prometheus (Version 0.0.4) für die Metriken.
+///
+/// Zeichen, die in Prometheus-Namen nicht erlaubt sind (z.B. Punkte), werden durch `_`
+/// ersetzt.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::metrics::exporter::render_prometheus;
+/// use novade_core::metrics::MetricsRegistry;
+///
+/// let registry = MetricsRegistry::new();
+/// registry.counter("apps.launched").increment(3);
+/// assert_eq!(render_prometheus(&registry.snapshot()), "# TYPE apps_launched counter\napps_launched 3\n");
+/// ```
+pub fn render_prometheus(metrics: &[MetricSnapshot]) -> String {
+    let mut output = String::new();
+    for metric in metrics {
+        let name = prometheus_name(&metric.name);
+        // Schreiben in einen String schlägt nicht fehl.
+        let _ = match &metric.value {
+            MetricValue::Counter(value) => writeln!(output, "# TYPE {name} counter\n{name} {value}"),
+            MetricValue::Gauge(value) => writeln!(output, "# TYPE {name} gauge\n{name} {}", prometheus_float(*value)),
+            MetricValue::Histogram(histogram) => {
+                let _ = writeln!(output, "# TYPE {name} histogram");
+                for (bound, count) in &histogram.buckets {
+                    let _ = writeln!(output, "{name}_bucket{{le=\"{}\"}} {count}", prometheus_float(*bound));
+                }
+                writeln!(
+                    output,
+                    "{name}_bucket{{le=\"+Inf\"}} {count}\n{name}_sum {}\n{name}_count {count}",
+                    prometheus_float(histogram.sum),
+                    count = histogram.count
+                )
+            }
+        };
+    }
+    output
+}
+
+/// Ersetzt die in Prometheus-Namen nicht erlaubten Zeichen durch `_`.
+fn prometheus_name(name: &str) -> String {
+    let mut result: String =
+        name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' }).collect();
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+    result
+}
+
+/// Formatiert eine Zahl so, wie Prometheus sie erwartet (`+Inf`, `-Inf`, `NaN`).
+fn prometheus_float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::MetricsRegistry;
+
+    #[test]
+    fn test_render_prometheus_histogram_and_gauge() {
+        let registry = MetricsRegistry::new();
+        registry.gauge("compositor.outputs").set(2.0);
+        let frame_time = registry.histogram_with_buckets("compositor.frame_time_seconds", &[0.01, 0.05]);
+        frame_time.record(0.004);
+        frame_time.record(0.02);
+        frame_time.record(0.5);
+
+        assert_eq!(
+            render_prometheus(&registry.snapshot()),
+            "# TYPE compositor_frame_time_seconds histogram\n\
+             compositor_frame_time_seconds_bucket{le=\"0.01\"} 1\n\
+             compositor_frame_time_seconds_bucket{le=\"0.05\"} 2\n\
+             compositor_frame_time_seconds_bucket{le=\"+Inf\"} 3\n\
+             compositor_frame_time_seconds_sum 0.524\n\
+             compositor_frame_time_seconds_count 3\n\
+             # TYPE compositor_outputs gauge\n\
+             compositor_outputs 2\n"
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! # Metriken in `novade-core`
+//!
+//! Dieses Modul sammelt Leistungsdaten (z.B. Frame-Zeiten des Compositors oder die Anzahl
+//! gestarteter Anwendungen) in einer prozessweiten [`MetricsRegistry`], damit sie nicht nur
+//! als verstreute Debug-Ausgaben vorliegen. Erfasst wird über Makros, ähnlich den
+//! Logging-Makros in [`crate::logging`]; ausgegeben wird über austauschbare Exporter.
+//!
+//! ## Hauptkomponenten:
+//!
+//! - [`Counter`], [`Gauge`] und [`Histogram`]: Die drei Arten von Metriken – ein nur
+//!   wachsender Zähler, ein beliebig setzbarer Messwert und eine Verteilung über Buckets.
+//! - [`MetricsRegistry`]: Verwaltet die Metriken nach Namen und erstellt Momentaufnahmen
+//!   ([`MetricSnapshot`]). Die globale Instanz liefert [`registry()`].
+//! - **Makros** [`counter!`](crate::counter), [`gauge!`](crate::gauge) und
+//!   [`histogram!`](crate::histogram): Erfassen Werte in der globalen Registry.
+//! - [`exporter`]: Ein Untermodul mit dem Trait [`exporter::MetricsExporter`], einer
+//!   Zusammenfassung im Log ([`exporter::LogSummaryExporter`]) und dem Textformat von
+//!   Prometheus ([`exporter::render_prometheus()`]), das die Systemschicht über HTTP anbietet.
+//!
+//! ## Namen:
+//!
+//! Metriknamen dürfen Punkte zur Gliederung enthalten (z.B. `"compositor.frame_time_seconds"`).
+//! Für Prometheus werden alle Zeichen außer Buchstaben, Ziffern, `_` und `:` durch `_` ersetzt.
+//!
+//! ## Verwendung:
+//!
+//! ```rust
+//! use novade_core::{counter, gauge, histogram};
+//! use novade_core::metrics::{registry, MetricValue};
+//!
+//! counter!("doc.apps_launched");
+//! counter!("doc.apps_launched", 2);
+//! gauge!("doc.open_windows", 5.0);
+//! histogram!("doc.frame_time_seconds", 0.012);
+//!
+//! let snapshot = registry().snapshot();
+//! let launched = snapshot.iter().find(|metric| metric.name == "doc.apps_launched").unwrap();
+//! assert_eq!(launched.value, MetricValue::Counter(3));
+//! ```
+//!
+//! In häufig durchlaufenem Code kann die Metrik einmal geholt und behalten werden, statt sie
+//! bei jedem Aufruf über den Namen nachzuschlagen:
+//!
+//! ```rust
+//! let frames = novade_core::metrics::registry().counter("doc.frames_rendered");
+//! for _ in 0..3 {
+//!     frames.increment(1);
+//! }
+//! assert_eq!(frames.get(), 3);
+//! ```
+
+pub mod exporter;
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Die Bucket-Grenzen eines [`Histogram`] ohne eigene Grenzen, in Sekunden: von 1 ms bis 10 s,
+/// passend für Frame-, Start- und Antwortzeiten.
+pub const DEFAULT_BUCKETS: &[f64] = &[0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Ein Zähler, der nur wachsen kann, z.B. für die Anzahl gestarteter Anwendungen.
+#[derive(Debug, Default)]
+pub struct Counter {
+    value: AtomicU64,
+}
+
+impl Counter {
+    /// Erhöht den Zähler um `amount`.
+    pub fn increment(&self, amount: u64) {
+        self.value.fetch_add(amount, Ordering::Relaxed);
+    }
+
+    /// Der aktuelle Stand des Zählers.
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+/// Ein Messwert, der beliebig steigen und fallen kann, z.B. die Anzahl offener Fenster.
+#[derive(Debug, Default)]
+pub struct Gauge {
+    /// Die Bits des `f64`-Werts, damit er ohne Sperre gelesen und geschrieben werden kann.
+    bits: AtomicU64,
+}
+
+impl Gauge {
+    /// Setzt den Messwert.
+    pub fn set(&self, value: f64) {
+        self.bits.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Ändert den Messwert um `delta` (negativ zum Verringern).
+    pub fn add(&self, delta: f64) {
+        // Der Fehlerfall kann nicht eintreten, da die Closure immer `Some` liefert.
+        let _ = self.bits.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+            Some((f64::from_bits(bits) + delta).to_bits())
+        });
+    }
+
+    /// Der aktuelle Messwert.
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+}
+
+/// Eine Verteilung von Messwerten über feste Buckets, z.B. für Frame-Zeiten.
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: Vec<f64>,
+    state: Mutex<HistogramSnapshot>,
+}
+
+impl Histogram {
+    /// Erstellt ein Histogramm mit den gegebenen oberen Bucket-Grenzen. Die Grenzen werden
+    /// sortiert; ungültige Grenzen (`NaN`) und doppelte werden verworfen.
+    pub fn with_buckets(bounds: &[f64]) -> Self {
+        let mut bounds: Vec<f64> = bounds.iter().copied().filter(|bound| !bound.is_nan()).collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        let state = HistogramSnapshot { buckets: bounds.iter().map(|bound| (*bound, 0)).collect(), sum: 0.0, count: 0 };
+        Self { bounds, state: Mutex::new(state) }
+    }
+
+    /// Erfasst einen Messwert.
+    pub fn record(&self, value: f64) {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let first = self.bounds.partition_point(|bound| *bound < value);
+        for (_, count) in &mut state.buckets[first..] {
+            *count += 1;
+        }
+        state.sum += value;
+        state.count += 1;
+    }
+
+    /// Erfasst eine Dauer in Sekunden.
+    pub fn record_duration(&self, duration: Duration) {
+        self.record(duration.as_secs_f64());
+    }
+
+    /// Eine Momentaufnahme der bisher erfassten Werte.
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::with_buckets(DEFAULT_BUCKETS)
+    }
+}
+
+/// Der Stand eines [`Histogram`] zu einem Zeitpunkt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramSnapshot {
+    /// Je obere Bucket-Grenze die Anzahl der Werte kleiner oder gleich dieser Grenze
+    /// (kumulativ, wie bei Prometheus).
+    pub buckets: Vec<(f64, u64)>,
+    /// Die Summe aller Werte.
+    pub sum: f64,
+    /// Die Anzahl aller Werte, einschließlich derer über der größten Grenze.
+    pub count: u64,
+}
+
+/// Der Wert einer Metrik in einer [`MetricSnapshot`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricValue {
+    /// Der Stand eines [`Counter`].
+    Counter(u64),
+    /// Der Wert eines [`Gauge`].
+    Gauge(f64),
+    /// Der Stand eines [`Histogram`].
+    Histogram(HistogramSnapshot),
+}
+
+/// Eine Metrik mit ihrem Namen und Wert zu einem Zeitpunkt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSnapshot {
+    /// Der Name, unter dem die Metrik registriert ist.
+    pub name: String,
+    /// Der Wert zum Zeitpunkt der Aufnahme.
+    pub value: MetricValue,
+}
+
+/// Eine registrierte Metrik.
+#[derive(Debug, Clone)]
+enum Metric {
+    Counter(Arc<Counter>),
+    Gauge(Arc<Gauge>),
+    Histogram(Arc<Histogram>),
+}
+
+/// Verwaltet Metriken nach Namen.
+///
+/// Jede Metrik wird beim ersten Zugriff angelegt. Wird ein Name mit einer anderen Art als bei
+/// der Registrierung abgefragt (z.B. als `Gauge`, obwohl er ein `Counter` ist), wird eine
+/// Warnung geloggt und eine nicht registrierte Metrik zurückgegeben, deren Werte verloren gehen.
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    metrics: Mutex<BTreeMap<String, Metric>>,
+}
+
+impl MetricsRegistry {
+    /// Erstellt eine leere Registry, z.B. für Tests; die Makros verwenden [`registry()`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Der [`Counter`] mit dem Namen `name`, der bei Bedarf angelegt wird.
+    pub fn counter(&self, name: &str) -> Arc<Counter> {
+        match self.get_or_insert(name, || Metric::Counter(Arc::default())) {
+            Metric::Counter(counter) => counter,
+            other => {
+                warn_kind_mismatch(name, "Counter", &other);
+                Arc::default()
+            }
+        }
+    }
+
+    /// Der [`Gauge`] mit dem Namen `name`, der bei Bedarf angelegt wird.
+    pub fn gauge(&self, name: &str) -> Arc<Gauge> {
+        match self.get_or_insert(name, || Metric::Gauge(Arc::default())) {
+            Metric::Gauge(gauge) => gauge,
+            other => {
+                warn_kind_mismatch(name, "Gauge", &other);
+                Arc::default()
+            }
+        }
+    }
+
+    /// Das [`Histogram`] mit dem Namen `name`, das bei Bedarf mit [`DEFAULT_BUCKETS`]
+    /// angelegt wird.
+    pub fn histogram(&self, name: &str) -> Arc<Histogram> {
+        self.histogram_with_buckets(name, DEFAULT_BUCKETS)
+    }
+
+    /// Das [`Histogram`] mit dem Namen `name`, das bei Bedarf mit den Grenzen `bounds`
+    /// angelegt wird. Ist es bereits registriert, bleiben seine Grenzen unverändert.
+    pub fn histogram_with_buckets(&self, name: &str, bounds: &[f64]) -> Arc<Histogram> {
+        match self.get_or_insert(name, || Metric::Histogram(Arc::new(Histogram::with_buckets(bounds)))) {
+            Metric::Histogram(histogram) => histogram,
+            other => {
+                warn_kind_mismatch(name, "Histogram", &other);
+                Arc::new(Histogram::with_buckets(bounds))
+            }
+        }
+    }
+
+    /// Momentaufnahmen aller Metriken, nach Namen sortiert.
+    pub fn snapshot(&self) -> Vec<MetricSnapshot> {
+        let metrics = self.metrics.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        metrics
+            .iter()
+            .map(|(name, metric)| MetricSnapshot {
+                name: name.clone(),
+                value: match metric {
+                    Metric::Counter(counter) => MetricValue::Counter(counter.get()),
+                    Metric::Gauge(gauge) => MetricValue::Gauge(gauge.get()),
+                    Metric::Histogram(histogram) => MetricValue::Histogram(histogram.snapshot()),
+                },
+            })
+            .collect()
+    }
+
+    /// Gibt eine Momentaufnahme aller Metriken über `exporter` aus.
+    ///
+    /// # Fehler
+    /// Die Fehler des Exporters.
+    pub fn export(&self, exporter: &dyn exporter::MetricsExporter) -> crate::error::CoreResult<()> {
+        exporter.export(&self.snapshot())
+    }
+
+    fn get_or_insert(&self, name: &str, create: impl FnOnce() -> Metric) -> Metric {
+        let mut metrics = self.metrics.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        metrics.entry(name.to_string()).or_insert_with(create).clone()
+    }
+}
+
+impl Metric {
+    fn kind(&self) -> &'static str {
+        match self {
+            Metric::Counter(_) => "Counter",
+            Metric::Gauge(_) => "Gauge",
+            Metric::Histogram(_) => "Histogram",
+        }
+    }
+}
+
+/// Warnt, dass `name` als `requested` abgefragt wurde, aber als `registered` registriert ist.
+fn warn_kind_mismatch(name: &str, requested: &str, registered: &Metric) {
+    tracing::warn!(
+        metric = name,
+        requested,
+        registered = registered.kind(),
+        "Metrik '{}' ist als {} registriert; die Werte als {} werden verworfen.",
+        name,
+        registered.kind(),
+        requested
+    );
+}
+
+/// Die globale Registry, in die die Makros schreiben.
+pub fn registry() -> &'static MetricsRegistry {
+    static REGISTRY: OnceLock<MetricsRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(MetricsRegistry::new)
+}
+
+/// Erhöht einen [`Counter`](crate::metrics::Counter) der globalen Registry um 1 oder um den
+/// angegebenen Betrag.
+///
+/// ```rust
+/// novade_core::counter!("doc.counter_macro");
+/// novade_core::counter!("doc.counter_macro", 4);
+/// assert_eq!(novade_core::metrics::registry().counter("doc.counter_macro").get(), 5);
+/// ```
+#[macro_export]
+macro_rules! counter {
+    ($name:expr) => {
+        $crate::metrics::registry().counter($name).increment(1)
+    };
+    ($name:expr, $amount:expr) => {
+        $crate::metrics::registry().counter($name).increment($amount)
+    };
+}
+
+/// Setzt einen [`Gauge`](crate::metrics::Gauge) der globalen Registry.
+///
+/// ```rust
+/// novade_core::gauge!("doc.gauge_macro", 2.5);
+/// assert_eq!(novade_core::metrics::registry().gauge("doc.gauge_macro").get(), 2.5);
+/// ```
+#[macro_export]
+macro_rules! gauge {
+    ($name:expr, $value:expr) => {
+        $crate::metrics::registry().gauge($name).set($value)
+    };
+}
+
+/// Erfasst einen Wert (`f64`) in einem [`Histogram`](crate::metrics::Histogram) der globalen
+/// Registry.
+///
+/// ```rust
+/// novade_core::histogram!("doc.histogram_macro", 0.02);
+/// assert_eq!(novade_core::metrics::registry().histogram("doc.histogram_macro").snapshot().count, 1);
+/// ```
+#[macro_export]
+macro_rules! histogram {
+    ($name:expr, $value:expr) => {
+        $crate::metrics::registry().histogram($name).record($value)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let histogram = Histogram::with_buckets(&[1.0, 0.1, 0.5]);
+        for value in [0.05, 0.1, 0.3, 2.0] {
+            histogram.record(value);
+        }
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.buckets, vec![(0.1, 2), (0.5, 3), (1.0, 3)]);
+        assert_eq!(snapshot.count, 4);
+        assert!((snapshot.sum - 2.45).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_registry_keeps_the_first_kind_of_a_name() {
+        let registry = MetricsRegistry::new();
+        registry.counter("windows").increment(2);
+        registry.gauge("windows").set(7.0);
+        registry.gauge("load").add(1.5);
+        registry.gauge("load").add(-0.5);
+
+        let snapshot = registry.snapshot();
+        assert_eq!(
+            snapshot,
+            vec![
+                MetricSnapshot { name: "load".to_string(), value: MetricValue::Gauge(1.0) },
+                MetricSnapshot { name: "windows".to_string(), value: MetricValue::Counter(2) },
+            ]
+        );
+    }
+}
@@ -0,0 +1,430 @@
+//! # Typisierte Signale (`signal`)
+//!
+//! Dieses Modul stellt mit [`Signal`] ein kleines Publish/Subscribe-Primitiv bereit. Ein Signal
+//! verteilt jeden gesendeten Wert an alle Abonnenten; es ist die gemeinsame Grundlage für
+//! Ereignisbusse und Änderungsbenachrichtigungen der höheren Schichten (z.B. den `EventBus`
+//! der Domänenschicht oder die Benachrichtigungen über geänderte Einstellungen), damit diese
+//! nicht jeweils eigene Kanäle verwalten.
+//!
+//! ## Hauptkomponenten:
+//!
+//! - [`Signal`]: Das Signal selbst. Abonnenten können Werte auf drei Arten erhalten:
+//!   - synchron über einen [`std::sync::mpsc::Receiver`] ([`Signal::subscribe()`]),
+//!   - asynchron über einen [`AsyncReceiver`] ([`Signal::subscribe_async()`]), der ohne
+//!     bestimmte Laufzeitumgebung mit `.await` gelesen werden kann,
+//!   - über Rückruffunktionen ([`Signal::connect()`]), auch als schwache Verbindung, die mit
+//!     ihrem Besitzer endet ([`Signal::connect_weak()`]).
+//! - [`SubscriptionId`]: Kennung einer Rückrufverbindung zum Trennen mit [`Signal::disconnect()`].
+//!
+//! Empfänger-Abonnements enden, sobald der Empfänger verworfen wird; das Signal entfernt sie
+//! beim nächsten Senden. Senden blockiert nie: Die Kanäle sind unbegrenzt.
+//!
+//! # Beispiele
+//! ```
+//! use novade_core::signal::Signal;
+//!
+//! let theme_changed: Signal<String> = Signal::new();
+//! let dark_only = theme_changed.subscribe_filtered(|theme| theme.ends_with("-dark"));
+//! let all = theme_changed.subscribe();
+//!
+//! theme_changed.emit("adwaita".to_string());
+//! theme_changed.emit("nova-dark".to_string());
+//!
+//! assert_eq!(all.try_iter().count(), 2);
+//! assert_eq!(dark_only.try_recv().unwrap(), "nova-dark");
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+
+/// Entscheidet, ob ein Abonnent einen Wert erhält.
+type Filter<T> = Box<dyn Fn(&T) -> bool + Send>;
+
+/// Eine Rückruffunktion; liefert `false`, wenn die Verbindung nicht mehr besteht (z.B. weil
+/// der Besitzer einer schwachen Verbindung verworfen wurde).
+type Callback<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// Die Kennung einer Rückrufverbindung, siehe [`Signal::connect()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// Ein Abonnent eines Signals.
+enum Subscriber<T> {
+    Channel { filter: Filter<T>, sender: Sender<T> },
+    Async { filter: Filter<T>, queue: Weak<AsyncQueue<T>> },
+    Callback { id: SubscriptionId, callback: Callback<T> },
+}
+
+/// Die Abonnenten eines Signals.
+struct Subscribers<T> {
+    next_id: u64,
+    entries: Vec<Subscriber<T>>,
+}
+
+/// Ein typisiertes Signal, das gesendete Werte an alle Abonnenten verteilt.
+///
+/// Ein `Signal` ist `Send + Sync` und wird üblicherweise in einem `Arc` geteilt oder als Feld
+/// eines Dienstes gehalten.
+pub struct Signal<T> {
+    subscribers: Mutex<Subscribers<T>>,
+}
+
+impl<T> Default for Signal<T> {
+    fn default() -> Self {
+        Self { subscribers: Mutex::new(Subscribers { next_id: 0, entries: Vec::new() }) }
+    }
+}
+
+impl<T: Clone + Send + 'static> Signal<T> {
+    /// Erstellt ein Signal ohne Abonnenten.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Abonniert alle Werte über einen synchronen Kanal.
+    ///
+    /// Das Abonnement endet, sobald der `Receiver` verworfen wird.
+    pub fn subscribe(&self) -> Receiver<T> {
+        self.subscribe_filtered(|_| true)
+    }
+
+    /// Abonniert die Werte, für die `filter` `true` liefert, über einen synchronen Kanal.
+    ///
+    /// Der Filter wird beim Senden aufgerufen, während das Signal gesperrt ist; er darf das
+    /// Signal daher nicht selbst verwenden.
+    pub fn subscribe_filtered(&self, filter: impl Fn(&T) -> bool + Send + 'static) -> Receiver<T> {
+        let (sender, receiver) = mpsc::channel();
+        self.lock().entries.push(Subscriber::Channel { filter: Box::new(filter), sender });
+        receiver
+    }
+
+    /// Abonniert alle Werte über einen [`AsyncReceiver`].
+    ///
+    /// Das Abonnement endet, sobald der `AsyncReceiver` verworfen wird.
+    pub fn subscribe_async(&self) -> AsyncReceiver<T> {
+        self.subscribe_async_filtered(|_| true)
+    }
+
+    /// Abonniert die Werte, für die `filter` `true` liefert, über einen [`AsyncReceiver`].
+    pub fn subscribe_async_filtered(&self, filter: impl Fn(&T) -> bool + Send + 'static) -> AsyncReceiver<T> {
+        let queue = Arc::new(AsyncQueue::default());
+        self.lock().entries.push(Subscriber::Async { filter: Box::new(filter), queue: Arc::downgrade(&queue) });
+        AsyncReceiver { queue }
+    }
+
+    /// Verbindet eine Rückruffunktion, die für jeden gesendeten Wert aufgerufen wird.
+    ///
+    /// Rückruffunktionen werden nach den Kanälen und außerhalb der Sperre des Signals
+    /// aufgerufen; sie dürfen also selbst senden oder Abonnements ändern.
+    ///
+    /// # Rückgabe
+    /// Die Kennung, mit der die Verbindung über [`Signal::disconnect()`] getrennt wird.
+    pub fn connect(&self, callback: impl Fn(&T) + Send + Sync + 'static) -> SubscriptionId {
+        self.add_callback(Arc::new(move |value| {
+            callback(value);
+            true
+        }))
+    }
+
+    /// Verbindet eine Rückruffunktion, die nur so lange besteht wie `owner`.
+    ///
+    /// Das Signal hält nur eine schwache Referenz auf `owner`; ist er verworfen, wird die
+    /// Verbindung beim nächsten Senden entfernt. So hält ein Signal z.B. ein UI-Element, das
+    /// auf Änderungen reagiert, nicht am Leben.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_core::signal::Signal;
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let volume_changed: Signal<u32> = Signal::new();
+    /// let indicator = Arc::new(AtomicU32::new(0));
+    /// volume_changed.connect_weak(&indicator, |indicator, volume| indicator.store(*volume, Ordering::SeqCst));
+    ///
+    /// volume_changed.emit(40);
+    /// assert_eq!(indicator.load(Ordering::SeqCst), 40);
+    ///
+    /// drop(indicator);
+    /// volume_changed.emit(50);
+    /// assert_eq!(volume_changed.subscriber_count(), 0);
+    /// ```
+    pub fn connect_weak<O: Send + Sync + 'static>(
+        &self,
+        owner: &Arc<O>,
+        callback: impl Fn(&O, &T) + Send + Sync + 'static,
+    ) -> SubscriptionId {
+        let owner = Arc::downgrade(owner);
+        self.add_callback(Arc::new(move |value| match owner.upgrade() {
+            Some(owner) => {
+                callback(&owner, value);
+                true
+            }
+            None => false,
+        }))
+    }
+
+    /// Trennt eine Rückrufverbindung.
+    ///
+    /// # Rückgabe
+    /// `false`, wenn die Verbindung nicht (mehr) besteht.
+    pub fn disconnect(&self, id: SubscriptionId) -> bool {
+        let mut subscribers = self.lock();
+        let before = subscribers.entries.len();
+        subscribers.entries.retain(|subscriber| !matches!(subscriber, Subscriber::Callback { id: other, .. } if *other == id));
+        subscribers.entries.len() != before
+    }
+
+    /// Sendet `value` an alle passenden Abonnenten und entfernt beendete Abonnements.
+    pub fn emit(&self, value: T) {
+        let callbacks: Vec<(SubscriptionId, Callback<T>)> = {
+            let mut subscribers = self.lock();
+            subscribers.entries.retain(|subscriber| match subscriber {
+                Subscriber::Channel { filter, sender } => !filter(&value) || sender.send(value.clone()).is_ok(),
+                Subscriber::Async { filter, queue } => match queue.upgrade() {
+                    Some(queue) => {
+                        if filter(&value) {
+                            queue.push(value.clone());
+                        }
+                        true
+                    }
+                    None => false,
+                },
+                Subscriber::Callback { .. } => true,
+            });
+            subscribers
+                .entries
+                .iter()
+                .filter_map(|subscriber| match subscriber {
+                    Subscriber::Callback { id, callback } => Some((*id, Arc::clone(callback))),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let ended: Vec<SubscriptionId> =
+            callbacks.into_iter().filter(|(_, callback)| !callback(&value)).map(|(id, _)| id).collect();
+        for id in ended {
+            self.disconnect(id);
+        }
+    }
+
+    /// Die Anzahl der Abonnements. Verworfene Empfänger zählen bis zum nächsten Senden mit.
+    pub fn subscriber_count(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    fn add_callback(&self, callback: Callback<T>) -> SubscriptionId {
+        let mut subscribers = self.lock();
+        let id = SubscriptionId(subscribers.next_id);
+        subscribers.next_id += 1;
+        subscribers.entries.push(Subscriber::Callback { id, callback });
+        id
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Subscribers<T>> {
+        // Filter und Kanäle können nicht mitten in einer Änderung abbrechen; eine vergiftete
+        // Sperre enthält daher eine gültige Liste.
+        self.subscribers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<T> Drop for Signal<T> {
+    /// Beendet die asynchronen Abonnements, damit wartende Empfänger `None` erhalten.
+    fn drop(&mut self) {
+        let subscribers = self.subscribers.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for subscriber in &subscribers.entries {
+            if let Subscriber::Async { queue, .. } = subscriber {
+                if let Some(queue) = queue.upgrade() {
+                    queue.close();
+                }
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.subscribers.lock().map_or(0, |subscribers| subscribers.entries.len());
+        f.debug_struct("Signal").field("subscribers", &count).finish()
+    }
+}
+
+/// Die Warteschlange eines asynchronen Abonnements.
+struct AsyncQueue<T> {
+    state: Mutex<AsyncState<T>>,
+}
+
+struct AsyncState<T> {
+    values: VecDeque<T>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+impl<T> Default for AsyncQueue<T> {
+    fn default() -> Self {
+        Self { state: Mutex::new(AsyncState { values: VecDeque::new(), waker: None, closed: false }) }
+    }
+}
+
+impl<T> AsyncQueue<T> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, AsyncState<T>> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn push(&self, value: T) {
+        let mut state = self.lock();
+        state.values.push_back(value);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Der Empfänger eines asynchronen Abonnements, siehe [`Signal::subscribe_async()`].
+///
+/// Funktioniert mit jeder Laufzeitumgebung (z.B. Tokio), da er nur den `Waker` der Aufgabe
+/// verwendet.
+pub struct AsyncReceiver<T> {
+    queue: Arc<AsyncQueue<T>>,
+}
+
+impl<T> AsyncReceiver<T> {
+    /// Wartet auf den nächsten Wert.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn das Signal verworfen wurde und keine Werte mehr anstehen.
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: self }
+    }
+
+    /// Gibt den nächsten Wert zurück, ohne zu warten, oder `None`, wenn keiner ansteht.
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.queue.lock().values.pop_front()
+    }
+}
+
+impl<T> fmt::Debug for AsyncReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncReceiver").field("pending", &self.queue.lock().values.len()).finish()
+    }
+}
+
+/// Das Future von [`AsyncReceiver::recv()`].
+#[derive(Debug)]
+#[must_use = "Futures tun nichts, solange sie nicht abgefragt werden"]
+pub struct Recv<'a, T> {
+    receiver: &'a mut AsyncReceiver<T>,
+}
+
+impl<T> Future for Recv<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.receiver.queue.lock();
+        if let Some(value) = state.values.pop_front() {
+            return Poll::Ready(Some(value));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+    use std::thread;
+
+    /// Führt ein Future auf dem aktuellen Thread aus.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct ThreadWaker(thread::Thread);
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut context = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn test_async_receiver_wakes_and_closes() {
+        let signal = Arc::new(Signal::<u32>::new());
+        let mut even = signal.subscribe_async_filtered(|value| value % 2 == 0);
+
+        let sender = Arc::clone(&signal);
+        let handle = thread::spawn(move || {
+            for value in 1..=4 {
+                sender.emit(value);
+            }
+        });
+        assert_eq!(block_on(even.recv()), Some(2));
+        assert_eq!(block_on(even.recv()), Some(4));
+        handle.join().unwrap();
+
+        drop(signal);
+        assert_eq!(block_on(even.recv()), None);
+    }
+
+    #[test]
+    fn test_dropped_receivers_and_disconnected_callbacks_are_removed() {
+        let signal = Signal::<&'static str>::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let id = signal.connect(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        drop(signal.subscribe());
+        drop(signal.subscribe_async());
+        let kept = signal.subscribe();
+
+        signal.emit("a");
+        assert_eq!(signal.subscriber_count(), 2);
+        assert!(signal.disconnect(id));
+        assert!(!signal.disconnect(id));
+        signal.emit("b");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(kept.try_iter().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_callbacks_may_use_the_signal() {
+        let signal = Arc::new(Signal::<u32>::new());
+        let weak = Arc::downgrade(&signal);
+        let received = signal.subscribe();
+        signal.connect(move |value| {
+            if let (Some(signal), true) = (weak.upgrade(), *value < 3) {
+                signal.emit(value + 1);
+            }
+        });
+
+        signal.emit(1);
+        assert_eq!(received.try_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,204 @@
+//! # Zeitdauern für Konfigurationen (`types::duration`)
+//!
+//! Dieses Untermodul von [`crate::types`] stellt [`Duration`] bereit, eine Zeitdauer, die in
+//! Konfigurationsdateien in lesbarer Form wie `"500ms"`, `"2m"` oder `"1h30m"` angegeben wird.
+//! Sie ist für Einstellungen wie Leerlauf-Timeouts, die Verzögerung der Tastenwiederholung,
+//! Intervalle für automatisches Speichern oder Sicherungspläne gedacht.
+//!
+//! ## Format
+//!
+//! Eine Dauer besteht aus einer oder mehreren Angaben `<Ganzzahl><Einheit>`, die addiert
+//! werden. Zwischen den Angaben sind Leerzeichen erlaubt. Die Einheiten sind:
+//!
+//! - `ms`: Millisekunden
+//! - `s`: Sekunden
+//! - `m`: Minuten
+//! - `h`: Stunden
+//! - `d`: Tage
+//!
+//! Ein einzelnes `"0"` ohne Einheit steht für die Dauer null.
+
+use crate::error::CoreError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Die Einheiten in absteigender Größe, jeweils mit ihrer Länge in Millisekunden.
+const UNITS: &[(&str, u64)] = &[("d", 86_400_000), ("h", 3_600_000), ("m", 60_000), ("s", 1_000), ("ms", 1)];
+
+/// Eine Zeitdauer, die als lesbarer String wie `"1h30m"` geparst und serialisiert wird.
+///
+/// Umschließt [`std::time::Duration`] und lässt sich mit `From`/`Into` in beide Richtungen
+/// umwandeln. Die Genauigkeit der Textdarstellung ist eine Millisekunde; kleinere Anteile
+/// werden bei der Ausgabe abgeschnitten.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::types::Duration;
+/// use std::str::FromStr;
+///
+/// let idle_timeout = Duration::from_str("1h30m").unwrap();
+/// assert_eq!(idle_timeout.as_std(), std::time::Duration::from_secs(5400));
+/// assert_eq!(idle_timeout.to_string(), "1h30m");
+///
+/// let repeat_delay: Duration = "500ms".parse().unwrap();
+/// assert_eq!(repeat_delay, Duration::from_millis(500));
+/// assert!(Duration::from_str("10 Minuten").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Duration(std::time::Duration);
+
+impl Duration {
+    /// Die Dauer null.
+    pub const ZERO: Duration = Duration(std::time::Duration::ZERO);
+
+    /// Erstellt eine `Duration` aus Millisekunden.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(std::time::Duration::from_millis(millis))
+    }
+
+    /// Erstellt eine `Duration` aus Sekunden.
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(std::time::Duration::from_secs(secs))
+    }
+
+    /// Gibt die Dauer als [`std::time::Duration`] zurück.
+    pub const fn as_std(&self) -> std::time::Duration {
+        self.0
+    }
+}
+
+impl From<std::time::Duration> for Duration {
+    fn from(duration: std::time::Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<Duration> for std::time::Duration {
+    fn from(duration: Duration) -> Self {
+        duration.0
+    }
+}
+
+impl fmt::Display for Duration {
+    /// Formatiert die Dauer mit den größtmöglichen Einheiten, z.B. `1h30m` oder `2s500ms`.
+    /// Die Dauer null wird als `0s` ausgegeben.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut remaining = self.0.as_millis();
+        if remaining == 0 {
+            return write!(f, "0s");
+        }
+        for (unit, millis) in UNITS {
+            let count = remaining / u128::from(*millis);
+            if count > 0 {
+                write!(f, "{}{}", count, unit)?;
+                remaining %= u128::from(*millis);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Duration {
+    type Err = CoreError;
+
+    /// Parst eine Dauer im oben beschriebenen Format.
+    ///
+    /// # Fehler
+    /// Gibt `CoreError::DeserializationError` zurück, wenn der String leer ist, eine Angabe
+    /// keine Zahl oder keine bekannte Einheit hat oder die Dauer zu groß ist.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |message: String| CoreError::DeserializationError { format: "Duration".to_string(), message };
+        let input = s.trim();
+        if input == "0" {
+            return Ok(Self::ZERO);
+        }
+        if input.is_empty() {
+            return Err(error("Leere Zeitdauer. Erwartet z.B. '500ms', '2m' oder '1h30m'.".to_string()));
+        }
+
+        let mut total: u64 = 0;
+        let mut rest = input;
+        while !rest.is_empty() {
+            let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            let unit_len = rest[digits..].find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len() - digits);
+            let (number, unit) = (&rest[..digits], &rest[digits..digits + unit_len]);
+            if number.is_empty() {
+                return Err(error(format!("Ungültige Zeitdauer '{}': Zahl erwartet vor '{}'.", s, rest)));
+            }
+            let millis = UNITS.iter().find(|(name, _)| *name == unit).map(|(_, millis)| *millis).ok_or_else(|| {
+                error(format!("Ungültige Zeitdauer '{}': unbekannte Einheit '{}' (erlaubt: ms, s, m, h, d).", s, unit))
+            })?;
+            total = number
+                .parse::<u64>()
+                .ok()
+                .and_then(|count| count.checked_mul(millis))
+                .and_then(|part| total.checked_add(part))
+                .ok_or_else(|| error(format!("Zeitdauer '{}' ist zu groß.", s)))?;
+            rest = rest[digits + unit_len..].trim_start();
+        }
+        Ok(Self::from_millis(total))
+    }
+}
+
+impl Serialize for Duration {
+    /// Serialisiert die Dauer als String im Format von `Display`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Duration {
+    /// Deserialisiert die Dauer aus einem String über `FromStr`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_format() {
+        for (input, millis, formatted) in [
+            ("500ms", 500, "500ms"),
+            ("2m", 120_000, "2m"),
+            ("1h30m", 5_400_000, "1h30m"),
+            ("1h 30m 15s", 5_415_000, "1h30m15s"),
+            ("90s", 90_000, "1m30s"),
+            ("1d", 86_400_000, "1d"),
+            ("0", 0, "0s"),
+            ("0ms", 0, "0s"),
+        ] {
+            let duration = Duration::from_str(input).unwrap();
+            assert_eq!(duration, Duration::from_millis(millis), "{}", input);
+            assert_eq!(duration.to_string(), formatted, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_input() {
+        for input in ["", "10", "ms", "5 minutes", "1.5h", "-1s", "99999999999999999999d"] {
+            assert!(
+                matches!(Duration::from_str(input), Err(CoreError::DeserializationError { .. })),
+                "'{}' should be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde_roundtrip_in_toml() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Settings {
+            idle_timeout: Duration,
+        }
+
+        let settings: Settings = toml::from_str("idle_timeout = \"5m\"").unwrap();
+        assert_eq!(settings.idle_timeout.as_std(), std::time::Duration::from_secs(300));
+        assert_eq!(toml::to_string(&settings).unwrap(), "idle_timeout = \"5m\"\n");
+        assert!(toml::from_str::<Settings>("idle_timeout = \"5 Minuten\"").is_err());
+    }
+}
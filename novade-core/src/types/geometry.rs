@@ -0,0 +1,350 @@
+//! # Geometrische Grundtypen (`types::geometry`)
+//!
+//! Dieses Untermodul von [`crate::types`] stellt generische Typen für Positionen, Größen und
+//! Rechtecke bereit, wie sie z.B. für Fenster, Ausgaben (Monitore) und Zeichenelemente benötigt
+//! werden. Die Typen sind generisch über den Koordinatentyp, damit sowohl logische Koordinaten
+//! (`i32`), Pixelgrößen (`u32`) als auch Fließkommawerte (`f64`) abgebildet werden können.
+//!
+//! ## Hauptkomponenten:
+//!
+//! - [`Point`]: Eine Position mit `x`- und `y`-Koordinate.
+//! - [`Size`]: Eine Größe mit Breite und Höhe.
+//! - [`Rect`]: Ein achsenparalleles Rechteck aus Ursprung (linke obere Ecke) und Größe, mit
+//!   Hilfsfunktionen für Schnittmenge, Vereinigung, Enthaltensein und Skalierung.
+//! - [`Coordinate`]: Das Trait für die zulässigen Koordinatentypen.
+//!
+//! Rechtecke sind halboffen: Die rechte und die untere Kante gehören nicht mehr zum Rechteck.
+//! Zwei aneinandergrenzende Fenster überschneiden sich also nicht.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// Ein Zahlentyp, der als Koordinate in [`Point`], [`Size`] und [`Rect`] verwendet werden kann.
+///
+/// Implementiert für `i32`, `i64`, `u32`, `f32` und `f64`. Der Wert von `Default` muss die
+/// Null des Typs sein.
+pub trait Coordinate:
+    Copy
+    + PartialOrd
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+}
+
+impl Coordinate for i32 {}
+impl Coordinate for i64 {}
+impl Coordinate for u32 {}
+impl Coordinate for f32 {}
+impl Coordinate for f64 {}
+
+/// Gibt den kleineren der beiden Werte zurück (`PartialOrd` bietet kein `min`).
+fn min<T: Coordinate>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+/// Gibt den größeren der beiden Werte zurück.
+fn max<T: Coordinate>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+/// Eine Position im zweidimensionalen Raum.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::types::Point;
+///
+/// let p = Point::new(10, 20) + Point::new(5, -5);
+/// assert_eq!(p, Point::new(15, 15));
+/// assert_eq!(p.scale(2), Point::new(30, 30));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Point<T> {
+    /// Die horizontale Koordinate, nach rechts wachsend.
+    pub x: T,
+    /// Die vertikale Koordinate, nach unten wachsend.
+    pub y: T,
+}
+
+impl<T: Coordinate> Point<T> {
+    /// Erstellt einen neuen `Point`.
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Multipliziert beide Koordinaten mit `factor`, z.B. um logische in physische
+    /// Koordinaten umzurechnen.
+    pub fn scale(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+
+    /// Teilt beide Koordinaten durch `factor`; die Umkehrung von [`Point::scale()`].
+    pub fn downscale(self, factor: T) -> Self {
+        Self::new(self.x / factor, self.y / factor)
+    }
+}
+
+impl<T: Coordinate> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Coordinate> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    /// Formatiert den Punkt als `(x, y)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Eine Größe mit Breite und Höhe.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::types::Size;
+///
+/// let size = Size::new(3840u32, 2160);
+/// assert_eq!(size.downscale(2), Size::new(1920, 1080));
+/// assert_eq!(size.to_string(), "3840x2160");
+/// assert!(Size::new(0, 10).is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Size<T> {
+    /// Die Breite.
+    pub width: T,
+    /// Die Höhe.
+    pub height: T,
+}
+
+impl<T: Coordinate> Size<T> {
+    /// Erstellt eine neue `Size`.
+    pub fn new(width: T, height: T) -> Self {
+        Self { width, height }
+    }
+
+    /// Gibt `true` zurück, wenn Breite oder Höhe null (oder negativ) ist.
+    pub fn is_empty(&self) -> bool {
+        self.width <= T::default() || self.height <= T::default()
+    }
+
+    /// Die Fläche (Breite mal Höhe).
+    pub fn area(&self) -> T {
+        self.width * self.height
+    }
+
+    /// Multipliziert Breite und Höhe mit `factor`.
+    pub fn scale(self, factor: T) -> Self {
+        Self::new(self.width * factor, self.height * factor)
+    }
+
+    /// Teilt Breite und Höhe durch `factor`; die Umkehrung von [`Size::scale()`].
+    pub fn downscale(self, factor: T) -> Self {
+        Self::new(self.width / factor, self.height / factor)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Size<T> {
+    /// Formatiert die Größe als `BREITExHÖHE`, z.B. `1920x1080`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// Ein achsenparalleles Rechteck aus Ursprung (linke obere Ecke) und Größe.
+///
+/// Das Rechteck ist halboffen: Ein Punkt liegt darin, wenn
+/// `x <= p.x < x + width` und `y <= p.y < y + height` gilt.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::types::{Point, Rect};
+///
+/// let window = Rect::new(100, 100, 800, 600);
+/// let output = Rect::new(0, 0, 1920, 1080);
+///
+/// assert!(output.contains_rect(&window));
+/// assert!(window.contains(Point::new(899, 699)));
+/// assert!(!window.contains(Point::new(900, 699)));
+///
+/// let right = Rect::new(1920, 0, 1280, 1024);
+/// assert_eq!(output.intersection(&right), None);
+/// assert_eq!(output.union(&right), Rect::new(0, 0, 3200, 1080));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Rect<T> {
+    /// Die linke obere Ecke.
+    pub origin: Point<T>,
+    /// Die Größe.
+    pub size: Size<T>,
+}
+
+impl<T: Coordinate> Rect<T> {
+    /// Erstellt ein neues `Rect` aus Position und Größe.
+    pub fn new(x: T, y: T, width: T, height: T) -> Self {
+        Self::from_parts(Point::new(x, y), Size::new(width, height))
+    }
+
+    /// Erstellt ein neues `Rect` aus Ursprung und Größe.
+    pub fn from_parts(origin: Point<T>, size: Size<T>) -> Self {
+        Self { origin, size }
+    }
+
+    /// Die linke Kante.
+    pub fn left(&self) -> T {
+        self.origin.x
+    }
+
+    /// Die obere Kante.
+    pub fn top(&self) -> T {
+        self.origin.y
+    }
+
+    /// Die rechte Kante (gehört nicht mehr zum Rechteck).
+    pub fn right(&self) -> T {
+        self.origin.x + self.size.width
+    }
+
+    /// Die untere Kante (gehört nicht mehr zum Rechteck).
+    pub fn bottom(&self) -> T {
+        self.origin.y + self.size.height
+    }
+
+    /// Gibt `true` zurück, wenn das Rechteck keine Fläche hat.
+    pub fn is_empty(&self) -> bool {
+        self.size.is_empty()
+    }
+
+    /// Gibt `true` zurück, wenn `point` im Rechteck liegt.
+    pub fn contains(&self, point: Point<T>) -> bool {
+        point.x >= self.left() && point.x < self.right() && point.y >= self.top() && point.y < self.bottom()
+    }
+
+    /// Gibt `true` zurück, wenn `other` vollständig im Rechteck liegt.
+    ///
+    /// Ein leeres `other` ist in jedem nichtleeren Rechteck enthalten.
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        if other.is_empty() {
+            return !self.is_empty();
+        }
+        other.left() >= self.left()
+            && other.right() <= self.right()
+            && other.top() >= self.top()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Gibt `true` zurück, wenn sich die beiden Rechtecke überschneiden.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Die Schnittmenge der beiden Rechtecke.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn sich die Rechtecke nicht überschneiden (auch wenn sie sich nur an einer
+    /// Kante berühren).
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let left = max(self.left(), other.left());
+        let top = max(self.top(), other.top());
+        let right = min(self.right(), other.right());
+        let bottom = min(self.bottom(), other.bottom());
+        if left < right && top < bottom {
+            Some(Self::new(left, top, right - left, bottom - top))
+        } else {
+            None
+        }
+    }
+
+    /// Das kleinste Rechteck, das beide Rechtecke enthält.
+    ///
+    /// Leere Rechtecke werden ignoriert; sind beide leer, wird `self` zurückgegeben.
+    pub fn union(&self, other: &Self) -> Self {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
+        }
+        let left = min(self.left(), other.left());
+        let top = min(self.top(), other.top());
+        let right = max(self.right(), other.right());
+        let bottom = max(self.bottom(), other.bottom());
+        Self::new(left, top, right - left, bottom - top)
+    }
+
+    /// Verschiebt das Rechteck um `offset`.
+    pub fn translate(self, offset: Point<T>) -> Self {
+        Self::from_parts(self.origin + offset, self.size)
+    }
+
+    /// Multipliziert Ursprung und Größe mit `factor`, z.B. um ein Rechteck in logischen
+    /// Koordinaten in physische Pixel umzurechnen.
+    pub fn scale(self, factor: T) -> Self {
+        Self::from_parts(self.origin.scale(factor), self.size.scale(factor))
+    }
+
+    /// Teilt Ursprung und Größe durch `factor`; die Umkehrung von [`Rect::scale()`].
+    pub fn downscale(self, factor: T) -> Self {
+        Self::from_parts(self.origin.downscale(factor), self.size.downscale(factor))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Rect<T> {
+    /// Formatiert das Rechteck im X11-Geometrieformat `BREITExHÖHE+X+Y`, z.B. `800x600+100+50`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}+{}+{}", self.size, self.origin.x, self.origin.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection_and_touching_edges() {
+        let a = Rect::new(0, 0, 100, 100);
+        let b = Rect::new(50, -20, 100, 100);
+        assert_eq!(a.intersection(&b), Some(Rect::new(50, 0, 50, 80)));
+        assert!(a.intersects(&b));
+
+        // Aneinandergrenzende Rechtecke überschneiden sich nicht.
+        let c = Rect::new(100, 0, 100, 100);
+        assert_eq!(a.intersection(&c), None);
+        assert!(!a.intersects(&c));
+    }
+
+    #[test]
+    fn test_union_ignores_empty_rects() {
+        let a = Rect::new(10, 10, 20, 20);
+        let empty = Rect::new(-500, -500, 0, 0);
+        assert_eq!(a.union(&empty), a);
+        assert_eq!(empty.union(&a), a);
+        assert_eq!(a.union(&Rect::new(-10, 40, 5, 5)), Rect::new(-10, 10, 40, 35));
+    }
+
+    #[test]
+    fn test_contains_rect_and_scaling() {
+        let output = Rect::new(0u32, 0, 1920, 1080);
+        assert!(output.contains_rect(&Rect::new(0, 0, 1920, 1080)));
+        assert!(!output.contains_rect(&Rect::new(1, 0, 1920, 1080)));
+        assert_eq!(output.scale(2).size, Size::new(3840, 2160));
+
+        let logical = Rect::new(10.0, 20.0, 100.0, 50.0).scale(1.5);
+        assert_eq!(logical, Rect::new(15.0, 30.0, 150.0, 75.0));
+        assert_eq!(logical.downscale(1.5), Rect::new(10.0, 20.0, 100.0, 50.0));
+        assert_eq!(logical.translate(Point::new(-15.0, 0.0)).origin, Point::new(0.0, 30.0));
+        assert_eq!(Rect::new(100, 50, 800, 600).to_string(), "800x600+100+50");
+    }
+}
@@ -0,0 +1,915 @@
+//! # Grundlegende Datentypen in `novade-core`
+//!
+//! Dieses Modul definiert eine Reihe von grundlegenden Datentypen, die schichtübergreifend
+//! im NovaDE-System verwendet werden. Dazu gehören Identifikatoren, Versionierung,
+//! Zeitstempel und Ressourcenbezeichner.
+//!
+//! ## Wichtige Typen:
+//!
+//! - [`NovaId`]: Ein eindeutiger Identifikator (UUID v4) für Entitäten.
+//! - [`Version`]: Repräsentiert eine semantische Version (Major, Minor, Patch, optional mit
+//!   Pre-Release- und Build-Angabe) mit Kompatibilitätsprüfung.
+//! - [`Timestamp`]: Ein Zeitstempel im UTC-Format.
+//! - [`MonotonicInstant`]: Ein Zeitpunkt der monotonen Uhr für Zeitmessungen (z.B. Frames,
+//!   Eingabeereignisse), der bei Änderungen der Systemzeit nicht springt.
+//! - [`ResourceIdentifier`]: Ein Enum zur eindeutigen Identifizierung verschiedener
+//!   Arten von Ressourcen (Dateien, Dienste, Komponenten etc.).
+//! - [`Point`], [`Size`], [`Rect`]: Generische geometrische Typen für Positionen, Größen und
+//!   Rechtecke, z.B. von Fenstern und Ausgaben. Siehe [`geometry`].
+//! - [`Duration`]: Eine Zeitdauer für Konfigurationen, die als `"500ms"` oder `"1h30m"`
+//!   angegeben wird. Siehe [`duration`].
+//!
+//! Diese Typen sind oft mit `serde` für Serialisierung/Deserialisierung und `FromStr`
+//! für das Parsen aus Strings ausgestattet.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::path::PathBuf;
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::error::CoreError; // CoreResult entfernt
+
+pub mod duration;
+pub mod geometry;
+
+pub use duration::Duration;
+pub use geometry::{Coordinate, Point, Rect, Size};
+
+/// Ein eindeutiger Identifikator für Entitäten im NovaDE-System.
+///
+/// Basiert intern auf einem UUID v4, um globale Eindeutigkeit zu gewährleisten.
+/// `NovaId` implementiert `Default`, `Display`, `FromStr` sowie `Serialize` und `Deserialize`
+/// für eine einfache Handhabung.
+///
+/// Für Einträge, die in zeitlicher Reihenfolge gespeichert und gelesen werden (z.B.
+/// Audit-Protokolle), erzeugt [`NovaId::new_v7()`] zeitlich sortierbare IDs (UUID v7). Die
+/// Ordnung von `NovaId` ist die bytweise Ordnung der UUID; bei v7-IDs entspricht sie der
+/// Erzeugungsreihenfolge (auf die Millisekunde genau).
+///
+/// Neben der Standarddarstellung gibt es mit [`NovaId::short()`] eine kompakte Base58-Form
+/// mit 22 Zeichen, z.B. für Dateinamen oder Ausgaben auf der Kommandozeile.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::types::NovaId;
+/// use std::str::FromStr;
+///
+/// // Eine neue, zufällige ID erstellen
+/// let id1 = NovaId::new();
+/// println!("Neue ID: {}", id1);
+///
+/// // Eine ID aus einem String parsen
+/// let id_str = "f47ac10b-58cc-4372-a567-0e02b2c3d479";
+/// let id2 = NovaId::from_str(id_str).unwrap();
+/// assert_ne!(id1, id2); // id1 ist zufällig erzeugt
+/// assert_eq!(id2.to_string(), id_str);
+///
+/// // Kompakte Darstellung
+/// assert_eq!(NovaId::from_short(&id2.short()).unwrap(), id2);
+///
+/// // Zeitlich sortierbare IDs
+/// let earlier = NovaId::new_v7();
+/// std::thread::sleep(std::time::Duration::from_millis(2));
+/// assert!(earlier < NovaId::new_v7());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct NovaId(Uuid);
+
+/// Das Base58-Alphabet (wie bei Bitcoin, ohne die verwechselbaren Zeichen `0`, `O`, `I`, `l`).
+/// Die Zeichen sind aufsteigend nach ASCII sortiert, daher sortieren die Kurzformen wie die IDs.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Die Länge der Kurzform; 58^22 ist größer als 2^128.
+const SHORT_ID_LEN: usize = 22;
+
+impl NovaId {
+    /// Erstellt eine neue, zufällige `NovaId` (UUID v4).
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Erstellt eine neue, zeitlich sortierbare `NovaId` (UUID v7).
+    ///
+    /// Die ersten 48 Bit enthalten den Zeitpunkt der Erzeugung in Millisekunden, der Rest ist
+    /// zufällig. Später erzeugte IDs sind größer, sodass Einträge mit solchen IDs als
+    /// Schlüssel in einem geordneten Speicher nahe beieinander und in zeitlicher Reihenfolge
+    /// liegen. Innerhalb derselben Millisekunde ist die Reihenfolge nicht garantiert.
+    pub fn new_v7() -> Self {
+        Self(Uuid::now_v7())
+    }
+
+    /// Erstellt eine `NovaId` aus einem bestehenden `Uuid`.
+    ///
+    /// # Parameter
+    /// * `uuid`: Das `Uuid`, das für diese `NovaId` verwendet werden soll.
+    pub fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Gibt eine Referenz auf das zugrundeliegende `Uuid` zurück.
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.0
+    }
+
+    /// Gibt die kompakte Base58-Darstellung der ID zurück.
+    ///
+    /// Die Kurzform hat immer 22 Zeichen (mit führenden `1` aufgefüllt), sodass ihre
+    /// lexikografische Ordnung der Ordnung der IDs entspricht.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_core::types::NovaId;
+    /// use std::str::FromStr;
+    ///
+    /// let id = NovaId::from_str("f47ac10b-58cc-4372-a567-0e02b2c3d479").unwrap();
+    /// assert_eq!(id.short(), "XBz3jkFgmHZpHEmghHCsXn");
+    /// assert_eq!(NovaId::from_short("XBz3jkFgmHZpHEmghHCsXn").unwrap(), id);
+    /// assert!(NovaId::from_short("0Bz3jkFgmHZpHEmghHCsXn").is_err()); // '0' ist nicht Base58
+    /// assert!(NovaId::from_short("zzzzzzzzzzzzzzzzzzzzzz").is_err()); // größer als 128 Bit
+    /// ```
+    pub fn short(&self) -> String {
+        let mut value = self.0.as_u128();
+        let mut encoded = [BASE58_ALPHABET[0]; SHORT_ID_LEN];
+        for digit in encoded.iter_mut().rev() {
+            *digit = BASE58_ALPHABET[(value % 58) as usize];
+            value /= 58;
+        }
+        encoded.iter().map(|&b| b as char).collect()
+    }
+
+    /// Parst die Kurzform aus [`NovaId::short()`].
+    ///
+    /// # Fehler
+    /// Gibt `CoreError::DeserializationError` zurück, wenn der String nicht aus 22 Zeichen des
+    /// Base58-Alphabets besteht oder keinen gültigen 128-Bit-Wert ergibt.
+    pub fn from_short(s: &str) -> Result<Self, CoreError> {
+        let error = |message: String| CoreError::DeserializationError { format: "NovaId".to_string(), message };
+        if s.len() != SHORT_ID_LEN {
+            return Err(error(format!("Ungültige Kurzform '{}': Erwartet {} Zeichen.", s, SHORT_ID_LEN)));
+        }
+        let mut value: u128 = 0;
+        for c in s.bytes() {
+            let digit = BASE58_ALPHABET
+                .iter()
+                .position(|&b| b == c)
+                .ok_or_else(|| error(format!("Ungültige Kurzform '{}': Zeichen '{}' ist nicht Base58.", s, c as char)))?;
+            value = value
+                .checked_mul(58)
+                .and_then(|v| v.checked_add(digit as u128))
+                .ok_or_else(|| error(format!("Ungültige Kurzform '{}': Wert ist größer als 128 Bit.", s)))?;
+        }
+        Ok(Self(Uuid::from_u128(value)))
+    }
+}
+
+impl Default for NovaId {
+    /// Erstellt eine neue, zufällige `NovaId` als Standardwert.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for NovaId {
+    /// Formatiert die `NovaId` als String (die Standard-UUID-Darstellung).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for NovaId {
+    type Err = CoreError;
+    /// Versucht, eine `NovaId` aus einem String zu parsen.
+    ///
+    /// Der String muss eine gültige UUID-Repräsentation sein.
+    ///
+    /// # Fehler
+    /// Gibt `CoreError::DeserializationError` zurück, wenn der String keine gültige UUID ist.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::from_str(s)
+            .map(NovaId)
+            .map_err(|e| CoreError::DeserializationError {
+                format: "NovaId".to_string(),
+                message: format!("Ungültige UUID-Zeichenkette '{}': {}", s, e),
+            })
+    }
+}
+
+/// Repräsentiert eine semantische Version (Major, Minor, Patch) nach
+/// [Semantic Versioning 2.0.0](https://semver.org), optional mit Pre-Release- und
+/// Build-Angabe (`1.2.3-beta.1+20240101`).
+///
+/// Implementiert `PartialOrd` und `Ord` für Versionsvergleiche sowie `Display`, `FromStr`,
+/// `Serialize` und `Deserialize`. Die Ordnung folgt der Vorrangregel von SemVer: Eine
+/// Pre-Release-Version ist kleiner als die zugehörige Release-Version, und Pre-Release-Angaben
+/// werden Teil für Teil verglichen (numerische Teile als Zahl). Die Build-Angabe hat keinen
+/// Vorrang; sie entscheidet nur zwischen sonst gleichen Versionen, damit die Ordnung zu `Eq`
+/// passt.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::types::Version;
+/// use std::str::FromStr;
+///
+/// let v1 = Version::new(1, 2, 3);
+/// let v2 = Version::from_str("1.2.4").unwrap();
+/// assert!(v2 > v1);
+/// assert_eq!(v1.to_string(), "1.2.3");
+///
+/// let beta = Version::from_str("1.3.0-beta.2+build.7").unwrap();
+/// assert_eq!(beta.pre.as_deref(), Some("beta.2"));
+/// assert!(beta < Version::new(1, 3, 0));
+/// assert!(Version::from_str("1.3.0-beta.11").unwrap() > beta);
+/// assert_eq!(beta.to_string(), "1.3.0-beta.2+build.7");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Version {
+    /// Die Major-Komponente der Version. Inkompatible API-Änderungen.
+    pub major: u16,
+    /// Die Minor-Komponente der Version. Rückwärtskompatible neue Funktionalität.
+    pub minor: u16,
+    /// Die Patch-Komponente der Version. Rückwärtskompatible Fehlerbehebungen.
+    pub patch: u16,
+    /// Die Pre-Release-Angabe ohne führendes `-` (z.B. `"beta.2"`), `None` für ein Release.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre: Option<String>,
+    /// Die Build-Metadaten ohne führendes `+` (z.B. `"build.7"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<String>,
+}
+
+impl Version {
+    /// Erstellt eine neue Release-`Version` ohne Pre-Release- und Build-Angabe.
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self { major, minor, patch, pre: None, build: None }
+    }
+
+    /// Setzt die Pre-Release-Angabe (ohne führendes `-`).
+    pub fn with_pre(mut self, pre: impl Into<String>) -> Self {
+        self.pre = Some(pre.into());
+        self
+    }
+
+    /// Setzt die Build-Metadaten (ohne führendes `+`).
+    pub fn with_build(mut self, build: impl Into<String>) -> Self {
+        self.build = Some(build.into());
+        self
+    }
+
+    /// Gibt `true` zurück, wenn es sich um eine Pre-Release-Version handelt.
+    pub fn is_pre_release(&self) -> bool {
+        self.pre.is_some()
+    }
+
+    /// Prüft, ob diese Version eine Anforderung an `required` erfüllt, d.h. ob sie gleich oder
+    /// neuer ist, ohne inkompatible Änderungen zu enthalten.
+    ///
+    /// Es gelten die Regeln von Cargo für `^required`: Die Major-Version muss übereinstimmen;
+    /// bei Major `0` zusätzlich die Minor-Version und bei `0.0` auch die Patch-Version. Eine
+    /// Pre-Release-Version erfüllt eine Anforderung nur, wenn diese selbst eine Pre-Release
+    /// derselben Major-, Minor- und Patch-Version ist. Build-Metadaten werden ignoriert.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_core::types::Version;
+    ///
+    /// let required = Version::new(1, 2, 0);
+    /// assert!(Version::new(1, 4, 1).is_compatible_with(&required));
+    /// assert!(!Version::new(1, 1, 9).is_compatible_with(&required));
+    /// assert!(!Version::new(2, 0, 0).is_compatible_with(&required));
+    /// assert!(!Version::new(0, 3, 0).is_compatible_with(&Version::new(0, 2, 0)));
+    /// assert!(!Version::new(1, 3, 0).with_pre("rc.1").is_compatible_with(&required));
+    /// ```
+    pub fn is_compatible_with(&self, required: &Version) -> bool {
+        if self.major != required.major
+            || (required.major == 0 && self.minor != required.minor)
+            || (required.major == 0 && required.minor == 0 && self.patch != required.patch)
+        {
+            return false;
+        }
+        if self.is_pre_release()
+            && (!required.is_pre_release()
+                || (self.major, self.minor, self.patch) != (required.major, required.minor, required.patch))
+        {
+            return false;
+        }
+        self.cmp_precedence(required) != Ordering::Less
+    }
+
+    /// Vergleicht zwei Versionen nach dem Vorrang von SemVer, also ohne die Build-Metadaten.
+    pub fn cmp_precedence(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_pre_release(a, b),
+            })
+    }
+}
+
+/// Vergleicht zwei Pre-Release-Angaben Teil für Teil: Numerische Teile werden als Zahl
+/// verglichen und sind kleiner als alphanumerische; bei gleichem Anfang ist die kürzere Angabe
+/// kleiner.
+fn compare_pre_release(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        let ordering = match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    /// Vergleicht nach [`Version::cmp_precedence()`] und bei Gleichheit nach den
+    /// Build-Metadaten (Versionen ohne Build-Angabe zuerst).
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_precedence(other).then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl fmt::Display for Version {
+    /// Formatiert die `Version` als String im Format "major.minor.patch[-pre][+build]".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Version {
+    type Err = CoreError;
+    /// Versucht, eine `Version` aus einem String im Format "major.minor.patch[-pre][+build]"
+    /// zu parsen.
+    ///
+    /// # Fehler
+    /// Gibt `CoreError::DeserializationError` zurück, wenn das Format ungültig ist,
+    /// die Komponenten nicht als Zahlen geparst werden können oder die Pre-Release- bzw.
+    /// Build-Angabe leere Teile oder andere Zeichen als `[0-9A-Za-z-]` enthält.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, build) = match s.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (s, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() != 3 {
+            return Err(CoreError::DeserializationError{
+                format: "Version".to_string(),
+                message: format!("Ungültiges Versionsformat: '{}'. Erwartet 'major.minor.patch'.", s)
+            });
+        }
+        let major = parts[0].parse::<u16>().map_err(|_| CoreError::DeserializationError{
+            format: "Version".to_string(),
+            message: format!("Ungültige Major-Version: '{}'", parts[0])
+        })?;
+        let minor = parts[1].parse::<u16>().map_err(|_| CoreError::DeserializationError{
+            format: "Version".to_string(),
+            message: format!("Ungültige Minor-Version: '{}'", parts[1])
+        })?;
+        let patch = parts[2].parse::<u16>().map_err(|_| CoreError::DeserializationError{
+            format: "Version".to_string(),
+            message: format!("Ungültige Patch-Version: '{}'", parts[2])
+        })?;
+        let mut version = Version::new(major, minor, patch);
+        if let Some(pre) = pre {
+            version.pre = Some(parse_version_identifiers(pre, "Pre-Release-Angabe", true)?);
+        }
+        if let Some(build) = build {
+            version.build = Some(parse_version_identifiers(build, "Build-Angabe", false)?);
+        }
+        Ok(version)
+    }
+}
+
+/// Prüft eine Pre-Release- oder Build-Angabe: durch Punkte getrennte, nichtleere Teile aus
+/// `[0-9A-Za-z-]`. Numerische Pre-Release-Teile dürfen keine führenden Nullen haben.
+fn parse_version_identifiers(value: &str, what: &str, numeric_without_leading_zero: bool) -> Result<String, CoreError> {
+    let invalid = value.split('.').find(|part| {
+        part.is_empty()
+            || !part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            || (numeric_without_leading_zero
+                && part.len() > 1
+                && part.starts_with('0')
+                && part.chars().all(|c| c.is_ascii_digit()))
+    });
+    match invalid {
+        Some(part) => Err(CoreError::DeserializationError {
+            format: "Version".to_string(),
+            message: format!("Ungültige {} '{}': Teil '{}' ist nicht erlaubt.", what, value, part),
+        }),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Ein Zeitstempel im UTC-Format, basierend auf `chrono::DateTime<Utc>`.
+///
+/// Implementiert `Default` (setzt auf `Utc::now()`), `Display` (RFC3339-Format), `FromStr`,
+/// `Serialize` und `Deserialize`. Mit `+` und `-` lässt sich ein Zeitstempel um eine
+/// [`std::time::Duration`] oder [`Duration`] verschieben.
+///
+/// Ein `Timestamp` folgt der Systemzeit und kann springen, wenn diese umgestellt wird. Für das
+/// Messen von Zeitabständen innerhalb des Prozesses ist [`MonotonicInstant`] besser geeignet.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::types::Timestamp;
+/// use std::str::FromStr;
+///
+/// let now = Timestamp::now();
+/// println!("Aktueller Zeitstempel: {}", now);
+///
+/// let rfc_str = "2023-10-26T07:30:00+00:00";
+/// let ts_from_str = Timestamp::from_str(rfc_str).unwrap();
+/// assert_eq!(ts_from_str.to_string(), rfc_str);
+/// // Die Kurzform mit `Z` wird ebenfalls akzeptiert.
+/// assert_eq!(Timestamp::from_str("2023-10-26T07:30:00Z").unwrap(), ts_from_str);
+///
+/// // Rechnen mit Zeitstempeln
+/// let later = ts_from_str.clone() + std::time::Duration::from_secs(90);
+/// assert_eq!(later.to_string(), "2023-10-26T07:31:30+00:00");
+/// assert_eq!(later.duration_since(&ts_from_str), std::time::Duration::from_secs(90));
+/// assert_eq!(Timestamp::from_unix_millis(later.unix_millis()), Some(later));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct Timestamp(DateTime<Utc>);
+
+impl Timestamp {
+    /// Erstellt einen neuen `Timestamp` mit der aktuellen UTC-Zeit.
+    pub fn now() -> Self {
+        Self(Utc::now())
+    }
+
+    /// Erstellt einen `Timestamp` aus einem gegebenen `chrono::DateTime<Utc>`.
+    pub fn from_datetime(dt: DateTime<Utc>) -> Self {
+        Self(dt)
+    }
+
+    /// Gibt eine Referenz auf das zugrundeliegende `chrono::DateTime<Utc>` zurück.
+    pub fn as_datetime(&self) -> &DateTime<Utc> {
+        &self.0
+    }
+
+    /// Erstellt einen `Timestamp` aus Millisekunden seit der Unix-Epoche.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn der Wert außerhalb des darstellbaren Bereichs liegt.
+    pub fn from_unix_millis(millis: i64) -> Option<Self> {
+        DateTime::from_timestamp_millis(millis).map(Self)
+    }
+
+    /// Gibt die Millisekunden seit der Unix-Epoche zurück (negativ für frühere Zeitpunkte).
+    pub fn unix_millis(&self) -> i64 {
+        self.0.timestamp_millis()
+    }
+
+    /// Gibt die seit diesem Zeitstempel vergangene Zeit zurück.
+    ///
+    /// Liegt der Zeitstempel in der Zukunft (z.B. nach dem Zurückstellen der Systemzeit),
+    /// wird null zurückgegeben.
+    pub fn elapsed(&self) -> std::time::Duration {
+        Self::now().duration_since(self)
+    }
+
+    /// Gibt die Zeit zurück, die von `earlier` bis zu diesem Zeitstempel vergangen ist.
+    ///
+    /// Liegt `earlier` nach diesem Zeitstempel, wird null zurückgegeben.
+    pub fn duration_since(&self, earlier: &Timestamp) -> std::time::Duration {
+        (self.0 - earlier.0).to_std().unwrap_or_default()
+    }
+
+    /// Verschiebt den Zeitstempel um `duration` in die Zukunft.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn das Ergebnis außerhalb des darstellbaren Bereichs liegt.
+    pub fn checked_add(&self, duration: std::time::Duration) -> Option<Self> {
+        chrono::Duration::from_std(duration).ok().and_then(|d| self.0.checked_add_signed(d)).map(Self)
+    }
+
+    /// Verschiebt den Zeitstempel um `duration` in die Vergangenheit.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn das Ergebnis außerhalb des darstellbaren Bereichs liegt.
+    pub fn checked_sub(&self, duration: std::time::Duration) -> Option<Self> {
+        chrono::Duration::from_std(duration).ok().and_then(|d| self.0.checked_sub_signed(d)).map(Self)
+    }
+}
+
+impl Add<std::time::Duration> for Timestamp {
+    type Output = Timestamp;
+
+    /// # Panics
+    /// Wenn das Ergebnis außerhalb des darstellbaren Bereichs liegt, siehe
+    /// [`Timestamp::checked_add()`].
+    fn add(self, duration: std::time::Duration) -> Timestamp {
+        self.checked_add(duration).expect("Überlauf beim Addieren einer Dauer zu einem Timestamp")
+    }
+}
+
+impl Sub<std::time::Duration> for Timestamp {
+    type Output = Timestamp;
+
+    /// # Panics
+    /// Wenn das Ergebnis außerhalb des darstellbaren Bereichs liegt, siehe
+    /// [`Timestamp::checked_sub()`].
+    fn sub(self, duration: std::time::Duration) -> Timestamp {
+        self.checked_sub(duration).expect("Überlauf beim Subtrahieren einer Dauer von einem Timestamp")
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, duration: Duration) -> Timestamp {
+        self + duration.as_std()
+    }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, duration: Duration) -> Timestamp {
+        self - duration.as_std()
+    }
+}
+
+impl AddAssign<std::time::Duration> for Timestamp {
+    fn add_assign(&mut self, duration: std::time::Duration) {
+        *self = self.clone() + duration;
+    }
+}
+
+impl SubAssign<std::time::Duration> for Timestamp {
+    fn sub_assign(&mut self, duration: std::time::Duration) {
+        *self = self.clone() - duration;
+    }
+}
+
+impl Default for Timestamp {
+    /// Erstellt einen neuen `Timestamp` mit der aktuellen UTC-Zeit als Standardwert.
+    fn default() -> Self {
+        Self::now()
+    }
+}
+
+impl fmt::Display for Timestamp {
+    /// Formatiert den `Timestamp` als String im RFC3339-Format.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_rfc3339())
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = CoreError;
+    /// Versucht, einen `Timestamp` aus einem String im RFC3339-Format zu parsen.
+    ///
+    /// # Fehler
+    /// Gibt `CoreError::DeserializationError` zurück, wenn der String kein gültiges
+    /// RFC3339-Datum darstellt.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|dt| Timestamp(dt.with_timezone(&Utc)))
+            .map_err(|e| CoreError::DeserializationError {
+                format: "Timestamp".to_string(),
+                message: format!("Ungültiges RFC3339 Timestamp-Format '{}': {}", s, e),
+            })
+    }
+}
+
+/// Ein Zeitpunkt der monotonen Uhr, basierend auf [`std::time::Instant`].
+///
+/// Anders als [`Timestamp`] springt ein `MonotonicInstant` nicht, wenn die Systemzeit
+/// umgestellt wird (z.B. durch NTP oder den Benutzer), und eignet sich daher für Frame-Zeiten,
+/// Zeitstempel von Eingabeereignissen, Timeouts und Leistungsmessungen. Er hat keinen Bezug zur
+/// Uhrzeit und ist nur innerhalb desselben Prozesses aussagekräftig; er wird deshalb nicht
+/// serialisiert.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::types::MonotonicInstant;
+/// use std::time::Duration;
+///
+/// let frame_start = MonotonicInstant::now();
+/// let deadline = frame_start + Duration::from_millis(16);
+/// assert_eq!(deadline - frame_start, Duration::from_millis(16));
+/// assert_eq!(frame_start.duration_since(deadline), Duration::ZERO);
+/// assert!(frame_start.elapsed() < Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MonotonicInstant(std::time::Instant);
+
+impl MonotonicInstant {
+    /// Gibt den aktuellen Zeitpunkt der monotonen Uhr zurück.
+    pub fn now() -> Self {
+        Self(std::time::Instant::now())
+    }
+
+    /// Gibt den zugrundeliegenden [`std::time::Instant`] zurück.
+    pub fn as_std(&self) -> std::time::Instant {
+        self.0
+    }
+
+    /// Gibt die seit diesem Zeitpunkt vergangene Zeit zurück.
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.0.elapsed()
+    }
+
+    /// Gibt die Zeit zurück, die von `earlier` bis zu diesem Zeitpunkt vergangen ist, oder null,
+    /// wenn `earlier` später liegt.
+    pub fn duration_since(&self, earlier: MonotonicInstant) -> std::time::Duration {
+        self.0.saturating_duration_since(earlier.0)
+    }
+
+    /// Verschiebt den Zeitpunkt um `duration` in die Zukunft.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn das Ergebnis nicht darstellbar ist.
+    pub fn checked_add(&self, duration: std::time::Duration) -> Option<Self> {
+        self.0.checked_add(duration).map(Self)
+    }
+
+    /// Verschiebt den Zeitpunkt um `duration` in die Vergangenheit.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn das Ergebnis nicht darstellbar ist (z.B. vor dem Start der Uhr).
+    pub fn checked_sub(&self, duration: std::time::Duration) -> Option<Self> {
+        self.0.checked_sub(duration).map(Self)
+    }
+}
+
+impl From<std::time::Instant> for MonotonicInstant {
+    fn from(instant: std::time::Instant) -> Self {
+        Self(instant)
+    }
+}
+
+impl From<MonotonicInstant> for std::time::Instant {
+    fn from(instant: MonotonicInstant) -> Self {
+        instant.0
+    }
+}
+
+impl Add<std::time::Duration> for MonotonicInstant {
+    type Output = MonotonicInstant;
+
+    fn add(self, duration: std::time::Duration) -> MonotonicInstant {
+        Self(self.0 + duration)
+    }
+}
+
+impl Sub<std::time::Duration> for MonotonicInstant {
+    type Output = MonotonicInstant;
+
+    fn sub(self, duration: std::time::Duration) -> MonotonicInstant {
+        Self(self.0 - duration)
+    }
+}
+
+impl Sub for MonotonicInstant {
+    type Output = std::time::Duration;
+
+    /// Wie [`MonotonicInstant::duration_since()`]: null, wenn `earlier` später liegt.
+    fn sub(self, earlier: MonotonicInstant) -> std::time::Duration {
+        self.duration_since(earlier)
+    }
+}
+
+impl AddAssign<std::time::Duration> for MonotonicInstant {
+    fn add_assign(&mut self, duration: std::time::Duration) {
+        self.0 += duration;
+    }
+}
+
+impl SubAssign<std::time::Duration> for MonotonicInstant {
+    fn sub_assign(&mut self, duration: std::time::Duration) {
+        self.0 -= duration;
+    }
+}
+
+
+/// Identifiziert eine Ressource innerhalb des NovaDE-Systems.
+///
+/// Dieses Enum dient dazu, verschiedene Arten von Ressourcen (wie Dateien, Dienste,
+/// interne Komponenten oder URLs) eindeutig zu bezeichnen und zu referenzieren.
+/// Es implementiert `Display` für eine menschenlesbare Darstellung und `FromStr`, das diese
+/// Darstellung wieder einliest, sodass Bezeichner z.B. in Konfigurationsdateien stehen können.
+///
+/// # Varianten
+/// - `File(PathBuf)`: Eine Datei im Dateisystem.
+/// - `Directory(PathBuf)`: Ein Verzeichnis im Dateisystem.
+/// - `Service(String)`: Ein Systemdienst, z.B. ein D-Bus-Servicename.
+/// - `Component(String)`: Ein internes Softwaremodul oder eine Komponente von NovaDE.
+/// - `Url(String)`: Eine URL, z.B. für Web-Ressourcen.
+/// - `Other { r#type: String, identifier: String }`: Für andere, nicht spezifisch
+///   aufgeführte Ressourcentypen. `r#type` beschreibt die Art (z.B. "ipc_channel")
+///   und `identifier` den spezifischen Bezeichner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResourceIdentifier {
+    /// Eine Datei im Dateisystem.
+    File(PathBuf),
+    /// Ein Verzeichnis im Dateisystem.
+    Directory(PathBuf),
+    /// Ein Systemdienst (z.B. D-Bus Service Name).
+    Service(String),
+    /// Eine interne Softwarekomponente oder ein Modul.
+    Component(String),
+    /// Eine Uniform Resource Locator (URL).
+    Url(String),
+    /// Ein anderer, nicht spezifisch typisierter Ressourcenbezeichner.
+    Other {
+        /// Die Art der Ressource (z.B. "ipc_channel", "hardware_device").
+        r#type: String,
+        /// Der eindeutige Bezeichner für diese Art von Ressource.
+        identifier: String,
+    },
+}
+
+impl fmt::Display for ResourceIdentifier {
+    /// Formatiert den `ResourceIdentifier` als String.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_core::types::ResourceIdentifier;
+    /// use std::path::PathBuf;
+    ///
+    /// assert_eq!(ResourceIdentifier::File(PathBuf::from("/tmp/file.txt")).to_string(), "file:///tmp/file.txt");
+    /// assert_eq!(ResourceIdentifier::Service("org.novade.ExampleService".to_string()).to_string(), "service:org.novade.ExampleService");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceIdentifier::File(path) => write!(f, "file://{}", path.display()),
+            ResourceIdentifier::Directory(path) => write!(f, "dir://{}", path.display()),
+            ResourceIdentifier::Service(name) => write!(f, "service:{}", name),
+            ResourceIdentifier::Component(name) => write!(f, "component:{}", name),
+            ResourceIdentifier::Url(url) => write!(f, "url:{}", url),
+            ResourceIdentifier::Other { r#type, identifier } => write!(f, "{}:{}", r#type, identifier),
+        }
+    }
+}
+
+impl FromStr for ResourceIdentifier {
+    type Err = CoreError;
+    /// Parst die Darstellung von `Display` (`file://…`, `dir://…`, `service:…`, `component:…`,
+    /// `url:…` oder `art:bezeichner`).
+    ///
+    /// Die Art wird bis zum ersten `:` gelesen; der Bezeichner darf weitere `:` enthalten
+    /// (z.B. `url:https://novade.org`). Eine `Other`-Ressource, deren Art einer der
+    /// vordefinierten Varianten entspricht, wird als diese Variante gelesen.
+    ///
+    /// # Fehler
+    /// Gibt `CoreError::DeserializationError` zurück, wenn der String kein `:` enthält oder
+    /// Art oder Bezeichner leer sind.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_core::types::ResourceIdentifier;
+    /// use std::path::PathBuf;
+    ///
+    /// let file: ResourceIdentifier = "file:///etc/novade/core.toml".parse().unwrap();
+    /// assert_eq!(file, ResourceIdentifier::File(PathBuf::from("/etc/novade/core.toml")));
+    ///
+    /// let channel: ResourceIdentifier = "ipc_channel:compositor".parse().unwrap();
+    /// assert_eq!(
+    ///     channel,
+    ///     ResourceIdentifier::Other { r#type: "ipc_channel".to_string(), identifier: "compositor".to_string() }
+    /// );
+    /// assert!("ohne-art".parse::<ResourceIdentifier>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |message: String| CoreError::DeserializationError {
+            format: "ResourceIdentifier".to_string(),
+            message,
+        };
+        if let Some(path) = s.strip_prefix("file://") {
+            return Ok(ResourceIdentifier::File(PathBuf::from(path)));
+        }
+        if let Some(path) = s.strip_prefix("dir://") {
+            return Ok(ResourceIdentifier::Directory(PathBuf::from(path)));
+        }
+        let (kind, identifier) = s
+            .split_once(':')
+            .ok_or_else(|| error(format!("Ungültiger Ressourcenbezeichner '{}'. Erwartet 'art:bezeichner'.", s)))?;
+        if kind.is_empty() || identifier.is_empty() {
+            return Err(error(format!("Ungültiger Ressourcenbezeichner '{}': Art und Bezeichner dürfen nicht leer sein.", s)));
+        }
+        Ok(match kind {
+            "service" => ResourceIdentifier::Service(identifier.to_string()),
+            "component" => ResourceIdentifier::Component(identifier.to_string()),
+            "url" => ResourceIdentifier::Url(identifier.to_string()),
+            _ => ResourceIdentifier::Other { r#type: kind.to_string(), identifier: identifier.to_string() },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_arithmetic_bounds() {
+        let epoch = Timestamp::from_unix_millis(0).unwrap();
+        assert_eq!(epoch.to_string(), "1970-01-01T00:00:00+00:00");
+        assert_eq!((epoch.clone() - Duration::from_millis(1500)).unix_millis(), -1500);
+        assert_eq!(epoch.duration_since(&Timestamp::now()), std::time::Duration::ZERO);
+        assert_eq!(epoch.checked_add(std::time::Duration::MAX), None);
+        assert_eq!(Timestamp::from_unix_millis(i64::MAX), None);
+
+        let mut ts = epoch.clone();
+        ts += std::time::Duration::from_secs(60);
+        ts -= std::time::Duration::from_secs(15);
+        assert_eq!(ts.duration_since(&epoch), std::time::Duration::from_secs(45));
+    }
+
+    #[test]
+    fn test_version_semver_precedence() {
+        // Die Reihenfolge aus Abschnitt 11 der SemVer-Spezifikation.
+        let ordered = [
+            "1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-alpha.beta", "1.0.0-beta", "1.0.0-beta.2",
+            "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0", "1.0.1+build.1", "1.0.1+build.2",
+        ];
+        let versions: Vec<Version> = ordered.iter().map(|v| v.parse().unwrap()).collect();
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+        for (text, version) in ordered.iter().zip(&versions) {
+            assert_eq!(&version.to_string(), text);
+        }
+        assert_eq!(versions[8].cmp_precedence(&versions[9]), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_version_parse_errors_and_compatibility() {
+        for input in ["1.0", "1.0.0-", "1.0.0-beta..1", "1.0.0-01", "1.0.0+bu!ld", "1.x.0"] {
+            assert!(
+                matches!(input.parse::<Version>(), Err(CoreError::DeserializationError { .. })),
+                "'{}' should be rejected",
+                input
+            );
+        }
+
+        let rc = Version::new(2, 0, 0).with_pre("rc.1");
+        assert!(Version::new(2, 0, 0).with_pre("rc.2").is_compatible_with(&rc));
+        assert!(Version::new(2, 0, 0).is_compatible_with(&rc));
+        assert!(!Version::new(2, 0, 1).with_pre("rc.1").is_compatible_with(&rc));
+        assert!(Version::new(0, 0, 3).with_build("x").is_compatible_with(&Version::new(0, 0, 3)));
+        assert!(!Version::new(0, 0, 4).is_compatible_with(&Version::new(0, 0, 3)));
+    }
+
+    #[test]
+    fn test_resource_identifier_display_from_str_roundtrip() {
+        let identifiers = [
+            ResourceIdentifier::File(PathBuf::from("/home/user/Dokumente/Bericht 2024.odt")),
+            ResourceIdentifier::File(PathBuf::from("relative/file.txt")),
+            ResourceIdentifier::Directory(PathBuf::from("/usr/share/applications")),
+            ResourceIdentifier::Service("org.freedesktop.NetworkManager".to_string()),
+            ResourceIdentifier::Component("compositor".to_string()),
+            ResourceIdentifier::Url("https://novade.org/docs?page=1#top".to_string()),
+            ResourceIdentifier::Other { r#type: "ipc_channel".to_string(), identifier: "a:b:c".to_string() },
+            ResourceIdentifier::Other { r#type: "file".to_string(), identifier: "no-slashes".to_string() },
+        ];
+        for identifier in identifiers {
+            let text = identifier.to_string();
+            assert_eq!(text.parse::<ResourceIdentifier>().unwrap(), identifier, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_resource_identifier_from_str_rejects_invalid_input() {
+        for input in ["", "service", ":name", "component:"] {
+            assert!(
+                matches!(input.parse::<ResourceIdentifier>(), Err(CoreError::DeserializationError { .. })),
+                "'{}' should be rejected",
+                input
+            );
+        }
+    }
+}
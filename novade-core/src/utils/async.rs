@@ -0,0 +1,477 @@
+//! # Asynchrone Hilfsmittel (`utils::async`)
+//!
+//! Dieses Untermodul von [`crate::utils`] stellt Bausteine für asynchronen Code auf Basis von
+//! Tokio bereit, die in mehreren Schichten gebraucht werden.
+//!
+//! ## Hauptkomponenten:
+//!
+//! - [`CancellationToken`]: Ein Signal zum kooperativen Abbrechen von Aufgaben, auch
+//!   hierarchisch über [`CancellationToken::child_token()`].
+//! - [`debounce()`]: Entprellt einen `Stream`, sodass nach einer Ruhezeit nur der letzte Wert
+//!   weitergegeben wird (z.B. für Dateiänderungen, die in schneller Folge gemeldet werden).
+//! - [`debounce_batched()`]: Wie `debounce()`, gibt aber alle Werte der Ruhezeit gesammelt weiter
+//!   (z.B. um Schreibvorgänge zu bündeln).
+//! - [`retry_with_backoff()`]: Wiederholt eine fehlgeschlagene Operation mit exponentiell
+//!   wachsender Wartezeit gemäß einer [`BackoffPolicy`].
+//!
+//! Die Funktionen benötigen eine laufende Tokio-Laufzeitumgebung mit aktivierten Timern.
+//!
+//! Im Code wird das Modul als `utils::r#async` angesprochen, da `async` ein Schlüsselwort ist.
+
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::{sleep, Instant, Sleep};
+
+/// Der gemeinsame Zustand eines [`CancellationToken`] und seiner Kopien.
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    notify: Notify,
+    children: Mutex<Vec<Weak<TokenState>>>,
+}
+
+impl TokenState {
+    fn cancel(&self) {
+        if self.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.notify.notify_waiters();
+        let children = std::mem::take(&mut *self.children.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+/// Ein Signal zum kooperativen Abbrechen von Aufgaben.
+///
+/// Alle Kopien (`clone()`) eines Tokens teilen denselben Zustand: Wird eine abgebrochen, sind
+/// alle abgebrochen. Aufgaben prüfen [`is_cancelled()`](Self::is_cancelled) oder warten mit
+/// [`cancelled()`](Self::cancelled) auf den Abbruch. Ein Abbruch kann nicht zurückgenommen
+/// werden.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::utils::r#async::CancellationToken;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let shutdown = CancellationToken::new();
+/// let scanner = shutdown.child_token();
+///
+/// let task = tokio::spawn({
+///     let scanner = scanner.clone();
+///     async move {
+///         scanner.cancelled().await;
+///         "beendet"
+///     }
+/// });
+///
+/// shutdown.cancel();
+/// assert!(scanner.is_cancelled());
+/// assert_eq!(task.await.unwrap(), "beendet");
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<TokenState>,
+}
+
+impl CancellationToken {
+    /// Erstellt ein neues, nicht abgebrochenes Token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Erstellt ein Kind-Token, das mit diesem Token abgebrochen wird.
+    ///
+    /// Das Kind kann auch allein abgebrochen werden, ohne dieses Token zu beeinflussen. Ist
+    /// dieses Token bereits abgebrochen, ist es auch das Kind.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+        {
+            let mut children = self.state.children.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if !self.is_cancelled() {
+                children.retain(|child| child.strong_count() > 0);
+                children.push(Arc::downgrade(&child.state));
+                return child;
+            }
+        }
+        child.cancel();
+        child
+    }
+
+    /// Bricht das Token, alle Kopien und alle Kind-Tokens ab und weckt wartende Aufgaben.
+    pub fn cancel(&self) {
+        self.state.cancel();
+    }
+
+    /// Gibt `true` zurück, wenn das Token abgebrochen wurde.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wartet, bis das Token abgebrochen wird. Kehrt sofort zurück, wenn es bereits abgebrochen ist.
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.state.notify.notified();
+            tokio::pin!(notified);
+            // Vor der Prüfung registrieren, damit ein gleichzeitiger Abbruch nicht verloren geht.
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Entprellt `stream`: Ein Wert wird erst weitergegeben, wenn `duration` lang kein neuer Wert
+/// gekommen ist; dazwischen eintreffende Werte ersetzen ihn.
+///
+/// Endet `stream`, wird ein noch ausstehender Wert sofort weitergegeben.
+///
+/// # Parameter
+/// * `stream`: Die Quelle; muss `Unpin` sein (sonst mit `Box::pin` einpacken).
+/// * `duration`: Die Ruhezeit.
+///
+/// # Rückgabe
+/// Einen `Stream` mit den entprellten Werten.
+pub fn debounce<S: Stream + Unpin>(stream: S, duration: Duration) -> Debounce<S> {
+    Debounce { inner: DebounceState::new(stream, duration), pending: None }
+}
+
+/// Wie [`debounce()`], gibt aber alle Werte, die bis zum Ende der Ruhezeit eingetroffen sind,
+/// gesammelt und in ihrer Reihenfolge weiter.
+pub fn debounce_batched<S: Stream + Unpin>(stream: S, duration: Duration) -> DebounceBatched<S> {
+    DebounceBatched { inner: DebounceState::new(stream, duration), pending: Vec::new() }
+}
+
+/// Der gemeinsame Teil von [`Debounce`] und [`DebounceBatched`].
+struct DebounceState<S> {
+    stream: Option<S>,
+    duration: Duration,
+    timer: Pin<Box<Sleep>>,
+}
+
+/// Was beim Abfragen eines entprellten Streams passiert ist.
+enum DebounceStep<T> {
+    /// Ein neuer Wert ist eingetroffen.
+    Item(T),
+    /// Die Ruhezeit ist abgelaufen oder die Quelle hat geendet; ausstehende Werte weitergeben.
+    Flush,
+    /// Nichts zu tun, bis die Aufgabe geweckt wird.
+    Pending,
+}
+
+impl<S: Stream + Unpin> DebounceState<S> {
+    fn new(stream: S, duration: Duration) -> Self {
+        Self { stream: Some(stream), duration, timer: Box::pin(sleep(duration)) }
+    }
+
+    /// Fragt die Quelle und, falls `has_pending`, den Timer ab.
+    fn poll_step(&mut self, cx: &mut Context<'_>, has_pending: bool) -> DebounceStep<S::Item> {
+        if let Some(stream) = self.stream.as_mut() {
+            match Pin::new(stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    self.timer.as_mut().reset(Instant::now() + self.duration);
+                    return DebounceStep::Item(item);
+                }
+                Poll::Ready(None) => {
+                    self.stream = None;
+                    return DebounceStep::Flush;
+                }
+                Poll::Pending => {}
+            }
+        }
+        if has_pending && self.timer.as_mut().poll(cx).is_ready() {
+            return DebounceStep::Flush;
+        }
+        DebounceStep::Pending
+    }
+
+    fn is_finished(&self) -> bool {
+        self.stream.is_none()
+    }
+}
+
+/// Der `Stream` von [`debounce()`].
+pub struct Debounce<S: Stream> {
+    inner: DebounceState<S>,
+    pending: Option<S::Item>,
+}
+
+// Die gepufferten Werte werden nie angeheftet, nur verschoben.
+impl<S: Stream + Unpin> Unpin for Debounce<S> {}
+
+impl<S: Stream + Unpin> Stream for Debounce<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        let this = self.get_mut();
+        loop {
+            match this.inner.poll_step(cx, this.pending.is_some()) {
+                DebounceStep::Item(item) => this.pending = Some(item),
+                DebounceStep::Flush => match this.pending.take() {
+                    Some(item) => return Poll::Ready(Some(item)),
+                    None if this.inner.is_finished() => return Poll::Ready(None),
+                    None => {}
+                },
+                DebounceStep::Pending if this.inner.is_finished() => return Poll::Ready(this.pending.take()),
+                DebounceStep::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Der `Stream` von [`debounce_batched()`].
+pub struct DebounceBatched<S: Stream> {
+    inner: DebounceState<S>,
+    pending: Vec<S::Item>,
+}
+
+impl<S: Stream + Unpin> Unpin for DebounceBatched<S> {}
+
+impl<S: Stream + Unpin> Stream for DebounceBatched<S> {
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Vec<S::Item>>> {
+        let this = self.get_mut();
+        loop {
+            match this.inner.poll_step(cx, !this.pending.is_empty()) {
+                DebounceStep::Item(item) => this.pending.push(item),
+                DebounceStep::Flush if !this.pending.is_empty() => {
+                    return Poll::Ready(Some(std::mem::take(&mut this.pending)))
+                }
+                DebounceStep::Flush | DebounceStep::Pending if this.inner.is_finished() => {
+                    return Poll::Ready((!this.pending.is_empty()).then(|| std::mem::take(&mut this.pending)))
+                }
+                DebounceStep::Flush => {}
+                DebounceStep::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Legt fest, wie oft und in welchen Abständen [`retry_with_backoff()`] eine Operation wiederholt.
+///
+/// Die Wartezeit vor dem `n`-ten Wiederholungsversuch beträgt
+/// `initial_delay * multiplier^(n-1)`, höchstens aber `max_delay`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackoffPolicy {
+    /// Die Wartezeit vor dem ersten Wiederholungsversuch.
+    pub initial_delay: Duration,
+    /// Die größte Wartezeit zwischen zwei Versuchen.
+    pub max_delay: Duration,
+    /// Der Faktor, um den die Wartezeit nach jedem Fehlschlag wächst.
+    pub multiplier: f64,
+    /// Die Anzahl der Versuche insgesamt, einschließlich des ersten (mindestens 1).
+    pub max_attempts: u32,
+}
+
+impl Default for BackoffPolicy {
+    /// 5 Versuche, beginnend mit 100 ms Wartezeit, die sich bis höchstens 10 s jeweils verdoppelt.
+    fn default() -> Self {
+        Self { initial_delay: Duration::from_millis(100), max_delay: Duration::from_secs(10), multiplier: 2.0, max_attempts: 5 }
+    }
+}
+
+impl BackoffPolicy {
+    /// Die Wartezeit vor dem Wiederholungsversuch `retry` (ab 1 gezählt).
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retry.saturating_sub(1).min(i32::MAX as u32) as i32);
+        let delay = self.initial_delay.as_secs_f64() * factor;
+        if delay.is_finite() && delay < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max_delay
+        }
+    }
+}
+
+/// Führt `operation` aus und wiederholt sie bei einem Fehler gemäß `policy`.
+///
+/// Jeder Fehlschlag, auf den ein weiterer Versuch folgt, wird auf dem Level `DEBUG` geloggt.
+///
+/// # Parameter
+/// * `operation`: Erzeugt für jeden Versuch ein neues Future.
+/// * `policy`: Anzahl der Versuche und Wartezeiten.
+///
+/// # Rückgabe
+/// Das erste erfolgreiche Ergebnis.
+///
+/// # Fehler
+/// Der Fehler des letzten Versuchs, wenn alle Versuche fehlschlagen.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::utils::r#async::{retry_with_backoff, BackoffPolicy};
+/// use std::time::Duration;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let policy = BackoffPolicy { initial_delay: Duration::from_millis(1), max_attempts: 3, ..BackoffPolicy::default() };
+/// let mut attempts = 0;
+/// let result: Result<u32, String> = retry_with_backoff(
+///     || {
+///         attempts += 1;
+///         let attempt = attempts;
+///         async move { if attempt < 3 { Err(format!("Versuch {} fehlgeschlagen", attempt)) } else { Ok(attempt) } }
+///     },
+///     &policy,
+/// )
+/// .await;
+/// assert_eq!(result, Ok(3));
+/// # }
+/// ```
+pub async fn retry_with_backoff<T, E, F, Fut>(operation: F, policy: &BackoffPolicy) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    retry_with_backoff_if(operation, policy, |_| true).await
+}
+
+/// Wie [`retry_with_backoff()`], wiederholt aber nur Fehler, für die `should_retry` `true`
+/// liefert; andere werden sofort zurückgegeben (z.B. Validierungsfehler, die ein erneuter
+/// Versuch nicht behebt).
+pub async fn retry_with_backoff_if<T, E, F, Fut>(
+    mut operation: F,
+    policy: &BackoffPolicy,
+    should_retry: impl Fn(&E) -> bool,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt < max_attempts && should_retry(&error) => {
+                let delay = policy.delay_for(attempt);
+                tracing::debug!(
+                    "Versuch {}/{} fehlgeschlagen: {}. Neuer Versuch in {:?}.",
+                    attempt,
+                    max_attempts,
+                    error,
+                    delay
+                );
+                sleep(delay).await;
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Ein Stream aus Werten, die jeweils nach einer Verzögerung (relativ zum vorigen) eintreffen.
+    struct Timed<T> {
+        items: VecDeque<(Duration, T)>,
+        timer: Option<Pin<Box<Sleep>>>,
+    }
+
+    fn timed<T>(items: Vec<(u64, T)>) -> Timed<T> {
+        Timed { items: items.into_iter().map(|(ms, item)| (Duration::from_millis(ms), item)).collect(), timer: None }
+    }
+
+    impl<T: Unpin> Stream for Timed<T> {
+        type Item = T;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+            let this = self.get_mut();
+            let Some((delay, _)) = this.items.front() else {
+                return Poll::Ready(None);
+            };
+            let timer = this.timer.get_or_insert_with(|| Box::pin(sleep(*delay)));
+            if timer.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            this.timer = None;
+            Poll::Ready(this.items.pop_front().map(|(_, item)| item))
+        }
+    }
+
+    async fn collect<S: Stream + Unpin>(mut stream: S) -> Vec<S::Item> {
+        let mut items = Vec::new();
+        while let Some(item) = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            items.push(item);
+        }
+        items
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_debounce_keeps_last_value_of_each_burst() {
+        let source = timed(vec![(0, 1), (10, 2), (10, 3), (200, 4), (10, 5)]);
+        assert_eq!(collect(debounce(source, Duration::from_millis(50))).await, vec![3, 5]);
+
+        let source = timed(vec![(0, 1), (10, 2), (200, 3)]);
+        assert_eq!(collect(debounce_batched(source, Duration::from_millis(50))).await, vec![vec![1, 2], vec![3]]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_gives_up_and_respects_predicate() {
+        let policy = BackoffPolicy { initial_delay: Duration::from_millis(100), max_attempts: 3, ..BackoffPolicy::default() };
+        let start = Instant::now();
+        let mut attempts = 0;
+        let result: Result<(), String> = retry_with_backoff(
+            || {
+                attempts += 1;
+                async { Err("nicht erreichbar".to_string()) }
+            },
+            &policy,
+        )
+        .await;
+        assert_eq!(result, Err("nicht erreichbar".to_string()));
+        assert_eq!(attempts, 3);
+        assert_eq!(start.elapsed(), Duration::from_millis(300), "100 ms + 200 ms Wartezeit");
+
+        let mut attempts = 0;
+        let result: Result<(), &str> = retry_with_backoff_if(
+            || {
+                attempts += 1;
+                async { Err("ungültig") }
+            },
+            &policy,
+            |error| *error != "ungültig",
+        )
+        .await;
+        assert_eq!(result, Err("ungültig"));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_backoff_delays_are_capped() {
+        let policy = BackoffPolicy { max_delay: Duration::from_millis(500), ..BackoffPolicy::default() };
+        let delays: Vec<u64> = (1..=5).map(|retry| policy.delay_for(retry).as_millis() as u64).collect();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+        assert_eq!(policy.delay_for(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_child_tokens_follow_parent() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let independent = child.child_token();
+        independent.cancel();
+        assert!(!child.is_cancelled() && !parent.is_cancelled());
+
+        parent.cancel();
+        child.cancelled().await;
+        assert!(parent.child_token().is_cancelled());
+    }
+}
@@ -10,9 +10,16 @@
 //! - [`resolve_path()`]: Löst einen möglicherweise relativen Pfad relativ zu einem Basispfad auf
 //!   und normalisiert ihn (entfernt `.` und `..`).
 //! - [`read_file_to_string()`]: Liest den gesamten Inhalt einer Datei in einen String.
+//! - [`write_file_atomically()`]: Ersetzt den Inhalt einer Datei, ohne dass Leser je eine halb
+//!   geschriebene Datei sehen.
 //! - [`get_app_config_dir()`]: Ermittelt das Standard-Konfigurationsverzeichnis für die Anwendung.
 //! - [`get_app_data_dir()`]: Ermittelt das Standard-Datenverzeichnis für die Anwendung.
 //! - [`get_app_cache_dir()`]: Ermittelt das Standard-Cache-Verzeichnis für die Anwendung.
+//! - [`get_app_state_dir()`]: Ermittelt das Standard-Zustandsverzeichnis für die Anwendung (z.B. für Logdateien).
+//! - [`watch_directory()`]: Meldet entprellte Änderungen in einem Verzeichnis als [`FsEvent`]
+//!   (siehe [`watch`]).
+//! - [`r#async`]: Abbruch-Tokens, Entprellen von Streams und Wiederholen mit wachsender
+//!   Wartezeit für asynchronen Code.
 //!
 //! ## Fehlerbehandlung:
 //!
@@ -57,9 +64,14 @@
 //! // println!("Wert der Einstellung: {}", my_setting);
 //! ```
 
+pub mod r#async;
+pub mod watch;
+
 use crate::error::{CoreError, CoreResult};
 use std::fs;
-use std::path::{Component, Path, PathBuf}; // MAIN_SEPARATOR für Tests entfernt
+use std::path::{Component, Path, PathBuf};
+
+pub use watch::{watch_directory, DirectoryWatcher, FsEvent};
 
 /// Löst einen möglicherweise relativen Pfad relativ zu einem gegebenen Basispfad auf und normalisiert ihn.
 ///
@@ -176,7 +188,8 @@ pub fn resolve_path(base_path: &Path, relative_path_str: &str) -> CoreResult<Pat
 /// Ein `CoreResult<String>`:
 /// - `Ok(String)`: Der Inhalt der Datei als String.
 /// - `Err(CoreError::IoError)`: Wenn ein Fehler beim Lesen der Datei auftritt
-///   (z.B. Datei nicht gefunden, keine Berechtigungen, kein valides UTF-8).
+///   (z.B. Datei nicht gefunden, keine Berechtigungen, kein valides UTF-8). Die Art des
+///   Fehlers ist über [`std::io::Error::kind()`] abfragbar.
 ///
 /// # Beispiele
 /// ```no_run
@@ -189,7 +202,52 @@ pub fn resolve_path(base_path: &Path, relative_path_str: &str) -> CoreResult<Pat
 /// }
 /// ```
 pub fn read_file_to_string(path: &Path) -> CoreResult<String> {
-    fs::read_to_string(path).map_err(|err| CoreError::IoError(err.to_string()))
+    fs::read_to_string(path).map_err(|err| CoreError::io(format!("'{}' konnte nicht gelesen werden", path.display()), err))
+}
+
+/// Schreibt `contents` atomar in die Datei am Pfad `path`.
+///
+/// Der Inhalt wird zunächst in eine temporäre Datei im selben Verzeichnis geschrieben, auf den
+/// Datenträger synchronisiert und dann über die Zieldatei umbenannt. Leser (z.B. ein
+/// [`ConfigWatcher`](crate::config::ConfigWatcher)) sehen daher entweder den alten oder den
+/// neuen Inhalt, und ein Absturz während des Schreibens hinterlässt keine halbe Datei.
+///
+/// # Parameter
+/// * `path`: Der Pfad zur Zieldatei. Das Verzeichnis muss existieren.
+/// * `contents`: Der neue Inhalt der Datei.
+///
+/// # Rückgabe
+/// - `Ok(())`: Wenn die Datei ersetzt wurde.
+/// - `Err(CoreError::IoError)`: Wenn die temporäre Datei nicht geschrieben oder nicht umbenannt
+///   werden konnte. Die temporäre Datei wird in diesem Fall entfernt.
+///
+/// # Beispiele
+/// ```no_run
+/// use novade_core::utils::write_file_atomically;
+/// use std::path::Path;
+///
+/// write_file_atomically(Path::new("einstellungen.toml"), "log_level = \"debug\"\n").unwrap();
+/// ```
+pub fn write_file_atomically(path: &Path, contents: &str) -> CoreResult<()> {
+    let file_name = path.file_name().ok_or_else(|| CoreError::InvalidPathError {
+        path: path.display().to_string(),
+        message: "Der Pfad enthält keinen Dateinamen.".to_string(),
+    })?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let write = || -> std::io::Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        std::io::Write::write_all(&mut file, contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    };
+    write().map_err(|err| {
+        let _ = fs::remove_file(&temp_path);
+        CoreError::io(format!("'{}' konnte nicht geschrieben werden", path.display()), err)
+    })
 }
 
 /// Ermittelt das Standard-Konfigurationsverzeichnis für die Anwendung gemäß den Konventionen des Betriebssystems.
@@ -271,11 +329,40 @@ pub fn get_app_cache_dir(app_name: &str) -> Option<PathBuf> {
     dirs::cache_dir().map(|path| path.join(app_name))
 }
 
+/// Ermittelt das Standard-Zustandsverzeichnis für die Anwendung gemäß den Konventionen des Betriebssystems.
+///
+/// Im Zustandsverzeichnis liegen Daten, die einen Neustart überdauern sollen, aber nicht
+/// wichtig genug für das Datenverzeichnis sind (z.B. Logdateien oder der Verlauf).
+/// Basiert auf dem `dirs` Crate.
+/// - **Linux**: `$XDG_STATE_HOME/{app_name}` (typischerweise `~/.local/state/{app_name}`)
+/// - **macOS** und **Windows** kennen kein Zustandsverzeichnis; dort wird das lokale
+///   Datenverzeichnis verwendet (`~/Library/Application Support/{app_name}` bzw.
+///   `%LOCALAPPDATA%\{app_name}`).
+///
+/// # Parameter
+/// * `app_name`: Der Name der Anwendung.
+///
+/// # Rückgabe
+/// `Some(PathBuf)` mit dem Pfad zum anwendungsspezifischen Zustandsverzeichnis, oder `None`.
+///
+/// # Beispiele
+/// ```
+/// use novade_core::utils::get_app_state_dir;
+///
+/// if let Some(state_dir) = get_app_state_dir("MeineTolleApp") {
+///     println!("Zustandsverzeichnis: {}", state_dir.display());
+/// }
+/// ```
+pub fn get_app_state_dir(app_name: &str) -> Option<PathBuf> {
+    dirs::state_dir().or_else(dirs::data_local_dir).map(|path| path.join(app_name))
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::Write;
+    use std::path::MAIN_SEPARATOR;
     use tempfile::NamedTempFile;
     // tempdir kann für Tests nützlich sein, die Verzeichnisstrukturen erfordern.
     // use tempfile::tempdir;
@@ -356,6 +443,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_write_file_atomically_replaces_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("datei.txt");
+        fs::write(&path, "alt").unwrap();
+        write_file_atomically(&path, "neu").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "neu");
+        // Die temporäre Datei bleibt nicht liegen.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let missing_dir = dir.path().join("fehlt").join("datei.txt");
+        assert!(matches!(write_file_atomically(&missing_dir, "neu"), Err(CoreError::IoError(_))));
+    }
+
     const TEST_APP_NAME_FOR_DIRS: &str = "NovaDE-UtilsTest";
 
     #[test]
@@ -403,4 +504,16 @@ mod tests {
             eprintln!("get_app_cache_dir hat None zurückgegeben.");
         }
     }
+
+    #[test]
+    fn test_get_app_state_dir_structure() {
+        if let Some(path) = get_app_state_dir(TEST_APP_NAME_FOR_DIRS) {
+            assert!(path.ends_with(TEST_APP_NAME_FOR_DIRS));
+            if cfg!(all(unix, not(target_os = "macos"))) {
+                assert!(path.to_string_lossy().contains(".local/state"), "Unix-Zustandspfad {} sollte .local/state enthalten", path.display());
+            }
+        } else {
+            eprintln!("get_app_state_dir hat None zurückgegeben.");
+        }
+    }
 }
@@ -0,0 +1,309 @@
+//! # Überwachung von Verzeichnissen (`utils::watch`)
+//!
+//! Dieses Untermodul von [`crate::utils`] meldet Änderungen in Verzeichnissen als [`FsEvent`],
+//! z.B. neu installierte Anwendungen in den `applications`-Verzeichnissen, geänderte Themes
+//! oder bearbeitete Konfigurationsdateien.
+//!
+//! ## Entprellung:
+//!
+//! Viele Programme ändern eine Datei in mehreren Schritten (anlegen, schreiben, umbenennen),
+//! und Paketmanager installieren viele Dateien auf einmal. Ein [`DirectoryWatcher`] meldet
+//! eine Datei daher erst, wenn sie eine Wartezeit lang ([`DEFAULT_DEBOUNCE`]) unverändert
+//! geblieben ist, und fasst die Schritte zu einem Ereignis zusammen: Eine angelegte und danach
+//! beschriebene Datei wird einmal als [`FsEvent::Created`] gemeldet, eine angelegte und gleich
+//! wieder gelöschte (z.B. eine temporäre Datei) gar nicht. Wird ein Verzeichnis angelegt,
+//! werden auch die Dateien gemeldet, die es zu diesem Zeitpunkt schon enthält.
+
+use crate::error::{CoreError, CoreResult};
+use notify::event::{EventKind, ModifyKind, RenameMode};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Wie lange eine Datei unverändert bleiben muss, bevor ihre Änderung gemeldet wird.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Eine Änderung an einer Datei oder einem Verzeichnis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FsEvent {
+    /// Der Pfad ist neu hinzugekommen (angelegt oder hierher umbenannt).
+    Created(PathBuf),
+    /// Der Inhalt oder die Metadaten haben sich geändert, oder der Pfad wurde ersetzt.
+    Modified(PathBuf),
+    /// Der Pfad existiert nicht mehr (gelöscht oder wegbenannt).
+    Removed(PathBuf),
+}
+
+impl FsEvent {
+    /// Der betroffene Pfad.
+    pub fn path(&self) -> &Path {
+        match self {
+            FsEvent::Created(path) | FsEvent::Modified(path) | FsEvent::Removed(path) => path,
+        }
+    }
+}
+
+/// Überwacht ein oder mehrere Verzeichnisse einschließlich ihrer Unterverzeichnisse und meldet
+/// entprellte Änderungen als [`FsEvent`].
+///
+/// Die Überwachung endet, sobald der `DirectoryWatcher` verworfen wird.
+pub struct DirectoryWatcher {
+    // Muss am Leben bleiben, solange überwacht werden soll; mit ihm endet auch der Entprell-Thread.
+    watcher: RecommendedWatcher,
+    events: Receiver<FsEvent>,
+}
+
+impl DirectoryWatcher {
+    /// Erstellt einen Watcher ohne Verzeichnisse, der Pfade meldet, für die `filter` `true`
+    /// liefert. Verzeichnisse werden mit [`DirectoryWatcher::add()`] hinzugefügt.
+    ///
+    /// # Parameter
+    /// * `filter`: Wählt die zu meldenden Pfade aus, z.B. nur `*.desktop`-Dateien.
+    /// * `debounce`: Wie lange eine Datei unverändert bleiben muss, bevor sie gemeldet wird.
+    ///
+    /// # Fehler
+    /// `CoreError::InitializationError`, wenn das Betriebssystem keine Überwachung bereitstellt.
+    pub fn new(filter: impl Fn(&Path) -> bool + Send + Sync + 'static, debounce: Duration) -> CoreResult<Self> {
+        let (raw_sender, raw_changes) = mpsc::channel();
+        let (sender, events) = mpsc::channel();
+        let filter = Arc::new(filter);
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            for (path, change) in classify(event) {
+                // Was in einem neuen Verzeichnis angelegt wird, bevor es selbst überwacht wird,
+                // löst kein eigenes Ereignis aus und wird daher mit dem Verzeichnis gemeldet.
+                if change == Change::Created && path.is_dir() {
+                    let mut contents = Vec::new();
+                    collect_entries(&path, &mut contents);
+                    for entry in contents.into_iter().filter(|entry| filter(entry)) {
+                        let _ = raw_sender.send((entry, Change::Created));
+                    }
+                }
+                if filter(&path) {
+                    let _ = raw_sender.send((path, change));
+                }
+            }
+        })
+        .map_err(|error| watch_error("Die Verzeichnisüberwachung kann nicht eingerichtet werden", error))?;
+        thread::spawn(move || debounce_changes(raw_changes, sender, debounce));
+        Ok(Self { watcher, events })
+    }
+
+    /// Beginnt, das Verzeichnis `path` und seine Unterverzeichnisse zu überwachen.
+    ///
+    /// # Fehler
+    /// `CoreError::InitializationError`, wenn das Verzeichnis nicht existiert oder nicht
+    /// überwacht werden kann.
+    pub fn add(&mut self, path: &Path) -> CoreResult<()> {
+        self.watcher.watch(path, RecursiveMode::Recursive).map_err(|error| {
+            watch_error(&format!("Verzeichnis '{}' kann nicht überwacht werden", path.display()), error)
+        })
+    }
+
+    /// Die gemeldeten Änderungen. Das Iterieren über den `Receiver` blockiert bis zur
+    /// nächsten Änderung; `try_recv` bzw. `recv_timeout` blockieren nicht bzw. begrenzt.
+    pub fn events(&self) -> &Receiver<FsEvent> {
+        &self.events
+    }
+}
+
+impl std::fmt::Debug for DirectoryWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DirectoryWatcher").finish_non_exhaustive()
+    }
+}
+
+/// Überwacht das Verzeichnis `path` einschließlich seiner Unterverzeichnisse und meldet
+/// Änderungen an Pfaden, für die `filter` `true` liefert, entprellt um [`DEFAULT_DEBOUNCE`].
+///
+/// # Parameter
+/// * `path`: Das zu überwachende Verzeichnis.
+/// * `filter`: Wählt die zu meldenden Pfade aus.
+///
+/// # Rückgabe
+/// Der [`DirectoryWatcher`]; die Änderungen liefert [`DirectoryWatcher::events()`].
+///
+/// # Fehler
+/// `CoreError::InitializationError`, wenn das Verzeichnis nicht existiert oder nicht
+/// überwacht werden kann.
+///
+/// # Beispiele
+/// ```rust,no_run
+/// use novade_core::utils::{watch_directory, FsEvent};
+/// use std::path::Path;
+///
+/// # fn main() -> novade_core::CoreResult<()> {
+/// let watcher = watch_directory(Path::new("/usr/share/applications"), |path| {
+///     path.extension().is_some_and(|extension| extension == "desktop")
+/// })?;
+/// for event in watcher.events() {
+///     if let FsEvent::Created(path) = event {
+///         println!("Neue Anwendung: {}", path.display());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn watch_directory(path: &Path, filter: impl Fn(&Path) -> bool + Send + Sync + 'static) -> CoreResult<DirectoryWatcher> {
+    let mut watcher = DirectoryWatcher::new(filter, DEFAULT_DEBOUNCE)?;
+    watcher.add(path)?;
+    Ok(watcher)
+}
+
+fn watch_error(message: &str, error: notify::Error) -> CoreError {
+    CoreError::InitializationError { component: "directory_watcher".to_string(), message: format!("{}: {}", message, error) }
+}
+
+/// Eine einzelne, noch nicht entprellte Änderung.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// Die betroffenen Pfade eines Ereignisses mit ihrer Änderung; Zugriffe werden übergangen.
+fn classify(event: notify::Event) -> Vec<(PathBuf, Change)> {
+    let all = |paths: Vec<PathBuf>, change| paths.into_iter().map(|path| (path, change)).collect();
+    match event.kind {
+        EventKind::Access(_) => Vec::new(),
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => all(event.paths, Change::Created),
+        EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => all(event.paths, Change::Removed),
+        // Bei einer vollständigen Umbenennung ist der erste Pfad der alte, der zweite der neue.
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => event
+            .paths
+            .into_iter()
+            .enumerate()
+            .map(|(index, path)| (path, if index == 0 { Change::Removed } else { Change::Created }))
+            .collect(),
+        // Sonst entscheidet, ob der Pfad noch existiert.
+        _ => event
+            .paths
+            .into_iter()
+            .map(|path| {
+                let change = if path.exists() { Change::Modified } else { Change::Removed };
+                (path, change)
+            })
+            .collect(),
+    }
+}
+
+/// Sammelt alle Dateien und Verzeichnisse unterhalb von `dir`; unlesbare werden übergangen.
+fn collect_entries(dir: &Path, entries: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in read_dir.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        if path.is_dir() {
+            collect_entries(&path, entries);
+        }
+        entries.push(path);
+    }
+}
+
+/// Der gesammelte Zustand eines Pfades, dessen Änderung noch nicht gemeldet wurde.
+struct Pending {
+    /// Ob der Pfad vor der ersten Änderung existierte.
+    existed_before: bool,
+    /// Ob der Pfad nach der letzten Änderung existiert.
+    exists: bool,
+    last_change: Instant,
+}
+
+impl Pending {
+    fn into_event(self, path: PathBuf) -> Option<FsEvent> {
+        match (self.existed_before, self.exists) {
+            (false, true) => Some(FsEvent::Created(path)),
+            (true, true) => Some(FsEvent::Modified(path)),
+            (true, false) => Some(FsEvent::Removed(path)),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Sammelt die Änderungen aus `raw_changes` und meldet jeden Pfad, sobald er `debounce` lang
+/// unverändert war. Endet, wenn der Watcher verworfen wurde, nachdem alles Offene gemeldet ist.
+fn debounce_changes(raw_changes: Receiver<(PathBuf, Change)>, events: Sender<FsEvent>, debounce: Duration) {
+    let mut pending: HashMap<PathBuf, Pending> = HashMap::new();
+    loop {
+        let next_due = pending.values().map(|change| change.last_change + debounce).min();
+        let received = match next_due {
+            Some(due) => raw_changes.recv_timeout(due.saturating_duration_since(Instant::now())),
+            None => raw_changes.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        let disconnected = match received {
+            Ok((path, change)) => {
+                let now = Instant::now();
+                let exists = change != Change::Removed;
+                pending
+                    .entry(path)
+                    .and_modify(|pending| {
+                        pending.exists = exists;
+                        pending.last_change = now;
+                    })
+                    // Nur ein neu angelegter Pfad existierte vor der ersten Änderung nicht.
+                    .or_insert(Pending { existed_before: change != Change::Created, exists, last_change: now });
+                false
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+
+        let now = Instant::now();
+        let mut due: Vec<(PathBuf, Pending)> = if disconnected {
+            pending.drain().collect()
+        } else {
+            let due_paths: Vec<PathBuf> =
+                pending.iter().filter(|(_, change)| change.last_change + debounce <= now).map(|(path, _)| path.clone()).collect();
+            due_paths.into_iter().filter_map(|path| pending.remove_entry(&path)).collect()
+        };
+        due.sort_by_key(|(_, change)| change.last_change);
+        for (path, change) in due {
+            if let Some(event) = change.into_event(path) {
+                if events.send(event).is_err() {
+                    return;
+                }
+            }
+        }
+        if disconnected {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_watch_directory_debounces_and_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let desktop_file = |path: &Path| path.extension().is_some_and(|extension| extension == "desktop");
+        let watcher = watch_directory(dir.path(), desktop_file).unwrap();
+        let timeout = Duration::from_secs(5);
+
+        // Anlegen und mehrfaches Schreiben ergibt ein einziges Ereignis; andere Dateien und
+        // kurzlebige Dateien werden nicht gemeldet.
+        let app = dir.path().join("org.example.Editor.desktop");
+        fs::write(&app, "[Desktop Entry]\n").unwrap();
+        fs::write(&app, "[Desktop Entry]\nName=Editor\n").unwrap();
+        fs::write(dir.path().join("notizen.txt"), "ignoriert").unwrap();
+        let temporary = dir.path().join("kurzlebig.desktop");
+        fs::write(&temporary, "").unwrap();
+        fs::remove_file(&temporary).unwrap();
+        assert_eq!(watcher.events().recv_timeout(timeout).unwrap(), FsEvent::Created(app.clone()));
+        assert!(watcher.events().recv_timeout(DEFAULT_DEBOUNCE * 3).is_err());
+
+        fs::write(&app, "[Desktop Entry]\nName=Editor 2\n").unwrap();
+        assert_eq!(watcher.events().recv_timeout(timeout).unwrap(), FsEvent::Modified(app.clone()));
+        fs::remove_file(&app).unwrap();
+        assert_eq!(watcher.events().recv_timeout(timeout).unwrap(), FsEvent::Removed(app));
+    }
+}
@@ -1,13 +1,18 @@
 //! # Application Entität (`entities::application`)
 //!
 //! Definiert die Kernentität [`Application`] zur Repräsentation einer Anwendung
-//! im NovaDE-System sowie den zugehörigen Typ [`ApplicationType`].
+//! im NovaDE-System sowie die zugehörigen Typen [`ApplicationType`], [`ResourceLimits`]
+//! und [`SandboxKind`].
 //!
 //! Eine `Application` kann eine Desktop-Anwendung, ein Kommandozeilen-Tool,
 //! ein Hintergrunddienst oder eine Web-Anwendung sein. Die Struktur hält
 //! Metadaten wie Name, Pfad zur ausführbaren Datei, Icon, Kategorien und Version.
 
-use novade_core::types::{NovaId, Version};
+use std::collections::BTreeMap;
+
+use crate::entities::Entity;
+use crate::validation::{require_non_empty, FieldViolation, Validate};
+use novade_core::types::{NovaId, Timestamp, Version};
 use serde::{Deserialize, Serialize};
 
 /// Repräsentiert den Typ oder die Kategorie einer Anwendung.
@@ -28,6 +33,40 @@ pub enum ApplicationType {
     Other(String),
 }
 
+/// Ressourcenbeschränkungen für den Prozess einer Anwendung.
+///
+/// Damit lassen sich ressourcenhungrige Anwendungen begrenzen und Hintergrunddienste
+/// zurückhaltend betreiben. Nicht gesetzte Felder lassen die Standardwerte des Systems unverändert.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Maximaler Adressraum des Prozesses in Bytes.
+    pub max_memory_bytes: Option<u64>,
+    /// Maximale Anzahl gleichzeitig geöffneter Dateideskriptoren.
+    pub max_open_files: Option<u64>,
+    /// Nice-Wert für die CPU-Priorität (-20 bis 19, höher bedeutet niedrigere Priorität).
+    pub nice: Option<i32>,
+}
+
+/// Art des Sandboxes, in dem eine Anwendung installiert ist.
+///
+/// Sandboxed-Anwendungen werden nicht direkt gestartet, sondern über das jeweilige
+/// Werkzeug (`flatpak run`, `snap run`), das die Laufzeitumgebung einrichtet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SandboxKind {
+    /// Eine Flatpak-Anwendung.
+    Flatpak {
+        /// Die Flatpak-Anwendungs-ID (z.B. "org.gimp.GIMP").
+        app_id: String,
+        /// Zusätzliche Optionen für `flatpak run` (z.B. "--branch=stable").
+        run_options: Vec<String>,
+    },
+    /// Ein Snap-Paket.
+    Snap {
+        /// Der Name des Snaps bzw. der Snap-Anwendung (z.B. "spotify" oder "code.url-handler").
+        name: String,
+    },
+}
+
 /// Repräsentiert eine Anwendung, die im NovaDE-System bekannt ist und verwaltet werden kann.
 ///
 /// Enthält alle notwendigen Informationen, um eine Anwendung zu identifizieren, darzustellen
@@ -47,20 +86,43 @@ pub struct Application {
     pub arguments: Option<Vec<String>>,
     /// Optionales Arbeitsverzeichnis, in dem die Anwendung gestartet werden soll.
     pub working_directory: Option<String>,
+    /// Optionale Umgebungsvariablen, die beim Start der Anwendung zusätzlich gesetzt werden
+    /// (z.B. `GTK_THEME`). Sie überschreiben gleichnamige Variablen der Umgebung des Systems.
+    pub environment: Option<BTreeMap<String, String>>,
+    /// Optionale Ressourcenbeschränkungen, die beim Start auf den Prozess angewendet werden.
+    pub resource_limits: Option<ResourceLimits>,
+    /// Sandbox, in dem die Anwendung installiert ist (Flatpak, Snap), falls zutreffend.
+    /// `executable_path` und `arguments` beschreiben dann den Befehl innerhalb des Sandboxes.
+    pub sandbox: Option<SandboxKind>,
     /// Name des Icons für die Anwendung, typischerweise gemäß der Freedesktop Icon Theme Specification
     /// (z.B. "firefox", "system-search"). Das System ist verantwortlich, das passende Icon-Theme zu finden.
     pub icon_name: Option<String>,
     /// Der Typ der Anwendung, definiert durch [`ApplicationType`].
     pub app_type: ApplicationType,
     /// Optionale Liste von Kategorien, denen die Anwendung zugeordnet ist (z.B. "Network", "Office", "Utility").
-    /// Orientiert sich oft an den Kategorien der Freedesktop .desktop-Spezifikation.
+    /// Orientiert sich oft an den Kategorien der Freedesktop .desktop-Spezifikation. Wie die
+    /// Namen zu Haupt- und Unterkategorien gehören, bestimmt die
+    /// [`CategoryTaxonomy`](crate::entities::CategoryTaxonomy).
     pub categories: Option<Vec<String>>,
     /// Optionale Liste von Schlüsselwörtern, die für die Suche nach der Anwendung verwendet werden können.
     pub keywords: Option<Vec<String>>,
+    /// Vom Benutzer vergebene, frei wählbare Tags, mit denen Anwendungen unabhängig von den
+    /// festen Kategorien zu Sammlungen gruppiert werden (z.B. "Arbeit", "Spiele").
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Die MIME-Typen, die die Anwendung öffnen kann (z.B. "text/plain", "x-scheme-handler/https"),
+    /// wie im `MimeType`-Schlüssel ihres Desktop-Eintrags angegeben.
+    #[serde(default)]
+    pub mime_types: Vec<String>,
     /// Eine kurze, optionale Beschreibung der Funktionalität der Anwendung.
     pub description: Option<String>,
     /// Die Version der Anwendung, falls bekannt, repräsentiert durch [`novade_core::types::Version`].
     pub version: Option<Version>,
+    /// Wann die Anwendung archiviert wurde; `None` für aktive Anwendungen. Archivierte
+    /// Anwendungen bleiben gespeichert, werden aber in Listen und Suchen ausgeblendet, bis sie
+    /// wiederhergestellt oder endgültig gelöscht werden.
+    #[serde(default)]
+    pub deleted_at: Option<Timestamp>,
 }
 
 impl Application {
@@ -98,15 +160,95 @@ impl Application {
             executable_path,
             arguments: None,
             working_directory: None,
+            environment: None,
+            resource_limits: None,
+            sandbox: None,
             icon_name,
             app_type: ApplicationType::Desktop,
             categories: None,
             keywords: None,
+            tags: Vec::new(),
+            mime_types: Vec::new(),
             description: None,
             version: None,
+            deleted_at: None,
+        }
+    }
+
+    /// Ob die Anwendung archiviert ist.
+    pub fn is_archived(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Ergänzt die Metadaten dieser Anwendung um die von `other`.
+    ///
+    /// ID, Name, Typ und Startbefehl bleiben erhalten. Nicht gesetzte optionale Felder werden aus
+    /// `other` übernommen; Kategorien, Schlüsselwörter, Tags und MIME-Typen werden vereinigt.
+    pub fn merge_from(&mut self, other: &Application) {
+        fn fill<T: Clone>(target: &mut Option<T>, source: &Option<T>) {
+            if target.is_none() {
+                target.clone_from(source);
+            }
         }
+        fn union(target: &mut Vec<String>, source: &[String]) {
+            for value in source {
+                if !target.iter().any(|existing| existing.eq_ignore_ascii_case(value)) {
+                    target.push(value.clone());
+                }
+            }
+        }
+        fill(&mut self.display_name, &other.display_name);
+        fill(&mut self.arguments, &other.arguments);
+        fill(&mut self.working_directory, &other.working_directory);
+        fill(&mut self.environment, &other.environment);
+        fill(&mut self.resource_limits, &other.resource_limits);
+        fill(&mut self.sandbox, &other.sandbox);
+        fill(&mut self.icon_name, &other.icon_name);
+        fill(&mut self.description, &other.description);
+        fill(&mut self.version, &other.version);
+        for (target, source) in [(&mut self.categories, &other.categories), (&mut self.keywords, &other.keywords)] {
+            if let Some(source) = source {
+                union(target.get_or_insert_with(Vec::new), source);
+            }
+        }
+        union(&mut self.tags, &other.tags);
+        union(&mut self.mime_types, &other.mime_types);
     }
 
     // Weitere spezifische Konstruktoren oder Builder-Methoden könnten hier folgen,
     // z.B. `Application::new_cli(...)` oder ein `ApplicationBuilder`.
 }
+
+impl Entity for Application {
+    const ENTITY_TYPE: &'static str = "Application";
+}
+
+impl Validate for Application {
+    /// Name und Startbefehl dürfen nicht leer sein, Tags ebenfalls nicht; MIME-Typen
+    /// haben die Form `typ/untertyp`.
+    fn violations(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+        require_non_empty(&mut violations, "name", &self.name, "Name der Anwendung darf nicht leer sein.");
+        require_non_empty(
+            &mut violations,
+            "executable_path",
+            &self.executable_path,
+            "Pfad zur ausführbaren Datei darf nicht leer sein.",
+        );
+        for (index, tag) in self.tags.iter().enumerate() {
+            require_non_empty(&mut violations, &format!("tags[{}]", index), tag, "Tags dürfen nicht leer sein.");
+        }
+        for (index, mime_type) in self.mime_types.iter().enumerate() {
+            let valid = mime_type
+                .split_once('/')
+                .is_some_and(|(kind, subtype)| !kind.trim().is_empty() && !subtype.trim().is_empty());
+            if !valid {
+                violations.push(FieldViolation::new(
+                    format!("mime_types[{}]", index),
+                    format!("'{}' ist kein MIME-Typ der Form 'typ/untertyp'.", mime_type),
+                ));
+            }
+        }
+        violations
+    }
+}
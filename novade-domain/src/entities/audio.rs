@@ -0,0 +1,72 @@
+//! # Audio Entitäten (`entities::audio`)
+//!
+//! Definiert die Audiogeräte ([`AudioDevice`]) und die Audio-Streams der Anwendungen
+//! ([`AudioStream`]), die der [`AudioService`](crate::services::AudioService) verwaltet.
+//!
+//! Lautstärken sind so skaliert, wie sie der Benutzer wahrnimmt (wie in Lautstärkereglern
+//! üblich): 0.0 ist stumm, 1.0 entspricht 100 %. Werte bis [`MAX_VOLUME`] verstärken über
+//! 100 % hinaus.
+
+use crate::entities::Entity;
+use serde::{Deserialize, Serialize};
+
+/// Die höchste einstellbare Lautstärke (150 %).
+pub const MAX_VOLUME: f32 = 1.5;
+
+/// Ob ein Gerät Ton ausgibt oder aufnimmt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AudioDeviceKind {
+    /// Ein Ausgabegerät, z.B. Lautsprecher oder Kopfhörer.
+    Sink,
+    /// Ein Eingabegerät, z.B. ein Mikrofon.
+    Source,
+}
+
+/// Ein Audiogerät, identifiziert über seinen Namen im Audiosystem.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioDevice {
+    /// Der eindeutige, über Neustarts stabile Name (z.B. "alsa_output.pci-0000_00_1f.3.analog-stereo").
+    pub name: String,
+    /// Die Bezeichnung für die Oberfläche (z.B. "Eingebautes Audio Analog Stereo").
+    pub description: String,
+    /// Ob das Gerät Ton ausgibt oder aufnimmt.
+    pub kind: AudioDeviceKind,
+    /// Die Lautstärke (siehe Moduldokumentation).
+    pub volume: f32,
+    /// Ob das Gerät stummgeschaltet ist.
+    pub muted: bool,
+    /// Ob das Gerät das Standardgerät seiner Art ist.
+    pub is_default: bool,
+}
+
+impl Entity for AudioDevice {
+    const ENTITY_TYPE: &'static str = "AudioDevice";
+}
+
+/// Ob ein Stream Ton wiedergibt oder aufnimmt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AudioStreamKind {
+    Playback,
+    Recording,
+}
+
+/// Ein Audio-Stream einer Anwendung, z.B. die Wiedergabe eines Videos im Browser.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioStream {
+    /// Die ID des Streams im Audiosystem; nur gültig, solange der Stream besteht.
+    pub id: u32,
+    /// Der Name der Anwendung für die Oberfläche (z.B. "Firefox").
+    pub application: String,
+    /// Die Desktop-Datei-ID bzw. App-ID der Anwendung, falls bekannt.
+    pub app_id: Option<String>,
+    /// Ob der Stream Ton wiedergibt oder aufnimmt.
+    pub kind: AudioStreamKind,
+    /// Die Lautstärke (siehe Moduldokumentation).
+    pub volume: f32,
+    /// Ob der Stream stummgeschaltet ist.
+    pub muted: bool,
+}
+
+impl Entity for AudioStream {
+    const ENTITY_TYPE: &'static str = "AudioStream";
+}
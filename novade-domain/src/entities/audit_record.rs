@@ -0,0 +1,60 @@
+//! # Änderungsprotokoll Entität (`entities::audit_record`)
+//!
+//! Definiert die Entität [`AuditRecord`], die eine einzelne Änderung an einer Entität
+//! festhält: was geändert wurde, wie, wann und von wem. Die Dienste schreiben diese Einträge
+//! über den [`AuditService`](crate::services::AuditService), so dass sich z.B. Änderungen an
+//! Einstellungen oder die Registrierung von Anwendungen nachvollziehen lassen.
+
+use novade_core::types::{NovaId, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Die Art einer protokollierten Änderung.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuditOperation {
+    /// Die Entität wurde angelegt bzw. registriert.
+    Created,
+    /// Felder der Entität wurden geändert.
+    Updated,
+    /// Die Entität wurde archiviert.
+    Archived,
+    /// Eine archivierte Entität wurde wiederhergestellt.
+    Restored,
+    /// Die Entität wurde endgültig gelöscht.
+    Deleted,
+}
+
+/// Ein Eintrag im Änderungsprotokoll.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Die ID des Eintrags.
+    pub id: NovaId,
+    /// Der Typ der geänderten Entität (z.B. "Application"), wie in
+    /// [`Entity::ENTITY_TYPE`](crate::entities::Entity::ENTITY_TYPE).
+    pub entity_type: String,
+    /// Die ID der geänderten Entität; für Einstellungen deren Schlüssel.
+    pub entity_id: String,
+    /// Die Art der Änderung.
+    pub operation: AuditOperation,
+    /// Eine kurze Zusammenfassung der Änderung, bei [`AuditOperation::Updated`] die geänderten
+    /// Felder mit altem und neuem Wert.
+    pub summary: String,
+    /// Der Zeitpunkt der Änderung.
+    pub timestamp: Timestamp,
+    /// Wer die Änderung vorgenommen hat (z.B. ein Benutzername oder "system").
+    pub actor: String,
+}
+
+impl AuditRecord {
+    /// Erstellt einen Eintrag mit neuer ID zum aktuellen Zeitpunkt.
+    pub fn new(entity_type: &str, entity_id: &str, operation: AuditOperation, summary: String, actor: &str) -> Self {
+        Self {
+            id: NovaId::new_v7(),
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            operation,
+            summary,
+            timestamp: Timestamp::now(),
+            actor: actor.to_string(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! # Autostart Entität (`entities::autostart`)
+//!
+//! Definiert die Entität [`AutostartEntry`], die festhält, ob und wie eine Anwendung beim
+//! Start der Sitzung gestartet wird, sowie die Startbedingung [`AutostartCondition`].
+//!
+//! Anwendungen werden wie bei den MIME-Zuordnungen über ihre Desktop-Datei-ID referenziert
+//! (z.B. "nm-applet"), die dem [`Application::name`](crate::entities::Application::name)
+//! entspricht. Die Systemschicht speichert die Einträge als Desktop-Dateien in den
+//! XDG-Autostart-Verzeichnissen und startet beim Sitzungsbeginn genau die Einträge, die hier
+//! als aktiviert gelten.
+
+use crate::entities::Entity;
+use crate::validation::{require_non_empty, FieldViolation, Validate};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Die größte zulässige Startverzögerung.
+pub const MAX_AUTOSTART_DELAY: Duration = Duration::from_secs(600);
+
+/// Die Bedingung, unter der ein Autostart-Eintrag gestartet wird.
+///
+/// Dateipfade sind relativ zum Konfigurationsverzeichnis des Benutzers
+/// (`$XDG_CONFIG_HOME`), wie beim Schlüssel `AutostartCondition` der Desktop-Dateien.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AutostartCondition {
+    /// Immer starten.
+    #[default]
+    Always,
+    /// Nur starten, wenn die Datei existiert.
+    IfExists(String),
+    /// Nur starten, wenn die Datei nicht existiert.
+    UnlessExists(String),
+    /// Eine Bedingung, die NovaDE nicht auswertet (z.B. eine GSettings-Bedingung). Sie gilt
+    /// als erfüllt und bleibt beim Speichern erhalten.
+    Other(String),
+}
+
+/// Ein Autostart-Eintrag einer Anwendung.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AutostartEntry {
+    /// Die Desktop-Datei-ID der Anwendung (ohne ".desktop").
+    pub application_id: String,
+    /// Ob die Anwendung beim Sitzungsbeginn gestartet wird.
+    pub enabled: bool,
+    /// Die Verzögerung nach dem Sitzungsbeginn, bevor die Anwendung gestartet wird.
+    pub delay: Duration,
+    /// Die Bedingung, unter der die Anwendung gestartet wird.
+    pub condition: AutostartCondition,
+}
+
+impl AutostartEntry {
+    /// Erstellt einen aktivierten Eintrag ohne Verzögerung und Bedingung.
+    pub fn new(application_id: &str) -> Self {
+        Self {
+            application_id: application_id.to_string(),
+            enabled: true,
+            delay: Duration::ZERO,
+            condition: AutostartCondition::Always,
+        }
+    }
+}
+
+impl Entity for AutostartEntry {
+    const ENTITY_TYPE: &'static str = "AutostartEntry";
+}
+
+impl Validate for AutostartEntry {
+    /// Die Anwendungs-ID darf weder leer sein noch Pfadtrenner enthalten, die Verzögerung
+    /// höchstens [`MAX_AUTOSTART_DELAY`] betragen und Bedingungen müssen eine Datei angeben.
+    fn violations(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+        require_non_empty(&mut violations, "application_id", &self.application_id, "Die Anwendungs-ID darf nicht leer sein.");
+        if self.application_id.contains('/') || self.application_id.ends_with(".desktop") {
+            violations.push(FieldViolation::new(
+                "application_id",
+                format!("'{}' ist keine Desktop-Datei-ID (ohne Pfad und ohne \".desktop\").", self.application_id),
+            ));
+        }
+        if self.delay > MAX_AUTOSTART_DELAY {
+            violations.push(FieldViolation::new(
+                "delay",
+                format!("Die Verzögerung darf höchstens {} Sekunden betragen.", MAX_AUTOSTART_DELAY.as_secs()),
+            ));
+        }
+        match &self.condition {
+            AutostartCondition::IfExists(path) | AutostartCondition::UnlessExists(path) => {
+                require_non_empty(&mut violations, "condition", path, "Die Bedingung muss eine Datei angeben.");
+            }
+            AutostartCondition::Always | AutostartCondition::Other(_) => {}
+        }
+        violations
+    }
+}
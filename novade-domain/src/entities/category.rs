@@ -0,0 +1,346 @@
+//! # Anwendungskategorien (`entities::category`)
+//!
+//! Definiert die Kategorien, nach denen Anwendungen z.B. im Anwendungsmenü gruppiert werden
+//! ([`Category`]), und ihren Baum ([`CategoryTaxonomy`]).
+//!
+//! Die Anwendungen selbst führen ihre Kategorien weiterhin als flache Liste
+//! ([`Application::categories`]), wie sie im `Categories`-Schlüssel ihres Desktop-Eintrags
+//! stehen. Erst die Taxonomie ordnet diese Namen Haupt- und Unterkategorien zu, sodass z.B.
+//! "IDE" unter "Development" und "Audio" unter "AudioVideo" erscheint.
+
+use crate::entities::application::Application;
+use crate::entities::Entity;
+use crate::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+/// Die Art einer Kategorie nach der Freedesktop Desktop Menu Specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CategoryKind {
+    /// Eine Hauptkategorie (z.B. "Development"), die im Menü einen eigenen Eintrag erhält.
+    Main,
+    /// Eine zusätzliche Kategorie (z.B. "IDE"), die eine Hauptkategorie verfeinert.
+    Additional,
+}
+
+/// Eine Kategorie im Baum der [`CategoryTaxonomy`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Category {
+    /// Der Name der Kategorie, wie er in Desktop-Einträgen steht (z.B. "Development").
+    pub id: String,
+    pub kind: CategoryKind,
+    /// Die übergeordnete Kategorie; `None` für die Wurzeln des Baums.
+    pub parent: Option<String>,
+}
+
+impl Entity for Category {
+    const ENTITY_TYPE: &'static str = "Category";
+}
+
+impl Category {
+    pub fn new(id: &str, kind: CategoryKind, parent: Option<&str>) -> Self {
+        Self { id: id.to_string(), kind, parent: parent.map(str::to_string) }
+    }
+}
+
+/// Die Hauptkategorien der Freedesktop-Spezifikation. "Audio" und "Video" setzen dort
+/// "AudioVideo" voraus und stehen deshalb darunter.
+const FREEDESKTOP_MAIN_CATEGORIES: &[(&str, Option<&str>)] = &[
+    ("AudioVideo", None),
+    ("Audio", Some("AudioVideo")),
+    ("Video", Some("AudioVideo")),
+    ("Development", None),
+    ("Education", None),
+    ("Game", None),
+    ("Graphics", None),
+    ("Network", None),
+    ("Office", None),
+    ("Science", None),
+    ("Settings", None),
+    ("System", None),
+    ("Utility", None),
+];
+
+/// Die zusätzlichen Kategorien der Freedesktop-Spezifikation mit der Hauptkategorie, unter
+/// der sie einsortiert werden. Nennt die Spezifikation mehrere passende Hauptkategorien,
+/// steht hier die erste.
+const FREEDESKTOP_ADDITIONAL_CATEGORIES: &[(&str, &str)] = &[
+    ("Building", "Development"),
+    ("Debugger", "Development"),
+    ("IDE", "Development"),
+    ("GUIDesigner", "Development"),
+    ("Profiling", "Development"),
+    ("RevisionControl", "Development"),
+    ("Translation", "Development"),
+    ("WebDevelopment", "Development"),
+    ("Calendar", "Office"),
+    ("ContactManagement", "Office"),
+    ("Database", "Office"),
+    ("Dictionary", "Office"),
+    ("Chart", "Office"),
+    ("Email", "Office"),
+    ("Finance", "Office"),
+    ("FlowChart", "Office"),
+    ("PDA", "Office"),
+    ("ProjectManagement", "Office"),
+    ("Presentation", "Office"),
+    ("Spreadsheet", "Office"),
+    ("WordProcessor", "Office"),
+    ("2DGraphics", "Graphics"),
+    ("VectorGraphics", "2DGraphics"),
+    ("RasterGraphics", "2DGraphics"),
+    ("3DGraphics", "Graphics"),
+    ("Scanning", "Graphics"),
+    ("OCR", "Scanning"),
+    ("Photography", "Graphics"),
+    ("Publishing", "Graphics"),
+    ("Viewer", "Graphics"),
+    ("DesktopSettings", "Settings"),
+    ("HardwareSettings", "Settings"),
+    ("Printing", "HardwareSettings"),
+    ("PackageManager", "Settings"),
+    ("Dialup", "Network"),
+    ("InstantMessaging", "Network"),
+    ("Chat", "Network"),
+    ("IRCClient", "Network"),
+    ("Feed", "Network"),
+    ("FileTransfer", "Network"),
+    ("HamRadio", "Network"),
+    ("News", "Network"),
+    ("P2P", "Network"),
+    ("RemoteAccess", "Network"),
+    ("Telephony", "Network"),
+    ("VideoConference", "Network"),
+    ("WebBrowser", "Network"),
+    ("Midi", "Audio"),
+    ("Mixer", "Audio"),
+    ("Sequencer", "Audio"),
+    ("Tuner", "Audio"),
+    ("Music", "Audio"),
+    ("TV", "Video"),
+    ("AudioVideoEditing", "AudioVideo"),
+    ("Player", "AudioVideo"),
+    ("Recorder", "AudioVideo"),
+    ("DiscBurning", "AudioVideo"),
+    ("ActionGame", "Game"),
+    ("AdventureGame", "Game"),
+    ("ArcadeGame", "Game"),
+    ("BoardGame", "Game"),
+    ("BlocksGame", "Game"),
+    ("CardGame", "Game"),
+    ("KidsGame", "Game"),
+    ("LogicGame", "Game"),
+    ("RolePlaying", "Game"),
+    ("Shooter", "Game"),
+    ("Simulation", "Game"),
+    ("SportsGame", "Game"),
+    ("StrategyGame", "Game"),
+    ("Art", "Education"),
+    ("Construction", "Education"),
+    ("Languages", "Education"),
+    ("Economy", "Education"),
+    ("Geography", "Education"),
+    ("History", "Education"),
+    ("Humanities", "Education"),
+    ("Literature", "Education"),
+    ("Spirituality", "Education"),
+    ("Sports", "Education"),
+    ("ArtificialIntelligence", "Science"),
+    ("Astronomy", "Science"),
+    ("Biology", "Science"),
+    ("Chemistry", "Science"),
+    ("ComputerScience", "Science"),
+    ("DataVisualization", "Science"),
+    ("Electricity", "Science"),
+    ("Geology", "Science"),
+    ("Geoscience", "Science"),
+    ("ImageProcessing", "Science"),
+    ("Maps", "Science"),
+    ("Math", "Science"),
+    ("NumericalAnalysis", "Math"),
+    ("MedicalSoftware", "Science"),
+    ("ParallelComputing", "Science"),
+    ("Physics", "Science"),
+    ("Robotics", "Science"),
+    ("Emulator", "System"),
+    ("FileManager", "System"),
+    ("TerminalEmulator", "System"),
+    ("Filesystem", "System"),
+    ("Monitor", "System"),
+    ("Security", "System"),
+    ("Accessibility", "Utility"),
+    ("TextTools", "Utility"),
+    ("Archiving", "Utility"),
+    ("Compression", "Archiving"),
+    ("FileTools", "Utility"),
+    ("Calculator", "Utility"),
+    ("Clock", "Utility"),
+    ("TextEditor", "Utility"),
+    ("Documentation", "Utility"),
+];
+
+/// Der Baum der bekannten Kategorien.
+///
+/// Jede Kategorie hat höchstens eine übergeordnete Kategorie; Namen werden ohne
+/// Unterscheidung von Groß-/Kleinschreibung verglichen. Die Reihenfolge, in der Kategorien
+/// hinzugefügt wurden, bleibt erhalten.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::CategoryTaxonomy;
+///
+/// let taxonomy = CategoryTaxonomy::freedesktop();
+/// let path: Vec<&str> = taxonomy.path("ocr").iter().map(|category| category.id.as_str()).collect();
+/// assert_eq!(path, vec!["Graphics", "Scanning", "OCR"]);
+/// assert!(taxonomy.subtree("AudioVideo").iter().any(|category| category.id == "Music"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CategoryTaxonomy {
+    categories: Vec<Category>,
+}
+
+impl CategoryTaxonomy {
+    /// Ein Baum ohne Kategorien.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Der Baum der Haupt- und zusätzlichen Kategorien der Freedesktop Desktop Menu
+    /// Specification.
+    pub fn freedesktop() -> Self {
+        let main = FREEDESKTOP_MAIN_CATEGORIES.iter().map(|(id, parent)| Category::new(id, CategoryKind::Main, *parent));
+        let additional =
+            FREEDESKTOP_ADDITIONAL_CATEGORIES.iter().map(|(id, parent)| Category::new(id, CategoryKind::Additional, Some(parent)));
+        Self { categories: main.chain(additional).collect() }
+    }
+
+    /// Fügt eine Kategorie hinzu, z.B. eine herstellerspezifische ("X-GNOME-Utilities").
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError` für einen leeren oder bereits vergebenen Namen oder
+    /// eine unbekannte übergeordnete Kategorie. Da die übergeordnete Kategorie vorher
+    /// existieren muss, kann kein Zyklus entstehen.
+    pub fn add(&mut self, category: Category) -> DomainResult<()> {
+        let invalid = |field: &str, message: String| Err(DomainError::ValidationError { field: field.to_string(), message });
+        if category.id.trim().is_empty() {
+            return invalid("id", "Der Name einer Kategorie darf nicht leer sein.".to_string());
+        }
+        if self.get(&category.id).is_some() {
+            return invalid("id", format!("Die Kategorie '{}' existiert bereits.", category.id));
+        }
+        if let Some(parent) = category.parent.as_deref().filter(|parent| self.get(parent).is_none()) {
+            return invalid("parent", format!("Die übergeordnete Kategorie '{}' existiert nicht.", parent));
+        }
+        self.categories.push(category);
+        Ok(())
+    }
+
+    /// Alle Kategorien in der Reihenfolge, in der sie hinzugefügt wurden.
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Die Kategorie mit dem Namen `id`.
+    pub fn get(&self, id: &str) -> Option<&Category> {
+        self.categories.iter().find(|category| category.id.eq_ignore_ascii_case(id))
+    }
+
+    /// Die Kategorien ohne übergeordnete Kategorie.
+    pub fn roots(&self) -> Vec<&Category> {
+        self.categories.iter().filter(|category| category.parent.is_none()).collect()
+    }
+
+    /// Die direkten Unterkategorien von `id`.
+    pub fn children(&self, id: &str) -> Vec<&Category> {
+        self.categories.iter().filter(|category| category.parent.as_deref().is_some_and(|parent| parent.eq_ignore_ascii_case(id))).collect()
+    }
+
+    /// Der Pfad von der Wurzel bis zur Kategorie `id` (einschließlich); leer für unbekannte
+    /// Kategorien.
+    pub fn path(&self, id: &str) -> Vec<&Category> {
+        let mut path = Vec::new();
+        let mut current = self.get(id);
+        while let Some(category) = current {
+            path.push(category);
+            current = category.parent.as_deref().and_then(|parent| self.get(parent));
+        }
+        path.reverse();
+        path
+    }
+
+    /// Die Kategorie `id` und alle ihr untergeordneten Kategorien; leer für unbekannte
+    /// Kategorien.
+    pub fn subtree(&self, id: &str) -> Vec<&Category> {
+        let mut subtree: Vec<&Category> = self.get(id).into_iter().collect();
+        let mut index = 0;
+        while let Some(category) = subtree.get(index) {
+            let children = self.children(&category.id);
+            subtree.extend(children);
+            index += 1;
+        }
+        subtree
+    }
+
+    /// Die bekannten Kategorien der Anwendung, ohne Duplikate. Unbekannte Namen in
+    /// [`Application::categories`] werden übergangen.
+    pub fn categories_of(&self, application: &Application) -> Vec<&Category> {
+        let mut categories: Vec<&Category> = Vec::new();
+        for category in application.categories.iter().flatten().filter_map(|id| self.get(id)) {
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        categories
+    }
+
+    /// Die Hauptkategorie, unter der die Anwendung im Menü erscheint: die spezifischste ihrer
+    /// Hauptkategorien (z.B. "Audio" statt "AudioVideo") oder, wenn sie keine nennt, die
+    /// Hauptkategorie über ihrer ersten zusätzlichen Kategorie.
+    pub fn primary_category(&self, application: &Application) -> Option<&Category> {
+        let categories = self.categories_of(application);
+        let main = categories
+            .iter()
+            .filter(|category| category.kind == CategoryKind::Main)
+            .max_by_key(|category| self.path(&category.id).len());
+        main.copied().or_else(|| {
+            categories
+                .first()
+                .and_then(|category| self.path(&category.id).into_iter().rev().find(|ancestor| ancestor.kind == CategoryKind::Main))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_freedesktop_taxonomy_is_a_tree() {
+        let taxonomy = CategoryTaxonomy::freedesktop();
+        let mut rebuilt = CategoryTaxonomy::new();
+        for category in taxonomy.categories() {
+            rebuilt.add(category.clone()).unwrap();
+        }
+        assert_eq!(rebuilt, taxonomy);
+        assert!(matches!(
+            rebuilt.add(Category::new("ide", CategoryKind::Additional, Some("Development"))),
+            Err(DomainError::ValidationError { field, .. }) if field == "id"
+        ));
+        assert!(matches!(
+            rebuilt.add(Category::new("X-Custom", CategoryKind::Additional, Some("Unknown"))),
+            Err(DomainError::ValidationError { field, .. }) if field == "parent"
+        ));
+    }
+
+    #[test]
+    fn test_primary_category() {
+        let taxonomy = CategoryTaxonomy::freedesktop();
+        let mut app = Application::new_desktop("player".to_string(), "/usr/bin/player".to_string(), None);
+        app.categories = Some(vec!["GTK".to_string(), "AudioVideo".to_string(), "Audio".to_string(), "Player".to_string()]);
+        assert_eq!(taxonomy.primary_category(&app).unwrap().id, "Audio");
+        assert_eq!(taxonomy.categories_of(&app).len(), 3);
+        app.categories = Some(vec!["Compression".to_string()]);
+        assert_eq!(taxonomy.primary_category(&app).unwrap().id, "Utility");
+        app.categories = None;
+        assert!(taxonomy.primary_category(&app).is_none());
+    }
+}
@@ -0,0 +1,233 @@
+//! # Bildschirmanordnung Entitäten (`entities::display`)
+//!
+//! Definiert die Entität [`DisplayLayout`], die festhält, wie die Bildschirme einer
+//! bestimmten Kombination von Anschlüssen angeordnet sind: Position, Modus, Skalierung,
+//! Drehung und Hauptbildschirm jedes Anschlusses ([`OutputConfiguration`]). Der
+//! [`DisplayService`](crate::services::DisplayService) speichert ein Layout je
+//! Anschlusskombination, so dass der Compositor es beim erneuten Anschließen derselben
+//! Bildschirme wiederherstellen kann.
+
+use crate::entities::Entity;
+use crate::validation::{require_non_empty, FieldViolation, Validate};
+use serde::{Deserialize, Serialize};
+
+/// Kleinste erlaubte Skalierung eines Bildschirms.
+pub const MIN_SCALE: f64 = 0.5;
+/// Größte erlaubte Skalierung eines Bildschirms.
+pub const MAX_SCALE: f64 = 4.0;
+
+/// Der Schlüssel, unter dem das Layout für die Anschlüsse `connectors` gespeichert wird: die
+/// sortierten Anschlussnamen, durch Kommas getrennt (z.B. "DP-1,eDP-1").
+pub fn layout_key<'a>(connectors: impl IntoIterator<Item = &'a str>) -> String {
+    let mut connectors: Vec<&str> = connectors.into_iter().collect();
+    connectors.sort_unstable();
+    connectors.join(",")
+}
+
+/// Ein Anzeigemodus eines Bildschirms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DisplayMode {
+    /// Die Breite in physischen Pixeln.
+    pub width: u32,
+    /// Die Höhe in physischen Pixeln.
+    pub height: u32,
+    /// Die Bildwiederholrate in Millihertz (z.B. 60000); 0, wenn unbekannt.
+    pub refresh_mhz: u32,
+}
+
+impl DisplayMode {
+    pub fn new(width: u32, height: u32, refresh_mhz: u32) -> Self {
+        Self { width, height, refresh_mhz }
+    }
+}
+
+/// Die Drehung eines Bildschirms im Uhrzeigersinn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Rotation {
+    #[default]
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl Rotation {
+    /// Ob Breite und Höhe durch die Drehung vertauscht werden.
+    pub fn swaps_axes(&self) -> bool {
+        matches!(self, Rotation::Rotate90 | Rotation::Rotate270)
+    }
+}
+
+/// Die Einstellungen eines Anschlusses innerhalb eines [`DisplayLayout`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputConfiguration {
+    /// Der Name des Anschlusses (z.B. "DP-1" oder "eDP-1").
+    pub connector: String,
+    /// Ob der Bildschirm verwendet wird; deaktivierte Bildschirme gehören nicht zum Desktop.
+    pub enabled: bool,
+    /// Die X-Koordinate der linken oberen Ecke im Desktop, in logischen Pixeln.
+    pub x: i32,
+    /// Die Y-Koordinate der linken oberen Ecke im Desktop, in logischen Pixeln.
+    pub y: i32,
+    /// Der Anzeigemodus.
+    pub mode: DisplayMode,
+    /// Die Skalierung zwischen physischen und logischen Pixeln (z.B. 2.0 für HiDPI).
+    pub scale: f64,
+    /// Die Drehung.
+    pub rotation: Rotation,
+    /// Ob dies der Hauptbildschirm ist, z.B. für die Leiste und neue Fenster.
+    pub primary: bool,
+}
+
+impl OutputConfiguration {
+    /// Erstellt eine aktive Konfiguration an Position (0, 0) mit Skalierung 1 ohne Drehung.
+    pub fn new(connector: &str, mode: DisplayMode) -> Self {
+        Self {
+            connector: connector.to_string(),
+            enabled: true,
+            x: 0,
+            y: 0,
+            mode,
+            scale: 1.0,
+            rotation: Rotation::Normal,
+            primary: false,
+        }
+    }
+
+    /// Die Größe im Desktop in logischen Pixeln, d.h. nach Drehung und Skalierung.
+    pub fn logical_size(&self) -> (u32, u32) {
+        let (width, height) = if self.rotation.swaps_axes() {
+            (self.mode.height, self.mode.width)
+        } else {
+            (self.mode.width, self.mode.height)
+        };
+        let scale = if self.scale > 0.0 { self.scale } else { 1.0 };
+        ((width as f64 / scale).round() as u32, (height as f64 / scale).round() as u32)
+    }
+
+    /// Ob sich die Flächen beider Bildschirme im Desktop überschneiden. Berühren sich nur
+    /// die Kanten, überschneiden sie sich nicht.
+    pub fn overlaps(&self, other: &OutputConfiguration) -> bool {
+        let (width, height) = self.logical_size();
+        let (other_width, other_height) = other.logical_size();
+        (self.x as i64) < other.x as i64 + other_width as i64
+            && (other.x as i64) < self.x as i64 + width as i64
+            && (self.y as i64) < other.y as i64 + other_height as i64
+            && (other.y as i64) < self.y as i64 + height as i64
+    }
+}
+
+/// Die Anordnung der Bildschirme für eine Kombination von Anschlüssen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DisplayLayout {
+    /// Die Konfiguration jedes Anschlusses.
+    pub outputs: Vec<OutputConfiguration>,
+}
+
+impl DisplayLayout {
+    pub fn new(outputs: Vec<OutputConfiguration>) -> Self {
+        Self { outputs }
+    }
+
+    /// Der Schlüssel des Layouts (siehe [`layout_key`]).
+    pub fn key(&self) -> String {
+        layout_key(self.outputs.iter().map(|output| output.connector.as_str()))
+    }
+
+    /// Die Konfiguration des Anschlusses `connector`.
+    pub fn output(&self, connector: &str) -> Option<&OutputConfiguration> {
+        self.outputs.iter().find(|output| output.connector == connector)
+    }
+
+    /// Der Hauptbildschirm.
+    pub fn primary(&self) -> Option<&OutputConfiguration> {
+        self.outputs.iter().find(|output| output.enabled && output.primary)
+    }
+}
+
+impl Entity for DisplayLayout {
+    const ENTITY_TYPE: &'static str = "DisplayLayout";
+}
+
+impl Validate for DisplayLayout {
+    /// Mindestens ein Bildschirm muss aktiv und genau einer davon der Hauptbildschirm sein;
+    /// Anschlüsse sind eindeutig, Modi und Skalierungen gültig, und aktive Bildschirme
+    /// überschneiden sich nicht.
+    fn violations(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+        let enabled: Vec<(usize, &OutputConfiguration)> =
+            self.outputs.iter().enumerate().filter(|(_, output)| output.enabled).collect();
+        if enabled.is_empty() {
+            violations.push(FieldViolation::new("outputs", "Mindestens ein Bildschirm muss aktiv sein."));
+        } else if enabled.iter().filter(|(_, output)| output.primary).count() != 1 {
+            violations.push(FieldViolation::new("outputs", "Genau ein aktiver Bildschirm muss der Hauptbildschirm sein."));
+        }
+        for (index, output) in self.outputs.iter().enumerate() {
+            let field = |name: &str| format!("outputs[{}].{}", index, name);
+            require_non_empty(&mut violations, &field("connector"), &output.connector, "Der Anschluss darf nicht leer sein.");
+            if self.outputs[..index].iter().any(|earlier| earlier.connector == output.connector) {
+                violations.push(FieldViolation::new(
+                    field("connector"),
+                    format!("Der Anschluss '{}' kommt mehrfach vor.", output.connector),
+                ));
+            }
+            if output.mode.width == 0 || output.mode.height == 0 {
+                violations.push(FieldViolation::new(field("mode"), "Breite und Höhe des Modus müssen größer als 0 sein."));
+            }
+            if !(MIN_SCALE..=MAX_SCALE).contains(&output.scale) {
+                violations.push(FieldViolation::new(
+                    field("scale"),
+                    format!("Die Skalierung muss zwischen {} und {} liegen.", MIN_SCALE, MAX_SCALE),
+                ));
+            }
+            if output.primary && !output.enabled {
+                violations.push(FieldViolation::new(field("primary"), "Ein deaktivierter Bildschirm kann nicht Hauptbildschirm sein."));
+            }
+        }
+        for (position, (index, output)) in enabled.iter().enumerate() {
+            if let Some((_, other)) = enabled[..position].iter().find(|(_, other)| other.overlaps(output)) {
+                violations.push(FieldViolation::new(
+                    format!("outputs[{}]", index),
+                    format!("'{}' überschneidet sich mit '{}'.", output.connector, other.connector),
+                ));
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DomainError;
+
+    fn output(connector: &str, x: i32, width: u32, height: u32) -> OutputConfiguration {
+        OutputConfiguration { x, ..OutputConfiguration::new(connector, DisplayMode::new(width, height, 60000)) }
+    }
+
+    #[test]
+    fn test_layout_validation() {
+        let mut laptop = output("eDP-1", 0, 2880, 1800);
+        laptop.scale = 2.0;
+        laptop.primary = true;
+        let mut monitor = output("DP-1", 1440, 2560, 1440);
+        let mut layout = DisplayLayout::new(vec![laptop.clone(), monitor.clone()]);
+        assert!(layout.validate().is_ok());
+        assert_eq!(layout.key(), "DP-1,eDP-1");
+
+        monitor.x = 1000;
+        layout.outputs[1] = monitor.clone();
+        assert!(matches!(layout.validate(), Err(DomainError::ValidationError { field, .. }) if field == "outputs[1]"));
+
+        // Gedreht ist der Monitor nur 1440 logische Pixel breit und passt links neben den Laptop.
+        monitor.x = -1440;
+        monitor.rotation = Rotation::Rotate90;
+        layout.outputs[1] = monitor;
+        assert!(layout.validate().is_ok());
+
+        layout.outputs[0].primary = false;
+        layout.outputs[0].scale = 8.0;
+        let fields: Vec<String> = layout.violations().into_iter().map(|violation| violation.field).collect();
+        assert_eq!(fields, vec!["outputs", "outputs[0].scale"]);
+    }
+}
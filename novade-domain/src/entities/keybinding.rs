@@ -0,0 +1,103 @@
+//! # Tastenkürzel Entität (`entities::keybinding`)
+//!
+//! Definiert die Entität [`Keybinding`], die eine Tastenkombination (Accelerator) in einem
+//! bestimmten Kontext einer Aktion zuordnet, sowie [`normalize_accelerator`], das
+//! gleichwertige Schreibweisen einer Tastenkombination auf eine kanonische Form bringt.
+
+use crate::entities::Entity;
+use crate::{DomainError, DomainResult};
+use novade_core::types::NovaId;
+use serde::{Deserialize, Serialize};
+
+/// Der Kontext für Tastenkürzel, die unabhängig vom fokussierten Element gelten.
+pub const GLOBAL_CONTEXT: &str = "global";
+
+/// Die unterstützten Modifikatortasten in kanonischer Reihenfolge, jeweils mit ihren
+/// akzeptierten Schreibweisen (in Kleinbuchstaben).
+const MODIFIERS: [(&str, &[&str]); 4] = [
+    ("Super", &["super", "logo", "meta", "win"]),
+    ("Ctrl", &["ctrl", "control"]),
+    ("Alt", &["alt"]),
+    ("Shift", &["shift"]),
+];
+
+/// Bringt eine Tastenkombination wie `"alt+ctrl+t"` in die kanonische Form `"Ctrl+Alt+T"`.
+///
+/// Modifikatoren werden in der Reihenfolge `Super`, `Ctrl`, `Alt`, `Shift` geschrieben;
+/// eine Taste aus einem einzelnen Zeichen wird großgeschrieben, benannte Tasten
+/// (z.B. `Return`, `F1`) bleiben unverändert.
+///
+/// # Rückgabe
+/// `DomainError::ValidationError`, wenn die Kombination leer ist, einen unbekannten oder
+/// doppelten Modifikator enthält oder keine Taste außer Modifikatoren angibt.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::keybinding::normalize_accelerator;
+///
+/// assert_eq!(normalize_accelerator("alt+control+t").unwrap(), "Ctrl+Alt+T");
+/// assert_eq!(normalize_accelerator("Super+Return").unwrap(), "Super+Return");
+/// assert!(normalize_accelerator("Ctrl+Shift").is_err());
+/// assert!(normalize_accelerator("Hyper+T").is_err());
+/// ```
+pub fn normalize_accelerator(accelerator: &str) -> DomainResult<String> {
+    let invalid = |message: String| Err(DomainError::ValidationError { field: "accelerator".to_string(), message });
+
+    let parts: Vec<&str> = accelerator.split('+').map(str::trim).collect();
+    let Some((key, modifiers)) = parts.split_last() else {
+        return invalid("Die Tastenkombination ist leer.".to_string());
+    };
+    let is_modifier = |part: &str| MODIFIERS.iter().any(|(_, names)| names.contains(&part.to_lowercase().as_str()));
+    if key.is_empty() || is_modifier(key) {
+        return invalid(format!("'{}' enthält keine Taste außer Modifikatoren.", accelerator));
+    }
+
+    let mut pressed = [false; MODIFIERS.len()];
+    for modifier in modifiers {
+        let lower = modifier.to_lowercase();
+        let Some(index) = MODIFIERS.iter().position(|(_, names)| names.contains(&lower.as_str())) else {
+            return invalid(format!("Unbekannter Modifikator '{}'.", modifier));
+        };
+        if std::mem::replace(&mut pressed[index], true) {
+            return invalid(format!("Modifikator '{}' ist doppelt angegeben.", modifier));
+        }
+    }
+
+    let key = if key.chars().count() == 1 { key.to_uppercase() } else { key.to_string() };
+    let mut normalized: Vec<String> =
+        MODIFIERS.iter().zip(pressed).filter(|(_, pressed)| *pressed).map(|((name, _), _)| name.to_string()).collect();
+    normalized.push(key);
+    Ok(normalized.join("+"))
+}
+
+/// Ordnet eine Tastenkombination in einem Kontext einer Aktion zu.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keybinding {
+    /// Ein eindeutiger Identifikator für das Tastenkürzel.
+    pub id: NovaId,
+    /// Die Tastenkombination in kanonischer Form (siehe [`normalize_accelerator`]).
+    pub accelerator: String,
+    /// Der Bezeichner der auszulösenden Aktion (z.B. "workspace.next" oder "launch.terminal").
+    pub action: String,
+    /// Der Kontext, in dem das Tastenkürzel gilt, z.B. [`GLOBAL_CONTEXT`] oder "window-switcher".
+    pub context: String,
+}
+
+impl Entity for Keybinding {
+    const ENTITY_TYPE: &'static str = "Keybinding";
+}
+
+impl Keybinding {
+    /// Erstellt ein Tastenkürzel mit neuer ID. Die Tastenkombination wird normalisiert.
+    ///
+    /// # Rückgabe
+    /// `DomainError::ValidationError`, wenn die Tastenkombination ungültig ist.
+    pub fn new(accelerator: &str, action: &str, context: &str) -> DomainResult<Self> {
+        Ok(Self {
+            id: NovaId::new(),
+            accelerator: normalize_accelerator(accelerator)?,
+            action: action.to_string(),
+            context: context.to_string(),
+        })
+    }
+}
@@ -0,0 +1,25 @@
+//! # Startprotokoll Entität (`entities::launch_record`)
+//!
+//! Definiert die Entität [`LaunchRecord`], die einen einzelnen Start einer Anwendung
+//! festhält. Aus der Gesamtheit dieser Einträge berechnet der
+//! [`LaunchHistoryService`](crate::services::LaunchHistoryService) die Frecency
+//! (Häufigkeit gewichtet nach Aktualität) einer Anwendung.
+
+use novade_core::types::{NovaId, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Ein Start einer Anwendung.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchRecord {
+    /// Die ID der gestarteten [`Application`](crate::entities::Application).
+    pub application_id: NovaId,
+    /// Der Zeitpunkt des Starts.
+    pub launched_at: Timestamp,
+}
+
+impl LaunchRecord {
+    /// Erstellt einen Eintrag für einen Start zum gegebenen Zeitpunkt.
+    pub fn new(application_id: NovaId, launched_at: Timestamp) -> Self {
+        Self { application_id, launched_at }
+    }
+}
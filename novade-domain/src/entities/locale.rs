@@ -0,0 +1,192 @@
+//! # Regionaleinstellungen (`entities::locale`)
+//!
+//! Definiert die Regionaleinstellungen eines Benutzers ([`LocaleSettings`]): die Sprache der
+//! Oberfläche, die Region für Datums- und Zahlenformate, abweichende Formate
+//! ([`DateFormat`], [`NumberFormat`]) und die Tastaturbelegungen ([`KeyboardLayout`]).
+//!
+//! Sprache und Region sind Locale-IDs nach BCP 47 (z.B. "de-DE", "sr-Latn-RS"). Die
+//! POSIX-Schreibweise ("de_DE.UTF-8") wird mit [`normalize_locale_id`] umgewandelt.
+
+use crate::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Die Sprache, die gilt, wenn weder der Benutzer noch die Kernkonfiguration eine gültige
+/// Locale-ID vorgeben.
+pub const FALLBACK_LOCALE: &str = "en-US";
+
+/// Die höchste Anzahl an Tastaturbelegungen, zwischen denen gewechselt werden kann (die
+/// Grenze von XKB).
+pub const MAX_KEYBOARD_LAYOUTS: usize = 4;
+
+fn invalid_locale(field: &str, id: &str) -> DomainError {
+    DomainError::ValidationError {
+        field: field.to_string(),
+        message: format!("'{}' ist keine gültige Locale-ID wie \"de-DE\" oder \"sr-Latn-RS\".", id),
+    }
+}
+
+/// Prüft eine Locale-ID und bringt sie in die BCP-47-Schreibweise: Sprache klein, Schrift mit
+/// großem Anfangsbuchstaben, Region groß (z.B. "de_de.UTF-8" → "de-DE").
+///
+/// Zeichensatz (".UTF-8") und Modifikator ("@euro") der POSIX-Schreibweise werden entfernt.
+///
+/// # Fehler
+/// `DomainError::ValidationError` für das Feld `locale`, wenn die ID nicht aus einer Sprache
+/// (2–3 Buchstaben), optional einer Schrift (4 Buchstaben) und optional einer Region (2
+/// Buchstaben oder 3 Ziffern) besteht.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::locale::normalize_locale_id;
+///
+/// assert_eq!(normalize_locale_id("de_AT.UTF-8").unwrap(), "de-AT");
+/// assert_eq!(normalize_locale_id("sr-latn-rs").unwrap(), "sr-Latn-RS");
+/// assert!(normalize_locale_id("deutsch").is_err());
+/// ```
+pub fn normalize_locale_id(id: &str) -> DomainResult<String> {
+    let trimmed = id.trim();
+    let without_modifier = trimmed.split('@').next().unwrap_or_default();
+    let without_codeset = without_modifier.split('.').next().unwrap_or_default();
+    let mut parts = without_codeset.split(['-', '_']);
+    let is_alpha = |part: &str, lengths: &[usize]| lengths.contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphabetic());
+
+    let language = parts.next().filter(|part| is_alpha(part, &[2, 3])).ok_or_else(|| invalid_locale("locale", id))?;
+    let mut normalized = language.to_ascii_lowercase();
+    let mut next = parts.next();
+    if let Some(script) = next.filter(|part| is_alpha(part, &[4])) {
+        normalized.push('-');
+        normalized.push_str(&script[..1].to_ascii_uppercase());
+        normalized.push_str(&script[1..].to_ascii_lowercase());
+        next = parts.next();
+    }
+    if let Some(region) = next {
+        let is_numeric = region.len() == 3 && region.chars().all(|c| c.is_ascii_digit());
+        if !is_alpha(region, &[2]) && !is_numeric {
+            return Err(invalid_locale("locale", id));
+        }
+        normalized.push('-');
+        normalized.push_str(&region.to_ascii_uppercase());
+    }
+    if parts.next().is_some() {
+        return Err(invalid_locale("locale", id));
+    }
+    Ok(normalized)
+}
+
+/// Das Datumsformat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DateFormat {
+    /// Das übliche Format der Region.
+    #[default]
+    Regional,
+    /// ISO 8601, z.B. "2026-12-31".
+    Iso8601,
+    /// Tag vor Monat, z.B. "31.12.2026".
+    DayMonthYear,
+    /// Monat vor Tag, z.B. "12/31/2026".
+    MonthDayYear,
+}
+
+impl DateFormat {
+    pub const ALL: [DateFormat; 4] = [DateFormat::Regional, DateFormat::Iso8601, DateFormat::DayMonthYear, DateFormat::MonthDayYear];
+
+    /// Der Name, unter dem das Format in den Einstellungen gespeichert wird.
+    pub fn id(self) -> &'static str {
+        match self {
+            DateFormat::Regional => "regional",
+            DateFormat::Iso8601 => "iso8601",
+            DateFormat::DayMonthYear => "day-month-year",
+            DateFormat::MonthDayYear => "month-day-year",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.id() == id)
+    }
+}
+
+/// Das Zahlenformat.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NumberFormat {
+    /// Das übliche Format der Region.
+    #[default]
+    Regional,
+    /// Dezimalpunkt und Komma als Tausendertrennzeichen, z.B. "1,234.5".
+    DecimalPoint,
+    /// Dezimalkomma und Punkt als Tausendertrennzeichen, z.B. "1.234,5".
+    DecimalComma,
+}
+
+impl NumberFormat {
+    pub const ALL: [NumberFormat; 3] = [NumberFormat::Regional, NumberFormat::DecimalPoint, NumberFormat::DecimalComma];
+
+    /// Der Name, unter dem das Format in den Einstellungen gespeichert wird.
+    pub fn id(self) -> &'static str {
+        match self {
+            NumberFormat::Regional => "regional",
+            NumberFormat::DecimalPoint => "decimal-point",
+            NumberFormat::DecimalComma => "decimal-comma",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.id() == id)
+    }
+}
+
+/// Eine XKB-Tastaturbelegung, optional mit Variante.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyboardLayout {
+    /// Die Belegung (z.B. "de").
+    pub layout: String,
+    /// Die Variante (z.B. "nodeadkeys").
+    pub variant: Option<String>,
+}
+
+impl KeyboardLayout {
+    /// Liest eine Belegung in der Schreibweise von XKB, "de" oder "de(nodeadkeys)".
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError` für das Feld `keyboard_layouts`, wenn Belegung oder
+    /// Variante leer sind oder andere Zeichen als Kleinbuchstaben, Ziffern, `_` und `-` enthalten.
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        let value = value.trim();
+        let (layout, variant) = match value.strip_suffix(')').and_then(|value| value.split_once('(')) {
+            Some((layout, variant)) => (layout, Some(variant)),
+            None => (value, None),
+        };
+        let is_name = |name: &str| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        };
+        if !is_name(layout) || variant.is_some_and(|variant| !is_name(variant)) {
+            return Err(DomainError::ValidationError {
+                field: "keyboard_layouts".to_string(),
+                message: format!("'{}' ist keine Tastaturbelegung wie \"de\" oder \"de(nodeadkeys)\".", value),
+            });
+        }
+        Ok(Self { layout: layout.to_string(), variant: variant.map(str::to_string) })
+    }
+}
+
+impl fmt::Display for KeyboardLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.variant {
+            Some(variant) => write!(f, "{}({})", self.layout, variant),
+            None => f.write_str(&self.layout),
+        }
+    }
+}
+
+/// Die wirksamen Regionaleinstellungen eines Benutzers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocaleSettings {
+    /// Die Sprache der Oberfläche als Locale-ID.
+    pub language: String,
+    /// Die Region für Datums-, Zahlen- und Währungsformate als Locale-ID.
+    pub region: String,
+    pub date_format: DateFormat,
+    pub number_format: NumberFormat,
+    /// Die Tastaturbelegungen; die erste ist die Standardbelegung.
+    pub keyboard_layouts: Vec<KeyboardLayout>,
+}
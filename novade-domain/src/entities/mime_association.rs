@@ -0,0 +1,123 @@
+//! # MIME-Zuordnung Entität (`entities::mime_association`)
+//!
+//! Definiert die Entität [`MimeAssociation`], die festhält, welche Anwendungen einen
+//! MIME-Typ öffnen. Sie entspricht den Gruppen `[Default Applications]`,
+//! `[Added Associations]` und `[Removed Associations]` einer `mimeapps.list` gemäß der
+//! freedesktop.org MIME Applications Associations Specification.
+//!
+//! Anwendungen werden über ihre Desktop-Datei-ID referenziert (z.B. "org.gnome.TextEditor"),
+//! die dem [`Application::name`](crate::entities::Application::name) entspricht.
+//! URL-Schemata werden als Pseudo-MIME-Typ `x-scheme-handler/<schema>` abgebildet.
+
+use crate::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+
+/// Der Präfix der Pseudo-MIME-Typen für URL-Schemata.
+pub const SCHEME_HANDLER_PREFIX: &str = "x-scheme-handler/";
+
+/// Die Zuordnungen eines MIME-Typs zu Anwendungen.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MimeAssociation {
+    /// Der MIME-Typ (z.B. "text/plain" oder "x-scheme-handler/https").
+    pub mime_type: String,
+    /// Die Standardanwendung für den MIME-Typ.
+    pub default_application: Option<String>,
+    /// Anwendungen, die zusätzlich zu den im Desktop-Eintrag angegebenen Typen für diesen
+    /// MIME-Typ angeboten werden, in Vorzugsreihenfolge.
+    pub added_applications: Vec<String>,
+    /// Anwendungen, die für diesen MIME-Typ nicht angeboten werden sollen, obwohl ihr
+    /// Desktop-Eintrag ihn angibt.
+    pub removed_applications: Vec<String>,
+}
+
+impl MimeAssociation {
+    /// Erstellt eine leere Zuordnung für einen MIME-Typ.
+    pub fn new(mime_type: &str) -> Self {
+        Self { mime_type: mime_type.to_string(), ..Self::default() }
+    }
+}
+
+/// Prüft, ob `mime_type` die Form `typ/untertyp` hat.
+///
+/// # Rückgabe
+/// `DomainError::ValidationError`, wenn Typ oder Untertyp fehlen oder Leerzeichen enthalten.
+pub fn validate_mime_type(mime_type: &str) -> DomainResult<()> {
+    let valid = |part: &str| !part.is_empty() && !part.contains(char::is_whitespace) && !part.contains('/');
+    match mime_type.split_once('/') {
+        Some((kind, subtype)) if valid(kind) && valid(subtype) => Ok(()),
+        _ => Err(DomainError::ValidationError {
+            field: "mime_type".to_string(),
+            message: format!("'{}' ist kein gültiger MIME-Typ der Form typ/untertyp.", mime_type),
+        }),
+    }
+}
+
+/// Der Pseudo-MIME-Typ eines URL-Schemas, z.B. `"x-scheme-handler/https"` für `"https"`.
+pub fn scheme_mime_type(scheme: &str) -> String {
+    format!("{}{}", SCHEME_HANDLER_PREFIX, scheme.to_lowercase())
+}
+
+/// Ob eine von einer Anwendung angegebene MIME-Typ-Angabe (auch mit Platzhalter wie
+/// `"image/*"`) den MIME-Typ abdeckt.
+pub fn mime_type_matches(declared: &str, mime_type: &str) -> bool {
+    match declared.strip_suffix("/*") {
+        Some(kind) => mime_type.split_once('/').is_some_and(|(actual, _)| actual.eq_ignore_ascii_case(kind)),
+        None => declared.eq_ignore_ascii_case(mime_type),
+    }
+}
+
+/// Bekannte Dateiendungen und ihre MIME-Typen.
+const EXTENSION_MIME_TYPES: &[(&str, &str)] = &[
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("rs", "text/rust"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("ods", "application/vnd.oasis.opendocument.spreadsheet"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("svg", "image/svg+xml"),
+    ("webp", "image/webp"),
+    ("mp3", "audio/mpeg"),
+    ("ogg", "audio/ogg"),
+    ("flac", "audio/flac"),
+    ("wav", "audio/x-wav"),
+    ("mp4", "video/mp4"),
+    ("mkv", "video/x-matroska"),
+    ("webm", "video/webm"),
+];
+
+/// Bestimmt den MIME-Typ einer Datei anhand ihrer Endung.
+///
+/// Dies ist eine Näherung für Fälle ohne Zugriff auf die Shared-MIME-Info-Datenbank;
+/// unbekannte Endungen ergeben `"application/octet-stream"`.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::mime_association::mime_type_for_file_name;
+///
+/// assert_eq!(mime_type_for_file_name("Urlaub.JPG"), "image/jpeg");
+/// assert_eq!(mime_type_for_file_name("/tmp/notizen.txt"), "text/plain");
+/// assert_eq!(mime_type_for_file_name("Makefile"), "application/octet-stream");
+/// ```
+pub fn mime_type_for_file_name(file_name: &str) -> &'static str {
+    let base_name = file_name.rsplit('/').next().unwrap_or(file_name);
+    base_name
+        .rsplit_once('.')
+        .filter(|(stem, _)| !stem.is_empty())
+        .and_then(|(_, extension)| {
+            EXTENSION_MIME_TYPES.iter().find(|(known, _)| known.eq_ignore_ascii_case(extension)).map(|(_, mime)| *mime)
+        })
+        .unwrap_or("application/octet-stream")
+}
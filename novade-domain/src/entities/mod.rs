@@ -6,21 +6,74 @@
 //!
 //! Jede Entität ist in ihrem eigenen Untermodul definiert:
 //! - [`application`]: Definiert [`Application`] und [`ApplicationType`].
+//! - [`audio`]: Definiert [`AudioDevice`], [`AudioDeviceKind`], [`AudioStream`] und [`AudioStreamKind`].
+//! - [`audit_record`]: Definiert [`AuditRecord`] und [`AuditOperation`].
+//! - [`autostart`]: Definiert [`AutostartEntry`] und [`AutostartCondition`].
+//! - [`category`]: Definiert [`Category`], [`CategoryKind`] und [`CategoryTaxonomy`].
+//! - [`display`]: Definiert [`DisplayLayout`], [`OutputConfiguration`], [`DisplayMode`] und [`Rotation`].
+//! - [`keybinding`]: Definiert [`Keybinding`].
+//! - [`launch_record`]: Definiert [`LaunchRecord`].
+//! - [`locale`]: Definiert [`LocaleSettings`], [`DateFormat`], [`NumberFormat`] und [`KeyboardLayout`].
+//! - [`mime_association`]: Definiert [`MimeAssociation`].
+//! - [`network`]: Definiert [`NetworkConnection`], [`ConnectionType`] und [`ConnectionState`].
+//! - [`notification`]: Definiert [`Notification`], [`NotificationAction`] und [`NotificationUrgency`].
+//! - [`power`]: Definiert [`PowerProfile`], [`BatteryState`] und [`ChargingState`].
 //! - [`user_preference`]: Definiert [`UserPreferenceSetting`] und [`PreferenceValue`].
-//! - [`workspace`]: Definiert [`Workspace`].
+//! - [`preference_schema`]: Definiert [`PreferenceSchema`] und [`PreferenceDefinition`].
+//! - [`recent_item`]: Definiert [`RecentItem`] und [`RecentItemExclusion`].
+//! - [`theme`]: Definiert [`Theme`], [`ColorPalette`] und [`FontSettings`].
+//! - [`workspace`]: Definiert [`Workspace`] und [`WorkspaceAssignmentRule`].
 //!
 //! Die wichtigsten Entitäten werden hier für einen einfacheren Zugriff aus anderen Teilen
 //! der `novade-domain` Crate oder von externen Crates re-exportiert.
 
 pub mod application;
+pub mod audio;
+pub mod audit_record;
+pub mod autostart;
+pub mod category;
+pub mod display;
+pub mod keybinding;
+pub mod launch_record;
+pub mod locale;
+pub mod mime_association;
+pub mod network;
+pub mod notification;
+pub mod power;
+pub mod preference_schema;
+pub mod recent_item;
+pub mod theme;
 pub mod user_preference;
 pub mod workspace;
 
+/// Eine Entität mit einem Typnamen, unter dem sie in Fehlermeldungen (siehe
+/// [`DomainError::not_found`](crate::DomainError::not_found)) und im Änderungsprotokoll
+/// erscheint.
+pub trait Entity {
+    /// Der Typ der Entität (z.B. "Application").
+    const ENTITY_TYPE: &'static str;
+}
+
 // Re-exportiere die Kernentitäten für einen einfacheren Zugriff.
 // Dies ermöglicht es, z.B. `novade_domain::entities::Application` anstelle von
 // `novade_domain::entities::application::Application` zu verwenden, wenn dieses Modul importiert wird.
 // Für den direkten Zugriff über `novade_domain::*` (wie in `lib.rs` konfiguriert) sind diese spezifischen
 // Re-Exporte hier weniger kritisch, aber sie sind nützlich für eine klare Struktur innerhalb des `entities`-Moduls.
-pub use application::{Application, ApplicationType};
+pub use application::{Application, ApplicationType, ResourceLimits, SandboxKind};
+pub use audio::{AudioDevice, AudioDeviceKind, AudioStream, AudioStreamKind};
+pub use audit_record::{AuditOperation, AuditRecord};
+pub use autostart::{AutostartCondition, AutostartEntry};
+pub use category::{Category, CategoryKind, CategoryTaxonomy};
+pub use display::{DisplayLayout, DisplayMode, OutputConfiguration, Rotation};
+pub use keybinding::Keybinding;
+pub use launch_record::LaunchRecord;
+pub use locale::{DateFormat, KeyboardLayout, LocaleSettings, NumberFormat};
+pub use mime_association::MimeAssociation;
+pub use network::{ConnectionState, ConnectionType, NetworkConnection};
+pub use notification::{Notification, NotificationAction, NotificationUrgency};
+pub use power::{BatteryState, ChargingState, PowerProfile};
+pub use preference_schema::{PreferenceDefinition, PreferenceSchema, PreferenceType};
+pub use recent_item::{RecentItem, RecentItemExclusion};
+pub use theme::{ColorPalette, FontSettings, Theme};
 pub use user_preference::{PreferenceValue, UserPreferenceSetting};
-pub use workspace::Workspace;
+pub use workspace::{Workspace, WorkspaceAssignmentRule};
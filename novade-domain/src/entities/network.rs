@@ -0,0 +1,68 @@
+//! # Netzwerk Entitäten (`entities::network`)
+//!
+//! Definiert die Netzwerkverbindungen ([`NetworkConnection`]), die der
+//! [`NetworkService`](crate::services::NetworkService) anzeigt und auf- bzw. abbaut.
+//!
+//! Eine Verbindung entspricht einem gespeicherten Verbindungsprofil (bzw. einem sichtbaren
+//! WLAN ohne Profil), nicht einer Netzwerkschnittstelle: Für eine WLAN-Karte kann es viele
+//! Verbindungen geben, von denen höchstens eine aktiv ist.
+
+use crate::entities::Entity;
+use serde::{Deserialize, Serialize};
+
+/// Die Art einer Verbindung.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ConnectionType {
+    Ethernet,
+    Wifi,
+    Vpn,
+}
+
+/// Der Zustand einer Verbindung.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ConnectionState {
+    #[default]
+    Disconnected,
+    /// Die Verbindung wird aufgebaut (z.B. Authentifizierung oder Adressvergabe).
+    Connecting,
+    Connected,
+    Disconnecting,
+}
+
+/// Eine Netzwerkverbindung.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkConnection {
+    /// Die eindeutige ID der Verbindung im Netzwerkdienst (z.B. die UUID des Profils).
+    pub id: String,
+    /// Der Name für die Oberfläche, bei WLAN die SSID.
+    pub name: String,
+    pub connection_type: ConnectionType,
+    pub state: ConnectionState,
+    /// Die Signalstärke in Prozent (0 bis 100); nur für WLAN-Verbindungen in Reichweite.
+    pub signal_strength: Option<u8>,
+    /// Die Netzwerkschnittstelle, über die die Verbindung besteht (z.B. "wlp3s0"), falls aktiv.
+    pub interface: Option<String>,
+}
+
+impl Entity for NetworkConnection {
+    const ENTITY_TYPE: &'static str = "NetworkConnection";
+}
+
+impl NetworkConnection {
+    /// Erstellt eine getrennte Verbindung ohne Signalstärke und Schnittstelle.
+    pub fn new(id: &str, name: &str, connection_type: ConnectionType) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            connection_type,
+            state: ConnectionState::Disconnected,
+            signal_strength: None,
+            interface: None,
+        }
+    }
+
+    /// Ob die Verbindung besteht oder gerade aufgebaut wird.
+    pub fn is_active(&self) -> bool {
+        matches!(self.state, ConnectionState::Connected | ConnectionState::Connecting)
+    }
+}
@@ -0,0 +1,137 @@
+//! # Benachrichtigung Entität (`entities::notification`)
+//!
+//! Definiert die Entität [`Notification`] für Desktop-Benachrichtigungen, wie sie ein
+//! Benachrichtigungsdienst (z.B. gemäß der freedesktop.org Notification-Spezifikation)
+//! entgegennimmt, anzeigt und in seiner Historie aufbewahrt.
+
+use crate::entities::Entity;
+use novade_core::types::{NovaId, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Die Dringlichkeit einer Benachrichtigung.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum NotificationUrgency {
+    /// Informative Benachrichtigung, z.B. ein abgeschlossener Download.
+    Low,
+    /// Die übliche Dringlichkeit.
+    #[default]
+    Normal,
+    /// Kritische Benachrichtigung, z.B. ein fast leerer Akku. Wird auch im
+    /// "Nicht stören"-Modus angezeigt.
+    Critical,
+}
+
+/// Eine Aktion, die der Benutzer aus einer Benachrichtigung heraus auslösen kann.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationAction {
+    /// Der Bezeichner, der beim Auslösen an die Anwendung zurückgemeldet wird (z.B. "reply").
+    pub key: String,
+    /// Die in der UI angezeigte Beschriftung (z.B. "Antworten").
+    pub label: String,
+}
+
+/// Repräsentiert eine Desktop-Benachrichtigung.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    /// Ein eindeutiger Identifikator für die Benachrichtigung.
+    pub id: NovaId,
+    /// Der Name der Anwendung, die die Benachrichtigung gesendet hat.
+    pub app_name: String,
+    /// Eine einzeilige Zusammenfassung.
+    pub summary: String,
+    /// Ein optionaler, ausführlicherer Text.
+    pub body: Option<String>,
+    /// Ein optionaler Icon-Name oder Pfad zu einem Icon.
+    pub icon: Option<String>,
+    /// Die Dringlichkeit der Benachrichtigung.
+    pub urgency: NotificationUrgency,
+    /// Die angebotenen Aktionen, in Anzeigereihenfolge.
+    pub actions: Vec<NotificationAction>,
+    /// Der Zeitpunkt, zu dem die Benachrichtigung erstellt wurde.
+    pub created_at: Timestamp,
+    /// Der Zeitpunkt, ab dem die Benachrichtigung nicht mehr angezeigt wird; `None` bedeutet,
+    /// dass sie bis zum Schließen bestehen bleibt.
+    pub expires_at: Option<Timestamp>,
+    /// Ob die Benachrichtigung geschlossen wurde. Geschlossene Benachrichtigungen bleiben
+    /// in der Historie erhalten.
+    pub dismissed: bool,
+}
+
+impl Entity for Notification {
+    const ENTITY_TYPE: &'static str = "Notification";
+}
+
+impl Notification {
+    /// Erstellt eine Benachrichtigung mit normaler Dringlichkeit, ohne Aktionen und ohne Ablaufzeit.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_domain::entities::{Notification, NotificationUrgency};
+    ///
+    /// let notification = Notification::new("Akku", "Akku fast leer")
+    ///     .with_body("Noch 5 % verbleibend.")
+    ///     .with_urgency(NotificationUrgency::Critical)
+    ///     .with_action("suspend", "Bereitschaft");
+    /// assert!(notification.has_action("suspend"));
+    /// assert!(!notification.dismissed);
+    /// ```
+    pub fn new(app_name: &str, summary: &str) -> Self {
+        Self {
+            id: NovaId::new(),
+            app_name: app_name.to_string(),
+            summary: summary.to_string(),
+            body: None,
+            icon: None,
+            urgency: NotificationUrgency::default(),
+            actions: Vec::new(),
+            created_at: Timestamp::now(),
+            expires_at: None,
+            dismissed: false,
+        }
+    }
+
+    /// Setzt den ausführlichen Text.
+    pub fn with_body(mut self, body: &str) -> Self {
+        self.body = Some(body.to_string());
+        self
+    }
+
+    /// Setzt das Icon.
+    pub fn with_icon(mut self, icon: &str) -> Self {
+        self.icon = Some(icon.to_string());
+        self
+    }
+
+    /// Setzt die Dringlichkeit.
+    pub fn with_urgency(mut self, urgency: NotificationUrgency) -> Self {
+        self.urgency = urgency;
+        self
+    }
+
+    /// Fügt eine Aktion hinzu.
+    pub fn with_action(mut self, key: &str, label: &str) -> Self {
+        self.actions.push(NotificationAction { key: key.to_string(), label: label.to_string() });
+        self
+    }
+
+    /// Setzt die Ablaufzeit.
+    pub fn expiring_at(mut self, expires_at: Timestamp) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Ob die Benachrichtigung eine Aktion mit diesem Bezeichner anbietet.
+    pub fn has_action(&self, key: &str) -> bool {
+        self.actions.iter().any(|action| action.key == key)
+    }
+
+    /// Ob die Benachrichtigung zum Zeitpunkt `now` abgelaufen ist.
+    pub fn is_expired(&self, now: &Timestamp) -> bool {
+        self.expires_at.as_ref().is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Ob die Benachrichtigung zum Zeitpunkt `now` noch angezeigt werden soll.
+    pub fn is_active(&self, now: &Timestamp) -> bool {
+        !self.dismissed && !self.is_expired(now)
+    }
+}
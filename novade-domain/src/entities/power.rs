@@ -0,0 +1,80 @@
+//! # Energieverwaltung Entitäten (`entities::power`)
+//!
+//! Definiert das Energieprofil [`PowerProfile`] und den Akkuzustand [`BatteryState`], mit
+//! denen der [`PowerService`](crate::services::PowerService) über Profilwechsel entscheidet.
+
+use serde::{Deserialize, Serialize};
+
+/// Ein Energieprofil, wie es power-profiles-daemon anbietet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PowerProfile {
+    /// Spart Energie auf Kosten der Leistung, z.B. bei niedrigem Akkustand.
+    PowerSaver,
+    /// Das übliche Profil.
+    #[default]
+    Balanced,
+    /// Höchste Leistung; nicht auf jeder Hardware verfügbar.
+    Performance,
+}
+
+impl PowerProfile {
+    /// Alle Profile, vom sparsamsten zum leistungsstärksten.
+    pub const ALL: [PowerProfile; 3] = [PowerProfile::PowerSaver, PowerProfile::Balanced, PowerProfile::Performance];
+
+    /// Der Bezeichner des Profils bei power-profiles-daemon (z.B. "power-saver").
+    pub fn id(&self) -> &'static str {
+        match self {
+            PowerProfile::PowerSaver => "power-saver",
+            PowerProfile::Balanced => "balanced",
+            PowerProfile::Performance => "performance",
+        }
+    }
+
+    /// Das Profil zum Bezeichner `id`, oder `None` für unbekannte Bezeichner.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.id() == id)
+    }
+}
+
+/// Ob der Akku geladen oder entladen wird.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChargingState {
+    /// Der Zustand ist nicht bekannt.
+    #[default]
+    Unknown,
+    /// Der Akku wird geladen.
+    Charging,
+    /// Der Akku wird entladen, d.h. das Gerät läuft im Akkubetrieb.
+    Discharging,
+    /// Der Akku ist voll geladen.
+    FullyCharged,
+    /// Das Netzteil ist angeschlossen, der Akku wird aber nicht geladen (z.B. wegen einer
+    /// Ladegrenze).
+    NotCharging,
+}
+
+/// Der Zustand des Akkus bzw. aller Akkus zusammen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryState {
+    /// Der Ladestand in Prozent (0 bis 100).
+    pub percentage: f64,
+    /// Ob der Akku geladen oder entladen wird.
+    pub charging_state: ChargingState,
+    /// Die geschätzte Restlaufzeit in Sekunden, falls bekannt.
+    pub time_to_empty_secs: Option<u64>,
+    /// Die geschätzte Zeit bis zur vollen Ladung in Sekunden, falls bekannt.
+    pub time_to_full_secs: Option<u64>,
+}
+
+impl BatteryState {
+    /// Erstellt einen Zustand ohne Zeitschätzungen.
+    pub fn new(percentage: f64, charging_state: ChargingState) -> Self {
+        Self { percentage, charging_state, time_to_empty_secs: None, time_to_full_secs: None }
+    }
+
+    /// Ob das Gerät vom Akku versorgt wird.
+    pub fn is_discharging(&self) -> bool {
+        self.charging_state == ChargingState::Discharging
+    }
+}
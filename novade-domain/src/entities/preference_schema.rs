@@ -0,0 +1,255 @@
+//! # Einstellungsschema (`entities::preference_schema`)
+//!
+//! Definiert das [`PreferenceSchema`], ein Verzeichnis der bekannten Einstellungsschlüssel.
+//! Jeder Schlüssel wird durch eine [`PreferenceDefinition`] beschrieben: Werttyp,
+//! Standardwert, erlaubter Wertebereich bzw. erlaubte Werte, Gruppe und ob eine Änderung
+//! einen Neustart erfordert.
+//!
+//! Der [`UserPreferenceService`](crate::services::UserPreferenceService) prüft neue Werte
+//! gegen das Schema und liefert für nicht gesetzte Einstellungen den Standardwert.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::user_preference::{PreferenceValue, UserPreferenceSetting};
+use crate::{DomainError, DomainResult};
+
+/// Der Typ eines [`PreferenceValue`] ohne den Wert selbst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PreferenceType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    ColorRgba,
+    StringList,
+}
+
+impl PreferenceValue {
+    /// Der Typ dieses Wertes.
+    pub fn value_type(&self) -> PreferenceType {
+        match self {
+            PreferenceValue::String(_) => PreferenceType::String,
+            PreferenceValue::Integer(_) => PreferenceType::Integer,
+            PreferenceValue::Float(_) => PreferenceType::Float,
+            PreferenceValue::Boolean(_) => PreferenceType::Boolean,
+            PreferenceValue::ColorRgba(_) => PreferenceType::ColorRgba,
+            PreferenceValue::StringList(_) => PreferenceType::StringList,
+        }
+    }
+}
+
+/// Beschreibung eines bekannten Einstellungsschlüssels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreferenceDefinition {
+    /// Der Schlüssel der Einstellung (z.B. "theme.dark_mode").
+    pub key: String,
+    /// Der in der UI anzuzeigende Name.
+    pub display_name: String,
+    /// Eine optionale Beschreibung.
+    pub description: Option<String>,
+    /// Der Standardwert; sein Typ ist der Typ der Einstellung.
+    pub default: PreferenceValue,
+    /// Untergrenze für `Integer`- und `Float`-Werte.
+    pub min: Option<f64>,
+    /// Obergrenze für `Integer`- und `Float`-Werte.
+    pub max: Option<f64>,
+    /// Erlaubte Werte für `String`-Einstellungen bzw. für die Elemente von `StringList`-Einstellungen.
+    pub allowed_values: Option<Vec<String>>,
+    /// Gruppierungskategorie für Einstellungsdialoge.
+    pub group: Option<String>,
+    /// Ob eine Änderung einen Neustart erfordert.
+    pub requires_restart: bool,
+}
+
+impl PreferenceDefinition {
+    /// Erstellt eine Definition ohne Einschränkungen; der Typ ergibt sich aus `default`.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_domain::entities::{PreferenceDefinition, PreferenceValue};
+    ///
+    /// let scale = PreferenceDefinition::new("display.scale", "Skalierung", PreferenceValue::Float(1.0))
+    ///     .with_range(0.5, 3.0)
+    ///     .in_group("Anzeige")
+    ///     .requiring_restart();
+    /// assert!(scale.validate(&PreferenceValue::Float(2.0)).is_ok());
+    /// assert!(scale.validate(&PreferenceValue::Float(4.0)).is_err());
+    /// assert!(scale.validate(&PreferenceValue::Integer(2)).is_err());
+    /// ```
+    pub fn new(key: &str, display_name: &str, default: PreferenceValue) -> Self {
+        Self {
+            key: key.to_string(),
+            display_name: display_name.to_string(),
+            description: None,
+            default,
+            min: None,
+            max: None,
+            allowed_values: None,
+            group: None,
+            requires_restart: false,
+        }
+    }
+
+    /// Beschränkt numerische Werte auf `min..=max`.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    /// Beschränkt Textwerte auf die gegebenen Werte.
+    pub fn with_allowed_values(mut self, values: &[&str]) -> Self {
+        self.allowed_values = Some(values.iter().map(|value| value.to_string()).collect());
+        self
+    }
+
+    /// Setzt die Beschreibung.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Ordnet die Einstellung einer Gruppe zu.
+    pub fn in_group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    /// Kennzeichnet die Einstellung als neustartpflichtig.
+    pub fn requiring_restart(mut self) -> Self {
+        self.requires_restart = true;
+        self
+    }
+
+    /// Der Typ der Einstellung.
+    pub fn value_type(&self) -> PreferenceType {
+        self.default.value_type()
+    }
+
+    /// Prüft einen Wert gegen Typ, Wertebereich und erlaubte Werte.
+    ///
+    /// # Rückgabe
+    /// `DomainError::ValidationError` mit dem Schlüssel als Feld, wenn der Wert ungültig ist.
+    pub fn validate(&self, value: &PreferenceValue) -> DomainResult<()> {
+        let invalid = |message: String| Err(DomainError::ValidationError { field: self.key.clone(), message });
+
+        if value.value_type() != self.value_type() {
+            return invalid(format!("Erwartet wurde ein Wert vom Typ {:?}, erhalten {:?}.", self.value_type(), value.value_type()));
+        }
+        let number = match value {
+            PreferenceValue::Integer(number) => Some(*number as f64),
+            PreferenceValue::Float(number) => Some(*number),
+            _ => None,
+        };
+        if let Some(number) = number {
+            if self.min.is_some_and(|min| number < min) || self.max.is_some_and(|max| number > max) || number.is_nan() {
+                return invalid(format!(
+                    "Wert {} liegt außerhalb des erlaubten Bereichs {:?}..={:?}.",
+                    number, self.min, self.max
+                ));
+            }
+        }
+        if let Some(allowed) = &self.allowed_values {
+            let texts: Vec<&String> = match value {
+                PreferenceValue::String(text) => vec![text],
+                PreferenceValue::StringList(list) => list.iter().collect(),
+                _ => Vec::new(),
+            };
+            if let Some(text) = texts.into_iter().find(|text| !allowed.contains(text)) {
+                return invalid(format!("Wert '{}' ist nicht erlaubt (erlaubt: {}).", text, allowed.join(", ")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Erstellt eine Einstellung mit dem gegebenen Wert und den Metadaten dieser Definition.
+    pub fn to_setting(&self, value: PreferenceValue) -> UserPreferenceSetting {
+        UserPreferenceSetting {
+            key: self.key.clone(),
+            value,
+            display_name: self.display_name.clone(),
+            description: self.description.clone(),
+            requires_restart: self.requires_restart,
+            group: self.group.clone(),
+        }
+    }
+
+    /// Die Einstellung mit dem Standardwert.
+    pub fn default_setting(&self) -> UserPreferenceSetting {
+        self.to_setting(self.default.clone())
+    }
+}
+
+/// Verzeichnis der bekannten Einstellungsschlüssel.
+///
+/// Schlüssel ohne Definition sind nicht eingeschränkt.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreferenceSchema {
+    definitions: BTreeMap<String, PreferenceDefinition>,
+}
+
+impl PreferenceSchema {
+    /// Erstellt ein leeres Schema.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registriert eine Definition und ersetzt eine vorhandene mit demselben Schlüssel.
+    ///
+    /// # Rückgabe
+    /// `DomainError::ValidationError`, wenn der Standardwert die eigenen Einschränkungen verletzt.
+    pub fn register(&mut self, definition: PreferenceDefinition) -> DomainResult<()> {
+        definition.validate(&definition.default)?;
+        self.definitions.insert(definition.key.clone(), definition);
+        Ok(())
+    }
+
+    /// Die Definition eines Schlüssels.
+    pub fn get(&self, key: &str) -> Option<&PreferenceDefinition> {
+        self.definitions.get(key)
+    }
+
+    /// Alle Definitionen, nach Schlüssel sortiert.
+    pub fn definitions(&self) -> impl Iterator<Item = &PreferenceDefinition> {
+        self.definitions.values()
+    }
+
+    /// Prüft einen Wert gegen die Definition seines Schlüssels, falls vorhanden.
+    pub fn validate(&self, key: &str, value: &PreferenceValue) -> DomainResult<()> {
+        self.get(key).map_or(Ok(()), |definition| definition.validate(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_values_and_lists() {
+        let theme = PreferenceDefinition::new("theme.variant", "Variante", PreferenceValue::String("hell".to_string()))
+            .with_allowed_values(&["hell", "dunkel"]);
+        assert!(theme.validate(&PreferenceValue::String("dunkel".to_string())).is_ok());
+        assert!(theme.validate(&PreferenceValue::String("lila".to_string())).is_err());
+        assert!(theme.validate(&PreferenceValue::ColorRgba("hell".to_string())).is_err());
+
+        let layouts = PreferenceDefinition::new("keyboard.layouts", "Layouts", PreferenceValue::StringList(vec![]))
+            .with_allowed_values(&["de", "us"]);
+        assert!(layouts.validate(&PreferenceValue::StringList(vec!["de".to_string(), "us".to_string()])).is_ok());
+        assert!(layouts.validate(&PreferenceValue::StringList(vec!["fr".to_string()])).is_err());
+    }
+
+    #[test]
+    fn test_register_rejects_invalid_default() {
+        let mut schema = PreferenceSchema::new();
+        let broken = PreferenceDefinition::new("audio.volume", "Lautstärke", PreferenceValue::Integer(150)).with_range(0.0, 100.0);
+        assert!(schema.register(broken).is_err());
+        assert!(schema.get("audio.volume").is_none());
+
+        let volume = PreferenceDefinition::new("audio.volume", "Lautstärke", PreferenceValue::Integer(50)).with_range(0.0, 100.0);
+        schema.register(volume).unwrap();
+        assert!(schema.validate("audio.volume", &PreferenceValue::Integer(101)).is_err());
+        assert!(schema.validate("unbekannt.schluessel", &PreferenceValue::Integer(101)).is_ok());
+    }
+}
@@ -0,0 +1,61 @@
+//! # Zuletzt verwendete Elemente (`entities::recent_item`)
+//!
+//! Definiert die Entität [`RecentItem`] für zuletzt geöffnete Dateien und Dokumente sowie
+//! [`RecentItemExclusion`] für Datenschutz-Ausnahmen, die nie aufgezeichnet werden.
+
+use crate::entities::mime_association::mime_type_matches;
+use novade_core::types::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// Ein zuletzt verwendetes Element, identifiziert über seine URI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentItem {
+    /// Die URI des Elements (z.B. "file:///home/nutzer/Bericht.odt").
+    pub uri: String,
+    /// Der MIME-Typ des Elements.
+    pub mime_type: String,
+    /// Die Desktop-Datei-ID der Anwendung, mit der das Element zuletzt geöffnet wurde.
+    pub application: Option<String>,
+    /// Der Zeitpunkt des ersten Zugriffs.
+    pub first_accessed: Timestamp,
+    /// Der Zeitpunkt des letzten Zugriffs.
+    pub last_accessed: Timestamp,
+    /// Die Anzahl der Zugriffe.
+    pub access_count: u32,
+}
+
+impl RecentItem {
+    /// Erstellt ein Element mit einem ersten Zugriff zum Zeitpunkt `accessed`.
+    pub fn new(uri: &str, mime_type: &str, application: Option<&str>, accessed: Timestamp) -> Self {
+        Self {
+            uri: uri.to_string(),
+            mime_type: mime_type.to_string(),
+            application: application.map(str::to_string),
+            first_accessed: accessed.clone(),
+            last_accessed: accessed,
+            access_count: 1,
+        }
+    }
+}
+
+/// Eine Datenschutz-Ausnahme: passende Elemente werden nicht aufgezeichnet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecentItemExclusion {
+    /// Alle Elemente, die mit dieser Anwendung geöffnet werden.
+    Application(String),
+    /// Alle Elemente dieses MIME-Typs; Platzhalter wie "image/*" sind erlaubt.
+    MimeType(String),
+    /// Alle Elemente, deren URI mit diesem Präfix beginnt (z.B. ein privates Verzeichnis).
+    UriPrefix(String),
+}
+
+impl RecentItemExclusion {
+    /// Ob das Element unter diese Ausnahme fällt.
+    pub fn matches(&self, item: &RecentItem) -> bool {
+        match self {
+            RecentItemExclusion::Application(application) => item.application.as_ref() == Some(application),
+            RecentItemExclusion::MimeType(mime_type) => mime_type_matches(mime_type, &item.mime_type),
+            RecentItemExclusion::UriPrefix(prefix) => item.uri.starts_with(prefix.as_str()),
+        }
+    }
+}
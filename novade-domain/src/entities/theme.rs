@@ -0,0 +1,110 @@
+//! # Theme Entität (`entities::theme`)
+//!
+//! Definiert die Entität [`Theme`], die das Erscheinungsbild von NovaDE beschreibt:
+//! Farbpalette, Icon- und Cursor-Theme sowie Schrifteinstellungen.
+
+use crate::entities::Entity;
+use serde::{Deserialize, Serialize};
+
+/// Die Farben eines Themes als Hex-Strings im Format `#RRGGBB` oder `#RRGGBBAA`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColorPalette {
+    /// Hintergrund von Fenstern und Panels.
+    pub background: String,
+    /// Hintergrund hervorgehobener Flächen wie Karten, Menüs und Eingabefelder.
+    pub surface: String,
+    /// Text- und Symbolfarbe.
+    pub foreground: String,
+    /// Akzentfarbe für Auswahl, Fokus und primäre Schaltflächen.
+    pub accent: String,
+    /// Farbe für Warnungen.
+    pub warning: String,
+    /// Farbe für Fehler und destruktive Aktionen.
+    pub error: String,
+}
+
+impl ColorPalette {
+    /// Die Farben zusammen mit ihrem Feldnamen, z.B. für die Validierung.
+    pub fn colors(&self) -> [(&'static str, &str); 6] {
+        [
+            ("background", &self.background),
+            ("surface", &self.surface),
+            ("foreground", &self.foreground),
+            ("accent", &self.accent),
+            ("warning", &self.warning),
+            ("error", &self.error),
+        ]
+    }
+}
+
+/// Die Schrifteinstellungen eines Themes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontSettings {
+    /// Die Schriftfamilie für die Benutzeroberfläche.
+    pub family: String,
+    /// Die Schriftfamilie für Festbreitentext, z.B. in Terminals.
+    pub monospace_family: String,
+    /// Die Schriftgröße in Punkt.
+    pub size: f64,
+}
+
+/// Repräsentiert ein Theme. Themes werden über ihren Namen identifiziert.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    /// Der eindeutige Name des Themes (z.B. "Nova Dunkel").
+    pub name: String,
+    /// Die Farbpalette.
+    pub palette: ColorPalette,
+    /// Der Name des Icon-Themes (z.B. "Adwaita").
+    pub icon_theme: String,
+    /// Der Name des Cursor-Themes.
+    pub cursor_theme: String,
+    /// Die Schrifteinstellungen.
+    pub fonts: FontSettings,
+}
+
+impl Entity for Theme {
+    const ENTITY_TYPE: &'static str = "Theme";
+}
+
+impl Default for Theme {
+    /// Das eingebaute helle Standard-Theme, das verwendet wird, wenn kein Theme ausgewählt
+    /// ist oder das ausgewählte Theme nicht existiert.
+    fn default() -> Self {
+        Self {
+            name: "Nova Hell".to_string(),
+            palette: ColorPalette {
+                background: "#FAFAFA".to_string(),
+                surface: "#FFFFFF".to_string(),
+                foreground: "#202124".to_string(),
+                accent: "#3584E4".to_string(),
+                warning: "#E5A50A".to_string(),
+                error: "#C01C28".to_string(),
+            },
+            icon_theme: "Adwaita".to_string(),
+            cursor_theme: "Adwaita".to_string(),
+            fonts: FontSettings {
+                family: "Cantarell".to_string(),
+                monospace_family: "Source Code Pro".to_string(),
+                size: 11.0,
+            },
+        }
+    }
+}
+
+/// Prüft, ob ein String eine Farbe im Format `#RRGGBB` oder `#RRGGBBAA` ist.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::theme::is_hex_color;
+///
+/// assert!(is_hex_color("#3584E4"));
+/// assert!(is_hex_color("#3584e480"));
+/// assert!(!is_hex_color("3584E4"));
+/// assert!(!is_hex_color("#FFF"));
+/// ```
+pub fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
@@ -7,9 +7,21 @@
 //! und zu verwalten, inklusive Metadaten wie Anzeigename, Beschreibung und ob ein Neustart
 //! für die Aktivierung der Einstellung erforderlich ist.
 
-// use novade_core::types::NovaId; // Import von NovaId für zukünftige Benutzerbindung - aktuell nicht verwendet
+use crate::entities::Entity;
+use crate::validation::{require_non_empty, FieldViolation, Validate};
 use serde::{Deserialize, Serialize};
 
+/// Ob ein Einstellungsschlüssel der Konvention `bereich.unterbereich.einstellung` folgt.
+///
+/// Jedes durch `.` getrennte Segment muss nicht leer sein und darf nur aus
+/// Kleinbuchstaben, Ziffern, `_` und `-` bestehen.
+pub fn is_valid_preference_key(key: &str) -> bool {
+    key.split('.').all(|segment| {
+        !segment.is_empty()
+            && segment.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    })
+}
+
 /// Repräsentiert den tatsächlichen Wert einer Benutzereinstellung.
 ///
 /// Dieses Enum ermöglicht es, verschiedene Datentypen für Einstellungen zu speichern.
@@ -50,10 +62,8 @@ pub struct UserPreferenceSetting {
     /// Eine optionale Gruppierungskategorie für die Einstellung,
     /// nützlich zur Organisation in Einstellungsdialogen (z.B. "Erscheinungsbild", "System", "Fensterverhalten").
     pub group: Option<String>,
-    // Zukünftig könnte hier eine `user_id: Option<NovaId>` stehen, um Einstellungen
-    // benutzerspezifisch zu machen oder systemweite Standardwerte zu kennzeichnen.
-    // Für den Moment wird angenommen, dass Einstellungen global oder durch den Kontext
-    // des Repositories benutzergebunden sind.
+    // Die Zuordnung zu einem Benutzer erfolgt über das Repository
+    // (siehe `UserPreferenceRepository`), nicht über die Einstellung selbst.
 }
 
 impl UserPreferenceSetting {
@@ -103,3 +113,30 @@ impl UserPreferenceSetting {
     }
     // Weitere Konstruktoren für andere Typen (Integer, Float, etc.) können bei Bedarf hinzugefügt werden.
 }
+
+impl Entity for UserPreferenceSetting {
+    const ENTITY_TYPE: &'static str = "UserPreferenceSetting";
+}
+
+impl Validate for UserPreferenceSetting {
+    /// Der Schlüssel folgt der Konvention (siehe [`is_valid_preference_key`]), der Anzeigename
+    /// ist nicht leer und Fließkommawerte sind endlich. Ob der Wert zum Schema passt, prüft der
+    /// [`UserPreferenceService`](crate::services::UserPreferenceService).
+    fn violations(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+        if !is_valid_preference_key(&self.key) {
+            violations.push(FieldViolation::new(
+                "key",
+                format!(
+                    "Ungültiger Einstellungsschlüssel '{}': erwartet werden durch '.' getrennte Segmente aus [a-z0-9_-].",
+                    self.key
+                ),
+            ));
+        }
+        require_non_empty(&mut violations, "display_name", &self.display_name, "Der Anzeigename darf nicht leer sein.");
+        if matches!(self.value, PreferenceValue::Float(value) if !value.is_finite()) {
+            violations.push(FieldViolation::new("value", "Fließkommawerte müssen endlich sein."));
+        }
+        violations
+    }
+}
@@ -8,9 +8,44 @@
 //! Er ermöglicht es Benutzern, ihre Arbeitsumgebung für verschiedene Aufgaben
 //! oder Kontexte zu organisieren.
 
-use novade_core::types::NovaId;
+use crate::entities::application::Application;
+use crate::entities::Entity;
+use crate::validation::{require_non_empty, FieldViolation, Validate};
+use novade_core::types::{NovaId, Timestamp};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Eine Regel, nach der neue Fenster einer Anwendung auf einem bestimmten Workspace geöffnet
+/// werden (z.B. "Anwendungen der Kategorie Development öffnen auf 'Code'").
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkspaceAssignmentRule {
+    /// Die Anwendung mit dieser App-ID (dem Namen ihres Desktop-Eintrags, z.B. "org.gnome.Nautilus").
+    ApplicationId(String),
+    /// Alle Anwendungen dieser Kategorie (z.B. "Development").
+    Category(String),
+}
+
+impl WorkspaceAssignmentRule {
+    /// Ob die Regel auf die Anwendung zutrifft. Groß- und Kleinschreibung werden ignoriert.
+    pub fn matches(&self, application: &Application) -> bool {
+        match self {
+            WorkspaceAssignmentRule::ApplicationId(app_id) => application.name.eq_ignore_ascii_case(app_id),
+            WorkspaceAssignmentRule::Category(category) => application
+                .categories
+                .iter()
+                .flatten()
+                .any(|app_category| app_category.eq_ignore_ascii_case(category)),
+        }
+    }
+
+    /// Der Wert, auf den die Regel prüft.
+    pub fn value(&self) -> &str {
+        match self {
+            WorkspaceAssignmentRule::ApplicationId(value) | WorkspaceAssignmentRule::Category(value) => value,
+        }
+    }
+}
 
 /// Repräsentiert einen Arbeitsbereich (Workspace) in NovaDE.
 ///
@@ -36,13 +71,37 @@ pub struct Workspace {
     /// Kann verwendet werden, um beliebige Schlüssel-Wert-Paare zu speichern,
     /// wie z.B. Hintergrundbild, spezifische Panel-Einstellungen etc.
     pub metadata: HashMap<String, String>,
+    /// Die Position des Workspaces in der Reihenfolge, in der er z.B. im Panel angezeigt und
+    /// beim Wechsel zum nächsten bzw. vorherigen Workspace durchlaufen wird.
+    #[serde(default)]
+    pub index: u32,
+    /// Die Regeln, nach denen neue Fenster auf diesem Workspace geöffnet werden.
+    #[serde(default)]
+    pub assignment_rules: Vec<WorkspaceAssignmentRule>,
+    /// Die IDs der [`Application`]s, deren Fenster auf diesem Workspace liegen. Eine Anwendung
+    /// gehört höchstens einem nicht archivierten Workspace.
+    #[serde(default)]
+    pub applications: Vec<NovaId>,
+    /// Wann der Workspace zuletzt aktiviert wurde; `None`, wenn er noch nie aktiv war.
+    #[serde(default)]
+    pub last_activated_at: Option<Timestamp>,
+    /// Wie lange der Workspace insgesamt aktiv war. Die laufende Aktivzeit des aktiven
+    /// Workspaces ist erst nach dem nächsten Wechsel bzw.
+    /// [`WorkspaceService::record_active_time`](crate::services::WorkspaceService::record_active_time) enthalten.
+    #[serde(default)]
+    pub active_duration: Duration,
+    /// Wann der Workspace archiviert wurde; `None` für aktive Workspaces. Archivierte
+    /// Workspaces bleiben gespeichert, werden aber nicht mehr angezeigt oder aktiviert, bis sie
+    /// wiederhergestellt oder endgültig gelöscht werden.
+    #[serde(default)]
+    pub deleted_at: Option<Timestamp>,
 }
 
 impl Workspace {
     /// Erstellt einen neuen `Workspace` mit einem gegebenen Namen und optionaler ID des primären Outputs.
     ///
     /// Die ID des Workspaces wird automatisch generiert. Die `layout_configuration` wird
-    /// standardmäßig auf "default" gesetzt.
+    /// standardmäßig auf "default" gesetzt, der `index` auf 0.
     ///
     /// # Parameter
     /// * `name`: Der Name für den neuen Workspace.
@@ -68,6 +127,47 @@ impl Workspace {
             layout_configuration: "default".to_string(), // Ein einfacher Standardwert
             primary_output_id,
             metadata: HashMap::new(),
+            index: 0,
+            assignment_rules: Vec::new(),
+            applications: Vec::new(),
+            last_activated_at: None,
+            active_duration: Duration::ZERO,
+            deleted_at: None,
+        }
+    }
+
+    /// Ob der Workspace archiviert ist.
+    pub fn is_archived(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
+
+impl Entity for Workspace {
+    const ENTITY_TYPE: &'static str = "Workspace";
+}
+
+impl Validate for Workspace {
+    /// Der Name und die Werte der Zuordnungsregeln dürfen nicht leer sein, eine Anwendung darf
+    /// nur einmal zugeordnet sein.
+    fn violations(&self) -> Vec<FieldViolation> {
+        let mut violations = Vec::new();
+        require_non_empty(&mut violations, "name", &self.name, "Workspace-Name darf nicht leer sein.");
+        for (index, rule) in self.assignment_rules.iter().enumerate() {
+            require_non_empty(
+                &mut violations,
+                &format!("assignment_rules[{}]", index),
+                rule.value(),
+                "Eine Zuordnungsregel braucht eine App-ID bzw. Kategorie.",
+            );
+        }
+        for (index, application_id) in self.applications.iter().enumerate() {
+            if self.applications[..index].contains(application_id) {
+                violations.push(FieldViolation::new(
+                    format!("applications[{}]", index),
+                    format!("Die Anwendung {} ist dem Workspace mehrfach zugeordnet.", application_id),
+                ));
+            }
         }
+        violations
     }
 }
@@ -32,7 +32,10 @@
 //! }
 //! ```
 
+use crate::entities::Entity;
+use crate::validation::FieldViolation;
 use novade_core::CoreError; // Importiere CoreError für das Wrapping
+use std::fmt;
 use thiserror::Error;
 
 /// Ein Alias für `Result<T, DomainError>`, der die Fehlerbehandlung in `novade-domain` vereinfacht.
@@ -66,6 +69,28 @@ pub enum DomainError {
         message: String,
     },
 
+    /// Wird zurückgegeben, wenn eine Entität gegen mehrere Validierungsregeln verstößt
+    /// (siehe [`Validate`](crate::validation::Validate)); enthält alle Verstöße.
+    #[error("Validierungsfehler für '{entity_type}': {}", format_violations(.violations))]
+    ValidationErrors {
+        /// Der Typ der Entität (z.B. "Application").
+        entity_type: String,
+        /// Die Verstöße in der Reihenfolge der Felder.
+        violations: Vec<FieldViolation>,
+    },
+
+    /// Wird zurückgegeben, wenn ein eindeutiger Wert (z.B. ein Name) bereits von einer anderen
+    /// Entität desselben Typs verwendet wird.
+    #[error("Konflikt für '{entity_type}': '{conflicting_field}' ist bereits durch '{existing_id}' belegt.")]
+    Conflict {
+        /// Der Typ der Entität (z.B. "Workspace").
+        entity_type: String,
+        /// Das Feld mit dem bereits vergebenen Wert (z.B. "name").
+        conflicting_field: String,
+        /// Die ID der vorhandenen Entität, die den Wert bereits verwendet.
+        existing_id: String,
+    },
+
     /// Wird zurückgegeben, wenn eine angeforderte Operation unter den aktuellen Umständen nicht erlaubt ist.
     #[error("Operation '{operation}' nicht erlaubt: {reason}")]
     OperationNotPermitted {
@@ -75,6 +100,18 @@ pub enum DomainError {
         reason: String,
     },
 
+    /// Wird zurückgegeben, wenn eine Entität auf eine nicht existierende Entität verweisen würde
+    /// oder eine Entität entfernt werden soll, auf die andere Entitäten noch verweisen.
+    #[error("Referenzielle Integrität für '{entity_type}' mit ID '{entity_id}' verletzt: {reason}")]
+    IntegrityViolation {
+        /// Der Typ der Entität, deren Verweise betroffen sind (z.B. "Workspace").
+        entity_type: String,
+        /// Die ID dieser Entität.
+        entity_id: String,
+        /// Welcher Verweis ungültig ist bzw. welche Entitäten noch verweisen.
+        reason: String,
+    },
+
     /// Kapselt einen Fehler, der aus der darunterliegenden `novade-core` Schicht stammt,
     /// oft im Kontext von Repository-Operationen (z.B. E/A-Fehler beim Dateizugriff).
     ///
@@ -95,3 +132,39 @@ pub enum DomainError {
     #[error("Ein unbekannter Domänenfehler ist aufgetreten: {0}")]
     UnknownError(String),
 }
+
+impl DomainError {
+    /// `EntityNotFound` für die Entität `E` mit der ID `id`.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_core::types::NovaId;
+    /// use novade_domain::entities::Application;
+    /// use novade_domain::DomainError;
+    ///
+    /// let id = NovaId::new();
+    /// match DomainError::not_found::<Application>(&id) {
+    ///     DomainError::EntityNotFound { entity_type, entity_id } => {
+    ///         assert_eq!((entity_type.as_str(), entity_id), ("Application", id.to_string()));
+    ///     }
+    ///     other => panic!("Unerwarteter Fehler: {:?}", other),
+    /// }
+    /// ```
+    pub fn not_found<E: Entity>(id: impl fmt::Display) -> Self {
+        DomainError::EntityNotFound { entity_type: E::ENTITY_TYPE.to_string(), entity_id: id.to_string() }
+    }
+
+    /// `Conflict` für die Entität `E`, deren Feld `conflicting_field` denselben Wert hat wie
+    /// die vorhandene Entität `existing_id`.
+    pub fn conflict<E: Entity>(conflicting_field: &str, existing_id: impl fmt::Display) -> Self {
+        DomainError::Conflict {
+            entity_type: E::ENTITY_TYPE.to_string(),
+            conflicting_field: conflicting_field.to_string(),
+            existing_id: existing_id.to_string(),
+        }
+    }
+}
+
+fn format_violations(violations: &[FieldViolation]) -> String {
+    violations.iter().map(|violation| format!("{}: {}", violation.field, violation.message)).collect::<Vec<_>>().join("; ")
+}
@@ -0,0 +1,155 @@
+//! # Domänenereignisse (`events`)
+//!
+//! Definiert die typisierten [`DomainEvent`]s, die die Domänendienste bei Änderungen melden,
+//! sowie die Abstraktion [`EventPublisher`], über die sie gemeldet werden. Der [`EventBus`]
+//! verteilt die Ereignisse an Abonnenten, so dass System- und UI-Schicht auf Änderungen
+//! reagieren können, ohne die Repositories abzufragen.
+//!
+//! Ein Dienst meldet nur dann Ereignisse, wenn ihm ein Publisher übergeben wurde
+//! (z.B. mit [`ApplicationService::with_event_publisher`](crate::services::ApplicationService::with_event_publisher)).
+
+use crate::entities::application::Application;
+use crate::entities::audio::{AudioDevice, AudioDeviceKind, AudioStream};
+use crate::entities::autostart::AutostartEntry;
+use crate::entities::display::DisplayLayout;
+use crate::entities::locale::LocaleSettings;
+use crate::entities::network::NetworkConnection;
+use crate::entities::power::PowerProfile;
+use crate::entities::workspace::Workspace;
+use crate::services::user_preference_service::PreferenceChange;
+use novade_core::signal::Signal;
+use novade_core::types::NovaId;
+use std::sync::mpsc::Receiver;
+
+/// Ein Ereignis der Domänenschicht.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DomainEvent {
+    /// Eine Anwendung wurde registriert.
+    ApplicationRegistered(Application),
+    /// Eine Anwendung wurde geändert (z.B. ihre Tags).
+    ApplicationUpdated(Application),
+    /// Eine Anwendung wurde archiviert.
+    ApplicationArchived { id: NovaId },
+    /// Eine archivierte Anwendung wurde wiederhergestellt.
+    ApplicationRestored(Application),
+    /// Eine archivierte Anwendung wurde endgültig gelöscht.
+    ApplicationPurged { id: NovaId },
+    /// Ein Workspace wurde angelegt.
+    WorkspaceCreated(Workspace),
+    /// Ein Workspace wurde gelöscht oder archiviert; seine Inhalte wurden `reassigned_to` zugeordnet.
+    WorkspaceRemoved { id: NovaId, reassigned_to: NovaId },
+    /// Ein archivierter Workspace wurde wiederhergestellt.
+    WorkspaceRestored(Workspace),
+    /// Ein archivierter Workspace wurde endgültig gelöscht.
+    WorkspacePurged { id: NovaId },
+    /// Ein Workspace wurde umbenannt.
+    WorkspaceRenamed { id: NovaId, old_name: String, new_name: String },
+    /// Ein anderer Workspace wurde aktiviert.
+    WorkspaceActivated { previous: Option<NovaId>, workspace: Workspace },
+    /// Die Reihenfolge der Workspaces hat sich geändert; enthält die IDs in neuer Reihenfolge.
+    WorkspacesReordered(Vec<NovaId>),
+    /// Eine Anwendung wurde dem Workspace `to` zugeordnet, bisher gehörte sie zu `from`.
+    WorkspaceApplicationAssigned { application_id: NovaId, from: Option<NovaId>, to: NovaId },
+    /// Der wirksame Wert einer Einstellung hat sich geändert.
+    PreferenceChanged(PreferenceChange),
+    /// Das Energieprofil wurde gewechselt; `automatic` ist `true`, wenn der Wechsel durch den
+    /// Akkustand ausgelöst wurde.
+    PowerProfileChanged { profile: PowerProfile, automatic: bool },
+    /// Ein Bildschirm-Layout wurde gespeichert.
+    DisplayLayoutSaved(DisplayLayout),
+    /// Das gespeicherte Bildschirm-Layout mit dem Schlüssel `key` wurde entfernt.
+    DisplayLayoutRemoved { key: String },
+    /// Lautstärke oder Stummschaltung eines Audiogeräts hat sich geändert.
+    AudioDeviceChanged(AudioDevice),
+    /// Das Standardgerät der Art `kind` ist jetzt das Gerät `name`.
+    DefaultAudioDeviceChanged { kind: AudioDeviceKind, name: String },
+    /// Lautstärke oder Stummschaltung eines Audio-Streams hat sich geändert.
+    AudioStreamChanged(AudioStream),
+    /// Der Zustand einer Netzwerkverbindung hat sich geändert.
+    NetworkConnectionChanged(NetworkConnection),
+    /// Ein Autostart-Eintrag wurde hinzugefügt oder geändert.
+    AutostartEntryChanged(AutostartEntry),
+    /// Der Autostart-Eintrag der Anwendung `application_id` wurde entfernt.
+    AutostartEntryRemoved { application_id: String },
+    /// Die wirksamen Regionaleinstellungen haben sich geändert.
+    LocaleSettingsChanged(LocaleSettings),
+}
+
+/// Eine Senke für [`DomainEvent`]s, in die die Dienste ihre Ereignisse melden.
+pub trait EventPublisher: Send + Sync {
+    /// Meldet ein Ereignis. Darf nicht blockieren und schlägt nicht fehl.
+    fn publish(&self, event: DomainEvent);
+}
+
+/// Ein [`EventPublisher`], der jedes Ereignis an alle passenden Abonnenten verteilt.
+///
+/// Baut auf [`Signal`] aus `novade-core` auf; mit [`signal`](Self::signal) stehen auch
+/// asynchrone Empfänger und Rückrufverbindungen zur Verfügung.
+#[derive(Debug, Default)]
+pub struct EventBus {
+    signal: Signal<DomainEvent>,
+}
+
+impl EventBus {
+    /// Erstellt einen Bus ohne Abonnenten.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Abonniert alle Ereignisse.
+    ///
+    /// Das Abonnement endet, sobald der `Receiver` verworfen wird.
+    pub fn subscribe(&self) -> Receiver<DomainEvent> {
+        self.signal.subscribe()
+    }
+
+    /// Abonniert die Ereignisse, für die `filter` `true` liefert.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_domain::events::{DomainEvent, EventBus, EventPublisher};
+    /// use novade_domain::entities::Workspace;
+    ///
+    /// let bus = EventBus::new();
+    /// let workspaces = bus.subscribe_filtered(|event| matches!(event, DomainEvent::WorkspaceCreated(_)));
+    /// bus.publish(DomainEvent::WorkspacesReordered(Vec::new()));
+    /// bus.publish(DomainEvent::WorkspaceCreated(Workspace::new("Arbeit".to_string(), None)));
+    /// assert_eq!(workspaces.try_iter().count(), 1);
+    /// ```
+    pub fn subscribe_filtered(&self, filter: impl Fn(&DomainEvent) -> bool + Send + 'static) -> Receiver<DomainEvent> {
+        self.signal.subscribe_filtered(filter)
+    }
+
+    /// Das zugrundeliegende Signal, z.B. für [`Signal::subscribe_async`].
+    pub fn signal(&self) -> &Signal<DomainEvent> {
+        &self.signal
+    }
+
+    /// Die Anzahl der aktiven Abonnements.
+    pub fn subscriber_count(&self) -> usize {
+        self.signal.subscriber_count()
+    }
+}
+
+impl EventPublisher for EventBus {
+    fn publish(&self, event: DomainEvent) {
+        self.signal.emit(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dropped_subscribers_are_removed() {
+        let bus = EventBus::new();
+        let all = bus.subscribe();
+        let dropped = bus.subscribe();
+        drop(dropped);
+
+        bus.publish(DomainEvent::WorkspacesReordered(vec![NovaId::new()]));
+        assert_eq!(all.try_iter().count(), 1);
+        assert_eq!(bus.subscriber_count(), 1);
+    }
+}
@@ -16,8 +16,16 @@
 //! - **Dienste ([`services`])**: Implementieren die eigentliche Geschäftslogik und
 //!   orchestrieren Operationen unter Verwendung von Entitäten und Repository-Abstraktionen
 //!   (z.B. [`ApplicationService`], [`WorkspaceService`]).
+//! - **Ereignisse ([`events`])**: Typisierte Domänenereignisse ([`DomainEvent`]), die die
+//!   Dienste über einen [`EventPublisher`] wie den [`EventBus`] melden.
 //! - **Fehlerbehandlung ([`error`])**: Definiert domänenspezifische Fehler (`DomainError`)
 //!   und ein `DomainResult<T>` für Operationen innerhalb dieser Schicht.
+//! - **Änderungsprotokoll**: Der [`AuditService`] hält jede Änderung an Entitäten als
+//!   [`AuditRecord`] fest, sofern er den Diensten übergeben wurde.
+//! - **Globale Suche**: Der [`SearchService`] durchsucht Anwendungen, Workspaces,
+//!   Einstellungen und zuletzt verwendete Elemente mit einer Anfrage.
+//! - **Validierung ([`validation`])**: Das Trait [`Validate`], mit dem Entitäten alle
+//!   Regelverstöße auf einmal melden, bevor die Dienste sie speichern.
 //!
 //! ## Designprinzipien:
 //!
@@ -50,6 +58,7 @@
 //!         Ok(None)
 //!     }
 //!     async fn get_all(&self) -> DomainResult<Vec<Application>> { Ok(vec![]) }
+//!     async fn get_archived(&self) -> DomainResult<Vec<Application>> { Ok(vec![]) }
 //!     async fn find_by_name(&self, search_term: &str) -> DomainResult<Vec<Application>> { Ok(vec![]) }
 //!     async fn add(&self, application: &Application) -> DomainResult<()> { Ok(()) }
 //!     async fn update(&self, application: &Application) -> DomainResult<()> { Ok(()) }
@@ -75,24 +84,29 @@
 // Module werden öffentlich gemacht
 pub mod entities;
 pub mod error;
+pub mod events;
 pub mod repositories;
 pub mod services;
+pub mod validation;
 
 // Re-exportiere die wichtigsten Elemente für eine einfachere Nutzung.
 pub use error::{DomainError, DomainResult};
+pub use events::{DomainEvent, EventBus, EventPublisher};
+pub use validation::{FieldViolation, Validate};
 
 // Re-Exporte aus entities (Beispiele, je nach Häufigkeit der Nutzung anpassen)
 pub use entities::{
-    Application, ApplicationType, PreferenceValue, UserPreferenceSetting, Workspace,
+    Application, ApplicationType, AudioDevice, AudioStream, AuditOperation, AuditRecord, AutostartEntry, Category, CategoryTaxonomy, DisplayLayout, Keybinding, LaunchRecord, MimeAssociation, NetworkConnection, Notification, NotificationUrgency, PreferenceDefinition, PreferenceSchema, PreferenceValue, RecentItem, Theme, UserPreferenceSetting,
+    Workspace, WorkspaceAssignmentRule,
 };
 
 // Re-Exporte aus repositories (Traits sind wichtig für Implementierer)
 pub use repositories::{
-    ApplicationRepository, UserPreferenceRepository, WorkspaceRepository,
+    ApplicationRepository, AudioRepository, AuditRepository, AutostartRepository, DisplayLayoutRepository, IconThemeRepository, KeybindingRepository, LaunchHistoryRepository, MimeAssociationRepository, NetworkRepository, NotificationRepository, RecentItemRepository, ThemeRepository, UserPreferenceRepository, WorkspaceRepository,
 };
 
 // Re-Exporte aus services (Dienste sind die Haupt-Einstiegspunkte für die Logik)
-pub use services::{ApplicationService, WorkspaceService};
+pub use services::{ApplicationService, AudioService, AuditService, AutostartService, CategoryService, DefaultApplicationService, DisplayService, HistoryService, IconResolverService, KeybindingService, LaunchHistoryService, LocalizationService, NetworkService, NotificationService, PowerService, RecentItemsService, SearchService, ThemeService, UserPreferenceService, WorkspaceService};
 
 
 /// Gibt eine Testnachricht aus, um die Funktionalität der Domänenschicht zu demonstrieren.
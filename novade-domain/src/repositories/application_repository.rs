@@ -8,11 +8,142 @@
 //! von Anwendungsdaten zu handhaben (z.B. aus einer Datenbank, Konfigurationsdateien
 //! oder einem Verzeichnis von `.desktop`-Dateien).
 
-use crate::entities::application::Application;
+use crate::entities::application::{Application, ApplicationType};
+use crate::entities::category::CategoryTaxonomy;
+use crate::repositories::change::RepositoryChange;
+use crate::repositories::paging::{paginate, Page, PagedResult};
 use crate::DomainResult; // Stellt sicher, dass Fehler als DomainError zurückgegeben werden
 use async_trait::async_trait;
+use novade_core::signal::Signal;
+use std::sync::Arc;
 use novade_core::types::NovaId;
 
+/// Eine Abfrage über Anwendungen, deren Bedingungen alle erfüllt sein müssen.
+///
+/// Nicht gesetzte Bedingungen schränken nicht ein; Texte werden ohne Unterscheidung von
+/// Groß-/Kleinschreibung verglichen. Archivierte Anwendungen passen nur, wenn die Abfrage
+/// sie mit [`including_archived`](Self::including_archived) ausdrücklich einschließt.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::{Application, ApplicationType};
+/// use novade_domain::repositories::ApplicationQuery;
+///
+/// let query = ApplicationQuery::new().category("Graphics").app_type_in([ApplicationType::Desktop]).has_icon(true);
+/// let mut gimp = Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), Some("gimp".to_string()));
+/// assert!(!query.matches(&gimp));
+/// gimp.categories = Some(vec!["graphics".to_string()]);
+/// assert!(query.matches(&gimp));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplicationQuery {
+    /// Teilstring des Namens oder Anzeigenamens.
+    pub name_contains: Option<String>,
+    /// Eine der Kategorien der Anwendung.
+    pub category: Option<String>,
+    /// Die Anwendung hat mindestens eine dieser Kategorien; leer bedeutet alle.
+    pub category_in: Vec<String>,
+    /// Erlaubte Anwendungstypen; leer bedeutet alle.
+    pub app_types: Vec<ApplicationType>,
+    /// Ob die Anwendung ein Icon hat bzw. keines hat.
+    pub has_icon: Option<bool>,
+    /// Teilstring eines der Schlüsselwörter.
+    pub keyword: Option<String>,
+    /// Ob auch archivierte Anwendungen passen.
+    pub include_archived: bool,
+}
+
+impl ApplicationQuery {
+    /// Eine Abfrage ohne Bedingungen, die auf alle Anwendungen passt.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Nur Anwendungen, deren Name oder Anzeigename `term` enthält.
+    pub fn name_contains(mut self, term: &str) -> Self {
+        self.name_contains = Some(term.to_string());
+        self
+    }
+
+    /// Nur Anwendungen der Kategorie `category`.
+    pub fn category(mut self, category: &str) -> Self {
+        self.category = Some(category.to_string());
+        self
+    }
+
+    /// Nur Anwendungen mit mindestens einer der Kategorien `categories`.
+    pub fn category_in(mut self, categories: impl IntoIterator<Item = String>) -> Self {
+        self.category_in = categories.into_iter().collect();
+        self
+    }
+
+    /// Nur Anwendungen der Kategorie `category` oder einer ihr in `taxonomy` untergeordneten
+    /// Kategorie (z.B. für "Graphics" auch Anwendungen, die nur "Photography" nennen). Eine
+    /// in `taxonomy` unbekannte Kategorie wird wie bei [`category`](Self::category) verglichen.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_domain::entities::{Application, CategoryTaxonomy};
+    /// use novade_domain::repositories::ApplicationQuery;
+    ///
+    /// let query = ApplicationQuery::new().in_category_subtree(&CategoryTaxonomy::freedesktop(), "Graphics");
+    /// let mut darktable = Application::new_desktop("darktable".to_string(), "/usr/bin/darktable".to_string(), None);
+    /// darktable.categories = Some(vec!["Photography".to_string()]);
+    /// assert!(query.matches(&darktable));
+    /// ```
+    pub fn in_category_subtree(self, taxonomy: &CategoryTaxonomy, category: &str) -> Self {
+        let subtree: Vec<String> = taxonomy.subtree(category).into_iter().map(|category| category.id.clone()).collect();
+        if subtree.is_empty() {
+            self.category_in([category.to_string()])
+        } else {
+            self.category_in(subtree)
+        }
+    }
+
+    /// Nur Anwendungen eines der Typen `app_types`.
+    pub fn app_type_in(mut self, app_types: impl IntoIterator<Item = ApplicationType>) -> Self {
+        self.app_types = app_types.into_iter().collect();
+        self
+    }
+
+    /// Nur Anwendungen mit (`true`) bzw. ohne (`false`) Icon.
+    pub fn has_icon(mut self, has_icon: bool) -> Self {
+        self.has_icon = Some(has_icon);
+        self
+    }
+
+    /// Nur Anwendungen, bei denen eines der Schlüsselwörter `keyword` enthält.
+    pub fn keyword(mut self, keyword: &str) -> Self {
+        self.keyword = Some(keyword.to_string());
+        self
+    }
+
+    /// Auch archivierte Anwendungen einschließen.
+    pub fn including_archived(mut self) -> Self {
+        self.include_archived = true;
+        self
+    }
+
+    /// Ob die Anwendung alle Bedingungen erfüllt.
+    pub fn matches(&self, application: &Application) -> bool {
+        let contains = |text: &str, term: &str| text.to_lowercase().contains(&term.to_lowercase());
+        (self.include_archived || !application.is_archived())
+            && self.name_contains.as_deref().is_none_or(|term| {
+                contains(&application.name, term) || application.display_name.as_deref().is_some_and(|name| contains(name, term))
+            })
+            && self.category.as_deref().is_none_or(|category| {
+                application.categories.iter().flatten().any(|app_category| app_category.eq_ignore_ascii_case(category))
+            })
+            && (self.category_in.is_empty()
+                || application.categories.iter().flatten().any(|app_category| {
+                    self.category_in.iter().any(|category| app_category.eq_ignore_ascii_case(category))
+                }))
+            && (self.app_types.is_empty() || self.app_types.contains(&application.app_type))
+            && self.has_icon.is_none_or(|has_icon| application.icon_name.as_deref().is_some_and(|icon| !icon.is_empty()) == has_icon)
+            && self.keyword.as_deref().is_none_or(|term| application.keywords.iter().flatten().any(|keyword| contains(keyword, term)))
+    }
+}
+
 /// Ein Trait, das Operationen zum Speichern, Abrufen und Verwalten von
 /// [`Application`](crate::entities::Application)-Entitäten abstrahiert.
 ///
@@ -35,19 +166,52 @@ pub trait ApplicationRepository: Send + Sync {
     /// Im Fehlerfall wird ein `DomainError` zurückgegeben.
     async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Application>>;
 
-    /// Ruft eine Liste aller im System bekannten Anwendungen ab.
+    /// Ruft eine Liste aller im System bekannten, nicht archivierten Anwendungen ab.
+    ///
+    /// Archivierte Anwendungen liefert [`get_archived`](Self::get_archived); alle zusammen
+    /// liefert [`find`](Self::find) mit [`ApplicationQuery::including_archived`].
     ///
     /// # Rückgabe
     /// Ein `DomainResult` das bei Erfolg einen Vektor von `Application`-Entitäten enthält.
     /// Der Vektor kann leer sein, wenn keine Anwendungen vorhanden sind.
     /// Im Fehlerfall wird ein `DomainError` zurückgegeben.
     async fn get_all(&self) -> DomainResult<Vec<Application>>;
-    
-    /// Sucht und ruft Anwendungen ab, deren Name (oder ggf. Anzeigename)
-    /// einem gegebenen Suchbegriff entspricht.
+
+    /// Ruft eine Seite der nicht archivierten Einträge ab (siehe [`Page`]).
     ///
-    /// Die genaue Suchlogik (z.B. exakte Übereinstimmung, Teilübereinstimmung, Groß-/Kleinschreibung)
-    /// ist der Implementierung überlassen.
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<Application>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Ruft alle archivierten Anwendungen ab.
+    async fn get_archived(&self) -> DomainResult<Vec<Application>>;
+
+    /// Ruft alle Anwendungen ab, die die Abfrage erfüllen.
+    ///
+    /// Die Standardimplementierung filtert das Ergebnis von [`get_all`](Self::get_all), bei
+    /// [`ApplicationQuery::including_archived`] zusätzlich das von [`get_archived`](Self::get_archived);
+    /// Implementierungen mit Indizes sollten die Abfrage in Index-Zugriffe übersetzen.
+    ///
+    /// # Parameter
+    /// * `query`: Die Bedingungen, siehe [`ApplicationQuery`].
+    ///
+    /// # Rückgabe
+    /// Ein `DomainResult` das bei Erfolg einen Vektor von passenden `Application`-Entitäten enthält.
+    async fn find(&self, query: &ApplicationQuery) -> DomainResult<Vec<Application>> {
+        let mut applications = self.get_all().await?;
+        if query.include_archived {
+            applications.extend(self.get_archived().await?);
+        }
+        Ok(applications.into_iter().filter(|app| query.matches(app)).collect())
+    }
+
+    /// Sucht Anwendungen, deren Name oder Anzeigename den Suchbegriff enthält
+    /// (Groß-/Kleinschreibung wird nicht unterschieden).
+    ///
+    /// Die Standardimplementierung ist eine Kurzform für [`find`](Self::find) mit
+    /// [`ApplicationQuery::name_contains`]; Implementierungen können weitere Felder einbeziehen.
     ///
     /// # Parameter
     /// * `search_term`: Der Begriff, nach dem im Anwendungsnamen gesucht werden soll.
@@ -55,7 +219,25 @@ pub trait ApplicationRepository: Send + Sync {
     /// # Rückgabe
     /// Ein `DomainResult` das bei Erfolg einen Vektor von passenden `Application`-Entitäten enthält.
     /// Im Fehlerfall wird ein `DomainError` zurückgegeben.
-    async fn find_by_name(&self, search_term: &str) -> DomainResult<Vec<Application>>;
+    async fn find_by_name(&self, search_term: &str) -> DomainResult<Vec<Application>> {
+        self.find(&ApplicationQuery::new().name_contains(search_term)).await
+    }
+
+    /// Ruft alle nicht archivierten Anwendungen ab, die den gegebenen Tag tragen.
+    /// Groß-/Kleinschreibung wird nicht unterschieden.
+    ///
+    /// Die Standardimplementierung filtert das Ergebnis von [`get_all`](Self::get_all);
+    /// Implementierungen mit einem Index sollten sie überschreiben.
+    ///
+    /// # Parameter
+    /// * `tag`: Der gesuchte Tag.
+    ///
+    /// # Rückgabe
+    /// Ein `DomainResult` das bei Erfolg einen Vektor von passenden `Application`-Entitäten enthält.
+    async fn find_by_tag(&self, tag: &str) -> DomainResult<Vec<Application>> {
+        let tag = tag.to_lowercase();
+        Ok(self.get_all().await?.into_iter().filter(|app| app.tags.iter().any(|t| t.to_lowercase() == tag)).collect())
+    }
 
     /// Fügt eine neue Anwendung zum Repository hinzu.
     ///
@@ -65,8 +247,8 @@ pub trait ApplicationRepository: Send + Sync {
     ///
     /// # Rückgabe
     /// Ein `DomainResult<()>` das bei Erfolg `Ok(())` zurückgibt.
-    /// Im Fehlerfall wird ein `DomainError` zurückgegeben (z.B. wenn eine Anwendung
-    /// mit derselben ID bereits existiert oder ein Speicherfehler auftritt).
+    /// Im Fehlerfall wird ein `DomainError` zurückgegeben: `DomainError::Conflict`, wenn eine
+    /// Anwendung mit derselben ID bereits existiert, sonst z.B. bei einem Speicherfehler.
     async fn add(&self, application: &Application) -> DomainResult<()>;
 
     /// Aktualisiert eine bereits im Repository vorhandene Anwendung.
@@ -82,6 +264,25 @@ pub trait ApplicationRepository: Send + Sync {
     /// Anwendung nicht gefunden wird).
     async fn update(&self, application: &Application) -> DomainResult<()>;
 
+    /// Fügt mehrere Anwendungen hinzu und aktualisiert andere in einem Schritt.
+    ///
+    /// Implementierungen sollten die Änderungen transaktional schreiben: Schlägt eine Änderung
+    /// fehl, wird keine übernommen. Die Standardimplementierung ruft [`add`](Self::add) und
+    /// [`update`](Self::update) nacheinander auf und ist daher nicht transaktional.
+    ///
+    /// # Parameter
+    /// * `added`: Die neuen Anwendungen.
+    /// * `updated`: Die geänderten, bereits vorhandenen Anwendungen.
+    async fn save_batch(&self, added: &[Application], updated: &[Application]) -> DomainResult<()> {
+        for application in added {
+            self.add(application).await?;
+        }
+        for application in updated {
+            self.update(application).await?;
+        }
+        Ok(())
+    }
+
     /// Entfernt eine Anwendung anhand ihrer eindeutigen ID aus dem Repository.
     ///
     /// # Parameter
@@ -92,4 +293,11 @@ pub trait ApplicationRepository: Send + Sync {
     /// Im Fehlerfall wird ein `DomainError` zurückgegeben (z.B. wenn keine Anwendung
     /// mit dieser ID zum Entfernen gefunden wird).
     async fn remove(&self, id: &NovaId) -> DomainResult<()>;
+
+    /// Das Signal, über das das Repository seine Änderungen meldet (siehe [`RepositoryChange`]).
+    ///
+    /// Die Standardimplementierung liefert `None` für Repositories, die keine Änderungen melden.
+    fn changes(&self) -> Option<Arc<Signal<RepositoryChange<Application>>>> {
+        None
+    }
 }
@@ -0,0 +1,39 @@
+//! # Audio Repository Trait (`repositories::audio_repository`)
+//!
+//! Definiert das Trait [`AudioRepository`], über das der
+//! [`AudioService`](crate::services::AudioService) Audiogeräte und Streams abfragt und
+//! steuert. Die Implementierung in der Systemschicht spricht dafür mit PipeWire.
+
+use crate::entities::audio::{AudioDevice, AudioDeviceKind, AudioStream};
+use crate::DomainResult;
+use async_trait::async_trait;
+
+/// Ein Trait, das den Zugriff auf das Audiosystem abstrahiert.
+///
+/// Geräte werden über ihren Namen, Streams über ihre ID angesprochen. Lautstärken sind wie
+/// in [`entities::audio`](crate::entities::audio) beschrieben skaliert.
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AudioRepository: Send + Sync {
+    /// Alle Geräte beider Arten.
+    async fn get_devices(&self) -> DomainResult<Vec<AudioDevice>>;
+
+    /// Alle Streams der Anwendungen.
+    async fn get_streams(&self) -> DomainResult<Vec<AudioStream>>;
+
+    /// Macht das Gerät `name` zum Standardgerät der Art `kind`.
+    async fn set_default_device(&self, kind: AudioDeviceKind, name: &str) -> DomainResult<()>;
+
+    /// Setzt die Lautstärke des Geräts `name`.
+    async fn set_device_volume(&self, name: &str, volume: f32) -> DomainResult<()>;
+
+    /// Schaltet das Gerät `name` stumm oder wieder laut.
+    async fn set_device_muted(&self, name: &str, muted: bool) -> DomainResult<()>;
+
+    /// Setzt die Lautstärke des Streams `id`.
+    async fn set_stream_volume(&self, id: u32, volume: f32) -> DomainResult<()>;
+
+    /// Schaltet den Stream `id` stumm oder wieder laut.
+    async fn set_stream_muted(&self, id: u32, muted: bool) -> DomainResult<()>;
+}
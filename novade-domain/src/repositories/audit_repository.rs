@@ -0,0 +1,124 @@
+//! # Audit Repository Trait (`repositories::audit_repository`)
+//!
+//! Definiert das Trait [`AuditRepository`], das als Abstraktion für den Datenzugriff auf
+//! [`AuditRecord`](crate::entities::AuditRecord) Einträge dient, sowie die Abfrage
+//! [`AuditQuery`].
+
+use crate::entities::audit_record::{AuditOperation, AuditRecord};
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+use novade_core::types::Timestamp;
+
+/// Eine Abfrage über das Änderungsprotokoll, deren Bedingungen alle erfüllt sein müssen.
+///
+/// Nicht gesetzte Bedingungen schränken nicht ein. Der Zeitraum schließt `since` ein und
+/// `until` aus.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::{AuditOperation, AuditRecord};
+/// use novade_domain::repositories::AuditQuery;
+///
+/// let query = AuditQuery::new().entity_type("Application").operation(AuditOperation::Created);
+/// let record = AuditRecord::new("Application", "42", AuditOperation::Created, "Registriert.".to_string(), "system");
+/// assert!(query.matches(&record));
+/// assert!(!query.actor("anna").matches(&record));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditQuery {
+    /// Der Typ der geänderten Entität.
+    pub entity_type: Option<String>,
+    /// Die ID der geänderten Entität.
+    pub entity_id: Option<String>,
+    /// Die Art der Änderung.
+    pub operation: Option<AuditOperation>,
+    /// Wer die Änderung vorgenommen hat.
+    pub actor: Option<String>,
+    /// Frühester Zeitpunkt (einschließlich).
+    pub since: Option<Timestamp>,
+    /// Spätester Zeitpunkt (ausschließlich).
+    pub until: Option<Timestamp>,
+}
+
+impl AuditQuery {
+    /// Eine Abfrage ohne Bedingungen, die auf alle Einträge passt.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Nur Änderungen an Entitäten des Typs `entity_type`.
+    pub fn entity_type(mut self, entity_type: &str) -> Self {
+        self.entity_type = Some(entity_type.to_string());
+        self
+    }
+
+    /// Nur Änderungen an der Entität `entity_id`.
+    pub fn entity_id(mut self, entity_id: &str) -> Self {
+        self.entity_id = Some(entity_id.to_string());
+        self
+    }
+
+    /// Nur Änderungen der Art `operation`.
+    pub fn operation(mut self, operation: AuditOperation) -> Self {
+        self.operation = Some(operation);
+        self
+    }
+
+    /// Nur Änderungen von `actor`.
+    pub fn actor(mut self, actor: &str) -> Self {
+        self.actor = Some(actor.to_string());
+        self
+    }
+
+    /// Nur Änderungen im Zeitraum von `since` (einschließlich) bis `until` (ausschließlich).
+    pub fn between(mut self, since: Option<Timestamp>, until: Option<Timestamp>) -> Self {
+        self.since = since;
+        self.until = until;
+        self
+    }
+
+    /// Ob der Eintrag alle Bedingungen erfüllt.
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        self.entity_type.as_ref().is_none_or(|entity_type| &record.entity_type == entity_type)
+            && self.entity_id.as_ref().is_none_or(|entity_id| &record.entity_id == entity_id)
+            && self.operation.is_none_or(|operation| record.operation == operation)
+            && self.actor.as_ref().is_none_or(|actor| &record.actor == actor)
+            && self.since.as_ref().is_none_or(|since| &record.timestamp >= since)
+            && self.until.as_ref().is_none_or(|until| &record.timestamp < until)
+    }
+}
+
+/// Ein Trait, das das Anhängen und Abfragen des Änderungsprotokolls abstrahiert.
+///
+/// Einträge werden nur angehängt, nie geändert; entfernt werden sie nur gesammelt nach Alter.
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    /// Hängt einen Eintrag an das Protokoll an.
+    async fn append(&self, record: &AuditRecord) -> DomainResult<()>;
+
+    /// Ruft alle Einträge in der Reihenfolge ab, in der sie angehängt wurden.
+    async fn get_all(&self) -> DomainResult<Vec<AuditRecord>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<AuditRecord>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Ruft alle Einträge ab, die die Abfrage erfüllen, in der Reihenfolge, in der sie
+    /// angehängt wurden.
+    ///
+    /// Die Standardimplementierung filtert das Ergebnis von [`get_all`](Self::get_all);
+    /// Implementierungen mit Indizes sollten die Abfrage in Index-Zugriffe übersetzen.
+    async fn find(&self, query: &AuditQuery) -> DomainResult<Vec<AuditRecord>> {
+        Ok(self.get_all().await?.into_iter().filter(|record| query.matches(record)).collect())
+    }
+
+    /// Entfernt alle Einträge, die älter als `cutoff` sind.
+    async fn remove_before(&self, cutoff: &Timestamp) -> DomainResult<()>;
+}
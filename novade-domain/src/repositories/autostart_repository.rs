@@ -0,0 +1,37 @@
+//! # Autostart Repository Trait (`repositories::autostart_repository`)
+//!
+//! Definiert das Trait [`AutostartRepository`], das als Abstraktion für den
+//! Datenzugriff auf [`AutostartEntry`](crate::entities::AutostartEntry) Entitäten dient.
+//! Einträge werden über die Desktop-Datei-ID ihrer Anwendung identifiziert.
+
+use crate::entities::autostart::AutostartEntry;
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+
+/// Ein Trait, das das Speichern und Abrufen von Autostart-Einträgen abstrahiert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AutostartRepository: Send + Sync {
+    /// Ruft den Eintrag der Anwendung `application_id` ab.
+    async fn get_by_id(&self, application_id: &str) -> DomainResult<Option<AutostartEntry>>;
+
+    /// Ruft alle Einträge ab, aktivierte wie deaktivierte.
+    async fn get_all(&self) -> DomainResult<Vec<AutostartEntry>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<AutostartEntry>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Speichert einen Eintrag und ersetzt einen vorhandenen derselben Anwendung.
+    async fn save(&self, entry: &AutostartEntry) -> DomainResult<()>;
+
+    /// Entfernt den Eintrag der Anwendung `application_id`, falls vorhanden.
+    async fn remove(&self, application_id: &str) -> DomainResult<()>;
+}
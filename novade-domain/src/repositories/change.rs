@@ -0,0 +1,36 @@
+//! # Änderungsbenachrichtigungen (`repositories::change`)
+//!
+//! Definiert [`RepositoryChange`], mit dem Repositories über ein
+//! [`Signal`](novade_core::signal::Signal) melden, welche Entitäten hinzugefügt, geändert oder
+//! entfernt wurden. So können z.B. die Benutzeroberfläche oder Caches Änderungen übernehmen,
+//! ohne das Repository regelmäßig abzufragen.
+//!
+//! Die Repository-Traits bieten das Signal über eine `changes`-Methode an, deren
+//! Standardimplementierung `None` liefert. Implementierungen, die Änderungen melden, senden
+//! jede Änderung erst, nachdem sie gespeichert wurde.
+
+use crate::entities::UserPreferenceSetting;
+use novade_core::types::NovaId;
+
+/// Eine Änderung an einem Repository mit der betroffenen Entität.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepositoryChange<T> {
+    /// Die Entität wurde hinzugefügt.
+    Added(T),
+    /// Die Entität wurde geändert; enthalten ist der neue Stand.
+    Updated(T),
+    /// Die Entität wurde entfernt; enthalten ist der zuletzt gespeicherte Stand.
+    Removed(T),
+}
+
+impl<T> RepositoryChange<T> {
+    /// Die betroffene Entität.
+    pub fn entity(&self) -> &T {
+        match self {
+            Self::Added(entity) | Self::Updated(entity) | Self::Removed(entity) => entity,
+        }
+    }
+}
+
+/// Eine Einstellung zusammen mit dem Benutzer, dem sie gehört (`None` für systemweit).
+pub type ScopedPreference = (Option<NovaId>, UserPreferenceSetting);
@@ -0,0 +1,38 @@
+//! # Display Layout Repository Trait (`repositories::display_layout_repository`)
+//!
+//! Definiert das Trait [`DisplayLayoutRepository`], das als Abstraktion für den
+//! Datenzugriff auf [`DisplayLayout`](crate::entities::DisplayLayout) Entitäten dient.
+//! Layouts werden über ihren Schlüssel identifiziert, die sortierten Namen ihrer Anschlüsse
+//! (siehe [`layout_key`](crate::entities::display::layout_key)).
+
+use crate::entities::display::DisplayLayout;
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+
+/// Ein Trait, das das Speichern und Abrufen von Bildschirm-Layouts abstrahiert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DisplayLayoutRepository: Send + Sync {
+    /// Ruft das Layout mit dem Schlüssel `key` ab.
+    async fn get_by_key(&self, key: &str) -> DomainResult<Option<DisplayLayout>>;
+
+    /// Ruft alle gespeicherten Layouts ab.
+    async fn get_all(&self) -> DomainResult<Vec<DisplayLayout>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<DisplayLayout>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Speichert ein Layout und ersetzt ein vorhandenes mit demselben Schlüssel.
+    async fn save(&self, layout: &DisplayLayout) -> DomainResult<()>;
+
+    /// Entfernt das Layout mit dem Schlüssel `key`, falls vorhanden.
+    async fn remove(&self, key: &str) -> DomainResult<()>;
+}
@@ -0,0 +1,35 @@
+//! # Icon Theme Repository Trait (`repositories::icon_theme_repository`)
+//!
+//! Definiert das Trait [`IconThemeRepository`], über das der
+//! [`IconResolverService`](crate::services::IconResolverService) installierte Icon-Themes
+//! durchsucht. Die Implementierung in der Systemschicht liest die Themes nach der
+//! freedesktop.org Icon Theme Specification aus den Icon-Verzeichnissen.
+
+use crate::DomainResult;
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Ein Trait, das die Suche nach Icon-Dateien in installierten Icon-Themes abstrahiert.
+/// Themes werden über ihren Verzeichnisnamen identifiziert (z.B. "Adwaita", "hicolor").
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait IconThemeRepository: Send + Sync {
+    /// Sucht `icon_name` (ohne Dateiendung) nur im Theme `theme`, ohne dessen Eltern-Themes.
+    ///
+    /// Bevorzugt ein Icon, das zu `size` und `scale` passt, und liefert sonst das Icon mit dem
+    /// geringsten Größenabstand.
+    ///
+    /// # Rückgabe
+    /// Der Pfad der Icon-Datei, oder `None`, wenn das Theme das Icon nicht enthält oder nicht
+    /// installiert ist.
+    async fn lookup_icon(&self, theme: &str, icon_name: &str, size: u32, scale: u32) -> DomainResult<Option<PathBuf>>;
+
+    /// Die Themes, von denen `theme` erbt, in der angegebenen Reihenfolge; leer für
+    /// unbekannte Themes.
+    async fn parent_themes(&self, theme: &str) -> DomainResult<Vec<String>>;
+
+    /// Sucht `icon_name` außerhalb der Themes (z.B. in `/usr/share/pixmaps`).
+    async fn lookup_fallback_icon(&self, icon_name: &str) -> DomainResult<Option<PathBuf>>;
+}
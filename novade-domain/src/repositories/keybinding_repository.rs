@@ -0,0 +1,47 @@
+//! # Keybinding Repository Trait (`repositories::keybinding_repository`)
+//!
+//! Definiert das Trait [`KeybindingRepository`], das als Abstraktion für den
+//! Datenzugriff auf [`Keybinding`](crate::entities::Keybinding) Entitäten dient.
+
+use crate::entities::keybinding::Keybinding;
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+use novade_core::types::NovaId;
+
+/// Ein Trait, das Operationen zum Speichern und Abrufen von
+/// [`Keybinding`](crate::entities::Keybinding)-Entitäten abstrahiert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait KeybindingRepository: Send + Sync {
+    /// Ruft ein Tastenkürzel anhand seiner ID ab.
+    ///
+    /// # Rückgabe
+    /// `Some(Keybinding)`, wenn das Tastenkürzel gefunden wurde, sonst `None`.
+    async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Keybinding>>;
+
+    /// Ruft alle Tastenkürzel aller Kontexte ab.
+    async fn get_all(&self) -> DomainResult<Vec<Keybinding>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<Keybinding>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Fügt ein neues Tastenkürzel hinzu.
+    async fn add(&self, keybinding: &Keybinding) -> DomainResult<()>;
+
+    /// Aktualisiert ein vorhandenes Tastenkürzel, identifiziert über seine `id`.
+    ///
+    /// # Rückgabe
+    /// Ein `DomainError`, wenn das Tastenkürzel nicht gefunden wird.
+    async fn update(&self, keybinding: &Keybinding) -> DomainResult<()>;
+
+    /// Entfernt ein Tastenkürzel anhand seiner ID.
+    async fn remove(&self, id: &NovaId) -> DomainResult<()>;
+}
@@ -0,0 +1,37 @@
+//! # Launch History Repository Trait (`repositories::launch_history_repository`)
+//!
+//! Definiert das Trait [`LaunchHistoryRepository`], das als Abstraktion für den
+//! Datenzugriff auf [`LaunchRecord`](crate::entities::LaunchRecord) Einträge dient.
+
+use crate::entities::launch_record::LaunchRecord;
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+use novade_core::types::{NovaId, Timestamp};
+
+/// Ein Trait, das das Speichern und Abrufen des Startprotokolls von Anwendungen abstrahiert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait LaunchHistoryRepository: Send + Sync {
+    /// Fügt einen Eintrag zum Startprotokoll hinzu.
+    async fn add(&self, record: &LaunchRecord) -> DomainResult<()>;
+
+    /// Ruft alle Einträge des Startprotokolls ab.
+    async fn get_all(&self) -> DomainResult<Vec<LaunchRecord>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<LaunchRecord>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Entfernt alle Einträge einer Anwendung, z.B. wenn sie deinstalliert wurde.
+    async fn remove_for_application(&self, application_id: &NovaId) -> DomainResult<()>;
+
+    /// Entfernt alle Einträge, die älter als `cutoff` sind.
+    async fn remove_before(&self, cutoff: &Timestamp) -> DomainResult<()>;
+}
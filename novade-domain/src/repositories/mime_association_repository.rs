@@ -0,0 +1,40 @@
+//! # MIME Association Repository Trait (`repositories::mime_association_repository`)
+//!
+//! Definiert das Trait [`MimeAssociationRepository`], das als Abstraktion für den
+//! Datenzugriff auf [`MimeAssociation`](crate::entities::MimeAssociation) Entitäten dient.
+//!
+//! Eine Implementierung in der Systemschicht liest und schreibt typischerweise die
+//! `mimeapps.list`-Dateien des Benutzers und des Systems.
+
+use crate::entities::mime_association::MimeAssociation;
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+
+/// Ein Trait, das das Lesen und Schreiben von MIME-Zuordnungen abstrahiert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait MimeAssociationRepository: Send + Sync {
+    /// Ruft die wirksame Zuordnung eines MIME-Typs ab, z.B. zusammengeführt aus den
+    /// Einstellungen des Benutzers und des Systems.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn für den MIME-Typ nichts festgelegt ist.
+    async fn get(&self, mime_type: &str) -> DomainResult<Option<MimeAssociation>>;
+
+    /// Ruft die wirksamen Zuordnungen aller MIME-Typen ab, für die etwas festgelegt ist.
+    async fn get_all(&self) -> DomainResult<Vec<MimeAssociation>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<MimeAssociation>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Speichert die Zuordnung eines MIME-Typs in den Einstellungen des Benutzers.
+    async fn set(&self, association: &MimeAssociation) -> DomainResult<()>;
+}
@@ -13,17 +13,65 @@
 //! ## Definierte Repository-Traits:
 //!
 //! - [`application_repository::ApplicationRepository`]: Für den Zugriff auf [`Application`](crate::entities::Application) Entitäten.
+//! - [`audio_repository::AudioRepository`]: Für Audiogeräte und die Audio-Streams der Anwendungen.
+//! - [`audit_repository::AuditRepository`]: Für das Änderungsprotokoll aus [`AuditRecord`](crate::entities::AuditRecord) Einträgen.
+//! - [`autostart_repository::AutostartRepository`]: Für den Zugriff auf [`AutostartEntry`](crate::entities::AutostartEntry) Entitäten.
+//! - [`display_layout_repository::DisplayLayoutRepository`]: Für den Zugriff auf [`DisplayLayout`](crate::entities::DisplayLayout) Entitäten.
+//! - [`icon_theme_repository::IconThemeRepository`]: Für die Suche nach Icon-Dateien in installierten Icon-Themes.
+//! - [`keybinding_repository::KeybindingRepository`]: Für den Zugriff auf [`Keybinding`](crate::entities::Keybinding) Entitäten.
+//! - [`launch_history_repository::LaunchHistoryRepository`]: Für den Zugriff auf [`LaunchRecord`](crate::entities::LaunchRecord) Einträge.
+//! - [`mime_association_repository::MimeAssociationRepository`]: Für den Zugriff auf [`MimeAssociation`](crate::entities::MimeAssociation) Entitäten.
+//! - [`network_repository::NetworkRepository`]: Für den Zustand sowie den Auf- und Abbau von Netzwerkverbindungen.
+//! - [`notification_repository::NotificationRepository`]: Für den Zugriff auf [`Notification`](crate::entities::Notification) Entitäten.
+//! - [`power_repository::PowerRepository`]: Für Energieprofile und den Akkuzustand.
+//! - [`recent_item_repository::RecentItemRepository`]: Für den Zugriff auf [`RecentItem`](crate::entities::RecentItem) Entitäten.
+//! - [`theme_repository::ThemeRepository`]: Für den Zugriff auf [`Theme`](crate::entities::Theme) Entitäten.
 //! - [`user_preference_repository::UserPreferenceRepository`]: Für den Zugriff auf [`UserPreferenceSetting`](crate::entities::UserPreferenceSetting) Entitäten.
 //! - [`workspace_repository::WorkspaceRepository`]: Für den Zugriff auf [`Workspace`](crate::entities::Workspace) Entitäten.
 //!
+//! Die Repositories für Anwendungen, Workspaces und Einstellungen können ihre Änderungen als
+//! [`RepositoryChange`] melden (siehe [`change`]).
+//!
+//! Alle Traits für Entitäten bieten mit `get_page` einen seitenweisen, sortierten Abruf (siehe [`paging`]).
+//!
 //! Die Traits werden hier für einen einfacheren Zugriff re-exportiert.
 
 pub mod application_repository;
+pub mod audio_repository;
+pub mod audit_repository;
+pub mod autostart_repository;
+pub mod change;
+pub mod display_layout_repository;
+pub mod icon_theme_repository;
+pub mod keybinding_repository;
+pub mod launch_history_repository;
+pub mod mime_association_repository;
+pub mod network_repository;
+pub mod notification_repository;
+pub mod paging;
+pub mod power_repository;
+pub mod recent_item_repository;
+pub mod theme_repository;
 pub mod user_preference_repository;
 pub mod workspace_repository;
 
 // Re-exportiere die Repository-Traits, um den Zugriff für Implementierer und Nutzer zu vereinfachen.
 // Ermöglicht z.B. `use novade_domain::repositories::ApplicationRepository;`
-pub use application_repository::ApplicationRepository;
+pub use application_repository::{ApplicationQuery, ApplicationRepository};
+pub use audio_repository::AudioRepository;
+pub use audit_repository::{AuditQuery, AuditRepository};
+pub use autostart_repository::AutostartRepository;
+pub use change::{RepositoryChange, ScopedPreference};
+pub use display_layout_repository::DisplayLayoutRepository;
+pub use icon_theme_repository::IconThemeRepository;
+pub use keybinding_repository::KeybindingRepository;
+pub use launch_history_repository::LaunchHistoryRepository;
+pub use mime_association_repository::MimeAssociationRepository;
+pub use network_repository::NetworkRepository;
+pub use notification_repository::NotificationRepository;
+pub use paging::{Page, PagedResult, SortOrder};
+pub use power_repository::PowerRepository;
+pub use recent_item_repository::RecentItemRepository;
+pub use theme_repository::ThemeRepository;
 pub use user_preference_repository::UserPreferenceRepository;
 pub use workspace_repository::WorkspaceRepository;
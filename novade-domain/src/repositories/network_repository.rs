@@ -0,0 +1,28 @@
+//! # Network Repository Trait (`repositories::network_repository`)
+//!
+//! Definiert das Trait [`NetworkRepository`], über das der
+//! [`NetworkService`](crate::services::NetworkService) die Netzwerkverbindungen abfragt sowie
+//! auf- und abbaut. Die Implementierung in der Systemschicht spricht dafür über D-Bus mit
+//! NetworkManager.
+
+use crate::entities::network::NetworkConnection;
+use crate::DomainResult;
+use async_trait::async_trait;
+
+/// Ein Trait, das den Zugriff auf den Netzwerkdienst abstrahiert.
+///
+/// Verbindungen werden über ihre ID angesprochen. Auf- und Abbau kehren zurück, sobald der
+/// Netzwerkdienst den Auftrag angenommen hat, nicht erst, wenn die Verbindung steht.
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NetworkRepository: Send + Sync {
+    /// Alle bekannten Verbindungen mit ihrem aktuellen Zustand.
+    async fn get_connections(&self) -> DomainResult<Vec<NetworkConnection>>;
+
+    /// Baut die Verbindung `id` auf.
+    async fn connect(&self, id: &str) -> DomainResult<()>;
+
+    /// Baut die Verbindung `id` ab.
+    async fn disconnect(&self, id: &str) -> DomainResult<()>;
+}
@@ -0,0 +1,53 @@
+//! # Notification Repository Trait (`repositories::notification_repository`)
+//!
+//! Definiert das Trait [`NotificationRepository`], das als Abstraktion für den
+//! Datenzugriff auf [`Notification`](crate::entities::Notification) Entitäten dient.
+//!
+//! Das Repository hält sowohl aktive als auch geschlossene Benachrichtigungen und bildet
+//! damit die Benachrichtigungshistorie.
+
+use crate::entities::notification::Notification;
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+use novade_core::types::NovaId;
+
+/// Ein Trait, das Operationen zum Speichern und Abrufen von
+/// [`Notification`](crate::entities::Notification)-Entitäten abstrahiert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    /// Ruft eine Benachrichtigung anhand ihrer ID ab.
+    ///
+    /// # Rückgabe
+    /// `Some(Notification)`, wenn die Benachrichtigung gefunden wurde, sonst `None`.
+    async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Notification>>;
+
+    /// Ruft alle gespeicherten Benachrichtigungen ab, einschließlich der geschlossenen.
+    async fn get_all(&self) -> DomainResult<Vec<Notification>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<Notification>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Fügt eine neue Benachrichtigung hinzu.
+    ///
+    /// # Rückgabe
+    /// Ein `DomainError`, wenn bereits eine Benachrichtigung mit derselben ID existiert.
+    async fn add(&self, notification: &Notification) -> DomainResult<()>;
+
+    /// Aktualisiert eine vorhandene Benachrichtigung, identifiziert über ihre `id`.
+    ///
+    /// # Rückgabe
+    /// Ein `DomainError`, wenn die Benachrichtigung nicht gefunden wird.
+    async fn update(&self, notification: &Notification) -> DomainResult<()>;
+
+    /// Entfernt eine Benachrichtigung endgültig, auch aus der Historie.
+    async fn remove(&self, id: &NovaId) -> DomainResult<()>;
+}
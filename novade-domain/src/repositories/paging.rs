@@ -0,0 +1,317 @@
+//! # Seitenweiser Abruf (`repositories::paging`)
+//!
+//! Definiert [`Page`] und [`PagedResult`] für den seitenweisen, sortierten Abruf aus den
+//! Repositories sowie das Trait [`Sortable`], das festlegt, nach welchen Feldern eine
+//! Entität sortiert werden kann.
+//!
+//! Die `get_page`-Methoden der Repository-Traits haben Standardimplementierungen, die
+//! [`paginate`] auf das Ergebnis von `get_all` anwenden. Implementierungen mit eigener
+//! Sortierung oder Indizes (z.B. Datenbanken) sollten sie überschreiben.
+
+use crate::entities::{
+    Application, AuditRecord, AutostartEntry, DisplayLayout, Keybinding, LaunchRecord, MimeAssociation, Notification, RecentItem, Theme,
+    UserPreferenceSetting, Workspace,
+};
+use crate::{DomainError, DomainResult};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Die Sortierrichtung.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// Eine angeforderte Seite: ab `offset` höchstens `limit` Einträge, optional sortiert nach
+/// dem Feld `sort_by`. Ohne `sort_by` bleibt die Reihenfolge des Repositorys erhalten.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page {
+    pub offset: usize,
+    pub limit: usize,
+    pub sort_by: Option<String>,
+    pub order: SortOrder,
+}
+
+impl Page {
+    /// Die erste Seite mit höchstens `limit` Einträgen.
+    pub fn first(limit: usize) -> Self {
+        Self { offset: 0, limit, sort_by: None, order: SortOrder::Ascending }
+    }
+
+    /// Sortiert die Seite nach `field` in der Richtung `order`.
+    pub fn sorted_by(mut self, field: &str, order: SortOrder) -> Self {
+        self.sort_by = Some(field.to_string());
+        self.order = order;
+        self
+    }
+
+    /// Die darauf folgende Seite mit derselben Größe und Sortierung.
+    pub fn next(&self) -> Self {
+        Self { offset: self.offset + self.limit, ..self.clone() }
+    }
+}
+
+/// Eine Seite von Einträgen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PagedResult<T> {
+    /// Die Einträge der Seite.
+    pub items: Vec<T>,
+    /// Die Gesamtzahl der Einträge über alle Seiten.
+    pub total: usize,
+    /// Der Offset des ersten Eintrags der Seite.
+    pub offset: usize,
+}
+
+impl<T> PagedResult<T> {
+    /// Ob nach dieser Seite weitere Einträge folgen.
+    pub fn has_more(&self) -> bool {
+        self.offset + self.items.len() < self.total
+    }
+}
+
+/// Eine Entität, die nach benannten Feldern sortiert werden kann.
+pub trait Sortable {
+    /// Die Felder, nach denen sortiert werden kann.
+    fn sort_fields() -> &'static [&'static str];
+
+    /// Vergleicht aufsteigend nach `field`, einem Eintrag aus [`sort_fields`](Self::sort_fields).
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering;
+}
+
+/// Sortiert `items` nach `page` und schneidet die Seite heraus.
+///
+/// # Rückgabe
+/// Die Seite, oder `DomainError::ValidationError`, wenn `limit` 0 ist oder nach einem
+/// unbekannten Feld sortiert werden soll.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::Workspace;
+/// use novade_domain::repositories::paging::{paginate, Page, SortOrder};
+///
+/// let workspaces = ["B", "C", "A"].map(|name| Workspace::new(name.to_string(), None)).to_vec();
+/// let page = paginate(workspaces, &Page::first(2).sorted_by("name", SortOrder::Ascending)).unwrap();
+/// assert_eq!(page.items.iter().map(|ws| ws.name.as_str()).collect::<Vec<_>>(), vec!["A", "B"]);
+/// assert!(page.has_more());
+/// ```
+pub fn paginate<T: Sortable>(mut items: Vec<T>, page: &Page) -> DomainResult<PagedResult<T>> {
+    if page.limit == 0 {
+        return Err(DomainError::ValidationError {
+            field: "limit".to_string(),
+            message: "Eine Seite muss mindestens einen Eintrag umfassen.".to_string(),
+        });
+    }
+    if let Some(field) = &page.sort_by {
+        if !T::sort_fields().contains(&field.as_str()) {
+            return Err(DomainError::ValidationError {
+                field: "sort_by".to_string(),
+                message: format!("Nach '{}' kann nicht sortiert werden; möglich sind {:?}.", field, T::sort_fields()),
+            });
+        }
+        items.sort_by(|a, b| match page.order {
+            SortOrder::Ascending => a.compare_by(b, field),
+            SortOrder::Descending => b.compare_by(a, field),
+        });
+    }
+    let total = items.len();
+    let items = items.into_iter().skip(page.offset).take(page.limit).collect();
+    Ok(PagedResult { items, total, offset: page.offset })
+}
+
+/// Vergleicht Texte ohne Unterscheidung von Groß-/Kleinschreibung.
+fn compare_text(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+impl Sortable for Application {
+    fn sort_fields() -> &'static [&'static str] {
+        &["name", "display_name"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "display_name" => compare_text(
+                self.display_name.as_deref().unwrap_or(&self.name),
+                other.display_name.as_deref().unwrap_or(&other.name),
+            ),
+            _ => compare_text(&self.name, &other.name),
+        }
+    }
+}
+
+impl Sortable for AuditRecord {
+    fn sort_fields() -> &'static [&'static str] {
+        &["timestamp", "entity_type", "actor"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "entity_type" => self.entity_type.cmp(&other.entity_type).then_with(|| self.timestamp.cmp(&other.timestamp)),
+            "actor" => self.actor.cmp(&other.actor).then_with(|| self.timestamp.cmp(&other.timestamp)),
+            _ => self.timestamp.cmp(&other.timestamp),
+        }
+    }
+}
+
+impl Sortable for Workspace {
+    fn sort_fields() -> &'static [&'static str] {
+        &["name", "index", "last_activated_at", "active_duration"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "index" => self.index.cmp(&other.index),
+            "last_activated_at" => self.last_activated_at.cmp(&other.last_activated_at),
+            "active_duration" => self.active_duration.cmp(&other.active_duration),
+            _ => compare_text(&self.name, &other.name),
+        }
+    }
+}
+
+impl Sortable for UserPreferenceSetting {
+    fn sort_fields() -> &'static [&'static str] {
+        &["key", "group"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "group" => self.group.cmp(&other.group).then_with(|| self.key.cmp(&other.key)),
+            _ => self.key.cmp(&other.key),
+        }
+    }
+}
+
+impl Sortable for Notification {
+    fn sort_fields() -> &'static [&'static str] {
+        &["created_at", "app_name"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "app_name" => compare_text(&self.app_name, &other.app_name),
+            _ => self.created_at.cmp(&other.created_at),
+        }
+    }
+}
+
+impl Sortable for Theme {
+    fn sort_fields() -> &'static [&'static str] {
+        &["name"]
+    }
+
+    fn compare_by(&self, other: &Self, _field: &str) -> Ordering {
+        compare_text(&self.name, &other.name)
+    }
+}
+
+impl Sortable for Keybinding {
+    fn sort_fields() -> &'static [&'static str] {
+        &["accelerator", "action", "context"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "action" => self.action.cmp(&other.action),
+            "context" => self.context.cmp(&other.context),
+            _ => self.accelerator.cmp(&other.accelerator),
+        }
+    }
+}
+
+impl Sortable for LaunchRecord {
+    fn sort_fields() -> &'static [&'static str] {
+        &["launched_at"]
+    }
+
+    fn compare_by(&self, other: &Self, _field: &str) -> Ordering {
+        self.launched_at.cmp(&other.launched_at)
+    }
+}
+
+impl Sortable for MimeAssociation {
+    fn sort_fields() -> &'static [&'static str] {
+        &["mime_type"]
+    }
+
+    fn compare_by(&self, other: &Self, _field: &str) -> Ordering {
+        self.mime_type.cmp(&other.mime_type)
+    }
+}
+
+impl Sortable for RecentItem {
+    fn sort_fields() -> &'static [&'static str] {
+        &["uri", "last_accessed", "access_count"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "last_accessed" => self.last_accessed.cmp(&other.last_accessed),
+            "access_count" => self.access_count.cmp(&other.access_count),
+            _ => self.uri.cmp(&other.uri),
+        }
+    }
+}
+
+impl Sortable for DisplayLayout {
+    fn sort_fields() -> &'static [&'static str] {
+        &["key", "outputs"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "outputs" => self.outputs.len().cmp(&other.outputs.len()),
+            _ => self.key().cmp(&other.key()),
+        }
+    }
+}
+
+impl Sortable for AutostartEntry {
+    fn sort_fields() -> &'static [&'static str] {
+        &["application_id", "delay"]
+    }
+
+    fn compare_by(&self, other: &Self, field: &str) -> Ordering {
+        match field {
+            "delay" => self.delay.cmp(&other.delay),
+            _ => self.application_id.cmp(&other.application_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspaces() -> Vec<Workspace> {
+        ["b", "C", "a", "D", "e"].iter().map(|name| Workspace::new(name.to_string(), None)).collect()
+    }
+
+    #[test]
+    fn test_pages_cover_all_items() {
+        let mut page = Page::first(2).sorted_by("name", SortOrder::Descending);
+        let mut names = Vec::new();
+        loop {
+            let result = paginate(workspaces(), &page).unwrap();
+            assert_eq!(result.total, 5);
+            names.extend(result.items.iter().map(|ws| ws.name.clone()));
+            if !result.has_more() {
+                break;
+            }
+            page = page.next();
+        }
+        assert_eq!(names, vec!["e", "D", "C", "b", "a"]);
+    }
+
+    #[test]
+    fn test_invalid_pages() {
+        assert!(matches!(paginate(workspaces(), &Page::first(0)), Err(DomainError::ValidationError { .. })));
+        let unknown = Page::first(10).sorted_by("color", SortOrder::Ascending);
+        assert!(matches!(paginate(workspaces(), &unknown), Err(DomainError::ValidationError { field, .. }) if field == "sort_by"));
+        // Ohne Sortierung bleibt die Reihenfolge des Repositorys erhalten.
+        let unsorted = paginate(workspaces(), &Page { offset: 3, ..Page::first(10) }).unwrap();
+        assert_eq!(unsorted.items.iter().map(|ws| ws.name.as_str()).collect::<Vec<_>>(), vec!["D", "e"]);
+        assert!(!unsorted.has_more());
+    }
+}
@@ -0,0 +1,29 @@
+//! # Power Repository Trait (`repositories::power_repository`)
+//!
+//! Definiert das Trait [`PowerRepository`], über das der
+//! [`PowerService`](crate::services::PowerService) Energieprofile liest und setzt sowie den
+//! Akkuzustand abfragt. Die Implementierung in der Systemschicht spricht dafür über D-Bus
+//! mit power-profiles-daemon und UPower.
+
+use crate::entities::power::{BatteryState, PowerProfile};
+use crate::DomainResult;
+use async_trait::async_trait;
+
+/// Ein Trait, das den Zugriff auf Energieprofile und den Akkuzustand abstrahiert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PowerRepository: Send + Sync {
+    /// Das aktuell aktive Energieprofil.
+    async fn active_profile(&self) -> DomainResult<PowerProfile>;
+
+    /// Die Profile, die die Hardware unterstützt, vom sparsamsten zum leistungsstärksten.
+    async fn available_profiles(&self) -> DomainResult<Vec<PowerProfile>>;
+
+    /// Aktiviert `profile`.
+    async fn set_active_profile(&self, profile: PowerProfile) -> DomainResult<()>;
+
+    /// Der aktuelle Akkuzustand, oder `None`, wenn das Gerät keinen Akku hat.
+    async fn battery_state(&self) -> DomainResult<Option<BatteryState>>;
+}
@@ -0,0 +1,37 @@
+//! # Recent Item Repository Trait (`repositories::recent_item_repository`)
+//!
+//! Definiert das Trait [`RecentItemRepository`], das als Abstraktion für den
+//! Datenzugriff auf [`RecentItem`](crate::entities::RecentItem) Entitäten dient.
+
+use crate::entities::recent_item::RecentItem;
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+
+/// Ein Trait, das das Speichern und Abrufen zuletzt verwendeter Elemente abstrahiert.
+/// Elemente werden über ihre URI identifiziert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait RecentItemRepository: Send + Sync {
+    /// Ruft ein Element anhand seiner URI ab.
+    async fn get_by_uri(&self, uri: &str) -> DomainResult<Option<RecentItem>>;
+
+    /// Ruft alle gespeicherten Elemente ab.
+    async fn get_all(&self) -> DomainResult<Vec<RecentItem>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<RecentItem>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Speichert ein Element und ersetzt ein vorhandenes mit derselben URI.
+    async fn save(&self, item: &RecentItem) -> DomainResult<()>;
+
+    /// Entfernt das Element mit der gegebenen URI, falls vorhanden.
+    async fn remove(&self, uri: &str) -> DomainResult<()>;
+}
@@ -0,0 +1,52 @@
+//! # Theme Repository Trait (`repositories::theme_repository`)
+//!
+//! Definiert das Trait [`ThemeRepository`], das als Abstraktion für den
+//! Datenzugriff auf [`Theme`](crate::entities::Theme) Entitäten dient.
+//!
+//! Eine Implementierung in der Systemschicht kann Themes z.B. aus Theme-Verzeichnissen
+//! im Dateisystem laden. Themes werden über ihren Namen identifiziert.
+
+use crate::entities::theme::Theme;
+use crate::repositories::paging::{paginate, Page, PagedResult};
+use crate::DomainResult;
+use async_trait::async_trait;
+
+/// Ein Trait, das Operationen zum Speichern und Abrufen von
+/// [`Theme`](crate::entities::Theme)-Entitäten abstrahiert.
+///
+/// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ThemeRepository: Send + Sync {
+    /// Ruft ein Theme anhand seines Namens ab.
+    ///
+    /// # Rückgabe
+    /// `Some(Theme)`, wenn das Theme gefunden wurde, sonst `None`.
+    async fn get_by_name(&self, name: &str) -> DomainResult<Option<Theme>>;
+
+    /// Ruft alle bekannten Themes ab.
+    async fn get_all(&self) -> DomainResult<Vec<Theme>>;
+
+    /// Ruft eine Seite der Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<Theme>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Fügt ein neues Theme hinzu.
+    ///
+    /// # Rückgabe
+    /// `DomainError::Conflict`, wenn bereits ein Theme mit demselben Namen existiert.
+    async fn add(&self, theme: &Theme) -> DomainResult<()>;
+
+    /// Aktualisiert ein vorhandenes Theme, identifiziert über seinen `name`.
+    ///
+    /// # Rückgabe
+    /// Ein `DomainError`, wenn das Theme nicht gefunden wird.
+    async fn update(&self, theme: &Theme) -> DomainResult<()>;
+
+    /// Entfernt ein Theme anhand seines Namens.
+    async fn remove(&self, name: &str) -> DomainResult<()>;
+}
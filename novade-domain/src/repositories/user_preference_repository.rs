@@ -7,16 +7,18 @@
 //! ohne dass die Domänendienste die Details der Persistenz kennen müssen.
 //! Die konkrete Implementierung erfolgt in der Systemschicht (`novade-system`).
 //!
-//! **Hinweis zur Benutzerbindung**: Die aktuellen Methoden sind nicht explizit
-//! benutzergebunden (d.h. sie nehmen keine `user_id` als Parameter). Es wird angenommen,
-//! dass die Implementierung des Repositories den aktuellen Benutzerkontext kennt
-//! oder dass die Einstellungen systemweit gelten. Zukünftige Erweiterungen könnten
-//! eine explizite Benutzer-ID einführen.
+//! **Benutzerbindung**: Alle Methoden nehmen eine `user_id: Option<NovaId>` entgegen.
+//! Einstellungen verschiedener Benutzer sind vollständig voneinander getrennt;
+//! `None` bezeichnet den benutzerunabhängigen (systemweiten) Bereich.
 
 use crate::entities::user_preference::UserPreferenceSetting;
+use crate::repositories::change::{RepositoryChange, ScopedPreference};
+use crate::repositories::paging::{paginate, Page, PagedResult};
 use crate::DomainResult;
 use async_trait::async_trait;
-// use novade_core::types::NovaId; // Auskommentiert, da aktuell nicht für Benutzerbindung verwendet
+use novade_core::signal::Signal;
+use std::sync::Arc;
+use novade_core::types::NovaId;
 
 /// Ein Trait, das Operationen zum Speichern und Abrufen von
 /// [`UserPreferenceSetting`](crate::entities::user_preference::UserPreferenceSetting)-Entitäten abstrahiert.
@@ -24,27 +26,37 @@ use async_trait::async_trait;
 /// Implementierungen dieses Traits sind für die Persistenzlogik von Benutzereinstellungen zuständig.
 /// Das Trait ist `async_trait`, um asynchrone Operationen zu unterstützen.
 /// `Send + Sync` Bounds sind für die thread-sichere Nutzung erforderlich.
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait UserPreferenceRepository: Send + Sync {
     /// Ruft eine spezifische Benutzereinstellung anhand ihres eindeutigen Schlüssels ab.
     ///
     /// # Parameter
+    /// * `user_id`: Der Benutzer, dessen Einstellung gesucht wird (`None` für systemweit).
     /// * `key`: Der eindeutige Schlüssel der gesuchten Einstellung (z.B. "theme.dark_mode").
     ///
     /// # Rückgabe
     /// Ein `DomainResult`, das bei Erfolg `Some(UserPreferenceSetting)` enthält, wenn die
     /// Einstellung gefunden wurde, oder `None`, andernfalls ein `DomainError`.
-    async fn get_preference(&self, key: &str) -> DomainResult<Option<UserPreferenceSetting>>;
+    async fn get_preference(&self, user_id: Option<NovaId>, key: &str) -> DomainResult<Option<UserPreferenceSetting>>;
     
     /// Ruft eine Liste aller bekannten Benutzereinstellungen ab.
     ///
-    /// Abhängig von der Implementierung können dies systemweite Standardeinstellungen
-    /// oder benutzerspezifische Einstellungen sein, falls der Kontext bekannt ist.
+    /// # Parameter
+    /// * `user_id`: Der Benutzer, dessen Einstellungen geliefert werden (`None` für systemweit).
     ///
     /// # Rückgabe
     /// Ein `DomainResult`, das bei Erfolg einen Vektor von `UserPreferenceSetting`-Entitäten enthält.
     /// Der Vektor kann leer sein. Im Fehlerfall wird ein `DomainError` zurückgegeben.
-    async fn get_all_preferences(&self) -> DomainResult<Vec<UserPreferenceSetting>>;
+    async fn get_all_preferences(&self, user_id: Option<NovaId>) -> DomainResult<Vec<UserPreferenceSetting>>;
+
+    /// Ruft eine Seite der Einstellungen eines Benutzers ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von
+    /// [`get_all_preferences`](Self::get_all_preferences) mit [`paginate`].
+    async fn get_preferences_page(&self, user_id: Option<NovaId>, page: &Page) -> DomainResult<PagedResult<UserPreferenceSetting>> {
+        paginate(self.get_all_preferences(user_id).await?, page)
+    }
 
     /// Speichert eine Benutzereinstellung (fügt hinzu oder aktualisiert sie).
     ///
@@ -52,17 +64,36 @@ pub trait UserPreferenceRepository: Send + Sync {
     /// typischerweise überschrieben.
     ///
     /// # Parameter
+    /// * `user_id`: Der Benutzer, für den gespeichert wird (`None` für systemweit).
     /// * `setting`: Eine Referenz auf die zu speichernde `UserPreferenceSetting`.
     ///
     /// # Rückgabe
     /// Ein `DomainResult<()>` das bei Erfolg `Ok(())` zurückgibt.
     /// Im Fehlerfall wird ein `DomainError` zurückgegeben.
-    async fn set_preference(&self, setting: &UserPreferenceSetting) -> DomainResult<()>;
+    async fn set_preference(&self, user_id: Option<NovaId>, setting: &UserPreferenceSetting) -> DomainResult<()>;
+
+    /// Entfernt eine Einstellung anhand ihres Schlüssels.
+    ///
+    /// Existiert keine Einstellung mit diesem Schlüssel, ist das kein Fehler.
+    ///
+    /// # Parameter
+    /// * `user_id`: Der Benutzer, dessen Einstellung entfernt wird (`None` für systemweit).
+    /// * `key`: Der Schlüssel der zu entfernenden Einstellung.
+    ///
+    /// # Rückgabe
+    /// Ein `DomainResult<()>` das bei Erfolg `Ok(())` zurückgibt.
+    /// Im Fehlerfall wird ein `DomainError` zurückgegeben.
+    async fn remove_preference(&self, user_id: Option<NovaId>, key: &str) -> DomainResult<()>;
+
+    /// Das Signal, über das das Repository seine Änderungen zusammen mit dem betroffenen
+    /// Benutzer meldet (siehe [`RepositoryChange`]).
+    ///
+    /// Die Standardimplementierung liefert `None` für Repositories, die keine Änderungen melden.
+    fn changes(&self) -> Option<Arc<Signal<RepositoryChange<ScopedPreference>>>> {
+        None
+    }
 
     // Zukünftige mögliche Erweiterungen:
-    // /// Entfernt eine Einstellung anhand ihres Schlüssels.
-    // async fn remove_preference(&self, key: &str) -> DomainResult<()>;
-    //
     // /// Setzt eine Einstellung auf ihren Standardwert zurück (falls definiert).
     // async fn reset_preference(&self, key: &str) -> DomainResult<()>;
     //
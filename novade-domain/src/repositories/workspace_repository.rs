@@ -8,8 +8,12 @@
 //! zu gewährleisten (z.B. Speichern in einer Konfigurationsdatei oder Datenbank).
 
 use crate::entities::workspace::Workspace;
+use crate::repositories::change::RepositoryChange;
+use crate::repositories::paging::{paginate, Page, PagedResult};
 use crate::DomainResult; // Stellt sicher, dass Fehler als DomainError zurückgegeben werden
 use async_trait::async_trait;
+use novade_core::signal::Signal;
+use std::sync::Arc;
 use novade_core::types::NovaId;
 
 /// Ein Trait, das Operationen zum Speichern, Abrufen und Verwalten von
@@ -44,13 +48,37 @@ pub trait WorkspaceRepository: Send + Sync {
     /// mit diesem Namen gefunden wurde, oder `None`, andernfalls ein `DomainError`.
     async fn get_by_name(&self, name: &str) -> DomainResult<Option<Workspace>>;
     
-    /// Ruft eine Liste aller im System bekannten Workspaces ab.
+    /// Ruft eine Liste aller im System bekannten, nicht archivierten Workspaces ab.
+    ///
+    /// Archivierte Workspaces liefert [`get_archived`](Self::get_archived), alle zusammen
+    /// [`get_all_including_archived`](Self::get_all_including_archived).
     ///
     /// # Rückgabe
     /// Ein `DomainResult`, das bei Erfolg einen Vektor von `Workspace`-Entitäten enthält.
     /// Der Vektor kann leer sein. Im Fehlerfall wird ein `DomainError` zurückgegeben.
     async fn get_all(&self) -> DomainResult<Vec<Workspace>>;
 
+    /// Ruft eine Seite der nicht archivierten Einträge ab (siehe [`Page`]).
+    ///
+    /// Die Standardimplementierung sortiert und teilt das Ergebnis von [`get_all`](Self::get_all)
+    /// mit [`paginate`]; Implementierungen mit eigener Sortierung sollten sie überschreiben.
+    async fn get_page(&self, page: &Page) -> DomainResult<PagedResult<Workspace>> {
+        paginate(self.get_all().await?, page)
+    }
+
+    /// Ruft alle archivierten Workspaces ab.
+    async fn get_archived(&self) -> DomainResult<Vec<Workspace>>;
+
+    /// Ruft alle Workspaces ab, archivierte eingeschlossen, z.B. für Prüfungen auf Konflikte.
+    ///
+    /// Die Standardimplementierung hängt das Ergebnis von [`get_archived`](Self::get_archived)
+    /// an das von [`get_all`](Self::get_all) an.
+    async fn get_all_including_archived(&self) -> DomainResult<Vec<Workspace>> {
+        let mut workspaces = self.get_all().await?;
+        workspaces.extend(self.get_archived().await?);
+        Ok(workspaces)
+    }
+
     /// Fügt einen neuen Workspace zum Repository hinzu.
     ///
     /// # Parameter
@@ -59,8 +87,8 @@ pub trait WorkspaceRepository: Send + Sync {
     ///
     /// # Rückgabe
     /// Ein `DomainResult<()>` das bei Erfolg `Ok(())` zurückgibt.
-    /// Im Fehlerfall wird ein `DomainError` zurückgegeben (z.B. wenn ein Workspace
-    /// mit derselben ID oder demselben Namen bereits existiert).
+    /// Im Fehlerfall wird ein `DomainError` zurückgegeben: `DomainError::Conflict`, wenn ein
+    /// Workspace mit derselben ID oder demselben Namen bereits existiert.
     async fn add(&self, workspace: &Workspace) -> DomainResult<()>;
 
     /// Aktualisiert einen bereits im Repository vorhandenen Workspace.
@@ -86,4 +114,11 @@ pub trait WorkspaceRepository: Send + Sync {
     /// Im Fehlerfall wird ein `DomainError` zurückgegeben (z.B. wenn kein Workspace
     /// mit dieser ID gefunden wird).
     async fn remove(&self, id: &NovaId) -> DomainResult<()>;
+
+    /// Das Signal, über das das Repository seine Änderungen meldet (siehe [`RepositoryChange`]).
+    ///
+    /// Die Standardimplementierung liefert `None` für Repositories, die keine Änderungen melden.
+    fn changes(&self) -> Option<Arc<Signal<RepositoryChange<Workspace>>>> {
+        None
+    }
 }
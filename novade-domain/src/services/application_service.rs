@@ -1,59 +1,582 @@
 //! Domänendienst für die Verwaltung von Anwendungen.
+//!
+//! Anwendungen werden nicht direkt gelöscht, sondern zunächst archiviert
+//! ([`ApplicationService::archive_application`]). Archivierte Anwendungen erscheinen nicht mehr
+//! in Listen und Suchen, lassen sich aber wiederherstellen, bis sie mit
+//! [`ApplicationService::purge_application`] endgültig gelöscht werden. Dabei räumt der Dienst
+//! die Zuordnungen der Anwendung zu Workspaces auf; Zuordnungsregeln, die nur auf diese
+//! Anwendung zutreffen, verhindern das Löschen, bis sie entfernt wurden.
 
 use crate::entities::application::{Application, ApplicationType};
-use crate::repositories::application_repository::ApplicationRepository;
+use crate::entities::audit_record::AuditOperation;
+use crate::entities::workspace::{Workspace, WorkspaceAssignmentRule};
+use crate::entities::Entity;
+use crate::events::{DomainEvent, EventPublisher};
+use crate::repositories::application_repository::{ApplicationQuery, ApplicationRepository};
+use crate::repositories::paging::{Page, PagedResult};
+use crate::repositories::workspace_repository::WorkspaceRepository;
+use crate::services::audit_service::{summarize_changes, AuditService};
+use crate::services::dry_run::DryRun;
+use crate::services::metrics::{self, NoopMetrics, ServiceMetrics};
+use crate::validation::Validate;
 use crate::{DomainError, DomainResult};
-use novade_core::types::NovaId;
+use novade_core::types::{NovaId, Timestamp};
 use novade_core::info; // Logging
+use std::future::Future;
 use std::sync::Arc;
 
+/// Wie [`ApplicationService::import_batch`] mit Anwendungen umgeht, die bereits existieren
+/// (gleiche ID, gleicher Startbefehl oder gleiche Desktop-Datei-ID).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportConflictPolicy {
+    /// Die vorhandene Anwendung bleibt unverändert.
+    Skip,
+    /// Die vorhandene Anwendung wird unter ihrer ID durch die importierte ersetzt.
+    Replace,
+    /// Die vorhandene Anwendung wird um die Metadaten der importierten ergänzt
+    /// (siehe [`Application::merge_from`]).
+    Merge,
+}
+
+/// Ergebnis von [`ApplicationService::import_batch`]. Die IDs sind die der gespeicherten Anwendungen.
+#[derive(Debug, Default)]
+pub struct ApplicationImportReport {
+    pub added: Vec<NovaId>,
+    pub replaced: Vec<NovaId>,
+    pub merged: Vec<NovaId>,
+    pub skipped: Vec<NovaId>,
+    /// Abgelehnte Anwendungen (Name und Grund); sie werden nicht gespeichert.
+    pub rejected: Vec<(String, DomainError)>,
+    /// Je ersetzter oder ergänzter Anwendung die geänderten Felder (siehe
+    /// [`summarize_changes`]); unveränderte Anwendungen fehlen.
+    pub changes: Vec<(NovaId, String)>,
+}
+
+/// Ergebnis von [`ApplicationService::purge_application`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplicationPurgeReport {
+    /// Die endgültig gelöschte Anwendung.
+    pub application: Application,
+    /// Die Workspaces, denen die Anwendung nicht mehr zugeordnet ist.
+    pub workspaces: Vec<NovaId>,
+}
+
+/// Ob zwei Einträge dieselbe Anwendung beschreiben: gleicher Startbefehl oder, bei
+/// Desktop-Anwendungen, gleiche Desktop-Datei-ID (`name`).
+fn is_duplicate(a: &Application, b: &Application) -> bool {
+    let executable_path = a.executable_path.trim();
+    (!executable_path.is_empty() && executable_path == b.executable_path.trim())
+        || (a.app_type == ApplicationType::Desktop && b.app_type == ApplicationType::Desktop && a.name == b.name)
+}
+
+/// Der Konflikt, wenn `new` bereits als `existing` registriert ist: das übereinstimmende Feld
+/// (`id`, `executable_path` oder `name`) und die ID der vorhandenen Anwendung.
+fn duplicate_conflict(existing: &Application, new: &Application) -> DomainError {
+    let field = if existing.id == new.id {
+        "id"
+    } else if !existing.executable_path.trim().is_empty() && existing.executable_path.trim() == new.executable_path.trim() {
+        "executable_path"
+    } else {
+        "name"
+    };
+    DomainError::conflict::<Application>(field, &existing.id)
+}
+
 pub struct ApplicationService {
     app_repository: Arc<dyn ApplicationRepository>,
+    workspace_repository: Option<Arc<dyn WorkspaceRepository>>,
+    events: Option<Arc<dyn EventPublisher>>,
+    audit: Option<Arc<AuditService>>,
+    metrics: Arc<dyn ServiceMetrics>,
 }
 
 impl ApplicationService {
     /// Erstellt einen neuen `ApplicationService`.
     pub fn new(app_repository: Arc<dyn ApplicationRepository>) -> Self {
-        Self { app_repository }
+        Self { app_repository, workspace_repository: None, events: None, audit: None, metrics: Arc::new(NoopMetrics) }
+    }
+
+    /// Prüft beim endgültigen Löschen von Anwendungen die Verweise der Workspaces in
+    /// `workspace_repository` (siehe [`purge_application`](Self::purge_application)).
+    pub fn with_workspace_repository(mut self, workspace_repository: Arc<dyn WorkspaceRepository>) -> Self {
+        self.workspace_repository = Some(workspace_repository);
+        self
+    }
+
+    /// Meldet Änderungen an Anwendungen als [`DomainEvent`]s an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Hält jede Änderung an Anwendungen im Änderungsprotokoll von `audit` fest.
+    pub fn with_audit_log(mut self, audit: Arc<AuditService>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Meldet Dauer und Ergebnis jedes Aufrufs an `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<dyn ServiceMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    async fn measure<T>(&self, method: &'static str, future: impl Future<Output = DomainResult<T>>) -> DomainResult<T> {
+        metrics::measure(&*self.metrics, "ApplicationService", method, future).await
     }
 
-    /// Listet alle bekannten Anwendungen auf.
+    fn publish(&self, event: DomainEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
+        }
+    }
+
+    async fn audit(&self, app_id: &NovaId, operation: AuditOperation, summary: String) {
+        if let Some(audit) = &self.audit {
+            audit.record(Application::ENTITY_TYPE, &app_id.to_string(), operation, summary).await;
+        }
+    }
+
+    async fn audit_update(&self, before: &Application, after: &Application) {
+        if let Some(audit) = &self.audit {
+            audit.record_update(Application::ENTITY_TYPE, &after.id.to_string(), before, after).await;
+        }
+    }
+
+    /// Listet alle bekannten, nicht archivierten Anwendungen auf.
     pub async fn list_all_applications(&self) -> DomainResult<Vec<Application>> {
-        info!("Auflistung aller Anwendungen angefordert.");
-        self.app_repository.get_all().await
+        self.measure("list_all_applications", async move {
+            info!("Auflistung aller Anwendungen angefordert.");
+            self.app_repository.get_all().await
+        })
+        .await
+    }
+
+    /// Listet die archivierten Anwendungen auf, z.B. für den Papierkorb in den Einstellungen.
+    pub async fn list_archived_applications(&self) -> DomainResult<Vec<Application>> {
+        self.measure("list_archived_applications", async move {
+            info!("Auflistung der archivierten Anwendungen angefordert.");
+            self.app_repository.get_archived().await
+        })
+        .await
+    }
+
+    /// Listet eine Seite der bekannten Anwendungen auf, z.B. für die Anwendungsübersicht.
+    pub async fn list_applications_page(&self, page: &Page) -> DomainResult<PagedResult<Application>> {
+        self.measure("list_applications_page", async move {
+            info!(offset = page.offset, limit = page.limit, "Seite der Anwendungen angefordert.");
+            self.app_repository.get_page(page).await
+        })
+        .await
     }
 
     /// Sucht Anwendungen anhand eines Namens.
     pub async fn find_applications_by_name(&self, name_query: &str) -> DomainResult<Vec<Application>> {
-        if name_query.trim().is_empty() {
-            return Err(DomainError::ValidationError {
-                field: "name_query".to_string(),
-                message: "Suchbegriff darf nicht leer sein.".to_string(),
-            });
-        }
-        info!(name_query, "Suche nach Anwendungen.");
-        self.app_repository.find_by_name(name_query).await
+        self.measure("find_applications_by_name", async move {
+            if name_query.trim().is_empty() {
+                return Err(DomainError::ValidationError {
+                    field: "name_query".to_string(),
+                    message: "Suchbegriff darf nicht leer sein.".to_string(),
+                });
+            }
+            info!(name_query, "Suche nach Anwendungen.");
+            self.app_repository.find_by_name(name_query).await
+        })
+        .await
     }
     
+    /// Listet die Anwendungen auf, die die Abfrage erfüllen, z.B. für Filter im Launcher
+    /// oder in den Einstellungen.
+    pub async fn query_applications(&self, query: &ApplicationQuery) -> DomainResult<Vec<Application>> {
+        self.measure("query_applications", async move {
+            info!(?query, "Abfrage von Anwendungen.");
+            self.app_repository.find(query).await
+        })
+        .await
+    }
+
     /// Registriert eine neue Anwendung im System.
+    ///
+    /// Ist die Anwendung bereits registriert (gleiche ID, gleicher Startbefehl oder gleiche
+    /// Desktop-Datei-ID), wird kein Duplikat angelegt: Die vorhandene Anwendung wird um die
+    /// Metadaten von `app_data` ergänzt (siehe [`Application::merge_from`]) und die
+    /// Registrierung abgelehnt.
+    ///
+    /// # Rückgabe
+    /// Die registrierte Anwendung, `DomainError::ValidationError` bei fehlenden Pflichtfeldern
+    /// oder `DomainError::Conflict` mit der ID der vorhandenen (ggf. archivierten) Anwendung.
     pub async fn register_application(&self, app_data: Application) -> DomainResult<Application> {
-        info!(app_name = %app_data.name, app_id = %app_data.id, "Registriere neue Anwendung.");
-        // Hier könnten Validierungen stattfinden, z.B. ob der Pfad existiert (obwohl das eher Systemschicht wäre)
-        // oder ob eine Anwendung mit gleichem Namen/Pfad schon existiert.
-        if app_data.executable_path.trim().is_empty() {
-             return Err(DomainError::ValidationError {
-                field: "executable_path".to_string(),
-                message: "Pfad zur ausführbaren Datei darf nicht leer sein.".to_string(),
+        self.measure("register_application", async move {
+            info!(app_name = %app_data.name, app_id = %app_data.id, "Registriere neue Anwendung.");
+            // Hier könnten Validierungen stattfinden, z.B. ob der Pfad existiert (obwohl das eher Systemschicht wäre).
+            app_data.validate()?;
+            let existing = self.app_repository.find(&ApplicationQuery::new().including_archived()).await?;
+            if let Some(existing) = existing.into_iter().find(|app| app.id == app_data.id || is_duplicate(app, &app_data)) {
+                let mut merged = existing.clone();
+                merged.merge_from(&app_data);
+                if merged != existing {
+                    info!(app_id = %existing.id, app_name = %app_data.name, "Ergänze Metadaten der bereits registrierten Anwendung.");
+                    self.update_application(&merged).await?;
+                    self.audit_update(&existing, &merged).await;
+                    self.publish(DomainEvent::ApplicationUpdated(merged));
+                }
+                return Err(duplicate_conflict(&existing, &app_data));
+            }
+            self.app_repository.add(&app_data).await?;
+            self.audit(&app_data.id, AuditOperation::Created, format!("Anwendung '{}' registriert.", app_data.name)).await;
+            self.publish(DomainEvent::ApplicationRegistered(app_data.clone()));
+            Ok(app_data)
+        })
+        .await
+    }
+
+    /// Importiert mehrere Anwendungen, z.B. aus dem Scan der Desktop-Einträge oder einer Migration.
+    ///
+    /// Jede Anwendung wird einzeln geprüft; ungültige Anwendungen und Duplikate einer bereits im
+    /// Import enthaltenen Anwendung werden abgelehnt, ohne den Import
+    /// abzubrechen. Anwendungen, die bereits existieren, werden nach `policy` behandelt. Alle
+    /// Änderungen werden mit einem einzigen [`ApplicationRepository::save_batch`] geschrieben;
+    /// mit [`DryRun::Yes`] wird nichts geschrieben.
+    ///
+    /// # Rückgabe
+    /// Der Bericht über den Import, oder ein `DomainError`, wenn das Schreiben fehlschlägt.
+    pub async fn import_batch(
+        &self,
+        applications: Vec<Application>,
+        policy: ImportConflictPolicy,
+        dry_run: DryRun,
+    ) -> DomainResult<ApplicationImportReport> {
+        self.measure("import_batch", async move {
+            let existing = self.app_repository.find(&ApplicationQuery::new().including_archived()).await?;
+            let mut report = ApplicationImportReport::default();
+            let mut added: Vec<Application> = Vec::new();
+            let mut updated: Vec<Application> = Vec::new();
+            // Der vorherige Stand der Einträge in `updated`, für das Änderungsprotokoll.
+            let mut previous: Vec<&Application> = Vec::new();
+            for application in applications {
+                if let Err(error) = application.validate() {
+                    report.rejected.push((application.name, error));
+                    continue;
+                }
+                if added.iter().chain(&updated).any(|pending| is_duplicate(pending, &application)) {
+                    let message = format!("'{}' ist im Import mehrfach enthalten.", application.name);
+                    report.rejected.push((
+                        application.name,
+                        DomainError::ValidationError { field: "executable_path".to_string(), message },
+                    ));
+                    continue;
+                }
+                let conflict = existing.iter().find(|app| app.id == application.id || is_duplicate(app, &application));
+                match (conflict, policy) {
+                    (None, _) => {
+                        report.added.push(application.id.clone());
+                        added.push(application);
+                    }
+                    (Some(existing), ImportConflictPolicy::Skip) => report.skipped.push(existing.id.clone()),
+                    (Some(existing), ImportConflictPolicy::Replace) => {
+                        report.replaced.push(existing.id.clone());
+                        previous.push(existing);
+                        updated.push(Application { id: existing.id.clone(), ..application });
+                    }
+                    (Some(existing), ImportConflictPolicy::Merge) => {
+                        let mut merged = existing.clone();
+                        merged.merge_from(&application);
+                        // Die vorhandene Anwendung kann Felder enthalten, die inzwischen ungültig sind.
+                        if let Err(error) = merged.validate() {
+                            report.rejected.push((application.name, error));
+                            continue;
+                        }
+                        report.merged.push(merged.id.clone());
+                        previous.push(existing);
+                        updated.push(merged);
+                    }
+                }
+            }
+            report.changes = updated
+                .iter()
+                .zip(&previous)
+                .map(|(application, before)| (application.id.clone(), summarize_changes(*before, application)))
+                .filter(|(_, changes)| !changes.is_empty())
+                .collect();
+            if dry_run.is_dry_run() {
+                return Ok(report);
+            }
+            info!(
+                added = added.len(),
+                updated = updated.len(),
+                skipped = report.skipped.len(),
+                rejected = report.rejected.len(),
+                "Importiere Anwendungen."
+            );
+            if !added.is_empty() || !updated.is_empty() {
+                self.app_repository.save_batch(&added, &updated).await?;
+            }
+            for application in added {
+                self.audit(&application.id, AuditOperation::Created, format!("Anwendung '{}' importiert.", application.name)).await;
+                self.publish(DomainEvent::ApplicationRegistered(application));
+            }
+            for (application, before) in updated.into_iter().zip(previous) {
+                self.audit_update(before, &application).await;
+                self.publish(DomainEvent::ApplicationUpdated(application));
+            }
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Führt doppelt gespeicherte Anwendungen zusammen (Wartung, z.B. nach einer Migration).
+    ///
+    /// Von mehreren Einträgen derselben Anwendung (gleicher Startbefehl oder gleiche
+    /// Desktop-Datei-ID) bleibt der erste erhalten und wird um die Metadaten der übrigen
+    /// ergänzt; die übrigen werden entfernt. Archivierte Anwendungen bleiben unberührt.
+    ///
+    /// # Rückgabe
+    /// Je zusammengeführter Anwendung ihre ID und die IDs der entfernten Einträge.
+    pub async fn deduplicate(&self) -> DomainResult<Vec<(NovaId, Vec<NovaId>)>> {
+        self.measure("deduplicate", async move {
+            // Je Anwendung der ursprüngliche und der zusammengeführte Eintrag sowie die entfernten IDs.
+            let mut groups: Vec<(Application, Application, Vec<NovaId>)> = Vec::new();
+            for application in self.list_all_applications().await? {
+                match groups.iter_mut().find(|(_, kept, _)| is_duplicate(kept, &application)) {
+                    Some((_, kept, removed)) => {
+                        kept.merge_from(&application);
+                        removed.push(application.id);
+                    }
+                    None => groups.push((application.clone(), application, Vec::new())),
+                }
+            }
+            let mut merged = Vec::new();
+            for (original, application, removed) in groups.into_iter().filter(|(_, _, removed)| !removed.is_empty()) {
+                info!(app_id = %application.id, duplicates = removed.len(), "Führe doppelte Anwendungen zusammen.");
+                for id in &removed {
+                    self.app_repository.remove(id).await?;
+                    self.audit(id, AuditOperation::Deleted, format!("Als Duplikat mit {} zusammengeführt.", application.id)).await;
+                }
+                self.update_application(&application).await?;
+                self.audit_update(&original, &application).await;
+                merged.push((application.id.clone(), removed));
+                self.publish(DomainEvent::ApplicationUpdated(application));
+            }
+            Ok(merged)
+        })
+        .await
+    }
+
+    /// Archiviert eine Anwendung. Sie bleibt gespeichert, erscheint aber nicht mehr in Listen
+    /// und Suchen. Eine bereits archivierte Anwendung bleibt unverändert.
+    ///
+    /// # Rückgabe
+    /// Die archivierte Anwendung oder `DomainError::EntityNotFound`.
+    pub async fn archive_application(&self, app_id: &NovaId) -> DomainResult<Application> {
+        self.measure("archive_application", async move {
+            let mut app = self.get_existing(app_id).await?;
+            if app.is_archived() {
+                return Ok(app);
+            }
+            info!(%app_id, app_name = %app.name, "Archiviere Anwendung.");
+            app.deleted_at = Some(Timestamp::now());
+            self.update_application(&app).await?;
+            self.audit(app_id, AuditOperation::Archived, format!("Anwendung '{}' archiviert.", app.name)).await;
+            self.publish(DomainEvent::ApplicationArchived { id: app_id.clone() });
+            Ok(app)
+        })
+        .await
+    }
+
+    /// Stellt eine archivierte Anwendung wieder her. Eine nicht archivierte Anwendung bleibt
+    /// unverändert.
+    ///
+    /// # Rückgabe
+    /// Die wiederhergestellte Anwendung, `DomainError::EntityNotFound` oder
+    /// `DomainError::Conflict`, wenn die Anwendung inzwischen erneut registriert wurde.
+    pub async fn restore_application(&self, app_id: &NovaId) -> DomainResult<Application> {
+        self.measure("restore_application", async move {
+            let mut app = self.get_existing(app_id).await?;
+            if !app.is_archived() {
+                return Ok(app);
+            }
+            if let Some(duplicate) = self.list_all_applications().await?.into_iter().find(|other| is_duplicate(other, &app)) {
+                return Err(duplicate_conflict(&duplicate, &app));
+            }
+            info!(%app_id, app_name = %app.name, "Stelle archivierte Anwendung wieder her.");
+            app.deleted_at = None;
+            self.update_application(&app).await?;
+            self.audit(app_id, AuditOperation::Restored, format!("Anwendung '{}' wiederhergestellt.", app.name)).await;
+            self.publish(DomainEvent::ApplicationRestored(app.clone()));
+            Ok(app)
+        })
+        .await
+    }
+
+    /// Löscht eine archivierte Anwendung endgültig.
+    ///
+    /// Mit einem [`WorkspaceRepository`] wird die Anwendung zugleich aus
+    /// [`Workspace::applications`] aller Workspaces entfernt. Mit [`DryRun::Yes`] wird
+    /// nur geprüft und berichtet, was gelöscht würde.
+    ///
+    /// # Rückgabe
+    /// Der Bericht über das Löschen, `DomainError::EntityNotFound`,
+    /// `DomainError::OperationNotPermitted`, wenn die Anwendung nicht archiviert ist, oder
+    /// `DomainError::IntegrityViolation`, wenn eine Zuordnungsregel für ihre App-ID auf keine
+    /// andere Anwendung mehr zuträfe.
+    pub async fn purge_application(&self, app_id: &NovaId, dry_run: DryRun) -> DomainResult<ApplicationPurgeReport> {
+        self.measure("purge_application", async move {
+            let app = self.get_existing(app_id).await?;
+            if !app.is_archived() {
+                return Err(DomainError::OperationNotPermitted {
+                    operation: "purge_application".to_string(),
+                    reason: format!("Die Anwendung '{}' muss vor dem endgültigen Löschen archiviert werden.", app.name),
+                });
+            }
+            let workspaces = self.referencing_workspaces(&app).await?;
+            let report = ApplicationPurgeReport {
+                application: app,
+                workspaces: workspaces.iter().map(|workspace| workspace.id.clone()).collect(),
+            };
+            if dry_run.is_dry_run() {
+                return Ok(report);
+            }
+            if let Some(workspace_repository) = &self.workspace_repository {
+                for mut workspace in workspaces {
+                    info!(%app_id, workspace_id = %workspace.id, "Entferne Anwendung aus Workspace.");
+                    workspace.applications.retain(|id| id != app_id);
+                    workspace_repository.update(&workspace).await?;
+                }
+            }
+            let app = &report.application;
+            info!(%app_id, app_name = %app.name, "Lösche archivierte Anwendung endgültig.");
+            self.app_repository.remove(app_id).await?;
+            self.audit(app_id, AuditOperation::Deleted, format!("Anwendung '{}' endgültig gelöscht.", app.name)).await;
+            self.publish(DomainEvent::ApplicationPurged { id: app_id.clone() });
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Die Workspaces, denen die Anwendung zugeordnet ist. Regeln für ihre App-ID, die auf
+    /// keine andere Anwendung zutreffen, werden nicht stillschweigend entfernt, sondern als
+    /// `DomainError::IntegrityViolation` gemeldet.
+    async fn referencing_workspaces(&self, app: &Application) -> DomainResult<Vec<Workspace>> {
+        let Some(workspace_repository) = &self.workspace_repository else {
+            return Ok(Vec::new());
+        };
+        let workspaces = workspace_repository.get_all_including_archived().await?;
+        let others = self.app_repository.find(&ApplicationQuery::new().including_archived()).await?;
+        let orphaned_rules: Vec<String> = workspaces
+            .iter()
+            .filter(|ws| {
+                ws.assignment_rules.iter().any(|rule| {
+                    matches!(rule, WorkspaceAssignmentRule::ApplicationId(_))
+                        && rule.matches(app)
+                        && !others.iter().any(|other| other.id != app.id && rule.matches(other))
+                })
+            })
+            .map(|ws| format!("'{}'", ws.name))
+            .collect();
+        if !orphaned_rules.is_empty() {
+            return Err(DomainError::IntegrityViolation {
+                entity_type: Application::ENTITY_TYPE.to_string(),
+                entity_id: app.id.to_string(),
+                reason: format!(
+                    "Zuordnungsregeln der Workspaces {} verweisen noch auf die Anwendung '{}'.",
+                    orphaned_rules.join(", "),
+                    app.name
+                ),
             });
         }
-        self.app_repository.add(&app_data).await?;
-        Ok(app_data)
+        Ok(workspaces.into_iter().filter(|ws| ws.applications.contains(&app.id)).collect())
+    }
+
+    /// Speichert eine geänderte Anwendung, nachdem sie mit [`Validate`] geprüft wurde.
+    async fn update_application(&self, application: &Application) -> DomainResult<()> {
+        application.validate()?;
+        self.app_repository.update(application).await
     }
 
     /// Ruft Details zu einer spezifischen Anwendung ab.
     pub async fn get_application_details(&self, app_id: &NovaId) -> DomainResult<Option<Application>> {
-        info!(%app_id, "Details für Anwendung angefordert.");
-        self.app_repository.get_by_id(app_id).await
+        self.measure("get_application_details", async move {
+            info!(%app_id, "Details für Anwendung angefordert.");
+            self.app_repository.get_by_id(app_id).await
+        })
+        .await
+    }
+
+    async fn get_existing(&self, app_id: &NovaId) -> DomainResult<Application> {
+        self.app_repository.get_by_id(app_id).await?.ok_or_else(|| DomainError::not_found::<Application>(app_id))
+    }
+
+    /// Versieht eine Anwendung mit einem Tag.
+    ///
+    /// Der Tag wird ohne führende und abschließende Leerzeichen gespeichert. Trägt die Anwendung
+    /// den Tag bereits (ohne Unterscheidung von Groß-/Kleinschreibung), bleibt sie unverändert.
+    ///
+    /// # Rückgabe
+    /// Die aktualisierte Anwendung; `DomainError::ValidationError` bei leerem Tag bzw.
+    /// `DomainError::EntityNotFound`, wenn die Anwendung nicht existiert.
+    pub async fn tag(&self, app_id: &NovaId, tag: &str) -> DomainResult<Application> {
+        self.measure("tag", async move {
+            let tag = tag.trim();
+            if tag.is_empty() {
+                return Err(DomainError::ValidationError {
+                    field: "tag".to_string(),
+                    message: "Tag darf nicht leer sein.".to_string(),
+                });
+            }
+            let mut app = self.get_existing(app_id).await?;
+            if app.tags.iter().any(|existing| existing.to_lowercase() == tag.to_lowercase()) {
+                return Ok(app);
+            }
+            info!(%app_id, tag, "Versehe Anwendung mit Tag.");
+            let before = app.clone();
+            app.tags.push(tag.to_string());
+            self.update_application(&app).await?;
+            self.audit_update(&before, &app).await;
+            self.publish(DomainEvent::ApplicationUpdated(app.clone()));
+            Ok(app)
+        })
+        .await
+    }
+
+    /// Entfernt einen Tag von einer Anwendung (ohne Unterscheidung von Groß-/Kleinschreibung).
+    ///
+    /// # Rückgabe
+    /// Die aktualisierte Anwendung, oder `DomainError::EntityNotFound`, wenn die Anwendung nicht existiert.
+    pub async fn untag(&self, app_id: &NovaId, tag: &str) -> DomainResult<Application> {
+        self.measure("untag", async move {
+            let tag = tag.trim().to_lowercase();
+            let mut app = self.get_existing(app_id).await?;
+            let before = app.clone();
+            app.tags.retain(|existing| existing.to_lowercase() != tag);
+            if app.tags != before.tags {
+                info!(%app_id, tag, "Entferne Tag von Anwendung.");
+                self.update_application(&app).await?;
+                self.audit_update(&before, &app).await;
+                self.publish(DomainEvent::ApplicationUpdated(app.clone()));
+            }
+            Ok(app)
+        })
+        .await
+    }
+
+    /// Listet alle Anwendungen mit dem gegebenen Tag auf.
+    pub async fn list_by_tag(&self, tag: &str) -> DomainResult<Vec<Application>> {
+        self.measure("list_by_tag", async move {
+            info!(tag, "Auflistung der Anwendungen mit Tag angefordert.");
+            self.app_repository.find_by_tag(tag.trim()).await
+        })
+        .await
+    }
+
+    /// Alle an nicht archivierte Anwendungen vergebenen Tags, alphabetisch sortiert und ohne Duplikate.
+    pub async fn list_tags(&self) -> DomainResult<Vec<String>> {
+        self.measure("list_tags", async move {
+            let mut tags: Vec<String> = self.list_all_applications().await?.into_iter().flat_map(|app| app.tags).collect();
+            tags.sort_by_key(|tag| tag.to_lowercase());
+            tags.dedup_by(|a, b| a.to_lowercase() == b.to_lowercase());
+            Ok(tags)
+        })
+        .await
     }
 
     // Weitere Methoden, z.B. für das Starten einer Anwendung (was hier eher das
@@ -65,6 +588,7 @@ impl ApplicationService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::application::ApplicationType;
     use crate::repositories::application_repository::MockApplicationRepository; // mockall generiert dies
     use novade_core::CoreError; // für RepositoryError wrapping
     use tokio; // für async tests
@@ -110,6 +634,42 @@ mod tests {
         }
     }
     
+    #[tokio::test]
+    async fn test_tag_and_untag() {
+        let mut app = Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), None);
+        app.tags.push("Grafik".to_string());
+        let app_id = app.id.clone();
+
+        let mut mock_repo = MockApplicationRepository::new();
+        mock_repo.expect_get_by_id().returning(move |_| Ok(Some(app.clone())));
+        mock_repo
+            .expect_update()
+            .withf(|app: &Application| app.tags == vec!["Grafik".to_string(), "Arbeit".to_string()])
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_repo.expect_update().withf(|app: &Application| app.tags.is_empty()).times(1).returning(|_| Ok(()));
+
+        let service = ApplicationService::new(Arc::new(mock_repo));
+        assert!(matches!(service.tag(&app_id, "  ").await, Err(DomainError::ValidationError { .. })));
+        // Bereits vorhandene Tags lösen kein Update aus.
+        assert_eq!(service.tag(&app_id, "grafik").await.unwrap().tags, vec!["Grafik".to_string()]);
+        service.tag(&app_id, " Arbeit ").await.unwrap();
+        assert!(service.untag(&app_id, "GRAFIK").await.unwrap().tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_tags() {
+        let mut gimp = Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), None);
+        gimp.tags = vec!["Grafik".to_string(), "arbeit".to_string()];
+        let mut office = Application::new_desktop("office".to_string(), "/usr/bin/office".to_string(), None);
+        office.tags = vec!["Arbeit".to_string()];
+
+        let mut mock_repo = MockApplicationRepository::new();
+        mock_repo.expect_get_all().returning(move || Ok(vec![gimp.clone(), office.clone()]));
+        let service = ApplicationService::new(Arc::new(mock_repo));
+        assert_eq!(service.list_tags().await.unwrap(), vec!["arbeit".to_string(), "Grafik".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_register_application_empty_path() {
         let mock_repo = MockApplicationRepository::new(); // Wird nicht aufgerufen
@@ -121,15 +681,264 @@ mod tests {
             executable_path: " ".to_string(), // Leerer Pfad
             arguments: None,
             working_directory: None,
+            environment: None,
+            resource_limits: None,
+            sandbox: None,
             icon_name: None,
             app_type: ApplicationType::Desktop,
             categories: None,
             keywords: None,
+            tags: Vec::new(),
+            mime_types: Vec::new(),
             description: None,
             version: None,
+            deleted_at: None,
         };
 
         let result = service.register_application(app_data).await;
         assert!(matches!(result, Err(DomainError::ValidationError {field, ..}) if field == "executable_path"));
     }
+
+    #[tokio::test]
+    async fn test_register_application_publishes_event() {
+        let mut mock_repo = MockApplicationRepository::new();
+        mock_repo.expect_find().returning(|_| Ok(Vec::new()));
+        mock_repo.expect_add().times(1).returning(|_| Ok(()));
+        let bus = Arc::new(crate::events::EventBus::new());
+        let events = bus.subscribe();
+
+        let service = ApplicationService::new(Arc::new(mock_repo)).with_event_publisher(bus);
+        let app = service
+            .register_application(Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), None))
+            .await
+            .unwrap();
+        assert_eq!(events.try_iter().collect::<Vec<_>>(), vec![DomainEvent::ApplicationRegistered(app)]);
+    }
+
+    #[tokio::test]
+    async fn test_register_duplicate_merges_into_existing() {
+        let existing = Application::new_desktop("org.gnome.gedit".to_string(), "/usr/bin/gedit".to_string(), None);
+        let existing_id = existing.id.clone();
+        let mut mock_repo = MockApplicationRepository::new();
+        mock_repo.expect_find().returning(move |_| Ok(vec![existing.clone()]));
+        mock_repo
+            .expect_update()
+            .withf(|app: &Application| app.icon_name.as_deref() == Some("gedit") && app.executable_path == "/usr/bin/gedit")
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_repo.expect_add().never();
+        let bus = Arc::new(crate::events::EventBus::new());
+        let events = bus.subscribe();
+
+        let service = ApplicationService::new(Arc::new(mock_repo)).with_event_publisher(bus);
+        // Gleiche Desktop-Datei-ID, anderer Startbefehl.
+        let duplicate = Application::new_desktop("org.gnome.gedit".to_string(), "/usr/local/bin/gedit".to_string(), Some("gedit".to_string()));
+        let result = service.register_application(duplicate).await;
+        assert!(matches!(
+            result,
+            Err(DomainError::Conflict { conflicting_field, existing_id: id, .. }) if conflicting_field == "name" && id == existing_id.to_string()
+        ));
+        assert!(matches!(events.try_recv(), Ok(DomainEvent::ApplicationUpdated(app)) if app.id == existing_id));
+        // Ohne neue Metadaten wird nichts geschrieben.
+        let same_path = Application::new_desktop("gedit-kopie".to_string(), " /usr/bin/gedit".to_string(), None);
+        assert!(matches!(
+            service.register_application(same_path).await,
+            Err(DomainError::Conflict { conflicting_field, .. }) if conflicting_field == "executable_path"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_deduplicate() {
+        let first = Application::new_desktop("firefox".to_string(), "/usr/bin/firefox".to_string(), None);
+        let mut second = Application::new_desktop("firefox-esr".to_string(), "/usr/bin/firefox".to_string(), Some("firefox".to_string()));
+        second.tags = vec!["Web".to_string()];
+        let other = Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), None);
+        let (first_id, second_id) = (first.id.clone(), second.id.clone());
+
+        let mut mock_repo = MockApplicationRepository::new();
+        mock_repo.expect_get_all().returning(move || Ok(vec![first.clone(), second.clone(), other.clone()]));
+        let removed_id = second_id.clone();
+        mock_repo.expect_remove().withf(move |id: &NovaId| *id == removed_id).times(1).returning(|_| Ok(()));
+        let kept_id = first_id.clone();
+        mock_repo
+            .expect_update()
+            .withf(move |app: &Application| {
+                app.id == kept_id && app.icon_name.as_deref() == Some("firefox") && app.tags == vec!["Web".to_string()]
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = ApplicationService::new(Arc::new(mock_repo));
+        assert_eq!(service.deduplicate().await.unwrap(), vec![(first_id, vec![second_id])]);
+    }
+
+    #[tokio::test]
+    async fn test_archive_restore_and_purge() {
+        let gimp = Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), None);
+        let gimp_id = gimp.id.clone();
+        let stored = Arc::new(std::sync::Mutex::new(vec![gimp]));
+        let mut mock_repo = MockApplicationRepository::new();
+        let apps = stored.clone();
+        mock_repo.expect_get_by_id().returning(move |id| Ok(apps.lock().unwrap().iter().find(|app| &app.id == id).cloned()));
+        let apps = stored.clone();
+        mock_repo
+            .expect_get_all()
+            .returning(move || Ok(apps.lock().unwrap().iter().filter(|app| !app.is_archived()).cloned().collect()));
+        let apps = stored.clone();
+        mock_repo
+            .expect_get_archived()
+            .returning(move || Ok(apps.lock().unwrap().iter().filter(|app| app.is_archived()).cloned().collect()));
+        let apps = stored.clone();
+        mock_repo
+            .expect_find()
+            .returning(move |query| Ok(apps.lock().unwrap().iter().filter(|app| query.matches(app)).cloned().collect()));
+        let apps = stored.clone();
+        mock_repo.expect_update().returning(move |updated| {
+            apps.lock().unwrap().iter_mut().filter(|app| app.id == updated.id).for_each(|app| *app = updated.clone());
+            Ok(())
+        });
+        let apps = stored.clone();
+        mock_repo.expect_remove().times(1).returning(move |id| {
+            apps.lock().unwrap().retain(|app| &app.id != id);
+            Ok(())
+        });
+        let bus = Arc::new(crate::events::EventBus::new());
+        let events = bus.subscribe();
+        let service = ApplicationService::new(Arc::new(mock_repo)).with_event_publisher(bus);
+
+        // Nicht archivierte Anwendungen können nicht endgültig gelöscht werden.
+        assert!(matches!(service.purge_application(&gimp_id, DryRun::No).await, Err(DomainError::OperationNotPermitted { .. })));
+
+        assert!(service.archive_application(&gimp_id).await.unwrap().is_archived());
+        assert!(service.list_all_applications().await.unwrap().is_empty());
+        assert_eq!(service.list_archived_applications().await.unwrap().len(), 1);
+        let duplicate = Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), None);
+        assert!(matches!(
+            service.register_application(duplicate).await,
+            Err(DomainError::Conflict { existing_id, .. }) if existing_id == gimp_id.to_string()
+        ));
+
+        assert!(!service.restore_application(&gimp_id).await.unwrap().is_archived());
+        assert_eq!(service.list_all_applications().await.unwrap().len(), 1);
+
+        service.archive_application(&gimp_id).await.unwrap();
+        service.purge_application(&gimp_id, DryRun::No).await.unwrap();
+        assert!(stored.lock().unwrap().is_empty());
+        assert!(matches!(service.restore_application(&gimp_id).await, Err(DomainError::EntityNotFound { .. })));
+
+        let events: Vec<DomainEvent> = events.try_iter().collect();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                DomainEvent::ApplicationArchived { .. },
+                DomainEvent::ApplicationRestored(_),
+                DomainEvent::ApplicationArchived { .. },
+                DomainEvent::ApplicationPurged { id },
+            ] if *id == gimp_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_purge_application_checks_workspace_references() {
+        let mut gimp = Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), None);
+        gimp.deleted_at = Some(Timestamp::now());
+        let gimp_id = gimp.id.clone();
+        let mut workspace = crate::entities::Workspace::new("Grafik".to_string(), None);
+        workspace.applications.push(gimp_id.clone());
+        workspace.assignment_rules.push(WorkspaceAssignmentRule::ApplicationId("GIMP".to_string()));
+        let workspaces = Arc::new(std::sync::Mutex::new(vec![workspace]));
+
+        let mut mock_repo = MockApplicationRepository::new();
+        let app = gimp.clone();
+        mock_repo.expect_get_by_id().returning(move |_| Ok(Some(app.clone())));
+        mock_repo.expect_find().returning(move |_| Ok(vec![gimp.clone()]));
+        mock_repo.expect_remove().times(1).returning(|_| Ok(()));
+        let mut workspace_repo = crate::repositories::workspace_repository::MockWorkspaceRepository::new();
+        let stored = workspaces.clone();
+        workspace_repo.expect_get_all_including_archived().returning(move || Ok(stored.lock().unwrap().clone()));
+        let stored = workspaces.clone();
+        workspace_repo.expect_update().returning(move |updated| {
+            stored.lock().unwrap().iter_mut().filter(|ws| ws.id == updated.id).for_each(|ws| *ws = updated.clone());
+            Ok(())
+        });
+        let service = ApplicationService::new(Arc::new(mock_repo)).with_workspace_repository(Arc::new(workspace_repo));
+
+        assert!(matches!(
+            service.purge_application(&gimp_id, DryRun::No).await,
+            Err(DomainError::IntegrityViolation { reason, .. }) if reason.contains("'Grafik'")
+        ));
+        assert_eq!(workspaces.lock().unwrap()[0].applications, vec![gimp_id.clone()]);
+        workspaces.lock().unwrap()[0].assignment_rules.clear();
+        let workspace_id = workspaces.lock().unwrap()[0].id.clone();
+        let preview = service.purge_application(&gimp_id, DryRun::Yes).await.unwrap();
+        assert_eq!(preview.workspaces, vec![workspace_id]);
+        assert_eq!(workspaces.lock().unwrap()[0].applications, vec![gimp_id.clone()]);
+        assert_eq!(service.purge_application(&gimp_id, DryRun::No).await.unwrap(), preview);
+        assert!(workspaces.lock().unwrap()[0].applications.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_applications_page() {
+        let apps: Vec<Application> = ["gimp", "Blender", "firefox"]
+            .iter()
+            .map(|name| Application::new_desktop(name.to_string(), format!("/usr/bin/{}", name), None))
+            .collect();
+        let mut mock_repo = MockApplicationRepository::new();
+        mock_repo.expect_get_page().returning(move |page| crate::repositories::paging::paginate(apps.clone(), page));
+
+        let service = ApplicationService::new(Arc::new(mock_repo));
+        let page = Page::first(2).sorted_by("name", crate::repositories::SortOrder::Ascending);
+        let first = service.list_applications_page(&page).await.unwrap();
+        assert_eq!(first.items.iter().map(|app| app.name.as_str()).collect::<Vec<_>>(), vec!["Blender", "firefox"]);
+        assert_eq!(service.list_applications_page(&page.next()).await.unwrap().items[0].name, "gimp");
+    }
+
+    #[tokio::test]
+    async fn test_import_batch_conflict_policies() {
+        let mut existing = Application::new_desktop("editor".to_string(), "/usr/bin/editor".to_string(), None);
+        existing.tags = vec!["Arbeit".to_string()];
+        let existing_id = existing.id.clone();
+        let mut incoming = Application::new_desktop("editor-neu".to_string(), "/usr/bin/editor".to_string(), Some("editor".to_string()));
+        incoming.tags = vec!["Text".to_string()];
+        let batch = || {
+            vec![
+                incoming.clone(),
+                Application::new_desktop("browser".to_string(), "/usr/bin/browser".to_string(), None),
+                Application::new_desktop("browser-kopie".to_string(), "/usr/bin/browser".to_string(), None),
+                Application::new_desktop("".to_string(), "/usr/bin/leer".to_string(), None),
+            ]
+        };
+
+        let mut mock_repo = MockApplicationRepository::new();
+        mock_repo.expect_find().returning(move |_| Ok(vec![existing.clone()]));
+        let merged_id = existing_id.clone();
+        mock_repo
+            .expect_save_batch()
+            .withf(move |added: &[Application], updated: &[Application]| {
+                added.len() == 1
+                    && updated.len() == 1
+                    && updated[0].id == merged_id
+                    && updated[0].name == "editor"
+                    && updated[0].icon_name.as_deref() == Some("editor")
+                    && updated[0].tags == vec!["Arbeit".to_string(), "Text".to_string()]
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_repo.expect_save_batch().withf(|added: &[Application], updated: &[Application]| added.len() == 1 && updated.is_empty()).times(1).returning(|_, _| Ok(()));
+
+        let service = ApplicationService::new(Arc::new(mock_repo));
+        let report = service.import_batch(batch(), ImportConflictPolicy::Merge, DryRun::No).await.unwrap();
+        assert_eq!(report.merged, vec![existing_id.clone()]);
+        assert_eq!(report.added.len(), 1);
+        assert_eq!(report.rejected.len(), 2);
+
+        let report = service.import_batch(batch(), ImportConflictPolicy::Skip, DryRun::No).await.unwrap();
+        assert_eq!(report.skipped, vec![existing_id.clone()]);
+        assert!(report.merged.is_empty() && report.replaced.is_empty());
+
+        // Im Probelauf wird nichts gespeichert (`save_batch` wird nicht mehr erwartet).
+        let report = service.import_batch(batch(), ImportConflictPolicy::Replace, DryRun::Yes).await.unwrap();
+        assert_eq!(report.replaced, vec![existing_id.clone()]);
+        assert!(matches!(report.changes.as_slice(), [(id, changes)] if *id == existing_id && changes.contains("editor-neu")));
+    }
 }
@@ -0,0 +1,302 @@
+//! Domänendienst für die Audioeinstellungen.
+//!
+//! Der [`AudioService`] wählt die Standardgeräte für Ausgabe und Aufnahme, regelt Lautstärke
+//! und Stummschaltung der Geräte und die Lautstärke der einzelnen Anwendungen. Er prüft die
+//! Anfragen gegen den aktuellen Zustand des Audiosystems, bevor er sie an das
+//! [`AudioRepository`] weitergibt, und meldet jede Änderung als [`DomainEvent`].
+
+use crate::entities::audio::{AudioDevice, AudioDeviceKind, AudioStream, MAX_VOLUME};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::repositories::audio_repository::AudioRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use std::sync::Arc;
+
+/// Die Schrittweite der Lautstärketasten (5 %).
+pub const VOLUME_STEP: f32 = 0.05;
+
+fn validate_volume(volume: f32) -> DomainResult<()> {
+    if (0.0..=MAX_VOLUME).contains(&volume) {
+        Ok(())
+    } else {
+        Err(DomainError::ValidationError {
+            field: "volume".to_string(),
+            message: format!("Die Lautstärke muss zwischen 0 und {} liegen, nicht {}.", MAX_VOLUME, volume),
+        })
+    }
+}
+
+pub struct AudioService {
+    audio_repository: Arc<dyn AudioRepository>,
+    events: Option<Arc<dyn EventPublisher>>,
+}
+
+impl AudioService {
+    pub fn new(audio_repository: Arc<dyn AudioRepository>) -> Self {
+        Self { audio_repository, events: None }
+    }
+
+    /// Meldet Änderungen an Geräten und Streams als [`DomainEvent`]s an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn publish(&self, event: DomainEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
+        }
+    }
+
+    /// Die Geräte der Art `kind`, nach Bezeichnung sortiert.
+    pub async fn list_devices(&self, kind: AudioDeviceKind) -> DomainResult<Vec<AudioDevice>> {
+        let mut devices: Vec<AudioDevice> =
+            self.audio_repository.get_devices().await?.into_iter().filter(|device| device.kind == kind).collect();
+        devices.sort_by(|a, b| a.description.cmp(&b.description));
+        Ok(devices)
+    }
+
+    /// Das Standardgerät der Art `kind`, oder `None`, wenn es keines gibt.
+    pub async fn default_device(&self, kind: AudioDeviceKind) -> DomainResult<Option<AudioDevice>> {
+        Ok(self.audio_repository.get_devices().await?.into_iter().find(|device| device.kind == kind && device.is_default))
+    }
+
+    async fn device(&self, name: &str) -> DomainResult<AudioDevice> {
+        self.audio_repository
+            .get_devices()
+            .await?
+            .into_iter()
+            .find(|device| device.name == name)
+            .ok_or_else(|| DomainError::not_found::<AudioDevice>(name))
+    }
+
+    async fn stream(&self, id: u32) -> DomainResult<AudioStream> {
+        self.audio_repository
+            .get_streams()
+            .await?
+            .into_iter()
+            .find(|stream| stream.id == id)
+            .ok_or_else(|| DomainError::not_found::<AudioStream>(id))
+    }
+
+    /// Macht das Gerät `name` zum Standardgerät seiner Art.
+    ///
+    /// # Fehler
+    /// `DomainError::EntityNotFound`, wenn es das Gerät nicht gibt, und
+    /// `DomainError::OperationNotPermitted`, wenn es nicht von der Art `kind` ist.
+    pub async fn set_default_device(&self, kind: AudioDeviceKind, name: &str) -> DomainResult<()> {
+        info!(?kind, name, "Setze Standard-Audiogerät.");
+        let device = self.device(name).await?;
+        if device.kind != kind {
+            return Err(DomainError::OperationNotPermitted {
+                operation: "set_default_audio_device".to_string(),
+                reason: format!("Das Gerät '{}' ist kein Gerät der Art {:?}.", device.description, kind),
+            });
+        }
+        if device.is_default {
+            return Ok(());
+        }
+        self.audio_repository.set_default_device(kind, name).await?;
+        self.publish(DomainEvent::DefaultAudioDeviceChanged { kind, name: name.to_string() });
+        Ok(())
+    }
+
+    /// Setzt die Lautstärke des Geräts `name`.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError`, wenn die Lautstärke nicht zwischen 0 und
+    /// [`MAX_VOLUME`] liegt; `DomainError::EntityNotFound` für unbekannte Geräte.
+    pub async fn set_device_volume(&self, name: &str, volume: f32) -> DomainResult<()> {
+        validate_volume(volume)?;
+        let mut device = self.device(name).await?;
+        self.audio_repository.set_device_volume(name, volume).await?;
+        device.volume = volume;
+        self.publish(DomainEvent::AudioDeviceChanged(device));
+        Ok(())
+    }
+
+    /// Schaltet das Gerät `name` stumm oder wieder laut.
+    pub async fn set_device_muted(&self, name: &str, muted: bool) -> DomainResult<()> {
+        let mut device = self.device(name).await?;
+        if device.muted == muted {
+            return Ok(());
+        }
+        self.audio_repository.set_device_muted(name, muted).await?;
+        device.muted = muted;
+        self.publish(DomainEvent::AudioDeviceChanged(device));
+        Ok(())
+    }
+
+    /// Ändert die Lautstärke des Standardgeräts der Art `kind` um `delta`, z.B. für die
+    /// Lautstärketasten mit ±[`VOLUME_STEP`]. Das Ergebnis wird auf 0 bis 100 % begrenzt (bzw.
+    /// die bisherige Lautstärke, falls diese schon darüber lag); beim Lauterstellen wird die
+    /// Stummschaltung aufgehoben.
+    ///
+    /// # Rückgabe
+    /// Die neue Lautstärke, oder `None`, wenn es kein Standardgerät gibt.
+    pub async fn adjust_default_volume(&self, kind: AudioDeviceKind, delta: f32) -> DomainResult<Option<f32>> {
+        let Some(device) = self.default_device(kind).await? else {
+            return Ok(None);
+        };
+        let volume = (device.volume + delta).clamp(0.0, device.volume.max(1.0));
+        if delta > 0.0 {
+            self.set_device_muted(&device.name, false).await?;
+        }
+        if volume != device.volume {
+            self.set_device_volume(&device.name, volume).await?;
+        }
+        Ok(Some(volume))
+    }
+
+    /// Alle Streams der Anwendungen, nach Anwendung sortiert.
+    pub async fn list_streams(&self) -> DomainResult<Vec<AudioStream>> {
+        let mut streams = self.audio_repository.get_streams().await?;
+        streams.sort_by(|a, b| a.application.cmp(&b.application).then(a.id.cmp(&b.id)));
+        Ok(streams)
+    }
+
+    /// Setzt die Lautstärke des Streams `id`.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError` für ungültige Lautstärken (siehe
+    /// [`set_device_volume`](Self::set_device_volume)); `DomainError::EntityNotFound`, wenn
+    /// der Stream nicht (mehr) besteht.
+    pub async fn set_stream_volume(&self, id: u32, volume: f32) -> DomainResult<()> {
+        validate_volume(volume)?;
+        let mut stream = self.stream(id).await?;
+        self.audio_repository.set_stream_volume(id, volume).await?;
+        stream.volume = volume;
+        self.publish(DomainEvent::AudioStreamChanged(stream));
+        Ok(())
+    }
+
+    /// Schaltet den Stream `id` stumm oder wieder laut.
+    pub async fn set_stream_muted(&self, id: u32, muted: bool) -> DomainResult<()> {
+        let mut stream = self.stream(id).await?;
+        if stream.muted == muted {
+            return Ok(());
+        }
+        self.audio_repository.set_stream_muted(id, muted).await?;
+        stream.muted = muted;
+        self.publish(DomainEvent::AudioStreamChanged(stream));
+        Ok(())
+    }
+
+    /// Setzt die Lautstärke aller Streams der Anwendung `app_id`.
+    ///
+    /// # Rückgabe
+    /// Die Anzahl der geänderten Streams.
+    pub async fn set_application_volume(&self, app_id: &str, volume: f32) -> DomainResult<usize> {
+        validate_volume(volume)?;
+        info!(app_id, volume, "Setze Lautstärke einer Anwendung.");
+        let streams: Vec<AudioStream> = self
+            .audio_repository
+            .get_streams()
+            .await?
+            .into_iter()
+            .filter(|stream| stream.app_id.as_deref() == Some(app_id))
+            .collect();
+        for mut stream in streams.iter().cloned() {
+            self.audio_repository.set_stream_volume(stream.id, volume).await?;
+            stream.volume = volume;
+            self.publish(DomainEvent::AudioStreamChanged(stream));
+        }
+        Ok(streams.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::audio::AudioStreamKind;
+    use crate::events::EventBus;
+    use crate::repositories::audio_repository::MockAudioRepository;
+    use std::sync::Mutex;
+
+    fn device(name: &str, kind: AudioDeviceKind, is_default: bool) -> AudioDevice {
+        AudioDevice { name: name.to_string(), description: name.to_uppercase(), kind, volume: 0.5, muted: false, is_default }
+    }
+
+    fn stateful_repository(devices: Vec<AudioDevice>, streams: Vec<AudioStream>) -> MockAudioRepository {
+        let devices = Arc::new(Mutex::new(devices));
+        let streams = Arc::new(Mutex::new(streams));
+        let mut mock_repo = MockAudioRepository::new();
+        let state = devices.clone();
+        mock_repo.expect_get_devices().returning(move || Ok(state.lock().unwrap().clone()));
+        let state = streams.clone();
+        mock_repo.expect_get_streams().returning(move || Ok(state.lock().unwrap().clone()));
+        let state = devices.clone();
+        mock_repo.expect_set_default_device().returning(move |kind, name| {
+            for device in state.lock().unwrap().iter_mut().filter(|device| device.kind == kind) {
+                device.is_default = device.name == name;
+            }
+            Ok(())
+        });
+        let state = devices.clone();
+        mock_repo.expect_set_device_volume().returning(move |name, volume| {
+            state.lock().unwrap().iter_mut().filter(|device| device.name == name).for_each(|device| device.volume = volume);
+            Ok(())
+        });
+        let state = devices;
+        mock_repo.expect_set_device_muted().returning(move |name, muted| {
+            state.lock().unwrap().iter_mut().filter(|device| device.name == name).for_each(|device| device.muted = muted);
+            Ok(())
+        });
+        let state = streams;
+        mock_repo.expect_set_stream_volume().returning(move |id, volume| {
+            state.lock().unwrap().iter_mut().filter(|stream| stream.id == id).for_each(|stream| stream.volume = volume);
+            Ok(())
+        });
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_default_device_and_volume() {
+        let devices = vec![
+            device("speakers", AudioDeviceKind::Sink, true),
+            device("headset", AudioDeviceKind::Sink, false),
+            device("microphone", AudioDeviceKind::Source, true),
+        ];
+        let bus = Arc::new(EventBus::new());
+        let events = bus.subscribe();
+        let service = AudioService::new(Arc::new(stateful_repository(devices, Vec::new()))).with_event_publisher(bus);
+
+        service.set_default_device(AudioDeviceKind::Sink, "headset").await.unwrap();
+        assert_eq!(service.default_device(AudioDeviceKind::Sink).await.unwrap().unwrap().name, "headset");
+        assert!(matches!(
+            service.set_default_device(AudioDeviceKind::Sink, "microphone").await,
+            Err(DomainError::OperationNotPermitted { .. })
+        ));
+        assert!(matches!(service.set_device_volume("headset", 2.0).await, Err(DomainError::ValidationError { .. })));
+
+        service.set_device_muted("headset", true).await.unwrap();
+        assert_eq!(service.adjust_default_volume(AudioDeviceKind::Sink, VOLUME_STEP).await.unwrap(), Some(0.55));
+        let headset = service.default_device(AudioDeviceKind::Sink).await.unwrap().unwrap();
+        assert!(!headset.muted);
+        service.set_device_volume("headset", 0.98).await.unwrap();
+        assert_eq!(service.adjust_default_volume(AudioDeviceKind::Sink, VOLUME_STEP).await.unwrap(), Some(1.0));
+        assert_eq!(
+            events.try_iter().next(),
+            Some(DomainEvent::DefaultAudioDeviceChanged { kind: AudioDeviceKind::Sink, name: "headset".to_string() })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_application_volume_applies_to_all_streams() {
+        let stream = |id, app_id: &str| AudioStream {
+            id,
+            application: app_id.to_string(),
+            app_id: Some(app_id.to_string()),
+            kind: AudioStreamKind::Playback,
+            volume: 1.0,
+            muted: false,
+        };
+        let streams = vec![stream(40, "firefox"), stream(41, "firefox"), stream(52, "spotify")];
+        let service = AudioService::new(Arc::new(stateful_repository(Vec::new(), streams)));
+
+        assert_eq!(service.set_application_volume("firefox", 0.3).await.unwrap(), 2);
+        let volumes: Vec<(u32, f32)> = service.list_streams().await.unwrap().iter().map(|s| (s.id, s.volume)).collect();
+        assert_eq!(volumes, vec![(40, 0.3), (41, 0.3), (52, 1.0)]);
+        assert!(matches!(service.set_stream_volume(99, 0.5).await, Err(DomainError::EntityNotFound { .. })));
+    }
+}
@@ -0,0 +1,197 @@
+//! Domänendienst für das Änderungsprotokoll.
+//!
+//! Der [`AuditService`] hängt für jede Änderung, die ihm ein anderer Dienst meldet, einen
+//! [`AuditRecord`] an das Protokoll an und beantwortet Abfragen darüber. Die Dienste erhalten
+//! ihn über ihre `with_audit_log`-Methoden (z.B. [`ApplicationService::with_audit_log`]).
+//!
+//! Wie das Melden von Ereignissen schlägt das Protokollieren nicht fehl: Kann ein Eintrag
+//! nicht geschrieben werden, wird das als Warnung geloggt, die Änderung selbst bleibt bestehen.
+//!
+//! [`ApplicationService::with_audit_log`]: crate::services::ApplicationService::with_audit_log
+
+use crate::entities::audit_record::{AuditOperation, AuditRecord};
+use crate::repositories::audit_repository::{AuditQuery, AuditRepository};
+use crate::repositories::paging::{Page, PagedResult};
+use crate::DomainResult;
+use novade_core::types::Timestamp;
+use novade_core::{info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// Der Akteur für Änderungen, solange keiner mit [`AuditService::set_actor`] gesetzt wurde.
+pub const SYSTEM_ACTOR: &str = "system";
+
+/// Höchstlänge eines Werts in [`summarize_changes`]; längere Werte werden gekürzt.
+const MAX_VALUE_LENGTH: usize = 80;
+
+/// Fasst die Unterschiede zwischen zwei Zuständen einer Entität zusammen, z.B.
+/// `name: "Arbeit" → "Büro"`. Verglichen werden die Felder der Serialisierung.
+///
+/// # Rückgabe
+/// Die geänderten Felder, durch Kommas getrennt; leer, wenn sich nichts geändert hat.
+///
+/// # Beispiele
+/// ```
+/// use novade_domain::entities::Workspace;
+/// use novade_domain::services::audit_service::summarize_changes;
+///
+/// let before = Workspace::new("Arbeit".to_string(), None);
+/// let mut after = before.clone();
+/// after.name = "Büro".to_string();
+/// assert_eq!(summarize_changes(&before, &after), r#"name: "Arbeit" → "Büro""#);
+/// assert!(summarize_changes(&before, &before).is_empty());
+/// ```
+pub fn summarize_changes<T: Serialize>(before: &T, after: &T) -> String {
+    let (Ok(before), Ok(after)) = (serde_json::to_value(before), serde_json::to_value(after)) else {
+        return String::new();
+    };
+    let changes: Vec<String> = match (&before, &after) {
+        (Value::Object(before_fields), Value::Object(after_fields)) => {
+            let removed = before_fields.keys().filter(|field| !after_fields.contains_key(*field));
+            after_fields
+                .keys()
+                .chain(removed)
+                .filter(|field| before_fields.get(*field) != after_fields.get(*field))
+                .map(|field| format!("{}: {} → {}", field, render(before_fields.get(field)), render(after_fields.get(field))))
+                .collect()
+        }
+        _ if before != after => vec![format!("{} → {}", render(Some(&before)), render(Some(&after)))],
+        _ => Vec::new(),
+    };
+    changes.join(", ")
+}
+
+fn render(value: Option<&Value>) -> String {
+    let text = value.map_or_else(|| "null".to_string(), Value::to_string);
+    if text.chars().count() > MAX_VALUE_LENGTH {
+        format!("{}…", text.chars().take(MAX_VALUE_LENGTH).collect::<String>())
+    } else {
+        text
+    }
+}
+
+pub struct AuditService {
+    audit_repository: Arc<dyn AuditRepository>,
+    actor: Mutex<String>,
+}
+
+impl AuditService {
+    /// Erstellt einen Dienst, der Änderungen [`SYSTEM_ACTOR`] zuschreibt.
+    pub fn new(audit_repository: Arc<dyn AuditRepository>) -> Self {
+        Self { audit_repository, actor: Mutex::new(SYSTEM_ACTOR.to_string()) }
+    }
+
+    /// Wem Änderungen derzeit zugeschrieben werden.
+    pub fn actor(&self) -> String {
+        self.actor.lock().unwrap().clone()
+    }
+
+    /// Schreibt alle folgenden Änderungen `actor` zu, z.B. dem angemeldeten Benutzer.
+    pub fn set_actor(&self, actor: &str) {
+        *self.actor.lock().unwrap() = actor.to_string();
+    }
+
+    /// Protokolliert eine Änderung.
+    ///
+    /// # Rückgabe
+    /// Der angehängte Eintrag, oder `None`, wenn er nicht geschrieben werden konnte.
+    pub async fn record(&self, entity_type: &str, entity_id: &str, operation: AuditOperation, summary: String) -> Option<AuditRecord> {
+        let record = AuditRecord::new(entity_type, entity_id, operation, summary, &self.actor());
+        match self.audit_repository.append(&record).await {
+            Ok(()) => Some(record),
+            Err(error) => {
+                warn!(entity_type, entity_id, ?operation, %error, "Eintrag im Änderungsprotokoll konnte nicht geschrieben werden.");
+                None
+            }
+        }
+    }
+
+    /// Protokolliert eine Änderung von `before` zu `after` als [`AuditOperation::Updated`] mit
+    /// den geänderten Feldern (siehe [`summarize_changes`]).
+    ///
+    /// # Rückgabe
+    /// Der angehängte Eintrag, oder `None`, wenn sich nichts geändert hat oder der Eintrag
+    /// nicht geschrieben werden konnte.
+    pub async fn record_update<T: Serialize + Sync>(&self, entity_type: &str, entity_id: &str, before: &T, after: &T) -> Option<AuditRecord> {
+        let summary = summarize_changes(before, after);
+        if summary.is_empty() {
+            return None;
+        }
+        self.record(entity_type, entity_id, AuditOperation::Updated, summary).await
+    }
+
+    /// Die Einträge, die die Abfrage erfüllen, neueste zuerst.
+    pub async fn query(&self, query: &AuditQuery) -> DomainResult<Vec<AuditRecord>> {
+        info!(?query, "Abfrage des Änderungsprotokolls.");
+        let mut records = self.audit_repository.find(query).await?;
+        records.reverse();
+        Ok(records)
+    }
+
+    /// Alle Änderungen an einer Entität, neueste zuerst.
+    pub async fn history_of(&self, entity_type: &str, entity_id: &str) -> DomainResult<Vec<AuditRecord>> {
+        self.query(&AuditQuery::new().entity_type(entity_type).entity_id(entity_id)).await
+    }
+
+    /// Listet eine Seite der Einträge auf, z.B. für die Protokollansicht in den Einstellungen.
+    pub async fn list_page(&self, page: &Page) -> DomainResult<PagedResult<AuditRecord>> {
+        self.audit_repository.get_page(page).await
+    }
+
+    /// Entfernt alle Einträge, die älter als `cutoff` sind.
+    pub async fn prune_before(&self, cutoff: &Timestamp) -> DomainResult<()> {
+        info!(%cutoff, "Entferne alte Einträge des Änderungsprotokolls.");
+        self.audit_repository.remove_before(cutoff).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::Application;
+    use crate::repositories::audit_repository::MockAuditRepository;
+    use crate::DomainError;
+    use novade_core::CoreError;
+
+    #[tokio::test]
+    async fn test_record_and_query() {
+        let stored: Arc<Mutex<Vec<AuditRecord>>> = Arc::default();
+        let mut mock_repo = MockAuditRepository::new();
+        let records = stored.clone();
+        mock_repo.expect_append().times(2).returning(move |record| {
+            records.lock().unwrap().push(record.clone());
+            Ok(())
+        });
+        let records = stored.clone();
+        mock_repo
+            .expect_find()
+            .returning(move |query| Ok(records.lock().unwrap().iter().filter(|record| query.matches(record)).cloned().collect()));
+
+        let service = AuditService::new(Arc::new(mock_repo));
+        let before = Application::new_desktop("gimp".to_string(), "/usr/bin/gimp".to_string(), None);
+        let id = before.id.to_string();
+        service.record("Application", &id, AuditOperation::Created, "Registriert.".to_string()).await.unwrap();
+        assert!(service.record_update("Application", &id, &before, &before).await.is_none());
+        service.set_actor("anna");
+        let mut after = before.clone();
+        after.tags.push("Grafik".to_string());
+        let update = service.record_update("Application", &id, &before, &after).await.unwrap();
+        assert_eq!(update.summary, r#"tags: [] → ["Grafik"]"#);
+
+        let history = service.history_of("Application", &id).await.unwrap();
+        assert_eq!(history.iter().map(|record| record.operation).collect::<Vec<_>>(), vec![AuditOperation::Updated, AuditOperation::Created]);
+        assert_eq!(history[1].actor, SYSTEM_ACTOR);
+        assert_eq!(service.query(&AuditQuery::new().actor("anna")).await.unwrap(), vec![update]);
+    }
+
+    #[tokio::test]
+    async fn test_record_failure_does_not_propagate() {
+        let mut mock_repo = MockAuditRepository::new();
+        mock_repo
+            .expect_append()
+            .returning(|_| Err(DomainError::RepositoryError(CoreError::UnknownError("Datenträger voll".to_string()))));
+        let service = AuditService::new(Arc::new(mock_repo));
+        assert!(service.record("Workspace", "1", AuditOperation::Deleted, String::new()).await.is_none());
+    }
+}
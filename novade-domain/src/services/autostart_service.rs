@@ -0,0 +1,176 @@
+//! Domänendienst für den Autostart von Anwendungen.
+//!
+//! Über den [`AutostartService`] fügen die Einstellungen Anwendungen zum Autostart hinzu,
+//! aktivieren oder deaktivieren sie und ändern Verzögerung und Startbedingung. Die
+//! Systemschicht startet beim Sitzungsbeginn die Einträge aus
+//! [`AutostartService::entries_to_start`].
+
+use crate::entities::autostart::{AutostartCondition, AutostartEntry};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::repositories::autostart_repository::AutostartRepository;
+use crate::validation::Validate;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct AutostartService {
+    autostart_repository: Arc<dyn AutostartRepository>,
+    events: Option<Arc<dyn EventPublisher>>,
+}
+
+impl AutostartService {
+    pub fn new(autostart_repository: Arc<dyn AutostartRepository>) -> Self {
+        Self { autostart_repository, events: None }
+    }
+
+    /// Meldet geänderte und entfernte Einträge als [`DomainEvent`]s an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn publish(&self, event: DomainEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
+        }
+    }
+
+    async fn entry(&self, application_id: &str) -> DomainResult<AutostartEntry> {
+        self.autostart_repository.get_by_id(application_id).await?.ok_or_else(|| DomainError::not_found::<AutostartEntry>(application_id))
+    }
+
+    async fn save(&self, entry: AutostartEntry) -> DomainResult<AutostartEntry> {
+        entry.validate()?;
+        self.autostart_repository.save(&entry).await?;
+        self.publish(DomainEvent::AutostartEntryChanged(entry.clone()));
+        Ok(entry)
+    }
+
+    /// Alle Einträge, nach Anwendungs-ID sortiert.
+    pub async fn list_entries(&self) -> DomainResult<Vec<AutostartEntry>> {
+        let mut entries = self.autostart_repository.get_all().await?;
+        entries.sort_by(|a, b| a.application_id.cmp(&b.application_id));
+        Ok(entries)
+    }
+
+    /// Die aktivierten Einträge in der Reihenfolge, in der sie fällig werden: nach
+    /// Verzögerung, dann nach Anwendungs-ID. Die Startbedingung prüft die Systemschicht.
+    pub async fn entries_to_start(&self) -> DomainResult<Vec<AutostartEntry>> {
+        let mut entries: Vec<AutostartEntry> =
+            self.autostart_repository.get_all().await?.into_iter().filter(|entry| entry.enabled).collect();
+        entries.sort_by(|a, b| a.delay.cmp(&b.delay).then_with(|| a.application_id.cmp(&b.application_id)));
+        Ok(entries)
+    }
+
+    /// Fügt die Anwendung `application_id` mit der Verzögerung `delay` zum Autostart hinzu.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError`, wenn die Anwendung bereits eingetragen ist oder der
+    /// Eintrag ungültig ist (siehe [`AutostartEntry`]).
+    pub async fn add_entry(&self, application_id: &str, delay: Duration) -> DomainResult<AutostartEntry> {
+        if self.autostart_repository.get_by_id(application_id).await?.is_some() {
+            return Err(DomainError::ValidationError {
+                field: "application_id".to_string(),
+                message: format!("'{}' ist bereits im Autostart eingetragen.", application_id),
+            });
+        }
+        info!(application_id, ?delay, "Füge Anwendung zum Autostart hinzu.");
+        self.save(AutostartEntry { delay, ..AutostartEntry::new(application_id) }).await
+    }
+
+    /// Aktiviert oder deaktiviert den Eintrag der Anwendung `application_id`.
+    ///
+    /// # Fehler
+    /// `DomainError::EntityNotFound`, wenn es keinen Eintrag für die Anwendung gibt.
+    pub async fn set_enabled(&self, application_id: &str, enabled: bool) -> DomainResult<AutostartEntry> {
+        let entry = self.entry(application_id).await?;
+        if entry.enabled == enabled {
+            return Ok(entry);
+        }
+        info!(application_id, enabled, "Ändere Autostart-Eintrag.");
+        self.save(AutostartEntry { enabled, ..entry }).await
+    }
+
+    /// Setzt die Startverzögerung des Eintrags der Anwendung `application_id`.
+    ///
+    /// # Fehler
+    /// `DomainError::EntityNotFound` wie bei [`set_enabled`](Self::set_enabled);
+    /// `DomainError::ValidationError` für Verzögerungen über
+    /// [`MAX_AUTOSTART_DELAY`](crate::entities::autostart::MAX_AUTOSTART_DELAY).
+    pub async fn set_delay(&self, application_id: &str, delay: Duration) -> DomainResult<AutostartEntry> {
+        let entry = self.entry(application_id).await?;
+        self.save(AutostartEntry { delay, ..entry }).await
+    }
+
+    /// Setzt die Startbedingung des Eintrags der Anwendung `application_id`.
+    pub async fn set_condition(&self, application_id: &str, condition: AutostartCondition) -> DomainResult<AutostartEntry> {
+        let entry = self.entry(application_id).await?;
+        self.save(AutostartEntry { condition, ..entry }).await
+    }
+
+    /// Entfernt die Anwendung `application_id` aus dem Autostart.
+    ///
+    /// # Fehler
+    /// `DomainError::EntityNotFound`, wenn es keinen Eintrag für die Anwendung gibt.
+    pub async fn remove_entry(&self, application_id: &str) -> DomainResult<()> {
+        self.entry(application_id).await?;
+        info!(application_id, "Entferne Anwendung aus dem Autostart.");
+        self.autostart_repository.remove(application_id).await?;
+        self.publish(DomainEvent::AutostartEntryRemoved { application_id: application_id.to_string() });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::autostart_repository::MockAutostartRepository;
+    use std::sync::Mutex;
+
+    fn stateful_repository(entries: Vec<AutostartEntry>) -> (MockAutostartRepository, Arc<Mutex<Vec<AutostartEntry>>>) {
+        let stored = Arc::new(Mutex::new(entries));
+        let mut mock_repo = MockAutostartRepository::new();
+        let state = stored.clone();
+        mock_repo
+            .expect_get_by_id()
+            .returning(move |id| Ok(state.lock().unwrap().iter().find(|entry| entry.application_id == id).cloned()));
+        let state = stored.clone();
+        mock_repo.expect_get_all().returning(move || Ok(state.lock().unwrap().clone()));
+        let state = stored.clone();
+        mock_repo.expect_save().returning(move |entry| {
+            let mut entries = state.lock().unwrap();
+            entries.retain(|existing| existing.application_id != entry.application_id);
+            entries.push(entry.clone());
+            Ok(())
+        });
+        (mock_repo, stored)
+    }
+
+    #[tokio::test]
+    async fn test_add_and_disable_entries() {
+        let (mock_repo, stored) = stateful_repository(vec![AutostartEntry::new("nm-applet")]);
+        let service = AutostartService::new(Arc::new(mock_repo));
+
+        assert!(matches!(service.add_entry("nm-applet", Duration::ZERO).await, Err(DomainError::ValidationError { .. })));
+        service.add_entry("org.example.Chat", Duration::from_secs(10)).await.unwrap();
+        service.add_entry("blueman-applet", Duration::from_secs(3)).await.unwrap();
+        assert!(matches!(
+            service.add_entry("mail.desktop", Duration::ZERO).await,
+            Err(DomainError::ValidationError { field, .. }) if field == "application_id"
+        ));
+
+        service.set_enabled("nm-applet", false).await.unwrap();
+        let to_start: Vec<String> =
+            service.entries_to_start().await.unwrap().into_iter().map(|entry| entry.application_id).collect();
+        assert_eq!(to_start, vec!["blueman-applet", "org.example.Chat"]);
+
+        assert!(matches!(service.set_delay("blueman-applet", Duration::from_secs(3600)).await, Err(DomainError::ValidationError { .. })));
+        assert!(matches!(
+            service.set_condition("org.example.Chat", AutostartCondition::IfExists(" ".to_string())).await,
+            Err(DomainError::ValidationError { field, .. }) if field == "condition"
+        ));
+        assert!(matches!(service.set_enabled("unknown", true).await, Err(DomainError::EntityNotFound { .. })));
+        assert_eq!(stored.lock().unwrap().len(), 3);
+    }
+}
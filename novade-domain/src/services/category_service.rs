@@ -0,0 +1,155 @@
+//! Domänendienst zum Durchblättern der Anwendungen nach Kategorien.
+//!
+//! Der [`CategoryService`] ordnet die Anwendungen über eine [`CategoryTaxonomy`] (standardmäßig
+//! die der Freedesktop-Spezifikation) in einen Kategorienbaum ein, wie ihn z.B. der
+//! Anwendungsdrawer anzeigt: zuerst die Hauptkategorien, darunter ihre Unterkategorien und
+//! jeweils alle Anwendungen des Teilbaums.
+
+use crate::entities::application::Application;
+use crate::entities::category::{Category, CategoryTaxonomy};
+use crate::repositories::application_repository::{ApplicationQuery, ApplicationRepository};
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use std::sync::Arc;
+
+/// Eine Kategorie beim Durchblättern mit der Anzahl ihrer Anwendungen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryEntry {
+    pub category: Category,
+    /// Die Anzahl der nicht archivierten Anwendungen in der Kategorie und ihren Unterkategorien.
+    pub application_count: usize,
+    /// Ob es Unterkategorien mit Anwendungen gibt.
+    pub has_subcategories: bool,
+}
+
+pub struct CategoryService {
+    app_repository: Arc<dyn ApplicationRepository>,
+    taxonomy: CategoryTaxonomy,
+}
+
+impl CategoryService {
+    /// Erstellt den Dienst mit der Taxonomie der Freedesktop-Spezifikation.
+    pub fn new(app_repository: Arc<dyn ApplicationRepository>) -> Self {
+        Self { app_repository, taxonomy: CategoryTaxonomy::freedesktop() }
+    }
+
+    /// Verwendet `taxonomy` statt der Freedesktop-Taxonomie, z.B. um sie um
+    /// herstellerspezifische Kategorien zu ergänzen.
+    pub fn with_taxonomy(mut self, taxonomy: CategoryTaxonomy) -> Self {
+        self.taxonomy = taxonomy;
+        self
+    }
+
+    pub fn taxonomy(&self) -> &CategoryTaxonomy {
+        &self.taxonomy
+    }
+
+    fn category(&self, id: &str) -> DomainResult<&Category> {
+        self.taxonomy
+            .get(id)
+            .ok_or_else(|| DomainError::not_found::<Category>(id))
+    }
+
+    /// Die Unterkategorien von `parent` bzw. ohne `parent` die Wurzeln des Baums, die
+    /// Anwendungen enthalten, in der Reihenfolge der Taxonomie.
+    ///
+    /// # Rückgabe
+    /// Die Kategorien oder `DomainError::EntityNotFound` für eine unbekannte Kategorie `parent`.
+    pub async fn browse(&self, parent: Option<&str>) -> DomainResult<Vec<CategoryEntry>> {
+        let categories = match parent {
+            Some(parent) => self.taxonomy.children(&self.category(parent)?.id),
+            None => self.taxonomy.roots(),
+        };
+        let applications = self.app_repository.find(&ApplicationQuery::new()).await?;
+        let count = |category: &Category| {
+            let query = ApplicationQuery::new().in_category_subtree(&self.taxonomy, &category.id);
+            applications.iter().filter(|app| query.matches(app)).count()
+        };
+        Ok(categories
+            .into_iter()
+            .map(|category| CategoryEntry {
+                category: category.clone(),
+                application_count: count(category),
+                has_subcategories: self.taxonomy.children(&category.id).into_iter().any(|child| count(child) > 0),
+            })
+            .filter(|entry| entry.application_count > 0)
+            .collect())
+    }
+
+    /// Der Pfad von der Wurzel bis zur Kategorie `id`, z.B. für eine Brotkrumennavigation.
+    ///
+    /// # Rückgabe
+    /// Der Pfad oder `DomainError::EntityNotFound` für eine unbekannte Kategorie.
+    pub fn path(&self, id: &str) -> DomainResult<Vec<Category>> {
+        self.category(id)?;
+        Ok(self.taxonomy.path(id).into_iter().cloned().collect())
+    }
+
+    /// Die nicht archivierten Anwendungen der Kategorie `id` und ihrer Unterkategorien, nach
+    /// Anzeigenamen sortiert.
+    ///
+    /// # Rückgabe
+    /// Die Anwendungen oder `DomainError::EntityNotFound` für eine unbekannte Kategorie.
+    pub async fn applications_in(&self, id: &str) -> DomainResult<Vec<Application>> {
+        let category = self.category(id)?;
+        info!(category = %category.id, "Anwendungen der Kategorie angefordert.");
+        let query = ApplicationQuery::new().in_category_subtree(&self.taxonomy, &category.id);
+        let mut applications = self.app_repository.find(&query).await?;
+        applications.sort_by_key(|app| app.display_name.as_deref().unwrap_or(&app.name).to_lowercase());
+        Ok(applications)
+    }
+
+    /// Die nicht archivierten Anwendungen ohne bekannte Kategorie, nach Anzeigenamen sortiert
+    /// (z.B. für einen Eintrag "Sonstige").
+    pub async fn uncategorized_applications(&self) -> DomainResult<Vec<Application>> {
+        let mut applications: Vec<Application> = self
+            .app_repository
+            .find(&ApplicationQuery::new())
+            .await?
+            .into_iter()
+            .filter(|app| self.taxonomy.categories_of(app).is_empty())
+            .collect();
+        applications.sort_by_key(|app| app.display_name.as_deref().unwrap_or(&app.name).to_lowercase());
+        Ok(applications)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::application_repository::MockApplicationRepository;
+
+    fn app(name: &str, categories: &[&str]) -> Application {
+        let mut app = Application::new_desktop(name.to_string(), format!("/usr/bin/{}", name), None);
+        app.categories = Some(categories.iter().map(|category| category.to_string()).collect());
+        app
+    }
+
+    #[tokio::test]
+    async fn test_browse_category_tree() {
+        let applications = [
+            app("gimp", &["Graphics", "2DGraphics", "RasterGraphics"]),
+            app("darktable", &["Photography"]),
+            app("simple-scan", &["Graphics", "Scanning"]),
+            app("gedit", &["GNOME", "Utility", "TextEditor"]),
+            app("tool", &["X-Vendor"]),
+        ];
+        let mut mock_repo = MockApplicationRepository::new();
+        mock_repo.expect_find().returning(move |query| Ok(applications.iter().filter(|app| query.matches(app)).cloned().collect()));
+        let service = CategoryService::new(Arc::new(mock_repo));
+
+        let roots: Vec<(String, usize)> =
+            service.browse(None).await.unwrap().into_iter().map(|entry| (entry.category.id, entry.application_count)).collect();
+        assert_eq!(roots, vec![("Graphics".to_string(), 3), ("Utility".to_string(), 1)]);
+        let graphics = service.browse(Some("graphics")).await.unwrap();
+        let ids: Vec<&str> = graphics.iter().map(|entry| entry.category.id.as_str()).collect();
+        assert_eq!(ids, vec!["2DGraphics", "Scanning", "Photography"]);
+        assert!(graphics[0].has_subcategories && !graphics[2].has_subcategories);
+
+        let names: Vec<String> = service.applications_in("Graphics").await.unwrap().into_iter().map(|app| app.name).collect();
+        assert_eq!(names, vec!["darktable", "gimp", "simple-scan"]);
+        assert_eq!(service.uncategorized_applications().await.unwrap()[0].name, "tool");
+        assert_eq!(service.path("RasterGraphics").unwrap().len(), 3);
+        assert!(matches!(service.applications_in("X-Vendor").await, Err(DomainError::EntityNotFound { .. })));
+    }
+}
@@ -0,0 +1,165 @@
+//! Domänendienst für Standardanwendungen und "Öffnen mit …".
+//!
+//! Der [`DefaultApplicationService`] beantwortet, mit welchen Anwendungen ein MIME-Typ, eine
+//! Datei oder ein URL-Schema geöffnet werden kann und welche davon die Standardanwendung ist.
+//! Grundlage sind die MIME-Typen aus den Desktop-Einträgen der Anwendungen
+//! ([`Application::mime_types`]) und die [`MimeAssociation`]s des Benutzers und des Systems.
+
+use crate::entities::application::Application;
+use crate::entities::mime_association::{
+    mime_type_for_file_name, mime_type_matches, scheme_mime_type, validate_mime_type, MimeAssociation,
+};
+use crate::repositories::application_repository::ApplicationRepository;
+use crate::repositories::mime_association_repository::MimeAssociationRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use novade_core::types::NovaId;
+use std::sync::Arc;
+
+pub struct DefaultApplicationService {
+    association_repository: Arc<dyn MimeAssociationRepository>,
+    app_repository: Arc<dyn ApplicationRepository>,
+}
+
+impl DefaultApplicationService {
+    pub fn new(
+        association_repository: Arc<dyn MimeAssociationRepository>,
+        app_repository: Arc<dyn ApplicationRepository>,
+    ) -> Self {
+        Self { association_repository, app_repository }
+    }
+
+    /// Alle Anwendungen, die den MIME-Typ öffnen können, in Vorzugsreihenfolge: die
+    /// Standardanwendung, dann zusätzlich zugeordnete Anwendungen, dann alle Anwendungen, deren
+    /// Desktop-Eintrag den Typ angibt. Ausdrücklich entfernte Zuordnungen werden ausgelassen.
+    pub async fn handlers_for_mime(&self, mime_type: &str) -> DomainResult<Vec<Application>> {
+        validate_mime_type(mime_type)?;
+        let association = self.association_repository.get(mime_type).await?.unwrap_or_else(|| MimeAssociation::new(mime_type));
+        let apps = self.app_repository.get_all().await?;
+        let by_name = |name: &String| apps.iter().find(|app| &app.name == name);
+
+        let mut handlers: Vec<&Application> = Vec::new();
+        let associated = association.default_application.iter().chain(&association.added_applications).filter_map(by_name);
+        let declared = apps.iter().filter(|app| {
+            app.mime_types.iter().any(|declared| mime_type_matches(declared, mime_type))
+                && !association.removed_applications.contains(&app.name)
+        });
+        for app in associated.chain(declared) {
+            if !handlers.iter().any(|handler| handler.id == app.id) {
+                handlers.push(app);
+            }
+        }
+        Ok(handlers.into_iter().cloned().collect())
+    }
+
+    /// Die Anwendungen, die die Datei öffnen können (siehe [`handlers_for_mime`](Self::handlers_for_mime)).
+    /// Der MIME-Typ wird aus der Dateiendung bestimmt.
+    pub async fn handlers_for_file(&self, file_name: &str) -> DomainResult<Vec<Application>> {
+        self.handlers_for_mime(mime_type_for_file_name(file_name)).await
+    }
+
+    /// Die Standardanwendung für den MIME-Typ: die festgelegte, sofern sie noch existiert,
+    /// sonst die erste Anwendung aus [`handlers_for_mime`](Self::handlers_for_mime).
+    pub async fn default_for_mime(&self, mime_type: &str) -> DomainResult<Option<Application>> {
+        Ok(self.handlers_for_mime(mime_type).await?.into_iter().next())
+    }
+
+    /// Die Standardanwendung für ein URL-Schema wie "https" oder "mailto".
+    pub async fn default_for_scheme(&self, scheme: &str) -> DomainResult<Option<Application>> {
+        self.default_for_mime(&scheme_mime_type(scheme)).await
+    }
+
+    /// Legt die Standardanwendung für einen MIME-Typ fest.
+    ///
+    /// Eine zuvor entfernte Zuordnung der Anwendung zu diesem Typ wird aufgehoben.
+    ///
+    /// # Rückgabe
+    /// `DomainError::ValidationError` für ungültige MIME-Typen bzw. `DomainError::EntityNotFound`,
+    /// wenn die Anwendung nicht existiert.
+    pub async fn set_default_for_mime(&self, mime_type: &str, app_id: &NovaId) -> DomainResult<()> {
+        validate_mime_type(mime_type)?;
+        let app = self.app_repository.get_by_id(app_id).await?.ok_or_else(|| DomainError::not_found::<Application>(app_id))?;
+        let mut association = self.association_repository.get(mime_type).await?.unwrap_or_else(|| MimeAssociation::new(mime_type));
+        association.removed_applications.retain(|name| name != &app.name);
+        association.default_application = Some(app.name.clone());
+        info!(mime_type, app_name = %app.name, "Lege Standardanwendung fest.");
+        self.association_repository.set(&association).await
+    }
+
+    /// Legt die Standardanwendung für ein URL-Schema fest (siehe [`set_default_for_mime`](Self::set_default_for_mime)).
+    pub async fn set_default_for_scheme(&self, scheme: &str, app_id: &NovaId) -> DomainResult<()> {
+        self.set_default_for_mime(&scheme_mime_type(scheme), app_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::application_repository::MockApplicationRepository;
+    use crate::repositories::mime_association_repository::MockMimeAssociationRepository;
+
+    fn app(name: &str, mime_types: &[&str]) -> Application {
+        let mut app = Application::new_desktop(name.to_string(), format!("/usr/bin/{}", name), None);
+        app.mime_types = mime_types.iter().map(|mime| mime.to_string()).collect();
+        app
+    }
+
+    fn app_repository(apps: Vec<Application>) -> MockApplicationRepository {
+        let mut mock_repo = MockApplicationRepository::new();
+        let all = apps.clone();
+        mock_repo.expect_get_all().returning(move || Ok(all.clone()));
+        mock_repo.expect_get_by_id().returning(move |id| Ok(apps.iter().find(|app| &app.id == id).cloned()));
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_handler_order_and_removed_associations() {
+        let apps = vec![
+            app("viewer", &["image/png"]),
+            app("gimp", &["image/*"]),
+            app("editor", &["text/plain"]),
+            app("legacy", &["image/png"]),
+        ];
+        let association = MimeAssociation {
+            mime_type: "image/png".to_string(),
+            default_application: Some("gimp".to_string()),
+            added_applications: vec!["editor".to_string(), "uninstalled".to_string()],
+            removed_applications: vec!["legacy".to_string()],
+        };
+        let mut associations = MockMimeAssociationRepository::new();
+        associations.expect_get().returning(move |_| Ok(Some(association.clone())));
+
+        let service = DefaultApplicationService::new(Arc::new(associations), Arc::new(app_repository(apps)));
+        let handlers = service.handlers_for_file("Bild.png").await.unwrap();
+        assert_eq!(handlers.iter().map(|app| app.name.as_str()).collect::<Vec<_>>(), vec!["gimp", "editor", "viewer"]);
+        assert!(matches!(service.handlers_for_mime("kein-mime-typ").await, Err(DomainError::ValidationError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_set_default_for_scheme() {
+        let browser = app("firefox", &["x-scheme-handler/https"]);
+        let browser_id = browser.id.clone();
+        let mut associations = MockMimeAssociationRepository::new();
+        associations.expect_get().returning(|mime_type| {
+            let mut association = MimeAssociation::new(mime_type);
+            association.removed_applications.push("firefox".to_string());
+            Ok(Some(association))
+        });
+        associations
+            .expect_set()
+            .withf(|association: &MimeAssociation| {
+                association.mime_type == "x-scheme-handler/https"
+                    && association.default_application.as_deref() == Some("firefox")
+                    && association.removed_applications.is_empty()
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = DefaultApplicationService::new(Arc::new(associations), Arc::new(app_repository(vec![browser])));
+        service.set_default_for_scheme("HTTPS", &browser_id).await.unwrap();
+        assert!(matches!(
+            service.set_default_for_scheme("https", &NovaId::new()).await,
+            Err(DomainError::EntityNotFound { .. })
+        ));
+    }
+}
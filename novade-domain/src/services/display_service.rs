@@ -0,0 +1,158 @@
+//! Domänendienst für die Bildschirmeinstellungen.
+//!
+//! Der [`DisplayService`] ist die einzige Stelle, an der Bildschirm-Layouts geprüft und
+//! gespeichert werden. Der Compositor meldet ihm beim Start und nach dem Anschließen oder
+//! Entfernen von Bildschirmen, was er erkannt hat, und erhält mit
+//! [`DisplayService::resolve_layout`] das gespeicherte Layout für diese Anschlüsse zurück;
+//! die Einstellungsoberfläche speichert Änderungen mit [`DisplayService::save_layout`].
+
+use crate::entities::display::{layout_key, DisplayLayout};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::repositories::display_layout_repository::DisplayLayoutRepository;
+use crate::validation::Validate;
+use crate::{DomainError, DomainResult};
+use novade_core::{info, warn};
+use std::sync::Arc;
+
+pub struct DisplayService {
+    layout_repository: Arc<dyn DisplayLayoutRepository>,
+    events: Option<Arc<dyn EventPublisher>>,
+}
+
+impl DisplayService {
+    pub fn new(layout_repository: Arc<dyn DisplayLayoutRepository>) -> Self {
+        Self { layout_repository, events: None }
+    }
+
+    /// Meldet gespeicherte und entfernte Layouts als [`DomainEvent`]s an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn publish(&self, event: DomainEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
+        }
+    }
+
+    /// Prüft ein Layout, z.B. bevor der Compositor es anwendet.
+    pub fn validate_layout(&self, layout: &DisplayLayout) -> DomainResult<()> {
+        layout.validate()
+    }
+
+    /// Das gespeicherte Layout für genau die Anschlüsse `connectors`, in beliebiger Reihenfolge.
+    pub async fn layout_for(&self, connectors: &[&str]) -> DomainResult<Option<DisplayLayout>> {
+        self.layout_repository.get_by_key(&layout_key(connectors.iter().copied())).await
+    }
+
+    /// Das Layout, das für die erkannten Bildschirme gelten soll: das gespeicherte Layout für
+    /// dieselben Anschlüsse, oder `detected` selbst, wenn keines gespeichert ist.
+    ///
+    /// Ein gespeichertes Layout, das nicht (mehr) gültig ist, wird ignoriert.
+    pub async fn resolve_layout(&self, detected: DisplayLayout) -> DomainResult<DisplayLayout> {
+        let key = detected.key();
+        match self.layout_repository.get_by_key(&key).await? {
+            Some(saved) => match saved.validate() {
+                Ok(()) => {
+                    info!(key, "Verwende gespeichertes Bildschirm-Layout.");
+                    Ok(saved)
+                }
+                Err(error) => {
+                    warn!(key, %error, "Gespeichertes Bildschirm-Layout ist ungültig und wird ignoriert.");
+                    Ok(detected)
+                }
+            },
+            None => Ok(detected),
+        }
+    }
+
+    /// Prüft und speichert ein Layout; ein gespeichertes Layout für dieselben Anschlüsse wird
+    /// ersetzt.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError`/`ValidationErrors`, wenn das Layout ungültig ist, z.B.
+    /// weil sich Bildschirme überschneiden.
+    pub async fn save_layout(&self, layout: &DisplayLayout) -> DomainResult<()> {
+        info!(key = layout.key(), "Speichere Bildschirm-Layout.");
+        layout.validate()?;
+        self.layout_repository.save(layout).await?;
+        self.publish(DomainEvent::DisplayLayoutSaved(layout.clone()));
+        Ok(())
+    }
+
+    /// Alle gespeicherten Layouts, nach Schlüssel sortiert.
+    pub async fn list_layouts(&self) -> DomainResult<Vec<DisplayLayout>> {
+        let mut layouts = self.layout_repository.get_all().await?;
+        layouts.sort_by_key(DisplayLayout::key);
+        Ok(layouts)
+    }
+
+    /// Entfernt das gespeicherte Layout mit dem Schlüssel `key`.
+    ///
+    /// # Fehler
+    /// `DomainError::EntityNotFound`, wenn kein Layout mit diesem Schlüssel gespeichert ist.
+    pub async fn remove_layout(&self, key: &str) -> DomainResult<()> {
+        info!(key, "Entferne Bildschirm-Layout.");
+        if self.layout_repository.get_by_key(key).await?.is_none() {
+            return Err(DomainError::not_found::<DisplayLayout>(key));
+        }
+        self.layout_repository.remove(key).await?;
+        self.publish(DomainEvent::DisplayLayoutRemoved { key: key.to_string() });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::display::{DisplayMode, OutputConfiguration};
+    use crate::repositories::display_layout_repository::MockDisplayLayoutRepository;
+    use std::sync::Mutex;
+
+    fn layout(connectors: &[&str]) -> DisplayLayout {
+        let mut x = 0;
+        let outputs = connectors
+            .iter()
+            .map(|connector| {
+                let output = OutputConfiguration { x, primary: x == 0, ..OutputConfiguration::new(connector, DisplayMode::new(1920, 1080, 60000)) };
+                x += 1920;
+                output
+            })
+            .collect();
+        DisplayLayout::new(outputs)
+    }
+
+    #[tokio::test]
+    async fn test_save_and_resolve_layout() {
+        let stored: Arc<Mutex<Vec<DisplayLayout>>> = Arc::default();
+        let mut mock_repo = MockDisplayLayoutRepository::new();
+        let layouts = stored.clone();
+        mock_repo
+            .expect_get_by_key()
+            .returning(move |key| Ok(layouts.lock().unwrap().iter().find(|layout| layout.key() == key).cloned()));
+        let layouts = stored.clone();
+        mock_repo.expect_save().returning(move |layout| {
+            let mut layouts = layouts.lock().unwrap();
+            layouts.retain(|existing| existing.key() != layout.key());
+            layouts.push(layout.clone());
+            Ok(())
+        });
+        let service = DisplayService::new(Arc::new(mock_repo));
+
+        let detected = layout(&["eDP-1", "HDMI-A-1"]);
+        assert_eq!(service.resolve_layout(detected.clone()).await.unwrap(), detected);
+
+        let mut arranged = detected.clone();
+        arranged.outputs[1].x = -1920;
+        service.save_layout(&arranged).await.unwrap();
+        assert_eq!(service.resolve_layout(detected.clone()).await.unwrap(), arranged);
+        assert_eq!(service.layout_for(&["HDMI-A-1", "eDP-1"]).await.unwrap(), Some(arranged));
+        assert_eq!(service.resolve_layout(layout(&["eDP-1"])).await.unwrap(), layout(&["eDP-1"]));
+
+        let mut overlapping = detected;
+        overlapping.outputs[1].x = 100;
+        assert!(matches!(service.save_layout(&overlapping).await, Err(DomainError::ValidationError { .. })));
+        assert_eq!(stored.lock().unwrap().len(), 1);
+    }
+}
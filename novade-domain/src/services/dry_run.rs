@@ -0,0 +1,27 @@
+//! Probeläufe destruktiver Operationen.
+//!
+//! [`WorkspaceService::delete_workspace`], [`ApplicationService::purge_application`] und
+//! [`ApplicationService::import_batch`] nehmen einen [`DryRun`] entgegen. Im Probelauf prüfen
+//! sie alles wie sonst und geben denselben Bericht zurück, schreiben aber nichts, melden keine
+//! Ereignisse und protokollieren nichts. So können z.B. Bestätigungsdialoge genau anzeigen,
+//! was sich ändern wird.
+//!
+//! [`WorkspaceService::delete_workspace`]: crate::services::WorkspaceService::delete_workspace
+//! [`ApplicationService::purge_application`]: crate::services::ApplicationService::purge_application
+//! [`ApplicationService::import_batch`]: crate::services::ApplicationService::import_batch
+
+/// Ob eine Operation ihre Änderungen ausführt oder nur berichtet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DryRun {
+    /// Die Änderungen werden ausgeführt.
+    #[default]
+    No,
+    /// Die Änderungen werden nur berichtet.
+    Yes,
+}
+
+impl DryRun {
+    pub fn is_dry_run(self) -> bool {
+        self == DryRun::Yes
+    }
+}
@@ -0,0 +1,197 @@
+//! Domänendienst für Rückgängig/Wiederherstellen innerhalb einer Sitzung.
+//!
+//! Dienste, denen ein [`HistoryService`] übergeben wurde, zeichnen destruktive Operationen
+//! (z.B. das Löschen eines Workspaces oder eine Sammeländerung von Einstellungen) als
+//! [`UndoableCommand`] auf. Der Verlauf wird je Aggregat (z.B. [`WORKSPACE_HISTORY`]) geführt,
+//! so dass ein "Rückgängig" in den Einstellungen keine Workspace-Änderung zurücknimmt.
+//! Der Verlauf wird nicht gespeichert.
+
+use crate::{DomainError, DomainResult};
+use async_trait::async_trait;
+use novade_core::info;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Der Verlauf der Workspace-Operationen.
+pub const WORKSPACE_HISTORY: &str = "workspaces";
+/// Der Verlauf der Einstellungsänderungen.
+pub const PREFERENCE_HISTORY: &str = "preferences";
+/// Die Anzahl der Operationen, die je Aggregat höchstens rückgängig gemacht werden können.
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// Eine aufgezeichnete Operation, die rückgängig gemacht und wiederhergestellt werden kann.
+#[async_trait]
+pub trait UndoableCommand: Send + Sync {
+    /// Eine kurze Beschreibung für die Anzeige (z.B. "Workspace 'Arbeit' löschen").
+    fn description(&self) -> String;
+
+    /// Macht die Operation rückgängig.
+    async fn undo(&self) -> DomainResult<()>;
+
+    /// Führt die Operation nach einem [`undo`](Self::undo) erneut aus.
+    async fn redo(&self) -> DomainResult<()>;
+}
+
+#[derive(Default)]
+struct AggregateHistory {
+    undo: Vec<Box<dyn UndoableCommand>>,
+    redo: Vec<Box<dyn UndoableCommand>>,
+}
+
+pub struct HistoryService {
+    histories: Mutex<HashMap<String, AggregateHistory>>,
+    limit: usize,
+}
+
+impl Default for HistoryService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HistoryService {
+    /// Erstellt einen leeren Verlauf mit [`DEFAULT_HISTORY_LIMIT`].
+    pub fn new() -> Self {
+        Self::with_limit(DEFAULT_HISTORY_LIMIT)
+    }
+
+    /// Erstellt einen leeren Verlauf, der je Aggregat höchstens `limit` Operationen behält.
+    pub fn with_limit(limit: usize) -> Self {
+        Self { histories: Mutex::new(HashMap::new()), limit }
+    }
+
+    /// Zeichnet eine ausgeführte Operation auf. Wiederherstellbare Operationen des Aggregats
+    /// werden dabei verworfen, die älteste Operation fällt bei Überschreiten des Limits heraus.
+    pub fn record(&self, aggregate: &str, command: Box<dyn UndoableCommand>) {
+        let mut histories = self.histories.lock().unwrap();
+        let history = histories.entry(aggregate.to_string()).or_default();
+        history.redo.clear();
+        history.undo.push(command);
+        if history.undo.len() > self.limit {
+            history.undo.remove(0);
+        }
+    }
+
+    /// Ob im Aggregat eine Operation rückgängig gemacht werden kann.
+    pub fn can_undo(&self, aggregate: &str) -> bool {
+        self.histories.lock().unwrap().get(aggregate).is_some_and(|history| !history.undo.is_empty())
+    }
+
+    /// Ob im Aggregat eine Operation wiederhergestellt werden kann.
+    pub fn can_redo(&self, aggregate: &str) -> bool {
+        self.histories.lock().unwrap().get(aggregate).is_some_and(|history| !history.redo.is_empty())
+    }
+
+    /// Die Beschreibungen der rückgängig machbaren Operationen, neueste zuerst.
+    pub fn undo_descriptions(&self, aggregate: &str) -> Vec<String> {
+        self.histories
+            .lock()
+            .unwrap()
+            .get(aggregate)
+            .map(|history| history.undo.iter().rev().map(|command| command.description()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Macht die letzte Operation des Aggregats rückgängig.
+    ///
+    /// Schlägt das Rückgängigmachen fehl, bleibt die Operation im Verlauf.
+    ///
+    /// # Rückgabe
+    /// Die Beschreibung der Operation oder `DomainError::OperationNotPermitted`, wenn es
+    /// nichts rückgängig zu machen gibt.
+    pub async fn undo(&self, aggregate: &str) -> DomainResult<String> {
+        let command = self.take(aggregate, true)?;
+        let description = command.description();
+        info!(aggregate, %description, "Mache Operation rückgängig.");
+        let result = command.undo().await;
+        self.put_back(aggregate, command, result.is_ok());
+        result.map(|()| description)
+    }
+
+    /// Stellt die zuletzt rückgängig gemachte Operation des Aggregats wieder her
+    /// (siehe [`undo`](Self::undo)).
+    pub async fn redo(&self, aggregate: &str) -> DomainResult<String> {
+        let command = self.take(aggregate, false)?;
+        let description = command.description();
+        info!(aggregate, %description, "Stelle Operation wieder her.");
+        let result = command.redo().await;
+        self.put_back(aggregate, command, result.is_err());
+        result.map(|()| description)
+    }
+
+    /// Verwirft den Verlauf eines Aggregats.
+    pub fn clear(&self, aggregate: &str) {
+        self.histories.lock().unwrap().remove(aggregate);
+    }
+
+    fn take(&self, aggregate: &str, undo: bool) -> DomainResult<Box<dyn UndoableCommand>> {
+        let mut histories = self.histories.lock().unwrap();
+        let history = histories.get_mut(aggregate);
+        let command = history.and_then(|history| if undo { history.undo.pop() } else { history.redo.pop() });
+        command.ok_or_else(|| DomainError::OperationNotPermitted {
+            operation: if undo { "undo" } else { "redo" }.to_string(),
+            reason: format!("Im Verlauf '{}' gibt es keine passende Operation.", aggregate),
+        })
+    }
+
+    /// Legt eine Operation auf den Wiederherstellen-Stapel (`to_redo`) bzw. den
+    /// Rückgängig-Stapel zurück.
+    fn put_back(&self, aggregate: &str, command: Box<dyn UndoableCommand>, to_redo: bool) {
+        let mut histories = self.histories.lock().unwrap();
+        let history = histories.entry(aggregate.to_string()).or_default();
+        if to_redo {
+            history.redo.push(command);
+        } else {
+            history.undo.push(command);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Ein Zähler, den die Operation erhöht und beim Rückgängigmachen wieder verringert.
+    struct Increment(Arc<Mutex<i32>>);
+
+    #[async_trait]
+    impl UndoableCommand for Increment {
+        fn description(&self) -> String {
+            "Erhöhen".to_string()
+        }
+
+        async fn undo(&self) -> DomainResult<()> {
+            *self.0.lock().unwrap() -= 1;
+            Ok(())
+        }
+
+        async fn redo(&self) -> DomainResult<()> {
+            *self.0.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_undo_redo_per_aggregate() {
+        let counter = Arc::new(Mutex::new(0));
+        let history = HistoryService::with_limit(2);
+        for _ in 0..3 {
+            *counter.lock().unwrap() += 1;
+            history.record("zähler", Box::new(Increment(counter.clone())));
+        }
+        assert_eq!(history.undo_descriptions("zähler").len(), 2, "Das Limit verwirft die älteste Operation");
+        assert!(!history.can_undo(PREFERENCE_HISTORY));
+
+        history.undo("zähler").await.unwrap();
+        history.undo("zähler").await.unwrap();
+        assert_eq!(*counter.lock().unwrap(), 1);
+        assert!(matches!(history.undo("zähler").await, Err(DomainError::OperationNotPermitted { .. })));
+
+        assert_eq!(history.redo("zähler").await.unwrap(), "Erhöhen");
+        assert_eq!(*counter.lock().unwrap(), 2);
+        // Eine neue Operation verwirft die wiederherstellbaren.
+        history.record("zähler", Box::new(Increment(counter.clone())));
+        assert!(!history.can_redo("zähler"));
+    }
+}
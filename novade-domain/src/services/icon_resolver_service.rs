@@ -0,0 +1,212 @@
+//! Domänendienst für die Auflösung von Icon-Namen zu Icon-Dateien.
+//!
+//! Der [`IconResolverService`] folgt dem Suchalgorithmus der freedesktop.org Icon Theme
+//! Specification: Zuerst werden das aktuelle Icon-Theme und die Themes, von denen es erbt,
+//! durchsucht, dann das Standard-Theme [`FALLBACK_ICON_THEME`] und schließlich die Icons
+//! außerhalb der Themes. Findet sich ein Icon nicht, wird der nächst allgemeinere Name
+//! versucht ("network-wireless-signal" → "network-wireless" → "network").
+//!
+//! Ergebnisse werden zwischengespeichert, bis sich das Icon-Theme ändert oder
+//! [`IconResolverService::clear_cache`] aufgerufen wird (z.B. nach der Installation von Icons).
+
+use crate::entities::application::Application;
+use crate::repositories::icon_theme_repository::IconThemeRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Das Theme, das nach dem aktuellen Icon-Theme und dessen Eltern-Themes durchsucht wird.
+pub const FALLBACK_ICON_THEME: &str = "hicolor";
+/// Das Icon für Anwendungen ohne eigenes oder ohne auffindbares Icon.
+pub const FALLBACK_APPLICATION_ICON: &str = "application-x-executable";
+
+/// Die Schlüssel des Zwischenspeichers: Icon-Name, Größe und Skalierung.
+type IconKey = (String, u32, u32);
+
+pub struct IconResolverService {
+    icon_theme_repository: Arc<dyn IconThemeRepository>,
+    icon_theme: Mutex<String>,
+    cache: Mutex<HashMap<IconKey, Option<PathBuf>>>,
+}
+
+impl IconResolverService {
+    /// Erstellt einen Dienst, der zunächst nur [`FALLBACK_ICON_THEME`] durchsucht.
+    pub fn new(icon_theme_repository: Arc<dyn IconThemeRepository>) -> Self {
+        Self {
+            icon_theme_repository,
+            icon_theme: Mutex::new(FALLBACK_ICON_THEME.to_string()),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Das aktuelle Icon-Theme.
+    pub fn icon_theme(&self) -> String {
+        self.icon_theme.lock().unwrap().clone()
+    }
+
+    /// Wechselt das Icon-Theme, z.B. auf das `icon_theme` eines gemeldeten
+    /// [`ThemeChange`](crate::services::ThemeChange), und verwirft den Zwischenspeicher.
+    pub fn set_icon_theme(&self, icon_theme: &str) {
+        let mut current = self.icon_theme.lock().unwrap();
+        if *current != icon_theme {
+            info!(previous = %current, icon_theme, "Icon-Theme gewechselt.");
+            *current = icon_theme.to_string();
+            self.cache.lock().unwrap().clear();
+        }
+    }
+
+    /// Verwirft alle zwischengespeicherten Ergebnisse.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Löst einen Icon-Namen zu einer Icon-Datei auf.
+    ///
+    /// Ein absoluter Pfad (wie er in Desktop-Einträgen erlaubt ist) wird unverändert übernommen.
+    ///
+    /// # Parameter
+    /// * `size`: Die gewünschte Größe in logischen Pixeln.
+    /// * `scale`: Der Skalierungsfaktor der Ausgabe (1 für normale, 2 für HiDPI-Ausgaben).
+    ///
+    /// # Rückgabe
+    /// Der Pfad der Icon-Datei, `None`, wenn kein passendes Icon gefunden wurde, oder
+    /// `DomainError::ValidationError` bei leerem Namen, Größe oder Skalierung 0.
+    pub async fn resolve(&self, icon_name: &str, size: u32, scale: u32) -> DomainResult<Option<PathBuf>> {
+        let icon_name = icon_name.trim();
+        if icon_name.is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "icon_name".to_string(),
+                message: "Der Icon-Name darf nicht leer sein.".to_string(),
+            });
+        }
+        if size == 0 || scale == 0 {
+            return Err(DomainError::ValidationError {
+                field: if size == 0 { "size" } else { "scale" }.to_string(),
+                message: "Größe und Skalierung müssen mindestens 1 sein.".to_string(),
+            });
+        }
+        if Path::new(icon_name).is_absolute() {
+            return Ok(Some(PathBuf::from(icon_name)));
+        }
+        let key = (icon_name.to_string(), size, scale);
+        if let Some(cached) = self.cache.lock().unwrap().get(&key) {
+            return Ok(cached.clone());
+        }
+
+        let themes = self.theme_chain().await?;
+        let mut name = icon_name;
+        let resolved = loop {
+            let resolved = self.lookup(&themes, name, size, scale).await?;
+            match name.rsplit_once('-') {
+                Some((generic, _)) if resolved.is_none() => name = generic,
+                _ => break resolved,
+            }
+        };
+        self.cache.lock().unwrap().insert(key, resolved.clone());
+        Ok(resolved)
+    }
+
+    /// Löst das Icon einer Anwendung auf; ohne eigenes oder auffindbares Icon das
+    /// [`FALLBACK_APPLICATION_ICON`].
+    pub async fn resolve_application_icon(&self, application: &Application, size: u32, scale: u32) -> DomainResult<Option<PathBuf>> {
+        if let Some(icon_name) = application.icon_name.as_deref().filter(|name| !name.trim().is_empty()) {
+            if let Some(path) = self.resolve(icon_name, size, scale).await? {
+                return Ok(Some(path));
+            }
+        }
+        self.resolve(FALLBACK_APPLICATION_ICON, size, scale).await
+    }
+
+    /// Das aktuelle Icon-Theme und die Themes, von denen es (auch mittelbar) erbt, in
+    /// Suchreihenfolge (Tiefensuche), gefolgt von [`FALLBACK_ICON_THEME`].
+    async fn theme_chain(&self) -> DomainResult<Vec<String>> {
+        let mut themes: Vec<String> = Vec::new();
+        let mut pending = vec![self.icon_theme()];
+        while let Some(theme) = pending.pop() {
+            if themes.contains(&theme) {
+                continue;
+            }
+            let parents = self.icon_theme_repository.parent_themes(&theme).await?;
+            pending.extend(parents.into_iter().rev());
+            themes.push(theme);
+        }
+        if !themes.iter().any(|theme| theme == FALLBACK_ICON_THEME) {
+            themes.push(FALLBACK_ICON_THEME.to_string());
+        }
+        Ok(themes)
+    }
+
+    async fn lookup(&self, themes: &[String], icon_name: &str, size: u32, scale: u32) -> DomainResult<Option<PathBuf>> {
+        for theme in themes {
+            if let Some(path) = self.icon_theme_repository.lookup_icon(theme, icon_name, size, scale).await? {
+                return Ok(Some(path));
+            }
+        }
+        self.icon_theme_repository.lookup_fallback_icon(icon_name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::icon_theme_repository::MockIconThemeRepository;
+    use mockall::predicate::eq;
+
+    /// "Papirus" erbt von "breeze", das wiederum von "hicolor" erbt.
+    fn repository() -> MockIconThemeRepository {
+        let mut mock_repo = MockIconThemeRepository::new();
+        mock_repo.expect_parent_themes().with(eq("Papirus")).returning(|_| Ok(vec!["breeze".to_string()]));
+        mock_repo.expect_parent_themes().with(eq("breeze")).returning(|_| Ok(vec!["hicolor".to_string()]));
+        mock_repo.expect_parent_themes().returning(|_| Ok(Vec::new()));
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_resolve_follows_inheritance_and_caches() {
+        let mut mock_repo = repository();
+        mock_repo
+            .expect_lookup_icon()
+            .with(eq("breeze"), eq("firefox"), eq(48), eq(2))
+            .times(1)
+            .returning(|_, _, _, _| Ok(Some(PathBuf::from("/usr/share/icons/breeze/apps/48@2x/firefox.svg"))));
+        mock_repo.expect_lookup_icon().returning(|_, _, _, _| Ok(None));
+        mock_repo.expect_lookup_fallback_icon().returning(|_| Ok(None));
+
+        let service = IconResolverService::new(Arc::new(mock_repo));
+        service.set_icon_theme("Papirus");
+        let expected = Some(PathBuf::from("/usr/share/icons/breeze/apps/48@2x/firefox.svg"));
+        assert_eq!(service.resolve("firefox", 48, 2).await.unwrap(), expected);
+        // Das zweite Mal aus dem Zwischenspeicher, ohne erneute Suche.
+        assert_eq!(service.resolve("firefox", 48, 2).await.unwrap(), expected);
+        assert_eq!(service.resolve("/opt/app/icon.png", 48, 1).await.unwrap(), Some(PathBuf::from("/opt/app/icon.png")));
+        assert!(matches!(service.resolve(" ", 48, 1).await, Err(DomainError::ValidationError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_falls_back_to_generic_names_and_application_icon() {
+        let mut mock_repo = repository();
+        mock_repo
+            .expect_lookup_icon()
+            .with(eq("hicolor"), eq("network-wireless"), eq(16), eq(1))
+            .returning(|_, _, _, _| Ok(Some(PathBuf::from("/icons/network-wireless.png"))));
+        mock_repo.expect_lookup_icon().returning(|_, _, _, _| Ok(None));
+        mock_repo
+            .expect_lookup_fallback_icon()
+            .with(eq(FALLBACK_APPLICATION_ICON))
+            .returning(|_| Ok(Some(PathBuf::from("/usr/share/pixmaps/application-x-executable.png"))));
+        mock_repo.expect_lookup_fallback_icon().returning(|_| Ok(None));
+
+        let service = IconResolverService::new(Arc::new(mock_repo));
+        assert_eq!(
+            service.resolve("network-wireless-signal-good", 16, 1).await.unwrap(),
+            Some(PathBuf::from("/icons/network-wireless.png"))
+        );
+        let app = Application::new_desktop("tool".to_string(), "/usr/bin/tool".to_string(), Some("tool".to_string()));
+        assert_eq!(
+            service.resolve_application_icon(&app, 16, 1).await.unwrap(),
+            Some(PathBuf::from("/usr/share/pixmaps/application-x-executable.png"))
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Domänendienst für die Verwaltung von Tastenkürzeln.
+//!
+//! Der [`KeybindingService`] stellt sicher, dass eine Tastenkombination innerhalb eines
+//! Kontexts höchstens einer Aktion zugeordnet ist, und liefert der Systemschicht die
+//! Aktion zu einer gedrückten Tastenkombination.
+
+use crate::entities::keybinding::{normalize_accelerator, Keybinding};
+use crate::repositories::keybinding_repository::KeybindingRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use novade_core::types::NovaId;
+use std::sync::Arc;
+
+pub struct KeybindingService {
+    keybinding_repository: Arc<dyn KeybindingRepository>,
+}
+
+impl KeybindingService {
+    pub fn new(keybinding_repository: Arc<dyn KeybindingRepository>) -> Self {
+        Self { keybinding_repository }
+    }
+
+    /// Sucht ein anderes Tastenkürzel mit derselben Tastenkombination im selben Kontext.
+    async fn find_conflict(&self, candidate: &Keybinding) -> DomainResult<Option<Keybinding>> {
+        Ok(self.keybinding_repository.get_all().await?.into_iter().find(|existing| {
+            existing.id != candidate.id
+                && existing.context == candidate.context
+                && existing.accelerator.eq_ignore_ascii_case(&candidate.accelerator)
+        }))
+    }
+
+    async fn validate(&self, keybinding: &Keybinding) -> DomainResult<()> {
+        for (field, value) in [("action", &keybinding.action), ("context", &keybinding.context)] {
+            if value.trim().is_empty() {
+                return Err(DomainError::ValidationError {
+                    field: field.to_string(),
+                    message: "Darf nicht leer sein.".to_string(),
+                });
+            }
+        }
+        if let Some(conflict) = self.find_conflict(keybinding).await? {
+            return Err(DomainError::ValidationError {
+                field: "accelerator".to_string(),
+                message: format!(
+                    "'{}' ist im Kontext '{}' bereits der Aktion '{}' zugeordnet.",
+                    keybinding.accelerator, keybinding.context, conflict.action
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Legt ein neues Tastenkürzel an.
+    ///
+    /// # Rückgabe
+    /// Das gespeicherte Tastenkürzel, oder `DomainError::ValidationError`, wenn die
+    /// Tastenkombination ungültig ist, Aktion oder Kontext leer sind oder die Kombination im
+    /// Kontext bereits belegt ist.
+    pub async fn add_binding(&self, accelerator: &str, action: &str, context: &str) -> DomainResult<Keybinding> {
+        let keybinding = Keybinding::new(accelerator, action, context)?;
+        self.validate(&keybinding).await?;
+        info!(accelerator = %keybinding.accelerator, action, context, "Lege Tastenkürzel an.");
+        self.keybinding_repository.add(&keybinding).await?;
+        Ok(keybinding)
+    }
+
+    /// Ordnet einem vorhandenen Tastenkürzel eine neue Tastenkombination zu.
+    ///
+    /// # Rückgabe
+    /// Das aktualisierte Tastenkürzel; Fehler wie bei [`add_binding`](Self::add_binding) bzw.
+    /// `DomainError::EntityNotFound`, wenn es kein Tastenkürzel mit dieser ID gibt.
+    pub async fn rebind(&self, id: &NovaId, accelerator: &str) -> DomainResult<Keybinding> {
+        let mut keybinding = self.keybinding_repository.get_by_id(id).await?.ok_or_else(|| DomainError::not_found::<Keybinding>(id))?;
+        keybinding.accelerator = normalize_accelerator(accelerator)?;
+        self.validate(&keybinding).await?;
+        info!(keybinding_id = %id, accelerator = %keybinding.accelerator, "Ändere Tastenkürzel.");
+        self.keybinding_repository.update(&keybinding).await?;
+        Ok(keybinding)
+    }
+
+    /// Entfernt ein Tastenkürzel.
+    pub async fn remove_binding(&self, id: &NovaId) -> DomainResult<()> {
+        info!(keybinding_id = %id, "Entferne Tastenkürzel.");
+        self.keybinding_repository.remove(id).await
+    }
+
+    /// Alle Tastenkürzel eines Kontexts, nach Tastenkombination sortiert.
+    pub async fn bindings_for_context(&self, context: &str) -> DomainResult<Vec<Keybinding>> {
+        let mut bindings: Vec<Keybinding> =
+            self.keybinding_repository.get_all().await?.into_iter().filter(|binding| binding.context == context).collect();
+        bindings.sort_by(|a, b| a.accelerator.cmp(&b.accelerator));
+        Ok(bindings)
+    }
+
+    /// Die Aktion, die einer gedrückten Tastenkombination im gegebenen Kontext zugeordnet ist.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn die Kombination im Kontext nicht belegt ist; `DomainError::ValidationError`,
+    /// wenn die Tastenkombination ungültig ist.
+    pub async fn action_for(&self, context: &str, accelerator: &str) -> DomainResult<Option<String>> {
+        let accelerator = normalize_accelerator(accelerator)?;
+        Ok(self
+            .keybinding_repository
+            .get_all()
+            .await?
+            .into_iter()
+            .find(|binding| binding.context == context && binding.accelerator.eq_ignore_ascii_case(&accelerator))
+            .map(|binding| binding.action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::keybinding::GLOBAL_CONTEXT;
+    use crate::repositories::keybinding_repository::MockKeybindingRepository;
+
+    fn repository_with(bindings: Vec<Keybinding>) -> MockKeybindingRepository {
+        let mut mock_repo = MockKeybindingRepository::new();
+        let all = bindings.clone();
+        mock_repo.expect_get_all().returning(move || Ok(all.clone()));
+        mock_repo
+            .expect_get_by_id()
+            .returning(move |id| Ok(bindings.iter().find(|binding| &binding.id == id).cloned()));
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_add_binding_detects_conflicts_per_context() {
+        let terminal = Keybinding::new("Ctrl+Alt+T", "launch.terminal", GLOBAL_CONTEXT).unwrap();
+        let mut mock_repo = repository_with(vec![terminal]);
+        mock_repo.expect_add().times(1).returning(|_| Ok(()));
+
+        let service = KeybindingService::new(Arc::new(mock_repo));
+        match service.add_binding("alt+ctrl+t", "launch.browser", GLOBAL_CONTEXT).await {
+            Err(DomainError::ValidationError { field, message }) => {
+                assert_eq!(field, "accelerator");
+                assert!(message.contains("launch.terminal"));
+            }
+            other => panic!("Konflikt erwartet, erhalten {:?}", other),
+        }
+
+        let binding = service.add_binding("ctrl+alt+t", "switcher.close", "window-switcher").await.unwrap();
+        assert_eq!(binding.accelerator, "Ctrl+Alt+T");
+    }
+
+    #[tokio::test]
+    async fn test_rebind() {
+        let terminal = Keybinding::new("Ctrl+Alt+T", "launch.terminal", GLOBAL_CONTEXT).unwrap();
+        let files = Keybinding::new("Super+E", "launch.files", GLOBAL_CONTEXT).unwrap();
+        let terminal_id = terminal.id.clone();
+        let mut mock_repo = repository_with(vec![terminal, files]);
+        mock_repo
+            .expect_update()
+            .withf(|binding: &Keybinding| binding.accelerator == "Super+Return")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = KeybindingService::new(Arc::new(mock_repo));
+        assert!(matches!(service.rebind(&terminal_id, "Super+e").await, Err(DomainError::ValidationError { .. })));
+        // Die eigene Tastenkombination erneut zu vergeben ist kein Konflikt.
+        assert!(service.rebind(&terminal_id, "super+Return").await.is_ok());
+        assert!(matches!(service.rebind(&NovaId::new(), "Super+X").await, Err(DomainError::EntityNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_action_for() {
+        let terminal = Keybinding::new("Ctrl+Alt+T", "launch.terminal", GLOBAL_CONTEXT).unwrap();
+        let service = KeybindingService::new(Arc::new(repository_with(vec![terminal])));
+
+        assert_eq!(service.action_for(GLOBAL_CONTEXT, "Alt+Ctrl+t").await.unwrap().as_deref(), Some("launch.terminal"));
+        assert_eq!(service.action_for("window-switcher", "Ctrl+Alt+T").await.unwrap(), None);
+        assert!(service.action_for(GLOBAL_CONTEXT, "Ctrl+").await.is_err());
+    }
+}
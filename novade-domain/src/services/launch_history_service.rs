@@ -0,0 +1,170 @@
+//! Domänendienst für das Startprotokoll von Anwendungen.
+//!
+//! Die Systemschicht meldet jeden Start einer Anwendung über
+//! [`LaunchHistoryService::record_launch`] (z.B. aus den Start-Ereignissen des
+//! Prozessmanagers). Daraus berechnet der Dienst für jede Anwendung einen Frecency-Wert:
+//! Jeder Start zählt umso mehr, je kürzer er zurückliegt. Der Launcher verwendet diese Werte,
+//! um häufig und kürzlich genutzte Anwendungen zuerst anzuzeigen.
+
+use crate::entities::application::Application;
+use crate::entities::launch_record::LaunchRecord;
+use crate::repositories::application_repository::ApplicationRepository;
+use crate::repositories::launch_history_repository::LaunchHistoryRepository;
+use crate::DomainResult;
+use novade_core::info;
+use novade_core::types::{NovaId, Timestamp};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Gewicht eines Starts abhängig von seinem Alter in Tagen: `(höchstens so viele Tage alt, Gewicht)`.
+/// Ältere Starts zählen mit [`OLD_LAUNCH_WEIGHT`].
+const AGE_WEIGHTS: [(i64, u64); 4] = [(4, 100), (14, 70), (31, 50), (90, 30)];
+
+/// Gewicht eines Starts, der älter ist als alle Stufen in [`AGE_WEIGHTS`].
+const OLD_LAUNCH_WEIGHT: u64 = 10;
+
+/// Das Gewicht eines Starts zum Zeitpunkt `now`.
+fn launch_weight(record: &LaunchRecord, now: &Timestamp) -> u64 {
+    let age_days = now.as_datetime().signed_duration_since(*record.launched_at.as_datetime()).num_days().max(0);
+    AGE_WEIGHTS
+        .iter()
+        .find(|(max_age, _)| age_days <= *max_age)
+        .map_or(OLD_LAUNCH_WEIGHT, |(_, weight)| *weight)
+}
+
+pub struct LaunchHistoryService {
+    launch_history_repository: Arc<dyn LaunchHistoryRepository>,
+    app_repository: Arc<dyn ApplicationRepository>,
+}
+
+impl LaunchHistoryService {
+    pub fn new(
+        launch_history_repository: Arc<dyn LaunchHistoryRepository>,
+        app_repository: Arc<dyn ApplicationRepository>,
+    ) -> Self {
+        Self { launch_history_repository, app_repository }
+    }
+
+    /// Hält einen Start der Anwendung zum aktuellen Zeitpunkt fest.
+    pub async fn record_launch(&self, application_id: &NovaId) -> DomainResult<()> {
+        info!(%application_id, "Halte Anwendungsstart fest.");
+        self.launch_history_repository.add(&LaunchRecord::new(application_id.clone(), Timestamp::now())).await
+    }
+
+    /// Entfernt das Startprotokoll einer Anwendung.
+    pub async fn forget_application(&self, application_id: &NovaId) -> DomainResult<()> {
+        info!(%application_id, "Entferne Startprotokoll der Anwendung.");
+        self.launch_history_repository.remove_for_application(application_id).await
+    }
+
+    /// Die Frecency-Werte aller Anwendungen, die mindestens einmal gestartet wurden.
+    pub async fn frecency_scores(&self) -> DomainResult<HashMap<NovaId, u64>> {
+        self.frecency_scores_at(&Timestamp::now()).await
+    }
+
+    async fn frecency_scores_at(&self, now: &Timestamp) -> DomainResult<HashMap<NovaId, u64>> {
+        let mut scores = HashMap::new();
+        for record in self.launch_history_repository.get_all().await? {
+            *scores.entry(record.application_id.clone()).or_insert(0) += launch_weight(&record, now);
+        }
+        Ok(scores)
+    }
+
+    /// Die `n` Anwendungen mit dem höchsten Frecency-Wert, beste zuerst.
+    ///
+    /// Anwendungen, die nicht mehr im [`ApplicationRepository`] bekannt sind, werden übersprungen.
+    pub async fn top_applications(&self, n: usize) -> DomainResult<Vec<Application>> {
+        self.top_applications_at(n, &Timestamp::now()).await
+    }
+
+    async fn top_applications_at(&self, n: usize, now: &Timestamp) -> DomainResult<Vec<Application>> {
+        let mut scores: Vec<(NovaId, u64)> = self.frecency_scores_at(now).await?.into_iter().collect();
+        scores.sort_by_key(|(_, score)| Reverse(*score));
+        let mut top = Vec::with_capacity(n.min(scores.len()));
+        for (application_id, _) in scores {
+            if top.len() == n {
+                break;
+            }
+            if let Some(application) = self.app_repository.get_by_id(&application_id).await? {
+                top.push(application);
+            }
+        }
+        Ok(top)
+    }
+
+    /// Sortiert Suchergebnisse nach absteigendem Frecency-Wert.
+    ///
+    /// Die Sortierung ist stabil: Anwendungen mit gleichem Wert, insbesondere nie gestartete,
+    /// behalten die Reihenfolge der Suche.
+    pub async fn rank_search_results(&self, apps: Vec<Application>) -> DomainResult<Vec<Application>> {
+        self.rank_search_results_at(apps, &Timestamp::now()).await
+    }
+
+    async fn rank_search_results_at(&self, mut apps: Vec<Application>, now: &Timestamp) -> DomainResult<Vec<Application>> {
+        let scores = self.frecency_scores_at(now).await?;
+        apps.sort_by_key(|app| Reverse(scores.get(&app.id).copied().unwrap_or(0)));
+        Ok(apps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::application_repository::MockApplicationRepository;
+    use crate::repositories::launch_history_repository::MockLaunchHistoryRepository;
+    use std::str::FromStr;
+
+    fn day(date: &str) -> Timestamp {
+        Timestamp::from_str(&format!("{}T12:00:00Z", date)).unwrap()
+    }
+
+    fn app(name: &str) -> Application {
+        Application::new_desktop(name.to_string(), format!("/usr/bin/{}", name), None)
+    }
+
+    fn service(records: Vec<LaunchRecord>, apps: Vec<Application>) -> LaunchHistoryService {
+        let mut history = MockLaunchHistoryRepository::new();
+        history.expect_get_all().returning(move || Ok(records.clone()));
+        let mut app_repo = MockApplicationRepository::new();
+        app_repo.expect_get_by_id().returning(move |id| Ok(apps.iter().find(|app| &app.id == id).cloned()));
+        LaunchHistoryService::new(Arc::new(history), Arc::new(app_repo))
+    }
+
+    #[tokio::test]
+    async fn test_recent_launches_outweigh_old_ones() {
+        let (editor, browser, removed) = (app("editor"), app("browser"), app("removed"));
+        let mut records = vec![
+            LaunchRecord::new(browser.id.clone(), day("2024-05-30")),
+            LaunchRecord::new(browser.id.clone(), day("2024-05-29")),
+            LaunchRecord::new(removed.id.clone(), day("2024-05-31")),
+            LaunchRecord::new(removed.id.clone(), day("2024-05-31")),
+            LaunchRecord::new(removed.id.clone(), day("2024-05-31")),
+        ];
+        // Fünf Starts vor über drei Monaten zählen weniger als zwei aus dieser Woche.
+        records.extend((0..5).map(|_| LaunchRecord::new(editor.id.clone(), day("2024-01-10"))));
+
+        let service = service(records, vec![editor.clone(), browser.clone()]);
+        let now = day("2024-06-01");
+        let scores = service.frecency_scores_at(&now).await.unwrap();
+        assert_eq!(scores[&browser.id], 200);
+        assert_eq!(scores[&editor.id], 50);
+
+        let top = service.top_applications_at(5, &now).await.unwrap();
+        assert_eq!(top, vec![browser, editor], "Unbekannte Anwendungen werden übersprungen");
+        assert_eq!(service.top_applications_at(1, &now).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rank_search_results_is_stable() {
+        let (terminal, text, tetris) = (app("terminal"), app("text-editor"), app("tetris"));
+        let records = vec![LaunchRecord::new(tetris.id.clone(), day("2024-05-20"))];
+        let service = service(records, Vec::new());
+
+        let ranked = service
+            .rank_search_results_at(vec![terminal.clone(), text.clone(), tetris.clone()], &day("2024-06-01"))
+            .await
+            .unwrap();
+        assert_eq!(ranked, vec![tetris, terminal, text]);
+    }
+}
@@ -0,0 +1,295 @@
+//! Domänendienst für Sprache und Regionaleinstellungen.
+//!
+//! Die Regionaleinstellungen werden wie jede andere Einstellung pro Benutzer und Profil unter
+//! den Schlüsseln `locale.*` gespeichert. Was der Benutzer nicht eingestellt hat, ergibt sich
+//! aus der Standardsprache der Kernkonfiguration ([`CoreConfig::default_locale`]): Die Region
+//! folgt der Sprache, die Tastaturbelegung der Region. Ungültige gespeicherte Werte werden
+//! ignoriert, damit eine von Hand bearbeitete Einstellung die Sitzung nicht unbenutzbar macht.
+//!
+//! Der [`LocalizationService`] meldet jede Änderung der wirksamen Einstellungen als
+//! [`DomainEvent::LocaleSettingsChanged`], damit laufende Komponenten ihre Texte und Formate
+//! neu laden können.
+
+use crate::entities::locale::{
+    normalize_locale_id, DateFormat, KeyboardLayout, LocaleSettings, NumberFormat, FALLBACK_LOCALE, MAX_KEYBOARD_LAYOUTS,
+};
+use crate::entities::user_preference::PreferenceValue;
+use crate::events::{DomainEvent, EventPublisher};
+use crate::services::user_preference_service::{PreferenceChange, UserPreferenceService};
+use crate::{DomainError, DomainResult};
+use novade_core::config::CoreConfig;
+use novade_core::{info, warn};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+
+/// Die Sprache der Oberfläche.
+pub const LANGUAGE_KEY: &str = "locale.language";
+/// Die Region für Datums- und Zahlenformate.
+pub const REGION_KEY: &str = "locale.region";
+/// Das Datumsformat (siehe [`DateFormat::id`]).
+pub const DATE_FORMAT_KEY: &str = "locale.date_format";
+/// Das Zahlenformat (siehe [`NumberFormat::id`]).
+pub const NUMBER_FORMAT_KEY: &str = "locale.number_format";
+/// Die Tastaturbelegungen als Liste in XKB-Schreibweise.
+pub const KEYBOARD_LAYOUTS_KEY: &str = "locale.keyboard_layouts";
+
+/// Prüft eine Locale-ID wie [`normalize_locale_id`], meldet Fehler aber für `field`.
+fn normalize_field(field: &str, id: &str) -> DomainResult<String> {
+    normalize_locale_id(id).map_err(|error| match error {
+        DomainError::ValidationError { message, .. } => DomainError::ValidationError { field: field.to_string(), message },
+        other => other,
+    })
+}
+
+/// Die Standardbelegung für eine Region: die Belegung des Landes (z.B. "at" für "de-AT"),
+/// ohne Land die der Sprache.
+fn default_keyboard_layout(region: &str) -> KeyboardLayout {
+    let mut parts = region.split('-');
+    let language = parts.next().unwrap_or_default();
+    let country = parts.find(|part| part.len() == 2 && part.chars().all(|c| c.is_ascii_alphabetic()));
+    KeyboardLayout { layout: country.unwrap_or(language).to_ascii_lowercase(), variant: None }
+}
+
+pub struct LocalizationService {
+    preferences: Arc<UserPreferenceService>,
+    default_locale: String,
+    preference_changes: Mutex<Receiver<PreferenceChange>>,
+    effective_settings: Mutex<Option<LocaleSettings>>,
+    events: Option<Arc<dyn EventPublisher>>,
+}
+
+impl LocalizationService {
+    /// Erstellt den Dienst mit der Standardsprache aus `core_config`; ist diese keine gültige
+    /// Locale-ID, gilt [`FALLBACK_LOCALE`].
+    pub fn new(preferences: Arc<UserPreferenceService>, core_config: &CoreConfig) -> Self {
+        let default_locale = normalize_locale_id(&core_config.default_locale).unwrap_or_else(|_| {
+            warn!(default_locale = %core_config.default_locale, "Ungültige Standardsprache in der Kernkonfiguration, verwende {}.", FALLBACK_LOCALE);
+            FALLBACK_LOCALE.to_string()
+        });
+        let preference_changes = Mutex::new(preferences.subscribe("locale.*"));
+        Self { preferences, default_locale, preference_changes, effective_settings: Mutex::new(None), events: None }
+    }
+
+    /// Meldet jede Änderung der wirksamen Einstellungen als
+    /// [`DomainEvent::LocaleSettingsChanged`] an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn publish(&self, event: DomainEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
+        }
+    }
+
+    /// Die Standardsprache, die gilt, solange der Benutzer keine Sprache gewählt hat.
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    async fn stored_locale(&self, key: &str) -> DomainResult<Option<String>> {
+        let Some(id) = self.preferences.get_string(key).await? else {
+            return Ok(None);
+        };
+        match normalize_locale_id(&id) {
+            Ok(id) => Ok(Some(id)),
+            Err(_) => {
+                warn!(key, id = %id, "Ungültige Locale-ID in den Einstellungen wird ignoriert.");
+                Ok(None)
+            }
+        }
+    }
+
+    async fn stored_format<T: Default>(&self, key: &str, from_id: fn(&str) -> Option<T>) -> DomainResult<T> {
+        let Some(id) = self.preferences.get_string(key).await? else {
+            return Ok(T::default());
+        };
+        Ok(from_id(&id).unwrap_or_else(|| {
+            warn!(key, id = %id, "Unbekanntes Format in den Einstellungen wird ignoriert.");
+            T::default()
+        }))
+    }
+
+    /// Die wirksamen Regionaleinstellungen.
+    pub async fn settings(&self) -> DomainResult<LocaleSettings> {
+        let language = self.stored_locale(LANGUAGE_KEY).await?.unwrap_or_else(|| self.default_locale.clone());
+        let region = self.stored_locale(REGION_KEY).await?.unwrap_or_else(|| language.clone());
+        let mut keyboard_layouts = Vec::new();
+        if let Some(PreferenceValue::StringList(layouts)) = self.preferences.get_value(KEYBOARD_LAYOUTS_KEY).await? {
+            for layout in layouts.iter().take(MAX_KEYBOARD_LAYOUTS) {
+                match KeyboardLayout::parse(layout) {
+                    Ok(layout) => keyboard_layouts.push(layout),
+                    Err(_) => warn!(layout = %layout, "Ungültige Tastaturbelegung in den Einstellungen wird ignoriert."),
+                }
+            }
+        }
+        if keyboard_layouts.is_empty() {
+            keyboard_layouts.push(default_keyboard_layout(&region));
+        }
+        Ok(LocaleSettings {
+            date_format: self.stored_format(DATE_FORMAT_KEY, DateFormat::from_id).await?,
+            number_format: self.stored_format(NUMBER_FORMAT_KEY, NumberFormat::from_id).await?,
+            language,
+            region,
+            keyboard_layouts,
+        })
+    }
+
+    /// Schreibt einen Wert und meldet die daraus folgende Änderung.
+    async fn update(&self, key: &str, value: PreferenceValue) -> DomainResult<LocaleSettings> {
+        // Der bisherige Stand muss feststehen, bevor sich die Einstellung ändert.
+        self.sync_settings().await?;
+        info!(key, ?value, "Ändere Regionaleinstellung.");
+        self.preferences.set_value(key, value).await?;
+        self.sync_settings().await?;
+        self.settings().await
+    }
+
+    /// Setzt die Sprache der Oberfläche.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError` für das Feld `language`, wenn `id` keine gültige
+    /// Locale-ID ist (siehe [`normalize_locale_id`]).
+    pub async fn set_language(&self, id: &str) -> DomainResult<LocaleSettings> {
+        let id = normalize_field("language", id)?;
+        self.update(LANGUAGE_KEY, PreferenceValue::String(id)).await
+    }
+
+    /// Setzt die Region für Datums- und Zahlenformate.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError` für das Feld `region` wie bei
+    /// [`set_language`](Self::set_language).
+    pub async fn set_region(&self, id: &str) -> DomainResult<LocaleSettings> {
+        let id = normalize_field("region", id)?;
+        self.update(REGION_KEY, PreferenceValue::String(id)).await
+    }
+
+    pub async fn set_date_format(&self, format: DateFormat) -> DomainResult<LocaleSettings> {
+        self.update(DATE_FORMAT_KEY, PreferenceValue::String(format.id().to_string())).await
+    }
+
+    pub async fn set_number_format(&self, format: NumberFormat) -> DomainResult<LocaleSettings> {
+        self.update(NUMBER_FORMAT_KEY, PreferenceValue::String(format.id().to_string())).await
+    }
+
+    /// Setzt die Tastaturbelegungen; die erste ist die Standardbelegung.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError` für das Feld `keyboard_layouts`, wenn keine oder mehr als
+    /// [`MAX_KEYBOARD_LAYOUTS`] Belegungen angegeben sind oder eine doppelt vorkommt.
+    pub async fn set_keyboard_layouts(&self, layouts: &[KeyboardLayout]) -> DomainResult<LocaleSettings> {
+        let invalid = |message: String| DomainError::ValidationError { field: "keyboard_layouts".to_string(), message };
+        if layouts.is_empty() || layouts.len() > MAX_KEYBOARD_LAYOUTS {
+            return Err(invalid(format!("Es sind 1 bis {} Tastaturbelegungen möglich.", MAX_KEYBOARD_LAYOUTS)));
+        }
+        let mut values: Vec<String> = Vec::new();
+        for layout in layouts {
+            // Über `parse` wird auch eine direkt zusammengesetzte Belegung geprüft.
+            let value = KeyboardLayout::parse(&layout.to_string())?.to_string();
+            if values.contains(&value) {
+                return Err(invalid(format!("Die Tastaturbelegung '{}' kommt mehrfach vor.", value)));
+            }
+            values.push(value);
+        }
+        self.update(KEYBOARD_LAYOUTS_KEY, PreferenceValue::StringList(values)).await
+    }
+
+    /// Übernimmt Änderungen der Einstellungen `locale.*`, die am Dienst vorbei erfolgt sind
+    /// (z.B. durch einen Profil- oder Benutzerwechsel), und meldet die neuen wirksamen
+    /// Einstellungen. Der erste Aufruf legt nur den Ausgangsstand fest.
+    ///
+    /// # Rückgabe
+    /// Die neuen Einstellungen, falls sich die wirksamen Einstellungen geändert haben.
+    pub async fn sync_settings(&self) -> DomainResult<Option<LocaleSettings>> {
+        let changed = self.preference_changes.lock().unwrap().try_iter().count() > 0;
+        if !changed && self.effective_settings.lock().unwrap().is_some() {
+            return Ok(None);
+        }
+        let settings = self.settings().await?;
+        let previous = self.effective_settings.lock().unwrap().replace(settings.clone());
+        match previous {
+            Some(previous) if previous != settings => {
+                info!(language = %settings.language, region = %settings.region, "Regionaleinstellungen geändert.");
+                self.publish(DomainEvent::LocaleSettingsChanged(settings.clone()));
+                Ok(Some(settings))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::user_preference::UserPreferenceSetting;
+    use crate::events::EventBus;
+    use crate::repositories::user_preference_repository::MockUserPreferenceRepository;
+    use std::collections::HashMap;
+
+    /// Ein Einstellungs-Repository, das gesetzte Werte tatsächlich speichert.
+    fn preferences() -> Arc<UserPreferenceService> {
+        let settings: Arc<Mutex<HashMap<String, UserPreferenceSetting>>> = Arc::default();
+        let mut mock_repo = MockUserPreferenceRepository::new();
+        let stored = settings.clone();
+        mock_repo.expect_get_preference().returning(move |_, key| Ok(stored.lock().unwrap().get(key).cloned()));
+        let stored = settings.clone();
+        mock_repo.expect_get_all_preferences().returning(move |_| Ok(stored.lock().unwrap().values().cloned().collect()));
+        mock_repo.expect_set_preference().returning(move |_, setting| {
+            settings.lock().unwrap().insert(setting.key.clone(), setting.clone());
+            Ok(())
+        });
+        Arc::new(UserPreferenceService::new(Arc::new(mock_repo)))
+    }
+
+    fn core_config(default_locale: &str) -> CoreConfig {
+        CoreConfig { default_locale: default_locale.to_string(), ..CoreConfig::example() }
+    }
+
+    #[tokio::test]
+    async fn test_defaults_follow_core_config() {
+        let preferences = preferences();
+        let service = LocalizationService::new(preferences.clone(), &core_config("de_AT.UTF-8"));
+        let settings = service.settings().await.unwrap();
+        assert_eq!((settings.language.as_str(), settings.region.as_str()), ("de-AT", "de-AT"));
+        assert_eq!(settings.keyboard_layouts, vec![KeyboardLayout::parse("at").unwrap()]);
+        assert_eq!(settings.date_format, DateFormat::Regional);
+
+        // Von Hand eingetragene, ungültige Werte werden ignoriert.
+        preferences.set_string(LANGUAGE_KEY, "Deutsch".to_string()).await.unwrap();
+        preferences.set_string(DATE_FORMAT_KEY, "julianisch".to_string()).await.unwrap();
+        assert_eq!(service.settings().await.unwrap(), settings);
+
+        let invalid_default = LocalizationService::new(preferences, &core_config("Standard"));
+        assert_eq!(invalid_default.default_locale(), FALLBACK_LOCALE);
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_validated_and_reported() {
+        let preferences = preferences();
+        let bus = Arc::new(EventBus::new());
+        let events = bus.subscribe();
+        let service = LocalizationService::new(preferences.clone(), &core_config("en-US")).with_event_publisher(bus);
+
+        assert!(matches!(
+            service.set_language("english").await,
+            Err(DomainError::ValidationError { field, .. }) if field == "language"
+        ));
+        let settings = service.set_language("de_de").await.unwrap();
+        assert_eq!((settings.language.as_str(), settings.region.as_str()), ("de-DE", "de-DE"));
+        assert_eq!(events.try_recv().unwrap(), DomainEvent::LocaleSettingsChanged(settings));
+
+        let neo = KeyboardLayout::parse("de(neo)").unwrap();
+        let settings = service.set_keyboard_layouts(&[neo.clone(), KeyboardLayout::parse("us").unwrap()]).await.unwrap();
+        assert_eq!(settings.keyboard_layouts[0], neo);
+        assert!(matches!(service.set_keyboard_layouts(&[neo.clone(), neo]).await, Err(DomainError::ValidationError { .. })));
+        assert!(KeyboardLayout::parse("de(").is_err());
+
+        // Eine Änderung am Dienst vorbei wird beim Synchronisieren gemeldet.
+        preferences.set_string(NUMBER_FORMAT_KEY, NumberFormat::DecimalPoint.id().to_string()).await.unwrap();
+        let settings = service.sync_settings().await.unwrap().unwrap();
+        assert_eq!(settings.number_format, NumberFormat::DecimalPoint);
+        assert_eq!(events.try_iter().count(), 2);
+    }
+}
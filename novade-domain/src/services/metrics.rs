@@ -0,0 +1,181 @@
+//! Messwerte der Domänendienste.
+//!
+//! [`ApplicationService`] und [`WorkspaceService`] melden jeden Aufruf ihrer öffentlichen
+//! Methoden mit Dauer und Ergebnis an eine [`ServiceMetrics`]-Senke, die sie über ihre
+//! `with_metrics`-Methoden erhalten. Ohne Senke werden die Aufrufe nicht gemessen
+//! ([`NoopMetrics`]). [`TracingMetrics`] erfasst Aufrufe, Fehler und Dauer je Methode in einer
+//! [`MetricsRegistry`] aus `novade-core` – standardmäßig der globalen, sodass die Werte mit den
+//! übrigen Metriken exportiert werden – und loggt langsame Aufrufe als Warnung, sodass z.B.
+//! langsame Repositories auffallen.
+//!
+//! ## Namen:
+//!
+//! Je Methode werden drei Metriken angelegt, z.B. für `WorkspaceService::list_all_workspaces`:
+//!
+//! - `domain.WorkspaceService.list_all_workspaces.calls`: alle Aufrufe ([`Counter`](novade_core::metrics::Counter))
+//! - `domain.WorkspaceService.list_all_workspaces.errors`: fehlgeschlagene Aufrufe ([`Counter`](novade_core::metrics::Counter))
+//! - `domain.WorkspaceService.list_all_workspaces.duration_seconds`: die Dauer ([`Histogram`](novade_core::metrics::Histogram))
+//!
+//! [`ApplicationService`]: crate::services::ApplicationService
+//! [`WorkspaceService`]: crate::services::WorkspaceService
+
+use crate::{DomainError, DomainResult};
+use novade_core::metrics::{self, HistogramSnapshot, MetricValue, MetricsRegistry};
+use novade_core::{debug, warn};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Eine Senke für die Messwerte der Dienste.
+pub trait ServiceMetrics: Send + Sync {
+    /// Meldet einen Aufruf von `method` des Dienstes `service`, der `elapsed` gedauert hat und
+    /// mit `error` fehlgeschlagen ist (`None` bei Erfolg). Darf nicht blockieren und schlägt
+    /// nicht fehl.
+    fn record(&self, service: &'static str, method: &'static str, elapsed: Duration, error: Option<&DomainError>);
+}
+
+/// Eine [`ServiceMetrics`]-Senke, die alle Messwerte verwirft.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl ServiceMetrics for NoopMetrics {
+    fn record(&self, _service: &'static str, _method: &'static str, _elapsed: Duration, _error: Option<&DomainError>) {}
+}
+
+/// Die gesammelten Messwerte einer Methode, gelesen aus der Registry einer [`TracingMetrics`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodMetrics {
+    /// Die Anzahl aller Aufrufe, auch der fehlgeschlagenen.
+    pub calls: u64,
+    /// Die Anzahl der Aufrufe, die mit einem Fehler endeten.
+    pub errors: u64,
+    /// Die Verteilung der Dauer der Aufrufe in Sekunden über
+    /// [`DEFAULT_BUCKETS`](novade_core::metrics::DEFAULT_BUCKETS).
+    pub latency: HistogramSnapshot,
+}
+
+impl MethodMetrics {
+    /// Die mittlere Dauer eines Aufrufs; `None`, solange die Methode nicht aufgerufen wurde.
+    pub fn mean_latency(&self) -> Option<Duration> {
+        (self.latency.count > 0).then(|| Duration::from_secs_f64(self.latency.sum / self.latency.count as f64))
+    }
+}
+
+/// Eine [`ServiceMetrics`]-Senke, die die Messwerte je Methode in einer [`MetricsRegistry`]
+/// erfasst und über `tracing` loggt: jeden Aufruf auf Stufe `debug`, Aufrufe ab der Schwelle
+/// `slow_threshold` als Warnung.
+#[derive(Debug)]
+pub struct TracingMetrics {
+    slow_threshold: Duration,
+    /// Eine eigene Registry; `None` für die globale aus [`metrics::registry()`].
+    registry: Option<Arc<MetricsRegistry>>,
+}
+
+impl TracingMetrics {
+    /// Erstellt eine Senke, die in die globale Registry schreibt und Aufrufe ab
+    /// `slow_threshold` als Warnung loggt.
+    pub fn new(slow_threshold: Duration) -> Self {
+        Self { slow_threshold, registry: None }
+    }
+
+    /// Wie [`TracingMetrics::new`], schreibt aber in `registry` statt in die globale Registry,
+    /// z.B. in Tests.
+    pub fn with_registry(slow_threshold: Duration, registry: Arc<MetricsRegistry>) -> Self {
+        Self { slow_threshold, registry: Some(registry) }
+    }
+
+    /// Die Registry, in die die Messwerte geschrieben werden.
+    pub fn registry(&self) -> &MetricsRegistry {
+        match &self.registry {
+            Some(registry) => registry,
+            None => metrics::registry(),
+        }
+    }
+
+    /// Die bisherigen Messwerte von `method` des Dienstes `service`; `None`, solange die
+    /// Methode nicht aufgerufen wurde.
+    pub fn method(&self, service: &str, method: &str) -> Option<MethodMetrics> {
+        let prefix = metric_prefix(service, method);
+        let (mut calls, mut errors, mut latency) = (None, 0, None);
+        for snapshot in self.registry().snapshot() {
+            let Some(suffix) = snapshot.name.strip_prefix(&prefix) else { continue };
+            match (suffix, snapshot.value) {
+                (".calls", MetricValue::Counter(count)) => calls = Some(count),
+                (".errors", MetricValue::Counter(count)) => errors = count,
+                (".duration_seconds", MetricValue::Histogram(histogram)) => latency = Some(histogram),
+                _ => {}
+            }
+        }
+        Some(MethodMetrics { calls: calls?, errors, latency: latency? })
+    }
+}
+
+impl Default for TracingMetrics {
+    /// Schreibt in die globale Registry und warnt bei Aufrufen ab 100 ms.
+    fn default() -> Self {
+        Self::new(Duration::from_millis(100))
+    }
+}
+
+impl ServiceMetrics for TracingMetrics {
+    fn record(&self, service: &'static str, method: &'static str, elapsed: Duration, error: Option<&DomainError>) {
+        let prefix = metric_prefix(service, method);
+        let registry = self.registry();
+        registry.histogram(&format!("{prefix}.duration_seconds")).record_duration(elapsed);
+        registry.counter(&format!("{prefix}.errors")).increment(u64::from(error.is_some()));
+        // Zuletzt, damit `method` eine Methode erst mit allen drei Metriken findet.
+        registry.counter(&format!("{prefix}.calls")).increment(1);
+
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        if elapsed >= self.slow_threshold {
+            warn!(service, method, elapsed_ms, error = ?error, "Langsamer Aufruf eines Domänendienstes.");
+        } else {
+            debug!(service, method, elapsed_ms, error = ?error, "Aufruf eines Domänendienstes.");
+        }
+    }
+}
+
+/// Der gemeinsame Anfang der Namen der Metriken von `method` des Dienstes `service`.
+fn metric_prefix(service: &str, method: &str) -> String {
+    format!("domain.{service}.{method}")
+}
+
+/// Führt `future` aus und meldet Dauer und Ergebnis als Aufruf von `method` an `metrics`.
+pub(crate) async fn measure<T>(
+    metrics: &dyn ServiceMetrics,
+    service: &'static str,
+    method: &'static str,
+    future: impl Future<Output = DomainResult<T>>,
+) -> DomainResult<T> {
+    let started = Instant::now();
+    let result = future.await;
+    metrics.record(service, method, started.elapsed(), result.as_ref().err());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracing_metrics_records_into_the_registry() {
+        let registry = Arc::new(MetricsRegistry::new());
+        let metrics = TracingMetrics::with_registry(Duration::from_secs(1), registry.clone());
+        let error = DomainError::UnknownError("kaputt".to_string());
+        metrics.record("WorkspaceService", "list_all_workspaces", Duration::from_micros(500), None);
+        metrics.record("WorkspaceService", "list_all_workspaces", Duration::from_millis(30), Some(&error));
+        metrics.record("WorkspaceService", "list_all_workspaces", Duration::from_secs(3), None);
+
+        let list = metrics.method("WorkspaceService", "list_all_workspaces").unwrap();
+        assert_eq!((list.calls, list.errors), (3, 1));
+        assert_eq!(list.latency.count, 3);
+        assert_eq!(list.latency.buckets.first(), Some(&(0.001, 1)));
+        let mean = list.mean_latency().unwrap().as_secs_f64();
+        assert!((mean - 3.0305 / 3.0).abs() < 1e-6);
+        assert!(metrics.method("WorkspaceService", "rename_workspace").is_none());
+
+        // Die Werte stehen als gewöhnliche Metriken in der Registry.
+        assert_eq!(registry.counter("domain.WorkspaceService.list_all_workspaces.calls").get(), 3);
+        assert_eq!(registry.counter("domain.WorkspaceService.list_all_workspaces.errors").get(), 1);
+    }
+}
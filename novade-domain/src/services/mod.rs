@@ -3,10 +3,50 @@
 //! Datenzugriff und operieren auf Domänenentitäten.
 
 pub mod application_service;
+pub mod audio_service;
+pub mod audit_service;
+pub mod autostart_service;
+pub mod category_service;
+pub mod default_application_service;
+pub mod display_service;
+pub mod dry_run;
+pub mod history_service;
+pub mod icon_resolver_service;
+pub mod keybinding_service;
+pub mod launch_history_service;
+pub mod localization_service;
+pub mod metrics;
+pub mod network_service;
+pub mod notification_service;
+pub mod power_service;
+pub mod recent_items_service;
+pub mod search_service;
+pub mod theme_service;
+pub mod user_preference_service;
 pub mod workspace_service;
-// Zukünftig: user_preference_service.rs
 
 // Re-exportiere die Dienste für einfacheren Zugriff.
-pub use application_service::ApplicationService;
-pub use workspace_service::WorkspaceService;
-// pub use user_preference_service::UserPreferenceService;
+pub use application_service::{ApplicationImportReport, ApplicationPurgeReport, ApplicationService, ImportConflictPolicy};
+pub use audio_service::{AudioService, VOLUME_STEP};
+pub use audit_service::{AuditService, SYSTEM_ACTOR};
+pub use autostart_service::AutostartService;
+pub use category_service::{CategoryEntry, CategoryService};
+pub use default_application_service::DefaultApplicationService;
+pub use display_service::DisplayService;
+pub use dry_run::DryRun;
+pub use history_service::{HistoryService, UndoableCommand, PREFERENCE_HISTORY, WORKSPACE_HISTORY};
+pub use icon_resolver_service::{IconResolverService, FALLBACK_ICON_THEME};
+pub use keybinding_service::KeybindingService;
+pub use launch_history_service::LaunchHistoryService;
+pub use localization_service::LocalizationService;
+pub use metrics::{MethodMetrics, NoopMetrics, ServiceMetrics, TracingMetrics};
+pub use network_service::NetworkService;
+pub use notification_service::{NotificationEvent, NotificationService};
+pub use power_service::{PowerService, DEFAULT_POWER_SAVER_THRESHOLD};
+pub use recent_items_service::{RecentItemFilter, RecentItemsService};
+pub use search_service::{SearchHighlight, SearchResult, SearchResultKind, SearchService, SearchTarget};
+pub use theme_service::{ThemeChange, ThemeService, ACTIVE_THEME_KEY};
+pub use user_preference_service::{
+    ImportMode, ImportReport, PreferenceChange, PreferenceKeyMigration, UserPreferenceService,
+};
+pub use workspace_service::{WorkspaceRemovalReport, WorkspaceService};
@@ -0,0 +1,172 @@
+//! Domänendienst für die Netzwerkverbindungen.
+//!
+//! Der [`NetworkService`] stellt die Verbindungen für die Oberfläche zusammen und baut sie auf
+//! Wunsch des Benutzers auf oder ab. Die Systemschicht ruft [`NetworkService::refresh`] auf,
+//! wenn der Netzwerkdienst eine Änderung meldet; der Dienst meldet daraufhin jede Verbindung,
+//! deren Zustand sich geändert hat, als [`DomainEvent::NetworkConnectionChanged`].
+
+use crate::entities::network::{ConnectionState, NetworkConnection};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::repositories::network_repository::NetworkRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub struct NetworkService {
+    network_repository: Arc<dyn NetworkRepository>,
+    /// Der Zustand der Verbindungen beim letzten Abruf, nach ID.
+    known_states: Mutex<HashMap<String, ConnectionState>>,
+    events: Option<Arc<dyn EventPublisher>>,
+}
+
+impl NetworkService {
+    pub fn new(network_repository: Arc<dyn NetworkRepository>) -> Self {
+        Self { network_repository, known_states: Mutex::new(HashMap::new()), events: None }
+    }
+
+    /// Meldet Zustandsänderungen der Verbindungen als [`DomainEvent`]s an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn publish(&self, event: DomainEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
+        }
+    }
+
+    /// Alle Verbindungen in der Reihenfolge der Oberfläche: aktive zuerst, dann nach Art,
+    /// WLANs nach Signalstärke, zuletzt nach Name.
+    pub async fn list_connections(&self) -> DomainResult<Vec<NetworkConnection>> {
+        let mut connections = self.network_repository.get_connections().await?;
+        connections.sort_by(|a, b| {
+            (!a.is_active(), a.connection_type, Reverse(a.signal_strength), &a.name).cmp(&(
+                !b.is_active(),
+                b.connection_type,
+                Reverse(b.signal_strength),
+                &b.name,
+            ))
+        });
+        Ok(connections)
+    }
+
+    /// Die Verbindungen, die bestehen oder gerade aufgebaut werden.
+    pub async fn active_connections(&self) -> DomainResult<Vec<NetworkConnection>> {
+        Ok(self.list_connections().await?.into_iter().filter(NetworkConnection::is_active).collect())
+    }
+
+    async fn connection(&self, id: &str) -> DomainResult<NetworkConnection> {
+        self.network_repository
+            .get_connections()
+            .await?
+            .into_iter()
+            .find(|connection| connection.id == id)
+            .ok_or_else(|| DomainError::not_found::<NetworkConnection>(id))
+    }
+
+    /// Baut die Verbindung `id` auf; eine aktive Verbindung bleibt unverändert.
+    ///
+    /// # Fehler
+    /// `DomainError::EntityNotFound`, wenn es die Verbindung nicht gibt.
+    pub async fn connect(&self, id: &str) -> DomainResult<()> {
+        let connection = self.connection(id).await?;
+        if connection.is_active() {
+            return Ok(());
+        }
+        info!(id, name = %connection.name, "Baue Netzwerkverbindung auf.");
+        self.network_repository.connect(id).await?;
+        self.record_state(NetworkConnection { state: ConnectionState::Connecting, ..connection });
+        Ok(())
+    }
+
+    /// Baut die Verbindung `id` ab; eine getrennte Verbindung bleibt unverändert.
+    ///
+    /// # Fehler
+    /// `DomainError::EntityNotFound`, wenn es die Verbindung nicht gibt.
+    pub async fn disconnect(&self, id: &str) -> DomainResult<()> {
+        let connection = self.connection(id).await?;
+        if !connection.is_active() {
+            return Ok(());
+        }
+        info!(id, name = %connection.name, "Baue Netzwerkverbindung ab.");
+        self.network_repository.disconnect(id).await?;
+        self.record_state(NetworkConnection { state: ConnectionState::Disconnecting, ..connection });
+        Ok(())
+    }
+
+    /// Fragt die Verbindungen neu ab und meldet jede, deren Zustand sich seit dem letzten
+    /// Abruf geändert hat. Neue Verbindungen werden nur gemeldet, wenn sie aktiv sind.
+    ///
+    /// # Rückgabe
+    /// Die Verbindungen wie bei [`list_connections`](Self::list_connections).
+    pub async fn refresh(&self) -> DomainResult<Vec<NetworkConnection>> {
+        let connections = self.list_connections().await?;
+        let mut known_states = self.known_states.lock().unwrap();
+        let previous = std::mem::take(&mut *known_states);
+        for connection in &connections {
+            known_states.insert(connection.id.clone(), connection.state);
+            let changed = match previous.get(&connection.id) {
+                Some(state) => *state != connection.state,
+                None => connection.is_active(),
+            };
+            if changed {
+                self.publish(DomainEvent::NetworkConnectionChanged(connection.clone()));
+            }
+        }
+        Ok(connections)
+    }
+
+    fn record_state(&self, connection: NetworkConnection) {
+        self.known_states.lock().unwrap().insert(connection.id.clone(), connection.state);
+        self.publish(DomainEvent::NetworkConnectionChanged(connection));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::network::ConnectionType;
+    use crate::events::EventBus;
+    use crate::repositories::network_repository::MockNetworkRepository;
+
+    fn wifi(id: &str, signal_strength: u8) -> NetworkConnection {
+        NetworkConnection { signal_strength: Some(signal_strength), ..NetworkConnection::new(id, id, ConnectionType::Wifi) }
+    }
+
+    #[tokio::test]
+    async fn test_connections_are_listed_and_connected() {
+        let connections: Arc<Mutex<Vec<NetworkConnection>>> = Arc::new(Mutex::new(vec![
+            wifi("Cafe", 40),
+            NetworkConnection::new("Firma-VPN", "Firma-VPN", ConnectionType::Vpn),
+            NetworkConnection { state: ConnectionState::Connected, ..wifi("Zuhause", 90) },
+            wifi("Nachbar", 70),
+        ]));
+        let mut mock_repo = MockNetworkRepository::new();
+        let state = connections.clone();
+        mock_repo.expect_get_connections().returning(move || Ok(state.lock().unwrap().clone()));
+        mock_repo.expect_connect().withf(|id| id == "Firma-VPN").times(1).returning(|_| Ok(()));
+        let bus = Arc::new(EventBus::new());
+        let events = bus.subscribe();
+        let service = NetworkService::new(Arc::new(mock_repo)).with_event_publisher(bus);
+
+        let names: Vec<String> = service.list_connections().await.unwrap().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["Zuhause", "Nachbar", "Cafe", "Firma-VPN"]);
+
+        service.refresh().await.unwrap();
+        assert_eq!(events.try_recv().unwrap(), DomainEvent::NetworkConnectionChanged(connections.lock().unwrap()[2].clone()));
+        assert!(events.try_recv().is_err());
+
+        service.connect("Zuhause").await.unwrap();
+        service.connect("Firma-VPN").await.unwrap();
+        assert!(matches!(events.try_recv().unwrap(), DomainEvent::NetworkConnectionChanged(c) if c.state == ConnectionState::Connecting));
+        assert!(matches!(service.connect("Unbekannt").await, Err(DomainError::EntityNotFound { .. })));
+
+        connections.lock().unwrap()[1].state = ConnectionState::Connected;
+        service.refresh().await.unwrap();
+        assert!(matches!(events.try_recv().unwrap(), DomainEvent::NetworkConnectionChanged(c) if c.id == "Firma-VPN" && c.state == ConnectionState::Connected));
+        assert!(events.try_recv().is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! Domänendienst für Desktop-Benachrichtigungen.
+//!
+//! Der [`NotificationService`] nimmt Benachrichtigungen entgegen, verwaltet den
+//! "Nicht stören"-Modus und meldet über [`NotificationEvent`]s, welche Benachrichtigungen
+//! angezeigt, geschlossen oder über eine Aktion ausgelöst wurden. Ein Benachrichtigungsdaemon
+//! in der System- bzw. UI-Schicht setzt diese Ereignisse in die Anzeige und die
+//! Rückmeldung an die sendenden Anwendungen um.
+
+use crate::entities::notification::{Notification, NotificationUrgency};
+use crate::repositories::notification_repository::NotificationRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use novade_core::signal::Signal;
+use novade_core::types::{NovaId, Timestamp};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+
+/// Ein Ereignis des [`NotificationService`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotificationEvent {
+    /// Eine Benachrichtigung soll angezeigt werden.
+    Shown(Notification),
+    /// Eine Benachrichtigung wurde geschlossen oder ist abgelaufen.
+    Dismissed { id: NovaId },
+    /// Der Benutzer hat eine Aktion einer Benachrichtigung ausgelöst.
+    ActionInvoked { id: NovaId, action_key: String },
+}
+
+pub struct NotificationService {
+    notification_repository: Arc<dyn NotificationRepository>,
+    do_not_disturb: AtomicBool,
+    events: Signal<NotificationEvent>,
+}
+
+impl NotificationService {
+    pub fn new(notification_repository: Arc<dyn NotificationRepository>) -> Self {
+        Self { notification_repository, do_not_disturb: AtomicBool::new(false), events: Signal::new() }
+    }
+
+    /// Abonniert die Ereignisse des Dienstes.
+    ///
+    /// Das Abonnement endet, sobald der `Receiver` verworfen wird.
+    pub fn subscribe(&self) -> Receiver<NotificationEvent> {
+        self.events.subscribe()
+    }
+
+    /// Ob der "Nicht stören"-Modus aktiv ist.
+    pub fn is_do_not_disturb(&self) -> bool {
+        self.do_not_disturb.load(Ordering::SeqCst)
+    }
+
+    /// Schaltet den "Nicht stören"-Modus ein oder aus.
+    ///
+    /// Solange er aktiv ist, werden neue Benachrichtigungen nur in die Historie aufgenommen;
+    /// angezeigt werden lediglich kritische Benachrichtigungen.
+    pub fn set_do_not_disturb(&self, enabled: bool) {
+        info!(enabled, "'Nicht stören'-Modus geändert.");
+        self.do_not_disturb.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Nimmt eine neue Benachrichtigung entgegen.
+    ///
+    /// Die Benachrichtigung wird gespeichert und, sofern der "Nicht stören"-Modus dies zulässt,
+    /// als [`NotificationEvent::Shown`] gemeldet.
+    ///
+    /// # Rückgabe
+    /// Die ID der Benachrichtigung, oder `DomainError::ValidationError`, wenn die
+    /// Zusammenfassung leer ist oder Aktionsbezeichner leer bzw. doppelt sind.
+    pub async fn post(&self, notification: Notification) -> DomainResult<NovaId> {
+        if notification.summary.trim().is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "summary".to_string(),
+                message: "Die Zusammenfassung einer Benachrichtigung darf nicht leer sein.".to_string(),
+            });
+        }
+        let mut keys = HashSet::new();
+        if let Some(action) = notification.actions.iter().find(|action| action.key.is_empty() || !keys.insert(&action.key)) {
+            return Err(DomainError::ValidationError {
+                field: "actions".to_string(),
+                message: format!("Ungültiger oder doppelter Aktionsbezeichner '{}'.", action.key),
+            });
+        }
+
+        info!(notification_id = %notification.id, app_name = %notification.app_name, "Neue Benachrichtigung.");
+        self.notification_repository.add(&notification).await?;
+        let id = notification.id.clone();
+        if !self.is_do_not_disturb() || notification.urgency == NotificationUrgency::Critical {
+            self.events.emit(NotificationEvent::Shown(notification));
+        }
+        Ok(id)
+    }
+
+    async fn get_existing(&self, id: &NovaId) -> DomainResult<Notification> {
+        self.notification_repository.get_by_id(id).await?.ok_or_else(|| DomainError::not_found::<Notification>(id))
+    }
+
+    /// Schließt eine Benachrichtigung. Sie bleibt in der Historie erhalten.
+    ///
+    /// Das Schließen einer bereits geschlossenen Benachrichtigung hat keine Wirkung.
+    pub async fn dismiss(&self, id: &NovaId) -> DomainResult<()> {
+        let mut notification = self.get_existing(id).await?;
+        if notification.dismissed {
+            return Ok(());
+        }
+        notification.dismissed = true;
+        self.notification_repository.update(&notification).await?;
+        info!(notification_id = %id, "Benachrichtigung geschlossen.");
+        self.events.emit(NotificationEvent::Dismissed { id: id.clone() });
+        Ok(())
+    }
+
+    /// Meldet, dass der Benutzer eine Aktion einer Benachrichtigung ausgelöst hat, und schließt
+    /// die Benachrichtigung anschließend.
+    ///
+    /// # Rückgabe
+    /// `DomainError::ValidationError`, wenn die Benachrichtigung die Aktion nicht anbietet,
+    /// bzw. `DomainError::OperationNotPermitted`, wenn sie bereits geschlossen ist.
+    pub async fn invoke_action(&self, id: &NovaId, action_key: &str) -> DomainResult<()> {
+        let notification = self.get_existing(id).await?;
+        if notification.dismissed {
+            return Err(DomainError::OperationNotPermitted {
+                operation: "invoke_action".to_string(),
+                reason: format!("Die Benachrichtigung '{}' ist bereits geschlossen.", id),
+            });
+        }
+        if !notification.has_action(action_key) {
+            return Err(DomainError::ValidationError {
+                field: "action_key".to_string(),
+                message: format!("Die Benachrichtigung bietet keine Aktion '{}' an.", action_key),
+            });
+        }
+        info!(notification_id = %id, action_key, "Aktion einer Benachrichtigung ausgelöst.");
+        self.events.emit(NotificationEvent::ActionInvoked { id: id.clone(), action_key: action_key.to_string() });
+        self.dismiss(id).await
+    }
+
+    /// Schließt alle Benachrichtigungen, die zum Zeitpunkt `now` abgelaufen sind.
+    ///
+    /// # Rückgabe
+    /// Die IDs der geschlossenen Benachrichtigungen.
+    pub async fn dismiss_expired(&self, now: &Timestamp) -> DomainResult<Vec<NovaId>> {
+        let mut expired = Vec::new();
+        for notification in self.notification_repository.get_all().await? {
+            if !notification.dismissed && notification.is_expired(now) {
+                self.dismiss(&notification.id).await?;
+                expired.push(notification.id);
+            }
+        }
+        Ok(expired)
+    }
+
+    /// Die zum Zeitpunkt `now` noch anzuzeigenden Benachrichtigungen, älteste zuerst.
+    pub async fn active_notifications(&self, now: &Timestamp) -> DomainResult<Vec<Notification>> {
+        let mut active: Vec<Notification> =
+            self.notification_repository.get_all().await?.into_iter().filter(|n| n.is_active(now)).collect();
+        active.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(active)
+    }
+
+    /// Die Benachrichtigungshistorie einschließlich geschlossener Benachrichtigungen, neueste zuerst.
+    ///
+    /// # Parameter
+    /// * `limit`: Die maximale Anzahl zurückgegebener Benachrichtigungen; `None` für alle.
+    pub async fn history(&self, limit: Option<usize>) -> DomainResult<Vec<Notification>> {
+        let mut history = self.notification_repository.get_all().await?;
+        history.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        history.truncate(limit.unwrap_or(usize::MAX));
+        Ok(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::notification_repository::MockNotificationRepository;
+    use std::str::FromStr;
+
+    fn at(time: &str) -> Timestamp {
+        Timestamp::from_str(&format!("2024-05-01T{}Z", time)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_post_respects_do_not_disturb() {
+        let mut mock_repo = MockNotificationRepository::new();
+        mock_repo.expect_add().times(3).returning(|_| Ok(()));
+
+        let service = NotificationService::new(Arc::new(mock_repo));
+        let events = service.subscribe();
+        service.post(Notification::new("Mail", "Neue Nachricht")).await.unwrap();
+
+        service.set_do_not_disturb(true);
+        service.post(Notification::new("Mail", "Noch eine Nachricht")).await.unwrap();
+        service.post(Notification::new("Akku", "Akku fast leer").with_urgency(NotificationUrgency::Critical)).await.unwrap();
+
+        let shown: Vec<String> = events
+            .try_iter()
+            .map(|event| match event {
+                NotificationEvent::Shown(notification) => notification.summary,
+                other => panic!("Unerwartetes Ereignis {:?}", other),
+            })
+            .collect();
+        assert_eq!(shown, vec!["Neue Nachricht", "Akku fast leer"]);
+    }
+
+    #[tokio::test]
+    async fn test_post_rejects_invalid_notifications() {
+        let mut mock_repo = MockNotificationRepository::new();
+        mock_repo.expect_add().never();
+
+        let service = NotificationService::new(Arc::new(mock_repo));
+        assert!(matches!(
+            service.post(Notification::new("Mail", " ")).await,
+            Err(DomainError::ValidationError { field, .. }) if field == "summary"
+        ));
+        let duplicate = Notification::new("Mail", "Nachricht").with_action("open", "Öffnen").with_action("open", "Anzeigen");
+        assert!(matches!(
+            service.post(duplicate).await,
+            Err(DomainError::ValidationError { field, .. }) if field == "actions"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invoke_action_dismisses_notification() {
+        let notification = Notification::new("Mail", "Neue Nachricht").with_action("reply", "Antworten");
+        let id = notification.id.clone();
+
+        let mut mock_repo = MockNotificationRepository::new();
+        let stored = notification.clone();
+        mock_repo.expect_get_by_id().returning(move |_| Ok(Some(stored.clone())));
+        mock_repo.expect_update().withf(|n: &Notification| n.dismissed).times(1).returning(|_| Ok(()));
+
+        let service = NotificationService::new(Arc::new(mock_repo));
+        let events = service.subscribe();
+        assert!(matches!(service.invoke_action(&id, "delete").await, Err(DomainError::ValidationError { .. })));
+        service.invoke_action(&id, "reply").await.unwrap();
+
+        let events: Vec<NotificationEvent> = events.try_iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                NotificationEvent::ActionInvoked { id: id.clone(), action_key: "reply".to_string() },
+                NotificationEvent::Dismissed { id },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dismiss_unknown_notification() {
+        let mut mock_repo = MockNotificationRepository::new();
+        mock_repo.expect_get_by_id().returning(|_| Ok(None));
+
+        let service = NotificationService::new(Arc::new(mock_repo));
+        assert!(matches!(service.dismiss(&NovaId::new()).await, Err(DomainError::EntityNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_history_and_expiry() {
+        let mut first = Notification::new("Kalender", "Termin").expiring_at(at("10:05:00"));
+        first.created_at = at("10:00:00");
+        let mut second = Notification::new("Mail", "Nachricht");
+        second.created_at = at("10:01:00");
+        let mut third = Notification::new("Mail", "Gelesen");
+        third.created_at = at("10:02:00");
+        third.dismissed = true;
+
+        let all = vec![first.clone(), second.clone(), third.clone()];
+        let mut mock_repo = MockNotificationRepository::new();
+        mock_repo.expect_get_all().returning(move || Ok(all.clone()));
+        let stored = first.clone();
+        mock_repo.expect_get_by_id().returning(move |_| Ok(Some(stored.clone())));
+        mock_repo.expect_update().times(1).returning(|_| Ok(()));
+
+        let service = NotificationService::new(Arc::new(mock_repo));
+        let history = service.history(Some(2)).await.unwrap();
+        assert_eq!(history.iter().map(|n| n.summary.as_str()).collect::<Vec<_>>(), vec!["Gelesen", "Nachricht"]);
+
+        let active = service.active_notifications(&at("10:03:00")).await.unwrap();
+        assert_eq!(active.iter().map(|n| n.summary.as_str()).collect::<Vec<_>>(), vec!["Termin", "Nachricht"]);
+        let active = service.active_notifications(&at("10:06:00")).await.unwrap();
+        assert_eq!(active.iter().map(|n| n.summary.as_str()).collect::<Vec<_>>(), vec!["Nachricht"]);
+
+        assert_eq!(service.dismiss_expired(&at("10:06:00")).await.unwrap(), vec![first.id]);
+    }
+}
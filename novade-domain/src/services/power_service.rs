@@ -0,0 +1,260 @@
+//! Domänendienst für die Energieverwaltung.
+//!
+//! Der [`PowerService`] wechselt Energieprofile auf Wunsch des Benutzers und automatisch nach
+//! dem Akkustand: Fällt der Ladestand im Akkubetrieb unter die Schwelle (standardmäßig
+//! [`DEFAULT_POWER_SAVER_THRESHOLD`]), wird auf [`PowerProfile::PowerSaver`] gewechselt;
+//! sobald das Netzteil wieder angeschlossen ist, gilt wieder das vorherige Profil. Wählt der
+//! Benutzer währenddessen selbst ein Profil, bleibt es bis zum nächsten Laden dabei.
+//!
+//! Die Systemschicht ruft [`PowerService::update_from_battery`] auf, wenn sich der
+//! Akkuzustand ändert (z.B. bei Änderungen der UPower-Eigenschaften).
+
+use crate::entities::power::{BatteryState, PowerProfile};
+use crate::events::{DomainEvent, EventPublisher};
+use crate::repositories::power_repository::PowerRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use std::sync::{Arc, Mutex};
+
+/// Der Ladestand in Prozent, unter dem im Akkubetrieb auf [`PowerProfile::PowerSaver`]
+/// gewechselt wird, solange keine andere Schwelle gesetzt ist.
+pub const DEFAULT_POWER_SAVER_THRESHOLD: f64 = 20.0;
+
+/// Der Zustand der automatischen Umschaltung.
+#[derive(Debug, Default)]
+struct AutoSwitch {
+    /// Das Profil vor der automatischen Umschaltung, das beim Laden wiederhergestellt wird.
+    restore_profile: Option<PowerProfile>,
+    /// Der Benutzer hat bei niedrigem Akkustand selbst ein Profil gewählt; bis zum nächsten
+    /// Laden wird nicht mehr automatisch umgeschaltet.
+    user_override: bool,
+}
+
+pub struct PowerService {
+    power_repository: Arc<dyn PowerRepository>,
+    power_saver_threshold: Mutex<Option<f64>>,
+    auto_switch: Mutex<AutoSwitch>,
+    events: Option<Arc<dyn EventPublisher>>,
+}
+
+impl PowerService {
+    /// Erstellt einen Dienst mit der Schwelle [`DEFAULT_POWER_SAVER_THRESHOLD`].
+    pub fn new(power_repository: Arc<dyn PowerRepository>) -> Self {
+        Self {
+            power_repository,
+            power_saver_threshold: Mutex::new(Some(DEFAULT_POWER_SAVER_THRESHOLD)),
+            auto_switch: Mutex::new(AutoSwitch::default()),
+            events: None,
+        }
+    }
+
+    /// Meldet jeden Profilwechsel als [`DomainEvent::PowerProfileChanged`] an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn publish(&self, event: DomainEvent) {
+        if let Some(events) = &self.events {
+            events.publish(event);
+        }
+    }
+
+    /// Die Schwelle für den automatischen Wechsel auf [`PowerProfile::PowerSaver`], oder
+    /// `None`, wenn nicht automatisch gewechselt wird.
+    pub fn power_saver_threshold(&self) -> Option<f64> {
+        *self.power_saver_threshold.lock().unwrap()
+    }
+
+    /// Setzt die Schwelle für den automatischen Wechsel; `None` schaltet ihn ab.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError`, wenn die Schwelle nicht zwischen 0 und 100 liegt.
+    pub fn set_power_saver_threshold(&self, threshold: Option<f64>) -> DomainResult<()> {
+        if let Some(threshold) = threshold {
+            if !(0.0..=100.0).contains(&threshold) {
+                return Err(DomainError::ValidationError {
+                    field: "power_saver_threshold".to_string(),
+                    message: format!("Die Schwelle muss zwischen 0 und 100 Prozent liegen, nicht {}.", threshold),
+                });
+            }
+        }
+        info!(?threshold, "Setze Schwelle für automatischen Energiesparmodus.");
+        *self.power_saver_threshold.lock().unwrap() = threshold;
+        Ok(())
+    }
+
+    /// Das aktuell aktive Energieprofil.
+    pub async fn active_profile(&self) -> DomainResult<PowerProfile> {
+        self.power_repository.active_profile().await
+    }
+
+    /// Die Profile, die die Hardware unterstützt.
+    pub async fn available_profiles(&self) -> DomainResult<Vec<PowerProfile>> {
+        self.power_repository.available_profiles().await
+    }
+
+    /// Der aktuelle Akkuzustand, oder `None`, wenn das Gerät keinen Akku hat.
+    pub async fn battery_state(&self) -> DomainResult<Option<BatteryState>> {
+        self.power_repository.battery_state().await
+    }
+
+    /// Aktiviert `profile` auf Wunsch des Benutzers.
+    ///
+    /// Wurde zuvor wegen niedrigen Akkustands automatisch umgeschaltet, wird das vorherige
+    /// Profil beim Laden nicht mehr wiederhergestellt, und bis dahin wird nicht erneut
+    /// automatisch umgeschaltet.
+    ///
+    /// # Fehler
+    /// `DomainError::OperationNotPermitted`, wenn die Hardware das Profil nicht unterstützt.
+    pub async fn set_profile(&self, profile: PowerProfile) -> DomainResult<()> {
+        info!(profile = profile.id(), "Energieprofil vom Benutzer gewählt.");
+        if !self.power_repository.available_profiles().await?.contains(&profile) {
+            return Err(DomainError::OperationNotPermitted {
+                operation: "set_power_profile".to_string(),
+                reason: format!("Das Energieprofil '{}' wird von dieser Hardware nicht unterstützt.", profile.id()),
+            });
+        }
+        {
+            let mut auto_switch = self.auto_switch.lock().unwrap();
+            if auto_switch.restore_profile.take().is_some() {
+                auto_switch.user_override = true;
+            }
+        }
+        self.switch_to(profile, false).await
+    }
+
+    /// Liest den Akkuzustand und wendet die Regeln für den automatischen Wechsel an
+    /// (siehe [`apply_battery_state`](Self::apply_battery_state)).
+    pub async fn update_from_battery(&self) -> DomainResult<Option<PowerProfile>> {
+        match self.power_repository.battery_state().await? {
+            Some(battery) => self.apply_battery_state(&battery).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Wendet die Regeln für den automatischen Wechsel auf den Akkuzustand `battery` an.
+    ///
+    /// Im Akkubetrieb unter der Schwelle wird auf [`PowerProfile::PowerSaver`] gewechselt
+    /// (sofern verfügbar und vom Benutzer nicht übersteuert); wird der Akku wieder geladen,
+    /// wird das Profil von vor dem Wechsel wiederhergestellt.
+    ///
+    /// # Rückgabe
+    /// Das Profil, auf das gewechselt wurde, oder `None`, wenn das Profil gleich blieb.
+    pub async fn apply_battery_state(&self, battery: &BatteryState) -> DomainResult<Option<PowerProfile>> {
+        if !battery.is_discharging() {
+            let restore_profile = {
+                let mut auto_switch = self.auto_switch.lock().unwrap();
+                auto_switch.user_override = false;
+                auto_switch.restore_profile.take()
+            };
+            let Some(profile) = restore_profile else {
+                return Ok(None);
+            };
+            info!(profile = profile.id(), "Akku wird geladen, stelle vorheriges Energieprofil wieder her.");
+            self.switch_to(profile, true).await?;
+            return Ok(Some(profile));
+        }
+
+        let below_threshold = self.power_saver_threshold().is_some_and(|threshold| battery.percentage < threshold);
+        {
+            let auto_switch = self.auto_switch.lock().unwrap();
+            if !below_threshold || auto_switch.user_override || auto_switch.restore_profile.is_some() {
+                return Ok(None);
+            }
+        }
+        let active = self.power_repository.active_profile().await?;
+        if active == PowerProfile::PowerSaver
+            || !self.power_repository.available_profiles().await?.contains(&PowerProfile::PowerSaver)
+        {
+            return Ok(None);
+        }
+        info!(percentage = battery.percentage, previous = active.id(), "Niedriger Akkustand, wechsle in den Energiesparmodus.");
+        self.auto_switch.lock().unwrap().restore_profile = Some(active);
+        self.switch_to(PowerProfile::PowerSaver, true).await?;
+        Ok(Some(PowerProfile::PowerSaver))
+    }
+
+    async fn switch_to(&self, profile: PowerProfile, automatic: bool) -> DomainResult<()> {
+        if self.power_repository.active_profile().await? == profile {
+            return Ok(());
+        }
+        self.power_repository.set_active_profile(profile).await?;
+        self.publish(DomainEvent::PowerProfileChanged { profile, automatic });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::power::ChargingState;
+    use crate::events::EventBus;
+    use crate::repositories::power_repository::MockPowerRepository;
+
+    fn service(available: Vec<PowerProfile>) -> (PowerService, Arc<Mutex<PowerProfile>>) {
+        let active = Arc::new(Mutex::new(PowerProfile::Balanced));
+        let mut mock_repo = MockPowerRepository::new();
+        let current = active.clone();
+        mock_repo.expect_active_profile().returning(move || Ok(*current.lock().unwrap()));
+        mock_repo.expect_available_profiles().returning(move || Ok(available.clone()));
+        let current = active.clone();
+        mock_repo.expect_set_active_profile().returning(move |profile| {
+            *current.lock().unwrap() = profile;
+            Ok(())
+        });
+        (PowerService::new(Arc::new(mock_repo)), active)
+    }
+
+    #[tokio::test]
+    async fn test_low_battery_switches_to_power_saver_and_back() {
+        let (service, active) = service(PowerProfile::ALL.to_vec());
+        let bus = Arc::new(EventBus::new());
+        let events = bus.subscribe();
+        let service = service.with_event_publisher(bus);
+        service.set_profile(PowerProfile::Performance).await.unwrap();
+
+        let discharging = |percentage| BatteryState::new(percentage, ChargingState::Discharging);
+        assert_eq!(service.apply_battery_state(&discharging(35.0)).await.unwrap(), None);
+        assert_eq!(service.apply_battery_state(&discharging(19.0)).await.unwrap(), Some(PowerProfile::PowerSaver));
+        assert_eq!(service.apply_battery_state(&discharging(18.0)).await.unwrap(), None);
+        assert_eq!(*active.lock().unwrap(), PowerProfile::PowerSaver);
+
+        let charging = BatteryState::new(18.5, ChargingState::Charging);
+        assert_eq!(service.apply_battery_state(&charging).await.unwrap(), Some(PowerProfile::Performance));
+        assert_eq!(*active.lock().unwrap(), PowerProfile::Performance);
+        assert_eq!(
+            events.try_iter().collect::<Vec<_>>(),
+            vec![
+                DomainEvent::PowerProfileChanged { profile: PowerProfile::Performance, automatic: false },
+                DomainEvent::PowerProfileChanged { profile: PowerProfile::PowerSaver, automatic: true },
+                DomainEvent::PowerProfileChanged { profile: PowerProfile::Performance, automatic: true },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_user_choice_overrides_automatic_switch_until_charging() {
+        let (service, active) = service(PowerProfile::ALL.to_vec());
+        let low = BatteryState::new(10.0, ChargingState::Discharging);
+        service.apply_battery_state(&low).await.unwrap();
+        service.set_profile(PowerProfile::Balanced).await.unwrap();
+        assert_eq!(service.apply_battery_state(&low).await.unwrap(), None);
+        assert_eq!(*active.lock().unwrap(), PowerProfile::Balanced);
+
+        // Nach dem Laden gilt die Regel wieder; die Wahl des Benutzers wird nicht überschrieben.
+        assert_eq!(service.apply_battery_state(&BatteryState::new(12.0, ChargingState::Charging)).await.unwrap(), None);
+        assert_eq!(service.apply_battery_state(&low).await.unwrap(), Some(PowerProfile::PowerSaver));
+
+        service.set_power_saver_threshold(None).unwrap();
+        assert!(service.set_power_saver_threshold(Some(120.0)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_profile_is_rejected() {
+        let (service, active) = service(vec![PowerProfile::PowerSaver, PowerProfile::Balanced]);
+        let result = service.set_profile(PowerProfile::Performance).await;
+        assert!(matches!(result, Err(DomainError::OperationNotPermitted { .. })));
+        assert_eq!(*active.lock().unwrap(), PowerProfile::Balanced);
+    }
+}
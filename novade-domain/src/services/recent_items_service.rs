@@ -0,0 +1,209 @@
+//! Domänendienst für zuletzt verwendete Dateien und Dokumente.
+//!
+//! Der [`RecentItemsService`] zeichnet geöffnete Elemente auf, liefert sie gefiltert nach
+//! Anwendung oder MIME-Typ (z.B. für die "Zuletzt verwendet"-Ergebnisse des Launchers) und
+//! beachtet dabei [`RecentItemExclusion`]s, damit private Elemente nie gespeichert werden.
+
+use crate::entities::mime_association::mime_type_matches;
+use crate::entities::recent_item::{RecentItem, RecentItemExclusion};
+use crate::repositories::recent_item_repository::RecentItemRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::info;
+use novade_core::types::Timestamp;
+use std::cmp::Reverse;
+use std::sync::{Arc, Mutex};
+
+/// Filter für [`RecentItemsService::list`] und [`RecentItemsService::clear`]. Nicht gesetzte
+/// Felder schränken nicht ein.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecentItemFilter {
+    /// Nur Elemente, die mit dieser Anwendung geöffnet wurden.
+    pub application: Option<String>,
+    /// Nur Elemente dieses MIME-Typs; Platzhalter wie "image/*" sind erlaubt.
+    pub mime_type: Option<String>,
+}
+
+impl RecentItemFilter {
+    fn matches(&self, item: &RecentItem) -> bool {
+        self.application.as_ref().is_none_or(|application| item.application.as_ref() == Some(application))
+            && self.mime_type.as_ref().is_none_or(|mime_type| mime_type_matches(mime_type, &item.mime_type))
+    }
+}
+
+pub struct RecentItemsService {
+    recent_item_repository: Arc<dyn RecentItemRepository>,
+    exclusions: Mutex<Vec<RecentItemExclusion>>,
+}
+
+impl RecentItemsService {
+    pub fn new(recent_item_repository: Arc<dyn RecentItemRepository>) -> Self {
+        Self { recent_item_repository, exclusions: Mutex::new(Vec::new()) }
+    }
+
+    /// Die aktuellen Datenschutz-Ausnahmen.
+    pub fn exclusions(&self) -> Vec<RecentItemExclusion> {
+        self.exclusions.lock().unwrap().clone()
+    }
+
+    fn is_excluded(&self, item: &RecentItem) -> bool {
+        self.exclusions.lock().unwrap().iter().any(|exclusion| exclusion.matches(item))
+    }
+
+    /// Fügt eine Datenschutz-Ausnahme hinzu und entfernt bereits gespeicherte Elemente, die
+    /// darunter fallen.
+    ///
+    /// # Rückgabe
+    /// Die Anzahl der entfernten Elemente.
+    pub async fn add_exclusion(&self, exclusion: RecentItemExclusion) -> DomainResult<usize> {
+        info!(?exclusion, "Füge Datenschutz-Ausnahme für zuletzt verwendete Elemente hinzu.");
+        {
+            let mut exclusions = self.exclusions.lock().unwrap();
+            if !exclusions.contains(&exclusion) {
+                exclusions.push(exclusion.clone());
+            }
+        }
+        let mut removed = 0;
+        for item in self.recent_item_repository.get_all().await? {
+            if exclusion.matches(&item) {
+                self.recent_item_repository.remove(&item.uri).await?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Entfernt eine Datenschutz-Ausnahme. Bereits entfernte Elemente bleiben entfernt.
+    pub fn remove_exclusion(&self, exclusion: &RecentItemExclusion) {
+        self.exclusions.lock().unwrap().retain(|existing| existing != exclusion);
+    }
+
+    /// Zeichnet einen Zugriff auf ein Element auf.
+    ///
+    /// Ein bereits bekanntes Element erhält einen neuen letzten Zugriff, die zuletzt verwendete
+    /// Anwendung und den angegebenen MIME-Typ.
+    ///
+    /// # Rückgabe
+    /// Das gespeicherte Element, `None`, wenn es unter eine Datenschutz-Ausnahme fällt, oder
+    /// `DomainError::ValidationError` bei leerer URI.
+    pub async fn record(&self, uri: &str, mime_type: &str, application: Option<&str>) -> DomainResult<Option<RecentItem>> {
+        if uri.trim().is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "uri".to_string(),
+                message: "Die URI darf nicht leer sein.".to_string(),
+            });
+        }
+        let now = Timestamp::now();
+        let item = match self.recent_item_repository.get_by_uri(uri).await? {
+            Some(mut item) => {
+                item.mime_type = mime_type.to_string();
+                item.application = application.map(str::to_string);
+                item.last_accessed = now;
+                item.access_count = item.access_count.saturating_add(1);
+                item
+            }
+            None => RecentItem::new(uri, mime_type, application, now),
+        };
+        if self.is_excluded(&item) {
+            return Ok(None);
+        }
+        self.recent_item_repository.save(&item).await?;
+        Ok(Some(item))
+    }
+
+    /// Die Elemente, die zum Filter passen, zuletzt verwendete zuerst.
+    ///
+    /// # Parameter
+    /// * `limit`: Die maximale Anzahl zurückgegebener Elemente; `None` für alle.
+    pub async fn list(&self, filter: &RecentItemFilter, limit: Option<usize>) -> DomainResult<Vec<RecentItem>> {
+        let mut items: Vec<RecentItem> = self
+            .recent_item_repository
+            .get_all()
+            .await?
+            .into_iter()
+            .filter(|item| filter.matches(item) && !self.is_excluded(item))
+            .collect();
+        items.sort_by_key(|item| Reverse(item.last_accessed.clone()));
+        items.truncate(limit.unwrap_or(usize::MAX));
+        Ok(items)
+    }
+
+    /// Entfernt alle Elemente, die zum Filter passen; mit dem Standardfilter alle Elemente.
+    ///
+    /// # Rückgabe
+    /// Die Anzahl der entfernten Elemente.
+    pub async fn clear(&self, filter: &RecentItemFilter) -> DomainResult<usize> {
+        info!(?filter, "Entferne zuletzt verwendete Elemente.");
+        let mut removed = 0;
+        for item in self.recent_item_repository.get_all().await? {
+            if filter.matches(&item) {
+                self.recent_item_repository.remove(&item.uri).await?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::recent_item_repository::MockRecentItemRepository;
+    use std::collections::HashMap;
+
+    /// Ein Repository, das gespeicherte Elemente tatsächlich hält.
+    fn repository() -> (MockRecentItemRepository, Arc<Mutex<HashMap<String, RecentItem>>>) {
+        let items: Arc<Mutex<HashMap<String, RecentItem>>> = Arc::default();
+        let mut mock_repo = MockRecentItemRepository::new();
+        let stored = items.clone();
+        mock_repo.expect_get_by_uri().returning(move |uri| Ok(stored.lock().unwrap().get(uri).cloned()));
+        let stored = items.clone();
+        mock_repo.expect_get_all().returning(move || Ok(stored.lock().unwrap().values().cloned().collect()));
+        let stored = items.clone();
+        mock_repo.expect_save().returning(move |item| {
+            stored.lock().unwrap().insert(item.uri.clone(), item.clone());
+            Ok(())
+        });
+        let stored = items.clone();
+        mock_repo.expect_remove().returning(move |uri| {
+            stored.lock().unwrap().remove(uri);
+            Ok(())
+        });
+        (mock_repo, items)
+    }
+
+    #[tokio::test]
+    async fn test_record_and_filter() {
+        let (mock_repo, _) = repository();
+        let service = RecentItemsService::new(Arc::new(mock_repo));
+        service.record("file:///a.odt", "application/vnd.oasis.opendocument.text", Some("writer")).await.unwrap();
+        service.record("file:///b.png", "image/png", Some("gimp")).await.unwrap();
+        let again = service.record("file:///a.odt", "application/vnd.oasis.opendocument.text", Some("writer")).await.unwrap();
+        assert_eq!(again.unwrap().access_count, 2);
+
+        assert_eq!(service.list(&RecentItemFilter::default(), None).await.unwrap()[0].uri, "file:///a.odt");
+        let images = RecentItemFilter { mime_type: Some("image/*".to_string()), ..RecentItemFilter::default() };
+        assert_eq!(service.list(&images, None).await.unwrap().len(), 1);
+        let writer = RecentItemFilter { application: Some("writer".to_string()), ..RecentItemFilter::default() };
+        assert_eq!(service.clear(&writer).await.unwrap(), 1);
+        assert_eq!(service.list(&RecentItemFilter::default(), Some(5)).await.unwrap().len(), 1);
+        assert!(matches!(service.record(" ", "text/plain", None).await, Err(DomainError::ValidationError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_exclusions_prevent_recording_and_purge_items() {
+        let (mock_repo, items) = repository();
+        let service = RecentItemsService::new(Arc::new(mock_repo));
+        service.record("file:///home/nutzer/Privat/tagebuch.txt", "text/plain", Some("editor")).await.unwrap();
+        service.record("file:///home/nutzer/notizen.txt", "text/plain", Some("editor")).await.unwrap();
+
+        let private = RecentItemExclusion::UriPrefix("file:///home/nutzer/Privat/".to_string());
+        assert_eq!(service.add_exclusion(private.clone()).await.unwrap(), 1);
+        assert!(service.record("file:///home/nutzer/Privat/brief.txt", "text/plain", None).await.unwrap().is_none());
+        assert_eq!(items.lock().unwrap().len(), 1);
+
+        service.add_exclusion(RecentItemExclusion::Application("editor".to_string())).await.unwrap();
+        assert!(items.lock().unwrap().is_empty());
+        service.remove_exclusion(&private);
+        assert_eq!(service.exclusions(), vec![RecentItemExclusion::Application("editor".to_string())]);
+    }
+}
@@ -0,0 +1,417 @@
+//! Domänendienst für die globale Suche.
+//!
+//! Der [`SearchService`] durchsucht Anwendungen, Workspaces, Einstellungen und zuletzt
+//! verwendete Elemente mit einer einzigen Anfrage und liefert ein gemeinsam bewertetes
+//! Ergebnis, z.B. für die Eingabezeile des Launchers. Die Quellen werden über die
+//! `with_*`-Methoden angeschlossen; fehlende Quellen werden nicht durchsucht.
+//!
+//! Bewertet wird nach der Art der Übereinstimmung ([`EXACT_MATCH_SCORE`] bis
+//! [`FUZZY_MATCH_SCORE`]); Übereinstimmungen in Nebenfeldern wie Schlüsselwörtern zählen
+//! weniger als im Namen. Jedes Ergebnis beschreibt mit [`SearchHighlight`], welche Teile
+//! welches Felds gefunden wurden, damit die Oberfläche sie hervorheben kann.
+
+use crate::entities::application::Application;
+use crate::entities::recent_item::RecentItem;
+use crate::entities::user_preference::UserPreferenceSetting;
+use crate::entities::workspace::Workspace;
+use crate::services::application_service::ApplicationService;
+use crate::services::recent_items_service::{RecentItemFilter, RecentItemsService};
+use crate::services::user_preference_service::UserPreferenceService;
+use crate::services::workspace_service::WorkspaceService;
+use crate::{DomainError, DomainResult};
+use novade_core::{info, warn};
+use std::cmp::Reverse;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Bewertung, wenn das Feld genau der Anfrage entspricht.
+pub const EXACT_MATCH_SCORE: u32 = 100;
+/// Bewertung, wenn das Feld mit der Anfrage beginnt.
+pub const PREFIX_MATCH_SCORE: u32 = 80;
+/// Bewertung, wenn ein Wort im Feld mit der Anfrage beginnt.
+pub const WORD_MATCH_SCORE: u32 = 60;
+/// Bewertung, wenn die Anfrage irgendwo im Feld vorkommt.
+pub const SUBSTRING_MATCH_SCORE: u32 = 40;
+/// Bewertung, wenn die Zeichen der Anfrage in dieser Reihenfolge, aber nicht zusammenhängend
+/// im Feld vorkommen. Wird nur für Hauptfelder geprüft.
+pub const FUZZY_MATCH_SCORE: u32 = 20;
+
+/// Abzug für Übereinstimmungen in Nebenfeldern (Schlüsselwörter, Beschreibungen, URIs).
+const SECONDARY_FIELD_PENALTY: u32 = 15;
+
+/// Die Art eines Suchergebnisses; bei gleicher Bewertung werden die Arten in dieser
+/// Reihenfolge angezeigt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SearchResultKind {
+    Application,
+    Workspace,
+    Preference,
+    RecentItem,
+}
+
+/// Die gefundene Entität eines Suchergebnisses.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchTarget {
+    Application(Box<Application>),
+    Workspace(Workspace),
+    Preference(UserPreferenceSetting),
+    RecentItem(RecentItem),
+}
+
+impl SearchTarget {
+    /// Die Art der Entität.
+    pub fn kind(&self) -> SearchResultKind {
+        match self {
+            SearchTarget::Application(_) => SearchResultKind::Application,
+            SearchTarget::Workspace(_) => SearchResultKind::Workspace,
+            SearchTarget::Preference(_) => SearchResultKind::Preference,
+            SearchTarget::RecentItem(_) => SearchResultKind::RecentItem,
+        }
+    }
+}
+
+/// Beschreibt, wo die Anfrage in einem Ergebnis gefunden wurde.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHighlight {
+    /// Das Feld mit der besten Übereinstimmung (z.B. "name" oder "keywords").
+    pub field: String,
+    /// Der Text dieses Felds.
+    pub text: String,
+    /// Die gefundenen Teile von `text` als Byte-Bereiche, aufsteigend und ohne Überlappung.
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Ein Ergebnis von [`SearchService::search`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    /// Die gefundene Entität.
+    pub target: SearchTarget,
+    /// Der anzuzeigende Titel, z.B. der Anzeigename einer Anwendung.
+    pub title: String,
+    /// Eine optionale zweite Zeile, z.B. die Beschreibung einer Anwendung oder die URI eines
+    /// zuletzt verwendeten Elements.
+    pub subtitle: Option<String>,
+    /// Die Bewertung; höher ist besser.
+    pub score: u32,
+    /// Die Übereinstimmung, die zur Bewertung geführt hat.
+    pub highlight: SearchHighlight,
+}
+
+impl SearchResult {
+    /// Die Art des Ergebnisses.
+    pub fn kind(&self) -> SearchResultKind {
+        self.target.kind()
+    }
+}
+
+/// Ein durchsuchbares Feld einer Entität.
+struct SearchField<'a> {
+    name: &'static str,
+    text: &'a str,
+    primary: bool,
+}
+
+impl<'a> SearchField<'a> {
+    fn primary(name: &'static str, text: &'a str) -> Self {
+        Self { name, text, primary: true }
+    }
+
+    fn secondary(name: &'static str, text: &'a str) -> Self {
+        Self { name, text, primary: false }
+    }
+}
+
+/// Vergleicht ohne Beachtung der Groß- und Kleinschreibung.
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Sucht `query` (bereits mit [`fold`] umgewandelt) in `text`.
+///
+/// # Rückgabe
+/// Die Bewertung der besten Übereinstimmung mit den gefundenen Byte-Bereichen, oder `None`.
+fn match_text(query: &[char], text: &str, fuzzy: bool) -> Option<(u32, Vec<Range<usize>>)> {
+    let chars: Vec<(usize, char)> = text.char_indices().map(|(offset, c)| (offset, fold(c))).collect();
+    let offset_of = |index: usize| chars.get(index).map_or(text.len(), |(offset, _)| *offset);
+    let last_start = chars.len().checked_sub(query.len())?;
+    let best = (0..=last_start)
+        .filter(|&start| chars[start..start + query.len()].iter().map(|(_, c)| *c).eq(query.iter().copied()))
+        .map(|start| {
+            let score = match start {
+                0 if query.len() == chars.len() => EXACT_MATCH_SCORE,
+                0 => PREFIX_MATCH_SCORE,
+                _ if !chars[start - 1].1.is_alphanumeric() => WORD_MATCH_SCORE,
+                _ => SUBSTRING_MATCH_SCORE,
+            };
+            (score, start)
+        })
+        .max_by_key(|(score, start)| (*score, Reverse(*start)));
+    if let Some((score, start)) = best {
+        return Some((score, std::iter::once(offset_of(start)..offset_of(start + query.len())).collect()));
+    }
+    if !fuzzy {
+        return None;
+    }
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut remaining = query.iter().peekable();
+    for (index, (offset, c)) in chars.iter().enumerate() {
+        if remaining.peek() == Some(&c) {
+            remaining.next();
+            let end = offset_of(index + 1);
+            match ranges.last_mut() {
+                Some(range) if range.end == *offset => range.end = end,
+                _ => ranges.push(*offset..end),
+            }
+        }
+    }
+    remaining.peek().is_none().then_some((FUZZY_MATCH_SCORE, ranges))
+}
+
+/// Die beste Übereinstimmung über alle Felder; bei gleicher Bewertung gewinnt das erste Feld.
+fn best_match(query: &[char], fields: &[SearchField<'_>]) -> Option<(u32, SearchHighlight)> {
+    let mut best: Option<(u32, SearchHighlight)> = None;
+    for field in fields {
+        let Some((score, ranges)) = match_text(query, field.text, field.primary) else {
+            continue;
+        };
+        let score = if field.primary { score } else { score.saturating_sub(SECONDARY_FIELD_PENALTY) };
+        if best.as_ref().is_none_or(|(best_score, _)| score > *best_score) {
+            best = Some((score, SearchHighlight { field: field.name.to_string(), text: field.text.to_string(), ranges }));
+        }
+    }
+    best
+}
+
+/// Der Dateiname einer URI, d.h. ihr letzter nicht leerer Pfadabschnitt.
+fn file_name(uri: &str) -> &str {
+    uri.rsplit('/').find(|segment| !segment.is_empty()).unwrap_or(uri)
+}
+
+#[derive(Default)]
+pub struct SearchService {
+    applications: Option<Arc<ApplicationService>>,
+    workspaces: Option<Arc<WorkspaceService>>,
+    preferences: Option<Arc<UserPreferenceService>>,
+    recent_items: Option<Arc<RecentItemsService>>,
+}
+
+impl SearchService {
+    /// Erstellt einen Dienst ohne Quellen.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Durchsucht Namen, Anzeigenamen und Schlüsselwörter der nicht archivierten Anwendungen.
+    pub fn with_applications(mut self, applications: Arc<ApplicationService>) -> Self {
+        self.applications = Some(applications);
+        self
+    }
+
+    /// Durchsucht die Namen der nicht archivierten Workspaces.
+    pub fn with_workspaces(mut self, workspaces: Arc<WorkspaceService>) -> Self {
+        self.workspaces = Some(workspaces);
+        self
+    }
+
+    /// Durchsucht Anzeigenamen, Schlüssel und Beschreibungen der wirksamen Einstellungen des
+    /// aktuellen Benutzers.
+    pub fn with_preferences(mut self, preferences: Arc<UserPreferenceService>) -> Self {
+        self.preferences = Some(preferences);
+        self
+    }
+
+    /// Durchsucht Dateinamen und URIs der zuletzt verwendeten Elemente; Datenschutz-Ausnahmen
+    /// werden beachtet.
+    pub fn with_recent_items(mut self, recent_items: Arc<RecentItemsService>) -> Self {
+        self.recent_items = Some(recent_items);
+        self
+    }
+
+    /// Durchsucht alle angeschlossenen Quellen.
+    ///
+    /// Die Ergebnisse sind nach absteigender Bewertung sortiert, bei gleicher Bewertung nach
+    /// [`SearchResultKind`] und innerhalb einer Art in der Reihenfolge der Quelle (zuletzt
+    /// verwendete Elemente z.B. nach letztem Zugriff). Kann eine Quelle nicht gelesen werden,
+    /// wird das als Warnung geloggt und mit den übrigen Quellen weitergesucht.
+    ///
+    /// # Parameter
+    /// * `limit`: Die maximale Anzahl zurückgegebener Ergebnisse.
+    ///
+    /// # Fehler
+    /// `DomainError::ValidationError`, wenn die Anfrage leer ist.
+    pub async fn search(&self, query: &str, limit: usize) -> DomainResult<Vec<SearchResult>> {
+        self.search_kinds(query, &[], limit).await
+    }
+
+    /// Wie [`search`](Self::search), aber nur über Ergebnisse der Arten `kinds`; eine leere
+    /// Liste schränkt nicht ein.
+    pub async fn search_kinds(&self, query: &str, kinds: &[SearchResultKind], limit: usize) -> DomainResult<Vec<SearchResult>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "query".to_string(),
+                message: "Die Suchanfrage darf nicht leer sein.".to_string(),
+            });
+        }
+        info!(query, ?kinds, limit, "Globale Suche angefordert.");
+        let query: Vec<char> = query.chars().map(fold).collect();
+        let wanted = |kind: SearchResultKind| kinds.is_empty() || kinds.contains(&kind);
+
+        let mut results = Vec::new();
+        if let Some(applications) = self.applications.as_ref().filter(|_| wanted(SearchResultKind::Application)) {
+            match applications.list_all_applications().await {
+                Ok(apps) => results.extend(apps.into_iter().filter_map(|app| Self::match_application(&query, app))),
+                Err(error) => warn!(%error, "Anwendungen konnten nicht durchsucht werden."),
+            }
+        }
+        if let Some(workspaces) = self.workspaces.as_ref().filter(|_| wanted(SearchResultKind::Workspace)) {
+            match workspaces.list_all_workspaces().await {
+                Ok(workspaces) => results.extend(workspaces.into_iter().filter_map(|ws| Self::match_workspace(&query, ws))),
+                Err(error) => warn!(%error, "Workspaces konnten nicht durchsucht werden."),
+            }
+        }
+        if let Some(preferences) = self.preferences.as_ref().filter(|_| wanted(SearchResultKind::Preference)) {
+            match preferences.list_all_preferences().await {
+                Ok(settings) => results.extend(settings.into_iter().filter_map(|setting| Self::match_preference(&query, setting))),
+                Err(error) => warn!(%error, "Einstellungen konnten nicht durchsucht werden."),
+            }
+        }
+        if let Some(recent_items) = self.recent_items.as_ref().filter(|_| wanted(SearchResultKind::RecentItem)) {
+            match recent_items.list(&RecentItemFilter::default(), None).await {
+                Ok(items) => results.extend(items.into_iter().filter_map(|item| Self::match_recent_item(&query, item))),
+                Err(error) => warn!(%error, "Zuletzt verwendete Elemente konnten nicht durchsucht werden."),
+            }
+        }
+        results.sort_by_key(|result| (Reverse(result.score), result.kind()));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    fn match_application(query: &[char], app: Application) -> Option<SearchResult> {
+        let mut fields = Vec::new();
+        if let Some(display_name) = &app.display_name {
+            fields.push(SearchField::primary("display_name", display_name));
+        }
+        fields.push(SearchField::primary("name", &app.name));
+        fields.extend(app.keywords.iter().flatten().map(|keyword| SearchField::secondary("keywords", keyword)));
+        let (score, highlight) = best_match(query, &fields)?;
+        Some(SearchResult {
+            title: app.display_name.clone().unwrap_or_else(|| app.name.clone()),
+            subtitle: app.description.clone(),
+            score,
+            highlight,
+            target: SearchTarget::Application(Box::new(app)),
+        })
+    }
+
+    fn match_workspace(query: &[char], workspace: Workspace) -> Option<SearchResult> {
+        let (score, highlight) = best_match(query, &[SearchField::primary("name", &workspace.name)])?;
+        Some(SearchResult {
+            title: workspace.name.clone(),
+            subtitle: None,
+            score,
+            highlight,
+            target: SearchTarget::Workspace(workspace),
+        })
+    }
+
+    fn match_preference(query: &[char], setting: UserPreferenceSetting) -> Option<SearchResult> {
+        let mut fields = vec![SearchField::primary("display_name", &setting.display_name), SearchField::secondary("key", &setting.key)];
+        if let Some(description) = &setting.description {
+            fields.push(SearchField::secondary("description", description));
+        }
+        let (score, highlight) = best_match(query, &fields)?;
+        Some(SearchResult {
+            title: setting.display_name.clone(),
+            subtitle: setting.description.clone(),
+            score,
+            highlight,
+            target: SearchTarget::Preference(setting),
+        })
+    }
+
+    fn match_recent_item(query: &[char], item: RecentItem) -> Option<SearchResult> {
+        let name = file_name(&item.uri);
+        let (score, highlight) = best_match(query, &[SearchField::primary("name", name), SearchField::secondary("uri", &item.uri)])?;
+        Some(SearchResult {
+            title: name.to_string(),
+            subtitle: Some(item.uri.clone()),
+            score,
+            highlight,
+            target: SearchTarget::RecentItem(item),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::application_repository::MockApplicationRepository;
+    use crate::repositories::recent_item_repository::MockRecentItemRepository;
+    use crate::repositories::user_preference_repository::MockUserPreferenceRepository;
+    use crate::repositories::workspace_repository::MockWorkspaceRepository;
+    use novade_core::types::Timestamp;
+
+    fn chars(query: &str) -> Vec<char> {
+        query.chars().map(fold).collect()
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_match_text_ranks_match_kinds() {
+        assert_eq!(match_text(&chars("firefox"), "Firefox", true), Some((EXACT_MATCH_SCORE, vec![0..7])));
+        assert_eq!(match_text(&chars("fire"), "Firefox", true), Some((PREFIX_MATCH_SCORE, vec![0..4])));
+        assert_eq!(match_text(&chars("fox"), "Firefox Web-Browser", true), Some((SUBSTRING_MATCH_SCORE, vec![4..7])));
+        assert_eq!(match_text(&chars("brow"), "Firefox Web-Browser", true), Some((WORD_MATCH_SCORE, vec![12..16])));
+        assert_eq!(match_text(&chars("ffx"), "Firefox", true), Some((FUZZY_MATCH_SCORE, vec![0..1, 4..5, 6..7])));
+        assert_eq!(match_text(&chars("ffx"), "Firefox", false), None);
+        // Bereiche sind Byte-Bereiche im Originaltext.
+        assert_eq!(match_text(&chars("über"), "Grüße über alles", true), Some((WORD_MATCH_SCORE, vec![8..13])));
+    }
+
+    #[tokio::test]
+    async fn test_search_across_sources() {
+        let mut firefox = Application::new_desktop("firefox".to_string(), "/usr/bin/firefox".to_string(), None);
+        firefox.display_name = Some("Firefox".to_string());
+        let mut editor = Application::new_desktop("gedit".to_string(), "/usr/bin/gedit".to_string(), None);
+        editor.keywords = Some(vec!["Text".to_string(), "Firefox-Erweiterungen".to_string()]);
+        let mut app_repo = MockApplicationRepository::new();
+        app_repo.expect_get_all().returning(move || Ok(vec![firefox.clone(), editor.clone()]));
+
+        let mut workspace_repo = MockWorkspaceRepository::new();
+        let workspace = Workspace::new("Firma".to_string(), None);
+        workspace_repo.expect_get_all().returning(move || Ok(vec![workspace.clone()]));
+
+        let mut preference_repo = MockUserPreferenceRepository::new();
+        preference_repo.expect_get_preference().returning(|_, _| Ok(None));
+        preference_repo
+            .expect_get_all_preferences()
+            .returning(|_| Ok(vec![UserPreferenceSetting::new_boolean("browser.fire_warnings", "Warnungen anzeigen", true)]));
+
+        let mut recent_repo = MockRecentItemRepository::new();
+        let item = RecentItem::new("file:///home/nutzer/Firewall.txt", "text/plain", None, Timestamp::now());
+        recent_repo.expect_get_all().returning(move || Ok(vec![item.clone()]));
+
+        let service = SearchService::new()
+            .with_applications(Arc::new(ApplicationService::new(Arc::new(app_repo))))
+            .with_workspaces(Arc::new(WorkspaceService::new(Arc::new(workspace_repo))))
+            .with_preferences(Arc::new(UserPreferenceService::new(Arc::new(preference_repo))))
+            .with_recent_items(Arc::new(RecentItemsService::new(Arc::new(recent_repo))));
+
+        let results = service.search("FIRE", 10).await.unwrap();
+        let titles: Vec<&str> = results.iter().map(|result| result.title.as_str()).collect();
+        assert_eq!(titles, vec!["Firefox", "Firewall.txt", "gedit", "Warnungen anzeigen"]);
+        assert_eq!(results[0].score, PREFIX_MATCH_SCORE);
+        assert_eq!(results[0].highlight.field, "display_name");
+        assert_eq!(results[2].kind(), SearchResultKind::Application);
+        assert_eq!(results[2].highlight.field, "keywords");
+        assert_eq!(results[2].score, PREFIX_MATCH_SCORE - SECONDARY_FIELD_PENALTY);
+        assert_eq!(results[3].highlight.field, "key");
+        assert_eq!(&results[3].highlight.text[results[3].highlight.ranges[0].clone()], "fire");
+
+        let workspaces = service.search_kinds("firm", &[SearchResultKind::Workspace], 10).await.unwrap();
+        assert!(matches!(&workspaces[..], [SearchResult { target: SearchTarget::Workspace(ws), .. }] if ws.name == "Firma"));
+        assert_eq!(service.search("fire", 1).await.unwrap().len(), 1);
+        assert!(matches!(service.search("  ", 10).await, Err(DomainError::ValidationError { .. })));
+    }
+}
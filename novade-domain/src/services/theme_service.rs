@@ -0,0 +1,299 @@
+//! Domänendienst für die Verwaltung von Themes.
+//!
+//! Das aktive Theme wird über die Einstellung [`ACTIVE_THEME_KEY`] ausgewählt und damit wie
+//! jede andere Einstellung pro Benutzer und Profil gespeichert. Der [`ThemeService`] meldet
+//! jeden Wechsel des wirksamen Themes als [`ThemeChange`], damit die UI live neu zeichnen kann.
+
+use crate::entities::theme::{is_hex_color, Theme};
+use crate::repositories::theme_repository::ThemeRepository;
+use crate::services::user_preference_service::{PreferenceChange, UserPreferenceService};
+use crate::{DomainError, DomainResult};
+use novade_core::signal::Signal;
+use novade_core::{info, warn};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+
+/// Der Einstellungsschlüssel, unter dem der Name des aktiven Themes gespeichert wird.
+pub const ACTIVE_THEME_KEY: &str = "theme.active";
+
+/// Ein Wechsel des wirksamen Themes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeChange {
+    /// Der Name des zuvor wirksamen Themes.
+    pub previous: String,
+    /// Das nun wirksame Theme.
+    pub theme: Theme,
+}
+
+pub struct ThemeService {
+    theme_repository: Arc<dyn ThemeRepository>,
+    preferences: Arc<UserPreferenceService>,
+    preference_changes: Mutex<Receiver<PreferenceChange>>,
+    effective_theme: Mutex<Option<String>>,
+    changes: Signal<ThemeChange>,
+}
+
+impl ThemeService {
+    pub fn new(theme_repository: Arc<dyn ThemeRepository>, preferences: Arc<UserPreferenceService>) -> Self {
+        let preference_changes = Mutex::new(preferences.subscribe(ACTIVE_THEME_KEY));
+        Self {
+            theme_repository,
+            preferences,
+            preference_changes,
+            effective_theme: Mutex::new(None),
+            changes: Signal::new(),
+        }
+    }
+
+    /// Abonniert Wechsel des wirksamen Themes.
+    ///
+    /// Das Abonnement endet, sobald der `Receiver` verworfen wird.
+    pub fn subscribe(&self) -> Receiver<ThemeChange> {
+        self.changes.subscribe()
+    }
+
+    /// Prüft ein Theme auf einen nicht leeren Namen, gültige Farben und gültige Schrifteinstellungen.
+    ///
+    /// # Rückgabe
+    /// `DomainError::ValidationError` mit dem betroffenen Feld.
+    pub fn validate_theme(theme: &Theme) -> DomainResult<()> {
+        let invalid = |field: &str, message: String| Err(DomainError::ValidationError { field: field.to_string(), message });
+
+        if theme.name.trim().is_empty() {
+            return invalid("name", "Der Name eines Themes darf nicht leer sein.".to_string());
+        }
+        if let Some((field, color)) = theme.palette.colors().into_iter().find(|(_, color)| !is_hex_color(color)) {
+            return invalid(
+                &format!("palette.{}", field),
+                format!("'{}' ist keine Farbe im Format #RRGGBB oder #RRGGBBAA.", color),
+            );
+        }
+        for (field, value) in [("icon_theme", &theme.icon_theme), ("cursor_theme", &theme.cursor_theme)] {
+            if value.trim().is_empty() {
+                return invalid(field, "Der Name darf nicht leer sein.".to_string());
+            }
+        }
+        if theme.fonts.family.trim().is_empty() || theme.fonts.monospace_family.trim().is_empty() {
+            return invalid("fonts", "Die Schriftfamilien dürfen nicht leer sein.".to_string());
+        }
+        if !(theme.fonts.size > 0.0 && theme.fonts.size <= 96.0) {
+            return invalid("fonts.size", format!("Schriftgröße {} liegt außerhalb von 0..=96 pt.", theme.fonts.size));
+        }
+        Ok(())
+    }
+
+    /// Fügt ein neues Theme hinzu.
+    ///
+    /// # Rückgabe
+    /// `DomainError::ValidationError` für ungültige Themes bzw. `DomainError::Conflict`, wenn
+    /// bereits ein Theme mit diesem Namen existiert.
+    pub async fn add_theme(&self, theme: Theme) -> DomainResult<()> {
+        Self::validate_theme(&theme)?;
+        if self.theme_repository.get_by_name(&theme.name).await?.is_some() {
+            return Err(DomainError::conflict::<Theme>("name", &theme.name));
+        }
+        info!(theme_name = %theme.name, "Füge Theme hinzu.");
+        self.theme_repository.add(&theme).await
+    }
+
+    /// Aktualisiert ein vorhandenes Theme. Ist es das wirksame Theme, wird ein [`ThemeChange`] gemeldet.
+    pub async fn update_theme(&self, theme: Theme) -> DomainResult<()> {
+        Self::validate_theme(&theme)?;
+        if self.theme_repository.get_by_name(&theme.name).await?.is_none() {
+            return Err(theme_not_found(&theme.name));
+        }
+        info!(theme_name = %theme.name, "Aktualisiere Theme.");
+        self.theme_repository.update(&theme).await?;
+        if self.active_theme().await?.name == theme.name {
+            self.changes.emit(ThemeChange { previous: theme.name.clone(), theme });
+        }
+        Ok(())
+    }
+
+    /// Entfernt ein Theme.
+    ///
+    /// # Rückgabe
+    /// `DomainError::OperationNotPermitted`, wenn das Theme gerade ausgewählt ist.
+    pub async fn remove_theme(&self, name: &str) -> DomainResult<()> {
+        if self.preferences.get_string(ACTIVE_THEME_KEY).await?.as_deref() == Some(name) {
+            return Err(DomainError::OperationNotPermitted {
+                operation: "remove_theme".to_string(),
+                reason: format!("Das Theme '{}' ist ausgewählt.", name),
+            });
+        }
+        info!(theme_name = %name, "Entferne Theme.");
+        self.theme_repository.remove(name).await
+    }
+
+    /// Alle bekannten Themes, nach Namen sortiert.
+    pub async fn list_themes(&self) -> DomainResult<Vec<Theme>> {
+        let mut themes = self.theme_repository.get_all().await?;
+        themes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(themes)
+    }
+
+    /// Das wirksame Theme.
+    ///
+    /// Ist über [`ACTIVE_THEME_KEY`] kein Theme ausgewählt oder existiert das ausgewählte
+    /// Theme nicht, wird das eingebaute Standard-Theme ([`Theme::default`]) verwendet.
+    pub async fn active_theme(&self) -> DomainResult<Theme> {
+        let Some(name) = self.preferences.get_string(ACTIVE_THEME_KEY).await? else {
+            return Ok(Theme::default());
+        };
+        match self.theme_repository.get_by_name(&name).await? {
+            Some(theme) => Ok(theme),
+            None => {
+                warn!(theme_name = %name, "Ausgewähltes Theme existiert nicht, verwende Standard-Theme.");
+                Ok(Theme::default())
+            }
+        }
+    }
+
+    /// Wählt ein Theme aus und meldet den Wechsel.
+    ///
+    /// # Rückgabe
+    /// Das nun wirksame Theme, oder `DomainError::EntityNotFound`, wenn es kein Theme mit diesem Namen gibt.
+    pub async fn set_active_theme(&self, name: &str) -> DomainResult<Theme> {
+        let theme = self.theme_repository.get_by_name(name).await?.ok_or_else(|| theme_not_found(name))?;
+        self.preferences.set_string(ACTIVE_THEME_KEY, name.to_string()).await?;
+        self.sync_active_theme().await?;
+        Ok(theme)
+    }
+
+    /// Übernimmt Änderungen der Einstellung [`ACTIVE_THEME_KEY`], die am Dienst vorbei erfolgt
+    /// sind (z.B. durch einen Profilwechsel oder einen Benutzerwechsel), und meldet einen
+    /// daraus folgenden Theme-Wechsel.
+    ///
+    /// # Rückgabe
+    /// Der gemeldete Wechsel, falls sich das wirksame Theme geändert hat.
+    pub async fn sync_active_theme(&self) -> DomainResult<Option<ThemeChange>> {
+        let changed = self.preference_changes.lock().unwrap().try_iter().count() > 0;
+        let previous = self.effective_theme.lock().unwrap().clone();
+        if !changed && previous.is_some() {
+            return Ok(None);
+        }
+        let theme = self.active_theme().await?;
+        let previous = self.effective_theme.lock().unwrap().replace(theme.name.clone()).unwrap_or_else(|| Theme::default().name);
+        if previous == theme.name {
+            return Ok(None);
+        }
+        info!(previous = %previous, theme_name = %theme.name, "Wirksames Theme gewechselt.");
+        let change = ThemeChange { previous, theme };
+        self.changes.emit(change.clone());
+        Ok(Some(change))
+    }
+}
+
+fn theme_not_found(name: &str) -> DomainError {
+    DomainError::not_found::<Theme>(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::user_preference::UserPreferenceSetting;
+    use crate::repositories::theme_repository::MockThemeRepository;
+    use crate::repositories::user_preference_repository::MockUserPreferenceRepository;
+    use std::collections::HashMap;
+
+    /// Ein Einstellungs-Repository, das gesetzte Werte tatsächlich speichert.
+    fn preferences() -> Arc<UserPreferenceService> {
+        let settings: Arc<Mutex<HashMap<String, UserPreferenceSetting>>> = Arc::default();
+        let mut mock_repo = MockUserPreferenceRepository::new();
+        let stored = settings.clone();
+        mock_repo.expect_get_preference().returning(move |_, key| Ok(stored.lock().unwrap().get(key).cloned()));
+        let stored = settings.clone();
+        mock_repo.expect_get_all_preferences().returning(move |_| Ok(stored.lock().unwrap().values().cloned().collect()));
+        mock_repo.expect_set_preference().returning(move |_, setting| {
+            settings.lock().unwrap().insert(setting.key.clone(), setting.clone());
+            Ok(())
+        });
+        Arc::new(UserPreferenceService::new(Arc::new(mock_repo)))
+    }
+
+    fn dark_theme() -> Theme {
+        let mut theme = Theme { name: "Nova Dunkel".to_string(), ..Theme::default() };
+        theme.palette.background = "#1E1E1E".to_string();
+        theme.palette.foreground = "#FFFFFFDE".to_string();
+        theme
+    }
+
+    fn repository_with(themes: Vec<Theme>) -> MockThemeRepository {
+        let mut mock_repo = MockThemeRepository::new();
+        mock_repo
+            .expect_get_by_name()
+            .returning(move |name| Ok(themes.iter().find(|theme| theme.name == name).cloned()));
+        mock_repo
+    }
+
+    #[test]
+    fn test_validate_theme() {
+        assert!(ThemeService::validate_theme(&dark_theme()).is_ok());
+
+        let mut theme = dark_theme();
+        theme.palette.accent = "blau".to_string();
+        assert!(matches!(
+            ThemeService::validate_theme(&theme),
+            Err(DomainError::ValidationError { field, .. }) if field == "palette.accent"
+        ));
+
+        let mut theme = dark_theme();
+        theme.fonts.size = 0.0;
+        assert!(matches!(
+            ThemeService::validate_theme(&theme),
+            Err(DomainError::ValidationError { field, .. }) if field == "fonts.size"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_active_theme_falls_back_to_default() {
+        let preferences = preferences();
+        let service = ThemeService::new(Arc::new(repository_with(vec![dark_theme()])), preferences.clone());
+        assert_eq!(service.active_theme().await.unwrap(), Theme::default());
+
+        preferences.set_string(ACTIVE_THEME_KEY, "Gelöscht".to_string()).await.unwrap();
+        assert_eq!(service.active_theme().await.unwrap(), Theme::default());
+
+        preferences.set_string(ACTIVE_THEME_KEY, "Nova Dunkel".to_string()).await.unwrap();
+        assert_eq!(service.active_theme().await.unwrap(), dark_theme());
+    }
+
+    #[tokio::test]
+    async fn test_theme_changes_are_reported() {
+        let preferences = preferences();
+        let service = ThemeService::new(Arc::new(repository_with(vec![dark_theme()])), preferences.clone());
+        let changes = service.subscribe();
+        assert!(service.sync_active_theme().await.unwrap().is_none(), "Standard-Theme ist bereits wirksam");
+
+        assert!(matches!(service.set_active_theme("Unbekannt").await, Err(DomainError::EntityNotFound { .. })));
+        service.set_active_theme("Nova Dunkel").await.unwrap();
+        assert_eq!(
+            changes.try_iter().collect::<Vec<_>>(),
+            vec![ThemeChange { previous: "Nova Hell".to_string(), theme: dark_theme() }]
+        );
+
+        // Eine Änderung der Einstellung am Dienst vorbei wird beim Synchronisieren übernommen.
+        preferences.set_string(ACTIVE_THEME_KEY, "Nova Hell".to_string()).await.unwrap();
+        let change = service.sync_active_theme().await.unwrap().unwrap();
+        assert_eq!(change.previous, "Nova Dunkel");
+        assert_eq!(change.theme, Theme::default());
+        assert_eq!(changes.try_iter().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_theme() {
+        let mut mock_repo = repository_with(vec![dark_theme()]);
+        mock_repo.expect_add().times(1).returning(|_| Ok(()));
+        mock_repo.expect_remove().never();
+
+        let preferences = preferences();
+        let service = ThemeService::new(Arc::new(mock_repo), preferences.clone());
+        assert!(matches!(service.add_theme(dark_theme()).await, Err(DomainError::Conflict { .. })));
+        let mut contrast = dark_theme();
+        contrast.name = "Kontrast".to_string();
+        service.add_theme(contrast).await.unwrap();
+
+        service.set_active_theme("Nova Dunkel").await.unwrap();
+        assert!(matches!(service.remove_theme("Nova Dunkel").await, Err(DomainError::OperationNotPermitted { .. })));
+    }
+}
@@ -0,0 +1,1379 @@
+//! Domänendienst für die Verwaltung von Benutzereinstellungen.
+
+use crate::entities::audit_record::AuditOperation;
+use crate::entities::preference_schema::PreferenceSchema;
+use crate::entities::user_preference::{is_valid_preference_key, PreferenceValue, UserPreferenceSetting};
+use crate::entities::Entity;
+use crate::validation::Validate;
+use crate::events::{DomainEvent, EventPublisher};
+use crate::services::audit_service::AuditService;
+use crate::services::history_service::{HistoryService, UndoableCommand, PREFERENCE_HISTORY};
+use async_trait::async_trait;
+use crate::repositories::user_preference_repository::UserPreferenceRepository;
+use crate::{DomainError, DomainResult};
+use novade_core::signal::Signal;
+use novade_core::types::NovaId;
+use novade_core::{info, warn}; // Logging
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Prüft, ob ein Einstellungsschlüssel der Konvention `bereich.unterbereich.einstellung` folgt.
+///
+/// Jedes durch `.` getrennte Segment muss nicht leer sein und darf nur aus
+/// Kleinbuchstaben, Ziffern, `_` und `-` bestehen.
+pub fn validate_preference_key(key: &str) -> DomainResult<()> {
+    if is_valid_preference_key(key) {
+        Ok(())
+    } else {
+        Err(DomainError::ValidationError {
+            field: "key".to_string(),
+            message: format!(
+                "Ungültiger Einstellungsschlüssel '{}': erwartet werden durch '.' getrennte Segmente aus [a-z0-9_-].",
+                key
+            ),
+        })
+    }
+}
+
+fn type_mismatch(key: &str, expected: &str, value: &PreferenceValue) -> DomainError {
+    DomainError::ValidationError {
+        field: key.to_string(),
+        message: format!("Einstellung ist vom Typ {:?}, erwartet wurde {}.", value, expected),
+    }
+}
+
+/// Eine Änderung einer Einstellung, wie sie an Abonnenten verschickt wird.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreferenceChange {
+    /// Der Schlüssel der geänderten Einstellung.
+    pub key: String,
+    /// Der bisherige Wert (`None`, wenn die Einstellung bisher keinen Wert hatte).
+    pub old_value: Option<PreferenceValue>,
+    /// Der neue Wert (`None`, wenn die Einstellung keinen Wert mehr hat).
+    pub new_value: Option<PreferenceValue>,
+}
+
+/// Prüft, ob `key` zu einem Abonnement-Muster passt.
+///
+/// Muster sind entweder ein exakter Schlüssel (`theme.dark_mode`), ein Präfix mit
+/// abschließendem `*` (`theme.*` passt auf alle Schlüssel unterhalb von `theme`) oder `*`
+/// für alle Schlüssel.
+pub fn key_matches_pattern(key: &str, pattern: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => key.starts_with(prefix),
+        None => key == pattern,
+    }
+}
+
+/// Wie [`UserPreferenceService::import`] mit vorhandenen Benutzerwerten umgeht.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Importierte Werte werden übernommen, alle anderen Benutzerwerte bleiben erhalten.
+    Merge,
+    /// Benutzerwerte, die nicht im Import enthalten sind, werden entfernt.
+    Replace,
+}
+
+/// Ergebnis eines (ggf. nur simulierten) Imports.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    /// Die Benutzerwerte, die sich ändern (bzw. bei einem Probelauf ändern würden).
+    /// `new_value` ist `None` für Werte, die im Modus [`ImportMode::Replace`] entfernt werden.
+    pub changes: Vec<PreferenceChange>,
+    /// Ob der Import nur simuliert wurde.
+    pub dry_run: bool,
+}
+
+/// Format der exportierten Einstellungen.
+#[derive(Debug, Serialize, Deserialize)]
+struct PreferenceExport {
+    /// Version des Formats.
+    version: u32,
+    /// Die Benutzerwerte.
+    preferences: Vec<UserPreferenceSetting>,
+}
+
+/// Aktuelle Version des Exportformats.
+const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Umwandlung eines Wertes bei einer Schlüsselmigration; `None` verwirft den Wert.
+pub type PreferenceValueTransform = Arc<dyn Fn(PreferenceValue) -> Option<PreferenceValue> + Send + Sync>;
+
+/// Eine Umbenennung eines Einstellungsschlüssels.
+///
+/// Gespeicherte Werte unter `old_key` (auch in Profilen) werden beim Ausführen der
+/// Migrationen unter `new_key` übernommen, statt stillschweigend auf den Standardwert
+/// zurückzufallen.
+#[derive(Clone)]
+pub struct PreferenceKeyMigration {
+    /// Der bisherige Schlüssel.
+    pub old_key: String,
+    /// Der neue Schlüssel.
+    pub new_key: String,
+    transform: Option<PreferenceValueTransform>,
+}
+
+impl PreferenceKeyMigration {
+    /// Erstellt eine Migration, die den Wert unverändert übernimmt.
+    pub fn new(old_key: &str, new_key: &str) -> Self {
+        Self { old_key: old_key.to_string(), new_key: new_key.to_string(), transform: None }
+    }
+
+    /// Wandelt den Wert bei der Übernahme um, z.B. wenn sich der Typ geändert hat.
+    pub fn with_transform(
+        mut self,
+        transform: impl Fn(PreferenceValue) -> Option<PreferenceValue> + Send + Sync + 'static,
+    ) -> Self {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    fn apply(&self, value: PreferenceValue) -> Option<PreferenceValue> {
+        match &self.transform {
+            Some(transform) => transform(value),
+            None => Some(value),
+        }
+    }
+}
+
+impl fmt::Debug for PreferenceKeyMigration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreferenceKeyMigration")
+            .field("old_key", &self.old_key)
+            .field("new_key", &self.new_key)
+            .field("transform", &self.transform.is_some())
+            .finish()
+    }
+}
+
+/// Schlüssel, unter dem der Name des aktiven Profils gespeichert wird.
+pub const ACTIVE_PROFILE_KEY: &str = "profiles.active";
+
+/// Namensraum der Profil-Daten im Repository. Einstellungen dürfen ihn nicht verwenden.
+const PROFILES_NAMESPACE: &str = "profiles";
+
+fn is_reserved_key(key: &str) -> bool {
+    key.split('.').next() == Some(PROFILES_NAMESPACE)
+}
+
+fn reject_reserved_key(key: &str) -> DomainResult<()> {
+    if is_reserved_key(key) {
+        return Err(DomainError::OperationNotPermitted {
+            operation: "access_preference".to_string(),
+            reason: format!("Der Namensraum '{}' ist für Profile reserviert.", PROFILES_NAMESPACE),
+        });
+    }
+    Ok(())
+}
+
+/// Präfix der Profilwerte eines Profils, z.B. "profiles.work.".
+fn profile_prefix(profile: &str) -> String {
+    format!("{}.{}.", PROFILES_NAMESPACE, profile)
+}
+
+fn validate_profile_name(name: &str) -> DomainResult<()> {
+    if name.contains('.') || name == "active" || validate_preference_key(name).is_err() {
+        return Err(DomainError::ValidationError {
+            field: "profile".to_string(),
+            message: format!("Ungültiger Profilname '{}'.", name),
+        });
+    }
+    Ok(())
+}
+
+/// Dienst für Benutzereinstellungen.
+///
+/// Gelesen und geschrieben werden die Einstellungen des mit [`select_user`](Self::select_user)
+/// gewählten Benutzers; systemweite Standardwerte gelten für alle Benutzer.
+///
+/// Einstellungen werden in vier Ebenen aufgelöst: Werte des aktiven Profils, Werte des
+/// Benutzers (im Repository), optionale systemweite Standardwerte (nur lesend) und die
+/// Standardwerte des Schemas. Schreibende Operationen wie [`set_value`](Self::set_value)
+/// betreffen die Benutzerebene; Profile werden mit [`save_profile`](Self::save_profile)
+/// gepflegt und mit [`switch_profile`](Self::switch_profile) aktiviert.
+pub struct UserPreferenceService {
+    preference_repository: Arc<dyn UserPreferenceRepository>,
+    system_defaults: Option<Arc<dyn UserPreferenceRepository>>,
+    current_user: Mutex<Option<NovaId>>,
+    schema: PreferenceSchema,
+    changes: Signal<PreferenceChange>,
+    events: Option<Arc<dyn EventPublisher>>,
+    history: Option<Arc<HistoryService>>,
+    audit: Option<Arc<AuditService>>,
+}
+
+impl UserPreferenceService {
+    /// Erstellt einen neuen `UserPreferenceService` ohne Schema.
+    pub fn new(preference_repository: Arc<dyn UserPreferenceRepository>) -> Self {
+        Self::with_schema(preference_repository, PreferenceSchema::new())
+    }
+
+    /// Erstellt einen `UserPreferenceService`, der Werte gegen `schema` prüft.
+    pub fn with_schema(preference_repository: Arc<dyn UserPreferenceRepository>, schema: PreferenceSchema) -> Self {
+        Self {
+            preference_repository,
+            system_defaults: None,
+            current_user: Mutex::new(None),
+            schema,
+            changes: Signal::new(),
+            events: None,
+            history: None,
+            audit: None,
+        }
+    }
+
+    /// Wählt den Benutzer, dessen Einstellungen gelesen und geschrieben werden
+    /// (`None` für den benutzerunabhängigen Bereich).
+    ///
+    /// Abonnenten werden über alle Einstellungen benachrichtigt, deren wirksamer Wert sich
+    /// durch den Wechsel ändert.
+    ///
+    /// # Rückgabe
+    /// Die verschickten Änderungen.
+    pub async fn select_user(&self, user_id: Option<NovaId>) -> DomainResult<Vec<PreferenceChange>> {
+        if self.current_user() == user_id {
+            return Ok(Vec::new());
+        }
+        let mut keys: Vec<String> = self.list_all_preferences().await?.into_iter().map(|s| s.key).collect();
+        let old_values = self.effective_values(&keys).await?;
+        info!(user_id = ?user_id, "Wechsle Benutzerkontext für Einstellungen.");
+        *self.current_user.lock().unwrap() = user_id;
+        keys = self.list_all_preferences().await?.into_iter().map(|s| s.key).collect();
+        let mut values = old_values;
+        for key in keys {
+            if !values.iter().any(|(known, _)| known == &key) {
+                values.push((key, None));
+            }
+        }
+        self.notify_differences(values).await
+    }
+
+    /// Der aktuell gewählte Benutzer.
+    pub fn current_user(&self) -> Option<NovaId> {
+        self.current_user.lock().unwrap().clone()
+    }
+
+    /// Legt eine systemweite Standardwert-Quelle fest, die zwischen Benutzerwerten und
+    /// Schema-Standardwerten liegt. Sie wird nur gelesen.
+    pub fn with_system_defaults(mut self, system_defaults: Arc<dyn UserPreferenceRepository>) -> Self {
+        self.system_defaults = Some(system_defaults);
+        self
+    }
+
+    /// Meldet jede Änderung zusätzlich als [`DomainEvent::PreferenceChanged`] an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Zeichnet Sammeländerungen ([`set_values`](Self::set_values)) in `history` auf
+    /// ([`PREFERENCE_HISTORY`]).
+    pub fn with_history(mut self, history: Arc<HistoryService>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Hält jedes Schreiben und Entfernen von Benutzerwerten (einschließlich Profilen) im
+    /// Änderungsprotokoll von `audit` fest; die Einträge tragen den Schlüssel als ID.
+    pub fn with_audit_log(mut self, audit: Arc<AuditService>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Abonniert Änderungen an Einstellungen, deren Schlüssel zu `pattern` passt
+    /// (siehe [`key_matches_pattern`]).
+    ///
+    /// Das Abonnement endet, sobald der `Receiver` verworfen wird.
+    pub fn subscribe(&self, pattern: &str) -> Receiver<PreferenceChange> {
+        let pattern = pattern.to_string();
+        self.changes.subscribe_filtered(move |change| key_matches_pattern(&change.key, &pattern))
+    }
+
+    /// Benachrichtigt die passenden Abonnenten, falls sich der Wert tatsächlich geändert hat.
+    fn notify(&self, key: &str, old_value: Option<PreferenceValue>, new_value: Option<PreferenceValue>) {
+        if old_value == new_value {
+            return;
+        }
+        let change = PreferenceChange { key: key.to_string(), old_value, new_value };
+        self.changes.emit(change.clone());
+        if let Some(events) = &self.events {
+            events.publish(DomainEvent::PreferenceChanged(change));
+        }
+    }
+
+    /// Das Schema der bekannten Einstellungen.
+    pub fn schema(&self) -> &PreferenceSchema {
+        &self.schema
+    }
+
+    /// Ruft eine Einstellung anhand ihres Schlüssels ab.
+    ///
+    /// Aufgelöst wird in der Reihenfolge Wert des aktiven Profils → Benutzerwert →
+    /// systemweiter Standardwert → Standardwert aus dem Schema.
+    pub async fn get_preference(&self, key: &str) -> DomainResult<Option<UserPreferenceSetting>> {
+        validate_preference_key(key)?;
+        reject_reserved_key(key)?;
+        let base = match self.preference_repository.get_preference(self.current_user(), key).await? {
+            Some(setting) => Some(setting),
+            None => self.get_default_preference(key).await?,
+        };
+        self.apply_active_profile(key, base).await
+    }
+
+    /// Ersetzt den Wert von `base` durch den Wert des aktiven Profils, falls dieses `key` überschreibt.
+    async fn apply_active_profile(
+        &self,
+        key: &str,
+        base: Option<UserPreferenceSetting>,
+    ) -> DomainResult<Option<UserPreferenceSetting>> {
+        let Some(profile) = self.active_profile().await? else {
+            return Ok(base);
+        };
+        let profile_key = format!("{}{}", profile_prefix(&profile), key);
+        match self.preference_repository.get_preference(self.current_user(), &profile_key).await? {
+            Some(profile_value) => {
+                let mut setting = base.unwrap_or_else(|| self.new_setting(key, profile_value.value.clone()));
+                setting.value = profile_value.value;
+                Ok(Some(setting))
+            }
+            None => Ok(base),
+        }
+    }
+
+    /// Erstellt eine Einstellung mit den Metadaten aus dem Schema bzw. dem Schlüssel als Anzeigenamen.
+    fn new_setting(&self, key: &str, value: PreferenceValue) -> UserPreferenceSetting {
+        match self.schema.get(key) {
+            Some(definition) => definition.to_setting(value),
+            None => UserPreferenceSetting {
+                key: key.to_string(),
+                value,
+                display_name: key.to_string(),
+                description: None,
+                requires_restart: false,
+                group: None,
+            },
+        }
+    }
+
+    /// Ruft den Wert ab, den eine Einstellung ohne Benutzerwert hätte
+    /// (systemweiter Standardwert oder Standardwert aus dem Schema).
+    pub async fn get_default_preference(&self, key: &str) -> DomainResult<Option<UserPreferenceSetting>> {
+        if let Some(system_defaults) = &self.system_defaults {
+            if let Some(setting) = system_defaults.get_preference(None, key).await? {
+                return Ok(Some(setting));
+            }
+        }
+        Ok(self.schema.get(key).map(|definition| definition.default_setting()))
+    }
+
+    /// Listet alle Einstellungen mit ihren wirksamen Werten auf, nach Schlüssel sortiert.
+    ///
+    /// Enthalten sind die Einstellungen aller Ebenen; bei gleichem Schlüssel gewinnt
+    /// die höhere Ebene.
+    pub async fn list_all_preferences(&self) -> DomainResult<Vec<UserPreferenceSetting>> {
+        info!("Auflistung aller Einstellungen angefordert.");
+        let mut merged: BTreeMap<String, UserPreferenceSetting> =
+            self.schema.definitions().map(|definition| (definition.key.clone(), definition.default_setting())).collect();
+        if let Some(system_defaults) = &self.system_defaults {
+            merged.extend(system_defaults.get_all_preferences(None).await?.into_iter().map(|s| (s.key.clone(), s)));
+        }
+        let stored = self.preference_repository.get_all_preferences(self.current_user()).await?;
+        let active_prefix = self.active_profile().await?.map(|profile| profile_prefix(&profile));
+        let mut profile_values = Vec::new();
+        for setting in stored {
+            match active_prefix.as_deref().and_then(|prefix| setting.key.strip_prefix(prefix)) {
+                Some(key) => profile_values.push((key.to_string(), setting.value)),
+                None if !is_reserved_key(&setting.key) => {
+                    merged.insert(setting.key.clone(), setting);
+                }
+                None => {}
+            }
+        }
+        for (key, value) in profile_values {
+            match merged.get_mut(&key) {
+                Some(setting) => setting.value = value,
+                None => {
+                    let setting = self.new_setting(&key, value);
+                    merged.insert(key, setting);
+                }
+            }
+        }
+        Ok(merged.into_values().collect())
+    }
+
+    /// Entfernt den Benutzerwert einer Einstellung, sodass wieder der Standardwert gilt.
+    ///
+    /// # Rückgabe
+    /// Die nun wirksame Einstellung, oder `None`, wenn es keinen Standardwert gibt.
+    pub async fn reset_preference(&self, key: &str) -> DomainResult<Option<UserPreferenceSetting>> {
+        let old_value = self.get_value(key).await?;
+        info!(key, "Setze Einstellung zurück.");
+        self.remove_setting(self.current_user(), key).await?;
+        let default = self.get_default_preference(key).await?;
+        let effective = self.apply_active_profile(key, default).await?;
+        self.notify(key, old_value, effective.as_ref().map(|setting| setting.value.clone()));
+        Ok(effective)
+    }
+
+    /// Der Name des aktiven Profils.
+    pub async fn active_profile(&self) -> DomainResult<Option<String>> {
+        match self.preference_repository.get_preference(self.current_user(), ACTIVE_PROFILE_KEY).await? {
+            Some(UserPreferenceSetting { value: PreferenceValue::String(name), .. }) => Ok(Some(name)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Die Werte eines Profils, nach Schlüssel sortiert (leer, wenn das Profil nicht existiert).
+    pub async fn profile(&self, name: &str) -> DomainResult<BTreeMap<String, PreferenceValue>> {
+        validate_profile_name(name)?;
+        let prefix = profile_prefix(name);
+        Ok(self
+            .preference_repository
+            .get_all_preferences(self.current_user())
+            .await?
+            .into_iter()
+            .filter_map(|setting| Some((setting.key.strip_prefix(&prefix)?.to_string(), setting.value)))
+            .collect())
+    }
+
+    /// Die Namen aller gespeicherten Profile, sortiert.
+    pub async fn list_profiles(&self) -> DomainResult<Vec<String>> {
+        let mut names: Vec<String> = self
+            .preference_repository
+            .get_all_preferences(self.current_user())
+            .await?
+            .into_iter()
+            .filter_map(|setting| {
+                let mut segments = setting.key.splitn(3, '.');
+                match (segments.next(), segments.next(), segments.next()) {
+                    (Some(PROFILES_NAMESPACE), Some(name), Some(_)) => Some(name.to_string()),
+                    _ => None,
+                }
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// Speichert ein Profil und ersetzt dessen bisherige Werte.
+    ///
+    /// Alle Werte werden vor dem Speichern gegen Schlüsselkonvention und Schema geprüft.
+    /// Ist das Profil aktiv, werden Änderungen an die Abonnenten gemeldet.
+    pub async fn save_profile(&self, name: &str, values: BTreeMap<String, PreferenceValue>) -> DomainResult<()> {
+        validate_profile_name(name)?;
+        for (key, value) in &values {
+            validate_preference_key(key)?;
+            reject_reserved_key(key)?;
+            self.schema.validate(key, value)?;
+        }
+        let previous = self.profile(name).await?;
+        let is_active = self.active_profile().await?.as_deref() == Some(name);
+        let affected: Vec<String> = previous.keys().chain(values.keys()).cloned().collect();
+        let old_values = if is_active { self.effective_values(&affected).await? } else { Vec::new() };
+
+        info!(profile = name, "Speichere Einstellungsprofil.");
+        let prefix = profile_prefix(name);
+        for key in previous.keys().filter(|key| !values.contains_key(*key)) {
+            self.remove_setting(self.current_user(), &format!("{}{}", prefix, key)).await?;
+        }
+        for (key, value) in values {
+            let mut setting = self.new_setting(&key, value);
+            setting.key = format!("{}{}", prefix, key);
+            self.store_setting(self.current_user(), &setting).await?;
+        }
+        if is_active {
+            self.notify_differences(old_values).await?;
+        }
+        Ok(())
+    }
+
+    /// Aktiviert ein Profil (`None` deaktiviert das aktive Profil).
+    ///
+    /// Das Umschalten erfolgt durch eine einzige Schreiboperation, sodass nie eine Mischung
+    /// zweier Profile wirksam ist. Für jede Einstellung, deren wirksamer Wert sich dadurch
+    /// ändert, wird ein Ereignis verschickt.
+    ///
+    /// # Rückgabe
+    /// Die verschickten Änderungen, oder `DomainError::EntityNotFound`, wenn das Profil nicht existiert.
+    pub async fn switch_profile(&self, name: Option<&str>) -> DomainResult<Vec<PreferenceChange>> {
+        let new_values = match name {
+            Some(name) => {
+                let values = self.profile(name).await?;
+                if values.is_empty() {
+                    return Err(DomainError::EntityNotFound {
+                        entity_type: "PreferenceProfile".to_string(),
+                        entity_id: name.to_string(),
+                    });
+                }
+                values
+            }
+            None => BTreeMap::new(),
+        };
+        let current = self.active_profile().await?;
+        if current.as_deref() == name {
+            return Ok(Vec::new());
+        }
+        let current_values = match &current {
+            Some(profile) => self.profile(profile).await?,
+            None => BTreeMap::new(),
+        };
+        let affected: Vec<String> = current_values.keys().chain(new_values.keys()).cloned().collect();
+        let old_values = self.effective_values(&affected).await?;
+
+        info!(from = ?current, to = ?name, "Wechsle Einstellungsprofil.");
+        match name {
+            Some(name) => {
+                let active = UserPreferenceSetting::new_string(ACTIVE_PROFILE_KEY, "Aktives Profil", name.to_string());
+                self.store_setting(self.current_user(), &active).await?;
+            }
+            None => self.remove_setting(self.current_user(), ACTIVE_PROFILE_KEY).await?,
+        }
+        self.notify_differences(old_values).await
+    }
+
+    /// Löscht ein Profil; ist es aktiv, wird es zuvor deaktiviert.
+    pub async fn delete_profile(&self, name: &str) -> DomainResult<()> {
+        let values = self.profile(name).await?;
+        if self.active_profile().await?.as_deref() == Some(name) {
+            self.switch_profile(None).await?;
+        }
+        let prefix = profile_prefix(name);
+        for key in values.keys() {
+            self.remove_setting(self.current_user(), &format!("{}{}", prefix, key)).await?;
+        }
+        Ok(())
+    }
+
+    /// Exportiert alle Benutzerwerte als JSON, z.B. zur Sicherung oder Weitergabe.
+    ///
+    /// Standardwerte (System und Schema) und Profile sind nicht enthalten.
+    pub async fn export_all(&self) -> DomainResult<String> {
+        let preferences = self
+            .preference_repository
+            .get_all_preferences(self.current_user())
+            .await?
+            .into_iter()
+            .filter(|setting| !is_reserved_key(&setting.key))
+            .collect();
+        let export = PreferenceExport { version: EXPORT_FORMAT_VERSION, preferences };
+        serde_json::to_string_pretty(&export).map_err(|e| DomainError::ServiceError {
+            service_name: "UserPreferenceService".to_string(),
+            message: format!("Export fehlgeschlagen: {}", e),
+        })
+    }
+
+    /// Importiert Benutzerwerte aus einem JSON-Export von [`export_all`](Self::export_all).
+    ///
+    /// Alle Einträge werden vor dem ersten Schreibvorgang gegen Schlüsselkonvention und
+    /// Schema geprüft; ein ungültiger Eintrag bricht den gesamten Import ab. Mit `dry_run`
+    /// wird nur der Bericht erstellt, ohne etwas zu speichern.
+    pub async fn import(&self, json: &str, mode: ImportMode, dry_run: bool) -> DomainResult<ImportReport> {
+        let export: PreferenceExport = serde_json::from_str(json).map_err(|e| DomainError::ValidationError {
+            field: "import".to_string(),
+            message: format!("Ungültiges Exportformat: {}", e),
+        })?;
+        if export.version > EXPORT_FORMAT_VERSION {
+            return Err(DomainError::ValidationError {
+                field: "import.version".to_string(),
+                message: format!("Exportformat-Version {} wird nicht unterstützt.", export.version),
+            });
+        }
+        for setting in &export.preferences {
+            setting.validate()?;
+            reject_reserved_key(&setting.key)?;
+            self.schema.validate(&setting.key, &setting.value)?;
+        }
+
+        let current: BTreeMap<String, UserPreferenceSetting> = self
+            .preference_repository
+            .get_all_preferences(self.current_user())
+            .await?
+            .into_iter()
+            .filter(|setting| !is_reserved_key(&setting.key))
+            .map(|setting| (setting.key.clone(), setting))
+            .collect();
+        let imported: BTreeMap<String, UserPreferenceSetting> =
+            export.preferences.into_iter().map(|setting| (setting.key.clone(), setting)).collect();
+
+        let mut changes: Vec<PreferenceChange> = imported
+            .values()
+            .filter(|setting| current.get(&setting.key).map(|c| &c.value) != Some(&setting.value))
+            .map(|setting| PreferenceChange {
+                key: setting.key.clone(),
+                old_value: current.get(&setting.key).map(|c| c.value.clone()),
+                new_value: Some(setting.value.clone()),
+            })
+            .collect();
+        if mode == ImportMode::Replace {
+            changes.extend(current.values().filter(|c| !imported.contains_key(&c.key)).map(|c| PreferenceChange {
+                key: c.key.clone(),
+                old_value: Some(c.value.clone()),
+                new_value: None,
+            }));
+            changes.sort_by(|a, b| a.key.cmp(&b.key));
+        }
+        if dry_run {
+            return Ok(ImportReport { changes, dry_run });
+        }
+
+        info!(count = changes.len(), ?mode, "Importiere Einstellungen.");
+        let affected: Vec<String> = changes.iter().map(|change| change.key.clone()).collect();
+        let old_values = self.effective_values(&affected).await?;
+        for change in &changes {
+            match imported.get(&change.key) {
+                Some(setting) => self.store_setting(self.current_user(), setting).await?,
+                None => self.remove_setting(self.current_user(), &change.key).await?,
+            }
+        }
+        self.notify_differences(old_values).await?;
+        Ok(ImportReport { changes, dry_run })
+    }
+
+    /// Führt Schlüsselmigrationen in der gegebenen Reihenfolge aus; gedacht für den Start.
+    ///
+    /// Für jede Migration wird der Benutzerwert und jeder Profilwert unter `old_key` unter
+    /// `new_key` gespeichert und der alte Eintrag entfernt. Ein bereits vorhandener Wert unter
+    /// `new_key` hat Vorrang. Werte, die nach der Umwandlung nicht dem Schema entsprechen,
+    /// werden verworfen. Bereits migrierte Einstellungen werden nicht erneut angefasst.
+    ///
+    /// # Rückgabe
+    /// Die gespeicherten Schlüssel, die migriert wurden (einschließlich Profil-Schlüsseln).
+    pub async fn run_key_migrations(&self, migrations: &[PreferenceKeyMigration]) -> DomainResult<Vec<String>> {
+        let mut migrated = Vec::new();
+        for migration in migrations {
+            validate_preference_key(&migration.new_key)?;
+            reject_reserved_key(&migration.new_key)?;
+            let stored = self.preference_repository.get_all_preferences(self.current_user()).await?;
+            let stored_keys: Vec<&str> = stored.iter().map(|setting| setting.key.as_str()).collect();
+            let renames: Vec<(&UserPreferenceSetting, String)> = stored
+                .iter()
+                .filter_map(|setting| {
+                    let prefix = setting.key.strip_suffix(migration.old_key.as_str())?;
+                    // "profiles.<name>." vor dem alten Schlüssel kennzeichnet einen Profilwert.
+                    let is_profile_value = prefix.starts_with(&format!("{}.", PROFILES_NAMESPACE))
+                        && prefix.ends_with('.')
+                        && prefix.matches('.').count() == 2;
+                    (prefix.is_empty() || is_profile_value).then(|| (setting, format!("{}{}", prefix, migration.new_key)))
+                })
+                .collect();
+            if renames.is_empty() {
+                continue;
+            }
+
+            let old_values = self.effective_values(&[migration.old_key.clone(), migration.new_key.clone()]).await?;
+            for (setting, new_stored_key) in renames {
+                let value = migration.apply(setting.value.clone());
+                match value {
+                    _ if stored_keys.contains(&new_stored_key.as_str()) => {
+                        info!(key = %setting.key, "Migration übersprungen, neuer Schlüssel ist bereits gesetzt.");
+                    }
+                    Some(value) if self.schema.validate(&migration.new_key, &value).is_ok() => {
+                        let mut renamed = setting.clone();
+                        renamed.key = new_stored_key;
+                        renamed.value = value;
+                        if let Some(definition) = self.schema.get(&migration.new_key) {
+                            renamed.display_name = definition.display_name.clone();
+                            renamed.description = definition.description.clone();
+                            renamed.group = definition.group.clone();
+                            renamed.requires_restart = definition.requires_restart;
+                        }
+                        self.store_setting(self.current_user(), &renamed).await?;
+                    }
+                    _ => warn!(key = %setting.key, "Wert ist nach der Migration ungültig und wird verworfen."),
+                }
+                self.remove_setting(self.current_user(), &setting.key).await?;
+                info!(from = %setting.key, to = %migration.new_key, "Einstellung migriert.");
+                migrated.push(setting.key.clone());
+            }
+            self.notify_differences(old_values).await?;
+        }
+        Ok(migrated)
+    }
+
+    /// Die wirksamen Werte der gegebenen Schlüssel (doppelte Schlüssel werden zusammengefasst).
+    async fn effective_values(&self, keys: &[String]) -> DomainResult<Vec<(String, Option<PreferenceValue>)>> {
+        let mut keys = keys.to_vec();
+        keys.sort();
+        keys.dedup();
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            let value = self.get_value(&key).await?;
+            values.push((key, value));
+        }
+        Ok(values)
+    }
+
+    /// Vergleicht die zuvor ermittelten Werte mit den nun wirksamen und meldet die Unterschiede.
+    async fn notify_differences(&self, old_values: Vec<(String, Option<PreferenceValue>)>) -> DomainResult<Vec<PreferenceChange>> {
+        let mut changes = Vec::new();
+        for (key, old_value) in old_values {
+            let new_value = self.get_value(&key).await?;
+            if old_value != new_value {
+                changes.push(PreferenceChange { key: key.clone(), old_value: old_value.clone(), new_value: new_value.clone() });
+            }
+            self.notify(&key, old_value, new_value);
+        }
+        Ok(changes)
+    }
+
+    /// Ruft nur den Wert einer Einstellung ab.
+    pub async fn get_value(&self, key: &str) -> DomainResult<Option<PreferenceValue>> {
+        Ok(self.get_preference(key).await?.map(|setting| setting.value))
+    }
+
+    /// Ruft eine boolesche Einstellung ab.
+    ///
+    /// # Rückgabe
+    /// `None`, wenn die Einstellung nicht existiert; ein `DomainError::ValidationError`,
+    /// wenn sie einen anderen Typ hat.
+    pub async fn get_bool(&self, key: &str) -> DomainResult<Option<bool>> {
+        match self.get_value(key).await? {
+            None => Ok(None),
+            Some(PreferenceValue::Boolean(value)) => Ok(Some(value)),
+            Some(other) => Err(type_mismatch(key, "Boolean", &other)),
+        }
+    }
+
+    /// Wie [`get_bool`](Self::get_bool), liefert aber `default`, wenn die Einstellung nicht existiert.
+    pub async fn get_bool_or_default(&self, key: &str, default: bool) -> DomainResult<bool> {
+        Ok(self.get_bool(key).await?.unwrap_or(default))
+    }
+
+    /// Ruft eine ganzzahlige Einstellung ab (siehe [`get_bool`](Self::get_bool)).
+    pub async fn get_integer(&self, key: &str) -> DomainResult<Option<i64>> {
+        match self.get_value(key).await? {
+            None => Ok(None),
+            Some(PreferenceValue::Integer(value)) => Ok(Some(value)),
+            Some(other) => Err(type_mismatch(key, "Integer", &other)),
+        }
+    }
+
+    /// Wie [`get_integer`](Self::get_integer), liefert aber `default`, wenn die Einstellung nicht existiert.
+    pub async fn get_integer_or_default(&self, key: &str, default: i64) -> DomainResult<i64> {
+        Ok(self.get_integer(key).await?.unwrap_or(default))
+    }
+
+    /// Ruft eine Text-Einstellung ab (siehe [`get_bool`](Self::get_bool)).
+    ///
+    /// Farbwerte (`ColorRgba`) werden ebenfalls als Text geliefert.
+    pub async fn get_string(&self, key: &str) -> DomainResult<Option<String>> {
+        match self.get_value(key).await? {
+            None => Ok(None),
+            Some(PreferenceValue::String(value)) | Some(PreferenceValue::ColorRgba(value)) => Ok(Some(value)),
+            Some(other) => Err(type_mismatch(key, "String", &other)),
+        }
+    }
+
+    /// Wie [`get_string`](Self::get_string), liefert aber `default`, wenn die Einstellung nicht existiert.
+    pub async fn get_string_or_default(&self, key: &str, default: &str) -> DomainResult<String> {
+        Ok(self.get_string(key).await?.unwrap_or_else(|| default.to_string()))
+    }
+
+    /// Speichert eine vollständige Einstellung.
+    ///
+    /// # Rückgabe
+    /// Die gespeicherte Einstellung, oder `DomainError::ValidationError`, wenn der Schlüssel
+    /// ungültig ist oder der Wert nicht dem Schema entspricht. Überschreibt das aktive Profil
+    /// die Einstellung, bleibt dessen Wert wirksam, bis das Profil gewechselt wird.
+    pub async fn set_preference(&self, setting: UserPreferenceSetting) -> DomainResult<UserPreferenceSetting> {
+        setting.validate()?;
+        reject_reserved_key(&setting.key)?;
+        self.schema.validate(&setting.key, &setting.value)?;
+        let old_value = self.get_value(&setting.key).await?;
+        info!(key = %setting.key, "Speichere Einstellung.");
+        self.store_setting(self.current_user(), &setting).await?;
+        self.notify(&setting.key, old_value, Some(setting.value.clone()));
+        Ok(setting)
+    }
+
+    /// Setzt den Wert einer Einstellung.
+    ///
+    /// Die Metadaten (Anzeigename, Beschreibung, Gruppe) einer bestehenden Einstellung
+    /// bleiben erhalten; eine neue Einstellung übernimmt sie aus dem Schema oder verwendet
+    /// den Schlüssel als Anzeigenamen.
+    ///
+    /// # Rückgabe
+    /// Die gespeicherte Einstellung.
+    pub async fn set_value(&self, key: &str, value: PreferenceValue) -> DomainResult<UserPreferenceSetting> {
+        let mut setting = match self.get_preference(key).await? {
+            Some(existing) => existing,
+            None => self.new_setting(key, value.clone()),
+        };
+        setting.value = value;
+        self.set_preference(setting).await
+    }
+
+    /// Setzt mehrere Einstellungen auf einmal. Alle Werte werden vor dem Speichern geprüft;
+    /// ist einer ungültig, wird keiner gespeichert.
+    ///
+    /// Mit einem [`HistoryService`] kann die Änderung als Ganzes rückgängig gemacht werden.
+    ///
+    /// # Rückgabe
+    /// Die Änderungen der wirksamen Werte.
+    pub async fn set_values(self: &Arc<Self>, values: BTreeMap<String, PreferenceValue>) -> DomainResult<Vec<PreferenceChange>> {
+        let user = self.current_user();
+        let mut before = Vec::with_capacity(values.len());
+        let mut after = Vec::with_capacity(values.len());
+        for (key, value) in values {
+            validate_preference_key(&key)?;
+            reject_reserved_key(&key)?;
+            self.schema.validate(&key, &value)?;
+            let stored = self.preference_repository.get_preference(user.clone(), &key).await?;
+            let mut setting = match &stored {
+                Some(existing) => existing.clone(),
+                None => self.new_setting(&key, value.clone()),
+            };
+            setting.value = value;
+            before.push((key.clone(), stored));
+            after.push((key, Some(setting)));
+        }
+        info!(count = after.len(), "Speichere mehrere Einstellungen.");
+        let changes = self.write_user_settings(user.clone(), &after).await?;
+        if let (Some(history), false) = (&self.history, changes.is_empty()) {
+            history.record(PREFERENCE_HISTORY, Box::new(PreferenceBatchCommand { service: Arc::downgrade(self), user, before, after }));
+        }
+        Ok(changes)
+    }
+
+    /// Speichert eine Einstellung für `user`, nachdem sie mit [`Validate`] geprüft wurde.
+    async fn store_setting(&self, user: Option<NovaId>, setting: &UserPreferenceSetting) -> DomainResult<()> {
+        setting.validate()?;
+        let before = self.stored_for_audit(user.clone(), &setting.key).await?;
+        self.preference_repository.set_preference(user, setting).await?;
+        self.audit_setting(&setting.key, before, Some(setting)).await;
+        Ok(())
+    }
+
+    /// Entfernt den Wert `key` von `user`.
+    async fn remove_setting(&self, user: Option<NovaId>, key: &str) -> DomainResult<()> {
+        let before = self.stored_for_audit(user.clone(), key).await?;
+        self.preference_repository.remove_preference(user, key).await?;
+        self.audit_setting(key, before, None).await;
+        Ok(())
+    }
+
+    /// Der gespeicherte Wert vor einer Änderung; wird nur mit Änderungsprotokoll gelesen.
+    async fn stored_for_audit(&self, user: Option<NovaId>, key: &str) -> DomainResult<Option<UserPreferenceSetting>> {
+        match &self.audit {
+            Some(_) => self.preference_repository.get_preference(user, key).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn audit_setting(&self, key: &str, before: Option<UserPreferenceSetting>, after: Option<&UserPreferenceSetting>) {
+        let Some(audit) = &self.audit else { return };
+        let entity_type = UserPreferenceSetting::ENTITY_TYPE;
+        match (before, after) {
+            (None, Some(after)) => audit.record(entity_type, key, AuditOperation::Created, format!("Wert {:?} gesetzt.", after.value)).await,
+            (Some(before), Some(after)) => audit.record_update(entity_type, key, &before, after).await,
+            (Some(before), None) => audit.record(entity_type, key, AuditOperation::Deleted, format!("Wert {:?} entfernt.", before.value)).await,
+            (None, None) => None,
+        };
+    }
+
+    /// Schreibt die Benutzerwerte von `user` (`None` entfernt den Wert) und benachrichtigt über
+    /// die Änderungen, falls `user` der aktuelle Benutzer ist.
+    async fn write_user_settings(
+        &self,
+        user: Option<NovaId>,
+        settings: &[(String, Option<UserPreferenceSetting>)],
+    ) -> DomainResult<Vec<PreferenceChange>> {
+        // Erst alle prüfen, damit eine ungültige Einstellung nichts halb geschrieben zurücklässt.
+        for setting in settings.iter().filter_map(|(_, setting)| setting.as_ref()) {
+            setting.validate()?;
+        }
+        let keys: Vec<String> = settings.iter().map(|(key, _)| key.clone()).collect();
+        let old_values = self.effective_values(&keys).await?;
+        for (key, setting) in settings {
+            match setting {
+                Some(setting) => self.store_setting(user.clone(), setting).await?,
+                None => self.remove_setting(user.clone(), key).await?,
+            }
+        }
+        if user == self.current_user() {
+            self.notify_differences(old_values).await
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Setzt eine boolesche Einstellung und liefert den übernommenen Wert.
+    pub async fn set_bool(&self, key: &str, value: bool) -> DomainResult<bool> {
+        match self.set_value(key, PreferenceValue::Boolean(value)).await?.value {
+            PreferenceValue::Boolean(applied) => Ok(applied),
+            other => Err(type_mismatch(key, "Boolean", &other)),
+        }
+    }
+
+    /// Setzt eine Text-Einstellung und liefert den übernommenen Wert.
+    pub async fn set_string(&self, key: &str, value: String) -> DomainResult<String> {
+        match self.set_value(key, PreferenceValue::String(value)).await?.value {
+            PreferenceValue::String(applied) => Ok(applied),
+            other => Err(type_mismatch(key, "String", &other)),
+        }
+    }
+}
+
+/// Eine Sammeländerung von Benutzerwerten mit den Werten davor und danach.
+struct PreferenceBatchCommand {
+    service: Weak<UserPreferenceService>,
+    user: Option<NovaId>,
+    before: Vec<(String, Option<UserPreferenceSetting>)>,
+    after: Vec<(String, Option<UserPreferenceSetting>)>,
+}
+
+impl PreferenceBatchCommand {
+    async fn apply(&self, settings: &[(String, Option<UserPreferenceSetting>)]) -> DomainResult<()> {
+        let service = self.service.upgrade().ok_or_else(|| DomainError::OperationNotPermitted {
+            operation: "undo_preferences".to_string(),
+            reason: "Der Einstellungsdienst existiert nicht mehr.".to_string(),
+        })?;
+        service.write_user_settings(self.user.clone(), settings).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl UndoableCommand for PreferenceBatchCommand {
+    fn description(&self) -> String {
+        let keys: Vec<&str> = self.after.iter().map(|(key, _)| key.as_str()).collect();
+        format!("Einstellungen ändern ({})", keys.join(", "))
+    }
+
+    async fn undo(&self) -> DomainResult<()> {
+        self.apply(&self.before).await
+    }
+
+    async fn redo(&self) -> DomainResult<()> {
+        self.apply(&self.after).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::preference_schema::PreferenceDefinition;
+    use crate::repositories::user_preference_repository::MockUserPreferenceRepository;
+    use tokio;
+
+    #[test]
+    fn test_validate_preference_key() {
+        assert!(validate_preference_key("theme.dark_mode").is_ok());
+        assert!(validate_preference_key("keyboard.repeat-rate2").is_ok());
+        for invalid in ["", "theme.", ".theme", "theme..mode", "Theme.Mode", "theme mode"] {
+            assert!(validate_preference_key(invalid).is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_typed_getters_with_defaults() {
+        let mut mock_repo = MockUserPreferenceRepository::new();
+        mock_repo.expect_get_preference().returning(|_, key| {
+            Ok(match key {
+                "theme.dark_mode" => Some(UserPreferenceSetting::new_boolean(key, "Dunkler Modus", true)),
+                "keyboard.layout" => Some(UserPreferenceSetting::new_string(key, "Layout", "de".to_string())),
+                _ => None,
+            })
+        });
+        let service = UserPreferenceService::new(Arc::new(mock_repo));
+
+        assert_eq!(service.get_bool("theme.dark_mode").await.unwrap(), Some(true));
+        assert!(service.get_bool_or_default("theme.missing", true).await.unwrap());
+        assert_eq!(service.get_string_or_default("keyboard.layout", "us").await.unwrap(), "de");
+        assert_eq!(service.get_string_or_default("keyboard.variant", "nodeadkeys").await.unwrap(), "nodeadkeys");
+        assert!(matches!(
+            service.get_bool("keyboard.layout").await,
+            Err(DomainError::ValidationError { field, .. }) if field == "keyboard.layout"
+        ));
+        assert!(matches!(service.get_bool("Ungültig").await, Err(DomainError::ValidationError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_set_value_keeps_metadata() {
+        let mut mock_repo = MockUserPreferenceRepository::new();
+        mock_repo.expect_get_preference().returning(|_, key| {
+            let mut existing = UserPreferenceSetting::new_boolean(key, "Dunkler Modus", false);
+            existing.group = Some("Erscheinungsbild".to_string());
+            Ok(Some(existing))
+        });
+        mock_repo
+            .expect_set_preference()
+            .withf(|_, setting| setting.value == PreferenceValue::Boolean(true) && setting.group.is_some())
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = UserPreferenceService::new(Arc::new(mock_repo));
+
+        assert!(service.set_bool("theme.dark_mode", true).await.unwrap());
+    }
+
+    fn schema() -> PreferenceSchema {
+        let mut schema = PreferenceSchema::new();
+        schema
+            .register(
+                PreferenceDefinition::new("audio.volume", "Lautstärke", PreferenceValue::Integer(50))
+                    .with_range(0.0, 100.0)
+                    .in_group("Audio"),
+            )
+            .unwrap();
+        schema
+    }
+
+    #[tokio::test]
+    async fn test_schema_default_and_validation() {
+        let mut mock_repo = MockUserPreferenceRepository::new();
+        mock_repo.expect_get_preference().returning(|_, _| Ok(None));
+        mock_repo
+            .expect_set_preference()
+            .withf(|_, setting| setting.value == PreferenceValue::Integer(80) && setting.group.as_deref() == Some("Audio"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = UserPreferenceService::with_schema(Arc::new(mock_repo), schema());
+
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(50));
+        assert!(matches!(
+            service.set_value("audio.volume", PreferenceValue::String("laut".to_string())).await,
+            Err(DomainError::ValidationError { field, .. }) if field == "audio.volume"
+        ));
+        assert!(service.set_value("audio.volume", PreferenceValue::Integer(120)).await.is_err());
+        assert_eq!(service.set_value("audio.volume", PreferenceValue::Integer(80)).await.unwrap().value, PreferenceValue::Integer(80));
+    }
+
+    #[test]
+    fn test_key_matches_pattern() {
+        assert!(key_matches_pattern("theme.dark_mode", "theme.*"));
+        assert!(key_matches_pattern("theme.dark_mode", "theme.dark_mode"));
+        assert!(key_matches_pattern("theme.dark_mode", "*"));
+        assert!(!key_matches_pattern("themes.x", "theme.*"));
+        assert!(!key_matches_pattern("theme.dark_mode_auto", "theme.dark_mode"));
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_matching_changes() {
+        let mut mock_repo = MockUserPreferenceRepository::new();
+        mock_repo.expect_get_preference().returning(|_, key| {
+            Ok((key == "theme.dark_mode").then(|| UserPreferenceSetting::new_boolean(key, "Dunkler Modus", false)))
+        });
+        mock_repo.expect_set_preference().returning(|_, _| Ok(()));
+        let service = UserPreferenceService::new(Arc::new(mock_repo));
+        let theme = service.subscribe("theme.*");
+        let keyboard = service.subscribe("keyboard.*");
+        drop(service.subscribe("*"));
+
+        service.set_bool("theme.dark_mode", true).await.unwrap();
+        service.set_bool("theme.dark_mode", false).await.unwrap(); // Unverändert: kein Ereignis.
+        service.set_string("theme.accent", "blau".to_string()).await.unwrap();
+
+        let changes: Vec<PreferenceChange> = theme.try_iter().collect();
+        assert_eq!(
+            changes,
+            vec![
+                PreferenceChange {
+                    key: "theme.dark_mode".to_string(),
+                    old_value: Some(PreferenceValue::Boolean(false)),
+                    new_value: Some(PreferenceValue::Boolean(true)),
+                },
+                PreferenceChange {
+                    key: "theme.accent".to_string(),
+                    old_value: None,
+                    new_value: Some(PreferenceValue::String("blau".to_string())),
+                },
+            ]
+        );
+        assert!(keyboard.try_recv().is_err());
+        assert_eq!(service.changes.subscriber_count(), 2, "Verworfene Abonnements werden entfernt");
+    }
+
+    #[tokio::test]
+    async fn test_layers_resolve_user_then_system_then_schema() {
+        let mut user_repo = MockUserPreferenceRepository::new();
+        user_repo.expect_get_preference().returning(|_, key| {
+            Ok((key == "audio.volume").then(|| UserPreferenceSetting {
+                value: PreferenceValue::Integer(30),
+                ..UserPreferenceSetting::new_boolean(key, "Lautstärke", false)
+            }))
+        });
+        user_repo.expect_get_all_preferences().returning(|_| {
+            Ok(vec![UserPreferenceSetting {
+                value: PreferenceValue::Integer(30),
+                ..UserPreferenceSetting::new_boolean("audio.volume", "Lautstärke", false)
+            }])
+        });
+        user_repo.expect_remove_preference().withf(|_, key| key == "audio.volume").times(1).returning(|_, _| Ok(()));
+        let mut system_repo = MockUserPreferenceRepository::new();
+        system_repo.expect_get_preference().returning(|_, key| {
+            Ok((key == "theme.dark_mode").then(|| UserPreferenceSetting::new_boolean(key, "Dunkler Modus", true)))
+        });
+        system_repo.expect_get_all_preferences().returning(|_| {
+            Ok(vec![UserPreferenceSetting::new_boolean("theme.dark_mode", "Dunkler Modus", true)])
+        });
+        system_repo.expect_set_preference().never();
+        system_repo.expect_remove_preference().never();
+
+        let service = UserPreferenceService::with_schema(Arc::new(user_repo), schema())
+            .with_system_defaults(Arc::new(system_repo));
+        let changes = service.subscribe("audio.*");
+
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(30));
+        assert_eq!(service.get_bool("theme.dark_mode").await.unwrap(), Some(true));
+        assert_eq!(service.get_bool("theme.missing").await.unwrap(), None);
+        let all = service.list_all_preferences().await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].value, PreferenceValue::Integer(30));
+
+        let effective = service.reset_preference("audio.volume").await.unwrap().unwrap();
+        assert_eq!(effective.value, PreferenceValue::Integer(50));
+        assert_eq!(changes.try_recv().unwrap().new_value, Some(PreferenceValue::Integer(50)));
+    }
+
+    /// Zustandsbehaftetes Repository für Tests, die mehrere Schreib-/Lesezyklen benötigen.
+    #[derive(Default)]
+    struct MemoryRepository {
+        settings: Mutex<BTreeMap<(Option<String>, String), UserPreferenceSetting>>,
+    }
+
+    fn scoped(user_id: Option<NovaId>, key: &str) -> (Option<String>, String) {
+        (user_id.map(|id| id.to_string()), key.to_string())
+    }
+
+    #[async_trait::async_trait]
+    impl UserPreferenceRepository for MemoryRepository {
+        async fn get_preference(&self, user_id: Option<NovaId>, key: &str) -> DomainResult<Option<UserPreferenceSetting>> {
+            Ok(self.settings.lock().unwrap().get(&scoped(user_id, key)).cloned())
+        }
+
+        async fn get_all_preferences(&self, user_id: Option<NovaId>) -> DomainResult<Vec<UserPreferenceSetting>> {
+            let user = user_id.map(|id| id.to_string());
+            Ok(self.settings.lock().unwrap().iter().filter(|((u, _), _)| *u == user).map(|(_, s)| s.clone()).collect())
+        }
+
+        async fn set_preference(&self, user_id: Option<NovaId>, setting: &UserPreferenceSetting) -> DomainResult<()> {
+            self.settings.lock().unwrap().insert(scoped(user_id, &setting.key), setting.clone());
+            Ok(())
+        }
+
+        async fn remove_preference(&self, user_id: Option<NovaId>, key: &str) -> DomainResult<()> {
+            self.settings.lock().unwrap().remove(&scoped(user_id, key));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_profile_switching_emits_changes() {
+        let service = UserPreferenceService::with_schema(Arc::new(MemoryRepository::default()), schema());
+        service.set_bool("notifications.enabled", true).await.unwrap();
+        service
+            .save_profile(
+                "presentation",
+                BTreeMap::from([
+                    ("notifications.enabled".to_string(), PreferenceValue::Boolean(false)),
+                    ("audio.volume".to_string(), PreferenceValue::Integer(0)),
+                ]),
+            )
+            .await
+            .unwrap();
+        service
+            .save_profile("work", BTreeMap::from([("audio.volume".to_string(), PreferenceValue::Integer(20))]))
+            .await
+            .unwrap();
+        assert_eq!(service.list_profiles().await.unwrap(), vec!["presentation", "work"]);
+        assert!(service.save_profile("bad", BTreeMap::from([("audio.volume".to_string(), PreferenceValue::Integer(500))])).await.is_err());
+
+        let changes = service.subscribe("*");
+        let applied = service.switch_profile(Some("presentation")).await.unwrap();
+        assert_eq!(applied.len(), 2);
+        assert_eq!(changes.try_iter().count(), 2);
+        assert_eq!(service.get_bool("notifications.enabled").await.unwrap(), Some(false));
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(0));
+        let listed = service.list_all_preferences().await.unwrap();
+        assert!(listed.iter().all(|setting| !setting.key.starts_with("profiles")));
+        assert!(listed.iter().any(|s| s.key == "audio.volume" && s.value == PreferenceValue::Integer(0)));
+
+        // Nur die Lautstärke unterscheidet sich; "notifications.enabled" fällt auf den Benutzerwert zurück.
+        let applied = service.switch_profile(Some("work")).await.unwrap();
+        assert_eq!(applied.len(), 2);
+        assert_eq!(service.get_bool("notifications.enabled").await.unwrap(), Some(true));
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(20));
+
+        service.delete_profile("work").await.unwrap();
+        assert_eq!(service.active_profile().await.unwrap(), None);
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(50));
+        assert!(matches!(service.switch_profile(Some("work")).await, Err(DomainError::EntityNotFound { .. })));
+        assert!(matches!(
+            service.set_string("profiles.active", "work".to_string()).await,
+            Err(DomainError::OperationNotPermitted { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_published_as_domain_events() {
+        let bus = Arc::new(crate::events::EventBus::new());
+        let events = bus.subscribe();
+        let service = UserPreferenceService::with_schema(Arc::new(MemoryRepository::default()), schema()).with_event_publisher(bus);
+        service.set_bool("theme.dark_mode", true).await.unwrap();
+        service.set_bool("theme.dark_mode", true).await.unwrap();
+
+        let published: Vec<DomainEvent> = events.try_iter().collect();
+        assert_eq!(published.len(), 1, "Unveränderte Werte werden nicht gemeldet");
+        assert!(matches!(&published[0], DomainEvent::PreferenceChanged(change) if change.key == "theme.dark_mode"));
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_recorded_in_audit_log() {
+        use crate::entities::AuditRecord;
+        use crate::repositories::audit_repository::MockAuditRepository;
+
+        let records: Arc<Mutex<Vec<AuditRecord>>> = Arc::default();
+        let mut audit_repo = MockAuditRepository::new();
+        let appended = records.clone();
+        audit_repo.expect_append().returning(move |record| {
+            appended.lock().unwrap().push(record.clone());
+            Ok(())
+        });
+        let audit = Arc::new(AuditService::new(Arc::new(audit_repo)));
+        audit.set_actor("anna");
+        let service = UserPreferenceService::with_schema(Arc::new(MemoryRepository::default()), schema()).with_audit_log(audit);
+
+        service.set_bool("theme.dark_mode", true).await.unwrap();
+        service.set_bool("theme.dark_mode", true).await.unwrap();
+        service.set_bool("theme.dark_mode", false).await.unwrap();
+        service.reset_preference("theme.dark_mode").await.unwrap();
+
+        let records = records.lock().unwrap();
+        let operations: Vec<AuditOperation> = records.iter().map(|record| record.operation).collect();
+        assert_eq!(operations, vec![AuditOperation::Created, AuditOperation::Updated, AuditOperation::Deleted], "Unveränderte Werte werden nicht protokolliert");
+        assert!(records.iter().all(|record| record.entity_id == "theme.dark_mode" && record.actor == "anna"));
+        assert_eq!(records[1].summary, r#"value: {"Boolean":true} → {"Boolean":false}"#);
+    }
+
+    #[tokio::test]
+    async fn test_set_values_can_be_undone() {
+        let history = Arc::new(HistoryService::new());
+        let service = Arc::new(
+            UserPreferenceService::with_schema(Arc::new(MemoryRepository::default()), schema()).with_history(history.clone()),
+        );
+        service.set_bool("theme.dark_mode", true).await.unwrap();
+        let invalid = BTreeMap::from([
+            ("theme.dark_mode".to_string(), PreferenceValue::Boolean(false)),
+            ("audio.volume".to_string(), PreferenceValue::Integer(500)),
+        ]);
+        assert!(service.set_values(invalid).await.is_err());
+        assert_eq!(service.get_bool("theme.dark_mode").await.unwrap(), Some(true), "Ungültige Sammeländerungen werden nicht teilweise gespeichert");
+
+        let values = BTreeMap::from([
+            ("theme.dark_mode".to_string(), PreferenceValue::Boolean(false)),
+            ("audio.volume".to_string(), PreferenceValue::Integer(10)),
+        ]);
+        assert_eq!(service.set_values(values).await.unwrap().len(), 2);
+
+        let changes = service.subscribe("*");
+        history.undo(PREFERENCE_HISTORY).await.unwrap();
+        assert_eq!(service.get_bool("theme.dark_mode").await.unwrap(), Some(true));
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(50), "Ohne Benutzerwert gilt wieder der Standardwert");
+        assert_eq!(changes.try_iter().count(), 2);
+        history.redo(PREFERENCE_HISTORY).await.unwrap();
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_export_and_import() {
+        let source = UserPreferenceService::with_schema(Arc::new(MemoryRepository::default()), schema());
+        source.set_bool("theme.dark_mode", true).await.unwrap();
+        source.set_value("audio.volume", PreferenceValue::Integer(70)).await.unwrap();
+        source.save_profile("work", BTreeMap::from([("audio.volume".to_string(), PreferenceValue::Integer(20))])).await.unwrap();
+        let json = source.export_all().await.unwrap();
+        assert!(!json.contains("profiles"));
+
+        let target = UserPreferenceService::with_schema(Arc::new(MemoryRepository::default()), schema());
+        target.set_bool("theme.dark_mode", true).await.unwrap();
+        target.set_string("keyboard.layout", "de".to_string()).await.unwrap();
+
+        let report = target.import(&json, ImportMode::Replace, true).await.unwrap();
+        assert!(report.dry_run);
+        let keys: Vec<&str> = report.changes.iter().map(|change| change.key.as_str()).collect();
+        assert_eq!(keys, vec!["audio.volume", "keyboard.layout"]);
+        assert_eq!(target.get_integer("audio.volume").await.unwrap(), Some(50), "Probelauf ändert nichts");
+
+        let events = target.subscribe("*");
+        target.import(&json, ImportMode::Merge, false).await.unwrap();
+        assert_eq!(target.get_integer("audio.volume").await.unwrap(), Some(70));
+        assert_eq!(target.get_string("keyboard.layout").await.unwrap().as_deref(), Some("de"));
+        assert_eq!(events.try_iter().count(), 1);
+
+        target.import(&json, ImportMode::Replace, false).await.unwrap();
+        assert_eq!(target.get_string("keyboard.layout").await.unwrap(), None);
+
+        let invalid = json.replace("70", "700");
+        assert!(matches!(target.import(&invalid, ImportMode::Merge, false).await, Err(DomainError::ValidationError { .. })));
+        assert!(target.import("kein json", ImportMode::Merge, true).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_key_migrations_carry_values_forward() {
+        let service = UserPreferenceService::with_schema(Arc::new(MemoryRepository::default()), schema());
+        let repository = &service.preference_repository;
+        repository.set_preference(None, &UserPreferenceSetting::new_string("sound.level", "Pegel", "laut".to_string())).await.unwrap();
+        repository.set_preference(None, &UserPreferenceSetting::new_boolean("theme.dark", "Dunkel", true)).await.unwrap();
+        repository
+            .set_preference(None, &UserPreferenceSetting::new_boolean("profiles.work.theme.dark", "Dunkel", false))
+            .await
+            .unwrap();
+        repository.set_preference(None, &UserPreferenceSetting::new_boolean("theme.old_unused", "Alt", true)).await.unwrap();
+        repository.set_preference(None, &UserPreferenceSetting::new_boolean("theme.unused", "Neu", false)).await.unwrap();
+
+        let migrations = [
+            PreferenceKeyMigration::new("sound.level", "audio.volume").with_transform(|value| match value {
+                PreferenceValue::String(level) if level == "laut" => Some(PreferenceValue::Integer(90)),
+                _ => None,
+            }),
+            PreferenceKeyMigration::new("theme.dark", "theme.dark_mode"),
+            PreferenceKeyMigration::new("theme.old_unused", "theme.unused"),
+        ];
+        let events = service.subscribe("*");
+        let migrated = service.run_key_migrations(&migrations).await.unwrap();
+        assert_eq!(migrated, vec!["sound.level", "profiles.work.theme.dark", "theme.dark", "theme.old_unused"]);
+
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(90));
+        assert_eq!(service.get_preference("audio.volume").await.unwrap().unwrap().group.as_deref(), Some("Audio"));
+        assert_eq!(service.get_bool("theme.dark_mode").await.unwrap(), Some(true));
+        assert_eq!(service.profile("work").await.unwrap().get("theme.dark_mode"), Some(&PreferenceValue::Boolean(false)));
+        assert_eq!(service.get_bool("theme.unused").await.unwrap(), Some(false), "Vorhandener Wert hat Vorrang");
+        assert!(repository.get_preference(None, "theme.old_unused").await.unwrap().is_none());
+        assert!(events.try_iter().any(|change| change.key == "audio.volume"));
+
+        assert!(service.run_key_migrations(&migrations).await.unwrap().is_empty(), "Idempotent");
+    }
+
+    #[tokio::test]
+    async fn test_users_have_separate_preferences() {
+        let service = UserPreferenceService::with_schema(Arc::new(MemoryRepository::default()), schema());
+        let (alice, bob) = (NovaId::new(), NovaId::new());
+
+        service.select_user(Some(alice.clone())).await.unwrap();
+        service.set_value("audio.volume", PreferenceValue::Integer(10)).await.unwrap();
+        service.set_bool("theme.dark_mode", true).await.unwrap();
+
+        let changes = service.select_user(Some(bob.clone())).await.unwrap();
+        assert_eq!(service.current_user(), Some(bob));
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(50));
+        assert_eq!(service.get_bool("theme.dark_mode").await.unwrap(), None);
+        let keys: Vec<&str> = changes.iter().map(|change| change.key.as_str()).collect();
+        assert_eq!(keys, vec!["audio.volume", "theme.dark_mode"]);
+
+        service.select_user(Some(alice)).await.unwrap();
+        assert_eq!(service.get_integer("audio.volume").await.unwrap(), Some(10));
+        assert!(service.select_user(service.current_user()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_rejects_invalid_key() {
+        let mut mock_repo = MockUserPreferenceRepository::new();
+        mock_repo.expect_set_preference().never();
+        let service = UserPreferenceService::new(Arc::new(mock_repo));
+
+        let setting = UserPreferenceSetting::new_boolean("Kein Schlüssel", "X", true);
+        assert!(matches!(service.set_preference(setting).await, Err(DomainError::ValidationError { .. })));
+    }
+}
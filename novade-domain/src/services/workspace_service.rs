@@ -1,58 +1,867 @@
 //! Domänendienst für die Verwaltung von Workspaces.
+//!
+//! Neben dem Anlegen und Abfragen verwaltet der [`WorkspaceService`] die Reihenfolge der
+//! Workspaces (über [`Workspace::index`]) und den aktiven Workspace. Änderungen werden als
+//! [`DomainEvent`]s gemeldet, die z.B. der Compositor in einen Wechsel der angezeigten
+//! Fenster umsetzt. Über [`WorkspaceAssignmentRule`]s bestimmt der Dienst außerdem, auf
+//! welchem Workspace neue Fenster einer Anwendung geöffnet werden, und hält fest, welchem
+//! Workspace die laufenden Anwendungen zugeordnet sind ([`Workspace::applications`]).
+//!
+//! Bei jedem Wechsel des aktiven Workspaces hält der Dienst fest, wann ein Workspace zuletzt
+//! aktiviert wurde und wie lange er insgesamt aktiv war (siehe [`Workspace::last_activated_at`]
+//! und [`Workspace::active_duration`]), z.B. für die Sortierung im Workspace-Umschalter.
+//!
+//! Workspaces können archiviert statt gelöscht werden ([`WorkspaceService::archive_workspace`]).
+//! Archivierte Workspaces werden nicht mehr aufgelistet oder aktiviert, bleiben aber
+//! gespeichert, bis sie wiederhergestellt oder mit [`WorkspaceService::purge_workspace`]
+//! endgültig gelöscht werden.
 
-use crate::entities::workspace::Workspace;
+use crate::entities::application::Application;
+use crate::entities::audit_record::AuditOperation;
+use crate::entities::workspace::{Workspace, WorkspaceAssignmentRule};
+use crate::entities::Entity;
+use crate::events::{DomainEvent, EventPublisher};
+use crate::services::audit_service::AuditService;
+use crate::services::dry_run::DryRun;
+use crate::services::history_service::{HistoryService, UndoableCommand, WORKSPACE_HISTORY};
+use crate::services::metrics::{self, NoopMetrics, ServiceMetrics};
+use async_trait::async_trait;
+use crate::repositories::application_repository::ApplicationRepository;
+use crate::repositories::display_layout_repository::DisplayLayoutRepository;
+use crate::repositories::paging::{Page, PagedResult};
 use crate::repositories::workspace_repository::WorkspaceRepository;
+use crate::validation::Validate;
 use crate::{DomainError, DomainResult};
-use novade_core::types::NovaId;
+use novade_core::signal::Signal;
+use novade_core::types::{NovaId, Timestamp};
 use novade_core::info; // Logging
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Ergebnis von [`WorkspaceService::delete_workspace`]: der gelöschte Workspace und was auf den
+/// Ziel-Workspace übergeht.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceRemovalReport {
+    pub workspace: Workspace,
+    /// Der Workspace, der die Inhalte übernimmt.
+    pub target: NovaId,
+    /// Die Zuordnungsregeln, die auf das Ziel übergehen (ohne die, die es bereits hat).
+    pub moved_rules: Vec<WorkspaceAssignmentRule>,
+    /// Die Anwendungen, die auf das Ziel übergehen (ohne die, die ihm bereits zugeordnet sind).
+    pub moved_applications: Vec<NovaId>,
+    /// Ob der Workspace aktiv ist und deshalb das Ziel aktiviert wird.
+    pub activates_target: bool,
+}
 
 pub struct WorkspaceService {
     workspace_repository: Arc<dyn WorkspaceRepository>,
+    application_repository: Option<Arc<dyn ApplicationRepository>>,
+    display_layout_repository: Option<Arc<dyn DisplayLayoutRepository>>,
+    active_workspace: Mutex<Option<NovaId>>,
+    /// Seit wann die noch nicht gutgeschriebene Aktivzeit des aktiven Workspaces läuft.
+    active_since: Mutex<Option<Timestamp>>,
+    changes: Signal<DomainEvent>,
+    events: Option<Arc<dyn EventPublisher>>,
+    history: Option<Arc<HistoryService>>,
+    audit: Option<Arc<AuditService>>,
+    metrics: Arc<dyn ServiceMetrics>,
 }
 
 impl WorkspaceService {
     pub fn new(workspace_repository: Arc<dyn WorkspaceRepository>) -> Self {
-        Self { workspace_repository }
+        Self {
+            workspace_repository,
+            application_repository: None,
+            display_layout_repository: None,
+            active_workspace: Mutex::new(None),
+            active_since: Mutex::new(None),
+            changes: Signal::new(),
+            events: None,
+            history: None,
+            audit: None,
+            metrics: Arc::new(NoopMetrics),
+        }
+    }
+
+    /// Meldet alle Ereignisse des Dienstes zusätzlich an `events`.
+    pub fn with_event_publisher(mut self, events: Arc<dyn EventPublisher>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Prüft beim Zuordnen von Anwendungen ([`assign_application`](Self::assign_application)),
+    /// ob sie in `application_repository` existieren. Ohne Repository ist keine Zuordnung möglich.
+    pub fn with_application_repository(mut self, application_repository: Arc<dyn ApplicationRepository>) -> Self {
+        self.application_repository = Some(application_repository);
+        self
+    }
+
+    /// Prüft beim Anlegen von Workspaces und beim Ändern ihres primären Bildschirms, ob der
+    /// Bildschirm in einem der Layouts von `display_layout_repository` konfiguriert ist. Ohne
+    /// Repository wird jeder Bildschirm akzeptiert.
+    pub fn with_display_layout_repository(mut self, display_layout_repository: Arc<dyn DisplayLayoutRepository>) -> Self {
+        self.display_layout_repository = Some(display_layout_repository);
+        self
+    }
+
+    /// Zeichnet das Löschen von Workspaces in `history` auf ([`WORKSPACE_HISTORY`]).
+    pub fn with_history(mut self, history: Arc<HistoryService>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Hält jede Änderung an Workspaces im Änderungsprotokoll von `audit` fest. Ausgenommen ist
+    /// die Aktivzeit, die bei jedem Wechsel des aktiven Workspaces fortgeschrieben wird.
+    pub fn with_audit_log(mut self, audit: Arc<AuditService>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Meldet Dauer und Ergebnis jedes Aufrufs an `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<dyn ServiceMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    async fn measure<T>(&self, method: &'static str, future: impl Future<Output = DomainResult<T>>) -> DomainResult<T> {
+        metrics::measure(&*self.metrics, "WorkspaceService", method, future).await
+    }
+
+    /// Abonniert die Ereignisse des Dienstes, die `Workspace*`-Varianten von [`DomainEvent`].
+    ///
+    /// Das Abonnement endet, sobald der `Receiver` verworfen wird.
+    pub fn subscribe(&self) -> Receiver<DomainEvent> {
+        self.changes.subscribe()
+    }
+
+    fn emit(&self, event: DomainEvent) {
+        self.changes.emit(event.clone());
+        if let Some(events) = &self.events {
+            events.publish(event);
+        }
     }
 
+    async fn audit(&self, workspace: &Workspace, operation: AuditOperation, summary: String) {
+        if let Some(audit) = &self.audit {
+            audit.record(Workspace::ENTITY_TYPE, &workspace.id.to_string(), operation, summary).await;
+        }
+    }
+
+    async fn audit_update(&self, before: &Workspace, after: &Workspace) {
+        if let Some(audit) = &self.audit {
+            audit.record_update(Workspace::ENTITY_TYPE, &after.id.to_string(), before, after).await;
+        }
+    }
+
+    /// Legt einen Workspace an und hängt ihn hinten an die Reihenfolge an.
+    ///
+    /// # Rückgabe
+    /// Der neue Workspace, `DomainError::ValidationError` bei leerem Namen oder
+    /// `DomainError::Conflict` mit der ID des (ggf. archivierten) Workspaces, der den Namen
+    /// bereits trägt.
     pub async fn create_new_workspace(&self, name: String, primary_output_id: Option<String>) -> DomainResult<Workspace> {
-        if name.trim().is_empty() {
-            return Err(DomainError::ValidationError {
-                field: "name".to_string(),
-                message: "Workspace-Name darf nicht leer sein.".to_string(),
+        self.measure("create_new_workspace", async move {
+            let mut workspace = Workspace::new(name.clone(), primary_output_id);
+            workspace.validate()?;
+            self.check_primary_output(&workspace).await?;
+            // Prüfen, ob ein Workspace mit diesem Namen bereits existiert
+            // Archivierte Workspaces behalten ihren Namen, damit sie wiederhergestellt werden können.
+            if let Some(existing) = self.workspace_repository.get_by_name(&name).await? {
+                return Err(DomainError::conflict::<Workspace>("name", &existing.id));
+            }
+
+            // Neue Workspaces werden hinten angehängt.
+            workspace.index = self.workspace_repository.get_all_including_archived().await?.iter().map(|ws| ws.index + 1).max().unwrap_or(0);
+            info!(workspace_id = %workspace.id, workspace_name = %workspace.name, "Erstelle neuen Workspace.");
+            self.workspace_repository.add(&workspace).await?;
+            self.audit(&workspace, AuditOperation::Created, format!("Workspace '{}' angelegt.", workspace.name)).await;
+            self.emit(DomainEvent::WorkspaceCreated(workspace.clone()));
+            Ok(workspace)
+        })
+        .await
+    }
+
+    /// Löscht einen Workspace und ordnet seine Inhalte einem anderen Workspace zu.
+    ///
+    /// Die Zuordnungsregeln und Anwendungen des gelöschten Workspaces gehen auf den
+    /// Ziel-Workspace über, seine Fenster verschiebt der Compositor auf das gemeldete
+    /// [`DomainEvent::WorkspaceRemoved`] hin.
+    /// War der gelöschte Workspace aktiv, wird der Ziel-Workspace aktiviert.
+    ///
+    /// Mit einem [`HistoryService`] kann das Löschen rückgängig gemacht werden; der Workspace
+    /// wird dann mit derselben ID, Position und denselben Regeln und Anwendungen wiederhergestellt.
+    ///
+    /// # Parameter
+    /// * `reassign_to`: Der Ziel-Workspace; ohne Angabe der in der Reihenfolge vorherige
+    ///   (bzw. für den ersten der nächste) Workspace.
+    /// * `dry_run`: Mit [`DryRun::Yes`] wird nur berichtet, was sich ändern würde.
+    ///
+    /// # Rückgabe
+    /// Der Bericht über das Löschen, `DomainError::EntityNotFound` für einen unbekannten
+    /// Workspace oder ein unbekanntes Ziel, oder `DomainError::OperationNotPermitted`, wenn der
+    /// letzte Workspace gelöscht oder ein Workspace sich selbst zugeordnet werden soll.
+    pub async fn delete_workspace(
+        self: &Arc<Self>,
+        id: &NovaId,
+        reassign_to: Option<&NovaId>,
+        dry_run: DryRun,
+    ) -> DomainResult<WorkspaceRemovalReport> {
+        self.measure("delete_workspace", async move {
+            let report = self.remove_workspace(id, reassign_to, false, dry_run).await?;
+            if let (Some(history), DryRun::No) = (&self.history, dry_run) {
+                history.record(
+                    WORKSPACE_HISTORY,
+                    Box::new(DeleteWorkspaceCommand { service: Arc::downgrade(self), report: report.clone() }),
+                );
+            }
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Archiviert einen Workspace und ordnet seine Inhalte einem anderen Workspace zu, wie
+    /// [`delete_workspace`](Self::delete_workspace). Der Workspace bleibt jedoch gespeichert und
+    /// kann mit [`restore_workspace`](Self::restore_workspace) wiederhergestellt werden.
+    ///
+    /// # Rückgabe
+    /// Der archivierte Workspace oder dieselben Fehler wie [`delete_workspace`](Self::delete_workspace).
+    pub async fn archive_workspace(&self, id: &NovaId, reassign_to: Option<&NovaId>) -> DomainResult<Workspace> {
+        self.measure("archive_workspace", async move {
+            self.remove_workspace(id, reassign_to, true, DryRun::No).await.map(|report| report.workspace)
+        })
+        .await
+    }
+
+    /// Stellt einen archivierten Workspace wieder her und hängt ihn hinten an die Reihenfolge an.
+    ///
+    /// Zuordnungsregeln und Anwendungen, die inzwischen einem anderen Workspace gehören (z.B.
+    /// weil sie beim Archivieren dorthin verschoben wurden), bleiben dort. Ein nicht archivierter
+    /// Workspace bleibt unverändert.
+    ///
+    /// # Rückgabe
+    /// Der wiederhergestellte Workspace oder `DomainError::EntityNotFound`.
+    pub async fn restore_workspace(&self, id: &NovaId) -> DomainResult<Workspace> {
+        self.measure("restore_workspace", async move {
+            let mut workspace = self.workspace_repository.get_by_id(id).await?.ok_or_else(|| Self::not_found(id))?;
+            if !workspace.is_archived() {
+                return Ok(workspace);
+            }
+            let workspaces = self.list_all_workspaces().await?;
+            workspace.assignment_rules.retain(|rule| !workspaces.iter().any(|ws| ws.assignment_rules.contains(rule)));
+            workspace.applications.retain(|app_id| !workspaces.iter().any(|ws| ws.applications.contains(app_id)));
+            workspace.index = workspaces.iter().map(|ws| ws.index + 1).max().unwrap_or(0);
+            workspace.deleted_at = None;
+            info!(workspace_id = %id, workspace_name = %workspace.name, "Stelle archivierten Workspace wieder her.");
+            self.update_workspace(&workspace).await?;
+            self.audit(&workspace, AuditOperation::Restored, format!("Workspace '{}' wiederhergestellt.", workspace.name)).await;
+            self.emit(DomainEvent::WorkspaceRestored(workspace.clone()));
+            Ok(workspace)
+        })
+        .await
+    }
+
+    /// Löscht einen archivierten Workspace endgültig.
+    ///
+    /// # Rückgabe
+    /// `DomainError::EntityNotFound` oder `DomainError::OperationNotPermitted`, wenn der
+    /// Workspace nicht archiviert ist.
+    pub async fn purge_workspace(&self, id: &NovaId) -> DomainResult<()> {
+        self.measure("purge_workspace", async move {
+            let workspace = self.workspace_repository.get_by_id(id).await?.ok_or_else(|| Self::not_found(id))?;
+            if !workspace.is_archived() {
+                return Err(DomainError::OperationNotPermitted {
+                    operation: "purge_workspace".to_string(),
+                    reason: format!("Der Workspace '{}' muss vor dem endgültigen Löschen archiviert werden.", workspace.name),
+                });
+            }
+            info!(workspace_id = %id, workspace_name = %workspace.name, "Lösche archivierten Workspace endgültig.");
+            self.workspace_repository.remove(id).await?;
+            self.audit(&workspace, AuditOperation::Deleted, format!("Workspace '{}' endgültig gelöscht.", workspace.name)).await;
+            self.emit(DomainEvent::WorkspacePurged { id: id.clone() });
+            Ok(())
+        })
+        .await
+    }
+
+    /// Die archivierten Workspaces.
+    pub async fn list_archived_workspaces(&self) -> DomainResult<Vec<Workspace>> {
+        self.measure("list_archived_workspaces", async move {
+            info!("Auflistung der archivierten Workspaces angefordert.");
+            self.workspace_repository.get_archived().await
+        })
+        .await
+    }
+
+    /// Löscht bzw. archiviert (`archive`) den Workspace `id` (siehe
+    /// [`delete_workspace`](Self::delete_workspace)).
+    ///
+    /// # Rückgabe
+    /// Der gelöschte bzw. archivierte Workspace, die ID des Ziel-Workspaces und die dorthin
+    /// verschobenen Inhalte.
+    async fn remove_workspace(
+        &self,
+        id: &NovaId,
+        reassign_to: Option<&NovaId>,
+        archive: bool,
+        dry_run: DryRun,
+    ) -> DomainResult<WorkspaceRemovalReport> {
+        let operation = if archive { "archive_workspace" } else { "delete_workspace" };
+        let workspaces = self.list_all_workspaces().await?;
+        let position = workspaces.iter().position(|ws| &ws.id == id).ok_or_else(|| Self::not_found(id))?;
+        if workspaces.len() == 1 {
+            return Err(DomainError::OperationNotPermitted {
+                operation: operation.to_string(),
+                reason: "Der letzte Workspace kann nicht gelöscht werden.".to_string(),
             });
         }
-        // Prüfen, ob ein Workspace mit diesem Namen bereits existiert
-        if self.workspace_repository.get_by_name(&name).await?.is_some() {
-            return Err(DomainError::OperationNotPermitted {
-                operation: "create_workspace".to_string(),
-                reason: format!("Ein Workspace mit dem Namen '{}' existiert bereits.", name),
+        let target_id = match reassign_to {
+            Some(target_id) if target_id == id => {
+                return Err(DomainError::OperationNotPermitted {
+                    operation: operation.to_string(),
+                    reason: "Die Inhalte eines Workspaces können nicht ihm selbst zugeordnet werden.".to_string(),
+                });
+            }
+            Some(target_id) => target_id,
+            None => &workspaces[if position == 0 { 1 } else { position - 1 }].id,
+        };
+        let mut target = workspaces.iter().find(|ws| &ws.id == target_id).cloned().ok_or_else(|| Self::not_found(target_id))?;
+        let mut workspace = workspaces[position].clone();
+
+        let moved_rules: Vec<WorkspaceAssignmentRule> =
+            workspace.assignment_rules.iter().filter(|rule| !target.assignment_rules.contains(rule)).cloned().collect();
+        let moved_applications: Vec<NovaId> =
+            workspace.applications.iter().filter(|app_id| !target.applications.contains(app_id)).cloned().collect();
+        if dry_run.is_dry_run() {
+            return Ok(WorkspaceRemovalReport {
+                activates_target: self.active_workspace.lock().unwrap().as_ref() == Some(id),
+                workspace,
+                target: target.id,
+                moved_rules,
+                moved_applications,
             });
         }
 
-        let workspace = Workspace::new(name.clone(), primary_output_id);
-        info!(workspace_id = %workspace.id, workspace_name = %workspace.name, "Erstelle neuen Workspace.");
-        self.workspace_repository.add(&workspace).await?;
-        Ok(workspace)
+        info!(workspace_id = %id, workspace_name = %workspace.name, reassigned_to = %target.id, archive, "Lösche Workspace.");
+        let was_active = {
+            let mut active = self.active_workspace.lock().unwrap();
+            let was_active = active.as_ref() == Some(id);
+            if was_active {
+                *active = Some(target.id.clone());
+            }
+            was_active
+        };
+        let (operation, verb) = if archive {
+            workspace.deleted_at = Some(Timestamp::now());
+            self.update_workspace(&workspace).await?;
+            (AuditOperation::Archived, "archiviert")
+        } else {
+            self.workspace_repository.remove(id).await?;
+            (AuditOperation::Deleted, "gelöscht")
+        };
+        let summary = format!("Workspace '{}' {}, Inhalte an '{}' übergeben.", workspace.name, verb, target.name);
+        self.audit(&workspace, operation, summary).await;
+        if was_active {
+            let now = Timestamp::now();
+            *self.active_since.lock().unwrap() = Some(now.clone());
+            target.last_activated_at = Some(now);
+        }
+        if !moved_rules.is_empty() || !moved_applications.is_empty() || was_active {
+            let before = target.clone();
+            target.assignment_rules.extend(moved_rules.iter().cloned());
+            target.applications.extend(moved_applications.iter().cloned());
+            self.update_workspace(&target).await?;
+            self.audit_update(&before, &target).await;
+        }
+        self.emit(DomainEvent::WorkspaceRemoved { id: id.clone(), reassigned_to: target.id.clone() });
+        if was_active {
+            self.emit(DomainEvent::WorkspaceActivated { previous: Some(id.clone()), workspace: target.clone() });
+        }
+        Ok(WorkspaceRemovalReport { workspace, target: target.id, moved_rules, moved_applications, activates_target: was_active })
+    }
+
+    /// Benennt einen Workspace um.
+    ///
+    /// # Rückgabe
+    /// Der umbenannte Workspace, `DomainError::ValidationError` bei leerem Namen,
+    /// `DomainError::Conflict`, wenn ein anderer Workspace den Namen bereits trägt, oder
+    /// `DomainError::EntityNotFound`.
+    pub async fn rename_workspace(&self, id: &NovaId, new_name: String) -> DomainResult<Workspace> {
+        self.measure("rename_workspace", async move {
+            if new_name.trim().is_empty() {
+                return Err(DomainError::ValidationError {
+                    field: "name".to_string(),
+                    message: "Workspace-Name darf nicht leer sein.".to_string(),
+                });
+            }
+            let mut workspace = self.workspace_repository.get_by_id(id).await?.ok_or_else(|| Self::not_found(id))?;
+            if workspace.name == new_name {
+                return Ok(workspace);
+            }
+            if let Some(other) = self.workspace_repository.get_by_name(&new_name).await?.filter(|other| &other.id != id) {
+                return Err(DomainError::conflict::<Workspace>("name", &other.id));
+            }
+            let before = workspace.clone();
+            let old_name = std::mem::replace(&mut workspace.name, new_name.clone());
+            info!(workspace_id = %id, %old_name, %new_name, "Benenne Workspace um.");
+            self.update_workspace(&workspace).await?;
+            self.audit_update(&before, &workspace).await;
+            self.emit(DomainEvent::WorkspaceRenamed { id: id.clone(), old_name, new_name });
+            Ok(workspace)
+        })
+        .await
+    }
+
+    /// Setzt den primären Bildschirm eines Workspaces bzw. entfernt ihn mit `None`.
+    ///
+    /// # Rückgabe
+    /// Der aktualisierte Workspace, `DomainError::EntityNotFound` oder
+    /// `DomainError::IntegrityViolation`, wenn der Bildschirm in keinem gespeicherten Layout
+    /// konfiguriert ist (siehe [`with_display_layout_repository`](Self::with_display_layout_repository)).
+    pub async fn set_primary_output(&self, id: &NovaId, primary_output_id: Option<String>) -> DomainResult<Workspace> {
+        self.measure("set_primary_output", async move {
+            let mut workspace = self.workspace_repository.get_by_id(id).await?.ok_or_else(|| Self::not_found(id))?;
+            if workspace.primary_output_id == primary_output_id {
+                return Ok(workspace);
+            }
+            let before = workspace.clone();
+            workspace.primary_output_id = primary_output_id;
+            self.check_primary_output(&workspace).await?;
+            info!(workspace_id = %id, primary_output_id = ?workspace.primary_output_id, "Ändere primären Bildschirm des Workspaces.");
+            self.update_workspace(&workspace).await?;
+            self.audit_update(&before, &workspace).await;
+            Ok(workspace)
+        })
+        .await
+    }
+
+    /// Prüft, ob der primäre Bildschirm des Workspaces in einem gespeicherten Layout
+    /// konfiguriert ist.
+    async fn check_primary_output(&self, workspace: &Workspace) -> DomainResult<()> {
+        let (Some(output), Some(layouts)) = (&workspace.primary_output_id, &self.display_layout_repository) else {
+            return Ok(());
+        };
+        if layouts.get_all().await?.iter().any(|layout| layout.output(output).is_some()) {
+            return Ok(());
+        }
+        Err(DomainError::IntegrityViolation {
+            entity_type: Workspace::ENTITY_TYPE.to_string(),
+            entity_id: workspace.id.to_string(),
+            reason: format!("Der Bildschirm '{}' ist in keinem gespeicherten Bildschirm-Layout konfiguriert.", output),
+        })
     }
 
+    /// Alle nicht archivierten Workspaces, sortiert nach [`Workspace::index`].
     pub async fn list_all_workspaces(&self) -> DomainResult<Vec<Workspace>> {
-        info!("Auflistung aller Workspaces angefordert.");
-        self.workspace_repository.get_all().await
+        self.measure("list_all_workspaces", async move {
+            info!("Auflistung aller Workspaces angefordert.");
+            let mut workspaces = self.workspace_repository.get_all().await?;
+            workspaces.sort_by_key(|ws| ws.index);
+            Ok(workspaces)
+        })
+        .await
     }
     
+    /// Listet eine Seite der Workspaces auf. Ohne Sortierfeld wird nach [`Workspace::index`] sortiert.
+    pub async fn list_workspaces_page(&self, page: &Page) -> DomainResult<PagedResult<Workspace>> {
+        self.measure("list_workspaces_page", async move {
+            match page.sort_by {
+                Some(_) => self.workspace_repository.get_page(page).await,
+                None => self.workspace_repository.get_page(&Page { sort_by: Some("index".to_string()), ..page.clone() }).await,
+            }
+        })
+        .await
+    }
+
     pub async fn get_workspace_details(&self, id: &NovaId) -> DomainResult<Option<Workspace>> {
-        info!(workspace_id = %id, "Details für Workspace angefordert.");
-        self.workspace_repository.get_by_id(id).await
+        self.measure("get_workspace_details", async move {
+            info!(workspace_id = %id, "Details für Workspace angefordert.");
+            self.workspace_repository.get_by_id(id).await
+        })
+        .await
+    }
+
+    /// Verschiebt einen Workspace an die Position `position` (0-basiert, wird auf das Ende
+    /// begrenzt) und nummeriert alle Workspaces lückenlos neu.
+    ///
+    /// # Rückgabe
+    /// Die Workspaces in neuer Reihenfolge oder `DomainError::EntityNotFound`.
+    pub async fn move_workspace(&self, id: &NovaId, position: usize) -> DomainResult<Vec<Workspace>> {
+        self.measure("move_workspace", async move {
+            let mut workspaces = self.list_all_workspaces().await?;
+            let current = workspaces.iter().position(|ws| &ws.id == id).ok_or_else(|| Self::not_found(id))?;
+            let workspace = workspaces.remove(current);
+            workspaces.insert(position.min(workspaces.len()), workspace);
+            for (index, workspace) in workspaces.iter_mut().enumerate() {
+                let index = index as u32;
+                if workspace.index != index {
+                    let before = workspace.clone();
+                    workspace.index = index;
+                    self.update_workspace(workspace).await?;
+                    self.audit_update(&before, workspace).await;
+                }
+            }
+            info!(workspace_id = %id, position, "Workspace verschoben.");
+            self.emit(DomainEvent::WorkspacesReordered(workspaces.iter().map(|ws| ws.id.clone()).collect()));
+            Ok(workspaces)
+        })
+        .await
+    }
+
+    /// Der aktive Workspace, sofern einer aktiviert wurde und noch existiert.
+    pub async fn active_workspace(&self) -> DomainResult<Option<Workspace>> {
+        self.measure("active_workspace", async move {
+            let active = self.active_workspace.lock().unwrap().clone();
+            match active {
+                Some(id) => self.workspace_repository.get_by_id(&id).await,
+                None => Ok(None),
+            }
+        })
+        .await
+    }
+
+    /// Aktiviert einen Workspace. Ist er bereits aktiv, wird kein Ereignis gemeldet.
+    ///
+    /// Dem bisher aktiven Workspace wird seine Aktivzeit gutgeschrieben, der aktivierte erhält
+    /// einen neuen [`Workspace::last_activated_at`].
+    ///
+    /// # Rückgabe
+    /// Der aktivierte Workspace, `DomainError::EntityNotFound` oder
+    /// `DomainError::OperationNotPermitted` für einen archivierten Workspace.
+    pub async fn activate_workspace(&self, id: &NovaId) -> DomainResult<Workspace> {
+        self.measure("activate_workspace", async move {
+            let mut workspace = self.workspace_repository.get_by_id(id).await?.ok_or_else(|| Self::not_found(id))?;
+            if workspace.is_archived() {
+                return Err(DomainError::OperationNotPermitted {
+                    operation: "activate_workspace".to_string(),
+                    reason: format!("Der Workspace '{}' ist archiviert.", workspace.name),
+                });
+            }
+            let previous = self.active_workspace.lock().unwrap().replace(id.clone());
+            if previous.as_ref() != Some(id) {
+                info!(workspace_id = %id, workspace_name = %workspace.name, "Aktiviere Workspace.");
+                let now = Timestamp::now();
+                if let Some(previous) = &previous {
+                    self.add_active_time(previous, &now).await?;
+                }
+                *self.active_since.lock().unwrap() = Some(now.clone());
+                workspace.last_activated_at = Some(now);
+                self.update_workspace(&workspace).await?;
+                self.emit(DomainEvent::WorkspaceActivated { previous, workspace: workspace.clone() });
+            }
+            Ok(workspace)
+        })
+        .await
+    }
+
+    /// Schreibt dem aktiven Workspace die Aktivzeit seit seiner Aktivierung bzw. seit dem
+    /// letzten Aufruf gut, ohne den Workspace zu wechseln. Der Compositor ruft dies z.B. vor
+    /// dem Sperren des Bildschirms oder dem Beenden der Sitzung auf.
+    ///
+    /// # Rückgabe
+    /// Der aktive Workspace oder `None`, wenn keiner aktiv ist.
+    pub async fn record_active_time(&self) -> DomainResult<Option<Workspace>> {
+        self.measure("record_active_time", async move {
+            let active = self.active_workspace.lock().unwrap().clone();
+            let Some(id) = active else { return Ok(None) };
+            self.add_active_time(&id, &Timestamp::now()).await?;
+            self.workspace_repository.get_by_id(&id).await
+        })
+        .await
+    }
+
+    /// Die Workspaces, zuletzt aktivierte zuerst; nie aktivierte folgen in ihrer Reihenfolge.
+    pub async fn list_workspaces_by_recency(&self) -> DomainResult<Vec<Workspace>> {
+        self.measure("list_workspaces_by_recency", async move {
+            let mut workspaces = self.list_all_workspaces().await?;
+            workspaces.sort_by(|a, b| b.last_activated_at.cmp(&a.last_activated_at));
+            Ok(workspaces)
+        })
+        .await
+    }
+
+    /// Schreibt dem Workspace `id` die Zeit seit `active_since` bis `now` gut; die Aktivzeit
+    /// läuft danach ab `now`.
+    async fn add_active_time(&self, id: &NovaId, now: &Timestamp) -> DomainResult<()> {
+        let since = self.active_since.lock().unwrap().replace(now.clone());
+        let Some(since) = since else { return Ok(()) };
+        if let Some(mut workspace) = self.workspace_repository.get_by_id(id).await? {
+            let elapsed = now.as_datetime().signed_duration_since(*since.as_datetime());
+            workspace.active_duration += elapsed.to_std().unwrap_or_default();
+            self.update_workspace(&workspace).await?;
+        }
+        Ok(())
+    }
+
+    /// Aktiviert den nächsten Workspace in der Reihenfolge; nach dem letzten folgt der erste.
+    /// Ist kein Workspace aktiv, wird der erste aktiviert.
+    ///
+    /// # Rückgabe
+    /// Der aktivierte Workspace oder `None`, wenn es keine Workspaces gibt.
+    pub async fn next_workspace(&self) -> DomainResult<Option<Workspace>> {
+        self.measure("next_workspace", async move {
+            self.step_workspace(true).await
+        })
+        .await
+    }
+
+    /// Aktiviert den vorherigen Workspace in der Reihenfolge; vor dem ersten liegt der letzte.
+    /// Ist kein Workspace aktiv, wird der letzte aktiviert.
+    ///
+    /// # Rückgabe
+    /// Der aktivierte Workspace oder `None`, wenn es keine Workspaces gibt.
+    pub async fn previous_workspace(&self) -> DomainResult<Option<Workspace>> {
+        self.measure("previous_workspace", async move {
+            self.step_workspace(false).await
+        })
+        .await
+    }
+
+    async fn step_workspace(&self, forward: bool) -> DomainResult<Option<Workspace>> {
+        let workspaces = self.list_all_workspaces().await?;
+        if workspaces.is_empty() {
+            return Ok(None);
+        }
+        let active = self.active_workspace.lock().unwrap().clone();
+        let current = active.and_then(|id| workspaces.iter().position(|ws| ws.id == id));
+        let count = workspaces.len();
+        let target = match (current, forward) {
+            (Some(current), true) => (current + 1) % count,
+            (Some(current), false) => (current + count - 1) % count,
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+        self.activate_workspace(&workspaces[target].id).await.map(Some)
+    }
+
+    /// Fügt einem Workspace eine Zuordnungsregel hinzu. Ist die Regel dort bereits vorhanden,
+    /// bleibt der Workspace unverändert.
+    ///
+    /// # Rückgabe
+    /// Der aktualisierte Workspace, `DomainError::ValidationError` für eine Regel ohne Wert,
+    /// `DomainError::Conflict` mit der ID des Workspaces, dem dieselbe Regel bereits zugeordnet
+    /// ist, oder `DomainError::EntityNotFound`.
+    pub async fn add_assignment_rule(&self, workspace_id: &NovaId, rule: WorkspaceAssignmentRule) -> DomainResult<Workspace> {
+        self.measure("add_assignment_rule", async move {
+            if rule.value().trim().is_empty() {
+                return Err(DomainError::ValidationError {
+                    field: "rule".to_string(),
+                    message: "Eine Zuordnungsregel braucht eine App-ID bzw. Kategorie.".to_string(),
+                });
+            }
+            let workspaces = self.workspace_repository.get_all_including_archived().await?;
+            if let Some(other) =
+                workspaces.iter().find(|ws| &ws.id != workspace_id && !ws.is_archived() && ws.assignment_rules.contains(&rule))
+            {
+                return Err(DomainError::conflict::<Workspace>("assignment_rules", &other.id));
+            }
+            let mut workspace = workspaces.into_iter().find(|ws| &ws.id == workspace_id).ok_or_else(|| Self::not_found(workspace_id))?;
+            if !workspace.assignment_rules.contains(&rule) {
+                info!(workspace_id = %workspace_id, ?rule, "Füge Zuordnungsregel hinzu.");
+                let before = workspace.clone();
+                workspace.assignment_rules.push(rule);
+                self.update_workspace(&workspace).await?;
+                self.audit_update(&before, &workspace).await;
+            }
+            Ok(workspace)
+        })
+        .await
+    }
+
+    /// Entfernt eine Zuordnungsregel von einem Workspace.
+    ///
+    /// # Rückgabe
+    /// Der aktualisierte Workspace oder `DomainError::EntityNotFound`.
+    pub async fn remove_assignment_rule(&self, workspace_id: &NovaId, rule: &WorkspaceAssignmentRule) -> DomainResult<Workspace> {
+        self.measure("remove_assignment_rule", async move {
+            let mut workspace = self.workspace_repository.get_by_id(workspace_id).await?.ok_or_else(|| Self::not_found(workspace_id))?;
+            let before = workspace.clone();
+            workspace.assignment_rules.retain(|existing| existing != rule);
+            if workspace.assignment_rules != before.assignment_rules {
+                info!(workspace_id = %workspace_id, ?rule, "Entferne Zuordnungsregel.");
+                self.update_workspace(&workspace).await?;
+                self.audit_update(&before, &workspace).await;
+            }
+            Ok(workspace)
+        })
+        .await
+    }
+
+    /// Der Workspace, auf dem neue Fenster der Anwendung geöffnet werden sollen.
+    ///
+    /// Regeln für die App-ID haben Vorrang vor Kategorieregeln; treffen mehrere Regeln
+    /// derselben Art zu, gewinnt der Workspace, der in der Reihenfolge zuerst kommt.
+    ///
+    /// # Rückgabe
+    /// Der Workspace oder `None`, wenn keine Regel zutrifft (das Fenster öffnet dann auf dem
+    /// aktiven Workspace).
+    pub async fn workspace_for_application(&self, application: &Application) -> DomainResult<Option<Workspace>> {
+        self.measure("workspace_for_application", async move {
+            let workspaces = self.list_all_workspaces().await?;
+            let by_app_id = workspaces.iter().find(|ws| {
+                ws.assignment_rules
+                    .iter()
+                    .any(|rule| matches!(rule, WorkspaceAssignmentRule::ApplicationId(_)) && rule.matches(application))
+            });
+            let by_category = || {
+                workspaces.iter().find(|ws| {
+                    ws.assignment_rules
+                        .iter()
+                        .any(|rule| matches!(rule, WorkspaceAssignmentRule::Category(_)) && rule.matches(application))
+                })
+            };
+            Ok(by_app_id.or_else(by_category).cloned())
+        })
+        .await
+    }
+
+    /// Ordnet die Anwendung `application_id` dem Workspace `workspace_id` zu. War sie einem
+    /// anderen Workspace zugeordnet, wird sie von dort entfernt; ist sie dem Workspace bereits
+    /// zugeordnet, bleibt alles unverändert.
+    ///
+    /// # Rückgabe
+    /// Der Workspace, `DomainError::EntityNotFound` für einen unbekannten Workspace oder eine
+    /// unbekannte Anwendung, oder `DomainError::OperationNotPermitted` für einen archivierten
+    /// Workspace oder einen Dienst ohne [`ApplicationRepository`].
+    pub async fn assign_application(&self, workspace_id: &NovaId, application_id: &NovaId) -> DomainResult<Workspace> {
+        self.measure("assign_application", async move {
+            let workspaces = self.list_all_workspaces().await?;
+            let from = workspaces.iter().find(|ws| ws.applications.contains(application_id)).map(|ws| ws.id.clone());
+            self.reassign_application(workspaces, application_id, from.as_ref(), workspace_id, "assign_application").await
+        })
+        .await
+    }
+
+    /// Verschiebt die Anwendung `application_id` vom Workspace `from` auf den Workspace `to`.
+    ///
+    /// # Rückgabe
+    /// Der Workspace `to`, dieselben Fehler wie [`assign_application`](Self::assign_application)
+    /// oder `DomainError::OperationNotPermitted`, wenn die Anwendung nicht `from` zugeordnet ist.
+    pub async fn move_assignment(&self, application_id: &NovaId, from: &NovaId, to: &NovaId) -> DomainResult<Workspace> {
+        self.measure("move_assignment", async move {
+            let workspaces = self.list_all_workspaces().await?;
+            let source = workspaces.iter().find(|ws| &ws.id == from).ok_or_else(|| Self::not_found(from))?;
+            if !source.applications.contains(application_id) {
+                return Err(DomainError::OperationNotPermitted {
+                    operation: "move_assignment".to_string(),
+                    reason: format!("Die Anwendung {} ist nicht dem Workspace '{}' zugeordnet.", application_id, source.name),
+                });
+            }
+            self.reassign_application(workspaces, application_id, Some(from), to, "move_assignment").await
+        })
+        .await
+    }
+
+    /// Entfernt die Anwendung von `from` und fügt sie `to` hinzu; `workspaces` sind die nicht
+    /// archivierten Workspaces.
+    async fn reassign_application(
+        &self,
+        workspaces: Vec<Workspace>,
+        application_id: &NovaId,
+        from: Option<&NovaId>,
+        to: &NovaId,
+        operation: &str,
+    ) -> DomainResult<Workspace> {
+        let Some(mut target) = workspaces.iter().find(|ws| &ws.id == to).cloned() else {
+            let archived = self.workspace_repository.get_by_id(to).await?.ok_or_else(|| Self::not_found(to))?;
+            return Err(DomainError::OperationNotPermitted {
+                operation: operation.to_string(),
+                reason: format!("Der Workspace '{}' ist archiviert.", archived.name),
+            });
+        };
+        let application_repository = self.application_repository.as_ref().ok_or_else(|| DomainError::OperationNotPermitted {
+            operation: operation.to_string(),
+            reason: "Ohne Anwendungs-Repository können keine Anwendungen zugeordnet werden.".to_string(),
+        })?;
+        if application_repository.get_by_id(application_id).await?.is_none() {
+            return Err(DomainError::not_found::<Application>(application_id));
+        }
+        if from == Some(to) {
+            return Ok(target);
+        }
+
+        info!(application_id = %application_id, from = ?from, to = %to, "Ordne Anwendung einem Workspace zu.");
+        if let Some(mut source) = from.and_then(|from| workspaces.iter().find(|ws| &ws.id == from).cloned()) {
+            let before = source.clone();
+            source.applications.retain(|existing| existing != application_id);
+            self.update_workspace(&source).await?;
+            self.audit_update(&before, &source).await;
+        }
+        let before = target.clone();
+        target.applications.push(application_id.clone());
+        self.update_workspace(&target).await?;
+        self.audit_update(&before, &target).await;
+        self.emit(DomainEvent::WorkspaceApplicationAssigned {
+            application_id: application_id.clone(),
+            from: from.cloned(),
+            to: to.clone(),
+        });
+        Ok(target)
     }
 
-    // Weitere Methoden z.B. zum Wechseln, Schließen, Umbenennen von Workspaces
+    /// Speichert einen geänderten Workspace, nachdem er mit [`Validate`] geprüft wurde.
+    async fn update_workspace(&self, workspace: &Workspace) -> DomainResult<()> {
+        workspace.validate()?;
+        self.workspace_repository.update(workspace).await
+    }
+
+    fn not_found(id: &NovaId) -> DomainError {
+        DomainError::not_found::<Workspace>(id)
+    }
+
+    // Weitere Methoden z.B. zum Schließen, Umbenennen von Workspaces
+}
+
+/// Das Löschen eines Workspaces; rückgängig gemacht wird es durch erneutes Hinzufügen und
+/// das Zurücknehmen der auf `target` verschobenen Regeln und Anwendungen.
+struct DeleteWorkspaceCommand {
+    service: Weak<WorkspaceService>,
+    report: WorkspaceRemovalReport,
+}
+
+impl DeleteWorkspaceCommand {
+    fn service(&self) -> DomainResult<Arc<WorkspaceService>> {
+        self.service.upgrade().ok_or_else(|| DomainError::OperationNotPermitted {
+            operation: "undo_delete_workspace".to_string(),
+            reason: "Der Workspace-Dienst existiert nicht mehr.".to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl UndoableCommand for DeleteWorkspaceCommand {
+    fn description(&self) -> String {
+        format!("Workspace '{}' löschen", self.report.workspace.name)
+    }
+
+    async fn undo(&self) -> DomainResult<()> {
+        let (service, report) = (self.service()?, &self.report);
+        if let Some(mut target) = service.workspace_repository.get_by_id(&report.target).await? {
+            if !report.moved_rules.is_empty() || !report.moved_applications.is_empty() {
+                let before = target.clone();
+                target.assignment_rules.retain(|rule| !report.moved_rules.contains(rule));
+                target.applications.retain(|app_id| !report.moved_applications.contains(app_id));
+                service.update_workspace(&target).await?;
+                service.audit_update(&before, &target).await;
+            }
+        }
+        report.workspace.validate()?;
+        service.workspace_repository.add(&report.workspace).await?;
+        let summary = format!("Löschen von Workspace '{}' rückgängig gemacht.", report.workspace.name);
+        service.audit(&report.workspace, AuditOperation::Restored, summary).await;
+        service.emit(DomainEvent::WorkspaceCreated(report.workspace.clone()));
+        Ok(())
+    }
+
+    async fn redo(&self) -> DomainResult<()> {
+        self.service()?.remove_workspace(&self.report.workspace.id, Some(&self.report.target), false, DryRun::No).await.map(|_| ())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::repositories::application_repository::MockApplicationRepository;
     use crate::repositories::workspace_repository::MockWorkspaceRepository;
     use tokio;
 
@@ -66,6 +875,9 @@ mod tests {
             .times(1)
             .returning(|_| Ok(None)); // Kein Workspace mit dem Namen existiert
 
+        mock_repo.expect_get_all_including_archived()
+            .returning(|| Ok(vec![]));
+
         mock_repo.expect_add()
             .times(1)
             .returning(|_ws| Ok(()));
@@ -82,6 +894,7 @@ mod tests {
     async fn test_create_new_workspace_name_exists() {
         let mut mock_repo = MockWorkspaceRepository::new();
         let existing_workspace = Workspace::new("Existing".to_string(), None);
+        let existing_id_str = existing_workspace.id.to_string();
         
         mock_repo.expect_get_by_name()
             .withf(|name: &str| name == "Existing")
@@ -96,10 +909,324 @@ mod tests {
 
         assert!(result.is_err());
         match result.unwrap_err() {
-            DomainError::OperationNotPermitted { reason, .. } => {
-                assert!(reason.contains("existiert bereits"));
+            DomainError::Conflict { entity_type, conflicting_field, existing_id } => {
+                assert_eq!((entity_type.as_str(), conflicting_field.as_str()), ("Workspace", "name"));
+                assert_eq!(existing_id, existing_id_str);
             }
             _ => panic!("Falscher Fehlertyp"),
         }
     }
+
+    /// Ein Repository, das gespeicherte Workspaces tatsächlich hält.
+    fn stateful_repository() -> MockWorkspaceRepository {
+        let workspaces: Arc<Mutex<Vec<Workspace>>> = Arc::default();
+        let mut mock_repo = MockWorkspaceRepository::new();
+        mock_repo.expect_get_by_name().returning(|_| Ok(None));
+        let stored = workspaces.clone();
+        mock_repo.expect_get_by_id().returning(move |id| Ok(stored.lock().unwrap().iter().find(|ws| &ws.id == id).cloned()));
+        let stored = workspaces.clone();
+        mock_repo
+            .expect_get_all()
+            .returning(move || Ok(stored.lock().unwrap().iter().filter(|ws| !ws.is_archived()).cloned().collect()));
+        let stored = workspaces.clone();
+        mock_repo.expect_get_all_including_archived().returning(move || Ok(stored.lock().unwrap().clone()));
+        let stored = workspaces.clone();
+        mock_repo
+            .expect_get_archived()
+            .returning(move || Ok(stored.lock().unwrap().iter().filter(|ws| ws.is_archived()).cloned().collect()));
+        let stored = workspaces.clone();
+        mock_repo.expect_add().returning(move |ws| {
+            stored.lock().unwrap().push(ws.clone());
+            Ok(())
+        });
+        let stored = workspaces.clone();
+        mock_repo.expect_remove().returning(move |id| {
+            stored.lock().unwrap().retain(|ws| &ws.id != id);
+            Ok(())
+        });
+        let stored = workspaces.clone();
+        mock_repo.expect_update().returning(move |ws| {
+            let mut stored = stored.lock().unwrap();
+            let existing = stored.iter_mut().find(|existing| existing.id == ws.id).unwrap();
+            *existing = ws.clone();
+            Ok(())
+        });
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_ordering_and_switching() {
+        let service = WorkspaceService::new(Arc::new(stateful_repository()));
+        let events = service.subscribe();
+        let one = service.create_new_workspace("Eins".to_string(), None).await.unwrap();
+        let two = service.create_new_workspace("Zwei".to_string(), None).await.unwrap();
+        let three = service.create_new_workspace("Drei".to_string(), None).await.unwrap();
+        assert_eq!((one.index, two.index, three.index), (0, 1, 2));
+        assert!(service.active_workspace().await.unwrap().is_none());
+
+        assert_eq!(service.next_workspace().await.unwrap().unwrap().id, one.id);
+        assert_eq!(service.previous_workspace().await.unwrap().unwrap().id, three.id);
+        assert_eq!(service.next_workspace().await.unwrap().unwrap().id, one.id);
+
+        // Reihenfolge: Drei, Eins, Zwei
+        let order = service.move_workspace(&three.id, 0).await.unwrap();
+        assert_eq!(order.iter().map(|ws| ws.name.as_str()).collect::<Vec<_>>(), vec!["Drei", "Eins", "Zwei"]);
+        assert_eq!(service.next_workspace().await.unwrap().unwrap().id, two.id);
+        assert_eq!(service.next_workspace().await.unwrap().unwrap().id, three.id);
+        service.activate_workspace(&three.id).await.unwrap();
+        assert_eq!(service.active_workspace().await.unwrap().unwrap().id, three.id);
+        assert!(matches!(service.activate_workspace(&NovaId::new()).await, Err(DomainError::EntityNotFound { .. })));
+
+        let activations: Vec<(Option<NovaId>, String)> = events
+            .try_iter()
+            .filter_map(|event| match event {
+                DomainEvent::WorkspaceActivated { previous, workspace } => Some((previous, workspace.name)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(activations.len(), 5, "Erneutes Aktivieren meldet kein Ereignis");
+        assert_eq!(activations[0], (None, "Eins".to_string()));
+        assert_eq!(activations[4], (Some(two.id.clone()), "Drei".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_usage_tracking() {
+        let service = WorkspaceService::new(Arc::new(stateful_repository()));
+        let one = service.create_new_workspace("Eins".to_string(), None).await.unwrap();
+        let two = service.create_new_workspace("Zwei".to_string(), None).await.unwrap();
+        service.create_new_workspace("Drei".to_string(), None).await.unwrap();
+        assert!(service.record_active_time().await.unwrap().is_none());
+
+        service.activate_workspace(&one.id).await.unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        service.activate_workspace(&two.id).await.unwrap();
+        let one = service.get_workspace_details(&one.id).await.unwrap().unwrap();
+        assert!(one.active_duration >= std::time::Duration::from_millis(5));
+        assert!(one.last_activated_at.is_some());
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let two = service.record_active_time().await.unwrap().unwrap();
+        assert!(two.active_duration >= std::time::Duration::from_millis(5));
+        let names: Vec<String> = service.list_workspaces_by_recency().await.unwrap().into_iter().map(|ws| ws.name).collect();
+        assert_eq!(names, vec!["Zwei", "Eins", "Drei"]);
+    }
+
+    #[tokio::test]
+    async fn test_assignment_rules() {
+        let service = WorkspaceService::new(Arc::new(stateful_repository()));
+        let web = service.create_new_workspace("Web".to_string(), None).await.unwrap();
+        let code = service.create_new_workspace("Code".to_string(), None).await.unwrap();
+        let development = WorkspaceAssignmentRule::Category("Development".to_string());
+        service.add_assignment_rule(&code.id, development.clone()).await.unwrap();
+        service.add_assignment_rule(&web.id, WorkspaceAssignmentRule::ApplicationId("firefox".to_string())).await.unwrap();
+        // Die App-ID-Regel schlägt die Kategorieregel.
+        service.add_assignment_rule(&web.id, WorkspaceAssignmentRule::ApplicationId("devtools".to_string())).await.unwrap();
+        assert!(matches!(
+            service.add_assignment_rule(&web.id, development.clone()).await,
+            Err(DomainError::Conflict { existing_id, .. }) if existing_id == code.id.to_string()
+        ));
+        assert!(matches!(
+            service.add_assignment_rule(&web.id, WorkspaceAssignmentRule::Category(" ".to_string())).await,
+            Err(DomainError::ValidationError { .. })
+        ));
+
+        let app = |name: &str, category: &str| {
+            let mut app = Application::new_desktop(name.to_string(), format!("/usr/bin/{}", name), None);
+            app.categories = Some(vec![category.to_string()]);
+            app
+        };
+        let service = &service;
+        let target = |app| async move { service.workspace_for_application(&app).await.unwrap().map(|ws| ws.name) };
+        assert_eq!(target(app("Firefox", "Network")).await.as_deref(), Some("Web"));
+        assert_eq!(target(app("builder", "development")).await.as_deref(), Some("Code"));
+        assert_eq!(target(app("devtools", "Development")).await.as_deref(), Some("Web"));
+        assert_eq!(target(app("files", "Utility")).await, None);
+
+        let code = service.remove_assignment_rule(&code.id, &development).await.unwrap();
+        assert!(code.assignment_rules.is_empty());
+        assert_eq!(target(app("builder", "Development")).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_rename_workspace_publishes_domain_event() {
+        let bus = Arc::new(crate::events::EventBus::new());
+        let events = bus.subscribe_filtered(|event| matches!(event, DomainEvent::WorkspaceRenamed { .. }));
+        let service = WorkspaceService::new(Arc::new(stateful_repository())).with_event_publisher(bus);
+        let work = service.create_new_workspace("Arbeit".to_string(), None).await.unwrap();
+
+        let renamed = service.rename_workspace(&work.id, "Projekt".to_string()).await.unwrap();
+        assert_eq!(renamed.name, "Projekt");
+        assert!(matches!(
+            service.rename_workspace(&work.id, "".to_string()).await,
+            Err(DomainError::ValidationError { .. })
+        ));
+        assert_eq!(
+            events.try_iter().collect::<Vec<_>>(),
+            vec![DomainEvent::WorkspaceRenamed { id: work.id, old_name: "Arbeit".to_string(), new_name: "Projekt".to_string() }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_workspace_can_be_undone() {
+        let history = Arc::new(HistoryService::new());
+        let service = Arc::new(WorkspaceService::new(Arc::new(stateful_repository())).with_history(history.clone()));
+        let home = service.create_new_workspace("Start".to_string(), None).await.unwrap();
+        let work = service.create_new_workspace("Arbeit".to_string(), None).await.unwrap();
+        service.add_assignment_rule(&work.id, WorkspaceAssignmentRule::Category("Office".to_string())).await.unwrap();
+        service.activate_workspace(&work.id).await.unwrap();
+
+        service.delete_workspace(&work.id, None, DryRun::No).await.unwrap();
+        assert_eq!(service.list_all_workspaces().await.unwrap().len(), 1);
+        assert_eq!(service.active_workspace().await.unwrap().unwrap().id, home.id);
+
+        assert_eq!(history.undo(WORKSPACE_HISTORY).await.unwrap(), "Workspace 'Arbeit' löschen");
+        let restored = service.get_workspace_details(&work.id).await.unwrap().unwrap();
+        assert_eq!(restored.assignment_rules.len(), 1);
+        assert!(service.get_workspace_details(&home.id).await.unwrap().unwrap().assignment_rules.is_empty());
+        history.redo(WORKSPACE_HISTORY).await.unwrap();
+        assert!(service.get_workspace_details(&work.id).await.unwrap().is_none());
+        assert!(matches!(service.delete_workspace(&work.id, None, DryRun::No).await, Err(DomainError::EntityNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_delete_workspace_reassigns_contents() {
+        let service = Arc::new(WorkspaceService::new(Arc::new(stateful_repository())));
+        let one = service.create_new_workspace("Eins".to_string(), None).await.unwrap();
+        let two = service.create_new_workspace("Zwei".to_string(), None).await.unwrap();
+        let three = service.create_new_workspace("Drei".to_string(), None).await.unwrap();
+        let office = WorkspaceAssignmentRule::Category("Office".to_string());
+        service.add_assignment_rule(&one.id, office.clone()).await.unwrap();
+        let events = service.subscribe();
+
+        assert!(matches!(service.delete_workspace(&one.id, Some(&one.id), DryRun::No).await, Err(DomainError::OperationNotPermitted { .. })));
+        assert!(matches!(service.delete_workspace(&one.id, Some(&NovaId::new()), DryRun::No).await, Err(DomainError::EntityNotFound { .. })));
+        // Der Probelauf berichtet, ohne etwas zu ändern.
+        let preview = service.delete_workspace(&one.id, Some(&three.id), DryRun::Yes).await.unwrap();
+        assert_eq!((preview.target.clone(), preview.moved_rules.clone()), (three.id.clone(), vec![office.clone()]));
+        assert!(service.get_workspace_details(&one.id).await.unwrap().is_some());
+        assert!(events.try_recv().is_err());
+        assert_eq!(service.delete_workspace(&one.id, Some(&three.id), DryRun::No).await.unwrap(), preview);
+        assert_eq!(service.get_workspace_details(&three.id).await.unwrap().unwrap().assignment_rules, vec![office]);
+        assert_eq!(events.try_recv().unwrap(), DomainEvent::WorkspaceRemoved { id: one.id, reassigned_to: three.id.clone() });
+
+        // Ohne Ziel erhält der vorherige bzw. für den ersten der nächste Workspace die Inhalte.
+        service.delete_workspace(&two.id, None, DryRun::No).await.unwrap();
+        assert_eq!(events.try_recv().unwrap(), DomainEvent::WorkspaceRemoved { id: two.id, reassigned_to: three.id.clone() });
+        assert!(matches!(service.delete_workspace(&three.id, None, DryRun::No).await, Err(DomainError::OperationNotPermitted { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_archive_restore_and_purge_workspace() {
+        let service = WorkspaceService::new(Arc::new(stateful_repository()));
+        let one = service.create_new_workspace("Eins".to_string(), None).await.unwrap();
+        let two = service.create_new_workspace("Zwei".to_string(), None).await.unwrap();
+        let office = WorkspaceAssignmentRule::Category("Office".to_string());
+        let games = WorkspaceAssignmentRule::Category("Game".to_string());
+        service.add_assignment_rule(&one.id, office.clone()).await.unwrap();
+        service.activate_workspace(&one.id).await.unwrap();
+        let events = service.subscribe();
+
+        assert!(matches!(service.purge_workspace(&one.id).await, Err(DomainError::OperationNotPermitted { .. })));
+        assert!(service.archive_workspace(&one.id, None).await.unwrap().is_archived());
+        assert_eq!(events.try_recv().unwrap(), DomainEvent::WorkspaceRemoved { id: one.id.clone(), reassigned_to: two.id.clone() });
+        assert_eq!(service.active_workspace().await.unwrap().unwrap().id, two.id);
+        assert_eq!(service.list_all_workspaces().await.unwrap().len(), 1);
+        assert_eq!(service.list_archived_workspaces().await.unwrap()[0].id, one.id);
+        assert!(matches!(service.activate_workspace(&one.id).await, Err(DomainError::OperationNotPermitted { .. })));
+        // Archivierte Workspaces zählen nicht mit, "Zwei" ist damit der letzte Workspace.
+        assert!(matches!(service.archive_workspace(&two.id, None).await, Err(DomainError::OperationNotPermitted { .. })));
+
+        // Regeln archivierter Workspaces können neu vergeben werden; beim Wiederherstellen bleiben
+        // sie (wie die verschobene Regel) beim anderen Workspace.
+        let mut archived = service.get_workspace_details(&one.id).await.unwrap().unwrap();
+        archived.assignment_rules.push(games.clone());
+        service.update_workspace(&archived).await.unwrap();
+        service.add_assignment_rule(&two.id, games.clone()).await.unwrap();
+        let restored = service.restore_workspace(&one.id).await.unwrap();
+        assert!(!restored.is_archived());
+        assert!(restored.assignment_rules.is_empty());
+        assert!(restored.index > two.index);
+        assert!(matches!(events.try_iter().last(), Some(DomainEvent::WorkspaceRestored(ws)) if ws.id == one.id));
+
+        service.archive_workspace(&one.id, None).await.unwrap();
+        service.purge_workspace(&one.id).await.unwrap();
+        assert!(service.get_workspace_details(&one.id).await.unwrap().is_none());
+        assert!(service.list_archived_workspaces().await.unwrap().is_empty());
+        assert_eq!(events.try_iter().last(), Some(DomainEvent::WorkspacePurged { id: one.id }));
+    }
+
+    #[tokio::test]
+    async fn test_assign_and_move_applications() {
+        let editor = Application::new_desktop("editor".to_string(), "/usr/bin/editor".to_string(), None);
+        let mut app_repo = MockApplicationRepository::new();
+        let known = editor.clone();
+        app_repo.expect_get_by_id().returning(move |id| Ok((id == &known.id).then(|| known.clone())));
+        let service = Arc::new(WorkspaceService::new(Arc::new(stateful_repository())).with_application_repository(Arc::new(app_repo)));
+        let one = service.create_new_workspace("Eins".to_string(), None).await.unwrap();
+        let two = service.create_new_workspace("Zwei".to_string(), None).await.unwrap();
+        let events = service.subscribe();
+
+        assert!(matches!(service.assign_application(&one.id, &NovaId::new()).await, Err(DomainError::EntityNotFound { .. })));
+        assert_eq!(service.assign_application(&one.id, &editor.id).await.unwrap().applications, vec![editor.id.clone()]);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            DomainEvent::WorkspaceApplicationAssigned { application_id: editor.id.clone(), from: None, to: one.id.clone() }
+        );
+        assert!(matches!(
+            service.move_assignment(&editor.id, &two.id, &one.id).await,
+            Err(DomainError::OperationNotPermitted { .. })
+        ));
+        service.move_assignment(&editor.id, &one.id, &two.id).await.unwrap();
+        assert!(service.get_workspace_details(&one.id).await.unwrap().unwrap().applications.is_empty());
+        assert_eq!(service.get_workspace_details(&two.id).await.unwrap().unwrap().applications, vec![editor.id.clone()]);
+
+        // Beim Löschen gehen die Anwendungen auf den Ziel-Workspace über.
+        service.delete_workspace(&two.id, None, DryRun::No).await.unwrap();
+        assert_eq!(service.get_workspace_details(&one.id).await.unwrap().unwrap().applications, vec![editor.id.clone()]);
+
+        let without_applications = WorkspaceService::new(Arc::new(stateful_repository()));
+        let three = without_applications.create_new_workspace("Drei".to_string(), None).await.unwrap();
+        assert!(matches!(
+            without_applications.assign_application(&three.id, &editor.id).await,
+            Err(DomainError::OperationNotPermitted { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_primary_output_must_be_configured() {
+        use crate::entities::display::{DisplayLayout, DisplayMode, OutputConfiguration};
+        use crate::repositories::display_layout_repository::MockDisplayLayoutRepository;
+
+        let mut layouts = MockDisplayLayoutRepository::new();
+        let layout = DisplayLayout::new(vec![OutputConfiguration::new("HDMI-1", DisplayMode::new(1920, 1080, 60_000))]);
+        layouts.expect_get_all().returning(move || Ok(vec![layout.clone()]));
+        let service = WorkspaceService::new(Arc::new(stateful_repository())).with_display_layout_repository(Arc::new(layouts));
+
+        assert!(matches!(
+            service.create_new_workspace("Extern".to_string(), Some("DP-2".to_string())).await,
+            Err(DomainError::IntegrityViolation { .. })
+        ));
+        let workspace = service.create_new_workspace("Extern".to_string(), Some("HDMI-1".to_string())).await.unwrap();
+        assert!(matches!(
+            service.set_primary_output(&workspace.id, Some("DP-2".to_string())).await,
+            Err(DomainError::IntegrityViolation { .. })
+        ));
+        assert!(service.set_primary_output(&workspace.id, None).await.unwrap().primary_output_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_calls_are_recorded_in_metrics() {
+        let registry = Arc::new(novade_core::metrics::MetricsRegistry::new());
+        let metrics = Arc::new(crate::services::TracingMetrics::with_registry(std::time::Duration::from_millis(100), registry.clone()));
+        let service = WorkspaceService::new(Arc::new(stateful_repository())).with_metrics(metrics.clone());
+        service.create_new_workspace("Eins".to_string(), None).await.unwrap();
+        assert!(service.create_new_workspace(" ".to_string(), None).await.is_err());
+        service.list_all_workspaces().await.unwrap();
+
+        let create = metrics.method("WorkspaceService", "create_new_workspace").unwrap();
+        assert_eq!((create.calls, create.errors), (2, 1));
+        assert_eq!(create.latency.count, 2);
+        assert_eq!(metrics.method("WorkspaceService", "list_all_workspaces").unwrap().calls, 1);
+        assert_eq!(registry.snapshot().len(), 6);
+    }
 }
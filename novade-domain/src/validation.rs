@@ -0,0 +1,92 @@
+//! # Validierung von Entitäten (`validation`)
+//!
+//! Definiert das Trait [`Validate`], mit dem eine Entität alle Verstöße gegen ihre Regeln
+//! auf einmal meldet, statt beim ersten abzubrechen. Die Dienste rufen
+//! [`Validate::validate`] vor jedem Schreiben in ein Repository auf, so dass ungültige
+//! Entitäten gar nicht erst gespeichert werden.
+//!
+//! Regeln, die vom Zustand anderer Entitäten abhängen (z.B. eindeutige Namen), prüfen
+//! weiterhin die Dienste.
+
+use crate::entities::Entity;
+use crate::{DomainError, DomainResult};
+
+/// Ein Verstoß gegen eine Regel für ein Feld einer Entität.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldViolation {
+    /// Das betroffene Feld, bei Listen mit Index (z.B. "tags[1]").
+    pub field: String,
+    /// Eine Beschreibung des Verstoßes.
+    pub message: String,
+}
+
+impl FieldViolation {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { field: field.into(), message: message.into() }
+    }
+}
+
+/// Eine Entität, die ihre Felder unabhängig von anderen Entitäten prüfen kann.
+pub trait Validate: Entity {
+    /// Alle Verstöße; leer, wenn die Entität gültig ist.
+    fn violations(&self) -> Vec<FieldViolation>;
+
+    /// Prüft die Entität.
+    ///
+    /// # Rückgabe
+    /// `Ok(())` für eine gültige Entität, `DomainError::ValidationError` bei genau einem
+    /// Verstoß oder `DomainError::ValidationErrors` mit allen Verstößen.
+    ///
+    /// # Beispiele
+    /// ```
+    /// use novade_domain::entities::Application;
+    /// use novade_domain::validation::Validate;
+    /// use novade_domain::DomainError;
+    ///
+    /// let app = Application::new_desktop(" ".to_string(), "".to_string(), None);
+    /// match app.validate() {
+    ///     Err(DomainError::ValidationErrors { violations, .. }) => assert_eq!(violations.len(), 2),
+    ///     other => panic!("Unerwartetes Ergebnis: {:?}", other),
+    /// }
+    /// ```
+    fn validate(&self) -> DomainResult<()> {
+        let mut violations = self.violations();
+        match violations.len() {
+            0 => Ok(()),
+            1 => {
+                let FieldViolation { field, message } = violations.remove(0);
+                Err(DomainError::ValidationError { field, message })
+            }
+            _ => Err(DomainError::ValidationErrors { entity_type: Self::ENTITY_TYPE.to_string(), violations }),
+        }
+    }
+}
+
+/// Meldet einen Verstoß für `field`, wenn `value` leer ist oder nur aus Leerzeichen besteht.
+pub(crate) fn require_non_empty(violations: &mut Vec<FieldViolation>, field: &str, value: &str, message: &str) {
+    if value.trim().is_empty() {
+        violations.push(FieldViolation::new(field, message));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{PreferenceValue, UserPreferenceSetting, Workspace, WorkspaceAssignmentRule};
+
+    #[test]
+    fn test_violations_are_aggregated() {
+        let mut workspace = Workspace::new("Arbeit".to_string(), None);
+        assert!(workspace.validate().is_ok());
+        workspace.assignment_rules.push(WorkspaceAssignmentRule::Category(" ".to_string()));
+        assert!(matches!(workspace.validate(), Err(DomainError::ValidationError { field, .. }) if field == "assignment_rules[0]"));
+
+        let mut setting = UserPreferenceSetting::new_boolean("Ungültig", "", true);
+        setting.value = PreferenceValue::Float(f64::NAN);
+        let fields: Vec<String> = setting.violations().into_iter().map(|violation| violation.field).collect();
+        assert_eq!(fields, vec!["key", "display_name", "value"]);
+        let error = setting.validate().unwrap_err();
+        assert!(matches!(&error, DomainError::ValidationErrors { entity_type, .. } if entity_type == "UserPreferenceSetting"));
+        assert!(error.to_string().contains("display_name: Der Anzeigename darf nicht leer sein."));
+    }
+}
@@ -7,12 +7,23 @@
 pub struct Client {
     /// Unique identifier for the client.
     pub id: u32,
+    /// Whether the client is a trusted desktop component (e.g. the lock screen) allowed to
+    /// make privileged requests such as locking the session.
+    pub privileged: bool,
+    /// The process ID of the client (from the socket credentials), if known. Used to attach
+    /// the client's windows to the process that was launched for it.
+    pub pid: Option<u32>,
 }
 
 impl Client {
-    /// Creates a new client with the given ID.
+    /// Creates a new, unprivileged client with the given ID.
     pub fn new(id: u32) -> Self {
-        Self { id }
+        Self { id, privileged: false, pid: None }
+    }
+
+    /// Creates a new privileged client with the given ID.
+    pub fn new_privileged(id: u32) -> Self {
+        Self { id, privileged: true, pid: None }
     }
 }
 
@@ -47,6 +58,70 @@ pub enum ClientRequest {
         /// The ID of the client making the request.
         client_id: u32,
     },
+    /// Request to lock the session (privileged clients only).
+    ///
+    /// Also used by the lock client to take over a lock the compositor created on idle.
+    LockSession {
+        /// The ID of the client making the request.
+        client_id: u32,
+    },
+    /// Request to create a lock surface covering an output (lock owner only).
+    CreateLockSurface {
+        /// The ID of the client making the request.
+        client_id: u32,
+        /// The output the lock surface should cover.
+        output_id: u32,
+    },
+    /// Request to unlock the session (lock owner only), e.g. after successful authentication.
+    UnlockSession {
+        /// The ID of the client making the request.
+        client_id: u32,
+    },
+    /// Request to keep the session from going idle (no auto-lock, no display power-off)
+    /// while the window is mapped and visible, e.g. during video playback.
+    ///
+    /// Only the client owning the window may inhibit idle for it. Released automatically
+    /// when the window unmaps or its client disconnects.
+    InhibitIdle {
+        /// The ID of the client making the request.
+        client_id: u32,
+        /// The window requesting the inhibition.
+        window_id: u32,
+        /// Human-readable reason, e.g. "Playing video".
+        reason: String,
+    },
+    /// Request to release an idle inhibitor the client set with `InhibitIdle`.
+    ReleaseIdleInhibit {
+        /// The ID of the client making the request.
+        client_id: u32,
+        /// The window whose inhibitor should be released.
+        window_id: u32,
+    },
+    /// Request to activate a window with the startup notification token the client was
+    /// launched with (`XDG_ACTIVATION_TOKEN` / `DESKTOP_STARTUP_ID`).
+    ActivateWindow {
+        /// The window to activate.
+        window_id: u32,
+        /// The startup notification token.
+        token: String,
+    },
+}
+
+/// Represents client requests that need the backend to be carried out.
+///
+/// Kept apart from [`ClientRequest`] so they can only be handed to
+/// `Server::process_backend_request`, which takes the backend along with the request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackendRequest {
+    /// Request to turn the display behind an output on or off (DPMS).
+    SetOutputPower {
+        /// The ID of the client making the request.
+        client_id: u32,
+        /// The output to switch.
+        output_id: u32,
+        /// Whether the display should be on.
+        on: bool,
+    },
 }
 
 /// Represents events that the server can send to clients (or use internally for now).
@@ -78,4 +153,50 @@ pub enum ServerEvent {
         /// The text from the clipboard, or None if empty.
         text: Option<String>,
     },
+    /// The session has been locked, or an existing lock was handed to the client.
+    SessionLocked {
+        /// The ID of the client owning the lock.
+        client_id: u32,
+    },
+    /// A lock surface was created.
+    LockSurfaceCreated {
+        /// The ID of the window backing the lock surface.
+        window_id: u32,
+        /// The ID of the output it covers.
+        output_id: u32,
+        /// The geometry (x, y, width, height) of the lock surface.
+        geometry: (i32, i32, u32, u32),
+    },
+    /// The session has been unlocked.
+    SessionUnlocked {
+        /// The ID of the client that unlocked the session.
+        client_id: u32,
+    },
+    /// An idle inhibitor was registered for a window.
+    IdleInhibited {
+        /// The window holding the inhibitor.
+        window_id: u32,
+    },
+    /// An idle inhibitor was released.
+    IdleInhibitReleased {
+        /// The window that held the inhibitor.
+        window_id: u32,
+    },
+    /// A window presented a valid startup token and was focused; the launch is complete.
+    WindowActivated {
+        /// The activated window.
+        window_id: u32,
+        /// The application ID associated with the launch, if known.
+        app_id: Option<String>,
+    },
+    /// The compositor locked the session on its own (e.g. on idle) and asks the lock
+    /// client to claim the lock and present its lock surfaces.
+    LockRequested,
+    /// The display behind an output was turned on or off.
+    OutputPowerChanged {
+        /// The switched output.
+        output_id: u32,
+        /// Whether the display is now on.
+        on: bool,
+    },
 }
@@ -0,0 +1,141 @@
+// src/compositor/backend/drm/edid.rs
+
+//! Minimal EDID parser.
+//!
+//! Only extracts what the compositor needs to describe an output: the manufacturer ID,
+//! the monitor name and the serial number from the 128-byte base block.
+
+const EDID_HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+const EDID_BASE_BLOCK_LEN: usize = 128;
+const DESCRIPTOR_OFFSETS: [usize; 4] = [54, 72, 90, 108];
+const DESCRIPTOR_LEN: usize = 18;
+const DESCRIPTOR_SERIAL: u8 = 0xFF;
+const DESCRIPTOR_MONITOR_NAME: u8 = 0xFC;
+
+/// Display information extracted from an EDID blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdidInfo {
+    /// Three-letter PNP manufacturer ID (e.g., "DEL", "SAM").
+    pub manufacturer: String,
+    /// Monitor name descriptor, if present.
+    pub monitor_name: Option<String>,
+    /// Serial number descriptor, if present.
+    pub serial: Option<String>,
+}
+
+impl EdidInfo {
+    /// Returns a human-readable description such as "DEL DELL U2720Q".
+    pub fn description(&self) -> String {
+        match &self.monitor_name {
+            Some(name) => format!("{} {}", self.manufacturer, name),
+            None => self.manufacturer.clone(),
+        }
+    }
+}
+
+/// Parses the base block of an EDID blob.
+///
+/// Returns `None` if the blob is too short or does not start with the EDID header.
+/// The checksum is not verified; plenty of real monitors get it wrong.
+pub fn parse_edid(data: &[u8]) -> Option<EdidInfo> {
+    if data.len() < EDID_BASE_BLOCK_LEN || data[..8] != EDID_HEADER {
+        return None;
+    }
+
+    let mut info = EdidInfo {
+        manufacturer: decode_manufacturer(data[8], data[9]),
+        monitor_name: None,
+        serial: None,
+    };
+
+    for offset in DESCRIPTOR_OFFSETS {
+        let descriptor = &data[offset..offset + DESCRIPTOR_LEN];
+        // Display descriptors start with three zero bytes, detailed timings don't.
+        if descriptor[..3] != [0, 0, 0] {
+            continue;
+        }
+        match descriptor[3] {
+            DESCRIPTOR_MONITOR_NAME => info.monitor_name = decode_descriptor_text(&descriptor[5..]),
+            DESCRIPTOR_SERIAL => info.serial = decode_descriptor_text(&descriptor[5..]),
+            _ => {}
+        }
+    }
+
+    Some(info)
+}
+
+/// Decodes the manufacturer ID: three 5-bit letters packed big-endian, 'A' == 1.
+fn decode_manufacturer(high: u8, low: u8) -> String {
+    let packed = u16::from_be_bytes([high, low]);
+    [10, 5, 0]
+        .iter()
+        .map(|shift| {
+            let letter = ((packed >> shift) & 0x1F) as u8;
+            if (1..=26).contains(&letter) { (b'A' + letter - 1) as char } else { '?' }
+        })
+        .collect()
+}
+
+/// Decodes a descriptor text field: up to 13 bytes, terminated by a newline and padded with spaces.
+fn decode_descriptor_text(bytes: &[u8]) -> Option<String> {
+    let end = bytes.iter().position(|b| *b == b'\n').unwrap_or(bytes.len());
+    let text: String = bytes[..end].iter().map(|b| *b as char).collect();
+    let text = text.trim();
+    if text.is_empty() { None } else { Some(text.to_string()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor(tag: u8, text: &str) -> [u8; DESCRIPTOR_LEN] {
+        let mut descriptor = [0u8; DESCRIPTOR_LEN];
+        descriptor[3] = tag;
+        let mut payload = [b' '; 13];
+        payload[..text.len()].copy_from_slice(text.as_bytes());
+        if text.len() < 13 {
+            payload[text.len()] = b'\n';
+        }
+        descriptor[5..].copy_from_slice(&payload);
+        descriptor
+    }
+
+    fn sample_edid() -> Vec<u8> {
+        let mut edid = vec![0u8; EDID_BASE_BLOCK_LEN];
+        edid[..8].copy_from_slice(&EDID_HEADER);
+        // "DEL": D=4, E=5, L=12 -> 00100 00101 01100
+        edid[8] = 0x10;
+        edid[9] = 0xAC;
+        // First descriptor slot holds a detailed timing (non-zero pixel clock).
+        edid[54] = 0x01;
+        edid[72..90].copy_from_slice(&descriptor(DESCRIPTOR_SERIAL, "ABC123"));
+        edid[90..108].copy_from_slice(&descriptor(DESCRIPTOR_MONITOR_NAME, "DELL U2720Q"));
+        edid
+    }
+
+    #[test]
+    fn test_parse_edid_extracts_manufacturer_name_and_serial() {
+        let info = parse_edid(&sample_edid()).expect("Sample EDID should parse");
+        assert_eq!(info.manufacturer, "DEL");
+        assert_eq!(info.monitor_name.as_deref(), Some("DELL U2720Q"));
+        assert_eq!(info.serial.as_deref(), Some("ABC123"));
+        assert_eq!(info.description(), "DEL DELL U2720Q");
+    }
+
+    #[test]
+    fn test_parse_edid_rejects_invalid_data() {
+        assert_eq!(parse_edid(&[]), None);
+        let mut edid = sample_edid();
+        edid[0] = 0xAA;
+        assert_eq!(parse_edid(&edid), None);
+    }
+
+    #[test]
+    fn test_parse_edid_without_descriptors() {
+        let mut edid = sample_edid();
+        edid[72..].fill(0);
+        let info = parse_edid(&edid).expect("Header is still valid");
+        assert_eq!(info.monitor_name, None);
+        assert_eq!(info.description(), "DEL");
+    }
+}
@@ -0,0 +1,572 @@
+// src/compositor/backend/drm/mod.rs
+
+//! DRM/KMS hardware backend.
+//!
+//! Opens a DRM device (e.g. `/dev/dri/card0`), turns every connected connector into an
+//! [`Output`] and drives the displays with atomic modesetting. Each output owns two dumb
+//! buffers that are flipped on vblank; a renderer draws into the back buffer through
+//! [`Backend::render_frame`], which then queues the page flip.
+//!
+//! Input is not handled here; on real hardware it comes from a separate input backend.
+
+pub mod edid;
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::os::unix::io::{AsFd, BorrowedFd};
+use std::path::{Path, PathBuf};
+
+use ::drm::buffer::DrmFourcc;
+use ::drm::control::dumbbuffer::DumbBuffer;
+use ::drm::control::{
+    atomic, connector, crtc, framebuffer, plane, property, AtomicCommitFlags, Device as ControlDevice, Event, Mode,
+    ModeTypeFlags, PlaneType, ResourceHandle,
+};
+use ::drm::{ClientCapability, Device as BasicDevice};
+
+use self::edid::parse_edid;
+use super::{Backend, DrawFrame};
+use crate::compositor::core::Output;
+use crate::compositor::{CompositorError, CompositorResult};
+use crate::input::InputEvent;
+
+/// Default DRM device used by [`DrmBackend::open_default`].
+pub const DEFAULT_DRM_DEVICE: &str = "/dev/dri/card0";
+
+/// Displays at or above this density get a scale factor of 2.
+const HIDPI_THRESHOLD_DPI: f64 = 192.0;
+const MM_PER_INCH: f64 = 25.4;
+
+/// Number of buffers per output (front + back).
+const BUFFER_COUNT: usize = 2;
+
+/// Chooses an integer output scale from the mode width and the physical panel width.
+///
+/// Falls back to 1 if the physical size is unknown (projectors, some TVs and virtual
+/// displays report 0 mm).
+pub fn scale_for_physical_size(width_px: u32, width_mm: u32) -> u32 {
+    if width_mm == 0 {
+        return 1;
+    }
+    let dpi = width_px as f64 / (width_mm as f64 / MM_PER_INCH);
+    if dpi >= HIDPI_THRESHOLD_DPI { 2 } else { 1 }
+}
+
+/// Returns the connector name the kernel uses, e.g. "DP-1" or "HDMI-A-2".
+pub fn connector_name(interface: connector::Interface, interface_id: u32) -> String {
+    format!("{}-{}", interface.as_str(), interface_id)
+}
+
+/// Thin wrapper around the opened DRM device node.
+#[derive(Debug)]
+struct Card(File);
+
+impl AsFd for Card {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl BasicDevice for Card {}
+impl ControlDevice for Card {}
+
+/// Property handles of a KMS object, looked up by name.
+type PropertyMap = HashMap<String, property::Handle>;
+
+/// Per-output DRM state.
+#[derive(Debug)]
+struct DrmSurface {
+    output: Output,
+    connector: connector::Handle,
+    crtc: crtc::Handle,
+    plane: plane::Handle,
+    mode: Mode,
+    /// Property blob holding `mode`, created by the first modeset and reused afterwards.
+    mode_blob: Option<property::Value<'static>>,
+    connector_props: PropertyMap,
+    crtc_props: PropertyMap,
+    plane_props: PropertyMap,
+    buffers: Vec<(DumbBuffer, framebuffer::Handle)>,
+    /// Index into `buffers` of the buffer currently scanned out.
+    front: usize,
+    /// Whether a page flip has been queued and its completion event not yet received.
+    flip_pending: bool,
+}
+
+impl DrmSurface {
+    fn back(&self) -> usize {
+        (self.front + 1) % self.buffers.len()
+    }
+}
+
+/// Hardware backend based on DRM/KMS with atomic modesetting.
+#[derive(Debug)]
+pub struct DrmBackend {
+    card: Card,
+    path: PathBuf,
+    surfaces: Vec<DrmSurface>,
+    running: bool,
+    /// Set while the session is inactive; no commits are made in this state.
+    paused: bool,
+}
+
+fn backend_error(context: &str, error: std::io::Error) -> CompositorError {
+    CompositorError::BackendError(format!("{}: {}", context, error))
+}
+
+fn init_error(context: &str, error: std::io::Error) -> CompositorError {
+    CompositorError::InitializationFailed(format!("{}: {}", context, error))
+}
+
+impl DrmBackend {
+    /// Opens [`DEFAULT_DRM_DEVICE`].
+    pub fn open_default() -> CompositorResult<Self> {
+        Self::open(DEFAULT_DRM_DEVICE)
+    }
+
+    /// Opens the given DRM device, enumerates connected outputs and allocates their buffers.
+    ///
+    /// No mode is set yet; call [`DrmBackend::modeset`] once the outputs are configured.
+    pub fn open(path: impl AsRef<Path>) -> CompositorResult<Self> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| init_error(&format!("Failed to open DRM device {}", path.display()), e))?;
+        Self::from_file(file, path.to_path_buf())
+    }
+
+    /// Creates the backend from an already opened DRM device, e.g. a file descriptor
+    /// handed out by the session manager.
+    pub fn from_file(file: File, path: PathBuf) -> CompositorResult<Self> {
+        let card = Card(file);
+        card.set_client_capability(ClientCapability::UniversalPlanes, true)
+            .map_err(|e| init_error("DRM device does not support universal planes", e))?;
+        card.set_client_capability(ClientCapability::Atomic, true)
+            .map_err(|e| init_error("DRM device does not support atomic modesetting", e))?;
+
+        let mut backend = Self { card, path, surfaces: Vec::new(), running: true, paused: false };
+        backend.surfaces = backend.enumerate_surfaces()?;
+        println!(
+            "DrmBackend: Opened {} with {} connected output(s).",
+            backend.path.display(),
+            backend.surfaces.len()
+        );
+        Ok(backend)
+    }
+
+    /// Path of the opened DRM device.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn property_map<T: ResourceHandle>(&self, handle: T) -> CompositorResult<PropertyMap> {
+        let props = self
+            .card
+            .get_properties(handle)
+            .map_err(|e| init_error("Failed to read KMS object properties", e))?;
+        let infos = props
+            .as_hashmap(&self.card)
+            .map_err(|e| init_error("Failed to read KMS property info", e))?;
+        Ok(infos.into_iter().map(|(name, info)| (name, info.handle())).collect())
+    }
+
+    fn read_edid(&self, connector: connector::Handle) -> Option<Vec<u8>> {
+        let props = self.card.get_properties(connector).ok()?;
+        for (handle, raw_value) in props.iter() {
+            let info = self.card.get_property(*handle).ok()?;
+            if info.name().to_str() != Ok("EDID") {
+                continue;
+            }
+            if let property::Value::Blob(blob) = info.value_type().convert_value(*raw_value) {
+                if blob != 0 {
+                    return self.card.get_property_blob(blob).ok();
+                }
+            }
+        }
+        None
+    }
+
+    fn is_primary_plane(&self, plane: plane::Handle) -> bool {
+        let Ok(props) = self.card.get_properties(plane) else {
+            return false;
+        };
+        let is_primary = props.iter().any(|(handle, raw_value)| {
+            self.card
+                .get_property(*handle)
+                .map(|info| info.name().to_str() == Ok("type") && *raw_value == PlaneType::Primary as u64)
+                .unwrap_or(false)
+        });
+        is_primary
+    }
+
+    fn enumerate_surfaces(&self) -> CompositorResult<Vec<DrmSurface>> {
+        let resources = self
+            .card
+            .resource_handles()
+            .map_err(|e| init_error("Failed to query DRM resources", e))?;
+        let planes = self
+            .card
+            .plane_handles()
+            .map_err(|e| init_error("Failed to query DRM planes", e))?;
+
+        let mut used_crtcs = Vec::new();
+        let mut used_planes = Vec::new();
+        let mut surfaces = Vec::new();
+        let mut next_x = 0i32;
+
+        for &connector_handle in resources.connectors() {
+            let Ok(info) = self.card.get_connector(connector_handle, true) else {
+                continue;
+            };
+            if info.state() != connector::State::Connected {
+                continue;
+            }
+            // Prefer the mode the display advertises as preferred, else the first one.
+            let Some(&mode) = info
+                .modes()
+                .iter()
+                .find(|m| m.mode_type().contains(ModeTypeFlags::PREFERRED))
+                .or_else(|| info.modes().first())
+            else {
+                continue;
+            };
+
+            let crtc = info
+                .encoders()
+                .iter()
+                .filter_map(|encoder| self.card.get_encoder(*encoder).ok())
+                .flat_map(|encoder| resources.filter_crtcs(encoder.possible_crtcs()))
+                .find(|crtc| !used_crtcs.contains(crtc));
+            let Some(crtc) = crtc else {
+                eprintln!("DrmBackend: No free CRTC for connector {:?}, skipping.", connector_handle);
+                continue;
+            };
+
+            let plane = planes.iter().copied().find(|plane| {
+                !used_planes.contains(plane)
+                    && self.is_primary_plane(*plane)
+                    && self
+                        .card
+                        .get_plane(*plane)
+                        .map(|p| resources.filter_crtcs(p.possible_crtcs()).contains(&crtc))
+                        .unwrap_or(false)
+            });
+            let Some(plane) = plane else {
+                eprintln!("DrmBackend: No primary plane for CRTC {:?}, skipping.", crtc);
+                continue;
+            };
+
+            let (width, height) = mode.size();
+            let (width, height) = (width as u32, height as u32);
+            let name = connector_name(info.interface(), info.interface_id());
+            let scale = info.size().map(|(width_mm, _)| scale_for_physical_size(width, width_mm)).unwrap_or(1);
+            let mut output = Output::new(
+                surfaces.len() as u32 + 1,
+                name,
+                width,
+                height,
+                next_x,
+                0,
+                surfaces.is_empty(),
+            )
+            .with_scale(scale);
+            if let Some(edid) = self.read_edid(connector_handle).as_deref().and_then(parse_edid) {
+                output = output.with_description(edid.description());
+            }
+            // Lay outputs out left to right in logical pixels.
+            next_x += (width / scale) as i32;
+
+            let mut buffers = Vec::with_capacity(BUFFER_COUNT);
+            for _ in 0..BUFFER_COUNT {
+                let buffer = self
+                    .card
+                    .create_dumb_buffer((width, height), DrmFourcc::Xrgb8888, 32)
+                    .map_err(|e| init_error("Failed to allocate dumb buffer", e))?;
+                let framebuffer = self
+                    .card
+                    .add_framebuffer(&buffer, 24, 32)
+                    .map_err(|e| init_error("Failed to create framebuffer", e))?;
+                buffers.push((buffer, framebuffer));
+            }
+
+            println!(
+                "DrmBackend: Output '{}' ({}) {}x{}@{}Hz, scale {}.",
+                output.name,
+                output.description.as_deref().unwrap_or("unknown display"),
+                width,
+                height,
+                mode.vrefresh(),
+                scale
+            );
+
+            used_crtcs.push(crtc);
+            used_planes.push(plane);
+            surfaces.push(DrmSurface {
+                output,
+                connector: connector_handle,
+                crtc,
+                plane,
+                mode,
+                mode_blob: None,
+                connector_props: self.property_map(connector_handle)?,
+                crtc_props: self.property_map(crtc)?,
+                plane_props: self.property_map(plane)?,
+                buffers,
+                front: 0,
+                flip_pending: false,
+            });
+        }
+
+        Ok(surfaces)
+    }
+
+    /// Performs a single atomic modeset lighting up all outputs with their front buffers.
+    pub fn modeset(&mut self) -> CompositorResult<()> {
+        let mut request = atomic::AtomicModeReq::new();
+        for surface in &mut self.surfaces {
+            let mode_blob = match surface.mode_blob {
+                Some(blob) => blob,
+                None => *surface.mode_blob.insert(
+                    self.card
+                        .create_property_blob(&surface.mode)
+                        .map_err(|e| backend_error("Failed to create mode blob", e))?,
+                ),
+            };
+            request.add_property(
+                surface.connector,
+                prop(&surface.connector_props, "CRTC_ID")?,
+                property::Value::CRTC(Some(surface.crtc)),
+            );
+            request.add_property(surface.crtc, prop(&surface.crtc_props, "MODE_ID")?, mode_blob);
+            request.add_property(
+                surface.crtc,
+                prop(&surface.crtc_props, "ACTIVE")?,
+                property::Value::Boolean(surface.output.is_powered_on),
+            );
+            // A disabled CRTC must not have a plane attached, or the commit is rejected.
+            if surface.output.is_powered_on {
+                add_plane_properties(&mut request, surface, surface.front)?;
+            } else {
+                detach_plane(&mut request, surface)?;
+            }
+        }
+        self.card
+            .atomic_commit(AtomicCommitFlags::ALLOW_MODESET, request)
+            .map_err(|e| backend_error("Atomic modeset failed", e))?;
+        println!("DrmBackend: Modeset done for {} output(s).", self.surfaces.len());
+        Ok(())
+    }
+
+    /// Whether any output is waiting for a page flip to complete.
+    pub fn has_pending_flips(&self) -> bool {
+        self.surfaces.iter().any(|s| s.flip_pending)
+    }
+
+    /// Reads DRM events and completes the page flips they report.
+    ///
+    /// Blocks until the next event arrives, so only call this while a flip is pending.
+    fn complete_page_flips(&mut self) -> CompositorResult<()> {
+        let events = self
+            .card
+            .receive_events()
+            .map_err(|e| backend_error("Failed to read DRM events", e))?;
+        for event in events {
+            if let Event::PageFlip(flip) = event {
+                if let Some(surface) = self.surfaces.iter_mut().find(|s| s.crtc == flip.crtc) {
+                    surface.front = surface.back();
+                    surface.flip_pending = false;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn prop(props: &PropertyMap, name: &str) -> CompositorResult<property::Handle> {
+    props
+        .get(name)
+        .copied()
+        .ok_or_else(|| CompositorError::BackendError(format!("KMS object is missing the '{}' property", name)))
+}
+
+/// Points the surface's primary plane at the given buffer, covering the whole mode.
+fn add_plane_properties(
+    request: &mut atomic::AtomicModeReq,
+    surface: &DrmSurface,
+    buffer_index: usize,
+) -> CompositorResult<()> {
+    let (width, height) = surface.mode.size();
+    let (width, height) = (width as u64, height as u64);
+    let props = &surface.plane_props;
+    let plane = surface.plane;
+    request.add_property(plane, prop(props, "FB_ID")?, property::Value::Framebuffer(Some(surface.buffers[buffer_index].1)));
+    request.add_property(plane, prop(props, "CRTC_ID")?, property::Value::CRTC(Some(surface.crtc)));
+    // Source coordinates are 16.16 fixed point.
+    request.add_property(plane, prop(props, "SRC_X")?, property::Value::UnsignedRange(0));
+    request.add_property(plane, prop(props, "SRC_Y")?, property::Value::UnsignedRange(0));
+    request.add_property(plane, prop(props, "SRC_W")?, property::Value::UnsignedRange(width << 16));
+    request.add_property(plane, prop(props, "SRC_H")?, property::Value::UnsignedRange(height << 16));
+    request.add_property(plane, prop(props, "CRTC_X")?, property::Value::SignedRange(0));
+    request.add_property(plane, prop(props, "CRTC_Y")?, property::Value::SignedRange(0));
+    request.add_property(plane, prop(props, "CRTC_W")?, property::Value::UnsignedRange(width));
+    request.add_property(plane, prop(props, "CRTC_H")?, property::Value::UnsignedRange(height));
+    Ok(())
+}
+
+/// Adds the properties that take the primary plane of `surface` off its CRTC.
+fn detach_plane(request: &mut atomic::AtomicModeReq, surface: &DrmSurface) -> CompositorResult<()> {
+    request.add_property(surface.plane, prop(&surface.plane_props, "FB_ID")?, property::Value::Framebuffer(None));
+    request.add_property(surface.plane, prop(&surface.plane_props, "CRTC_ID")?, property::Value::CRTC(None));
+    Ok(())
+}
+
+impl Drop for DrmBackend {
+    fn drop(&mut self) {
+        for surface in self.surfaces.drain(..) {
+            if let Some(property::Value::Blob(blob)) = surface.mode_blob {
+                let _ = self.card.destroy_property_blob(blob);
+            }
+            for (buffer, framebuffer) in surface.buffers {
+                let _ = self.card.destroy_framebuffer(framebuffer);
+                let _ = self.card.destroy_dumb_buffer(buffer);
+            }
+        }
+    }
+}
+
+impl Backend for DrmBackend {
+    fn name(&self) -> &'static str {
+        "drm"
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        self.surfaces.iter().map(|s| s.output.clone()).collect()
+    }
+
+    fn dispatch_input_events(&mut self) -> CompositorResult<Vec<InputEvent>> {
+        if !self.paused && self.has_pending_flips() {
+            self.complete_page_flips()?;
+        }
+        Ok(Vec::new())
+    }
+
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn pause(&mut self) {
+        // Flips queued before the VT switch will never complete; logind drops DRM master.
+        self.paused = true;
+        for surface in &mut self.surfaces {
+            surface.flip_pending = false;
+        }
+        println!("DrmBackend: Paused.");
+    }
+
+    fn resume(&mut self) -> CompositorResult<()> {
+        if !self.paused {
+            return Ok(());
+        }
+        self.paused = false;
+        // Whoever owned the display in the meantime may have set a different mode.
+        println!("DrmBackend: Resuming, restoring modes.");
+        self.modeset()
+    }
+
+    fn device_path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+
+    fn render_frame(&mut self, output_id: u32, draw: &mut DrawFrame<'_>) -> CompositorResult<bool> {
+        if self.paused {
+            return Ok(false);
+        }
+        let index = self
+            .surfaces
+            .iter()
+            .position(|s| s.output.id == output_id)
+            .ok_or_else(|| CompositorError::BackendError(format!("Unknown DRM output {}", output_id)))?;
+        if self.surfaces[index].flip_pending || !self.surfaces[index].output.is_powered_on {
+            return Ok(false);
+        }
+
+        let back = self.surfaces[index].back();
+        {
+            let (width, height) = (self.surfaces[index].output.size.width, self.surfaces[index].output.size.height);
+            let buffer = &mut self.surfaces[index].buffers[back].0;
+            let pitch = ::drm::buffer::Buffer::pitch(buffer);
+            let mut mapping = self
+                .card
+                .map_dumb_buffer(buffer)
+                .map_err(|e| backend_error("Failed to map dumb buffer", e))?;
+            draw(mapping.as_mut(), width, height, pitch);
+        }
+
+        let surface = &self.surfaces[index];
+        let mut request = atomic::AtomicModeReq::new();
+        add_plane_properties(&mut request, surface, back)?;
+        self.card
+            .atomic_commit(AtomicCommitFlags::PAGE_FLIP_EVENT | AtomicCommitFlags::NONBLOCK, request)
+            .map_err(|e| backend_error("Failed to queue page flip", e))?;
+        self.surfaces[index].flip_pending = true;
+        Ok(true)
+    }
+
+    fn set_output_power(&mut self, output_id: u32, on: bool) -> CompositorResult<()> {
+        let surface = self
+            .surfaces
+            .iter_mut()
+            .find(|s| s.output.id == output_id)
+            .ok_or_else(|| CompositorError::BackendError(format!("Unknown DRM output {}", output_id)))?;
+        if surface.output.is_powered_on == on {
+            return Ok(());
+        }
+        surface.output.is_powered_on = on;
+        // A disabled CRTC never completes a pending flip.
+        surface.flip_pending = false;
+        if self.paused {
+            // Applied by the modeset on resume.
+            return Ok(());
+        }
+
+        let surface = self.surfaces.iter().find(|s| s.output.id == output_id).expect("surface looked up above");
+        let mut request = atomic::AtomicModeReq::new();
+        request.add_property(surface.crtc, prop(&surface.crtc_props, "ACTIVE")?, property::Value::Boolean(on));
+        if on {
+            add_plane_properties(&mut request, surface, surface.front)?;
+        } else {
+            detach_plane(&mut request, surface)?;
+        }
+        self.card
+            .atomic_commit(AtomicCommitFlags::ALLOW_MODESET, request)
+            .map_err(|e| backend_error("Failed to change output power", e))?;
+        println!("DrmBackend: Output '{}' powered {}.", surface.output.name, if on { "on" } else { "off" });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_for_physical_size() {
+        // 27" 1440p, ~109 DPI
+        assert_eq!(scale_for_physical_size(2560, 597), 1);
+        // 27" 4K, ~163 DPI
+        assert_eq!(scale_for_physical_size(3840, 597), 1);
+        // 13" 2560x1600 laptop panel, ~227 DPI
+        assert_eq!(scale_for_physical_size(2560, 286), 2);
+        // Unknown physical size
+        assert_eq!(scale_for_physical_size(3840, 0), 1);
+    }
+
+    #[test]
+    fn test_connector_name() {
+        assert_eq!(connector_name(connector::Interface::DisplayPort, 1), "DP-1");
+        assert_eq!(connector_name(connector::Interface::HDMIA, 2), "HDMI-A-2");
+        assert_eq!(connector_name(connector::Interface::EmbeddedDisplayPort, 1), "eDP-1");
+    }
+}
@@ -0,0 +1,91 @@
+// src/compositor/backend/mod.rs
+
+//! Backends connect the compositor to a source of outputs and input events.
+//!
+//! A backend owns whatever the compositor draws into (a window on an existing desktop,
+//! a DRM device, ...) and translates the input it receives into [`InputEvent`]s that
+//! can be fed into [`Server::run_loop_iteration`](crate::server::Server::run_loop_iteration).
+
+#[cfg(feature = "backend-drm")]
+pub mod drm;
+#[cfg(feature = "backend-winit")]
+pub mod winit;
+
+use std::fs::File;
+use std::path::Path;
+
+use crate::compositor::core::Output;
+use crate::compositor::CompositorResult;
+use crate::input::InputEvent;
+
+#[cfg(feature = "backend-drm")]
+pub use self::drm::DrmBackend;
+#[cfg(feature = "backend-winit")]
+pub use self::winit::WinitBackend;
+
+/// Draws a frame: receives the XRGB8888 pixels, width, height and pitch in bytes.
+pub type DrawFrame<'a> = dyn FnMut(&mut [u8], u32, u32, u32) + 'a;
+
+/// Common interface implemented by all compositor backends.
+pub trait Backend {
+    /// Short name of the backend, used in log output (e.g., "winit").
+    fn name(&self) -> &'static str;
+
+    /// Returns the outputs currently provided by this backend.
+    ///
+    /// The list may change between calls, e.g. when the host window is resized.
+    fn outputs(&self) -> Vec<Output>;
+
+    /// Waits briefly for pending input and returns it translated into `InputEvent`s.
+    ///
+    /// Returns an empty vector if nothing happened since the last call.
+    fn dispatch_input_events(&mut self) -> CompositorResult<Vec<InputEvent>>;
+
+    /// Whether the backend is still alive (e.g. the host window has not been closed).
+    fn is_running(&self) -> bool;
+
+    /// Stops rendering and releases device resources, e.g. while the session is switched
+    /// to another VT. Backends without device access ignore this.
+    fn pause(&mut self) {}
+
+    /// Reacquires resources released by [`Backend::pause`] and restores the display state.
+    fn resume(&mut self) -> CompositorResult<()> {
+        Ok(())
+    }
+
+    /// The device node the backend renders to (e.g. `/dev/dri/card0`), if any.
+    ///
+    /// Only a session pause of this device pauses the whole backend; other devices are
+    /// reported through [`Backend::device_paused`] and [`Backend::device_resumed`].
+    fn device_path(&self) -> Option<&Path> {
+        None
+    }
+
+    /// A device other than [`Backend::device_path`] was revoked by the session.
+    fn device_paused(&mut self, _path: &Path) {}
+
+    /// A device was handed back by the session.
+    ///
+    /// `file` is the new descriptor if the session reopened the device (input devices are
+    /// revoked on pause, so their old descriptor is useless); the backend must use it in
+    /// place of the old one.
+    fn device_resumed(&mut self, _path: &Path, _file: Option<File>) -> CompositorResult<()> {
+        Ok(())
+    }
+
+    /// Turns the display behind an output on or off (DPMS).
+    ///
+    /// The output's `is_powered_on` flag reported by [`Backend::outputs`] must reflect the
+    /// new state. Unknown output IDs are an error.
+    fn set_output_power(&mut self, output_id: u32, on: bool) -> CompositorResult<()>;
+
+    /// Draws the next frame of an output and queues it for presentation.
+    ///
+    /// `draw` receives the XRGB8888 pixels of the back buffer, its width and height and
+    /// the pitch in bytes. Returns `Ok(false)` without calling `draw` if the output cannot
+    /// take a frame right now (previous frame still pending, powered off, paused). Backends
+    /// that present nothing themselves keep this default.
+    fn render_frame(&mut self, _output_id: u32, _draw: &mut DrawFrame<'_>) -> CompositorResult<bool> {
+        Ok(false)
+    }
+}
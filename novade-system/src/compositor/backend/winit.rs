@@ -0,0 +1,466 @@
+// src/compositor/backend/winit.rs
+
+//! Nested development backend.
+//!
+//! Runs the compositor inside a regular window on an existing desktop (X11 or Wayland).
+//! The window's inner area is exposed as a single [`Output`] and everything winit reports
+//! for that window is translated into [`InputEvent`]s. No hardware access is needed,
+//! which makes this the quickest way to iterate on compositor logic.
+
+use std::time::Duration;
+
+use ::winit::application::ApplicationHandler;
+use ::winit::dpi::PhysicalSize;
+use ::winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
+use ::winit::event_loop::{ActiveEventLoop, EventLoop};
+use ::winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
+use ::winit::platform::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+use ::winit::platform::scancode::PhysicalKeyExtScancode;
+use ::winit::window::{Window as HostWindow, WindowId};
+
+use super::Backend;
+use crate::compositor::core::Output;
+use crate::compositor::{CompositorError, CompositorResult};
+use crate::input::{ButtonState, InputEvent, KeyState, Modifiers};
+
+/// ID of the single output exposed by the winit backend.
+pub const WINIT_OUTPUT_ID: u32 = 1;
+/// Name of the single output exposed by the winit backend.
+pub const WINIT_OUTPUT_NAME: &str = "winit-0";
+
+/// How long a single call to [`Backend::dispatch_input_events`] waits for new events.
+/// Roughly one frame at 60 Hz.
+const DISPATCH_TIMEOUT: Duration = Duration::from_millis(16);
+
+/// Pixels scrolled per wheel detent when winit reports line-based scrolling.
+const LINE_SCROLL_STEP: f64 = 15.0;
+
+/// Linux button codes (see `linux/input-event-codes.h`).
+const BTN_LEFT: u32 = 0x110;
+const BTN_RIGHT: u32 = 0x111;
+const BTN_MIDDLE: u32 = 0x112;
+const BTN_SIDE: u32 = 0x113;
+const BTN_EXTRA: u32 = 0x114;
+/// First of the ten generic buttons `BTN_0`..`BTN_9`, used for buttons winit cannot name.
+const BTN_MISC: u32 = 0x100;
+const BTN_MISC_COUNT: u16 = 10;
+
+/// Keys without an ASCII equivalent are reported as their platform scancode plus this
+/// offset, so they can never be mistaken for the ASCII key codes used for shortcuts.
+pub const SCANCODE_KEY_BASE: u32 = 0x1_0000;
+
+const LETTER_KEYS: [KeyCode; 26] = [
+    KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE, KeyCode::KeyF,
+    KeyCode::KeyG, KeyCode::KeyH, KeyCode::KeyI, KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL,
+    KeyCode::KeyM, KeyCode::KeyN, KeyCode::KeyO, KeyCode::KeyP, KeyCode::KeyQ, KeyCode::KeyR,
+    KeyCode::KeyS, KeyCode::KeyT, KeyCode::KeyU, KeyCode::KeyV, KeyCode::KeyW, KeyCode::KeyX,
+    KeyCode::KeyY, KeyCode::KeyZ,
+];
+
+const DIGIT_KEYS: [KeyCode; 10] = [
+    KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+    KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+];
+
+/// Maps a physical key to the key code carried by `InputEvent::Keyboard`.
+///
+/// Letters map to their uppercase ASCII value and digits to their ASCII value, matching the
+/// key codes the `InputManager` uses for shortcut detection (e.g. 67 for 'C'). A few
+/// control keys map to their ASCII control codes; everything else falls back to the
+/// scancode offset by [`SCANCODE_KEY_BASE`].
+pub fn key_code_from_physical(key: PhysicalKey) -> Option<u32> {
+    if let PhysicalKey::Code(code) = key {
+        if let Some(index) = LETTER_KEYS.iter().position(|k| *k == code) {
+            return Some(b'A' as u32 + index as u32);
+        }
+        if let Some(index) = DIGIT_KEYS.iter().position(|k| *k == code) {
+            return Some(b'0' as u32 + index as u32);
+        }
+        match code {
+            KeyCode::Backspace => return Some(8),
+            KeyCode::Tab => return Some(9),
+            KeyCode::Enter => return Some(13),
+            KeyCode::Escape => return Some(27),
+            KeyCode::Space => return Some(32),
+            _ => {}
+        }
+    }
+    key.to_scancode().map(|scancode| SCANCODE_KEY_BASE + scancode)
+}
+
+/// Maps a winit mouse button to a Linux button code.
+///
+/// Unnamed buttons map to `BTN_0`..`BTN_9`, so they never alias a named button. Returns
+/// `None` for buttons beyond that range.
+pub fn button_code_from_winit(button: MouseButton) -> Option<u32> {
+    match button {
+        MouseButton::Left => Some(BTN_LEFT),
+        MouseButton::Right => Some(BTN_RIGHT),
+        MouseButton::Middle => Some(BTN_MIDDLE),
+        MouseButton::Back => Some(BTN_SIDE),
+        MouseButton::Forward => Some(BTN_EXTRA),
+        MouseButton::Other(n) => (n < BTN_MISC_COUNT).then(|| BTN_MISC + n as u32),
+    }
+}
+
+/// Translates winit window events into `InputEvent`s.
+///
+/// Keeps the small amount of state the translation needs: the current modifier state
+/// (winit reports it separately from key events) and the last cursor position (winit
+/// reports absolute positions, `InputEvent::PointerMotion` carries deltas).
+#[derive(Debug, Default)]
+pub struct WinitInputConverter {
+    modifiers: Modifiers,
+    last_cursor_position: Option<(f64, f64)>,
+}
+
+impl WinitInputConverter {
+    /// Creates a converter with no modifiers held and no known cursor position.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the modifier state that will be attached to the next events.
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Updates the modifier state from a `WindowEvent::ModifiersChanged`.
+    pub fn set_modifiers(&mut self, state: ModifiersState) {
+        self.modifiers = Modifiers {
+            shift: state.shift_key(),
+            ctrl: state.control_key(),
+            alt: state.alt_key(),
+            logo: state.super_key(),
+        };
+    }
+
+    /// Converts a key press or release. Returns `None` for keys without a usable code.
+    pub fn key(&self, key: PhysicalKey, state: ElementState) -> Option<InputEvent> {
+        let key_code = key_code_from_physical(key)?;
+        let state = match state {
+            ElementState::Pressed => KeyState::Pressed,
+            ElementState::Released => KeyState::Released,
+        };
+        Some(InputEvent::Keyboard { key_code, state, modifiers: self.modifiers })
+    }
+
+    /// Converts an absolute cursor position into a relative motion event.
+    ///
+    /// The first position after the cursor enters the window only establishes the
+    /// reference point and yields `None`.
+    pub fn cursor_moved(&mut self, x: f64, y: f64) -> Option<InputEvent> {
+        let previous = self.last_cursor_position.replace((x, y));
+        previous.map(|(last_x, last_y)| InputEvent::PointerMotion {
+            delta_x: x - last_x,
+            delta_y: y - last_y,
+            modifiers: self.modifiers,
+        })
+    }
+
+    /// Forgets the last cursor position, e.g. when the cursor leaves the window.
+    pub fn cursor_left(&mut self) {
+        self.last_cursor_position = None;
+    }
+
+    /// Converts a mouse button press or release.
+    ///
+    /// Returns `None` for buttons without a Linux button code.
+    pub fn mouse_button(&self, button: MouseButton, state: ElementState) -> Option<InputEvent> {
+        let button_code = button_code_from_winit(button)?;
+        let state = match state {
+            ElementState::Pressed => ButtonState::Pressed,
+            ElementState::Released => ButtonState::Released,
+        };
+        Some(InputEvent::PointerButton { button_code, state, modifiers: self.modifiers })
+    }
+
+    /// Converts a scroll event.
+    ///
+    /// winit uses positive values for "content moves right/down", whereas
+    /// `InputEvent::PointerAxis` uses positive values for scrolling right/down, so both
+    /// axes are inverted.
+    pub fn scroll(&self, delta: MouseScrollDelta) -> InputEvent {
+        let (horizontal, vertical) = match delta {
+            MouseScrollDelta::LineDelta(x, y) => {
+                (x as f64 * LINE_SCROLL_STEP, y as f64 * LINE_SCROLL_STEP)
+            }
+            MouseScrollDelta::PixelDelta(position) => (position.x, position.y),
+        };
+        InputEvent::PointerAxis {
+            horizontal: -horizontal,
+            vertical: -vertical,
+            modifiers: self.modifiers,
+        }
+    }
+
+    /// Converts a touch event. Cancelled touch points are reported as lifted.
+    pub fn touch(&self, id: u64, phase: TouchPhase, x: f64, y: f64) -> InputEvent {
+        let touch_id = id as u32;
+        let modifiers = self.modifiers;
+        match phase {
+            TouchPhase::Started => InputEvent::TouchDown { touch_id, x, y, modifiers },
+            TouchPhase::Moved => InputEvent::TouchMotion { touch_id, x, y, modifiers },
+            TouchPhase::Ended | TouchPhase::Cancelled => InputEvent::TouchUp { touch_id, modifiers },
+        }
+    }
+}
+
+/// Receives callbacks from the winit event loop while it is being pumped.
+#[derive(Debug)]
+struct WinitHandler {
+    title: String,
+    requested_size: (u32, u32),
+    window: Option<HostWindow>,
+    size: (u32, u32),
+    converter: WinitInputConverter,
+    pending_events: Vec<InputEvent>,
+    running: bool,
+    error: Option<String>,
+    /// Simulated display power; there is no real display to turn off.
+    powered_on: bool,
+}
+
+impl WinitHandler {
+    fn push(&mut self, event: Option<InputEvent>) {
+        if let Some(event) = event {
+            self.pending_events.push(event);
+        }
+    }
+}
+
+impl ApplicationHandler for WinitHandler {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.window.is_some() {
+            return;
+        }
+        let (width, height) = self.requested_size;
+        let attributes = HostWindow::default_attributes()
+            .with_title(self.title.clone())
+            .with_inner_size(PhysicalSize::new(width, height));
+        match event_loop.create_window(attributes) {
+            Ok(window) => {
+                let size = window.inner_size();
+                self.size = (size.width, size.height);
+                println!("WinitBackend: Host window created with size {}x{}.", size.width, size.height);
+                self.window = Some(window);
+            }
+            Err(e) => {
+                self.error = Some(format!("Failed to create host window: {}", e));
+                self.running = false;
+                event_loop.exit();
+            }
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, _window_id: WindowId, event: WindowEvent) {
+        match event {
+            WindowEvent::CloseRequested => {
+                println!("WinitBackend: Host window closed.");
+                self.running = false;
+                event_loop.exit();
+            }
+            WindowEvent::Resized(size) => {
+                self.size = (size.width, size.height);
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.converter.set_modifiers(modifiers.state());
+            }
+            // Key repeat is the compositor's job, not the host's.
+            WindowEvent::KeyboardInput { event, .. } if !event.repeat => {
+                let converted = self.converter.key(event.physical_key, event.state);
+                self.push(converted);
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let converted = self.converter.cursor_moved(position.x, position.y);
+                self.push(converted);
+            }
+            WindowEvent::CursorLeft { .. } => self.converter.cursor_left(),
+            WindowEvent::MouseInput { state, button, .. } => {
+                let converted = self.converter.mouse_button(button, state);
+                self.push(converted);
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let converted = self.converter.scroll(delta);
+                self.pending_events.push(converted);
+            }
+            WindowEvent::Touch(touch) => {
+                let converted = self.converter.touch(touch.id, touch.phase, touch.location.x, touch.location.y);
+                self.pending_events.push(converted);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Development backend running the compositor inside a winit window.
+#[derive(Debug)]
+pub struct WinitBackend {
+    event_loop: EventLoop<()>,
+    handler: WinitHandler,
+}
+
+impl WinitBackend {
+    /// Opens a host window with the given title and inner size.
+    ///
+    /// Fails with `CompositorError::InitializationFailed` if no display server is
+    /// reachable or the window cannot be created.
+    pub fn new(title: &str, width: u32, height: u32) -> CompositorResult<Self> {
+        let event_loop = EventLoop::new().map_err(|e| {
+            CompositorError::InitializationFailed(format!("Failed to create winit event loop: {}", e))
+        })?;
+        let mut backend = Self {
+            event_loop,
+            handler: WinitHandler {
+                title: title.to_string(),
+                requested_size: (width, height),
+                window: None,
+                size: (width, height),
+                converter: WinitInputConverter::new(),
+                pending_events: Vec::new(),
+                running: true,
+                error: None,
+                powered_on: true,
+            },
+        };
+        // Pump once so the host window gets created before the first output query.
+        backend.pump(Some(Duration::ZERO))?;
+        Ok(backend)
+    }
+
+    fn pump(&mut self, timeout: Option<Duration>) -> CompositorResult<()> {
+        if let PumpStatus::Exit(_) = self.event_loop.pump_app_events(timeout, &mut self.handler) {
+            self.handler.running = false;
+        }
+        match self.handler.error.take() {
+            Some(message) => Err(CompositorError::InitializationFailed(message)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Backend for WinitBackend {
+    fn name(&self) -> &'static str {
+        "winit"
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        if self.handler.window.is_none() {
+            return Vec::new();
+        }
+        let (width, height) = self.handler.size;
+        let mut output = Output::new(WINIT_OUTPUT_ID, WINIT_OUTPUT_NAME.to_string(), width, height, 0, 0, true);
+        output.is_powered_on = self.handler.powered_on;
+        vec![output]
+    }
+
+    fn dispatch_input_events(&mut self) -> CompositorResult<Vec<InputEvent>> {
+        self.pump(Some(DISPATCH_TIMEOUT))?;
+        Ok(std::mem::take(&mut self.handler.pending_events))
+    }
+
+    fn is_running(&self) -> bool {
+        self.handler.running
+    }
+
+    fn set_output_power(&mut self, output_id: u32, on: bool) -> CompositorResult<()> {
+        if output_id != WINIT_OUTPUT_ID {
+            return Err(CompositorError::BackendError(format!("Unknown winit output {}", output_id)));
+        }
+        println!("WinitBackend: Output {} powered {}.", output_id, if on { "on" } else { "off" });
+        self.handler.powered_on = on;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::winit::dpi::PhysicalPosition;
+
+    #[test]
+    fn test_letter_and_digit_keys_map_to_ascii() {
+        assert_eq!(key_code_from_physical(PhysicalKey::Code(KeyCode::KeyA)), Some(65));
+        assert_eq!(key_code_from_physical(PhysicalKey::Code(KeyCode::KeyC)), Some(67));
+        assert_eq!(key_code_from_physical(PhysicalKey::Code(KeyCode::KeyZ)), Some(90));
+        assert_eq!(key_code_from_physical(PhysicalKey::Code(KeyCode::Digit0)), Some(48));
+        assert_eq!(key_code_from_physical(PhysicalKey::Code(KeyCode::Enter)), Some(13));
+    }
+
+    #[test]
+    fn test_other_keys_are_offset_scancodes() {
+        let code = key_code_from_physical(PhysicalKey::Code(KeyCode::F1)).expect("F1 has a scancode");
+        assert!(code >= SCANCODE_KEY_BASE);
+    }
+
+    #[test]
+    fn test_key_event_carries_current_modifiers() {
+        let mut converter = WinitInputConverter::new();
+        converter.set_modifiers(ModifiersState::CONTROL);
+        let event = converter.key(PhysicalKey::Code(KeyCode::KeyC), ElementState::Pressed);
+        assert_eq!(
+            event,
+            Some(InputEvent::Keyboard {
+                key_code: 67,
+                state: KeyState::Pressed,
+                modifiers: Modifiers { ctrl: true, ..Default::default() },
+            })
+        );
+    }
+
+    #[test]
+    fn test_cursor_motion_is_relative() {
+        let mut converter = WinitInputConverter::new();
+        assert_eq!(converter.cursor_moved(10.0, 20.0), None, "First position only sets the reference");
+        assert_eq!(
+            converter.cursor_moved(15.0, 18.0),
+            Some(InputEvent::PointerMotion { delta_x: 5.0, delta_y: -2.0, modifiers: Modifiers::default() })
+        );
+        converter.cursor_left();
+        assert_eq!(converter.cursor_moved(100.0, 100.0), None);
+    }
+
+    #[test]
+    fn test_mouse_buttons_use_linux_codes() {
+        let converter = WinitInputConverter::new();
+        assert_eq!(
+            converter.mouse_button(MouseButton::Left, ElementState::Pressed),
+            Some(InputEvent::PointerButton { button_code: BTN_LEFT, state: ButtonState::Pressed, modifiers: Modifiers::default() })
+        );
+        assert_eq!(button_code_from_winit(MouseButton::Right), Some(BTN_RIGHT));
+        assert_eq!(button_code_from_winit(MouseButton::Middle), Some(BTN_MIDDLE));
+    }
+
+    #[test]
+    fn test_unnamed_mouse_buttons_do_not_alias_named_ones() {
+        let named = [MouseButton::Left, MouseButton::Right, MouseButton::Middle, MouseButton::Back, MouseButton::Forward]
+            .map(button_code_from_winit);
+        assert_ne!(button_code_from_winit(MouseButton::Other(1)), button_code_from_winit(MouseButton::Right));
+        for n in 0..BTN_MISC_COUNT {
+            let code = button_code_from_winit(MouseButton::Other(n));
+            assert!(code.is_some() && !named.contains(&code), "Other({}) maps to {:?}", n, code);
+        }
+        assert_eq!(button_code_from_winit(MouseButton::Other(BTN_MISC_COUNT)), None);
+    }
+
+    #[test]
+    fn test_scroll_is_inverted() {
+        let converter = WinitInputConverter::new();
+        assert_eq!(
+            converter.scroll(MouseScrollDelta::LineDelta(0.0, 1.0)),
+            InputEvent::PointerAxis { horizontal: 0.0, vertical: -LINE_SCROLL_STEP, modifiers: Modifiers::default() }
+        );
+        assert_eq!(
+            converter.scroll(MouseScrollDelta::PixelDelta(PhysicalPosition::new(-4.0, 8.0))),
+            InputEvent::PointerAxis { horizontal: 4.0, vertical: -8.0, modifiers: Modifiers::default() }
+        );
+    }
+
+    #[test]
+    fn test_touch_phases() {
+        let converter = WinitInputConverter::new();
+        assert!(matches!(converter.touch(3, TouchPhase::Started, 1.0, 2.0), InputEvent::TouchDown { touch_id: 3, .. }));
+        assert!(matches!(converter.touch(3, TouchPhase::Moved, 1.0, 2.0), InputEvent::TouchMotion { touch_id: 3, .. }));
+        assert!(matches!(converter.touch(3, TouchPhase::Cancelled, 1.0, 2.0), InputEvent::TouchUp { touch_id: 3, .. }));
+    }
+}
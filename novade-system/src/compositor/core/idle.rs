@@ -0,0 +1,99 @@
+// src/compositor/core/idle.rs
+
+use std::time::{Duration, Instant};
+
+/// Tracks user activity and reports when the session becomes idle.
+///
+/// Every input event counts as activity. Once no activity has been seen for `timeout`,
+/// [`IdleTracker::poll`] reports the transition to idle exactly once; the next activity
+/// makes the session active again.
+#[derive(Debug, Clone)]
+pub struct IdleTracker {
+    /// Inactivity period after which the session is idle. `None` disables idle detection.
+    pub timeout: Option<Duration>,
+    last_activity: Instant,
+    idle: bool,
+}
+
+impl IdleTracker {
+    /// Creates a tracker with the given timeout, treating `now` as the last activity.
+    pub fn new(timeout: Option<Duration>, now: Instant) -> Self {
+        Self { timeout, last_activity: now, idle: false }
+    }
+
+    /// Records user activity.
+    ///
+    /// # Returns
+    /// `true` if the session was idle and has become active again.
+    pub fn notify_activity(&mut self, now: Instant) -> bool {
+        self.last_activity = now;
+        std::mem::replace(&mut self.idle, false)
+    }
+
+    /// Whether the session is currently considered idle.
+    pub fn is_idle(&self) -> bool {
+        self.idle
+    }
+
+    /// Checks the timeout.
+    ///
+    /// # Returns
+    /// `true` only on the call that detects the transition from active to idle.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        let Some(timeout) = self.timeout else {
+            return false;
+        };
+        if self.idle || now.saturating_duration_since(self.last_activity) < timeout {
+            return false;
+        }
+        self.idle = true;
+        true
+    }
+}
+
+impl Default for IdleTracker {
+    /// Idle detection disabled.
+    fn default() -> Self {
+        Self::new(None, Instant::now())
+    }
+}
+
+/// A request by a client to keep the session from going idle (e.g. a video player).
+///
+/// An inhibitor only takes effect while its window is mapped and visible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdleInhibitor {
+    /// The client that registered the inhibitor and owns its window.
+    pub client_id: u32,
+    /// The window the inhibitor is attached to.
+    pub window_id: u32,
+    /// Human-readable reason, e.g. "Playing video".
+    pub reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_transition_reported_once() {
+        let start = Instant::now();
+        let mut tracker = IdleTracker::new(Some(Duration::from_secs(60)), start);
+
+        assert!(!tracker.poll(start + Duration::from_secs(59)));
+        assert!(tracker.poll(start + Duration::from_secs(60)));
+        assert!(tracker.is_idle());
+        assert!(!tracker.poll(start + Duration::from_secs(120)), "Transition is only reported once");
+
+        assert!(tracker.notify_activity(start + Duration::from_secs(121)));
+        assert!(!tracker.is_idle());
+        assert!(!tracker.poll(start + Duration::from_secs(150)));
+    }
+
+    #[test]
+    fn test_disabled_tracker_never_idles() {
+        let start = Instant::now();
+        let mut tracker = IdleTracker::new(None, start);
+        assert!(!tracker.poll(start + Duration::from_secs(3600)));
+    }
+}
@@ -0,0 +1,42 @@
+// src/compositor/core/lock.rs
+
+/// A lock surface covering one output while the session is locked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockSurface {
+    /// ID of the output this surface covers.
+    pub output_id: u32,
+    /// ID of the window backing the lock surface.
+    pub window_id: u32,
+}
+
+/// State of a locked session.
+///
+/// While a `SessionLock` is active, only lock surfaces are shown and receive input.
+/// A lock can be created by a privileged lock client or by the compositor itself
+/// (auto-lock on idle); in the latter case it has no owner until a lock client claims it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionLock {
+    /// ID of the client that owns the lock and may unlock it.
+    pub owner_client_id: Option<u32>,
+    /// Lock surfaces registered by the owner, at most one per output.
+    pub surfaces: Vec<LockSurface>,
+    /// Focus of each seat at the time the session was locked, restored on unlock.
+    pub(crate) saved_focus: Vec<(String, Option<u32>)>,
+}
+
+impl SessionLock {
+    /// Creates a lock without surfaces, remembering the focus to restore on unlock.
+    pub fn new(owner_client_id: Option<u32>, saved_focus: Vec<(String, Option<u32>)>) -> Self {
+        Self { owner_client_id, surfaces: Vec::new(), saved_focus }
+    }
+
+    /// Whether the given window is one of the lock surfaces.
+    pub fn is_lock_surface(&self, window_id: u32) -> bool {
+        self.surfaces.iter().any(|s| s.window_id == window_id)
+    }
+
+    /// Returns the lock surface registered for an output, if any.
+    pub fn surface_for_output(&self, output_id: u32) -> Option<&LockSurface> {
+        self.surfaces.iter().find(|s| s.output_id == output_id)
+    }
+}
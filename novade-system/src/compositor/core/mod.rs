@@ -1,7 +1,10 @@
 // src/compositor/core/mod.rs
 mod display;
+mod idle;
+mod lock;
 mod output;
 mod seat;
+mod startup;
 mod state;
 mod window;
 
@@ -10,7 +13,10 @@ mod window;
 mod state_tests;
 
 pub use display::Display;
+pub use idle::{IdleInhibitor, IdleTracker};
+pub use lock::{LockSurface, SessionLock};
 pub use output::Output;
 pub use seat::Seat;
+pub use startup::PendingStartup;
 pub use state::CompositorState;
 pub use window::{Window, WindowState}; // also export WindowState
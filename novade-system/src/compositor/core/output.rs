@@ -1,5 +1,8 @@
 // src/compositor/core/output.rs
 
+use novade_core::types::{Point, Rect, Size};
+use novade_domain::entities::{DisplayMode, OutputConfiguration, Rotation};
+
 /// Represents a display output (e.g., a monitor).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Output {
@@ -7,24 +10,82 @@ pub struct Output {
     pub id: u32,
     /// Human-readable name for the output (e.g., "DP-1").
     pub name: String,
-    /// Width of the output in pixels.
-    pub width: u32,
-    /// Height of the output in pixels.
-    pub height: u32,
-    /// X-coordinate of the output's top-left corner in the global compositor space.
-    pub x: i32,
-    /// Y-coordinate of the output's top-left corner in the global compositor space.
-    pub y: i32,
+    /// Size of the output's current mode in pixels.
+    pub size: Size<u32>,
+    /// Position of the output's top-left corner in the global compositor space.
+    pub position: Point<i32>,
     /// Whether this output is considered the primary display.
     ///
     /// In a multi-output setup, one output is typically designated as primary.
     /// This can influence default window placement, taskbar location, etc.
     pub is_primary: bool,
+    /// Integer scale factor clients should render at (1 for regular, 2 for HiDPI displays).
+    pub scale: u32,
+    /// Human-readable description of the attached display (e.g., "DEL DELL U2720Q"),
+    /// usually taken from the monitor's EDID. `None` if unknown.
+    pub description: Option<String>,
+    /// Whether the display is powered on. Powered-off outputs are skipped when rendering.
+    pub is_powered_on: bool,
 }
 
 impl Output {
-    /// Creates a new display output.
+    /// Creates a new display output with a scale of 1 and no description.
     pub fn new(id: u32, name: String, width: u32, height: u32, x: i32, y: i32, is_primary: bool) -> Self {
-        Self { id, name, width, height, x, y, is_primary }
+        Self {
+            id,
+            name,
+            size: Size::new(width, height),
+            position: Point::new(x, y),
+            is_primary,
+            scale: 1,
+            description: None,
+            is_powered_on: true,
+        }
+    }
+
+    /// The area the output covers in the global compositor space, i.e. its pixel size
+    /// divided by the scale factor.
+    pub fn geometry(&self) -> Rect<i32> {
+        let size = Size::new(self.size.width as i32, self.size.height as i32);
+        Rect::from_parts(self.position, size.downscale(self.scale.max(1) as i32))
+    }
+
+    /// Sets the scale factor. A scale of 0 is treated as 1.
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Sets the human-readable description of the attached display.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// The output's current settings as part of a display layout. The refresh rate is not
+    /// tracked and reported as unknown.
+    pub fn configuration(&self) -> OutputConfiguration {
+        OutputConfiguration {
+            x: self.position.x,
+            y: self.position.y,
+            scale: self.scale as f64,
+            primary: self.is_primary,
+            ..OutputConfiguration::new(&self.name, DisplayMode::new(self.size.width, self.size.height, 0))
+        }
+    }
+
+    /// Takes over position, scale and the primary flag from `configuration`. Fractional
+    /// scales are rounded up, as clients render at integer scales.
+    ///
+    /// Mode and rotation are left alone, since no backend can change them yet.
+    ///
+    /// # Returns
+    /// `false` if the configured mode or rotation differs from the output's.
+    pub fn apply_configuration(&mut self, configuration: &OutputConfiguration) -> bool {
+        self.position = Point::new(configuration.x, configuration.y);
+        self.scale = (configuration.scale.ceil() as u32).max(1);
+        self.is_primary = configuration.primary;
+        (configuration.mode.width, configuration.mode.height) == (self.size.width, self.size.height)
+            && configuration.rotation == Rotation::Normal
     }
 }
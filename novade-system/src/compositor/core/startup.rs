@@ -0,0 +1,17 @@
+// src/compositor/core/startup.rs
+
+use std::time::Instant;
+
+/// An application launch announced by the launcher whose first window has not appeared yet.
+///
+/// While startups are pending the compositor shows a "launching" indicator. When a window
+/// presents the startup token, the startup completes and that window gets focus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingStartup {
+    /// Startup notification token handed to the launched process.
+    pub token: String,
+    /// Application ID of the launched application, assigned to the window if it has none.
+    pub app_id: Option<String>,
+    /// Time the launch was announced.
+    pub started_at: Instant,
+}
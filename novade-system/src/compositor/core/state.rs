@@ -5,6 +5,11 @@ use super::output::Output;
 use super::window::{Window, WindowState}; // WindowState needs to be in scope
 use super::seat::Seat;
 use super::display::Display;
+use super::idle::IdleInhibitor;
+use super::lock::{LockSurface, SessionLock};
+use super::startup::PendingStartup;
+use std::time::{Duration, Instant};
+use novade_core::types::{Point, Size};
 
 
 /// Manages the overall state of the Wayland compositor.
@@ -15,6 +20,12 @@ pub struct CompositorState {
     pub outputs: Vec<Output>,
     pub windows: Vec<Window>,
     pub seats: Vec<Seat>,
+    /// Active session lock, if the session is locked.
+    pub session_lock: Option<SessionLock>,
+    /// Idle inhibitors registered by clients, at most one per window.
+    pub idle_inhibitors: Vec<IdleInhibitor>,
+    /// Application launches waiting for their first window, oldest first.
+    pub pending_startups: Vec<PendingStartup>,
     next_window_id: u32,
     next_output_id: u32,
 }
@@ -29,6 +40,9 @@ impl CompositorState {
             outputs: Vec::new(),
             windows: Vec::new(),
             seats: vec![Seat::new("seat0".to_string())],
+            session_lock: None,
+            idle_inhibitors: Vec::new(),
+            pending_startups: Vec::new(),
             next_window_id: 1,
             next_output_id: 1,
         };
@@ -71,6 +85,28 @@ impl CompositorState {
         }
     }
 
+    /// Records the power state of an output.
+    ///
+    /// This only updates the compositor's view; use `Server::set_output_power` to also
+    /// switch the display through the backend.
+    ///
+    /// # Returns
+    /// `false` if the output does not exist.
+    pub fn set_output_power(&mut self, output_id: u32, on: bool) -> bool {
+        match self.outputs.iter_mut().find(|o| o.id == output_id) {
+            Some(output) => {
+                output.is_powered_on = on;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the outputs that are powered on and therefore need to be rendered.
+    pub fn powered_outputs(&self) -> impl Iterator<Item = &Output> {
+        self.outputs.iter().filter(|o| o.is_powered_on)
+    }
+
     /// Returns the next available window ID and increments the internal counter.
     pub fn next_window_id(&mut self) -> u32 {
         let id = self.next_window_id;
@@ -87,6 +123,10 @@ impl CompositorState {
     pub fn remove_window(&mut self, window_id: u32) -> bool {
         if let Some(index) = self.windows.iter().position(|w| w.id == window_id) {
             self.windows.remove(index);
+            if let Some(lock) = self.session_lock.as_mut() {
+                lock.surfaces.retain(|s| s.window_id != window_id);
+            }
+            self.idle_inhibitors.retain(|i| i.window_id != window_id);
             true
         } else {
             false
@@ -113,12 +153,23 @@ impl CompositorState {
     /// * `seat_name` - The name of the seat to update focus for.
     /// * `window_id` - Optional ID of the window to focus.
     ///
+    /// While the session is locked, only lock surfaces can receive focus; requests to focus
+    /// any other window are ignored.
+    ///
     /// # Returns
     /// `true` if the seat was found and focus processing was attempted.
-    /// `false` if the seat itself was not found.
+    /// `false` if the seat itself was not found, or the request was ignored because the
+    /// session is locked.
     pub fn set_focused_window_for_seat(&mut self, seat_name: &str, window_id: Option<u32>) -> bool {
+        if let (Some(id), Some(lock)) = (window_id, self.session_lock.as_ref()) {
+            if !lock.is_lock_surface(id) {
+                println!("CompositorState: Session is locked, refusing to focus window ID {} on seat '{}'.", id, seat_name);
+                return false;
+            }
+        }
+
         let target_window_is_mapped_and_exists = match window_id {
-            Some(id) => self.windows.iter().find(|w| w.id == id).map_or(false, |w| w.is_mapped),
+            Some(id) => self.windows.iter().find(|w| w.id == id).is_some_and(|w| w.is_mapped),
             None => true, // Clearing focus (target_id is None) is always allowed from a validity perspective
         };
 
@@ -156,8 +207,7 @@ impl CompositorState {
             return false;
         }
         if let Some(window) = self.find_window_mut(window_id) {
-            window.width = new_width;
-            window.height = new_height;
+            window.size = Size::new(new_width, new_height);
             println!("CompositorState: Window ID {} resized to {}x{}", window_id, new_width, new_height);
             true
         } else {
@@ -169,8 +219,7 @@ impl CompositorState {
     /// Moves a specified window to new coordinates.
     pub fn move_window(&mut self, window_id: u32, new_x: i32, new_y: i32) -> bool {
         if let Some(window) = self.find_window_mut(window_id) {
-            window.x = new_x;
-            window.y = new_y;
+            window.position = Point::new(new_x, new_y);
             println!("CompositorState: Window ID {} moved to ({}, {})", window_id, new_x, new_y);
             true
         } else {
@@ -187,7 +236,11 @@ impl CompositorState {
     /// Only mapped windows are considered for tiling. Tiled windows have their state set to `WindowState::Tiled`.
     pub fn tile_windows(&mut self) {
         // Collect mutable references to mapped windows first.
-        let mut mapped_windows_refs: Vec<&mut Window> = self.windows.iter_mut().filter(|w| w.is_mapped).collect();
+        // Lock surfaces cover their output and are never part of the layout.
+        let lock = &self.session_lock;
+        let mut mapped_windows_refs: Vec<&mut Window> = self.windows.iter_mut()
+            .filter(|w| w.is_mapped && !lock.as_ref().is_some_and(|l| l.is_lock_surface(w.id)))
+            .collect();
         let num_mapped_windows = mapped_windows_refs.len();
 
         if num_mapped_windows == 0 {
@@ -201,7 +254,7 @@ impl CompositorState {
         let (screen_x, screen_y, screen_width, screen_height) = match target_output {
             Some(output) => {
                 println!("CompositorState: Tiling on output ID: {}, Name: '{}', Primary: {}", output.id, output.name, output.is_primary);
-                (output.x, output.y, output.width, output.height)
+                (output.position.x, output.position.y, output.size.width, output.size.height)
             }
             None => {
                 println!("CompositorState: No outputs found, tiling on default 1920x1080 screen at (0,0).");
@@ -213,10 +266,8 @@ impl CompositorState {
         let window_height = screen_height;
 
         for (i, window) in mapped_windows_refs.iter_mut().enumerate() {
-            window.x = screen_x + (i as u32 * window_width) as i32;
-            window.y = screen_y;
-            window.width = window_width;
-            window.height = window_height;
+            window.position = Point::new(screen_x + (i as u32 * window_width) as i32, screen_y);
+            window.size = Size::new(window_width, window_height);
             window.state = WindowState::Tiled;
         }
         println!("CompositorState: Mapped windows tiled. Total mapped: {}. On screen area: {}x{} at ({},{}). Each window: {}x{}",
@@ -224,6 +275,7 @@ impl CompositorState {
     }
 
     /// Changes focus to the next **mapped** window in the list for the specified seat.
+    /// While the session is locked, only lock surfaces are cycled through.
     ///
     /// If no window is currently focused on the seat, or if the focused window is unmapped,
     /// it focuses the first mapped window. If the last mapped window is focused, it wraps around
@@ -236,8 +288,10 @@ impl CompositorState {
     /// `true` if focus was successfully set to a mapped window or cleared because no mapped windows exist (and seat was found).
     /// `false` if the seat was not found.
     pub fn focus_next_window(&mut self, seat_name: &str) -> bool {
+        let lock = &self.session_lock;
         let mapped_window_ids: Vec<u32> = self.windows.iter()
                                 .filter(|w| w.is_mapped) // Corrected: 'filter'
+                                .filter(|w| lock.as_ref().is_none_or(|l| l.is_lock_surface(w.id)))
                                 .map(|w| w.id)
                                 .collect();
 
@@ -293,6 +347,10 @@ impl CompositorState {
             .and_then(|s| s.focused_window);
 
         if let Some(focused_window_id) = focused_window_id_option {
+            if self.session_lock.as_ref().is_some_and(|l| !l.is_lock_surface(focused_window_id)) {
+                println!("CompositorState: Session is locked, not dispatching to window ID {}.", focused_window_id);
+                return false;
+            }
             // Find the window that is supposed to be focused
             if let Some(window) = self.windows.iter_mut().find(|w| w.id == focused_window_id) {
                 // Check if this window is actually mapped
@@ -311,6 +369,243 @@ impl CompositorState {
         }
         false
     }
+
+    /// Registers an idle inhibitor for a mapped window of `client_id`, replacing an existing one.
+    ///
+    /// # Returns
+    /// `false` if the window does not exist, belongs to another client or is not mapped.
+    pub fn add_idle_inhibitor(&mut self, client_id: u32, window_id: u32, reason: String) -> bool {
+        if !self.find_window(window_id).is_some_and(|w| w.client_id == client_id && w.is_mapped) {
+            println!(
+                "CompositorState: Client {} cannot inhibit idle for window ID {} (not its own, unmapped or non-existent).",
+                client_id, window_id
+            );
+            return false;
+        }
+        self.idle_inhibitors.retain(|i| i.window_id != window_id);
+        println!("CompositorState: Window ID {} inhibits idle: {}", window_id, reason);
+        self.idle_inhibitors.push(IdleInhibitor { client_id, window_id, reason });
+        true
+    }
+
+    /// Removes the idle inhibitor `client_id` registered for a window.
+    ///
+    /// # Returns
+    /// `true` if an inhibitor was removed.
+    pub fn remove_idle_inhibitor(&mut self, client_id: u32, window_id: u32) -> bool {
+        let count = self.idle_inhibitors.len();
+        self.idle_inhibitors.retain(|i| !(i.client_id == client_id && i.window_id == window_id));
+        self.idle_inhibitors.len() != count
+    }
+
+    /// Removes all idle inhibitors registered by a client, e.g. when it disconnects.
+    ///
+    /// # Returns
+    /// The number of removed inhibitors.
+    pub fn remove_client_idle_inhibitors(&mut self, client_id: u32) -> usize {
+        let count = self.idle_inhibitors.len();
+        self.idle_inhibitors.retain(|i| i.client_id != client_id);
+        count - self.idle_inhibitors.len()
+    }
+
+    /// Drops inhibitors whose window no longer exists or has been unmapped.
+    pub fn prune_idle_inhibitors(&mut self) {
+        let windows = &self.windows;
+        self.idle_inhibitors.retain(|i| {
+            let keep = windows.iter().any(|w| w.id == i.window_id && w.is_mapped);
+            if !keep {
+                println!("CompositorState: Releasing idle inhibitor of window ID {}.", i.window_id);
+            }
+            keep
+        });
+    }
+
+    /// Whether any inhibitor currently prevents the session from going idle.
+    ///
+    /// Only inhibitors of visible windows count: mapped, not minimized and not hidden
+    /// behind the lock screen.
+    pub fn is_idle_inhibited(&self) -> bool {
+        !self.is_locked()
+            && self.idle_inhibitors.iter().any(|i| {
+                self.find_window(i.window_id)
+                    .is_some_and(|w| w.is_mapped && w.state != WindowState::Minimized)
+            })
+    }
+
+    /// Registers a launch announced with a startup notification token.
+    ///
+    /// A startup with the same token replaces the existing one.
+    pub fn begin_startup(&mut self, token: String, app_id: Option<String>, now: Instant) {
+        self.pending_startups.retain(|s| s.token != token);
+        println!("CompositorState: Startup '{}' pending for {:?}.", token, app_id);
+        self.pending_startups.push(PendingStartup { token, app_id, started_at: now });
+    }
+
+    /// Completes the startup identified by `token` for the given window.
+    ///
+    /// The window inherits the startup's application ID if it has none yet.
+    ///
+    /// # Returns
+    /// The completed startup, or `None` if the token is unknown (or expired) or the window
+    /// does not exist.
+    pub fn complete_startup(&mut self, token: &str, window_id: u32) -> Option<PendingStartup> {
+        let index = self.pending_startups.iter().position(|s| s.token == token)?;
+        let window = self.windows.iter_mut().find(|w| w.id == window_id)?;
+        let startup = self.pending_startups.remove(index);
+        if window.app_id.is_none() {
+            window.app_id = startup.app_id.clone();
+        }
+        println!("CompositorState: Startup '{}' completed by window ID {}.", token, window_id);
+        Some(startup)
+    }
+
+    /// Drops startups that have been pending for at least `timeout`, e.g. because the
+    /// application never opened a window or does not support startup notification.
+    ///
+    /// # Returns
+    /// The expired startups.
+    pub fn expire_startups(&mut self, now: Instant, timeout: Duration) -> Vec<PendingStartup> {
+        let (expired, pending) = std::mem::take(&mut self.pending_startups)
+            .into_iter()
+            .partition(|s| now.saturating_duration_since(s.started_at) >= timeout);
+        self.pending_startups = pending;
+        expired
+    }
+
+    /// Whether an application launch is in progress (show the "launching" indicator).
+    pub fn is_launching(&self) -> bool {
+        !self.pending_startups.is_empty()
+    }
+
+    /// Whether the session is currently locked.
+    pub fn is_locked(&self) -> bool {
+        self.session_lock.is_some()
+    }
+
+    /// Whether the given window is a lock surface of the active session lock.
+    pub fn is_lock_surface(&self, window_id: u32) -> bool {
+        self.session_lock.as_ref().is_some_and(|l| l.is_lock_surface(window_id))
+    }
+
+    /// Locks the session.
+    ///
+    /// The focus of every seat is remembered and cleared; from now on only lock surfaces
+    /// are shown and receive input. A lock created without owner (e.g. auto-lock on idle)
+    /// can later be claimed by a lock client calling this again with its ID.
+    ///
+    /// # Returns
+    /// `true` if the session was locked or an unowned lock was claimed, `false` if the
+    /// session is already locked by a client.
+    pub fn lock_session(&mut self, owner_client_id: Option<u32>) -> bool {
+        if let Some(lock) = self.session_lock.as_mut() {
+            if lock.owner_client_id.is_none() && owner_client_id.is_some() {
+                lock.owner_client_id = owner_client_id;
+                println!("CompositorState: Session lock claimed by client {:?}.", owner_client_id);
+                return true;
+            }
+            println!("CompositorState: Session is already locked.");
+            return false;
+        }
+
+        let saved_focus = self.seats.iter().map(|s| (s.name.clone(), s.focused_window)).collect();
+        for seat in self.seats.iter_mut() {
+            seat.focused_window = None;
+        }
+        for window in self.windows.iter_mut() {
+            window.focused = false;
+        }
+        self.session_lock = Some(SessionLock::new(owner_client_id, saved_focus));
+        println!("CompositorState: Session locked (owner: {:?}).", owner_client_id);
+        true
+    }
+
+    /// Creates a lock surface covering the given output for the lock owner.
+    ///
+    /// Replaces an existing lock surface on the same output. The new surface gets focus
+    /// on seats that have none, and always if it covers the primary output.
+    ///
+    /// # Returns
+    /// The window ID of the lock surface, or `None` if the session is not locked, the
+    /// client does not own the lock, or the output does not exist.
+    pub fn add_lock_surface(&mut self, client_id: u32, output_id: u32) -> Option<u32> {
+        let owns_lock = self.session_lock.as_ref().is_some_and(|l| l.owner_client_id == Some(client_id));
+        if !owns_lock {
+            println!("CompositorState: Client {} does not own the session lock.", client_id);
+            return None;
+        }
+        let output = self.outputs.iter().find(|o| o.id == output_id)?.clone();
+
+        let previous = self
+            .session_lock
+            .as_ref()
+            .and_then(|l| l.surface_for_output(output_id))
+            .map(|s| s.window_id);
+        if let Some(previous_window_id) = previous {
+            self.remove_window(previous_window_id);
+        }
+
+        let window_id = self.next_window_id();
+        let mut window = Window::new(
+            window_id,
+            client_id,
+            "Lock Screen".to_string(),
+            output.size.width,
+            output.size.height,
+            output.position.x,
+            output.position.y,
+        );
+        window.map();
+        self.windows.push(window);
+        if let Some(lock) = self.session_lock.as_mut() {
+            lock.surfaces.push(LockSurface { output_id, window_id });
+        }
+
+        let seats_to_focus: Vec<String> = self.seats.iter()
+            .filter(|s| output.is_primary || s.focused_window.is_none())
+            .map(|s| s.name.clone())
+            .collect();
+        for seat_name in seats_to_focus {
+            self.set_focused_window_for_seat(&seat_name, Some(window_id));
+        }
+        println!("CompositorState: Lock surface {} created on output {}.", window_id, output_id);
+        Some(window_id)
+    }
+
+    /// Unlocks the session if `client_id` owns the lock.
+    ///
+    /// Lock surfaces are destroyed and the focus saved when locking is restored.
+    ///
+    /// # Returns
+    /// `true` if the session was unlocked.
+    pub fn unlock_session(&mut self, client_id: u32) -> bool {
+        let owns_lock = self.session_lock.as_ref().is_some_and(|l| l.owner_client_id == Some(client_id));
+        if !owns_lock {
+            println!("CompositorState: Client {} cannot unlock the session.", client_id);
+            return false;
+        }
+        let Some(lock) = self.session_lock.take() else {
+            return false;
+        };
+        for surface in &lock.surfaces {
+            self.remove_window(surface.window_id);
+        }
+        for (seat_name, window_id) in lock.saved_focus {
+            self.set_focused_window_for_seat(&seat_name, window_id);
+        }
+        println!("CompositorState: Session unlocked by client {}.", client_id);
+        true
+    }
+
+    /// Returns the IDs of the windows to draw, bottom to top.
+    ///
+    /// While the session is locked, only the lock surfaces are drawn so nothing else can
+    /// show through.
+    pub fn stacking_order(&self) -> Vec<u32> {
+        match &self.session_lock {
+            Some(lock) => lock.surfaces.iter().map(|s| s.window_id).collect(),
+            None => self.windows.iter().filter(|w| w.is_mapped).map(|w| w.id).collect(),
+        }
+    }
 }
 
 impl Default for CompositorState {
@@ -15,6 +15,15 @@ use crate::compositor::core::output::Output; // For testing tiling with an outpu
 use crate::compositor::core::window::WindowState; // For asserting tiled state
 
 
+// Creates a window for client 1 that is already mapped, since focus, dispatch and
+// tiling only consider mapped windows.
+fn new_mapped_window(id: u32, title: String, width: u32, height: u32, x: i32, y: i32) -> Window {
+    let mut window = Window::new(id, 1, title, width, height, x, y);
+    window.map();
+    window
+}
+
+
 #[test]
 fn test_next_ids() {
     // Let's adjust the expectation based on new() creating two outputs
@@ -67,7 +76,7 @@ fn test_add_remove_output() {
 fn test_add_find_remove_window() {
     let mut state = CompositorState::new();
     let window_id = state.next_window_id();
-    let window = Window::new(window_id, "Test Window".to_string(), 800, 600, 0, 0);
+    let window = new_mapped_window(window_id, "Test Window".to_string(), 800, 600, 0, 0);
     state.add_window(window.clone());
     assert_eq!(state.windows.len(), 1);
 
@@ -89,11 +98,11 @@ fn test_add_find_remove_window() {
 fn test_focus_management() {
     let mut state = CompositorState::new();
     let window1_id = state.next_window_id();
-    let window1 = Window::new(window1_id, "Window 1".to_string(), 800, 600, 0, 0);
+    let window1 = new_mapped_window(window1_id, "Window 1".to_string(), 800, 600, 0, 0);
     state.add_window(window1.clone());
 
     let window2_id = state.next_window_id();
-    let window2 = Window::new(window2_id, "Window 2".to_string(), 1024, 768, 50, 50);
+    let window2 = new_mapped_window(window2_id, "Window 2".to_string(), 1024, 768, 50, 50);
     state.add_window(window2.clone());
 
     assert!(state.set_focused_window_for_seat("seat0", Some(window1_id)));
@@ -125,7 +134,7 @@ fn default_input_modifiers() -> InputModifiers {
 fn test_dispatch_event_to_focused_window() {
     let mut state = CompositorState::new(); // Assumes new() creates "seat0"
     let window_id = state.next_window_id();
-    let window = Window::new(window_id, "Focused Window".to_string(), 800, 600, 0, 0);
+    let window = new_mapped_window(window_id, "Focused Window".to_string(), 800, 600, 0, 0);
     state.add_window(window);
     state.set_focused_window_for_seat("seat0", Some(window_id));
 
@@ -164,7 +173,7 @@ fn test_dispatch_event_no_focused_window() {
 fn test_dispatch_event_invalid_seat_name() {
     let mut state = CompositorState::new();
     let window_id = state.next_window_id();
-    let window = Window::new(window_id, "Test Window".to_string(), 800, 600, 0, 0);
+    let window = new_mapped_window(window_id, "Test Window".to_string(), 800, 600, 0, 0);
     state.add_window(window);
     state.set_focused_window_for_seat("seat0", Some(window_id)); // Focus on valid seat
 
@@ -222,16 +231,16 @@ fn test_tile_windows_one_window_default_screen() {
     let mut state = CompositorState::new();
     state.outputs.clear(); // Ensure tiling uses default screen, not default outputs
     let window_id = state.next_window_id();
-    state.add_window(Window::new(window_id, "Win1".to_string(), 600, 400, 0, 0));
+    state.add_window(new_mapped_window(window_id, "Win1".to_string(), 600, 400, 0, 0));
 
     state.tile_windows();
 
     assert_eq!(state.windows.len(), 1);
     let win = &state.windows[0];
-    assert_eq!(win.x, 0);
-    assert_eq!(win.y, 0);
-    assert_eq!(win.width, 1920); // Default screen width
-    assert_eq!(win.height, 1080); // Default screen height
+    assert_eq!(win.position.x, 0);
+    assert_eq!(win.position.y, 0);
+    assert_eq!(win.size.width, 1920); // Default screen width
+    assert_eq!(win.size.height, 1080); // Default screen height
     assert_eq!(win.state, WindowState::Tiled);
 }
 
@@ -243,9 +252,9 @@ fn test_tile_windows_multiple_windows_with_output() {
     state.add_output(Output::new(output_id, "Output-1".to_string(), 1600, 900, 0, 0, true)); // Make it primary
 
     let win1_id = state.next_window_id();
-    state.add_window(Window::new(win1_id, "Win1".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(win1_id, "Win1".to_string(), 100, 100, 0, 0));
     let win2_id = state.next_window_id();
-    state.add_window(Window::new(win2_id, "Win2".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(win2_id, "Win2".to_string(), 100, 100, 0, 0));
 
     state.tile_windows();
 
@@ -253,23 +262,23 @@ fn test_tile_windows_multiple_windows_with_output() {
     let win1 = state.find_window(win1_id).unwrap();
     let win2 = state.find_window(win2_id).unwrap();
 
-    assert_eq!(win1.width, 1600 / 2); // Tiled width based on output
-    assert_eq!(win1.height, 900);    // Full height of output
-    assert_eq!(win1.x, 0);
-    assert_eq!(win1.y, 0);
+    assert_eq!(win1.size.width, 1600 / 2); // Tiled width based on output
+    assert_eq!(win1.size.height, 900);    // Full height of output
+    assert_eq!(win1.position.x, 0);
+    assert_eq!(win1.position.y, 0);
     assert_eq!(win1.state, WindowState::Tiled);
 
-    assert_eq!(win2.width, 1600 / 2);
-    assert_eq!(win2.height, 900);
-    assert_eq!(win2.x, (1600 / 2) as i32);
-    assert_eq!(win2.y, 0);
+    assert_eq!(win2.size.width, 1600 / 2);
+    assert_eq!(win2.size.height, 900);
+    assert_eq!(win2.position.x, 1600 / 2);
+    assert_eq!(win2.position.y, 0);
     assert_eq!(win2.state, WindowState::Tiled);
 }
 
 #[test]
 fn test_focus_next_window_no_windows() {
     let mut state = CompositorState::new(); // Assumes "seat0" from new()
-    assert!(!state.focus_next_window("seat0"), "Should return false if no windows");
+    assert!(state.focus_next_window("seat0"), "Should clear focus and return true if no windows");
     // Re-fetch seat after potential modification
     let seat = state.seats.iter().find(|s| s.name == "seat0").unwrap();
     assert!(seat.focused_window.is_none());
@@ -279,7 +288,7 @@ fn test_focus_next_window_no_windows() {
 fn test_focus_next_window_one_window() {
     let mut state = CompositorState::new();
     let window_id = state.next_window_id();
-    state.add_window(Window::new(window_id, "Win1".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(window_id, "Win1".to_string(), 100, 100, 0, 0));
 
     assert!(state.focus_next_window("seat0"), "Focus should be set");
     let seat_after_first_focus = state.seats.iter().find(|s| s.name == "seat0").unwrap();
@@ -297,11 +306,11 @@ fn test_focus_next_window_one_window() {
 fn test_focus_next_window_multiple_windows_cycling_and_wrapping() {
     let mut state = CompositorState::new();
     let win1_id = state.next_window_id();
-    state.add_window(Window::new(win1_id, "Win1".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(win1_id, "Win1".to_string(), 100, 100, 0, 0));
     let win2_id = state.next_window_id();
-    state.add_window(Window::new(win2_id, "Win2".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(win2_id, "Win2".to_string(), 100, 100, 0, 0));
     let win3_id = state.next_window_id();
-    state.add_window(Window::new(win3_id, "Win3".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(win3_id, "Win3".to_string(), 100, 100, 0, 0));
 
     // Initial focus (should go to win1_id)
     assert!(state.focus_next_window("seat0"));
@@ -340,7 +349,7 @@ fn test_focus_next_window_multiple_windows_cycling_and_wrapping() {
 fn test_focus_next_window_invalid_seat() {
     let mut state = CompositorState::new();
     let window_id = state.next_window_id();
-    state.add_window(Window::new(window_id, "Win1".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(window_id, "Win1".to_string(), 100, 100, 0, 0));
     assert!(!state.focus_next_window("non_existent_seat"), "Should return false for invalid seat");
 }
 
@@ -348,7 +357,7 @@ fn test_focus_next_window_invalid_seat() {
 fn test_focus_next_window_stale_focus_id() {
     let mut state = CompositorState::new();
     let win1_id = state.next_window_id();
-    state.add_window(Window::new(win1_id, "Win1".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(win1_id, "Win1".to_string(), 100, 100, 0, 0));
     let win2_id = state.next_window_id(); // This window won't be added, making its ID stale if focused
 
     // Manually set a stale focused_window ID on the seat
@@ -368,36 +377,36 @@ fn test_focus_next_window_stale_focus_id() {
 fn test_resize_window_success() {
     let mut state = CompositorState::new();
     let window_id = state.next_window_id();
-    state.add_window(Window::new(window_id, "Resize Me".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(window_id, "Resize Me".to_string(), 100, 100, 0, 0));
 
     assert!(state.resize_window(window_id, 200, 150), "Resize should succeed");
     let window = state.find_window(window_id).unwrap();
-    assert_eq!(window.width, 200);
-    assert_eq!(window.height, 150);
+    assert_eq!(window.size.width, 200);
+    assert_eq!(window.size.height, 150);
 }
 
 #[test]
 fn test_resize_window_zero_width() {
     let mut state = CompositorState::new();
     let window_id = state.next_window_id();
-    state.add_window(Window::new(window_id, "No Zero Width".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(window_id, "No Zero Width".to_string(), 100, 100, 0, 0));
 
     assert!(!state.resize_window(window_id, 0, 150), "Resize with zero width should fail");
     let window = state.find_window(window_id).unwrap();
-    assert_eq!(window.width, 100, "Width should not change on failed resize");
-    assert_eq!(window.height, 100, "Height should not change on failed resize");
+    assert_eq!(window.size.width, 100, "Width should not change on failed resize");
+    assert_eq!(window.size.height, 100, "Height should not change on failed resize");
 }
 
 #[test]
 fn test_resize_window_zero_height() {
     let mut state = CompositorState::new();
     let window_id = state.next_window_id();
-    state.add_window(Window::new(window_id, "No Zero Height".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(window_id, "No Zero Height".to_string(), 100, 100, 0, 0));
 
     assert!(!state.resize_window(window_id, 200, 0), "Resize with zero height should fail");
     let window = state.find_window(window_id).unwrap();
-    assert_eq!(window.width, 100, "Width should not change on failed resize");
-    assert_eq!(window.height, 100, "Height should not change on failed resize");
+    assert_eq!(window.size.width, 100, "Width should not change on failed resize");
+    assert_eq!(window.size.height, 100, "Height should not change on failed resize");
 }
 
 #[test]
@@ -412,12 +421,12 @@ fn test_resize_window_non_existent() {
 fn test_move_window_success() {
     let mut state = CompositorState::new();
     let window_id = state.next_window_id();
-    state.add_window(Window::new(window_id, "Move Me".to_string(), 100, 100, 0, 0));
+    state.add_window(new_mapped_window(window_id, "Move Me".to_string(), 100, 100, 0, 0));
 
     assert!(state.move_window(window_id, 50, 75), "Move should succeed");
     let window = state.find_window(window_id).unwrap();
-    assert_eq!(window.x, 50);
-    assert_eq!(window.y, 75);
+    assert_eq!(window.position.x, 50);
+    assert_eq!(window.position.y, 75);
 }
 
 #[test]
@@ -438,20 +447,20 @@ fn test_compositor_state_new_initializes_outputs() {
     let primary_output = state.outputs.iter().find(|o| o.is_primary);
     assert!(primary_output.is_some(), "A primary output should exist");
     if let Some(po) = primary_output {
-        assert_eq!(po.x, 0);
-        assert_eq!(po.y, 0);
-        assert_eq!(po.width, 1920);
-        assert_eq!(po.height, 1080);
+        assert_eq!(po.position.x, 0);
+        assert_eq!(po.position.y, 0);
+        assert_eq!(po.size.width, 1920);
+        assert_eq!(po.size.height, 1080);
         assert_eq!(po.name, "Primary-1920x1080");
     }
 
     let secondary_output = state.outputs.iter().find(|o| !o.is_primary);
     assert!(secondary_output.is_some(), "A secondary output should exist");
     if let Some(so) = secondary_output {
-        assert_eq!(so.x, 1920);
-        assert_eq!(so.y, 0);
-        assert_eq!(so.width, 1280);
-        assert_eq!(so.height, 720);
+        assert_eq!(so.position.x, 1920);
+        assert_eq!(so.position.y, 0);
+        assert_eq!(so.size.width, 1280);
+        assert_eq!(so.size.height, 720);
         assert_eq!(so.name, "Secondary-1280x720");
     }
 }
@@ -460,27 +469,27 @@ fn test_compositor_state_new_initializes_outputs() {
 fn test_tile_windows_on_primary_output() {
     let mut state = CompositorState::new(); // new() creates a primary and a secondary output
     let win1_id = state.next_window_id();
-    state.add_window(Window::new(win1_id, "W1".to_string(), 10,10,0,0));
+    state.add_window(new_mapped_window(win1_id, "W1".to_string(), 10,10,0,0));
     let win2_id = state.next_window_id();
-    state.add_window(Window::new(win2_id, "W2".to_string(), 10,10,0,0));
+    state.add_window(new_mapped_window(win2_id, "W2".to_string(), 10,10,0,0));
 
     state.tile_windows(); // Should tile on the primary output (1920x1080 at 0,0)
 
     let primary_output = state.outputs.iter().find(|o| o.is_primary).unwrap();
-    let expected_width = primary_output.width / 2;
+    let expected_width = primary_output.size.width / 2;
 
     let w1 = state.find_window(win1_id).unwrap();
-    assert_eq!(w1.x, primary_output.x);
-    assert_eq!(w1.y, primary_output.y);
-    assert_eq!(w1.width, expected_width);
-    assert_eq!(w1.height, primary_output.height);
+    assert_eq!(w1.position.x, primary_output.position.x);
+    assert_eq!(w1.position.y, primary_output.position.y);
+    assert_eq!(w1.size.width, expected_width);
+    assert_eq!(w1.size.height, primary_output.size.height);
     assert_eq!(w1.state, WindowState::Tiled);
 
     let w2 = state.find_window(win2_id).unwrap();
-    assert_eq!(w2.x, primary_output.x + expected_width as i32);
-    assert_eq!(w2.y, primary_output.y);
-    assert_eq!(w2.width, expected_width);
-    assert_eq!(w2.height, primary_output.height);
+    assert_eq!(w2.position.x, primary_output.position.x + expected_width as i32);
+    assert_eq!(w2.position.y, primary_output.position.y);
+    assert_eq!(w2.size.width, expected_width);
+    assert_eq!(w2.size.height, primary_output.size.height);
     assert_eq!(w2.state, WindowState::Tiled);
 }
 
@@ -497,7 +506,7 @@ fn test_tile_windows_on_first_output_if_no_primary() {
 
 
     let win1_id = state.next_window_id();
-    state.add_window(Window::new(win1_id, "W1".to_string(), 10,10,0,0));
+    state.add_window(new_mapped_window(win1_id, "W1".to_string(), 10,10,0,0));
 
     state.tile_windows(); // Should tile on "OutputA" as it's the first one
 
@@ -505,10 +514,10 @@ fn test_tile_windows_on_first_output_if_no_primary() {
     assert_eq!(first_output.name, "OutputA-1000x600");
 
     let w1 = state.find_window(win1_id).unwrap();
-    assert_eq!(w1.x, first_output.x);
-    assert_eq!(w1.y, first_output.y);
-    assert_eq!(w1.width, first_output.width); // Only one window, takes full width
-    assert_eq!(w1.height, first_output.height);
+    assert_eq!(w1.position.x, first_output.position.x);
+    assert_eq!(w1.position.y, first_output.position.y);
+    assert_eq!(w1.size.width, first_output.size.width); // Only one window, takes full width
+    assert_eq!(w1.size.height, first_output.size.height);
     assert_eq!(w1.state, WindowState::Tiled);
 }
 
@@ -518,15 +527,15 @@ fn test_tile_windows_with_no_outputs_uses_default_screen() {
     state.outputs.clear(); // Ensure no outputs
 
     let win1_id = state.next_window_id();
-    state.add_window(Window::new(win1_id, "W1".to_string(), 10,10,0,0));
+    state.add_window(new_mapped_window(win1_id, "W1".to_string(), 10,10,0,0));
 
     state.tile_windows(); // Should use default 1920x1080 at (0,0)
 
     let w1 = state.find_window(win1_id).unwrap();
-    assert_eq!(w1.x, 0); // Default screen x
-    assert_eq!(w1.y, 0); // Default screen y
-    assert_eq!(w1.width, 1920); // Default screen width
-    assert_eq!(w1.height, 1080); // Default screen height
+    assert_eq!(w1.position.x, 0); // Default screen x
+    assert_eq!(w1.position.y, 0); // Default screen y
+    assert_eq!(w1.size.width, 1920); // Default screen width
+    assert_eq!(w1.size.height, 1080); // Default screen height
     assert_eq!(w1.state, WindowState::Tiled);
 }
 
@@ -539,22 +548,135 @@ fn test_tile_windows_relative_to_output_origin() {
     state.add_output(Output::new(out_id, "OffsetOutput-1024x768".to_string(), 1024, 768, 500, 300, true));
 
     let win1_id = state.next_window_id();
-    state.add_window(Window::new(win1_id, "W1".to_string(), 10,10,0,0));
+    state.add_window(new_mapped_window(win1_id, "W1".to_string(), 10,10,0,0));
     let win2_id = state.next_window_id();
-    state.add_window(Window::new(win2_id, "W2".to_string(), 10,10,0,0));
+    state.add_window(new_mapped_window(win2_id, "W2".to_string(), 10,10,0,0));
 
     state.tile_windows();
 
     let target_output = state.outputs.iter().find(|o| o.is_primary).unwrap();
-    let expected_width = target_output.width / 2;
+    let expected_width = target_output.size.width / 2;
 
     let w1 = state.find_window(win1_id).unwrap();
-    assert_eq!(w1.x, target_output.x); // X relative to output's X
-    assert_eq!(w1.y, target_output.y); // Y relative to output's Y
-    assert_eq!(w1.width, expected_width);
-    assert_eq!(w1.height, target_output.height);
+    assert_eq!(w1.position.x, target_output.position.x); // X relative to output's X
+    assert_eq!(w1.position.y, target_output.position.y); // Y relative to output's Y
+    assert_eq!(w1.size.width, expected_width);
+    assert_eq!(w1.size.height, target_output.size.height);
 
     let w2 = state.find_window(win2_id).unwrap();
-    assert_eq!(w2.x, target_output.x + expected_width as i32); // X relative to output's X
-    assert_eq!(w2.y, target_output.y);
+    assert_eq!(w2.position.x, target_output.position.x + expected_width as i32); // X relative to output's X
+    assert_eq!(w2.position.y, target_output.position.y);
+}
+
+#[test]
+fn test_tiled_windows_lie_within_output_geometry() {
+    let mut state = CompositorState::new();
+    state.outputs.clear();
+    let out_id = state.next_output_id();
+    let output = Output::new(out_id, "HiDPI-3840x2160".to_string(), 3840, 2160, 1920, 0, true).with_scale(2);
+    assert_eq!(output.geometry(), novade_core::types::Rect::new(1920, 0, 1920, 1080));
+    state.add_output(output);
+
+    let win_id = state.next_window_id();
+    state.add_window(new_mapped_window(win_id, "W1".to_string(), 10, 10, 0, 0));
+    state.tile_windows();
+
+    let window = state.find_window(win_id).unwrap().geometry();
+    assert!(window.intersects(&state.outputs[0].geometry()));
+    assert_eq!(window.origin, novade_core::types::Point::new(1920, 0));
+}
+
+#[test]
+fn test_lock_session_hides_windows_and_routes_input_to_lock_surface() {
+    let mut state = CompositorState::new();
+    state.add_window(new_mapped_window(10, "App".to_string(), 100, 100, 0, 0));
+    state.set_focused_window_for_seat("seat0", Some(10));
+
+    assert!(state.lock_session(Some(7)));
+    assert!(state.is_locked());
+    assert_eq!(state.seats[0].focused_window, None, "Locking clears focus");
+    assert!(state.stacking_order().is_empty(), "Nothing is drawn until a lock surface exists");
+
+    let lock_window_id = state.add_lock_surface(7, 1).expect("Owner can add a lock surface");
+    let lock_window = state.find_window(lock_window_id).unwrap();
+    assert_eq!((lock_window.position.x, lock_window.position.y, lock_window.size.width, lock_window.size.height), (0, 0, 1920, 1080));
+    assert_eq!(state.stacking_order(), vec![lock_window_id]);
+    assert_eq!(state.seats[0].focused_window, Some(lock_window_id));
+
+    // Other windows can neither be focused nor receive input.
+    assert!(!state.set_focused_window_for_seat("seat0", Some(10)));
+    state.focus_next_window("seat0");
+    assert_eq!(state.seats[0].focused_window, Some(lock_window_id));
+
+    let event = InputEvent::Keyboard { key_code: 65, state: KeyState::Pressed, modifiers: InputModifiers::default() };
+    assert!(state.dispatch_input_event(&event, "seat0"));
+    assert_eq!(state.find_window(lock_window_id).unwrap().event_queue.len(), 1);
+    assert!(state.find_window(10).unwrap().event_queue.is_empty());
+}
+
+#[test]
+fn test_unlock_session_restores_focus_and_removes_lock_surfaces() {
+    let mut state = CompositorState::new();
+    state.add_window(new_mapped_window(10, "App".to_string(), 100, 100, 0, 0));
+    state.set_focused_window_for_seat("seat0", Some(10));
+    state.lock_session(Some(7));
+    let lock_window_id = state.add_lock_surface(7, 1).unwrap();
+
+    assert!(!state.unlock_session(8), "Only the owner can unlock");
+    assert!(state.unlock_session(7));
+    assert!(!state.is_locked());
+    assert!(state.find_window(lock_window_id).is_none());
+    assert_eq!(state.seats[0].focused_window, Some(10));
+}
+
+#[test]
+fn test_unowned_lock_can_be_claimed() {
+    let mut state = CompositorState::new();
+    assert!(state.lock_session(None));
+    assert_eq!(state.add_lock_surface(7, 1), None, "Nobody owns the lock yet");
+    assert!(state.lock_session(Some(7)));
+    assert!(!state.lock_session(Some(8)), "An owned lock cannot be taken over");
+    assert!(state.add_lock_surface(7, 2).is_some());
+}
+
+#[test]
+fn test_tiling_ignores_lock_surfaces() {
+    let mut state = CompositorState::new();
+    state.add_window(new_mapped_window(10, "App".to_string(), 100, 100, 0, 0));
+    state.lock_session(Some(7));
+    let lock_window_id = state.add_lock_surface(7, 1).unwrap();
+
+    state.tile_windows();
+
+    assert_eq!(state.find_window(10).unwrap().size.width, 1920);
+    assert_eq!(state.find_window(lock_window_id).unwrap().state, WindowState::Floating);
+}
+
+#[test]
+fn test_startup_completed_by_window_with_token() {
+    let start = std::time::Instant::now();
+    let mut state = CompositorState::new();
+    state.begin_startup("novade-editor-1".to_string(), Some("org.novade.Editor".to_string()), start);
+    assert!(state.is_launching());
+
+    state.add_window(new_mapped_window(10, "Editor".to_string(), 100, 100, 0, 0));
+    assert_eq!(state.complete_startup("unknown-token", 10), None);
+    let startup = state.complete_startup("novade-editor-1", 10).expect("token is pending");
+    assert_eq!(startup.app_id.as_deref(), Some("org.novade.Editor"));
+    assert_eq!(state.find_window(10).unwrap().app_id.as_deref(), Some("org.novade.Editor"));
+    assert!(!state.is_launching());
+}
+
+#[test]
+fn test_startups_expire() {
+    let start = std::time::Instant::now();
+    let timeout = std::time::Duration::from_secs(15);
+    let mut state = CompositorState::new();
+    state.begin_startup("old".to_string(), None, start);
+    state.begin_startup("new".to_string(), None, start + std::time::Duration::from_secs(10));
+
+    let expired = state.expire_startups(start + timeout, timeout);
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].token, "old");
+    assert!(state.is_launching());
 }
@@ -2,6 +2,7 @@
 use crate::input::InputEvent;
 // KeyState is used in process_event_queue, ensure crate::input::KeyState is used if not already.
 use crate::input::KeyState;
+use novade_core::types::{Point, Rect, Size};
 
 
 /// Represents the different states a window can be in.
@@ -25,14 +26,10 @@ pub struct Window {
     pub client_id: u32,
     /// Title of the window.
     pub title: String,
-    /// Current width of the window in pixels.
-    pub width: u32,
-    /// Current height of the window in pixels.
-    pub height: u32,
-    /// X-coordinate of the window's top-left corner.
-    pub x: i32,
-    /// Y-coordinate of the window's top-left corner.
-    pub y: i32,
+    /// Position of the window's top-left corner.
+    pub position: Point<i32>,
+    /// Current size of the window in pixels.
+    pub size: Size<u32>,
     /// Current state of the window (e.g., floating, tiled).
     pub state: WindowState,
     /// Optional application identifier for the window.
@@ -75,10 +72,8 @@ impl Window {
             id,
             client_id,
             title,
-            width: if width == 0 { 100 } else { width },
-            height: if height == 0 { 100 } else { height },
-            x,
-            y,
+            position: Point::new(x, y),
+            size: Size::new(if width == 0 { 100 } else { width }, if height == 0 { 100 } else { height }),
             state: WindowState::Floating,
             app_id: None,
             focused: false,
@@ -87,6 +82,11 @@ impl Window {
         }
     }
 
+    /// The area the window covers in the global compositor space.
+    pub fn geometry(&self) -> Rect<i32> {
+        Rect::from_parts(self.position, Size::new(self.size.width as i32, self.size.height as i32))
+    }
+
     /// Queues an input event to be processed by this window.
     pub fn queue_event(&mut self, event: InputEvent) {
         self.event_queue.push(event);
@@ -100,15 +100,12 @@ impl Window {
         }
         for event in events_to_process {
             println!("Window [ID: {}, ClientID: {}]: Received event: {:?}", self.id, self.client_id, event);
-            match event {
-                InputEvent::Keyboard { key_code, state: KeyState::Pressed, modifiers } => { // Ensure KeyState is correctly pathed
-                    if key_code == 88 && modifiers.ctrl {
-                        println!("Window [ID: {}, ClientID: {}]: Action: Would close (Ctrl+X received).", self.id, self.client_id);
-                    } else if key_code == 70 {
-                         println!("Window [ID: {}, ClientID: {}]: Action: Would toggle fullscreen (F key received).", self.id, self.client_id);
-                    }
+            if let InputEvent::Keyboard { key_code, state: KeyState::Pressed, modifiers } = event {
+                if key_code == 88 && modifiers.ctrl {
+                    println!("Window [ID: {}, ClientID: {}]: Action: Would close (Ctrl+X received).", self.id, self.client_id);
+                } else if key_code == 70 {
+                     println!("Window [ID: {}, ClientID: {}]: Action: Would toggle fullscreen (F key received).", self.id, self.client_id);
                 }
-                _ => {}
             }
         }
     }
@@ -1,11 +1,15 @@
 // src/compositor/mod.rs
+pub mod backend;
 pub mod core; // Ensure the core module is declared
+pub mod render;
 
 // #[derive(Debug, thiserror::Error)] // Commented out due to thiserror being disabled
 #[derive(Debug)] // Basic Debug
 pub enum CompositorError {
     // #[error("Initialization failed: {0}")] // Commented out
     InitializationFailed(String),
+    /// A backend operation (modesetting, page flip, device I/O, ...) failed at runtime.
+    BackendError(String),
     // Add other error variants as needed later
 }
 // Manual Display if needed, or skip for now.
@@ -0,0 +1,96 @@
+// src/compositor/render.rs
+
+//! Software rendering of the compositor state into backend buffers.
+//!
+//! Clients cannot attach buffers yet, so every mapped window is drawn as a flat rectangle
+//! (the focused one highlighted) on top of a solid background. While the session is
+//! locked only the lock surfaces are drawn.
+
+use crate::compositor::core::{CompositorState, Output};
+
+/// Background of an unlocked desktop (XRGB8888).
+const BACKGROUND: u32 = 0x0020_2430;
+/// Background while the session is locked.
+const LOCKED_BACKGROUND: u32 = 0x0000_0000;
+/// Fill of an unfocused window.
+const WINDOW: u32 = 0x0050_5a6e;
+/// Fill of the focused window.
+const FOCUSED_WINDOW: u32 = 0x0070_8cc8;
+
+/// Draws one frame of `output` into an XRGB8888 buffer.
+///
+/// `width` and `height` are the buffer size in pixels and `pitch` the length of a row in
+/// bytes, as handed out by [`Backend::render_frame`](crate::compositor::backend::Backend::render_frame).
+/// Windows are drawn in stacking order, so later windows cover earlier ones.
+pub fn draw_output(state: &CompositorState, output: &Output, pixels: &mut [u8], width: u32, height: u32, pitch: u32) {
+    let lock = state.session_lock.as_ref();
+    let background = if lock.is_some() { LOCKED_BACKGROUND } else { BACKGROUND };
+    fill(pixels, pitch, (0, 0), (width, height), background);
+
+    let scale = output.scale.max(1) as i32;
+    // Converts a global logical coordinate into a buffer coordinate, clipped to the buffer.
+    let to_buffer = |global: i32, origin: i32, limit: u32| ((global - origin) * scale).clamp(0, limit as i32) as u32;
+    for window in state.windows.iter().filter(|w| w.is_mapped) {
+        if lock.is_some_and(|lock| !lock.is_lock_surface(window.id)) {
+            continue;
+        }
+        let geometry = window.geometry();
+        let from = (
+            to_buffer(geometry.origin.x, output.position.x, width),
+            to_buffer(geometry.origin.y, output.position.y, height),
+        );
+        let to = (
+            to_buffer(geometry.origin.x + geometry.size.width, output.position.x, width),
+            to_buffer(geometry.origin.y + geometry.size.height, output.position.y, height),
+        );
+        fill(pixels, pitch, from, to, if window.focused { FOCUSED_WINDOW } else { WINDOW });
+    }
+}
+
+/// Fills the pixels from `from` (inclusive) to `to` (exclusive) with `color`.
+fn fill(pixels: &mut [u8], pitch: u32, from: (u32, u32), to: (u32, u32), color: u32) {
+    let color = color.to_le_bytes();
+    for y in from.1..to.1 {
+        let row = y as usize * pitch as usize;
+        for x in from.0..to.0 {
+            let offset = row + x as usize * 4;
+            if let Some(pixel) = pixels.get_mut(offset..offset + 4) {
+                pixel.copy_from_slice(&color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositor::core::Window;
+
+    fn pixel(pixels: &[u8], pitch: u32, x: u32, y: u32) -> u32 {
+        let offset = (y * pitch + x * 4) as usize;
+        u32::from_le_bytes(pixels[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_draw_output_places_windows_in_output_coordinates() {
+        let mut state = CompositorState::new();
+        let output = Output::new(2, "HDMI-A-1".to_string(), 200, 100, 1000, 0, false).with_scale(2);
+        let mut window = Window::new(1, 1, "Editor".to_string(), 20, 10, 1010, 5);
+        window.map();
+        state.add_window(window);
+        // Partly off the output; drawing must clip instead of panicking.
+        let mut offscreen = Window::new(2, 1, "Offscreen".to_string(), 50, 50, 1090, 40);
+        offscreen.map();
+        state.add_window(offscreen);
+
+        let pitch = 200 * 4;
+        let mut pixels = vec![0; (pitch * 100) as usize];
+        draw_output(&state, &output, &mut pixels, 200, 100, pitch);
+
+        assert_eq!(pixel(&pixels, pitch, 0, 0), BACKGROUND);
+        assert_eq!(pixel(&pixels, pitch, 20, 10), WINDOW);
+        assert_eq!(pixel(&pixels, pitch, 59, 29), WINDOW);
+        assert_eq!(pixel(&pixels, pitch, 60, 30), BACKGROUND);
+        assert_eq!(pixel(&pixels, pitch, 199, 99), WINDOW);
+    }
+}
@@ -1,7 +1,7 @@
 // src/input/manager.rs
 
 use crate::input::state::InputState;
-use crate::input::event::{InputEvent, KeyState, Modifiers}; // Added KeyState and Modifiers
+use crate::input::event::{InputEvent, KeyState};
 
 // Define assumed key codes for C and V.
 // These are based on ASCII, but in a real system might come from xkbcommon or similar.
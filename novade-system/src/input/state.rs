@@ -62,6 +62,7 @@ impl InputState {
             InputEvent::TouchUp { modifiers, .. } => {
                 self.modifiers = *modifiers;
             }
+            InputEvent::CopyShortcut | InputEvent::PasteShortcut => {}
         }
 
         match event {
@@ -106,6 +107,7 @@ impl InputState {
             InputEvent::TouchUp { touch_id, .. } => {
                 self.active_touches.remove(touch_id);
             }
+            InputEvent::CopyShortcut | InputEvent::PasteShortcut => {}
         }
     }
 }
@@ -114,7 +116,6 @@ impl InputState {
 mod tests {
     use super::*; // Imports InputState
     use crate::input::event::{InputEvent, KeyState, ButtonState, Modifiers}; // Imports event types
-    use std::collections::HashSet;
 
     fn default_modifiers() -> Modifiers {
         Modifiers { shift: false, ctrl: false, alt: false, logo: false }
@@ -4,13 +4,26 @@ pub mod client; // Added client module
 pub mod clipboard;
 pub mod compositor;
 pub mod input;
+pub mod metrics;
+pub mod process_manager;
+pub mod repositories;
 pub mod server;
+pub mod session_management;
 
 // Re-export key types
-pub use client::{Client, ClientRequest, ServerEvent}; // Added re-exports
+pub use client::{BackendRequest, Client, ClientRequest, ServerEvent}; // Added re-exports
 pub use clipboard::Clipboard;
+pub use metrics::PrometheusEndpoint;
+pub use process_manager::{DefaultProcessManager, ProcessExitEvent, ProcessManager, ProcessManagerBackend};
 pub use server::Server;
 
+/// Prints a test message demonstrating that the system layer is linked and reachable.
+///
+/// Used by the UI layer during early development as a simple smoke test.
+pub fn print_system_message() {
+    println!("Nachricht von novade-system: Systemschicht bereit.");
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -4,14 +4,84 @@ use novade_system::server::Server;
 use novade_system::input::{InputEvent, KeyState, Modifiers};
 use novade_system::compositor::core::Window; // For creating sample windows
 
+/// Runs the compositor nested inside a window on the current desktop (`--winit`).
+#[cfg(feature = "backend-winit")]
+fn run_nested() {
+    use novade_system::compositor::backend::WinitBackend;
+
+    println!("NovaDE starting nested in a winit window...");
+    let mut backend = match WinitBackend::new("NovaDE (nested)", 1280, 800) {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("Failed to start winit backend: {:?}", e);
+            std::process::exit(1);
+        }
+    };
+    let mut server = Server::new();
+    if let Err(e) = server.run_with_backend(&mut backend) {
+        eprintln!("Nested session ended with error: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Runs the compositor directly on the display hardware (`--drm`).
+///
+/// The DRM device is opened through the logind session, so no root privileges are needed
+/// and the device is paused and resumed on VT switches.
+#[cfg(all(feature = "backend-drm", feature = "session-logind"))]
+fn run_drm() {
+    use novade_system::compositor::backend::drm::DEFAULT_DRM_DEVICE;
+    use novade_system::compositor::backend::DrmBackend;
+    use novade_system::session_management::{LogindSession, Session};
+    use std::path::Path;
+
+    println!("NovaDE starting on {}...", DEFAULT_DRM_DEVICE);
+    let mut session = match LogindSession::new() {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("Failed to take control of the logind session: {:?}", e);
+            std::process::exit(1);
+        }
+    };
+    let path = Path::new(DEFAULT_DRM_DEVICE);
+    let backend = session
+        .open_device(path)
+        .map_err(|e| format!("{:?}", e))
+        .and_then(|file| DrmBackend::from_file(file, path.to_path_buf()).map_err(|e| format!("{:?}", e)));
+    let mut backend = match backend {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("Failed to start DRM backend: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let mut server = Server::new();
+    if let Err(e) = backend.modeset().and_then(|()| server.run_with_session(&mut session, &mut backend)) {
+        eprintln!("DRM session ended with error: {:?}", e);
+        std::process::exit(1);
+    }
+}
+
 /// Main function to demonstrate Server orchestration, window management, and multi-output concepts.
 fn main() {
+    #[cfg(feature = "backend-winit")]
+    if std::env::args().any(|arg| arg == "--winit") {
+        run_nested();
+        return;
+    }
+    #[cfg(all(feature = "backend-drm", feature = "session-logind"))]
+    if std::env::args().any(|arg| arg == "--drm") {
+        run_drm();
+        return;
+    }
+
     println!("NovaDE Advanced Demo Starting...");
 
     // 1. Initialize Server
     // CompositorState::new() now initializes with a primary and secondary output.
     let mut server = Server::new();
     println!("Server initialized.");
+    let demo_client_id = server.add_client();
 
     println!("
 --- Initial Output Configuration ---");
@@ -20,7 +90,7 @@ fn main() {
     }
     for output in &server.compositor_state.outputs {
         println!("  Output ID: {}, Name: '{}', Geom: [{}x{} at ({},{})], Primary: {}",
-                 output.id, output.name, output.width, output.height, output.x, output.y, output.is_primary);
+                 output.id, output.name, output.size.width, output.size.height, output.position.x, output.position.y, output.is_primary);
     }
 
     // 2. Create sample windows
@@ -29,6 +99,7 @@ fn main() {
     let window_id_1 = server.compositor_state.next_window_id();
     let sample_window_1 = Window::new(
         window_id_1,
+        demo_client_id,
         "Window Alpha".to_string(),
         300, // Initial size, will be overridden
         200,
@@ -41,6 +112,7 @@ fn main() {
     let window_id_2 = server.compositor_state.next_window_id();
     let sample_window_2 = Window::new(
         window_id_2,
+        demo_client_id,
         "Window Beta".to_string(),
         250,
         150,
@@ -53,6 +125,7 @@ fn main() {
     let window_id_3 = server.compositor_state.next_window_id();
     let sample_window_3 = Window::new(
         window_id_3,
+        demo_client_id,
         "Window Gamma".to_string(),
         200,
         100,
@@ -72,13 +145,13 @@ fn main() {
 --- Window Resize & Move Demonstration (Window ID: {}) ---", window_id_1);
     if let Some(win_before) = server.compositor_state.find_window(window_id_1) {
         println!("  Before resize/move: pos=({},{}), size=({}x{})",
-                 win_before.x, win_before.y, win_before.width, win_before.height);
+                 win_before.position.x, win_before.position.y, win_before.size.width, win_before.size.height);
     }
     server.compositor_state.resize_window(window_id_1, 400, 350);
     server.compositor_state.move_window(window_id_1, 20, 30);
     if let Some(win_after) = server.compositor_state.find_window(window_id_1) {
         println!("  After resize/move:  pos=({},{}), size=({}x{})",
-                 win_after.x, win_after.y, win_after.width, win_after.height);
+                 win_after.position.x, win_after.position.y, win_after.size.width, win_after.size.height);
     }
 
 
@@ -101,13 +174,13 @@ Simulating {} input events...", events_to_simulate.len());
     println!("Windows before tiling:");
     for window in server.compositor_state.windows.iter() {
         println!("  Window {}: pos=({},{}), size=({}x{}), state={:?}",
-                 window.id, window.x, window.y, window.width, window.height, window.state);
+                 window.id, window.position.x, window.position.y, window.size.width, window.size.height, window.state);
     }
     server.compositor_state.tile_windows(); // This will use the new logic
     println!("Windows after tiling (check positions relative to the chosen output):");
     for window in server.compositor_state.windows.iter() {
         println!("  Window {}: pos=({},{}), size=({}x{}), state={:?}",
-                 window.id, window.x, window.y, window.width, window.height, window.state);
+                 window.id, window.position.x, window.position.y, window.size.width, window.size.height, window.state);
     }
 
     // 7. Illustrate Focus Cycling
@@ -0,0 +1,138 @@
+// src/metrics.rs
+
+//! HTTP endpoint serving the metrics of [`novade_core::metrics`] in the Prometheus text format.
+//!
+//! The endpoint answers `GET /metrics` with a snapshot of a [`MetricsRegistry`] rendered by
+//! [`render_prometheus`]; every other path is answered with `404 Not Found`. [`DEFAULT_METRICS_ADDR`] is on the
+//! loopback interface, since the metrics may reveal what the user is doing.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use novade_core::metrics::exporter::render_prometheus;
+use novade_core::metrics::MetricsRegistry;
+
+/// The address the endpoint is usually bound to: the port commonly used by Prometheus
+/// exporters, on loopback only.
+pub const DEFAULT_METRICS_ADDR: &str = "127.0.0.1:9464";
+
+/// The path the metrics are served on.
+pub const METRICS_PATH: &str = "/metrics";
+
+/// How long a client may take to send its request before the connection is dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A bound Prometheus endpoint that is not yet serving.
+#[derive(Debug)]
+pub struct PrometheusEndpoint {
+    listener: TcpListener,
+    registry: &'static MetricsRegistry,
+}
+
+impl PrometheusEndpoint {
+    /// Binds the endpoint to `addr`, serving the metrics of `registry`
+    /// (usually [`novade_core::metrics::registry()`]).
+    pub fn bind(addr: impl ToSocketAddrs, registry: &'static MetricsRegistry) -> io::Result<Self> {
+        Ok(Self { listener: TcpListener::bind(addr)?, registry })
+    }
+
+    /// The address the endpoint is bound to, e.g. to learn the port after binding to port 0.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves requests on a background thread for the rest of the process.
+    ///
+    /// Requests are handled one at a time; a scrape is cheap and Prometheus does not send
+    /// them concurrently.
+    pub fn spawn(self) -> JoinHandle<()> {
+        thread::spawn(move || {
+            for stream in self.listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                if let Err(e) = handle_request(stream, self.registry) {
+                    novade_core::logging::debug!("Metrics endpoint: failed to answer a request: {}", e);
+                }
+            }
+        })
+    }
+}
+
+/// Reads one request from `stream` and writes the response.
+fn handle_request(stream: TcpStream, registry: &MetricsRegistry) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers; the endpoint does not use any of them.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim_end().is_empty() {
+        header.clear();
+    }
+
+    let (status, body) = match parse_request_line(&request_line) {
+        Some(("GET", path)) if path == METRICS_PATH => ("200 OK", render_prometheus(&registry.snapshot())),
+        Some(("GET", _)) => ("404 Not Found", "Not Found\n".to_string()),
+        Some(_) => ("405 Method Not Allowed", "Method Not Allowed\n".to_string()),
+        None => ("400 Bad Request", "Bad Request\n".to_string()),
+    };
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Splits an HTTP request line into method and path, dropping any query string.
+fn parse_request_line(line: &str) -> Option<(&str, &str)> {
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    parts.next().filter(|version| version.starts_with("HTTP/"))?;
+    let path = target.split_once('?').map_or(target, |(path, _)| path);
+    Some((method, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_endpoint_serves_metrics() {
+        let registry = novade_core::metrics::registry();
+        registry.counter("system_test.scrapes").increment(7);
+        let endpoint = PrometheusEndpoint::bind("127.0.0.1:0", registry).unwrap();
+        let addr = endpoint.local_addr().unwrap();
+        endpoint.spawn();
+
+        let response = get(addr, "/metrics?format=text");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+        assert!(response.contains("# TYPE system_test_scrapes counter\nsystem_test_scrapes 7\n"));
+
+        assert!(get(addr, "/").starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[test]
+    fn test_parse_request_line() {
+        assert_eq!(parse_request_line("GET /metrics HTTP/1.1\r\n"), Some(("GET", "/metrics")));
+        assert_eq!(parse_request_line("POST /metrics?x=1 HTTP/1.0\r\n"), Some(("POST", "/metrics")));
+        assert_eq!(parse_request_line("GET /metrics\r\n"), None);
+    }
+}
@@ -0,0 +1,480 @@
+// src/process_manager/autostart.rs
+
+//! XDG autostart.
+//!
+//! Implements the Desktop Application Autostart Specification: desktop entries in the
+//! `autostart` directories below `$XDG_CONFIG_HOME` and `$XDG_CONFIG_DIRS` are started when
+//! the session starts. An entry in a more important directory overrides entries with the
+//! same file name in less important ones, so users can disable a system-wide entry by
+//! placing a copy with `Hidden=true` in `~/.config/autostart`.
+//!
+//! [`scan_autostart`] collects the entries to start; the [`AutostartScheduler`] feeds them
+//! into a [`LaunchQueue`] with [`LaunchPriority::Autostart`] once the compositor is ready,
+//! honouring `X-GNOME-Autostart-Delay`.
+//!
+//! Whether an entry is enabled, its delay and its `AutostartCondition` are read with
+//! [`autostart_settings`], the same conversion the
+//! [`XdgAutostartRepository`](crate::repositories::XdgAutostartRepository) uses, so the
+//! scheduler starts exactly the entries the settings show as enabled.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use novade_domain::entities::{Application, AutostartCondition, AutostartEntry as AutostartSettings};
+
+use super::exec::split_exec_line;
+use super::queue::{LaunchPriority, LaunchQueue, LaunchTicket};
+use super::sandbox::apply_detected_sandbox;
+use super::{LaunchOptions, ProcessManager};
+
+/// Name of this desktop as used in `OnlyShowIn`/`NotShowIn` and `XDG_CURRENT_DESKTOP`.
+pub const DESKTOP_NAME: &str = "NovaDE";
+
+/// An application to start at session startup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutostartEntry {
+    /// File name of the desktop entry (e.g. "nm-applet.desktop").
+    pub file_name: String,
+    /// The application described by the entry.
+    pub app: Application,
+    /// Delay after the compositor is ready before the application is started.
+    pub delay: Duration,
+}
+
+/// The autostart directories in order of decreasing importance:
+/// `$XDG_CONFIG_HOME/autostart` (default `~/.config/autostart`), then
+/// `$XDG_CONFIG_DIRS/autostart` (default `/etc/xdg/autostart`).
+pub fn autostart_dirs() -> Vec<PathBuf> {
+    let non_empty = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    let mut dirs = Vec::new();
+    let user_dir = match non_empty("XDG_CONFIG_HOME") {
+        Some(config_home) => Some(PathBuf::from(config_home).join("autostart")),
+        None => novade_core::utils::get_app_config_dir("autostart"),
+    };
+    dirs.extend(user_dir);
+    let config_dirs = non_empty("XDG_CONFIG_DIRS").unwrap_or_else(|| "/etc/xdg".into());
+    dirs.extend(std::env::split_paths(&config_dirs).map(|dir| dir.join("autostart")));
+    dirs
+}
+
+/// `$XDG_CONFIG_HOME` (default `~/.config`), against which autostart conditions are resolved.
+fn config_home() -> Option<PathBuf> {
+    match std::env::var_os("XDG_CONFIG_HOME").filter(|value| !value.is_empty()) {
+        Some(config_home) => Some(PathBuf::from(config_home)),
+        None => std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")),
+    }
+}
+
+/// Parses the value of an `AutostartCondition` key.
+pub fn parse_condition(value: &str) -> AutostartCondition {
+    let value = value.trim();
+    match value.split_once(char::is_whitespace) {
+        Some(("if-exists", path)) => AutostartCondition::IfExists(path.trim().to_string()),
+        Some(("unless-exists", path)) => AutostartCondition::UnlessExists(path.trim().to_string()),
+        _ if value.is_empty() => AutostartCondition::Always,
+        _ => AutostartCondition::Other(value.to_string()),
+    }
+}
+
+/// The `AutostartCondition` value for `condition`, or `None` if the key is not needed.
+pub fn condition_value(condition: &AutostartCondition) -> Option<String> {
+    match condition {
+        AutostartCondition::Always => None,
+        AutostartCondition::IfExists(path) => Some(format!("if-exists {}", path)),
+        AutostartCondition::UnlessExists(path) => Some(format!("unless-exists {}", path)),
+        AutostartCondition::Other(value) => Some(value.clone()),
+    }
+}
+
+/// Whether `condition` holds, resolving file names against `config_home`. Conditions NovaDE
+/// does not evaluate hold.
+pub fn condition_met(condition: &AutostartCondition, config_home: Option<&Path>) -> bool {
+    let exists = |path: &str| config_home.is_some_and(|config_home| config_home.join(path).exists());
+    match condition {
+        AutostartCondition::IfExists(path) => exists(path),
+        AutostartCondition::UnlessExists(path) => !exists(path),
+        AutostartCondition::Always | AutostartCondition::Other(_) => true,
+    }
+}
+
+/// Reads the autostart settings of a parsed desktop entry: whether it is enabled
+/// (`X-GNOME-Autostart-enabled`), its delay (`X-GNOME-Autostart-Delay`) and its
+/// `AutostartCondition`.
+pub fn autostart_settings(application_id: &str, entry: &HashMap<String, String>) -> AutostartSettings {
+    let delay = entry
+        .get("X-GNOME-Autostart-Delay")
+        .and_then(|seconds| seconds.parse::<f64>().ok())
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .map_or(Duration::ZERO, Duration::from_secs_f64);
+    AutostartSettings {
+        application_id: application_id.to_string(),
+        enabled: entry.get("X-GNOME-Autostart-enabled").is_none_or(|value| value != "false"),
+        delay,
+        condition: entry.get("AutostartCondition").map_or(AutostartCondition::Always, |value| parse_condition(value)),
+    }
+}
+
+/// Parses the `[Desktop Entry]` group of a desktop file into its (unlocalized) keys.
+pub fn parse_desktop_entry(content: &str) -> HashMap<String, String> {
+    let mut entries = parse_desktop_entry_localized(content);
+    // Localized keys such as "Name[de]" are not needed for launching.
+    entries.retain(|key, _| !key.contains('['));
+    entries
+}
+
+/// Parses the `[Desktop Entry]` group of a desktop file, keeping localized keys such as
+/// `Name[de]` (see [`localized_value`]).
+pub fn parse_desktop_entry_localized(content: &str) -> HashMap<String, String> {
+    let mut entries = HashMap::new();
+    let mut in_main_group = false;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_main_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_main_group {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            entries.insert(key.trim().to_string(), unescape_value(value.trim()));
+        }
+    }
+    entries
+}
+
+/// Looks up `key` for `locale` (e.g. "de_DE.UTF-8@euro") following the desktop entry
+/// matching rules: `lang_COUNTRY@MODIFIER`, `lang_COUNTRY`, `lang@MODIFIER`, `lang`, then the
+/// unlocalized key.
+pub fn localized_value<'a>(entry: &'a HashMap<String, String>, key: &str, locale: Option<&str>) -> Option<&'a String> {
+    let mut candidates = Vec::new();
+    if let Some(locale) = locale {
+        let (locale, modifier) = match locale.split_once('@') {
+            Some((locale, modifier)) => (locale, Some(modifier)),
+            None => (locale, None),
+        };
+        let locale = locale.split('.').next().unwrap_or(locale);
+        let (lang, country) = match locale.split_once('_') {
+            Some((lang, country)) => (lang, Some(country)),
+            None => (locale, None),
+        };
+        if let (Some(country), Some(modifier)) = (country, modifier) {
+            candidates.push(format!("{}_{}@{}", lang, country, modifier));
+        }
+        if let Some(country) = country {
+            candidates.push(format!("{}_{}", lang, country));
+        }
+        if let Some(modifier) = modifier {
+            candidates.push(format!("{}@{}", lang, modifier));
+        }
+        candidates.push(lang.to_string());
+    }
+    candidates
+        .iter()
+        .find_map(|candidate| entry.get(&format!("{}[{}]", key, candidate)))
+        .or_else(|| entry.get(key))
+}
+
+/// Resolves the escape sequences of desktop entry string values.
+fn unescape_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => result.push(' '),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some(other) => {
+                // "\\" stays escaped for the Exec quoting rules, which handle it themselves.
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+fn is_true(entry: &HashMap<String, String>, key: &str) -> bool {
+    entry.get(key).is_some_and(|value| value == "true")
+}
+
+fn desktop_list_contains(entry: &HashMap<String, String>, key: &str, desktops: &[String]) -> Option<bool> {
+    let list = entry.get(key)?;
+    Some(list.split(';').any(|item| desktops.iter().any(|d| d.eq_ignore_ascii_case(item))))
+}
+
+/// Whether `TryExec` names an existing executable (absolute or found in `PATH`).
+fn try_exec_exists(program: &str) -> bool {
+    let path = Path::new(program);
+    if path.is_absolute() {
+        return path.is_file();
+    }
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// Converts a parsed autostart desktop entry into an [`AutostartEntry`].
+///
+/// `desktops` are the names of the current desktop (from `XDG_CURRENT_DESKTOP`).
+///
+/// # Returns
+/// `None` if the entry must not be started: it is hidden, disabled, not meant for this
+/// desktop, its condition does not hold, it is not an application, its `TryExec` program is
+/// missing, or it has no valid `Exec`.
+pub fn entry_to_autostart(file_name: &str, entry: &HashMap<String, String>, desktops: &[String]) -> Option<AutostartEntry> {
+    let settings = autostart_settings(file_name.trim_end_matches(".desktop"), entry);
+    if entry.get("Type").is_some_and(|t| t != "Application")
+        || is_true(entry, "Hidden")
+        || !settings.enabled
+        || !condition_met(&settings.condition, config_home().as_deref())
+        || desktop_list_contains(entry, "OnlyShowIn", desktops) == Some(false)
+        || desktop_list_contains(entry, "NotShowIn", desktops) == Some(true)
+        || entry.get("TryExec").is_some_and(|program| !try_exec_exists(program))
+    {
+        return None;
+    }
+
+    let mut exec = split_exec_line(entry.get("Exec")?)?.into_iter();
+    let executable = exec.next()?;
+    let exec: Vec<String> = exec.collect();
+    let name = entry.get("Name").cloned().unwrap_or_else(|| file_name.trim_end_matches(".desktop").to_string());
+    let mut app = Application::new_desktop(name, executable, entry.get("Icon").cloned());
+    app.arguments = (!exec.is_empty()).then_some(exec);
+    app.working_directory = entry.get("Path").filter(|path| !path.is_empty()).cloned();
+    app.description = entry.get("Comment").cloned();
+    apply_detected_sandbox(&mut app);
+    Some(AutostartEntry { file_name: file_name.to_string(), app, delay: settings.delay })
+}
+
+/// The desktop names of the current session from `XDG_CURRENT_DESKTOP`, defaulting to
+/// [`DESKTOP_NAME`].
+pub fn current_desktops() -> Vec<String> {
+    match std::env::var("XDG_CURRENT_DESKTOP") {
+        Ok(value) if !value.is_empty() => value.split(':').map(str::to_string).collect(),
+        _ => vec![DESKTOP_NAME.to_string()],
+    }
+}
+
+/// Collects the autostart entries to start from `dirs` (most important first).
+///
+/// Unreadable directories and files are skipped. The result is sorted by file name.
+pub fn scan_autostart(dirs: &[PathBuf], desktops: &[String]) -> Vec<AutostartEntry> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for dir in dirs {
+        let Ok(read_dir) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut files: Vec<PathBuf> = read_dir
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "desktop"))
+            .collect();
+        files.sort();
+        for path in files {
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+                continue;
+            };
+            // The first (most important) entry with a file name decides, even if it is hidden.
+            if !seen.insert(file_name.clone()) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                eprintln!("Autostart: Failed to read {}", path.display());
+                continue;
+            };
+            if let Some(entry) = entry_to_autostart(&file_name, &parse_desktop_entry(&content), desktops) {
+                entries.push(entry);
+            }
+        }
+    }
+    entries.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    entries
+}
+
+/// Hands autostart entries to a [`LaunchQueue`] once their delay has passed.
+#[derive(Debug)]
+pub struct AutostartScheduler {
+    /// Entries not yet queued, with the time they become due.
+    pending: Vec<(Instant, AutostartEntry)>,
+}
+
+impl AutostartScheduler {
+    /// Creates a scheduler for `entries`, counting delays from `ready_at` (the moment the
+    /// compositor became ready).
+    pub fn new(entries: Vec<AutostartEntry>, ready_at: Instant) -> Self {
+        Self { pending: entries.into_iter().map(|entry| (ready_at + entry.delay, entry)).collect() }
+    }
+
+    /// Scans the standard autostart directories for the current desktop.
+    pub fn from_environment(ready_at: Instant) -> Self {
+        Self::new(scan_autostart(&autostart_dirs(), &current_desktops()), ready_at)
+    }
+
+    /// Whether all entries have been queued.
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Queues the entries that are due.
+    ///
+    /// # Returns
+    /// The file names and tickets of the queued entries.
+    pub fn poll<P: ProcessManager>(&mut self, queue: &mut LaunchQueue<P>, now: Instant) -> Vec<(String, LaunchTicket)> {
+        let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending).into_iter().partition(|(at, _)| *at <= now);
+        self.pending = pending;
+        due.into_iter()
+            .map(|(_, entry)| {
+                let ticket = queue.enqueue(entry.app, LaunchOptions::default(), LaunchPriority::Autostart, now);
+                (entry.file_name, ticket)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process_manager::{Pid, ProcessResult, Signal};
+    use std::fs;
+
+    fn desktops() -> Vec<String> {
+        vec![DESKTOP_NAME.to_string()]
+    }
+
+    fn entry(content: &str) -> Option<AutostartEntry> {
+        entry_to_autostart("test.desktop", &parse_desktop_entry(content), &desktops())
+    }
+
+    #[test]
+    fn test_parse_desktop_entry_main_group_only() {
+        let parsed = parse_desktop_entry(
+            "# comment\n[Desktop Entry]\nName=Applet\nName[de]=Miniprogramm\nExec=applet --tray\\s--quiet\n\
+             [Desktop Action New]\nExec=other\n",
+        );
+        assert_eq!(parsed.get("Name").map(String::as_str), Some("Applet"));
+        assert_eq!(parsed.get("Exec").map(String::as_str), Some("applet --tray --quiet"));
+        assert_eq!(parsed.len(), 2);
+    }
+
+    #[test]
+    fn test_localized_value() {
+        let parsed = parse_desktop_entry_localized(
+            "[Desktop Entry]\nName=Files\nName[de]=Dateien\nName[de_AT]=Dateien (AT)\nName[sr@latin]=Datoteke\n",
+        );
+        let name = |locale| localized_value(&parsed, "Name", locale).map(String::as_str);
+        assert_eq!(name(Some("de_AT.UTF-8")), Some("Dateien (AT)"));
+        assert_eq!(name(Some("de_CH.UTF-8")), Some("Dateien"));
+        assert_eq!(name(Some("sr_RS@latin")), Some("Datoteke"));
+        assert_eq!(name(Some("fr_FR")), Some("Files"));
+        assert_eq!(name(None), Some("Files"));
+    }
+
+    #[test]
+    fn test_entry_filters() {
+        let base = "[Desktop Entry]\nType=Application\nName=Applet\nExec=applet %U\n";
+        let app_entry = entry(base).unwrap();
+        assert_eq!(app_entry.app.executable_path, "applet");
+        assert_eq!(app_entry.app.arguments, Some(vec!["%U".to_string()]));
+        assert_eq!(app_entry.delay, Duration::ZERO);
+
+        assert!(entry(&format!("{}Hidden=true\n", base)).is_none());
+        assert!(entry(&format!("{}X-GNOME-Autostart-enabled=false\n", base)).is_none());
+        assert!(entry(&format!("{}OnlyShowIn=GNOME;KDE;\n", base)).is_none());
+        assert!(entry(&format!("{}OnlyShowIn=GNOME;NovaDE;\n", base)).is_some());
+        assert!(entry(&format!("{}NotShowIn=NovaDE;\n", base)).is_none());
+        assert!(entry(&format!("{}TryExec=/nonexistent/applet\n", base)).is_none());
+        assert!(entry("[Desktop Entry]\nType=Link\nExec=x\n").is_none());
+        assert!(entry("[Desktop Entry]\nName=No exec\n").is_none());
+
+        let delayed = entry(&format!("{}X-GNOME-Autostart-Delay=2.5\n", base)).unwrap();
+        assert_eq!(delayed.delay, Duration::from_millis(2500));
+
+        assert!(entry(&format!("{}AutostartCondition=if-exists /nonexistent/flag\n", base)).is_none());
+        assert!(entry(&format!("{}AutostartCondition=unless-exists /nonexistent/flag\n", base)).is_some());
+        assert!(entry(&format!("{}AutostartCondition=GSettings org.example.chat autostart\n", base)).is_some());
+    }
+
+    #[test]
+    fn test_conditions_round_trip() {
+        for value in ["if-exists chat/autostart", "unless-exists  chat/disabled", "GNOME3 unless-session gnome"] {
+            let condition = parse_condition(value);
+            assert_eq!(parse_condition(&condition_value(&condition).unwrap()), condition);
+        }
+        assert_eq!(parse_condition("if-exists chat/autostart"), AutostartCondition::IfExists("chat/autostart".to_string()));
+        assert_eq!(condition_value(&parse_condition("")), None);
+    }
+
+    #[test]
+    fn test_flatpak_autostart_entry_is_sandboxed() {
+        let flatpak = entry("[Desktop Entry]\nExec=/usr/bin/flatpak run org.example.Chat --minimized\n").unwrap();
+        assert!(flatpak.app.sandbox.is_some());
+        assert_eq!(flatpak.app.arguments, Some(vec!["--minimized".to_string()]));
+    }
+
+    #[test]
+    fn test_scan_user_entries_override_system_entries() {
+        let root = std::env::temp_dir().join(format!("novade-autostart-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (user, system) = (root.join("user"), root.join("system"));
+        fs::create_dir_all(&user).unwrap();
+        fs::create_dir_all(&system).unwrap();
+        fs::write(system.join("a.desktop"), "[Desktop Entry]\nExec=system-a\n").unwrap();
+        fs::write(system.join("b.desktop"), "[Desktop Entry]\nExec=system-b\n").unwrap();
+        fs::write(system.join("notes.txt"), "ignored").unwrap();
+        fs::write(user.join("a.desktop"), "[Desktop Entry]\nExec=user-a\n").unwrap();
+        fs::write(user.join("b.desktop"), "[Desktop Entry]\nExec=user-b\nHidden=true\n").unwrap();
+
+        let entries = scan_autostart(&[user, system], &desktops());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].app.executable_path, "user-a");
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[derive(Debug, Default)]
+    struct NullProcessManager;
+
+    impl ProcessManager for NullProcessManager {
+        fn launch_application_with_options(&self, _app: &Application, _options: &LaunchOptions) -> ProcessResult<Vec<Pid>> {
+            Ok(vec![1])
+        }
+
+        fn terminate_process(&self, _pid: Pid) -> ProcessResult<()> {
+            Ok(())
+        }
+
+        fn kill_process(&self, _pid: Pid) -> ProcessResult<()> {
+            Ok(())
+        }
+
+        fn send_signal(&self, _pid: Pid, _signal: Signal) -> ProcessResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_scheduler_honours_delay() {
+        let ready = Instant::now();
+        let immediate = entry("[Desktop Entry]\nExec=now\n").unwrap();
+        let mut delayed = entry("[Desktop Entry]\nExec=later\nX-GNOME-Autostart-Delay=5\n").unwrap();
+        delayed.file_name = "later.desktop".to_string();
+        let mut scheduler = AutostartScheduler::new(vec![immediate, delayed], ready);
+        let mut queue = LaunchQueue::new(NullProcessManager, 2, Duration::from_secs(5));
+
+        assert_eq!(scheduler.poll(&mut queue, ready).len(), 1);
+        assert!(!scheduler.is_finished());
+        assert!(scheduler.poll(&mut queue, ready + Duration::from_secs(4)).is_empty());
+        let queued = scheduler.poll(&mut queue, ready + Duration::from_secs(5));
+        assert_eq!(queued[0].0, "later.desktop");
+        assert!(scheduler.is_finished());
+        assert!(queue.state().queued.iter().all(|q| q.priority == LaunchPriority::Autostart));
+    }
+}
@@ -0,0 +1,237 @@
+// src/process_manager/exec.rs
+
+//! Exec line handling according to the Desktop Entry Specification.
+//!
+//! Desktop entries describe the command to run as an `Exec` line such as
+//! `gimp --new-instance %U`. [`split_exec_line`] splits such a line into arguments,
+//! honouring the spec's quoting rules, and [`expand_field_codes`] replaces the field codes
+//! (`%f`, `%F`, `%u`, `%U`, `%i`, `%c`, `%%`) for a concrete launch. Deprecated and
+//! unsupported codes are removed.
+
+use novade_domain::entities::Application;
+
+/// Splits an `Exec` value into its arguments.
+///
+/// Arguments are separated by spaces. Double-quoted arguments may contain spaces, and
+/// inside quotes a backslash escapes `"`, `` ` ``, `$` and `\`.
+///
+/// # Returns
+/// `None` if a quote is not terminated or the line contains no arguments.
+pub fn split_exec_line(exec: &str) -> Option<Vec<String>> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_argument = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_argument = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => current.push(chars.next()?),
+                        other => current.push(other),
+                    }
+                }
+            }
+            ' ' | '\t' | '\n' => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            other => {
+                in_argument = true;
+                current.push(other);
+            }
+        }
+    }
+    if in_argument {
+        arguments.push(current);
+    }
+    (!arguments.is_empty()).then_some(arguments)
+}
+
+/// Whether the arguments contain a code taking a single file or URL (`%f`, `%u`).
+///
+/// Such applications have to be started once per file when several are opened.
+pub fn takes_single_target(arguments: &[String]) -> bool {
+    let mut single = false;
+    for argument in arguments {
+        match argument.as_str() {
+            "%F" | "%U" => return false,
+            _ => single |= has_single_target_code(argument),
+        }
+    }
+    single
+}
+
+/// Whether `argument` embeds `%f` or `%u`, reading codes as [`expand_argument`] does so an
+/// escaped `%%f` is not taken for one.
+fn has_single_target_code(argument: &str) -> bool {
+    let mut chars = argument.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' && matches!(chars.next(), Some('f' | 'u')) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Converts a `file://` URI into a local path. Plain paths are returned unchanged,
+/// other URI schemes yield `None`.
+pub fn uri_to_path(uri: &str) -> Option<String> {
+    if let Some(rest) = uri.strip_prefix("file://") {
+        // Skip an optional host part ("file://localhost/...").
+        let path = &rest[rest.find('/')?..];
+        return percent_decode(path);
+    }
+    (!uri.contains("://")).then(|| uri.to_string())
+}
+
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = input.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Expands the field codes in `arguments` for launching `app` with the given URIs.
+///
+/// * `%f` / `%u`: the first file / URI, or removed if there is none
+/// * `%F` / `%U`: all files / URIs as separate arguments (must stand alone)
+/// * `%i`: `--icon <icon_name>` if the application has an icon (must stand alone)
+/// * `%c`: the display name of the application
+/// * `%%`: a literal `%`
+///
+/// Files are derived from `file://` URIs or plain paths; other URIs are skipped for `%f`/`%F`.
+/// All other codes are removed, and arguments consisting only of removed codes are dropped.
+pub fn expand_field_codes(app: &Application, arguments: &[String], uris: &[String]) -> Vec<String> {
+    let files: Vec<String> = uris.iter().filter_map(|uri| uri_to_path(uri)).collect();
+    let mut expanded = Vec::with_capacity(arguments.len() + uris.len());
+
+    for argument in arguments {
+        match argument.as_str() {
+            "%F" => expanded.extend(files.iter().cloned()),
+            "%U" => expanded.extend(uris.iter().cloned()),
+            "%i" => {
+                if let Some(icon) = &app.icon_name {
+                    expanded.push("--icon".to_string());
+                    expanded.push(icon.clone());
+                }
+            }
+            _ => {
+                if let Some(argument) = expand_argument(app, argument, files.first(), uris.first()) {
+                    expanded.push(argument);
+                }
+            }
+        }
+    }
+    expanded
+}
+
+/// Expands the codes embedded in a single argument. Returns `None` if the argument
+/// consisted only of codes that expanded to nothing.
+fn expand_argument(app: &Application, argument: &str, file: Option<&String>, uri: Option<&String>) -> Option<String> {
+    let mut result = String::with_capacity(argument.len());
+    let mut only_codes = true;
+    let mut chars = argument.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            only_codes = false;
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => {
+                only_codes = false;
+                result.push('%');
+            }
+            Some('f') => result.extend(file.map(String::as_str)),
+            Some('u') => result.extend(uri.map(String::as_str)),
+            Some('c') => result.push_str(app.display_name.as_deref().unwrap_or(&app.name)),
+            // %F, %U and %i are only valid as standalone arguments; %k, %d, %D, %n, %N,
+            // %v and %m are deprecated or unsupported. All of them are removed.
+            Some(_) | None => {}
+        }
+    }
+    (!(only_codes && result.is_empty())).then_some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn app() -> Application {
+        let mut app = Application::new_desktop("gimp".to_string(), "gimp".to_string(), Some("gimp".to_string()));
+        app.display_name = Some("GNU Image Manipulation Program".to_string());
+        app
+    }
+
+    #[test]
+    fn test_split_exec_line_with_quoting() {
+        assert_eq!(split_exec_line("gimp  --new-instance %U"), Some(args(&["gimp", "--new-instance", "%U"])));
+        assert_eq!(
+            split_exec_line(r#""/opt/My App/run" --title="a \"b\"" \x"#),
+            Some(args(&["/opt/My App/run", "--title=a \"b\"", "\\x"]))
+        );
+        assert_eq!(split_exec_line(r#"app "unterminated"#), None);
+        assert_eq!(split_exec_line("   "), None);
+    }
+
+    #[test]
+    fn test_expand_file_and_uri_lists() {
+        let uris = args(&["file:///home/user/a%20b.png", "https://example.org/c.png"]);
+        assert_eq!(
+            expand_field_codes(&app(), &args(&["--files", "%F"]), &uris),
+            args(&["--files", "/home/user/a b.png"])
+        );
+        assert_eq!(expand_field_codes(&app(), &args(&["%U"]), &uris), uris);
+    }
+
+    #[test]
+    fn test_expand_single_codes_and_removal() {
+        let uris = args(&["/tmp/x.txt"]);
+        assert_eq!(
+            expand_field_codes(&app(), &args(&["--open=%f", "%i", "--class=%c", "100%%", "%k", "%d"]), &uris),
+            args(&["--open=/tmp/x.txt", "--icon", "gimp", "--class=GNU Image Manipulation Program", "100%"])
+        );
+        assert_eq!(expand_field_codes(&app(), &args(&["%u", "--flag"]), &[]), args(&["--flag"]));
+        assert_eq!(expand_field_codes(&app(), &args(&["--open=%f"]), &[]), args(&["--open="]));
+    }
+
+    #[test]
+    fn test_takes_single_target() {
+        assert!(takes_single_target(&args(&["--open", "%f"])));
+        assert!(!takes_single_target(&args(&["%U"])));
+        assert!(!takes_single_target(&args(&["--new-window"])));
+        assert!(takes_single_target(&args(&["--file=%f"])));
+        // An escaped percent sign is not a field code.
+        assert!(!takes_single_target(&args(&["--format=%%f"])));
+        assert!(takes_single_target(&args(&["%%%u"])));
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(uri_to_path("file://localhost/etc/hosts"), Some("/etc/hosts".to_string()));
+        assert_eq!(uri_to_path("relative/path"), Some("relative/path".to_string()));
+        assert_eq!(uri_to_path("smb://server/share"), None);
+        assert_eq!(uri_to_path("file:///bad%zz"), None);
+    }
+}
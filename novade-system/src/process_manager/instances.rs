@@ -0,0 +1,49 @@
+// src/process_manager/instances.rs
+
+//! Types describing running application instances and how launches treat them.
+
+use std::time::Instant;
+
+use super::Pid;
+
+/// How a launch behaves if the application is already running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LaunchPolicy {
+    /// Always start a new process.
+    #[default]
+    MultipleInstances,
+    /// Start a process only if no instance is running; otherwise the existing instance is
+    /// returned so its window can be focused instead.
+    SingleInstance,
+}
+
+/// A running process of an application, as tracked by the process manager.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningInstance {
+    /// PID of the process.
+    pub pid: Pid,
+    /// Windows of the process known to the compositor, in the order they were attached.
+    pub window_ids: Vec<u32>,
+    /// Time the process was launched.
+    pub started_at: Instant,
+}
+
+/// Result of a launch under a [`LaunchPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchOutcome {
+    /// New processes were started.
+    Launched(Vec<Pid>),
+    /// The application is already running and no process was started. Focus the instance's
+    /// window (if it has one) instead.
+    AlreadyRunning(RunningInstance),
+}
+
+impl LaunchOutcome {
+    /// The window to focus if the launch was answered by an existing instance.
+    pub fn window_to_focus(&self) -> Option<u32> {
+        match self {
+            LaunchOutcome::AlreadyRunning(instance) => instance.window_ids.first().copied(),
+            LaunchOutcome::Launched(_) => None,
+        }
+    }
+}
@@ -0,0 +1,1001 @@
+// src/process_manager/mod.rs
+
+//! Launching and controlling application processes.
+//!
+//! [`ProcessManager`] is the interface the desktop uses to start [`Application`]s and to
+//! close them again, including applications that stopped responding.
+//! [`DefaultProcessManager`] implements it on top of `std::process`.
+
+pub mod autostart;
+pub mod exec;
+pub mod instances;
+pub mod output;
+pub mod queue;
+pub mod sandbox;
+pub mod stats;
+pub mod supervisor;
+#[cfg(all(unix, feature = "launch-systemd"))]
+pub mod systemd;
+
+use std::collections::HashMap;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::str::FromStr;
+use std::sync::mpsc::Receiver;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use novade_core::signal::Signal as EventSignal;
+use novade_core::types::NovaId;
+use novade_domain::entities::{Application, ResourceLimits};
+
+pub use self::autostart::{AutostartEntry, AutostartScheduler};
+pub use self::instances::{LaunchOutcome, LaunchPolicy, RunningInstance};
+pub use self::output::OutputCapture;
+pub use self::queue::{LaunchPriority, LaunchQueue, LaunchQueueState};
+pub use self::stats::{ManagedProcessInfo, ProcessStats};
+pub use self::supervisor::{RestartPolicy, ServiceState, Supervisor};
+#[cfg(all(unix, feature = "launch-systemd"))]
+pub use self::systemd::SystemdProcessManager;
+
+/// Operating system process ID.
+pub type Pid = u32;
+
+/// Errors returned by process management operations.
+#[derive(Debug)]
+pub enum ProcessError {
+    /// The process could not be started.
+    SpawnFailed { program: String, message: String },
+    /// No process with this PID exists (or it is not managed, where that is required).
+    NotFound(Pid),
+    /// Delivering a signal to the process failed.
+    SignalFailed { pid: Pid, message: String },
+    /// The operation is not supported on this platform.
+    Unsupported(String),
+}
+
+pub type ProcessResult<T> = Result<T, ProcessError>;
+
+/// Signals that can be sent to a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// Polite request to terminate (SIGTERM).
+    Terminate,
+    /// Immediate, non-catchable termination (SIGKILL).
+    Kill,
+    /// Interrupt, as from Ctrl+C (SIGINT).
+    Interrupt,
+    /// Hangup, often used to request a configuration reload (SIGHUP).
+    Hangup,
+    /// Suspend execution (SIGSTOP).
+    Stop,
+    /// Resume a stopped process (SIGCONT).
+    Continue,
+    /// User-defined signal 1 (SIGUSR1).
+    User1,
+    /// User-defined signal 2 (SIGUSR2).
+    User2,
+}
+
+#[cfg(unix)]
+impl Signal {
+    /// Returns the raw signal number for this platform.
+    pub fn as_raw(self) -> i32 {
+        match self {
+            Signal::Terminate => libc::SIGTERM,
+            Signal::Kill => libc::SIGKILL,
+            Signal::Interrupt => libc::SIGINT,
+            Signal::Hangup => libc::SIGHUP,
+            Signal::Stop => libc::SIGSTOP,
+            Signal::Continue => libc::SIGCONT,
+            Signal::User1 => libc::SIGUSR1,
+            Signal::User2 => libc::SIGUSR2,
+        }
+    }
+}
+
+/// Selects the [`ProcessManager`] implementation, e.g. from configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessManagerBackend {
+    /// Start applications as direct children ([`DefaultProcessManager`]).
+    #[default]
+    Direct,
+    /// Start applications in transient systemd scopes (`SystemdProcessManager`).
+    Systemd,
+}
+
+impl FromStr for ProcessManagerBackend {
+    type Err = ProcessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "direct" => Ok(ProcessManagerBackend::Direct),
+            "systemd" => Ok(ProcessManagerBackend::Systemd),
+            other => Err(ProcessError::Unsupported(format!("Unknown process manager backend '{}'", other))),
+        }
+    }
+}
+
+/// Creates the process manager for the configured backend.
+///
+/// # Errors
+/// `ProcessError::Unsupported` if the systemd backend is requested but not compiled in or
+/// the systemd user instance is not reachable.
+pub fn create_process_manager(backend: ProcessManagerBackend) -> ProcessResult<Box<dyn ProcessManager>> {
+    match backend {
+        ProcessManagerBackend::Direct => Ok(Box::new(DefaultProcessManager::new())),
+        #[cfg(all(unix, feature = "launch-systemd"))]
+        ProcessManagerBackend::Systemd => Ok(Box::new(SystemdProcessManager::new()?)),
+        #[cfg(not(all(unix, feature = "launch-systemd")))]
+        ProcessManagerBackend::Systemd => {
+            Err(ProcessError::Unsupported("systemd launching is not available in this build".to_string()))
+        }
+    }
+}
+
+/// Interface for starting and controlling application processes.
+pub trait ProcessManager: Send + Sync {
+    /// Starts the application and returns the PID of the new process.
+    fn launch_application(&self, app: &Application) -> ProcessResult<Pid> {
+        self.launch_application_with_env(app, &HashMap::new())
+    }
+
+    /// Starts the application for the given files or URIs, expanding the Exec field codes
+    /// in its arguments (see [`exec::expand_field_codes`]).
+    ///
+    /// Applications that only accept a single target (`%f`, `%u`) are started once per URI.
+    ///
+    /// # Returns
+    /// The PIDs of all started processes.
+    fn launch_application_with_uris(&self, app: &Application, uris: &[String]) -> ProcessResult<Vec<Pid>> {
+        let options = LaunchOptions { uris: uris.to_vec(), ..LaunchOptions::default() };
+        self.launch_application_with_options(app, &options)
+    }
+
+    /// Starts the application with additional environment variables for this launch.
+    ///
+    /// These take precedence over [`Application::environment`].
+    fn launch_application_with_env(&self, app: &Application, env: &HashMap<String, String>) -> ProcessResult<Pid> {
+        let options = LaunchOptions { environment: env.clone(), ..LaunchOptions::default() };
+        let pids = self.launch_application_with_options(app, &options)?;
+        pids.first().copied().ok_or_else(|| ProcessError::SpawnFailed {
+            program: app.executable_path.clone(),
+            message: "no process was started".to_string(),
+        })
+    }
+
+    /// Starts the application with a freshly generated startup notification token.
+    ///
+    /// Pass the returned token to `Server::begin_startup` so the compositor can show a
+    /// "launching" indicator and focus the application's first window.
+    fn launch_application_with_startup_notification(&self, app: &Application) -> ProcessResult<(Pid, String)> {
+        let token = generate_startup_token(app);
+        let options = LaunchOptions { startup_token: Some(token.clone()), ..LaunchOptions::default() };
+        let pids = self.launch_application_with_options(app, &options)?;
+        let pid = pids.first().copied().ok_or_else(|| ProcessError::SpawnFailed {
+            program: app.executable_path.clone(),
+            message: "no process was started".to_string(),
+        })?;
+        Ok((pid, token))
+    }
+
+    /// Starts the application as described by `options`.
+    ///
+    /// # Returns
+    /// The PIDs of all started processes (more than one if a single-target application is
+    /// opened with several URIs).
+    fn launch_application_with_options(&self, app: &Application, options: &LaunchOptions) -> ProcessResult<Vec<Pid>>;
+
+    /// Starts the application unless `policy` says to reuse a running instance.
+    ///
+    /// With [`LaunchPolicy::SingleInstance`] and an instance already running, nothing is
+    /// started and the caller should focus [`LaunchOutcome::window_to_focus`] instead.
+    fn launch_application_with_policy(
+        &self,
+        app: &Application,
+        options: &LaunchOptions,
+        policy: LaunchPolicy,
+    ) -> ProcessResult<LaunchOutcome> {
+        if policy == LaunchPolicy::SingleInstance {
+            if let Some(instance) = self.running_instances(&app.id).into_iter().next() {
+                println!("ProcessManager: '{}' is already running as PID {}.", app.name, instance.pid);
+                return Ok(LaunchOutcome::AlreadyRunning(instance));
+            }
+        }
+        self.launch_application_with_options(app, options).map(LaunchOutcome::Launched)
+    }
+
+    /// Running processes launched for the application, oldest first.
+    ///
+    /// Managers that do not track their processes report no instances.
+    fn running_instances(&self, _app_id: &NovaId) -> Vec<RunningInstance> {
+        Vec::new()
+    }
+
+    /// Current resource usage (CPU, memory, uptime) of a process.
+    fn process_stats(&self, pid: Pid) -> ProcessResult<ProcessStats> {
+        stats::read_process_stats(pid, None, Instant::now()).map(|(stats, _)| stats)
+    }
+
+    /// All processes started by this manager that are still running, with their resource
+    /// usage where available.
+    ///
+    /// Managers that do not track their processes report none.
+    fn list_managed_processes(&self) -> Vec<ManagedProcessInfo> {
+        Vec::new()
+    }
+
+    /// Asks the process to terminate gracefully (SIGTERM on Unix).
+    ///
+    /// On Windows there is no graceful equivalent for arbitrary processes, so managed
+    /// processes are terminated forcefully.
+    fn terminate_process(&self, pid: Pid) -> ProcessResult<()>;
+
+    /// Terminates the process immediately (SIGKILL on Unix), e.g. when it is hung.
+    fn kill_process(&self, pid: Pid) -> ProcessResult<()>;
+
+    /// Sends an arbitrary signal to the process.
+    ///
+    /// Only processes launched by this manager can be signalled; other PIDs yield
+    /// [`ProcessError::NotFound`]. The same applies to [`ProcessManager::terminate_process`]
+    /// and [`ProcessManager::kill_process`].
+    fn send_signal(&self, pid: Pid, signal: Signal) -> ProcessResult<()>;
+}
+
+/// Parameters of a single launch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchOptions {
+    /// Files or URIs to open, substituted for the Exec field codes.
+    pub uris: Vec<String>,
+    /// Environment variables for this launch only.
+    pub environment: HashMap<String, String>,
+    /// Startup notification token passed to the application as `XDG_ACTIVATION_TOKEN` and
+    /// `DESKTOP_STARTUP_ID`, see [`generate_startup_token`].
+    pub startup_token: Option<String>,
+}
+
+impl LaunchOptions {
+    /// The per-launch environment including the startup notification variables.
+    pub fn effective_environment(&self) -> HashMap<String, String> {
+        let mut environment = self.environment.clone();
+        if let Some(token) = &self.startup_token {
+            environment.insert(ACTIVATION_TOKEN_VARIABLE.to_string(), token.clone());
+            environment.insert(STARTUP_ID_VARIABLE.to_string(), token.clone());
+        }
+        environment
+    }
+}
+
+/// Environment variable carrying the startup token for Wayland clients (xdg-activation).
+pub const ACTIVATION_TOKEN_VARIABLE: &str = "XDG_ACTIVATION_TOKEN";
+/// Environment variable carrying the startup token per the Startup Notification spec.
+pub const STARTUP_ID_VARIABLE: &str = "DESKTOP_STARTUP_ID";
+
+static STARTUP_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Generates a unique startup notification token for launching `app`.
+///
+/// The token follows the `<unique>_TIME<timestamp>` convention of the Startup Notification
+/// specification, e.g. `novade-firefox-1234-0_TIME1718000000000`.
+pub fn generate_startup_token(app: &Application) -> String {
+    let name: String = app
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '_' })
+        .collect();
+    let sequence = STARTUP_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or_default();
+    format!("novade-{}-{}-{}_TIME{}", name, std::process::id(), sequence, millis)
+}
+
+/// Splits a launch of `app` for `uris` into the argument lists of the individual processes.
+pub(crate) fn launch_arguments(app: &Application, uris: &[String]) -> Vec<Vec<String>> {
+    let arguments = app.arguments.as_deref().unwrap_or_default();
+    if uris.len() > 1 && exec::takes_single_target(arguments) {
+        uris.iter()
+            .map(|uri| exec::expand_field_codes(app, arguments, std::slice::from_ref(uri)))
+            .collect()
+    } else {
+        vec![exec::expand_field_codes(app, arguments, uris)]
+    }
+}
+
+/// Builds the command used to start an application with already expanded arguments.
+///
+/// Environment variables are applied in increasing precedence: `base_environment`,
+/// [`Application::environment`], then `launch_environment`. Sandboxed applications are
+/// started through their sandbox tool (see [`sandbox::sandbox_invocation`]).
+pub(crate) fn build_command(
+    app: &Application,
+    arguments: &[String],
+    base_environment: &HashMap<String, String>,
+    launch_environment: &HashMap<String, String>,
+) -> Command {
+    let mut environment = base_environment.clone();
+    environment.extend(app.environment.iter().flatten().map(|(k, v)| (k.clone(), v.clone())));
+    environment.extend(launch_environment.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+    let mut command = match &app.sandbox {
+        Some(sandbox) => {
+            let (program, sandbox_arguments) = sandbox::sandbox_invocation(sandbox, arguments, &environment);
+            let mut command = Command::new(program);
+            command.args(sandbox_arguments);
+            command
+        }
+        None => {
+            let mut command = Command::new(&app.executable_path);
+            command.args(arguments);
+            command
+        }
+    };
+    command.envs(&environment);
+    if let Some(limits) = &app.resource_limits {
+        apply_resource_limits(&mut command, limits);
+    }
+    if let Some(working_directory) = &app.working_directory {
+        command.current_dir(working_directory);
+    }
+    command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    command
+}
+
+/// Applies the limits in the child between `fork` and `exec`.
+#[cfg(unix)]
+fn apply_resource_limits(command: &mut Command, limits: &ResourceLimits) {
+    use std::os::unix::process::CommandExt;
+
+    let limits = limits.clone();
+    // SAFETY: the closure only calls setrlimit(2) and setpriority(2), which are
+    // async-signal-safe, and does not allocate.
+    unsafe {
+        command.pre_exec(move || {
+            if let Some(bytes) = limits.max_memory_bytes {
+                set_rlimit(libc::RLIMIT_AS, bytes)?;
+            }
+            if let Some(files) = limits.max_open_files {
+                set_rlimit(libc::RLIMIT_NOFILE, files)?;
+            }
+            if let Some(nice) = limits.nice {
+                if libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Type of the resource argument of setrlimit(2), which differs between C libraries.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+#[cfg(unix)]
+fn set_rlimit(resource: RlimitResource, value: u64) -> std::io::Result<()> {
+    let limit = libc::rlimit { rlim_cur: value as libc::rlim_t, rlim_max: value as libc::rlim_t };
+    // SAFETY: `limit` is a valid rlimit structure for the duration of the call.
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn apply_resource_limits(_command: &mut Command, limits: &ResourceLimits) {
+    if *limits != ResourceLimits::default() {
+        eprintln!("ProcessManager: Resource limits are not supported on this platform and are ignored.");
+    }
+}
+
+/// Notification that a managed process has been started, e.g. for recording the launch in
+/// the launch history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessLaunchEvent {
+    /// PID of the new process.
+    pub pid: Pid,
+    /// ID of the application the process was launched for.
+    pub app_id: NovaId,
+}
+
+/// Notification that a managed process has exited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessExitEvent {
+    /// PID the process had while running.
+    pub pid: Pid,
+    /// ID of the application the process was launched for.
+    pub app_id: NovaId,
+    /// Exit code, if the process exited normally.
+    pub exit_code: Option<i32>,
+    /// Signal that terminated the process (Unix only).
+    pub signal: Option<i32>,
+    /// Time between launch and the moment the exit was noticed.
+    pub runtime: Duration,
+}
+
+impl ProcessExitEvent {
+    /// Whether the process exited normally with code 0.
+    pub fn is_success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// Interval at which the reaper thread polls managed children for exit.
+const REAP_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A child started by the [`DefaultProcessManager`].
+#[derive(Debug)]
+struct ManagedProcess {
+    child: Child,
+    app_id: NovaId,
+    started_at: Instant,
+    startup_token: Option<String>,
+    window_ids: Vec<u32>,
+}
+
+#[derive(Debug, Default)]
+struct ManagerState {
+    processes: HashMap<Pid, ManagedProcess>,
+    reaper_started: bool,
+    environment: HashMap<String, String>,
+    output_capture: OutputCapture,
+    /// Last CPU sample per process, for CPU usage since the previous query.
+    cpu_samples: HashMap<Pid, stats::CpuSample>,
+}
+
+/// Default [`ProcessManager`] based on `std::process`.
+///
+/// Keeps the `Child` handle of every process it started. A background thread, started
+/// with the first launch and stopped once the last clone of the manager is dropped,
+/// reaps exited children and reports them to [`DefaultProcessManager::subscribe_exits`].
+#[derive(Debug, Default, Clone)]
+pub struct DefaultProcessManager {
+    state: Arc<Mutex<ManagerState>>,
+    exits: Arc<EventSignal<ProcessExitEvent>>,
+    launches: Arc<EventSignal<ProcessLaunchEvent>>,
+}
+
+impl DefaultProcessManager {
+    /// Creates a process manager without any managed processes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an environment variable for all applications launched from now on
+    /// (e.g. `WAYLAND_DISPLAY` or a `GTK_THEME` chosen in the preferences).
+    ///
+    /// [`Application::environment`] and per-launch variables take precedence.
+    pub fn set_environment_variable(&self, key: impl Into<String>, value: impl Into<String>) {
+        self.state.lock().unwrap().environment.insert(key.into(), value.into());
+    }
+
+    /// Removes a variable set with [`DefaultProcessManager::set_environment_variable`].
+    pub fn remove_environment_variable(&self, key: &str) {
+        self.state.lock().unwrap().environment.remove(key);
+    }
+
+    /// Sets what happens with the stdout/stderr of applications launched from now on.
+    ///
+    /// Capture is disabled by default; the preferences may enable logging or log files.
+    pub fn set_output_capture(&self, capture: OutputCapture) {
+        self.state.lock().unwrap().output_capture = capture;
+    }
+
+    /// Associates a compositor window with a managed process.
+    ///
+    /// # Returns
+    /// `false` if the process is not managed.
+    pub fn attach_window(&self, pid: Pid, window_id: u32) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let Some(process) = state.processes.get_mut(&pid) else {
+            return false;
+        };
+        if !process.window_ids.contains(&window_id) {
+            process.window_ids.push(window_id);
+        }
+        true
+    }
+
+    /// Removes a closed window from the process it was attached to.
+    pub fn detach_window(&self, window_id: u32) {
+        for process in self.state.lock().unwrap().processes.values_mut() {
+            process.window_ids.retain(|&id| id != window_id);
+        }
+    }
+
+    /// The managed process that was launched with this startup notification token, used to
+    /// attach the window that activates itself with the token.
+    pub fn pid_for_startup_token(&self, token: &str) -> Option<Pid> {
+        let state = self.state.lock().unwrap();
+        state
+            .processes
+            .iter()
+            .find(|(_, process)| process.startup_token.as_deref() == Some(token))
+            .map(|(&pid, _)| pid)
+    }
+
+    /// Whether the process with this PID was started by this manager and not yet reaped.
+    pub fn is_managed(&self, pid: Pid) -> bool {
+        self.state.lock().unwrap().processes.contains_key(&pid)
+    }
+
+    /// The signal emitted for every managed process that exits, e.g. to connect callbacks.
+    pub fn exits(&self) -> &EventSignal<ProcessExitEvent> {
+        &self.exits
+    }
+
+    /// The signal emitted for every process this manager starts.
+    pub fn launches(&self) -> &EventSignal<ProcessLaunchEvent> {
+        &self.launches
+    }
+
+    /// Returns a channel on which an event is delivered for every managed process that exits
+    /// from now on.
+    pub fn subscribe_exits(&self) -> Receiver<ProcessExitEvent> {
+        self.exits.subscribe()
+    }
+
+    /// Returns a channel on which an event is delivered for every process this manager starts
+    /// from now on.
+    pub fn subscribe_launches(&self) -> Receiver<ProcessLaunchEvent> {
+        self.launches.subscribe()
+    }
+
+    /// Checks all managed children once, removes the ones that have exited and notifies
+    /// subscribers. Called periodically by the reaper thread.
+    ///
+    /// # Returns
+    /// The exit events of this sweep.
+    pub fn reap_exited(&self) -> Vec<ProcessExitEvent> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let mut exited = Vec::new();
+        state.processes.retain(|&pid, process| match process.child.try_wait() {
+            Ok(Some(status)) => {
+                exited.push(exit_event(pid, process, status, now));
+                false
+            }
+            Ok(None) => true,
+            Err(e) => {
+                eprintln!("ProcessManager: Failed to query status of process {}: {}", pid, e);
+                true
+            }
+        });
+        for event in &exited {
+            state.cpu_samples.remove(&event.pid);
+            println!("ProcessManager: Process {} exited after {:?} ({:?}).", event.pid, event.runtime, event.exit_code);
+        }
+        // Subscribers may call back into the manager.
+        drop(state);
+        for event in &exited {
+            self.exits.emit(event.clone());
+        }
+        exited
+    }
+
+    fn spawn(&self, app: &Application, arguments: &[String], options: &LaunchOptions) -> ProcessResult<Pid> {
+        let environment = options.effective_environment();
+        let (base_environment, capture) = {
+            let state = self.state.lock().unwrap();
+            (state.environment.clone(), state.output_capture.clone())
+        };
+        let mut command = build_command(app, arguments, &base_environment, &environment);
+        if capture.is_enabled() {
+            command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        }
+        let mut child = command.spawn().map_err(|e| ProcessError::SpawnFailed {
+            program: app.executable_path.clone(),
+            message: e.to_string(),
+        })?;
+        output::capture_output(&capture, &app.name, &mut child);
+        let pid = child.id();
+        let process = ManagedProcess {
+            child,
+            app_id: app.id.clone(),
+            started_at: Instant::now(),
+            startup_token: options.startup_token.clone(),
+            window_ids: Vec::new(),
+        };
+        self.state.lock().unwrap().processes.insert(pid, process);
+        self.launches.emit(ProcessLaunchEvent { pid, app_id: app.id.clone() });
+        self.ensure_reaper();
+        println!("ProcessManager: Launched '{}' with PID {}.", app.name, pid);
+        Ok(pid)
+    }
+
+    fn ensure_reaper(&self) {
+        let mut state = self.state.lock().unwrap();
+        if std::mem::replace(&mut state.reaper_started, true) {
+            return;
+        }
+        let weak_state = Arc::downgrade(&self.state);
+        let (exits, launches) = (self.exits.clone(), self.launches.clone());
+        thread::spawn(move || {
+            while let Some(state) = weak_state.upgrade() {
+                DefaultProcessManager { state, exits: exits.clone(), launches: launches.clone() }.reap_exited();
+                thread::sleep(REAP_INTERVAL);
+            }
+        });
+    }
+
+    #[cfg(unix)]
+    fn signal(&self, pid: Pid, signal: Signal) -> ProcessResult<()> {
+        // Only processes we launched and have not reaped yet: anything else may be a foreign
+        // process or a reused PID. This also rules out pid 0, for which kill(0, ..) would
+        // signal the compositor's whole process group.
+        if !self.is_managed(pid) {
+            return Err(ProcessError::NotFound(pid));
+        }
+        let raw_pid = libc::pid_t::try_from(pid).map_err(|_| ProcessError::NotFound(pid))?;
+        // SAFETY: kill(2) has no memory-safety preconditions.
+        if unsafe { libc::kill(raw_pid, signal.as_raw()) } == 0 {
+            println!("ProcessManager: Sent {:?} to process {}.", signal, pid);
+            return Ok(());
+        }
+        let error = std::io::Error::last_os_error();
+        if error.raw_os_error() == Some(libc::ESRCH) {
+            Err(ProcessError::NotFound(pid))
+        } else {
+            Err(ProcessError::SignalFailed { pid, message: error.to_string() })
+        }
+    }
+
+    #[cfg(windows)]
+    fn signal(&self, pid: Pid, signal: Signal) -> ProcessResult<()> {
+        match signal {
+            Signal::Terminate | Signal::Kill | Signal::Interrupt => {
+                let mut state = self.state.lock().unwrap();
+                let process = state.processes.get_mut(&pid).ok_or(ProcessError::NotFound(pid))?;
+                process
+                    .child
+                    .kill()
+                    .map_err(|e| ProcessError::SignalFailed { pid, message: e.to_string() })?;
+                println!("ProcessManager: Terminated process {}.", pid);
+                Ok(())
+            }
+            other => Err(ProcessError::Unsupported(format!("{:?} is not supported on Windows", other))),
+        }
+    }
+}
+
+fn exit_event(pid: Pid, process: &ManagedProcess, status: ExitStatus, now: Instant) -> ProcessExitEvent {
+    #[cfg(unix)]
+    let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+    #[cfg(not(unix))]
+    let signal = None;
+    ProcessExitEvent {
+        pid,
+        app_id: process.app_id.clone(),
+        exit_code: status.code(),
+        signal,
+        runtime: now.saturating_duration_since(process.started_at),
+    }
+}
+
+impl ProcessManager for DefaultProcessManager {
+    fn launch_application_with_options(&self, app: &Application, options: &LaunchOptions) -> ProcessResult<Vec<Pid>> {
+        launch_arguments(app, &options.uris)
+            .iter()
+            .map(|arguments| self.spawn(app, arguments, options))
+            .collect()
+    }
+
+    fn process_stats(&self, pid: Pid) -> ProcessResult<ProcessStats> {
+        let now = Instant::now();
+        let previous = self.state.lock().unwrap().cpu_samples.get(&pid).copied();
+        let (stats, sample) = stats::read_process_stats(pid, previous, now)?;
+        let mut state = self.state.lock().unwrap();
+        // Only remember samples of managed processes; they are dropped when reaped.
+        if state.processes.contains_key(&pid) {
+            state.cpu_samples.insert(pid, sample);
+        }
+        Ok(stats)
+    }
+
+    fn list_managed_processes(&self) -> Vec<ManagedProcessInfo> {
+        let mut processes: Vec<(Pid, NovaId, Instant)> = {
+            let state = self.state.lock().unwrap();
+            state.processes.iter().map(|(&pid, p)| (pid, p.app_id.clone(), p.started_at)).collect()
+        };
+        processes.sort_by_key(|&(_, _, started_at)| started_at);
+        processes
+            .into_iter()
+            .map(|(pid, app_id, _)| ManagedProcessInfo { pid, app_id, stats: self.process_stats(pid).ok() })
+            .collect()
+    }
+
+    fn running_instances(&self, app_id: &NovaId) -> Vec<RunningInstance> {
+        let state = self.state.lock().unwrap();
+        let mut instances: Vec<RunningInstance> = state
+            .processes
+            .iter()
+            .filter(|(_, process)| process.app_id == *app_id)
+            .map(|(&pid, process)| RunningInstance {
+                pid,
+                window_ids: process.window_ids.clone(),
+                started_at: process.started_at,
+            })
+            .collect();
+        instances.sort_by_key(|instance| instance.started_at);
+        instances
+    }
+
+    fn terminate_process(&self, pid: Pid) -> ProcessResult<()> {
+        self.signal(pid, Signal::Terminate)
+    }
+
+    fn kill_process(&self, pid: Pid) -> ProcessResult<()> {
+        self.signal(pid, Signal::Kill)
+    }
+
+    fn send_signal(&self, pid: Pid, signal: Signal) -> ProcessResult<()> {
+        self.signal(pid, signal)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn app(executable: &str, arguments: &[&str]) -> Application {
+        let mut app = Application::new_desktop(executable.to_string(), executable.to_string(), None);
+        app.arguments = Some(arguments.iter().map(|a| a.to_string()).collect());
+        app
+    }
+
+    fn sleeper() -> Application {
+        app("sleep", &["30"])
+    }
+
+    fn next_exit(exits: &Receiver<ProcessExitEvent>) -> ProcessExitEvent {
+        exits.recv_timeout(Duration::from_secs(5)).expect("exit event should arrive")
+    }
+
+    #[test]
+    fn test_terminate_process() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        let pid = manager.launch_application(&sleeper()).expect("sleep should launch");
+        assert!(manager.is_managed(pid));
+
+        manager.terminate_process(pid).unwrap();
+        let event = next_exit(&exits);
+        assert_eq!(event.pid, pid);
+        assert_eq!(event.signal, Some(libc::SIGTERM));
+        assert!(!manager.is_managed(pid));
+    }
+
+    #[test]
+    fn test_kill_process() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        let pid = manager.launch_application(&sleeper()).unwrap();
+
+        manager.kill_process(pid).unwrap();
+        assert_eq!(next_exit(&exits).signal, Some(libc::SIGKILL));
+    }
+
+    #[test]
+    fn test_exit_event_reports_code_and_app() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        let failing = app("sh", &["-c", "exit 3"]);
+        let pid = manager.launch_application(&failing).unwrap();
+
+        let event = next_exit(&exits);
+        assert_eq!(event.pid, pid);
+        assert_eq!(event.app_id, failing.id);
+        assert_eq!(event.exit_code, Some(3));
+        assert_eq!(event.signal, None);
+        assert!(!event.is_success());
+    }
+
+    #[test]
+    fn test_launches_are_reported() {
+        let manager = DefaultProcessManager::new();
+        let launches = manager.subscribe_launches();
+        let quick = app("true", &[]);
+        let pid = manager.launch_application(&quick).unwrap();
+
+        let event = launches.recv_timeout(Duration::from_secs(5)).expect("launch event should arrive");
+        assert_eq!(event, ProcessLaunchEvent { pid, app_id: quick.id });
+    }
+
+    #[test]
+    fn test_launch_once_per_uri_for_single_target_apps() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        let opener = app("true", &["%f"]);
+        let uris = vec!["/tmp/a".to_string(), "/tmp/b".to_string()];
+
+        let pids = manager.launch_application_with_uris(&opener, &uris).unwrap();
+        assert_eq!(pids.len(), 2);
+        assert!(next_exit(&exits).is_success());
+        assert!(next_exit(&exits).is_success());
+    }
+
+    #[test]
+    fn test_launch_arguments_expands_lists_in_one_process() {
+        let viewer = app("viewer", &["--", "%F"]);
+        let uris = vec!["file:///tmp/a".to_string(), "/tmp/b".to_string()];
+        assert_eq!(launch_arguments(&viewer, &uris), vec![vec!["--", "/tmp/a", "/tmp/b"]]);
+    }
+
+    #[test]
+    fn test_environment_precedence() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        manager.set_environment_variable("NOVADE_A", "manager");
+        manager.set_environment_variable("NOVADE_B", "manager");
+        manager.set_environment_variable("NOVADE_C", "manager");
+        let mut checker = app(
+            "sh",
+            &["-c", r#"[ "$NOVADE_A" = manager ] && [ "$NOVADE_B" = app ] && [ "$NOVADE_C" = launch ]"#],
+        );
+        checker.environment = Some(
+            [("NOVADE_B", "app"), ("NOVADE_C", "app")].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        );
+        let launch_env = HashMap::from([("NOVADE_C".to_string(), "launch".to_string())]);
+
+        manager.launch_application_with_env(&checker, &launch_env).unwrap();
+        assert!(next_exit(&exits).is_success());
+    }
+
+    #[test]
+    fn test_startup_token_passed_in_environment() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        let checker = app("sh", &["-c", r#"[ -n "$XDG_ACTIVATION_TOKEN" ] && [ "$XDG_ACTIVATION_TOKEN" = "$DESKTOP_STARTUP_ID" ]"#]);
+
+        let (_pid, token) = manager.launch_application_with_startup_notification(&checker).unwrap();
+        assert!(token.starts_with("novade-sh-"));
+        assert!(token.contains("_TIME"));
+        assert!(next_exit(&exits).is_success());
+        assert_ne!(generate_startup_token(&checker), token, "Tokens are unique");
+    }
+
+    #[test]
+    fn test_output_captured_to_file() {
+        let directory = std::env::temp_dir().join(format!("novade-capture-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        let manager = DefaultProcessManager::new();
+        manager.set_output_capture(OutputCapture::Files {
+            directory: directory.clone(),
+            max_file_size: output::DEFAULT_MAX_LOG_SIZE,
+            max_files: 1,
+        });
+        let exits = manager.subscribe_exits();
+        let pid = manager.launch_application(&app("sh", &["-c", "echo hello; echo oops >&2"])).unwrap();
+        next_exit(&exits);
+
+        let path = output::log_file_path(&directory, "sh");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut content = String::new();
+        while content.lines().count() < 2 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+            content = std::fs::read_to_string(&path).unwrap_or_default();
+        }
+        assert!(content.contains(&format!("[{} stdout] hello", pid)), "{}", content);
+        assert!(content.contains(&format!("[{} stderr] oops", pid)), "{}", content);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_resource_limits_applied() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        let mut checker = app("sh", &["-c", r#"[ "$(ulimit -n)" = 64 ] && [ "$(nice)" = 5 ]"#]);
+        checker.resource_limits = Some(ResourceLimits { max_open_files: Some(64), nice: Some(5), ..Default::default() });
+
+        manager.launch_application(&checker).unwrap();
+        assert!(next_exit(&exits).is_success());
+    }
+
+    #[test]
+    fn test_single_instance_reuses_running_process() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        let app = sleeper();
+        let options = LaunchOptions { startup_token: Some("token-1".to_string()), ..LaunchOptions::default() };
+
+        let LaunchOutcome::Launched(pids) =
+            manager.launch_application_with_policy(&app, &options, LaunchPolicy::SingleInstance).unwrap()
+        else {
+            panic!("First launch must start a process");
+        };
+        let pid = pids[0];
+        assert_eq!(manager.pid_for_startup_token("token-1"), Some(pid));
+        assert!(manager.attach_window(pid, 42));
+
+        let outcome = manager.launch_application_with_policy(&app, &options, LaunchPolicy::SingleInstance).unwrap();
+        assert_eq!(outcome.window_to_focus(), Some(42));
+        assert_eq!(manager.running_instances(&app.id).len(), 1);
+
+        let outcome = manager.launch_application_with_policy(&app, &options, LaunchPolicy::MultipleInstances).unwrap();
+        assert!(matches!(outcome, LaunchOutcome::Launched(_)));
+        let instances = manager.running_instances(&app.id);
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].pid, pid, "Oldest instance first");
+
+        for instance in instances {
+            manager.kill_process(instance.pid).unwrap();
+            next_exit(&exits);
+        }
+        assert!(manager.running_instances(&app.id).is_empty());
+    }
+
+    #[test]
+    fn test_sandboxed_application_started_through_sandbox_tool() {
+        let mut app = app("sleep", &["%U"]);
+        app.sandbox = Some(novade_domain::entities::SandboxKind::Snap { name: "sleep".to_string() });
+        let command = build_command(&app, &["1".to_string()], &HashMap::new(), &HashMap::new());
+        assert_eq!(command.get_program(), "snap");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), vec!["run", "sleep", "1"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_list_managed_processes_with_stats() {
+        let manager = DefaultProcessManager::new();
+        let exits = manager.subscribe_exits();
+        let sleeper = sleeper();
+        let pid = manager.launch_application(&sleeper).unwrap();
+
+        let processes = manager.list_managed_processes();
+        assert_eq!(processes.len(), 1);
+        assert_eq!((processes[0].pid, &processes[0].app_id), (pid, &sleeper.id));
+        let stats = processes[0].stats.as_ref().expect("stats of a running child");
+        // The RSS is not checked: right after exec the child may not have touched any page
+        // yet. Its conversion is covered by the tests in `stats`.
+        assert_eq!(stats.pid, pid);
+        assert!(manager.process_stats(pid).is_ok(), "Second query uses the stored sample");
+
+        manager.kill_process(pid).unwrap();
+        next_exit(&exits);
+        assert!(manager.list_managed_processes().is_empty());
+        assert!(manager.state.lock().unwrap().cpu_samples.is_empty());
+    }
+
+    #[test]
+    fn test_process_manager_backend_from_str() {
+        assert_eq!("direct".parse::<ProcessManagerBackend>().unwrap(), ProcessManagerBackend::Direct);
+        assert_eq!(" Systemd ".parse::<ProcessManagerBackend>().unwrap(), ProcessManagerBackend::Systemd);
+        assert!("docker".parse::<ProcessManagerBackend>().is_err());
+    }
+
+    #[test]
+    fn test_send_signal_to_missing_process() {
+        let manager = DefaultProcessManager::new();
+        // PIDs are capped well below i32::MAX on Linux.
+        let result = manager.send_signal(i32::MAX as Pid, Signal::User1);
+        assert!(matches!(result, Err(ProcessError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_unmanaged_process_is_not_signalled() {
+        let manager = DefaultProcessManager::new();
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+
+        assert!(matches!(manager.kill_process(pid), Err(ProcessError::NotFound(p)) if p == pid));
+        assert!(child.try_wait().unwrap().is_none());
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn test_pid_zero_is_rejected() {
+        let manager = DefaultProcessManager::new();
+        // Signal::User1 would terminate the test process if it reached our process group.
+        assert!(matches!(manager.send_signal(0, Signal::User1), Err(ProcessError::NotFound(0))));
+        assert!(matches!(manager.terminate_process(0), Err(ProcessError::NotFound(0))));
+    }
+
+    #[test]
+    fn test_launch_missing_executable() {
+        let manager = DefaultProcessManager::new();
+        let app = Application::new_desktop("missing".to_string(), "/nonexistent/novade-test".to_string(), None);
+        assert!(matches!(manager.launch_application(&app), Err(ProcessError::SpawnFailed { .. })));
+    }
+}
@@ -0,0 +1,245 @@
+// src/process_manager/output.rs
+
+//! Capturing the stdout/stderr of launched applications.
+//!
+//! By default the output of launched applications is discarded. With [`OutputCapture::Log`]
+//! every line is forwarded to the tracing infrastructure (target [`APP_OUTPUT_TARGET`],
+//! tagged with the application name), with [`OutputCapture::Files`] it is appended to a
+//! per-application log file that is rotated once it grows too large.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use super::Pid;
+
+/// Tracing target under which captured application output is logged.
+pub const APP_OUTPUT_TARGET: &str = "novade_system::app_output";
+
+/// Default size at which a per-application log file is rotated.
+pub const DEFAULT_MAX_LOG_SIZE: u64 = 1024 * 1024;
+/// Default number of rotated log files kept per application.
+pub const DEFAULT_MAX_LOG_FILES: usize = 3;
+
+/// What happens with the output of launched applications.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum OutputCapture {
+    /// Output is discarded.
+    #[default]
+    Disabled,
+    /// Each line is logged via tracing (stdout as info, stderr as warn).
+    Log,
+    /// Output is written to `<directory>/<app name>.log`, rotated at `max_file_size` bytes,
+    /// keeping `max_files` old files (`.log.1` being the newest).
+    Files { directory: PathBuf, max_file_size: u64, max_files: usize },
+}
+
+impl OutputCapture {
+    /// File capture into the default directory (`<cache dir>/novade/app-logs`) with default
+    /// limits, or `None` if no cache directory can be determined.
+    pub fn default_files() -> Option<Self> {
+        let directory = novade_core::utils::get_app_cache_dir("novade")?.join("app-logs");
+        Some(OutputCapture::Files { directory, max_file_size: DEFAULT_MAX_LOG_SIZE, max_files: DEFAULT_MAX_LOG_FILES })
+    }
+
+    /// Whether the output has to be piped from the child.
+    pub fn is_enabled(&self) -> bool {
+        *self != OutputCapture::Disabled
+    }
+}
+
+/// A log file that is rotated when it exceeds a size limit.
+#[derive(Debug)]
+pub struct RotatingLogFile {
+    path: PathBuf,
+    max_file_size: u64,
+    max_files: usize,
+    file: Option<File>,
+    size: u64,
+}
+
+impl RotatingLogFile {
+    /// Opens (or creates) the log file, creating the directory if necessary.
+    pub fn open(path: PathBuf, max_file_size: u64, max_files: usize) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, max_file_size, max_files, file: Some(file), size })
+    }
+
+    /// Appends a line, rotating the file first if the line would exceed the size limit.
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let length = line.len() as u64 + 1;
+        if self.size > 0 && self.size + length > self.max_file_size {
+            self.rotate()?;
+        }
+        let file = self.file.as_mut().ok_or_else(|| io::Error::other("log file closed"))?;
+        writeln!(file, "{}", line)?;
+        self.size += length;
+        Ok(())
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.rotated_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        self.file = Some(OpenOptions::new().create(true).append(true).open(&self.path)?);
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// Replaces characters that are unsafe in file names.
+fn log_file_name(app_name: &str) -> String {
+    let name: String = app_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    format!("{}.log", name.trim_start_matches('.'))
+}
+
+/// Path of the log file for an application inside `directory`.
+pub fn log_file_path(directory: &Path, app_name: &str) -> PathBuf {
+    directory.join(log_file_name(app_name))
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    fn name(self) -> &'static str {
+        match self {
+            Stream::Stdout => "stdout",
+            Stream::Stderr => "stderr",
+        }
+    }
+}
+
+/// Where the lines of one child end up.
+#[derive(Debug, Clone)]
+enum Sink {
+    Log,
+    File(Arc<Mutex<RotatingLogFile>>),
+}
+
+impl Sink {
+    fn write(&self, app_name: &str, pid: Pid, stream: Stream, line: &str) {
+        match self {
+            Sink::Log => match stream {
+                Stream::Stdout => novade_core::info!(target: APP_OUTPUT_TARGET, app = app_name, pid, "{}", line),
+                Stream::Stderr => novade_core::warn!(target: APP_OUTPUT_TARGET, app = app_name, pid, "{}", line),
+            },
+            Sink::File(file) => {
+                let entry = format!("[{} {}] {}", pid, stream.name(), line);
+                if let Err(e) = file.lock().unwrap().write_line(&entry) {
+                    eprintln!("ProcessManager: Failed to write output of '{}': {}", app_name, e);
+                }
+            }
+        }
+    }
+}
+
+/// Starts forwarding the piped stdout/stderr of `child` according to `capture`.
+///
+/// The forwarding threads end when the child closes its output.
+pub(crate) fn capture_output(capture: &OutputCapture, app_name: &str, child: &mut Child) {
+    let sink = match capture {
+        OutputCapture::Disabled => return,
+        OutputCapture::Log => Sink::Log,
+        OutputCapture::Files { directory, max_file_size, max_files } => {
+            let path = log_file_path(directory, app_name);
+            match RotatingLogFile::open(path.clone(), *max_file_size, *max_files) {
+                Ok(file) => Sink::File(Arc::new(Mutex::new(file))),
+                Err(e) => {
+                    eprintln!("ProcessManager: Failed to open log file {}: {}", path.display(), e);
+                    return;
+                }
+            }
+        }
+    };
+    let pid = child.id();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, sink.clone(), app_name.to_string(), pid, Stream::Stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, sink, app_name.to_string(), pid, Stream::Stderr);
+    }
+}
+
+fn forward_lines<R: Read + Send + 'static>(reader: R, sink: Sink, app_name: String, pid: Pid, stream: Stream) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut buffer = Vec::new();
+        // Read raw bytes so that invalid UTF-8 does not end the capture.
+        while matches!(reader.read_until(b'\n', &mut buffer), Ok(n) if n > 0) {
+            let line = String::from_utf8_lossy(&buffer);
+            sink.write(&app_name, pid, stream, line.trim_end_matches(['\n', '\r']));
+            buffer.clear();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("novade-output-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_log_file_rotation() {
+        let dir = temp_dir("rotation");
+        let path = dir.join("app.log");
+        let mut log = RotatingLogFile::open(path.clone(), 10, 2).unwrap();
+
+        for line in ["first", "second", "third", "fourth"] {
+            log.write_line(line).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\n");
+        assert_eq!(fs::read_to_string(dir.join("app.log.1")).unwrap(), "third\n");
+        assert_eq!(fs::read_to_string(dir.join("app.log.2")).unwrap(), "second\n");
+        assert!(!dir.join("app.log.3").exists(), "Only max_files old files are kept");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_log_file_path_is_sanitized() {
+        let dir = Path::new("/tmp/logs");
+        assert_eq!(log_file_path(dir, "org.gnome.Text Editor"), dir.join("org.gnome.Text_Editor.log"));
+        assert_eq!(log_file_path(dir, "../evil"), dir.join("_evil.log"));
+    }
+
+    #[test]
+    fn test_capture_default_is_disabled() {
+        assert!(!OutputCapture::default().is_enabled());
+        assert!(OutputCapture::Log.is_enabled());
+    }
+}
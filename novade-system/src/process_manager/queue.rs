@@ -0,0 +1,266 @@
+// src/process_manager/queue.rs
+
+//! Queueing of application launches.
+//!
+//! Starting many applications at once (e.g. all autostart entries at session start) causes
+//! an I/O storm that makes every one of them, and the desktop, slow to appear. The
+//! [`LaunchQueue`] limits how many launches are "cold" at the same time: a launch occupies
+//! a slot until it is reported complete (its window appeared), its process exits, or the
+//! warm-up time has passed. Interactive launches always go before autostart items.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use novade_domain::entities::Application;
+
+use super::{LaunchOptions, Pid, ProcessExitEvent, ProcessManager, ProcessResult};
+
+/// Priority of a queued launch. Lower values are started first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LaunchPriority {
+    /// Started by the user (launcher, dock, file manager).
+    Interactive,
+    /// Started automatically, e.g. from the XDG autostart directories.
+    Autostart,
+}
+
+/// Identifies a launch submitted to the [`LaunchQueue`].
+pub type LaunchTicket = u64;
+
+#[derive(Debug)]
+struct QueuedLaunch {
+    ticket: LaunchTicket,
+    app: Application,
+    options: LaunchOptions,
+    priority: LaunchPriority,
+    queued_at: Instant,
+}
+
+/// A queued launch as reported by [`LaunchQueue::state`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedLaunchInfo {
+    /// Ticket returned by [`LaunchQueue::enqueue`].
+    pub ticket: LaunchTicket,
+    /// Name of the application to launch.
+    pub app_name: String,
+    /// Priority of the launch.
+    pub priority: LaunchPriority,
+    /// Time the launch was queued.
+    pub queued_at: Instant,
+}
+
+/// Snapshot of the queue, e.g. for a "starting applications" indicator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchQueueState {
+    /// Launches waiting for a free slot, in the order they will be started.
+    pub queued: Vec<QueuedLaunchInfo>,
+    /// Number of launches currently in their cold-start phase.
+    pub in_flight: usize,
+    /// Maximum number of simultaneous cold launches.
+    pub max_concurrent: usize,
+}
+
+/// Result of a launch started by [`LaunchQueue::poll`].
+#[derive(Debug)]
+pub struct StartedLaunch {
+    /// Ticket returned by [`LaunchQueue::enqueue`].
+    pub ticket: LaunchTicket,
+    /// PIDs of the started processes, or the launch error.
+    pub result: ProcessResult<Vec<Pid>>,
+}
+
+/// Limits the number of simultaneous cold launches.
+#[derive(Debug)]
+pub struct LaunchQueue<P: ProcessManager> {
+    manager: P,
+    max_concurrent: usize,
+    warm_up: Duration,
+    queued: Vec<QueuedLaunch>,
+    /// Start times of launches in their cold-start phase, by PID.
+    in_flight: HashMap<Pid, Instant>,
+    next_ticket: LaunchTicket,
+}
+
+impl<P: ProcessManager> LaunchQueue<P> {
+    /// Creates a queue allowing `max_concurrent` (at least one) cold launches at a time,
+    /// each considered cold for at most `warm_up`.
+    pub fn new(manager: P, max_concurrent: usize, warm_up: Duration) -> Self {
+        Self {
+            manager,
+            max_concurrent: max_concurrent.max(1),
+            warm_up,
+            queued: Vec::new(),
+            in_flight: HashMap::new(),
+            next_ticket: 1,
+        }
+    }
+
+    /// The process manager used to launch applications.
+    pub fn manager(&self) -> &P {
+        &self.manager
+    }
+
+    /// Queues a launch; it is started by a later [`LaunchQueue::poll`].
+    pub fn enqueue(
+        &mut self,
+        app: Application,
+        options: LaunchOptions,
+        priority: LaunchPriority,
+        now: Instant,
+    ) -> LaunchTicket {
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        println!("LaunchQueue: Queued '{}' ({:?}) as ticket {}.", app.name, priority, ticket);
+        self.queued.push(QueuedLaunch { ticket, app, options, priority, queued_at: now });
+        // Stable sort keeps FIFO order within a priority.
+        self.queued.sort_by_key(|launch| launch.priority);
+        ticket
+    }
+
+    /// Removes a launch that has not been started yet.
+    ///
+    /// # Returns
+    /// `false` if the ticket is unknown or the launch has already started.
+    pub fn cancel(&mut self, ticket: LaunchTicket) -> bool {
+        let count = self.queued.len();
+        self.queued.retain(|launch| launch.ticket != ticket);
+        self.queued.len() != count
+    }
+
+    /// Marks the launch of `pid` as complete (e.g. its first window appeared), freeing its slot.
+    pub fn launch_completed(&mut self, pid: Pid) {
+        self.in_flight.remove(&pid);
+    }
+
+    /// Frees the slot of a process that exited during its cold start.
+    pub fn handle_exit(&mut self, event: &ProcessExitEvent) {
+        self.in_flight.remove(&event.pid);
+    }
+
+    /// Releases slots whose warm-up time has passed and starts queued launches while
+    /// slots are free.
+    ///
+    /// # Returns
+    /// The launches started in this call with their results.
+    pub fn poll(&mut self, now: Instant) -> Vec<StartedLaunch> {
+        let warm_up = self.warm_up;
+        self.in_flight.retain(|_, started| now.saturating_duration_since(*started) < warm_up);
+
+        let mut started = Vec::new();
+        while self.in_flight.len() < self.max_concurrent && !self.queued.is_empty() {
+            let launch = self.queued.remove(0);
+            let result = self.manager.launch_application_with_options(&launch.app, &launch.options);
+            if let Ok(pids) = &result {
+                self.in_flight.extend(pids.iter().map(|&pid| (pid, now)));
+            }
+            started.push(StartedLaunch { ticket: launch.ticket, result });
+        }
+        started
+    }
+
+    /// Current queue state.
+    pub fn state(&self) -> LaunchQueueState {
+        LaunchQueueState {
+            queued: self
+                .queued
+                .iter()
+                .map(|launch| QueuedLaunchInfo {
+                    ticket: launch.ticket,
+                    app_name: launch.app.name.clone(),
+                    priority: launch.priority,
+                    queued_at: launch.queued_at,
+                })
+                .collect(),
+            in_flight: self.in_flight.len(),
+            max_concurrent: self.max_concurrent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process_manager::Signal;
+    use std::sync::Mutex;
+
+    /// Process manager recording launched application names and handing out PIDs.
+    #[derive(Debug, Default)]
+    struct RecordingProcessManager {
+        launched: Mutex<Vec<String>>,
+    }
+
+    impl ProcessManager for RecordingProcessManager {
+        fn launch_application_with_options(&self, app: &Application, _options: &LaunchOptions) -> ProcessResult<Vec<Pid>> {
+            let mut launched = self.launched.lock().unwrap();
+            launched.push(app.name.clone());
+            Ok(vec![launched.len() as Pid])
+        }
+
+        fn terminate_process(&self, _pid: Pid) -> ProcessResult<()> {
+            Ok(())
+        }
+
+        fn kill_process(&self, _pid: Pid) -> ProcessResult<()> {
+            Ok(())
+        }
+
+        fn send_signal(&self, _pid: Pid, _signal: Signal) -> ProcessResult<()> {
+            Ok(())
+        }
+    }
+
+    fn app(name: &str) -> Application {
+        Application::new_desktop(name.to_string(), format!("/usr/bin/{}", name), None)
+    }
+
+    fn launched(queue: &LaunchQueue<RecordingProcessManager>) -> Vec<String> {
+        queue.manager().launched.lock().unwrap().clone()
+    }
+
+    #[test]
+    fn test_concurrency_limit_and_priority() {
+        let start = Instant::now();
+        let mut queue = LaunchQueue::new(RecordingProcessManager::default(), 1, Duration::from_secs(5));
+        queue.enqueue(app("tray"), LaunchOptions::default(), LaunchPriority::Autostart, start);
+        queue.enqueue(app("sync"), LaunchOptions::default(), LaunchPriority::Autostart, start);
+        queue.enqueue(app("terminal"), LaunchOptions::default(), LaunchPriority::Interactive, start);
+
+        assert_eq!(queue.poll(start).len(), 1);
+        assert_eq!(launched(&queue), vec!["terminal"]);
+        let state = queue.state();
+        assert_eq!(state.in_flight, 1);
+        assert_eq!(state.queued.iter().map(|q| q.app_name.as_str()).collect::<Vec<_>>(), vec!["tray", "sync"]);
+
+        assert!(queue.poll(start + Duration::from_secs(1)).is_empty(), "Slot still taken");
+        queue.launch_completed(1);
+        assert_eq!(queue.poll(start + Duration::from_secs(1)).len(), 1);
+        assert_eq!(launched(&queue), vec!["terminal", "tray"]);
+
+        // The warm-up time frees the slot even without completion.
+        assert_eq!(queue.poll(start + Duration::from_secs(6)).len(), 1);
+        assert_eq!(launched(&queue), vec!["terminal", "tray", "sync"]);
+        assert!(queue.state().queued.is_empty());
+    }
+
+    #[test]
+    fn test_exit_frees_slot_and_cancel() {
+        let start = Instant::now();
+        let mut queue = LaunchQueue::new(RecordingProcessManager::default(), 1, Duration::from_secs(5));
+        let first = app("crashy");
+        queue.enqueue(first.clone(), LaunchOptions::default(), LaunchPriority::Autostart, start);
+        let cancelled = queue.enqueue(app("unwanted"), LaunchOptions::default(), LaunchPriority::Autostart, start);
+        queue.enqueue(app("next"), LaunchOptions::default(), LaunchPriority::Autostart, start);
+        queue.poll(start);
+
+        assert!(queue.cancel(cancelled));
+        queue.handle_exit(&ProcessExitEvent {
+            pid: 1,
+            app_id: first.id,
+            exit_code: Some(1),
+            signal: None,
+            runtime: Duration::from_millis(10),
+        });
+        queue.poll(start);
+        assert_eq!(launched(&queue), vec!["crashy", "next"]);
+    }
+}
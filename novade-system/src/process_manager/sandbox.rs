@@ -0,0 +1,158 @@
+// src/process_manager/sandbox.rs
+
+//! Launching Flatpak and Snap applications.
+//!
+//! Sandboxed applications are started through `flatpak run` or `snap run`, which set up
+//! the sandbox and runtime. Their desktop entries already contain such an `Exec` line;
+//! [`apply_detected_sandbox`] recognises it and turns the [`Application`] into a sandboxed
+//! one whose `executable_path`/`arguments` describe the command inside the sandbox, so the
+//! process manager can build the launch command itself (including the environment, which a
+//! Flatpak sandbox does not inherit reliably).
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use novade_domain::entities::{Application, SandboxKind};
+
+/// Directory in which snapd installs the launchers of snap applications.
+const SNAP_BIN_DIR: &str = "/snap/bin";
+
+fn file_name(executable: &str) -> &str {
+    Path::new(executable).file_name().and_then(|name| name.to_str()).unwrap_or(executable)
+}
+
+/// Recognises a `flatpak run`/`snap run` command (or a `/snap/bin` launcher).
+///
+/// # Returns
+/// The sandbox, the executable inside the sandbox and the arguments passed to it.
+pub fn detect_sandbox(executable: &str, arguments: &[String]) -> Option<(SandboxKind, String, Vec<String>)> {
+    match file_name(executable) {
+        "flatpak" if arguments.first().map(String::as_str) == Some("run") => {
+            let mut run_options = Vec::new();
+            let mut rest = arguments[1..].iter();
+            let app_id = loop {
+                let argument = rest.next()?;
+                if argument.starts_with('-') {
+                    run_options.push(argument.clone());
+                } else {
+                    break argument.clone();
+                }
+            };
+            let command = run_options
+                .iter()
+                .find_map(|option| option.strip_prefix("--command="))
+                .map_or_else(|| app_id.clone(), str::to_string);
+            Some((SandboxKind::Flatpak { app_id, run_options }, command, rest.cloned().collect()))
+        }
+        "snap" if arguments.first().map(String::as_str) == Some("run") => {
+            let name = arguments.get(1)?.clone();
+            Some((SandboxKind::Snap { name: name.clone() }, name, arguments[2..].to_vec()))
+        }
+        _ if Path::new(executable).parent() == Some(Path::new(SNAP_BIN_DIR)) => {
+            let name = file_name(executable).to_string();
+            Some((SandboxKind::Snap { name: name.clone() }, name, arguments.to_vec()))
+        }
+        _ => None,
+    }
+}
+
+/// Marks the application as sandboxed if its command is a Flatpak or Snap invocation,
+/// replacing `executable_path` and `arguments` with the command inside the sandbox.
+///
+/// # Returns
+/// `true` if a sandbox was detected.
+pub fn apply_detected_sandbox(app: &mut Application) -> bool {
+    if app.sandbox.is_some() {
+        return false;
+    }
+    let arguments = app.arguments.clone().unwrap_or_default();
+    let Some((sandbox, executable, inner_arguments)) = detect_sandbox(&app.executable_path, &arguments) else {
+        return false;
+    };
+    app.sandbox = Some(sandbox);
+    app.executable_path = executable;
+    app.arguments = (!inner_arguments.is_empty()).then_some(inner_arguments);
+    true
+}
+
+/// Builds the program and arguments that start a sandboxed application.
+///
+/// For Flatpak, `environment` is passed into the sandbox with `--env` options.
+pub fn sandbox_invocation(
+    sandbox: &SandboxKind,
+    arguments: &[String],
+    environment: &HashMap<String, String>,
+) -> (String, Vec<String>) {
+    match sandbox {
+        SandboxKind::Flatpak { app_id, run_options } => {
+            let mut invocation = vec!["run".to_string()];
+            invocation.extend(run_options.iter().cloned());
+            let mut variables: Vec<_> = environment.iter().collect();
+            variables.sort();
+            invocation.extend(variables.into_iter().map(|(key, value)| format!("--env={}={}", key, value)));
+            invocation.push(app_id.clone());
+            invocation.extend(arguments.iter().cloned());
+            ("flatpak".to_string(), invocation)
+        }
+        SandboxKind::Snap { name } => {
+            let mut invocation = vec!["run".to_string(), name.clone()];
+            invocation.extend(arguments.iter().cloned());
+            ("snap".to_string(), invocation)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_detect_flatpak() {
+        let exec = args(&["run", "--branch=stable", "--command=gimp-2.10", "org.gimp.GIMP", "%U"]);
+        let (sandbox, executable, arguments) = detect_sandbox("/usr/bin/flatpak", &exec).unwrap();
+        assert_eq!(
+            sandbox,
+            SandboxKind::Flatpak {
+                app_id: "org.gimp.GIMP".to_string(),
+                run_options: args(&["--branch=stable", "--command=gimp-2.10"]),
+            }
+        );
+        assert_eq!(executable, "gimp-2.10");
+        assert_eq!(arguments, args(&["%U"]));
+        assert!(detect_sandbox("flatpak", &args(&["install", "x"])).is_none());
+    }
+
+    #[test]
+    fn test_detect_snap() {
+        let (sandbox, executable, arguments) = detect_sandbox("/snap/bin/spotify", &args(&["%U"])).unwrap();
+        assert_eq!(sandbox, SandboxKind::Snap { name: "spotify".to_string() });
+        assert_eq!((executable.as_str(), arguments), ("spotify", args(&["%U"])));
+
+        let (sandbox, _, arguments) = detect_sandbox("snap", &args(&["run", "code", "--new-window"])).unwrap();
+        assert_eq!(sandbox, SandboxKind::Snap { name: "code".to_string() });
+        assert_eq!(arguments, args(&["--new-window"]));
+        assert!(detect_sandbox("/usr/bin/gedit", &[]).is_none());
+    }
+
+    #[test]
+    fn test_apply_detected_sandbox_and_invocation() {
+        let mut app = Application::new_desktop("GIMP".to_string(), "flatpak".to_string(), None);
+        app.arguments = Some(args(&["run", "org.gimp.GIMP", "%U"]));
+        assert!(apply_detected_sandbox(&mut app));
+        assert!(!apply_detected_sandbox(&mut app), "Already sandboxed");
+        assert_eq!(app.executable_path, "org.gimp.GIMP");
+
+        let environment = HashMap::from([("GTK_THEME".to_string(), "Adwaita:dark".to_string())]);
+        let (program, arguments) =
+            sandbox_invocation(app.sandbox.as_ref().unwrap(), &args(&["/tmp/a.png"]), &environment);
+        assert_eq!(program, "flatpak");
+        assert_eq!(arguments, args(&["run", "--env=GTK_THEME=Adwaita:dark", "org.gimp.GIMP", "/tmp/a.png"]));
+
+        let snap = SandboxKind::Snap { name: "spotify".to_string() };
+        assert_eq!(sandbox_invocation(&snap, &[], &environment), ("snap".to_string(), args(&["run", "spotify"])));
+    }
+}
@@ -0,0 +1,146 @@
+// src/process_manager/stats.rs
+
+//! Resource usage of processes, read from `/proc` on Linux.
+
+use std::time::{Duration, Instant};
+
+use novade_core::types::NovaId;
+
+use super::{Pid, ProcessError, ProcessResult};
+
+/// Resource usage of a single process.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessStats {
+    /// The process ID.
+    pub pid: Pid,
+    /// CPU usage in percent of one core since the previous query of this process, or averaged
+    /// over its lifetime on the first query. Can exceed 100 for multi-threaded processes.
+    pub cpu_percent: f32,
+    /// Resident set size in bytes.
+    pub rss_bytes: u64,
+    /// Time since the process was started.
+    pub uptime: Duration,
+}
+
+/// A process managed by a process manager, as listed by
+/// [`ProcessManager::list_managed_processes`](super::ProcessManager::list_managed_processes).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagedProcessInfo {
+    /// The process ID.
+    pub pid: Pid,
+    /// ID of the application the process was launched for.
+    pub app_id: NovaId,
+    /// Current resource usage, `None` if it could not be read.
+    pub stats: Option<ProcessStats>,
+}
+
+/// CPU time consumed by a process at a point in time, used to compute CPU usage deltas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CpuSample {
+    pub cpu_seconds: f64,
+    pub taken_at: Instant,
+}
+
+/// The fields of `/proc/<pid>/stat` needed for [`ProcessStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ProcStat {
+    /// utime + stime in clock ticks.
+    pub cpu_ticks: u64,
+    /// Start time after boot in clock ticks.
+    pub start_ticks: u64,
+    /// Resident set size in pages.
+    pub rss_pages: u64,
+}
+
+/// Parses the content of `/proc/<pid>/stat`.
+///
+/// The command name (second field) may contain spaces and parentheses, so the remaining
+/// fields are located after the last `)`.
+pub(crate) fn parse_proc_stat(content: &str) -> Option<ProcStat> {
+    let rest = &content[content.rfind(')')? + 1..];
+    // Field 3 (state) is the first one after the command name.
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let field = |number: usize| -> Option<u64> { fields.get(number - 3)?.parse().ok() };
+    Some(ProcStat {
+        cpu_ticks: field(14)? + field(15)?,
+        start_ticks: field(22)?,
+        rss_pages: field(24)?,
+    })
+}
+
+/// Reads the resource usage of a process.
+///
+/// `previous` is the CPU sample of the last query; the returned sample should be passed
+/// to the next query.
+#[cfg(target_os = "linux")]
+pub(crate) fn read_process_stats(
+    pid: Pid,
+    previous: Option<CpuSample>,
+    now: Instant,
+) -> ProcessResult<(ProcessStats, CpuSample)> {
+    let content = std::fs::read_to_string(format!("/proc/{}/stat", pid)).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => ProcessError::NotFound(pid),
+        _ => ProcessError::Unsupported(format!("Failed to read stats of process {}: {}", pid, e)),
+    })?;
+    let stat = parse_proc_stat(&content)
+        .ok_or_else(|| ProcessError::Unsupported(format!("Malformed /proc/{}/stat", pid)))?;
+    let system_uptime = std::fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|uptime| uptime.split_whitespace().next()?.parse::<f64>().ok())
+        .ok_or_else(|| ProcessError::Unsupported("Failed to read /proc/uptime".to_string()))?;
+
+    // SAFETY: sysconf has no memory-safety preconditions.
+    let (ticks_per_second, page_size) = unsafe { (libc::sysconf(libc::_SC_CLK_TCK), libc::sysconf(libc::_SC_PAGESIZE)) };
+    let ticks_per_second = ticks_per_second.max(1) as f64;
+    let uptime_seconds = (system_uptime - stat.start_ticks as f64 / ticks_per_second).max(0.0);
+    let cpu_seconds = stat.cpu_ticks as f64 / ticks_per_second;
+
+    let (cpu_delta, wall_delta) = match previous {
+        Some(sample) => (cpu_seconds - sample.cpu_seconds, now.saturating_duration_since(sample.taken_at).as_secs_f64()),
+        None => (cpu_seconds, uptime_seconds),
+    };
+    let cpu_percent = if wall_delta > 0.0 { (cpu_delta.max(0.0) / wall_delta * 100.0) as f32 } else { 0.0 };
+
+    let stats = ProcessStats {
+        pid,
+        cpu_percent,
+        rss_bytes: stat.rss_pages * page_size.max(0) as u64,
+        uptime: Duration::from_secs_f64(uptime_seconds),
+    };
+    Ok((stats, CpuSample { cpu_seconds, taken_at: now }))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn read_process_stats(
+    _pid: Pid,
+    _previous: Option<CpuSample>,
+    _now: Instant,
+) -> ProcessResult<(ProcessStats, CpuSample)> {
+    Err(ProcessError::Unsupported("Process statistics are only available on Linux".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_stat_with_spaces_in_name() {
+        let content = "1234 (Web Content (x)) S 1 1234 1234 0 -1 4194560 1000 0 0 0 250 50 0 0 20 0 \
+                       12 0 98765 123456789 4321 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 3 0 0 0 0 0";
+        assert_eq!(
+            parse_proc_stat(content),
+            Some(ProcStat { cpu_ticks: 300, start_ticks: 98765, rss_pages: 4321 })
+        );
+        assert_eq!(parse_proc_stat("1234 (truncated) S 1"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_own_process_stats() {
+        let (stats, sample) = read_process_stats(std::process::id(), None, Instant::now()).unwrap();
+        assert!(stats.rss_bytes > 0);
+        assert!(stats.cpu_percent >= 0.0);
+        assert!(sample.cpu_seconds >= 0.0);
+        assert!(matches!(read_process_stats(u32::MAX, None, Instant::now()), Err(ProcessError::NotFound(_))));
+    }
+}
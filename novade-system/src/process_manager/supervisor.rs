@@ -0,0 +1,370 @@
+// src/process_manager/supervisor.rs
+
+//! Supervision of background services.
+//!
+//! The [`Supervisor`] launches [`ApplicationType::BackgroundService`] applications through a
+//! [`ProcessManager`] and restarts them according to their [`RestartPolicy`] when they exit.
+//! It does not watch processes itself: the owner forwards [`ProcessExitEvent`]s (e.g. from
+//! [`DefaultProcessManager::subscribe_exits`](super::DefaultProcessManager::subscribe_exits))
+//! to [`Supervisor::handle_exit`] and calls [`Supervisor::poll`] regularly to carry out
+//! restarts that are due.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use novade_core::types::NovaId;
+use novade_domain::entities::{Application, ApplicationType};
+
+use super::{Pid, ProcessError, ProcessExitEvent, ProcessManager, ProcessResult};
+
+/// A service that ran at least this long before exiting counts as having started
+/// successfully, which resets its retry counter.
+const STABLE_RUNTIME: Duration = Duration::from_secs(30);
+
+/// When a supervised service is restarted after it exits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Restart immediately after every exit, including successful ones. A restart that fails
+    /// to launch stops the service.
+    Always,
+    /// Restart immediately if the service exited with a non-zero code or was killed by a signal.
+    /// A restart that fails to launch stops the service.
+    OnFailure,
+    /// Restart on failure after a delay that doubles with every consecutive failure, starting
+    /// at `initial_delay` and capped at `max_delay`. After `max_retries` consecutive restarts
+    /// the supervisor gives up.
+    Backoff { initial_delay: Duration, max_delay: Duration, max_retries: u32 },
+}
+
+impl RestartPolicy {
+    /// Delay before the next restart, or `None` if the service should not be restarted.
+    ///
+    /// `retries` is the number of consecutive restarts already performed.
+    fn restart_delay(&self, event: &ProcessExitEvent, retries: u32) -> Option<Duration> {
+        match *self {
+            RestartPolicy::Always => Some(Duration::ZERO),
+            RestartPolicy::OnFailure => (!event.is_success()).then_some(Duration::ZERO),
+            RestartPolicy::Backoff { initial_delay, max_delay, max_retries } => {
+                if event.is_success() || retries >= max_retries {
+                    return None;
+                }
+                let factor = 2u32.saturating_pow(retries);
+                Some(initial_delay.saturating_mul(factor).min(max_delay))
+            }
+        }
+    }
+}
+
+/// Current state of a supervised service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceState {
+    /// The service is running with this PID.
+    Running(Pid),
+    /// The service exited and will be restarted at the given time.
+    RestartPending(Instant),
+    /// The service exited and will not be restarted (policy or retry limit).
+    Stopped,
+}
+
+#[derive(Debug)]
+struct SupervisedService {
+    app: Application,
+    policy: RestartPolicy,
+    state: ServiceState,
+    retries: u32,
+}
+
+/// Keeps background services running according to their restart policies.
+#[derive(Debug)]
+pub struct Supervisor<P: ProcessManager> {
+    manager: P,
+    services: HashMap<NovaId, SupervisedService>,
+}
+
+impl<P: ProcessManager> Supervisor<P> {
+    /// Creates a supervisor launching services through `manager`.
+    pub fn new(manager: P) -> Self {
+        Self { manager, services: HashMap::new() }
+    }
+
+    /// The process manager used to launch services.
+    pub fn manager(&self) -> &P {
+        &self.manager
+    }
+
+    /// Launches a background service and places it under supervision.
+    ///
+    /// # Errors
+    /// `ProcessError::Unsupported` if the application is not a
+    /// [`ApplicationType::BackgroundService`] or is already supervised; launch errors otherwise.
+    pub fn supervise(&mut self, app: Application, policy: RestartPolicy) -> ProcessResult<Pid> {
+        if app.app_type != ApplicationType::BackgroundService {
+            return Err(ProcessError::Unsupported(format!(
+                "'{}' is not a background service and cannot be supervised",
+                app.name
+            )));
+        }
+        if self.services.contains_key(&app.id) {
+            return Err(ProcessError::Unsupported(format!("'{}' is already supervised", app.name)));
+        }
+        let pid = self.manager.launch_application(&app)?;
+        println!("Supervisor: Supervising '{}' (PID {}) with policy {:?}.", app.name, pid, policy);
+        self.services
+            .insert(app.id.clone(), SupervisedService { app, policy, state: ServiceState::Running(pid), retries: 0 });
+        Ok(pid)
+    }
+
+    /// Removes a service from supervision and terminates it if it is running.
+    ///
+    /// # Returns
+    /// `false` if the service was not supervised.
+    pub fn stop(&mut self, app_id: &NovaId) -> ProcessResult<bool> {
+        let Some(service) = self.services.remove(app_id) else {
+            return Ok(false);
+        };
+        if let ServiceState::Running(pid) = service.state {
+            match self.manager.terminate_process(pid) {
+                Ok(()) | Err(ProcessError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        println!("Supervisor: Stopped supervising '{}'.", service.app.name);
+        Ok(true)
+    }
+
+    /// Current state of a supervised service.
+    pub fn service_state(&self, app_id: &NovaId) -> Option<ServiceState> {
+        self.services.get(app_id).map(|s| s.state)
+    }
+
+    /// Handles the exit of a process and schedules a restart if the policy asks for one.
+    ///
+    /// Exits of processes that are not supervised are ignored.
+    ///
+    /// # Returns
+    /// `true` if a restart was scheduled.
+    pub fn handle_exit(&mut self, event: &ProcessExitEvent, now: Instant) -> bool {
+        let Some(service) = self.services.get_mut(&event.app_id) else {
+            return false;
+        };
+        if service.state != ServiceState::Running(event.pid) {
+            return false;
+        }
+        if event.runtime >= STABLE_RUNTIME {
+            service.retries = 0;
+        }
+        match service.policy.restart_delay(event, service.retries) {
+            Some(delay) => {
+                println!(
+                    "Supervisor: '{}' exited ({:?}), restarting in {:?}.",
+                    service.app.name, event.exit_code, delay
+                );
+                service.state = ServiceState::RestartPending(now + delay);
+                true
+            }
+            None => {
+                println!("Supervisor: '{}' exited ({:?}), not restarting.", service.app.name, event.exit_code);
+                service.state = ServiceState::Stopped;
+                false
+            }
+        }
+    }
+
+    /// Restarts all services whose restart is due.
+    ///
+    /// Under [`RestartPolicy::Backoff`] a failed launch is treated like a failed exit and
+    /// scheduled again; under the immediate policies the service is stopped.
+    ///
+    /// # Returns
+    /// The IDs and new PIDs of the services that were restarted.
+    pub fn poll(&mut self, now: Instant) -> Vec<(NovaId, Pid)> {
+        let mut restarted = Vec::new();
+        for (app_id, service) in &mut self.services {
+            let ServiceState::RestartPending(at) = service.state else {
+                continue;
+            };
+            if now < at {
+                continue;
+            }
+            service.retries += 1;
+            match self.manager.launch_application(&service.app) {
+                Ok(pid) => {
+                    service.state = ServiceState::Running(pid);
+                    restarted.push((app_id.clone(), pid));
+                }
+                Err(e) => {
+                    eprintln!("Supervisor: Failed to restart '{}': {:?}", service.app.name, e);
+                    // Only a backoff waits between attempts and gives up eventually; an
+                    // immediate retry would fail again on every poll (e.g. a missing executable).
+                    let delay = match service.policy {
+                        RestartPolicy::Backoff { .. } => {
+                            let failure = ProcessExitEvent {
+                                pid: 0,
+                                app_id: app_id.clone(),
+                                exit_code: None,
+                                signal: None,
+                                runtime: Duration::ZERO,
+                            };
+                            service.policy.restart_delay(&failure, service.retries)
+                        }
+                        RestartPolicy::Always | RestartPolicy::OnFailure => None,
+                    };
+                    service.state = match delay {
+                        Some(delay) => ServiceState::RestartPending(now + delay),
+                        None => ServiceState::Stopped,
+                    };
+                }
+            }
+        }
+        restarted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process_manager::{LaunchOptions, Signal};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    /// Process manager handing out increasing PIDs without starting anything.
+    #[derive(Debug, Default)]
+    struct FakeProcessManager {
+        launched: Mutex<Vec<Pid>>,
+        terminated: Mutex<Vec<Pid>>,
+        /// Makes launches fail as if the executable did not exist.
+        executable_missing: AtomicBool,
+    }
+
+    impl ProcessManager for FakeProcessManager {
+        fn launch_application_with_options(&self, app: &Application, _options: &LaunchOptions) -> ProcessResult<Vec<Pid>> {
+            if self.executable_missing.load(Ordering::SeqCst) {
+                return Err(ProcessError::SpawnFailed {
+                    program: app.executable_path.clone(),
+                    message: "No such file or directory".to_string(),
+                });
+            }
+            let mut launched = self.launched.lock().unwrap();
+            let pid = 100 + launched.len() as Pid;
+            launched.push(pid);
+            Ok(vec![pid])
+        }
+
+        fn terminate_process(&self, pid: Pid) -> ProcessResult<()> {
+            self.terminated.lock().unwrap().push(pid);
+            Ok(())
+        }
+
+        fn kill_process(&self, pid: Pid) -> ProcessResult<()> {
+            self.terminate_process(pid)
+        }
+
+        fn send_signal(&self, _pid: Pid, _signal: Signal) -> ProcessResult<()> {
+            Ok(())
+        }
+    }
+
+    fn service() -> Application {
+        let mut app = Application::new_desktop("indexer".to_string(), "/usr/bin/indexer".to_string(), None);
+        app.app_type = ApplicationType::BackgroundService;
+        app
+    }
+
+    fn exit(app: &Application, pid: Pid, exit_code: i32) -> ProcessExitEvent {
+        ProcessExitEvent {
+            pid,
+            app_id: app.id.clone(),
+            exit_code: Some(exit_code),
+            signal: None,
+            runtime: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn test_only_background_services_can_be_supervised() {
+        let mut supervisor = Supervisor::new(FakeProcessManager::default());
+        let desktop_app = Application::new_desktop("editor".to_string(), "/usr/bin/editor".to_string(), None);
+        assert!(matches!(
+            supervisor.supervise(desktop_app, RestartPolicy::Always),
+            Err(ProcessError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_on_failure_restarts_only_failed_exits() {
+        let now = Instant::now();
+        let mut supervisor = Supervisor::new(FakeProcessManager::default());
+        let app = service();
+        let pid = supervisor.supervise(app.clone(), RestartPolicy::OnFailure).unwrap();
+
+        assert!(supervisor.handle_exit(&exit(&app, pid, 1), now));
+        let restarted = supervisor.poll(now);
+        assert_eq!(restarted, vec![(app.id.clone(), 101)]);
+        assert_eq!(supervisor.service_state(&app.id), Some(ServiceState::Running(101)));
+
+        assert!(!supervisor.handle_exit(&exit(&app, 101, 0), now));
+        assert_eq!(supervisor.service_state(&app.id), Some(ServiceState::Stopped));
+    }
+
+    #[test]
+    fn test_always_restarts_successful_exits() {
+        let now = Instant::now();
+        let mut supervisor = Supervisor::new(FakeProcessManager::default());
+        let app = service();
+        let pid = supervisor.supervise(app.clone(), RestartPolicy::Always).unwrap();
+
+        assert!(supervisor.handle_exit(&exit(&app, pid, 0), now));
+        assert_eq!(supervisor.poll(now).len(), 1);
+    }
+
+    #[test]
+    fn test_always_stops_when_restart_fails_to_launch() {
+        let now = Instant::now();
+        let mut supervisor = Supervisor::new(FakeProcessManager::default());
+        let app = service();
+        let pid = supervisor.supervise(app.clone(), RestartPolicy::Always).unwrap();
+
+        supervisor.manager().executable_missing.store(true, Ordering::SeqCst);
+        assert!(supervisor.handle_exit(&exit(&app, pid, 0), now));
+        assert!(supervisor.poll(now).is_empty());
+        assert_eq!(supervisor.service_state(&app.id), Some(ServiceState::Stopped));
+        assert!(supervisor.poll(now).is_empty());
+    }
+
+    #[test]
+    fn test_backoff_delays_and_gives_up() {
+        let start = Instant::now();
+        let policy = RestartPolicy::Backoff {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(3),
+            max_retries: 3,
+        };
+        let mut supervisor = Supervisor::new(FakeProcessManager::default());
+        let app = service();
+        let mut pid = supervisor.supervise(app.clone(), policy).unwrap();
+
+        for expected_delay in [1, 2, 3] {
+            assert!(supervisor.handle_exit(&exit(&app, pid, 1), start));
+            let due = start + Duration::from_secs(expected_delay);
+            assert_eq!(supervisor.service_state(&app.id), Some(ServiceState::RestartPending(due)));
+            assert!(supervisor.poll(due - Duration::from_millis(1)).is_empty());
+            pid = supervisor.poll(due)[0].1;
+        }
+
+        assert!(!supervisor.handle_exit(&exit(&app, pid, 1), start), "Retry limit reached");
+        assert_eq!(supervisor.service_state(&app.id), Some(ServiceState::Stopped));
+    }
+
+    #[test]
+    fn test_stop_terminates_and_ignores_later_exit() {
+        let now = Instant::now();
+        let mut supervisor = Supervisor::new(FakeProcessManager::default());
+        let app = service();
+        let pid = supervisor.supervise(app.clone(), RestartPolicy::Always).unwrap();
+
+        assert!(supervisor.stop(&app.id).unwrap());
+        assert_eq!(*supervisor.manager().terminated.lock().unwrap(), vec![pid]);
+        assert!(!supervisor.handle_exit(&exit(&app, pid, 0), now));
+        assert_eq!(supervisor.service_state(&app.id), None);
+    }
+}
@@ -0,0 +1,200 @@
+// src/process_manager/systemd.rs
+
+//! Process manager placing every application in its own transient systemd scope.
+//!
+//! Applications are spawned like with the [`DefaultProcessManager`] and then moved into a
+//! scope unit (`app-novade-<name>-<pid>.scope`) via `StartTransientUnit` on the user's
+//! systemd instance. Each application thereby gets its own cgroup, which gives resource
+//! accounting per application and allows tearing down an application together with all
+//! processes it forked.
+
+use std::collections::HashMap;
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::{OwnedObjectPath, Value};
+
+use novade_core::signal::Signal as EventSignal;
+use novade_core::types::NovaId;
+use novade_domain::entities::Application;
+
+use super::{
+    DefaultProcessManager, LaunchOptions, ManagedProcessInfo, Pid, ProcessError, ProcessExitEvent, ProcessLaunchEvent,
+    ProcessManager, ProcessResult, ProcessStats, RunningInstance, Signal,
+};
+
+const SYSTEMD_DESTINATION: &str = "org.freedesktop.systemd1";
+const SYSTEMD_PATH: &str = "/org/freedesktop/systemd1";
+const SYSTEMD_MANAGER_INTERFACE: &str = "org.freedesktop.systemd1.Manager";
+
+/// Slice the application scopes are placed in.
+const APP_SLICE: &str = "app.slice";
+
+/// Builds the name of the scope unit for an application process.
+///
+/// Characters not allowed in unit names are escaped as `\xNN`, like `systemd-escape` does.
+pub fn scope_unit_name(app_name: &str, pid: Pid) -> String {
+    let mut escaped = String::with_capacity(app_name.len());
+    for byte in app_name.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b':' | b'_' | b'.' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    format!("app-novade-{}-{}.scope", escaped, pid)
+}
+
+fn communication_error(pid: Pid, context: &str, error: zbus::Error) -> ProcessError {
+    ProcessError::SignalFailed { pid, message: format!("{}: {}", context, error) }
+}
+
+/// [`ProcessManager`] that launches applications in transient systemd scopes.
+///
+/// Signals are delivered to all processes of an application's scope. If a scope could not
+/// be created, the process keeps running outside of it and is handled like by the
+/// [`DefaultProcessManager`].
+#[derive(Debug, Clone)]
+pub struct SystemdProcessManager {
+    processes: DefaultProcessManager,
+    systemd: Proxy<'static>,
+    scopes: Arc<Mutex<HashMap<Pid, String>>>,
+}
+
+impl SystemdProcessManager {
+    /// Connects to the systemd user instance on the session bus.
+    pub fn new() -> ProcessResult<Self> {
+        let unsupported = |context: &str, error: zbus::Error| {
+            ProcessError::Unsupported(format!("{}: {}", context, error))
+        };
+        let connection = Connection::session().map_err(|e| unsupported("Failed to connect to session bus", e))?;
+        let systemd = Proxy::new_owned(connection, SYSTEMD_DESTINATION, SYSTEMD_PATH, SYSTEMD_MANAGER_INTERFACE)
+            .map_err(|e| unsupported("Failed to create systemd manager proxy", e))?;
+        Ok(Self { processes: DefaultProcessManager::new(), systemd, scopes: Arc::new(Mutex::new(HashMap::new())) })
+    }
+
+    /// The underlying process manager that spawns and reaps the processes.
+    pub fn processes(&self) -> &DefaultProcessManager {
+        &self.processes
+    }
+
+    /// See [`DefaultProcessManager::exits`].
+    pub fn exits(&self) -> &EventSignal<ProcessExitEvent> {
+        self.processes.exits()
+    }
+
+    /// See [`DefaultProcessManager::launches`].
+    pub fn launches(&self) -> &EventSignal<ProcessLaunchEvent> {
+        self.processes.launches()
+    }
+
+    /// See [`DefaultProcessManager::subscribe_exits`].
+    pub fn subscribe_exits(&self) -> Receiver<ProcessExitEvent> {
+        self.processes.subscribe_exits()
+    }
+
+    /// See [`DefaultProcessManager::subscribe_launches`].
+    pub fn subscribe_launches(&self) -> Receiver<ProcessLaunchEvent> {
+        self.processes.subscribe_launches()
+    }
+
+    /// Name of the scope unit the process was placed in, if any.
+    pub fn scope_of(&self, pid: Pid) -> Option<String> {
+        self.scopes.lock().unwrap().get(&pid).cloned()
+    }
+
+    /// Stops the application's scope, which terminates every process in it.
+    ///
+    /// Falls back to [`ProcessManager::terminate_process`] for processes without a scope.
+    pub fn stop_scope(&self, pid: Pid) -> ProcessResult<()> {
+        let Some(unit) = self.scopes.lock().unwrap().remove(&pid) else {
+            return self.processes.terminate_process(pid);
+        };
+        self.systemd
+            .call::<_, _, OwnedObjectPath>("StopUnit", &(unit.as_str(), "fail"))
+            .map_err(|e| communication_error(pid, "Failed to stop scope", e))?;
+        println!("SystemdProcessManager: Stopped scope {}.", unit);
+        Ok(())
+    }
+
+    fn start_scope(&self, app: &Application, pid: Pid) -> Result<String, zbus::Error> {
+        let unit = scope_unit_name(&app.name, pid);
+        let description = format!("NovaDE application {}", app.display_name.as_deref().unwrap_or(&app.name));
+        let properties: Vec<(&str, Value<'_>)> = vec![
+            ("Description", Value::from(description)),
+            ("PIDs", Value::from(vec![pid])),
+            ("Slice", Value::from(APP_SLICE)),
+            ("CollectMode", Value::from("inactive-or-failed")),
+        ];
+        let auxiliary: Vec<(&str, Vec<(&str, Value<'_>)>)> = Vec::new();
+        self.systemd
+            .call::<_, _, OwnedObjectPath>("StartTransientUnit", &(unit.as_str(), "fail", properties, auxiliary))?;
+        Ok(unit)
+    }
+
+    fn live_scope(&self, pid: Pid) -> Option<String> {
+        let mut scopes = self.scopes.lock().unwrap();
+        // Systemd collects the scope once its processes are gone.
+        scopes.retain(|&scope_pid, _| self.processes.is_managed(scope_pid));
+        scopes.get(&pid).cloned()
+    }
+}
+
+impl ProcessManager for SystemdProcessManager {
+    fn launch_application_with_options(&self, app: &Application, options: &LaunchOptions) -> ProcessResult<Vec<Pid>> {
+        let pids = self.processes.launch_application_with_options(app, options)?;
+        for &pid in &pids {
+            match self.start_scope(app, pid) {
+                Ok(unit) => {
+                    println!("SystemdProcessManager: Placed PID {} in {}.", pid, unit);
+                    self.scopes.lock().unwrap().insert(pid, unit);
+                }
+                Err(e) => eprintln!("SystemdProcessManager: Failed to create scope for PID {}: {}", pid, e),
+            }
+        }
+        Ok(pids)
+    }
+
+    fn running_instances(&self, app_id: &NovaId) -> Vec<RunningInstance> {
+        self.processes.running_instances(app_id)
+    }
+
+    fn process_stats(&self, pid: Pid) -> ProcessResult<ProcessStats> {
+        self.processes.process_stats(pid)
+    }
+
+    fn list_managed_processes(&self) -> Vec<ManagedProcessInfo> {
+        self.processes.list_managed_processes()
+    }
+
+    fn terminate_process(&self, pid: Pid) -> ProcessResult<()> {
+        self.send_signal(pid, Signal::Terminate)
+    }
+
+    fn kill_process(&self, pid: Pid) -> ProcessResult<()> {
+        self.send_signal(pid, Signal::Kill)
+    }
+
+    fn send_signal(&self, pid: Pid, signal: Signal) -> ProcessResult<()> {
+        let Some(unit) = self.live_scope(pid) else {
+            return self.processes.send_signal(pid, signal);
+        };
+        self.systemd
+            .call::<_, _, ()>("KillUnit", &(unit.as_str(), "all", signal.as_raw()))
+            .map_err(|e| communication_error(pid, "Failed to signal scope", e))?;
+        println!("SystemdProcessManager: Sent {:?} to {}.", signal, unit);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_unit_name_escapes_invalid_characters() {
+        assert_eq!(scope_unit_name("firefox", 42), "app-novade-firefox-42.scope");
+        assert_eq!(scope_unit_name("org.gnome.Text Editor", 7), "app-novade-org.gnome.Text\\x20Editor-7.scope");
+        assert_eq!(scope_unit_name("a-b/c", 1), "app-novade-a\\x2db\\x2fc-1.scope");
+    }
+}
@@ -0,0 +1,250 @@
+// src/repositories/autostart.rs
+
+//! Autostart entries from the XDG autostart directories.
+//!
+//! Reads the desktop entries the [`AutostartScheduler`](crate::process_manager::AutostartScheduler)
+//! starts, with the same precedence: an entry in the user's directory overrides system
+//! entries with the same file name. Changes are only ever written to the user's directory,
+//! as a copy of the system entry (or, for applications not yet in the autostart, of their
+//! desktop entry) with the autostart keys set. Removing an entry that also exists in a
+//! system directory leaves a copy with `Hidden=true`, as the specification requires.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use novade_core::CoreError;
+use novade_domain::entities::{Application, AutostartEntry};
+use novade_domain::repositories::AutostartRepository;
+use novade_domain::{DomainError, DomainResult};
+
+use crate::process_manager::autostart::{autostart_dirs, autostart_settings, condition_value, parse_desktop_entry};
+use crate::repositories::desktop_entries::application_dirs;
+
+fn io_error(path: &Path, error: std::io::Error) -> DomainError {
+    DomainError::RepositoryError(CoreError::io(path.display(), error))
+}
+
+/// Sets `key` in the `[Desktop Entry]` group of a desktop file, or removes it for `None`.
+/// Other lines, including comments and other groups, are kept.
+pub fn set_desktop_key(content: &str, key: &str, value: Option<&str>) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_main_group = false;
+    let mut insert_at = None;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_main_group = trimmed == "[Desktop Entry]";
+            if in_main_group {
+                insert_at = Some(lines.len() + 1);
+            }
+        } else if in_main_group {
+            if trimmed.split_once('=').is_some_and(|(existing, _)| existing.trim() == key) {
+                continue;
+            }
+            if !trimmed.is_empty() {
+                insert_at = Some(lines.len() + 1);
+            }
+        }
+        lines.push(line);
+    }
+    let line = value.map(|value| format!("{}={}", key, value));
+    let mut lines: Vec<String> = lines.into_iter().map(str::to_string).collect();
+    match (line, insert_at) {
+        (Some(line), Some(index)) => lines.insert(index, line),
+        (Some(line), None) => {
+            lines.insert(0, "[Desktop Entry]".to_string());
+            lines.insert(1, line);
+        }
+        (None, _) => {}
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+/// [`AutostartRepository`] backed by the XDG autostart directories.
+#[derive(Debug, Clone)]
+pub struct XdgAutostartRepository {
+    /// The user's autostart directory; the only one written to.
+    user_dir: PathBuf,
+    /// The system autostart directories in order of decreasing importance.
+    system_dirs: Vec<PathBuf>,
+    /// The directories with the applications' desktop entries, for applications that are
+    /// added to the autostart.
+    application_dirs: Vec<PathBuf>,
+}
+
+impl XdgAutostartRepository {
+    pub fn new(user_dir: PathBuf, system_dirs: Vec<PathBuf>, application_dirs: Vec<PathBuf>) -> Self {
+        Self { user_dir, system_dirs, application_dirs }
+    }
+
+    /// Uses the standard autostart and application directories.
+    pub fn from_environment() -> DomainResult<Self> {
+        let mut dirs = autostart_dirs().into_iter();
+        let user_dir = dirs.next().ok_or_else(|| {
+            DomainError::RepositoryError(CoreError::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "No user autostart directory available",
+            )))
+        })?;
+        Ok(Self::new(user_dir, dirs.collect(), application_dirs()))
+    }
+
+    fn user_file(&self, application_id: &str) -> PathBuf {
+        self.user_dir.join(format!("{}.desktop", application_id))
+    }
+
+    /// The most important existing autostart file of the application.
+    fn autostart_file(&self, application_id: &str) -> Option<PathBuf> {
+        std::iter::once(&self.user_dir)
+            .chain(&self.system_dirs)
+            .map(|dir| dir.join(format!("{}.desktop", application_id)))
+            .find(|path| path.is_file())
+    }
+
+    fn read_entry(path: &Path, application_id: &str) -> DomainResult<Option<AutostartEntry>> {
+        let content = std::fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+        let entry = parse_desktop_entry(&content);
+        let hidden = entry.get("Hidden").is_some_and(|value| value == "true");
+        if hidden || entry.get("Type").is_some_and(|t| t != "Application") {
+            return Ok(None);
+        }
+        Ok(Some(autostart_settings(application_id, &entry)))
+    }
+
+    fn write_user_file(&self, application_id: &str, content: &str) -> DomainResult<()> {
+        let path = self.user_file(application_id);
+        std::fs::create_dir_all(&self.user_dir).map_err(|e| io_error(&self.user_dir, e))?;
+        std::fs::write(&path, content).map_err(|e| io_error(&path, e))
+    }
+}
+
+#[async_trait]
+impl AutostartRepository for XdgAutostartRepository {
+    async fn get_by_id(&self, application_id: &str) -> DomainResult<Option<AutostartEntry>> {
+        match self.autostart_file(application_id) {
+            Some(path) => Self::read_entry(&path, application_id),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<AutostartEntry>> {
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for dir in std::iter::once(&self.user_dir).chain(&self.system_dirs) {
+            let Ok(read_dir) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            for path in read_dir.filter_map(|e| e.ok().map(|e| e.path())) {
+                let Some(application_id) = path.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".desktop")) else {
+                    continue;
+                };
+                // The first (most important) entry with a file name decides, even if it is hidden.
+                if !seen.insert(application_id.to_string()) {
+                    continue;
+                }
+                match Self::read_entry(&path, application_id) {
+                    Ok(entry) => entries.extend(entry),
+                    Err(error) => eprintln!("XdgAutostartRepository: {}", error),
+                }
+            }
+        }
+        entries.sort_by(|a, b| a.application_id.cmp(&b.application_id));
+        Ok(entries)
+    }
+
+    /// Writes the entry to the user's directory, based on the current autostart file or the
+    /// application's desktop entry.
+    async fn save(&self, entry: &AutostartEntry) -> DomainResult<()> {
+        let application_id = entry.application_id.as_str();
+        let source = self.autostart_file(application_id).or_else(|| {
+            self.application_dirs.iter().map(|dir| dir.join(format!("{}.desktop", application_id))).find(|path| path.is_file())
+        });
+        let Some(source) = source else {
+            return Err(DomainError::not_found::<Application>(application_id));
+        };
+        let mut content = std::fs::read_to_string(&source).map_err(|e| io_error(&source, e))?;
+        let delay = (!entry.delay.is_zero()).then(|| entry.delay.as_secs_f64().to_string());
+        let condition = condition_value(&entry.condition);
+        content = set_desktop_key(&content, "Hidden", None);
+        content = set_desktop_key(&content, "X-GNOME-Autostart-enabled", (!entry.enabled).then_some("false"));
+        content = set_desktop_key(&content, "X-GNOME-Autostart-Delay", delay.as_deref());
+        content = set_desktop_key(&content, "AutostartCondition", condition.as_deref());
+        self.write_user_file(application_id, &content)
+    }
+
+    async fn remove(&self, application_id: &str) -> DomainResult<()> {
+        let system_file =
+            self.system_dirs.iter().map(|dir| dir.join(format!("{}.desktop", application_id))).find(|path| path.is_file());
+        match system_file {
+            Some(system_file) => {
+                let content = std::fs::read_to_string(&system_file).map_err(|e| io_error(&system_file, e))?;
+                self.write_user_file(application_id, &set_desktop_key(&content, "Hidden", Some("true")))
+            }
+            None => {
+                let path = self.user_file(application_id);
+                match std::fs::remove_file(&path) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io_error(&path, e)),
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process_manager::autostart::{scan_autostart, DESKTOP_NAME};
+    use novade_domain::entities::AutostartCondition;
+    use std::fs;
+    use std::time::Duration;
+
+    #[test]
+    fn test_set_desktop_key() {
+        let content = "# comment\n[Desktop Entry]\nName=Applet\nHidden=false\n\n[Desktop Action New]\nExec=x\n";
+        let content = set_desktop_key(content, "Hidden", None);
+        let content = set_desktop_key(&content, "X-GNOME-Autostart-Delay", Some("5"));
+        assert_eq!(content, "# comment\n[Desktop Entry]\nName=Applet\nX-GNOME-Autostart-Delay=5\n\n[Desktop Action New]\nExec=x\n");
+        assert_eq!(set_desktop_key("", "Hidden", Some("true")), "[Desktop Entry]\nHidden=true\n");
+    }
+
+    #[tokio::test]
+    async fn test_entries_are_written_to_the_user_directory() {
+        let root = std::env::temp_dir().join(format!("novade-autostart-repository-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (user, system, applications) = (root.join("user"), root.join("system"), root.join("applications"));
+        fs::create_dir_all(&system).unwrap();
+        fs::create_dir_all(&applications).unwrap();
+        fs::write(system.join("nm-applet.desktop"), "[Desktop Entry]\nType=Application\nExec=nm-applet\n").unwrap();
+        fs::write(applications.join("chat.desktop"), "[Desktop Entry]\nType=Application\nName=Chat\nExec=chat\n").unwrap();
+        let repository = XdgAutostartRepository::new(user.clone(), vec![system.clone()], vec![applications]);
+
+        let mut applet = repository.get_by_id("nm-applet").await.unwrap().unwrap();
+        assert!(applet.enabled);
+        applet.enabled = false;
+        repository.save(&applet).await.unwrap();
+        let chat = AutostartEntry {
+            delay: Duration::from_millis(2500),
+            condition: AutostartCondition::UnlessExists("/nonexistent/chat-disabled".to_string()),
+            ..AutostartEntry::new("chat")
+        };
+        repository.save(&chat).await.unwrap();
+        assert!(matches!(repository.save(&AutostartEntry::new("missing")).await, Err(DomainError::EntityNotFound { .. })));
+
+        assert_eq!(repository.get_all().await.unwrap(), vec![chat, applet]);
+        // The scheduler starts what the settings show as enabled.
+        let started = scan_autostart(&[user.clone(), system.clone()], &[DESKTOP_NAME.to_string()]);
+        assert_eq!(started.len(), 1);
+        assert_eq!((started[0].file_name.as_str(), started[0].delay), ("chat.desktop", Duration::from_millis(2500)));
+
+        repository.remove("nm-applet").await.unwrap();
+        repository.remove("chat").await.unwrap();
+        assert!(repository.get_all().await.unwrap().is_empty());
+        assert!(fs::read_to_string(user.join("nm-applet.desktop")).unwrap().contains("Hidden=true"));
+        assert!(!user.join("chat.desktop").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,438 @@
+// src/repositories/cached.rs
+
+//! Read caches in front of repositories.
+//!
+//! [`CachedApplicationRepository`] and [`CachedWorkspaceRepository`] wrap another repository
+//! and answer `get_by_id`/`get_all` (and the name lookups of non-archived entities, once the
+//! list is cached) from memory; archived entities are always read from the inner repository. Writes through the decorator go to the inner repository first and then invalidate
+//! the cache. Changes made to the inner repository by other means invalidate the cache through
+//! the inner repository's `changes` signal; repositories without one must be announced with
+//! `invalidate`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use novade_core::signal::Signal;
+use novade_core::types::NovaId;
+use novade_domain::entities::{Application, Workspace};
+use novade_domain::repositories::{ApplicationQuery, ApplicationRepository, RepositoryChange, WorkspaceRepository};
+use novade_domain::DomainResult;
+
+/// Default number of entities cached by ID.
+pub const DEFAULT_CACHE_CAPACITY: usize = 256;
+
+/// Cache state shared by the decorators.
+#[derive(Debug)]
+struct EntityCache<T> {
+    capacity: usize,
+    /// Results of `get_by_id` (including misses) with their last use.
+    by_id: HashMap<NovaId, (Option<T>, u64)>,
+    /// Result of `get_all`, i.e. the non-archived entities.
+    all: Option<Vec<T>>,
+    /// Use counter for the LRU eviction.
+    tick: u64,
+    /// Incremented on every invalidation, so results loaded before a write are not stored.
+    generation: u64,
+}
+
+impl<T: Clone> EntityCache<T> {
+    fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), by_id: HashMap::new(), all: None, tick: 0, generation: 0 }
+    }
+
+    fn get(&mut self, id: &NovaId) -> Option<Option<T>> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (entity, last_used) = self.by_id.get_mut(id)?;
+        *last_used = tick;
+        Some(entity.clone())
+    }
+
+    fn insert(&mut self, generation: u64, id: NovaId, entity: Option<T>) {
+        if generation != self.generation {
+            return;
+        }
+        if self.by_id.len() >= self.capacity && !self.by_id.contains_key(&id) {
+            let least_recent = self.by_id.iter().min_by_key(|(_, (_, last_used))| *last_used).map(|(id, _)| id.clone());
+            if let Some(least_recent) = least_recent {
+                self.by_id.remove(&least_recent);
+            }
+        }
+        self.tick += 1;
+        self.by_id.insert(id, (entity, self.tick));
+    }
+
+    fn set_all(&mut self, generation: u64, entities: Vec<T>) {
+        if generation == self.generation {
+            self.all = Some(entities);
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.by_id.clear();
+        self.all = None;
+        self.generation += 1;
+    }
+}
+
+/// Caching decorator for an [`ApplicationRepository`].
+#[derive(Debug)]
+pub struct CachedApplicationRepository<R> {
+    inner: R,
+    cache: Arc<Mutex<EntityCache<Application>>>,
+}
+
+impl<R: ApplicationRepository> CachedApplicationRepository<R> {
+    /// Wraps `inner`, caching up to [`DEFAULT_CACHE_CAPACITY`] applications by ID.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(inner, DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Wraps `inner`, caching up to `capacity` applications by ID.
+    pub fn with_capacity(inner: R, capacity: usize) -> Self {
+        let cache = Arc::new(Mutex::new(EntityCache::new(capacity)));
+        if let Some(changes) = inner.changes() {
+            changes.connect_weak(&cache, |cache, _| cache.lock().unwrap().invalidate());
+        }
+        Self { inner, cache }
+    }
+
+    /// The wrapped repository.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Drops all cached results, e.g. after the inner repository was changed directly.
+    pub fn invalidate(&self) {
+        self.cache.lock().unwrap().invalidate();
+    }
+}
+
+#[async_trait]
+impl<R: ApplicationRepository> ApplicationRepository for CachedApplicationRepository<R> {
+    async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Application>> {
+        let generation = {
+            let mut cache = self.cache.lock().unwrap();
+            if let Some(cached) = cache.get(id) {
+                return Ok(cached);
+            }
+            // A miss in the list may still be an archived application.
+            if let Some(application) = cache.all.as_ref().and_then(|all| all.iter().find(|app| &app.id == id)) {
+                return Ok(Some(application.clone()));
+            }
+            cache.generation
+        };
+        let application = self.inner.get_by_id(id).await?;
+        self.cache.lock().unwrap().insert(generation, id.clone(), application.clone());
+        Ok(application)
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<Application>> {
+        let generation = {
+            let cache = self.cache.lock().unwrap();
+            if let Some(all) = &cache.all {
+                return Ok(all.clone());
+            }
+            cache.generation
+        };
+        let applications = self.inner.get_all().await?;
+        self.cache.lock().unwrap().set_all(generation, applications.clone());
+        Ok(applications)
+    }
+
+    async fn get_archived(&self) -> DomainResult<Vec<Application>> {
+        self.inner.get_archived().await
+    }
+
+    /// Served by the inner repository, which may answer it from an index.
+    async fn find(&self, query: &ApplicationQuery) -> DomainResult<Vec<Application>> {
+        self.inner.find(query).await
+    }
+
+    /// Served by the inner repository; its matching rules are not known to the cache.
+    async fn find_by_name(&self, search_term: &str) -> DomainResult<Vec<Application>> {
+        self.inner.find_by_name(search_term).await
+    }
+
+    async fn find_by_tag(&self, tag: &str) -> DomainResult<Vec<Application>> {
+        self.inner.find_by_tag(tag).await
+    }
+
+    async fn add(&self, application: &Application) -> DomainResult<()> {
+        let result = self.inner.add(application).await;
+        self.invalidate();
+        result
+    }
+
+    async fn update(&self, application: &Application) -> DomainResult<()> {
+        let result = self.inner.update(application).await;
+        self.invalidate();
+        result
+    }
+
+    async fn save_batch(&self, added: &[Application], updated: &[Application]) -> DomainResult<()> {
+        let result = self.inner.save_batch(added, updated).await;
+        self.invalidate();
+        result
+    }
+
+    async fn remove(&self, id: &NovaId) -> DomainResult<()> {
+        let result = self.inner.remove(id).await;
+        self.invalidate();
+        result
+    }
+
+    fn changes(&self) -> Option<Arc<Signal<RepositoryChange<Application>>>> {
+        self.inner.changes()
+    }
+}
+
+/// Caching decorator for a [`WorkspaceRepository`].
+#[derive(Debug)]
+pub struct CachedWorkspaceRepository<R> {
+    inner: R,
+    cache: Arc<Mutex<EntityCache<Workspace>>>,
+}
+
+impl<R: WorkspaceRepository> CachedWorkspaceRepository<R> {
+    /// Wraps `inner`, caching up to [`DEFAULT_CACHE_CAPACITY`] workspaces by ID.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(inner, DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Wraps `inner`, caching up to `capacity` workspaces by ID.
+    pub fn with_capacity(inner: R, capacity: usize) -> Self {
+        let cache = Arc::new(Mutex::new(EntityCache::new(capacity)));
+        if let Some(changes) = inner.changes() {
+            changes.connect_weak(&cache, |cache, _| cache.lock().unwrap().invalidate());
+        }
+        Self { inner, cache }
+    }
+
+    /// The wrapped repository.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Drops all cached results, e.g. after the inner repository was changed directly.
+    pub fn invalidate(&self) {
+        self.cache.lock().unwrap().invalidate();
+    }
+}
+
+#[async_trait]
+impl<R: WorkspaceRepository> WorkspaceRepository for CachedWorkspaceRepository<R> {
+    async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Workspace>> {
+        let generation = {
+            let mut cache = self.cache.lock().unwrap();
+            if let Some(cached) = cache.get(id) {
+                return Ok(cached);
+            }
+            // A miss in the list may still be an archived workspace.
+            if let Some(workspace) = cache.all.as_ref().and_then(|all| all.iter().find(|ws| &ws.id == id)) {
+                return Ok(Some(workspace.clone()));
+            }
+            cache.generation
+        };
+        let workspace = self.inner.get_by_id(id).await?;
+        self.cache.lock().unwrap().insert(generation, id.clone(), workspace.clone());
+        Ok(workspace)
+    }
+
+    /// Answered from the cached list if it contains the name; names are matched exactly.
+    async fn get_by_name(&self, name: &str) -> DomainResult<Option<Workspace>> {
+        let cached = self.cache.lock().unwrap().all.as_ref().and_then(|all| all.iter().find(|ws| ws.name == name).cloned());
+        match cached {
+            Some(workspace) => Ok(Some(workspace)),
+            None => self.inner.get_by_name(name).await,
+        }
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<Workspace>> {
+        let generation = {
+            let cache = self.cache.lock().unwrap();
+            if let Some(all) = &cache.all {
+                return Ok(all.clone());
+            }
+            cache.generation
+        };
+        let workspaces = self.inner.get_all().await?;
+        self.cache.lock().unwrap().set_all(generation, workspaces.clone());
+        Ok(workspaces)
+    }
+
+    async fn get_archived(&self) -> DomainResult<Vec<Workspace>> {
+        self.inner.get_archived().await
+    }
+
+    async fn get_all_including_archived(&self) -> DomainResult<Vec<Workspace>> {
+        self.inner.get_all_including_archived().await
+    }
+
+    async fn add(&self, workspace: &Workspace) -> DomainResult<()> {
+        let result = self.inner.add(workspace).await;
+        self.invalidate();
+        result
+    }
+
+    async fn update(&self, workspace: &Workspace) -> DomainResult<()> {
+        let result = self.inner.update(workspace).await;
+        self.invalidate();
+        result
+    }
+
+    async fn remove(&self, id: &NovaId) -> DomainResult<()> {
+        let result = self.inner.remove(id).await;
+        self.invalidate();
+        result
+    }
+
+    fn changes(&self) -> Option<Arc<Signal<RepositoryChange<Workspace>>>> {
+        self.inner.changes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{InMemoryApplicationRepository, InMemoryWorkspaceRepository};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts the reads reaching the wrapped repository.
+    #[derive(Debug, Default)]
+    struct CountingRepository {
+        inner: InMemoryApplicationRepository,
+        reads: AtomicUsize,
+    }
+
+    impl CountingRepository {
+        fn reads(&self) -> usize {
+            self.reads.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl ApplicationRepository for CountingRepository {
+        async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Application>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.get_by_id(id).await
+        }
+
+        async fn get_all(&self) -> DomainResult<Vec<Application>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.get_all().await
+        }
+
+        async fn get_archived(&self) -> DomainResult<Vec<Application>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.get_archived().await
+        }
+
+        async fn find_by_name(&self, search_term: &str) -> DomainResult<Vec<Application>> {
+            self.inner.find_by_name(search_term).await
+        }
+
+        async fn add(&self, application: &Application) -> DomainResult<()> {
+            self.inner.add(application).await
+        }
+
+        async fn update(&self, application: &Application) -> DomainResult<()> {
+            self.inner.update(application).await
+        }
+
+        async fn remove(&self, id: &NovaId) -> DomainResult<()> {
+            self.inner.remove(id).await
+        }
+    }
+
+    fn app(name: &str) -> Application {
+        Application::new_desktop(name.to_string(), format!("/usr/bin/{}", name), None)
+    }
+
+    #[tokio::test]
+    async fn test_reads_are_cached_until_write() {
+        let repository = CachedApplicationRepository::new(CountingRepository::default());
+        let editor = app("editor");
+        repository.add(&editor).await.unwrap();
+
+        assert_eq!(repository.get_by_id(&editor.id).await.unwrap(), Some(editor.clone()));
+        assert_eq!(repository.get_by_id(&editor.id).await.unwrap(), Some(editor.clone()));
+        assert_eq!(repository.inner().reads(), 1);
+
+        assert_eq!(repository.get_all().await.unwrap().len(), 1);
+        assert_eq!(repository.get_all().await.unwrap().len(), 1);
+        assert_eq!(repository.inner().reads(), 2);
+
+        let mut renamed = editor.clone();
+        renamed.name = "text-editor".to_string();
+        repository.update(&renamed).await.unwrap();
+        assert_eq!(repository.get_by_id(&editor.id).await.unwrap().unwrap().name, "text-editor");
+        assert_eq!(repository.inner().reads(), 3);
+
+        // Direct changes to the inner repository need an explicit invalidation.
+        repository.inner().inner.remove(&editor.id).await.unwrap();
+        assert!(repository.get_by_id(&editor.id).await.unwrap().is_some());
+        repository.invalidate();
+        assert!(repository.get_by_id(&editor.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_inner_change_notifications_invalidate() {
+        let repository = CachedWorkspaceRepository::new(InMemoryWorkspaceRepository::new());
+        let work = Workspace::new("Work".to_string(), None);
+        repository.add(&work).await.unwrap();
+        assert_eq!(repository.get_all().await.unwrap().len(), 1);
+
+        // Written past the decorator, e.g. by another service sharing the inner repository.
+        repository.inner().remove(&work.id).await.unwrap();
+        assert!(repository.get_all().await.unwrap().is_empty());
+        assert!(repository.get_by_id(&work.id).await.unwrap().is_none());
+        assert!(repository.changes().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_least_recently_used_entry_is_evicted() {
+        let (a, b, c) = (app("a"), app("b"), app("c"));
+        let inner = CountingRepository {
+            inner: InMemoryApplicationRepository::with_applications([a.clone(), b.clone(), c.clone()]),
+            reads: AtomicUsize::new(0),
+        };
+        let repository = CachedApplicationRepository::with_capacity(inner, 2);
+        repository.get_by_id(&a.id).await.unwrap();
+        repository.get_by_id(&b.id).await.unwrap();
+        repository.get_by_id(&a.id).await.unwrap();
+        repository.get_by_id(&c.id).await.unwrap(); // Evicts b.
+        assert_eq!(repository.inner().reads(), 3);
+
+        repository.get_by_id(&a.id).await.unwrap();
+        assert_eq!(repository.inner().reads(), 3);
+        repository.get_by_id(&b.id).await.unwrap();
+        assert_eq!(repository.inner().reads(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_workspace_name_lookup_uses_cached_list() {
+        let repository = CachedWorkspaceRepository::new(InMemoryWorkspaceRepository::new());
+        repository.add(&Workspace::new("Work".to_string(), None)).await.unwrap();
+        assert_eq!(repository.get_all().await.unwrap().len(), 1);
+        assert!(repository.get_by_name("Work").await.unwrap().is_some());
+
+        let failed = repository.add(&Workspace::new("Work".to_string(), None)).await;
+        assert!(failed.is_err());
+        assert_eq!(repository.get_all().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_archived_workspaces_are_read_through() {
+        let mut archived = Workspace::new("Alt".to_string(), None);
+        archived.deleted_at = Some(novade_core::types::Timestamp::now());
+        let repository = CachedWorkspaceRepository::new(InMemoryWorkspaceRepository::with_workspaces([archived.clone()]));
+        assert!(repository.get_all().await.unwrap().is_empty());
+
+        // The cached list holds no archived workspaces, so it must not answer misses.
+        assert_eq!(repository.get_by_name("Alt").await.unwrap(), Some(archived.clone()));
+        assert_eq!(repository.get_by_id(&archived.id).await.unwrap(), Some(archived.clone()));
+        assert_eq!(repository.get_archived().await.unwrap(), vec![archived]);
+    }
+}
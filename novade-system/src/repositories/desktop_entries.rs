@@ -0,0 +1,417 @@
+// src/repositories/desktop_entries.rs
+
+//! Applications from XDG desktop entries.
+//!
+//! The [`DesktopEntryRepository`] scans the `applications` directories below
+//! `$XDG_DATA_HOME` and `$XDG_DATA_DIRS` for `.desktop` files and exposes them as read-only
+//! [`Application`]s. The application name is the desktop file ID (e.g.
+//! "org.gnome.TextEditor"), the display name the `Name` localized for the current locale.
+//! As in the autostart directories, an entry in a more important directory overrides
+//! entries with the same desktop file ID in less important ones.
+//!
+//! [`DesktopEntryRepository::sync_into`] copies the scanned applications into another
+//! repository, only touching applications that were added, changed or removed.
+//! [`DesktopEntryRepository::watch`] reports changed desktop files, e.g. after a package
+//! manager installed an application, so the repository can be rescanned.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use novade_core::types::NovaId;
+use novade_core::utils::watch::DEFAULT_DEBOUNCE;
+use novade_core::utils::DirectoryWatcher;
+use novade_domain::entities::{Application, ApplicationType};
+use novade_domain::repositories::{ApplicationQuery, ApplicationRepository};
+use novade_domain::{DomainError, DomainResult};
+
+use crate::process_manager::autostart::{localized_value, parse_desktop_entry_localized};
+use crate::process_manager::exec::split_exec_line;
+use crate::process_manager::sandbox::apply_detected_sandbox;
+
+/// The application directories in order of decreasing importance:
+/// `$XDG_DATA_HOME/applications` (default `~/.local/share/applications`), then
+/// `$XDG_DATA_DIRS/applications` (default `/usr/local/share:/usr/share`).
+pub fn application_dirs() -> Vec<PathBuf> {
+    let non_empty = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    let mut dirs = Vec::new();
+    let data_home = non_empty("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".local/share")));
+    dirs.extend(data_home.map(|dir| dir.join("applications")));
+    let data_dirs = non_empty("XDG_DATA_DIRS").unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    dirs.extend(std::env::split_paths(&data_dirs).map(|dir| dir.join("applications")));
+    dirs
+}
+
+/// The locale for localized keys, from `LC_ALL`, `LC_MESSAGES` or `LANG`.
+pub fn current_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+}
+
+fn split_list(value: Option<&String>) -> Option<Vec<String>> {
+    let items: Vec<String> = value?.split(';').filter(|item| !item.is_empty()).map(str::to_string).collect();
+    (!items.is_empty()).then_some(items)
+}
+
+/// Converts a parsed desktop entry into an [`Application`] named after its desktop file ID.
+///
+/// # Returns
+/// `None` if the entry should not be listed: it is not an application, is `Hidden` or
+/// `NoDisplay`, or has no valid `Exec`.
+pub fn entry_to_application(desktop_id: &str, entry: &HashMap<String, String>, locale: Option<&str>) -> Option<Application> {
+    let is_true = |key: &str| entry.get(key).is_some_and(|value| value == "true");
+    if entry.get("Type").is_some_and(|t| t != "Application") || is_true("Hidden") || is_true("NoDisplay") {
+        return None;
+    }
+
+    let mut exec = split_exec_line(entry.get("Exec")?)?.into_iter();
+    let executable = exec.next()?;
+    let arguments: Vec<String> = exec.collect();
+    let mut app = Application::new_desktop(desktop_id.to_string(), executable, entry.get("Icon").cloned());
+    app.display_name = localized_value(entry, "Name", locale).cloned();
+    app.arguments = (!arguments.is_empty()).then_some(arguments);
+    app.working_directory = entry.get("Path").filter(|path| !path.is_empty()).cloned();
+    app.description = localized_value(entry, "Comment", locale).cloned();
+    app.categories = split_list(entry.get("Categories"));
+    app.keywords = split_list(localized_value(entry, "Keywords", locale));
+    app.mime_types = split_list(entry.get("MimeType")).unwrap_or_default();
+    apply_detected_sandbox(&mut app);
+    Some(app)
+}
+
+/// Collects `(desktop file ID, path)` for every `.desktop` file below `dir`. The ID is the
+/// path relative to `dir` with `/` replaced by `-`.
+fn collect_desktop_files(dir: &Path, prefix: &str, files: &mut Vec<(String, PathBuf)>) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = read_dir.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    paths.sort();
+    for path in paths {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if path.is_dir() {
+            collect_desktop_files(&path, &format!("{}{}-", prefix, name), files);
+        } else if let Some(stem) = name.strip_suffix(".desktop") {
+            files.push((format!("{}{}", prefix, stem), path));
+        }
+    }
+}
+
+/// Scans `dirs` (most important first) for applications, sorted by desktop file ID.
+///
+/// Unreadable directories and files are skipped.
+pub fn scan_applications(dirs: &[PathBuf], locale: Option<&str>) -> Vec<Application> {
+    let mut seen = HashSet::new();
+    let mut applications = Vec::new();
+    for dir in dirs {
+        let mut files = Vec::new();
+        collect_desktop_files(dir, "", &mut files);
+        for (desktop_id, path) in files {
+            // The first (most important) entry with an ID decides, even if it is hidden.
+            if !seen.insert(desktop_id.clone()) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                eprintln!("DesktopEntryRepository: Failed to read {}", path.display());
+                continue;
+            };
+            applications.extend(entry_to_application(&desktop_id, &parse_desktop_entry_localized(&content), locale));
+        }
+    }
+    applications.sort_by(|a, b| a.name.cmp(&b.name));
+    applications
+}
+
+/// Whether two applications describe the same desktop entry contents. The ID and the
+/// user's tags are not part of the desktop entry.
+fn same_entry(a: &Application, b: &Application) -> bool {
+    Application { id: b.id.clone(), tags: b.tags.clone(), ..a.clone() } == *b
+}
+
+/// Outcome of [`DesktopEntryRepository::sync_into`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Desktop file IDs of applications added to the target.
+    pub added: Vec<String>,
+    /// Desktop file IDs of applications whose entry changed.
+    pub updated: Vec<String>,
+    /// Desktop file IDs of applications removed from the target.
+    pub removed: Vec<String>,
+}
+
+impl SyncReport {
+    /// Whether the target was left unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Read-only [`ApplicationRepository`] over the installed desktop entries.
+#[derive(Debug)]
+pub struct DesktopEntryRepository {
+    dirs: Vec<PathBuf>,
+    locale: Option<String>,
+    applications: Mutex<Vec<Application>>,
+}
+
+impl DesktopEntryRepository {
+    /// Scans `dirs` (most important first), localizing names for `locale`.
+    pub fn new(dirs: Vec<PathBuf>, locale: Option<String>) -> Self {
+        let applications = scan_applications(&dirs, locale.as_deref());
+        Self { dirs, locale, applications: Mutex::new(applications) }
+    }
+
+    /// Scans [`application_dirs`] for the [`current_locale`].
+    pub fn from_environment() -> Self {
+        Self::new(application_dirs(), current_locale())
+    }
+
+    /// Scans the directories again, e.g. after a package was installed.
+    ///
+    /// Applications keep their ID as long as their desktop file ID stays the same.
+    pub fn rescan(&self) {
+        let mut scanned = scan_applications(&self.dirs, self.locale.as_deref());
+        let mut applications = self.applications.lock().unwrap();
+        let known: HashMap<&str, &NovaId> = applications.iter().map(|app| (app.name.as_str(), &app.id)).collect();
+        for app in &mut scanned {
+            if let Some(&id) = known.get(app.name.as_str()) {
+                app.id = id.clone();
+            }
+        }
+        *applications = scanned;
+    }
+
+    /// Watches the application directories that exist for added, changed or removed desktop
+    /// files. On each event, call [`rescan`](Self::rescan) and [`sync_into`](Self::sync_into).
+    ///
+    /// # Errors
+    /// `DomainError::RepositoryError` if the directories cannot be watched.
+    pub fn watch(&self) -> DomainResult<DirectoryWatcher> {
+        let is_desktop_file = |path: &Path| path.extension().is_some_and(|extension| extension == "desktop");
+        let mut watcher = DirectoryWatcher::new(is_desktop_file, DEFAULT_DEBOUNCE)?;
+        for dir in self.dirs.iter().filter(|dir| dir.is_dir()) {
+            watcher.add(dir)?;
+        }
+        Ok(watcher)
+    }
+
+    /// Makes the desktop-entry applications in `target` match the scanned ones.
+    ///
+    /// Applications are matched by name (the desktop file ID). New entries are added, changed
+    /// entries are updated keeping the application's ID and tags, and applications of type
+    /// [`ApplicationType::Desktop`] without a desktop entry are removed. Other application
+    /// types in `target` are left alone.
+    pub async fn sync_into(&self, target: &dyn ApplicationRepository) -> DomainResult<SyncReport> {
+        let scanned = self.applications.lock().unwrap().clone();
+        let existing: HashMap<String, Application> =
+            target.find(&ApplicationQuery::new().including_archived()).await?.into_iter().map(|app| (app.name.clone(), app)).collect();
+        let mut report = SyncReport::default();
+
+        for app in &scanned {
+            match existing.get(&app.name) {
+                None => {
+                    target.add(app).await?;
+                    report.added.push(app.name.clone());
+                }
+                Some(stored) if !same_entry(app, stored) => {
+                    let updated = Application { id: stored.id.clone(), tags: stored.tags.clone(), ..app.clone() };
+                    target.update(&updated).await?;
+                    report.updated.push(app.name.clone());
+                }
+                Some(_) => {}
+            }
+        }
+        let scanned_names: HashSet<&str> = scanned.iter().map(|app| app.name.as_str()).collect();
+        let mut stale: Vec<&Application> = existing
+            .values()
+            .filter(|app| app.app_type == ApplicationType::Desktop && !scanned_names.contains(app.name.as_str()))
+            .collect();
+        stale.sort_by(|a, b| a.name.cmp(&b.name));
+        for app in stale {
+            target.remove(&app.id).await?;
+            report.removed.push(app.name.clone());
+        }
+        Ok(report)
+    }
+}
+
+fn read_only(operation: &str) -> DomainError {
+    DomainError::OperationNotPermitted {
+        operation: operation.to_string(),
+        reason: "Applications from desktop entries are read-only.".to_string(),
+    }
+}
+
+#[async_trait]
+impl ApplicationRepository for DesktopEntryRepository {
+    async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Application>> {
+        Ok(self.applications.lock().unwrap().iter().find(|app| &app.id == id).cloned())
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<Application>> {
+        Ok(self.applications.lock().unwrap().clone())
+    }
+
+    /// Scanned entries are never archived.
+    async fn get_archived(&self) -> DomainResult<Vec<Application>> {
+        Ok(Vec::new())
+    }
+
+    async fn find(&self, query: &ApplicationQuery) -> DomainResult<Vec<Application>> {
+        Ok(self.applications.lock().unwrap().iter().filter(|app| query.matches(app)).cloned().collect())
+    }
+
+    /// Case-insensitive substring match on the desktop file ID, the name and the keywords.
+    async fn find_by_name(&self, search_term: &str) -> DomainResult<Vec<Application>> {
+        let term = search_term.to_lowercase();
+        let matches = |text: &str| text.to_lowercase().contains(&term);
+        Ok(self
+            .applications
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|app| {
+                matches(&app.name)
+                    || app.display_name.as_deref().is_some_and(matches)
+                    || app.keywords.iter().flatten().any(|keyword| matches(keyword))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn add(&self, _application: &Application) -> DomainResult<()> {
+        Err(read_only("add_application"))
+    }
+
+    async fn update(&self, _application: &Application) -> DomainResult<()> {
+        Err(read_only("update_application"))
+    }
+
+    async fn remove(&self, _id: &NovaId) -> DomainResult<()> {
+        Err(read_only("remove_application"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::InMemoryApplicationRepository;
+    use std::fs;
+
+    struct TempDirs {
+        root: PathBuf,
+    }
+
+    impl TempDirs {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("novade-desktop-entries-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            Self { root }
+        }
+
+        fn write(&self, path: &str, content: &str) {
+            let path = self.root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        fn dirs(&self) -> Vec<PathBuf> {
+            vec![self.root.join("user"), self.root.join("system")]
+        }
+    }
+
+    impl Drop for TempDirs {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn test_entry_to_application() {
+        let entry = parse_desktop_entry_localized(
+            "[Desktop Entry]\nType=Application\nName=Files\nName[de]=Dateien\nExec=nautilus --new-window %U\n\
+             Icon=org.gnome.Nautilus\nCategories=GNOME;Utility;Core;\nKeywords=folder;manager;\nMimeType=inode/directory;\n",
+        );
+        let app = entry_to_application("org.gnome.Nautilus", &entry, Some("de_DE.UTF-8")).unwrap();
+        assert_eq!(app.name, "org.gnome.Nautilus");
+        assert_eq!(app.display_name.as_deref(), Some("Dateien"));
+        assert_eq!(app.executable_path, "nautilus");
+        assert_eq!(app.arguments, Some(vec!["--new-window".to_string(), "%U".to_string()]));
+        assert_eq!(app.categories, Some(vec!["GNOME".to_string(), "Utility".to_string(), "Core".to_string()]));
+        assert_eq!(app.keywords, Some(vec!["folder".to_string(), "manager".to_string()]));
+        assert_eq!(app.mime_types, vec!["inode/directory".to_string()]);
+
+        let hidden = parse_desktop_entry_localized("[Desktop Entry]\nExec=helper\nNoDisplay=true\n");
+        assert!(entry_to_application("helper", &hidden, None).is_none());
+    }
+
+    #[test]
+    fn test_scan_prefers_user_entries_and_uses_desktop_ids() {
+        let temp = TempDirs::new("scan");
+        temp.write("system/applications-a.desktop", "");
+        temp.write("system/editor.desktop", "[Desktop Entry]\nName=System Editor\nExec=editor\n");
+        temp.write("system/kde/konsole.desktop", "[Desktop Entry]\nName=Konsole\nExec=konsole\n");
+        temp.write("system/removed.desktop", "[Desktop Entry]\nName=Removed\nExec=removed\n");
+        temp.write("user/editor.desktop", "[Desktop Entry]\nName=My Editor\nExec=editor --user\n");
+        temp.write("user/removed.desktop", "[Desktop Entry]\nName=Removed\nExec=removed\nHidden=true\n");
+
+        let apps = scan_applications(&temp.dirs(), None);
+        let names: Vec<&str> = apps.iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(names, vec!["editor", "kde-konsole"]);
+        assert_eq!(apps[0].display_name.as_deref(), Some("My Editor"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_detects_changes() {
+        let temp = TempDirs::new("sync");
+        temp.write("system/editor.desktop", "[Desktop Entry]\nName=Editor\nExec=editor\n");
+        temp.write("system/old.desktop", "[Desktop Entry]\nName=Old\nExec=old\n");
+        let entries = DesktopEntryRepository::new(temp.dirs(), None);
+        let target = InMemoryApplicationRepository::new();
+
+        let report = entries.sync_into(&target).await.unwrap();
+        assert_eq!(report.added, vec!["editor".to_string(), "old".to_string()]);
+        assert!(entries.sync_into(&target).await.unwrap().is_empty(), "unchanged entries are not written again");
+
+        // User tags survive an update of the entry.
+        let mut editor = target.find_by_name("editor").await.unwrap().remove(0);
+        editor.tags.push("Work".to_string());
+        target.update(&editor).await.unwrap();
+
+        temp.write("system/editor.desktop", "[Desktop Entry]\nName=Editor\nExec=editor --new\n");
+        fs::remove_file(temp.root.join("system/old.desktop")).unwrap();
+        entries.rescan();
+        let report = entries.sync_into(&target).await.unwrap();
+        assert_eq!(report, SyncReport { added: vec![], updated: vec!["editor".to_string()], removed: vec!["old".to_string()] });
+
+        let synced = target.get_by_id(&editor.id).await.unwrap().unwrap();
+        assert_eq!(synced.arguments, Some(vec!["--new".to_string()]));
+        assert_eq!(synced.tags, vec!["Work".to_string()]);
+        assert_eq!(target.len(), 1);
+        assert!(matches!(entries.remove(&editor.id).await, Err(DomainError::OperationNotPermitted { .. })));
+    }
+
+    #[test]
+    fn test_watch_reports_new_desktop_files() {
+        let temp = TempDirs::new("watch");
+        temp.write("user/editor.desktop", "[Desktop Entry]\nName=Editor\nExec=editor\n");
+        let repository = DesktopEntryRepository::new(temp.dirs(), None);
+        let watcher = repository.watch().unwrap();
+
+        temp.write("user/kde/konsole.desktop", "[Desktop Entry]\nName=Konsole\nExec=konsole\n");
+        temp.write("user/notes.txt", "ignored");
+        let event = watcher.events().recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert_eq!(event.path(), temp.root.join("user/kde/konsole.desktop"));
+
+        repository.rescan();
+        let names: Vec<String> = repository.applications.lock().unwrap().iter().map(|app| app.name.clone()).collect();
+        assert_eq!(names, vec!["editor".to_string(), "kde-konsole".to_string()]);
+    }
+}
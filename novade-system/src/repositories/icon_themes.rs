@@ -0,0 +1,336 @@
+// src/repositories/icon_themes.rs
+
+//! Icon themes from the XDG icon directories.
+//!
+//! Implements the lookup side of the Icon Theme Specification: themes are directories
+//! below the icon base directories (`~/.icons`, `$XDG_DATA_HOME/icons`,
+//! `$XDG_DATA_DIRS/icons`) described by an `index.theme` file, which lists the icon
+//! subdirectories with their sizes and the themes the theme inherits from. Unthemed icons
+//! are looked up in `/usr/share/pixmaps`.
+//!
+//! The theme chain and the fallback to `hicolor` are handled by the domain's
+//! `IconResolverService`; this repository only searches one theme at a time.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use novade_domain::repositories::IconThemeRepository;
+use novade_domain::DomainResult;
+
+/// The icon file extensions in order of preference.
+const ICON_EXTENSIONS: [&str; 3] = ["png", "svg", "xpm"];
+
+/// The icon base directories in order of decreasing importance: `~/.icons`,
+/// `$XDG_DATA_HOME/icons` (default `~/.local/share/icons`), then `$XDG_DATA_DIRS/icons`
+/// (default `/usr/local/share:/usr/share`).
+pub fn icon_base_dirs() -> Vec<PathBuf> {
+    let non_empty = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    let home = non_empty("HOME").map(PathBuf::from);
+    let mut dirs = Vec::new();
+    dirs.extend(home.as_ref().map(|home| home.join(".icons")));
+    let data_home = non_empty("XDG_DATA_HOME").map(PathBuf::from).or_else(|| home.map(|home| home.join(".local/share")));
+    dirs.extend(data_home.map(|dir| dir.join("icons")));
+    let data_dirs = non_empty("XDG_DATA_DIRS").unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    dirs.extend(std::env::split_paths(&data_dirs).map(|dir| dir.join("icons")));
+    dirs
+}
+
+/// How an icon directory matches requested sizes (the `Type` key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconDirectoryType {
+    /// Icons of exactly `Size`.
+    Fixed,
+    /// Icons that can be scaled between `MinSize` and `MaxSize`.
+    Scalable,
+    /// Icons usable within `Threshold` of `Size`.
+    Threshold,
+}
+
+/// An icon subdirectory of a theme, as described by its group in `index.theme`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IconDirectory {
+    /// The path relative to the theme directory (e.g. "48x48/apps").
+    pub path: String,
+    pub size: u32,
+    pub scale: u32,
+    pub kind: IconDirectoryType,
+    pub min_size: u32,
+    pub max_size: u32,
+    pub threshold: u32,
+}
+
+impl IconDirectory {
+    /// Whether the directory holds icons for `size` at `scale` (`DirectoryMatchesSize`).
+    pub fn matches_size(&self, size: u32, scale: u32) -> bool {
+        if self.scale != scale {
+            return false;
+        }
+        match self.kind {
+            IconDirectoryType::Fixed => self.size == size,
+            IconDirectoryType::Scalable => (self.min_size..=self.max_size).contains(&size),
+            IconDirectoryType::Threshold => {
+                (self.size.saturating_sub(self.threshold)..=self.size + self.threshold).contains(&size)
+            }
+        }
+    }
+
+    /// How far the directory's icons are from `size` at `scale` in device pixels
+    /// (`DirectorySizeDistance`).
+    pub fn size_distance(&self, size: u32, scale: u32) -> u32 {
+        let requested = size * scale;
+        match self.kind {
+            IconDirectoryType::Fixed => (self.size * self.scale).abs_diff(requested),
+            IconDirectoryType::Scalable | IconDirectoryType::Threshold => {
+                let (lower, upper) = match self.kind {
+                    IconDirectoryType::Scalable => (self.min_size, self.max_size),
+                    _ => (self.size.saturating_sub(self.threshold), self.size + self.threshold),
+                };
+                if requested < lower * self.scale {
+                    (self.min_size * self.scale).abs_diff(requested)
+                } else if requested > upper * self.scale {
+                    (self.max_size * self.scale).abs_diff(requested)
+                } else {
+                    0
+                }
+            }
+        }
+    }
+}
+
+/// The parsed `index.theme` of an icon theme.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IconTheme {
+    /// The themes this theme inherits from, in order.
+    pub inherits: Vec<String>,
+    pub directories: Vec<IconDirectory>,
+}
+
+impl IconTheme {
+    /// Parses the contents of an `index.theme` file. Directories listed in `Directories` or
+    /// `ScaledDirectories` without a valid `Size` are skipped.
+    pub fn parse(content: &str) -> Self {
+        let mut groups: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut current = None;
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(group) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                current = Some(group.to_string());
+                groups.entry(group.to_string()).or_default();
+            } else if let (Some((key, value)), Some(group)) = (line.split_once('='), &current) {
+                groups.entry(group.clone()).or_default().insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+
+        let main = groups.get("Icon Theme").cloned().unwrap_or_default();
+        let list = |key: &str| -> Vec<String> {
+            main.get(key)
+                .map(|value| value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+        let mut directories = Vec::new();
+        for path in list("Directories").into_iter().chain(list("ScaledDirectories")) {
+            let Some(group) = groups.get(&path) else { continue };
+            let number = |key: &str| group.get(key).and_then(|value| value.parse::<u32>().ok());
+            let Some(size) = number("Size") else { continue };
+            let kind = match group.get("Type").map(String::as_str) {
+                Some("Fixed") => IconDirectoryType::Fixed,
+                Some("Scalable") => IconDirectoryType::Scalable,
+                _ => IconDirectoryType::Threshold,
+            };
+            if directories.iter().any(|directory: &IconDirectory| directory.path == path) {
+                continue;
+            }
+            directories.push(IconDirectory {
+                size,
+                scale: number("Scale").filter(|scale| *scale > 0).unwrap_or(1),
+                kind,
+                min_size: number("MinSize").unwrap_or(size),
+                max_size: number("MaxSize").unwrap_or(size),
+                threshold: number("Threshold").unwrap_or(2),
+                path,
+            });
+        }
+        Self { inherits: list("Inherits"), directories }
+    }
+}
+
+/// [`IconThemeRepository`] over the icon themes installed in the icon base directories.
+///
+/// A theme's `index.theme` is read from the first base directory that has one; its icons
+/// may be spread over all base directories. Parsed themes are kept until
+/// [`XdgIconThemeRepository::reload`].
+#[derive(Debug)]
+pub struct XdgIconThemeRepository {
+    base_dirs: Vec<PathBuf>,
+    fallback_dirs: Vec<PathBuf>,
+    themes: Mutex<HashMap<String, Option<Arc<IconTheme>>>>,
+}
+
+impl XdgIconThemeRepository {
+    /// Searches themes in `base_dirs` and unthemed icons in `fallback_dirs` (most important first).
+    pub fn new(base_dirs: Vec<PathBuf>, fallback_dirs: Vec<PathBuf>) -> Self {
+        Self { base_dirs, fallback_dirs, themes: Mutex::new(HashMap::new()) }
+    }
+
+    /// Searches the directories from [`icon_base_dirs`] and `/usr/share/pixmaps`.
+    pub fn from_environment() -> Self {
+        Self::new(icon_base_dirs(), vec![PathBuf::from("/usr/share/pixmaps")])
+    }
+
+    /// Forgets the parsed themes, e.g. after themes were installed or removed.
+    pub fn reload(&self) {
+        self.themes.lock().unwrap().clear();
+    }
+
+    fn theme(&self, name: &str) -> Option<Arc<IconTheme>> {
+        let mut themes = self.themes.lock().unwrap();
+        themes
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                self.base_dirs
+                    .iter()
+                    .find_map(|dir| std::fs::read_to_string(dir.join(name).join("index.theme")).ok())
+                    .map(|content| Arc::new(IconTheme::parse(&content)))
+            })
+            .clone()
+    }
+
+    /// The first existing icon file named `icon_name` in `subdir` of `theme` in any base directory.
+    fn find_file(&self, theme: &str, subdir: &str, icon_name: &str) -> Option<PathBuf> {
+        self.base_dirs.iter().find_map(|dir| find_icon_file(&dir.join(theme).join(subdir), icon_name))
+    }
+}
+
+fn find_icon_file(dir: &Path, icon_name: &str) -> Option<PathBuf> {
+    ICON_EXTENSIONS
+        .iter()
+        .map(|extension| dir.join(format!("{}.{}", icon_name, extension)))
+        .find(|path| path.is_file())
+}
+
+#[async_trait]
+impl IconThemeRepository for XdgIconThemeRepository {
+    async fn lookup_icon(&self, theme: &str, icon_name: &str, size: u32, scale: u32) -> DomainResult<Option<PathBuf>> {
+        let Some(index) = self.theme(theme) else { return Ok(None) };
+        let exact = index
+            .directories
+            .iter()
+            .filter(|directory| directory.matches_size(size, scale))
+            .find_map(|directory| self.find_file(theme, &directory.path, icon_name));
+        if exact.is_some() {
+            return Ok(exact);
+        }
+        let closest = index
+            .directories
+            .iter()
+            .filter_map(|directory| Some((directory.size_distance(size, scale), self.find_file(theme, &directory.path, icon_name)?)))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, path)| path);
+        Ok(closest)
+    }
+
+    async fn parent_themes(&self, theme: &str) -> DomainResult<Vec<String>> {
+        Ok(self.theme(theme).map(|index| index.inherits.clone()).unwrap_or_default())
+    }
+
+    async fn lookup_fallback_icon(&self, icon_name: &str) -> DomainResult<Option<PathBuf>> {
+        Ok(self.fallback_dirs.iter().find_map(|dir| find_icon_file(dir, icon_name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use novade_domain::services::IconResolverService;
+    use std::fs;
+
+    struct TempIcons {
+        root: PathBuf,
+    }
+
+    impl TempIcons {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("novade-icon-themes-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            Self { root }
+        }
+
+        fn write(&self, path: &str, content: &str) {
+            let path = self.root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        fn repository(&self) -> XdgIconThemeRepository {
+            XdgIconThemeRepository::new(vec![self.root.join("user"), self.root.join("system")], vec![self.root.join("pixmaps")])
+        }
+    }
+
+    impl Drop for TempIcons {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    const HICOLOR: &str = "[Icon Theme]\nName=Hicolor\nDirectories=16x16/apps,48x48/apps,scalable/apps\n\n\
+        [16x16/apps]\nSize=16\nType=Threshold\n\n[48x48/apps]\nSize=48\nType=Fixed\n\n\
+        [scalable/apps]\nSize=128\nType=Scalable\nMinSize=32\nMaxSize=256\n";
+
+    #[test]
+    fn test_parse_index_theme() {
+        let theme = IconTheme::parse(
+            "[Icon Theme]\nName=Papirus\nInherits=breeze, hicolor\nDirectories=24x24/apps,missing\n\
+             ScaledDirectories=24x24@2x/apps\n\n[24x24/apps]\nSize=24\n\n[24x24@2x/apps]\nSize=24\nScale=2\nType=Fixed\n",
+        );
+        assert_eq!(theme.inherits, vec!["breeze".to_string(), "hicolor".to_string()]);
+        assert_eq!(theme.directories.len(), 2);
+        let threshold = &theme.directories[0];
+        assert_eq!((threshold.kind, threshold.scale, threshold.threshold), (IconDirectoryType::Threshold, 1, 2));
+        assert!(threshold.matches_size(22, 1) && !threshold.matches_size(22, 2));
+        assert!(theme.directories[1].matches_size(24, 2));
+        assert_eq!(theme.directories[1].size_distance(16, 1), 32);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_prefers_matching_then_closest_size() {
+        let icons = TempIcons::new("lookup");
+        icons.write("system/hicolor/index.theme", HICOLOR);
+        icons.write("system/hicolor/16x16/apps/editor.png", "");
+        icons.write("user/hicolor/48x48/apps/editor.png", "");
+        icons.write("system/hicolor/scalable/apps/editor.svg", "");
+        icons.write("pixmaps/legacy.xpm", "");
+        let repository = icons.repository();
+
+        let lookup = |size| repository.lookup_icon("hicolor", "editor", size, 1);
+        assert_eq!(lookup(48).await.unwrap(), Some(icons.root.join("user/hicolor/48x48/apps/editor.png")));
+        assert_eq!(lookup(17).await.unwrap(), Some(icons.root.join("system/hicolor/16x16/apps/editor.png")));
+        assert_eq!(lookup(64).await.unwrap(), Some(icons.root.join("system/hicolor/scalable/apps/editor.svg")));
+        // No directory matches 22; the 16px icon is closer than the scalable one from 32px.
+        assert_eq!(lookup(22).await.unwrap(), Some(icons.root.join("system/hicolor/16x16/apps/editor.png")));
+        assert_eq!(repository.lookup_icon("unknown", "editor", 48, 1).await.unwrap(), None);
+        assert_eq!(repository.lookup_fallback_icon("legacy").await.unwrap(), Some(icons.root.join("pixmaps/legacy.xpm")));
+    }
+
+    #[tokio::test]
+    async fn test_resolver_uses_theme_chain() {
+        let icons = TempIcons::new("resolver");
+        icons.write("system/hicolor/index.theme", HICOLOR);
+        icons.write("system/hicolor/48x48/apps/firefox.png", "");
+        icons.write("system/hicolor/48x48/apps/gimp.png", "");
+        icons.write(
+            "system/Nova/index.theme",
+            "[Icon Theme]\nName=Nova\nInherits=hicolor\nDirectories=apps/48\n\n[apps/48]\nSize=48\nType=Fixed\n",
+        );
+        icons.write("system/Nova/apps/48/firefox.svg", "");
+
+        let service = IconResolverService::new(Arc::new(icons.repository()));
+        assert_eq!(service.resolve("firefox", 48, 1).await.unwrap(), Some(icons.root.join("system/hicolor/48x48/apps/firefox.png")));
+        service.set_icon_theme("Nova");
+        assert_eq!(service.resolve("firefox", 48, 1).await.unwrap(), Some(icons.root.join("system/Nova/apps/48/firefox.svg")));
+        assert_eq!(service.resolve("gimp", 48, 1).await.unwrap(), Some(icons.root.join("system/hicolor/48x48/apps/gimp.png")));
+    }
+}
@@ -0,0 +1,605 @@
+// src/repositories/memory.rs
+
+//! In-memory repositories.
+//!
+//! The repositories follow the contracts documented on the domain traits: adding an entity
+//! whose ID (or, for workspaces, name) is already taken fails with
+//! `DomainError::Conflict`, and updating or removing an unknown entity fails with
+//! `DomainError::EntityNotFound`. Entities and audit records are returned in insertion order;
+//! preferences and display layouts are returned sorted by key.
+//!
+//! The application, workspace and preference repositories announce every successful write
+//! as a [`RepositoryChange`] on their `changes` signal, after the write is stored.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use novade_core::signal::Signal;
+use novade_core::types::{NovaId, Timestamp};
+use novade_domain::entities::{Application, AuditRecord, DisplayLayout, UserPreferenceSetting, Workspace};
+use novade_domain::repositories::{
+    ApplicationQuery, ApplicationRepository, AuditQuery, AuditRepository, DisplayLayoutRepository, RepositoryChange,
+    ScopedPreference, UserPreferenceRepository, WorkspaceRepository,
+};
+use novade_domain::{DomainError, DomainResult};
+
+/// [`ApplicationRepository`] keeping applications in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryApplicationRepository {
+    applications: Mutex<Vec<Application>>,
+    changes: Arc<Signal<RepositoryChange<Application>>>,
+}
+
+impl InMemoryApplicationRepository {
+    /// Creates an empty repository.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a repository containing `applications`.
+    pub fn with_applications(applications: impl IntoIterator<Item = Application>) -> Self {
+        Self { applications: Mutex::new(applications.into_iter().collect()), ..Self::default() }
+    }
+
+    /// Number of stored applications.
+    pub fn len(&self) -> usize {
+        self.applications.lock().unwrap().len()
+    }
+
+    /// Whether no applications are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl ApplicationRepository for InMemoryApplicationRepository {
+    async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Application>> {
+        Ok(self.applications.lock().unwrap().iter().find(|app| &app.id == id).cloned())
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<Application>> {
+        Ok(self.applications.lock().unwrap().iter().filter(|app| !app.is_archived()).cloned().collect())
+    }
+
+    async fn get_archived(&self) -> DomainResult<Vec<Application>> {
+        Ok(self.applications.lock().unwrap().iter().filter(|app| app.is_archived()).cloned().collect())
+    }
+
+    /// Filters without copying the non-matching applications.
+    async fn find(&self, query: &ApplicationQuery) -> DomainResult<Vec<Application>> {
+        Ok(self.applications.lock().unwrap().iter().filter(|app| query.matches(app)).cloned().collect())
+    }
+
+    /// Case-insensitive match on the tags, skipping archived applications.
+    async fn find_by_tag(&self, tag: &str) -> DomainResult<Vec<Application>> {
+        let tag = tag.to_lowercase();
+        Ok(self
+            .applications
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|app| !app.is_archived() && app.tags.iter().any(|t| t.to_lowercase() == tag))
+            .cloned()
+            .collect())
+    }
+
+    async fn add(&self, application: &Application) -> DomainResult<()> {
+        {
+            let mut applications = self.applications.lock().unwrap();
+            if applications.iter().any(|app| app.id == application.id) {
+                return Err(DomainError::conflict::<Application>("id", &application.id));
+            }
+            applications.push(application.clone());
+        }
+        self.changes.emit(RepositoryChange::Added(application.clone()));
+        Ok(())
+    }
+
+    async fn update(&self, application: &Application) -> DomainResult<()> {
+        {
+            let mut applications = self.applications.lock().unwrap();
+            let stored = applications
+                .iter_mut()
+                .find(|app| app.id == application.id)
+                .ok_or_else(|| DomainError::not_found::<Application>(&application.id))?;
+            *stored = application.clone();
+        }
+        self.changes.emit(RepositoryChange::Updated(application.clone()));
+        Ok(())
+    }
+
+    /// Checks all changes before applying any, so a failing batch leaves the repository unchanged.
+    async fn save_batch(&self, added: &[Application], updated: &[Application]) -> DomainResult<()> {
+        {
+            let mut applications = self.applications.lock().unwrap();
+            for (index, application) in added.iter().enumerate() {
+                if applications.iter().chain(&added[..index]).any(|app| app.id == application.id) {
+                    return Err(DomainError::conflict::<Application>("id", &application.id));
+                }
+            }
+            if let Some(unknown) = updated.iter().find(|application| !applications.iter().any(|app| app.id == application.id)) {
+                return Err(DomainError::not_found::<Application>(&unknown.id));
+            }
+            for application in updated {
+                if let Some(stored) = applications.iter_mut().find(|app| app.id == application.id) {
+                    *stored = application.clone();
+                }
+            }
+            applications.extend(added.iter().cloned());
+        }
+        for application in updated {
+            self.changes.emit(RepositoryChange::Updated(application.clone()));
+        }
+        for application in added {
+            self.changes.emit(RepositoryChange::Added(application.clone()));
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, id: &NovaId) -> DomainResult<()> {
+        let removed = {
+            let mut applications = self.applications.lock().unwrap();
+            let index = applications.iter().position(|app| &app.id == id).ok_or_else(|| DomainError::not_found::<Application>(id))?;
+            applications.remove(index)
+        };
+        self.changes.emit(RepositoryChange::Removed(removed));
+        Ok(())
+    }
+
+    fn changes(&self) -> Option<Arc<Signal<RepositoryChange<Application>>>> {
+        Some(self.changes.clone())
+    }
+}
+
+/// [`WorkspaceRepository`] keeping workspaces in memory. Workspace names are unique.
+#[derive(Debug, Default)]
+pub struct InMemoryWorkspaceRepository {
+    workspaces: Mutex<Vec<Workspace>>,
+    changes: Arc<Signal<RepositoryChange<Workspace>>>,
+}
+
+impl InMemoryWorkspaceRepository {
+    /// Creates an empty repository.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a repository containing `workspaces`.
+    pub fn with_workspaces(workspaces: impl IntoIterator<Item = Workspace>) -> Self {
+        Self { workspaces: Mutex::new(workspaces.into_iter().collect()), ..Self::default() }
+    }
+}
+
+#[async_trait]
+impl WorkspaceRepository for InMemoryWorkspaceRepository {
+    async fn get_by_id(&self, id: &NovaId) -> DomainResult<Option<Workspace>> {
+        Ok(self.workspaces.lock().unwrap().iter().find(|ws| &ws.id == id).cloned())
+    }
+
+    async fn get_by_name(&self, name: &str) -> DomainResult<Option<Workspace>> {
+        Ok(self.workspaces.lock().unwrap().iter().find(|ws| ws.name == name).cloned())
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<Workspace>> {
+        Ok(self.workspaces.lock().unwrap().iter().filter(|ws| !ws.is_archived()).cloned().collect())
+    }
+
+    async fn get_archived(&self) -> DomainResult<Vec<Workspace>> {
+        Ok(self.workspaces.lock().unwrap().iter().filter(|ws| ws.is_archived()).cloned().collect())
+    }
+
+    async fn get_all_including_archived(&self) -> DomainResult<Vec<Workspace>> {
+        Ok(self.workspaces.lock().unwrap().clone())
+    }
+
+    async fn add(&self, workspace: &Workspace) -> DomainResult<()> {
+        {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            if workspaces.iter().any(|ws| ws.id == workspace.id) {
+                return Err(DomainError::conflict::<Workspace>("id", &workspace.id));
+            }
+            if let Some(existing) = workspaces.iter().find(|ws| ws.name == workspace.name) {
+                return Err(DomainError::conflict::<Workspace>("name", &existing.id));
+            }
+            workspaces.push(workspace.clone());
+        }
+        self.changes.emit(RepositoryChange::Added(workspace.clone()));
+        Ok(())
+    }
+
+    async fn update(&self, workspace: &Workspace) -> DomainResult<()> {
+        {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            if let Some(existing) = workspaces.iter().find(|ws| ws.name == workspace.name && ws.id != workspace.id) {
+                return Err(DomainError::conflict::<Workspace>("name", &existing.id));
+            }
+            let stored = workspaces
+                .iter_mut()
+                .find(|ws| ws.id == workspace.id)
+                .ok_or_else(|| DomainError::not_found::<Workspace>(&workspace.id))?;
+            *stored = workspace.clone();
+        }
+        self.changes.emit(RepositoryChange::Updated(workspace.clone()));
+        Ok(())
+    }
+
+    async fn remove(&self, id: &NovaId) -> DomainResult<()> {
+        let removed = {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let index = workspaces.iter().position(|ws| &ws.id == id).ok_or_else(|| DomainError::not_found::<Workspace>(id))?;
+            workspaces.remove(index)
+        };
+        self.changes.emit(RepositoryChange::Removed(removed));
+        Ok(())
+    }
+
+    fn changes(&self) -> Option<Arc<Signal<RepositoryChange<Workspace>>>> {
+        Some(self.changes.clone())
+    }
+}
+
+/// [`UserPreferenceRepository`] keeping settings in memory, keyed by user and setting key.
+///
+/// The settings of each user (and the system-wide settings stored under `None`) are kept
+/// apart; no lookup falls back to another user's settings.
+#[derive(Debug, Default)]
+pub struct InMemoryUserPreferenceRepository {
+    settings: Mutex<HashMap<Option<NovaId>, BTreeMap<String, UserPreferenceSetting>>>,
+    changes: Arc<Signal<RepositoryChange<ScopedPreference>>>,
+}
+
+impl InMemoryUserPreferenceRepository {
+    /// Creates an empty repository.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a repository containing `settings` as system-wide settings (user `None`).
+    pub fn with_settings(settings: impl IntoIterator<Item = UserPreferenceSetting>) -> Self {
+        let system_wide = settings.into_iter().map(|s| (s.key.clone(), s)).collect();
+        Self { settings: Mutex::new(HashMap::from([(None, system_wide)])), ..Self::default() }
+    }
+}
+
+#[async_trait]
+impl UserPreferenceRepository for InMemoryUserPreferenceRepository {
+    async fn get_preference(&self, user_id: Option<NovaId>, key: &str) -> DomainResult<Option<UserPreferenceSetting>> {
+        Ok(self.settings.lock().unwrap().get(&user_id).and_then(|settings| settings.get(key)).cloned())
+    }
+
+    async fn get_all_preferences(&self, user_id: Option<NovaId>) -> DomainResult<Vec<UserPreferenceSetting>> {
+        Ok(self.settings.lock().unwrap().get(&user_id).map(|settings| settings.values().cloned().collect()).unwrap_or_default())
+    }
+
+    async fn set_preference(&self, user_id: Option<NovaId>, setting: &UserPreferenceSetting) -> DomainResult<()> {
+        let previous =
+            self.settings.lock().unwrap().entry(user_id.clone()).or_default().insert(setting.key.clone(), setting.clone());
+        let scoped = (user_id, setting.clone());
+        self.changes.emit(match previous {
+            Some(_) => RepositoryChange::Updated(scoped),
+            None => RepositoryChange::Added(scoped),
+        });
+        Ok(())
+    }
+
+    /// Removing a key that is not set is not an error and announces no change.
+    async fn remove_preference(&self, user_id: Option<NovaId>, key: &str) -> DomainResult<()> {
+        let removed = self.settings.lock().unwrap().get_mut(&user_id).and_then(|settings| settings.remove(key));
+        if let Some(removed) = removed {
+            self.changes.emit(RepositoryChange::Removed((user_id, removed)));
+        }
+        Ok(())
+    }
+
+    fn changes(&self) -> Option<Arc<Signal<RepositoryChange<ScopedPreference>>>> {
+        Some(self.changes.clone())
+    }
+}
+
+/// [`AuditRepository`] keeping the audit log in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryAuditRepository {
+    records: Mutex<Vec<AuditRecord>>,
+}
+
+impl InMemoryAuditRepository {
+    /// Creates an empty audit log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored records.
+    pub fn len(&self) -> usize {
+        self.records.lock().unwrap().len()
+    }
+
+    /// Whether no records are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl AuditRepository for InMemoryAuditRepository {
+    async fn append(&self, record: &AuditRecord) -> DomainResult<()> {
+        self.records.lock().unwrap().push(record.clone());
+        Ok(())
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<AuditRecord>> {
+        Ok(self.records.lock().unwrap().clone())
+    }
+
+    /// Filters without copying the non-matching records.
+    async fn find(&self, query: &AuditQuery) -> DomainResult<Vec<AuditRecord>> {
+        Ok(self.records.lock().unwrap().iter().filter(|record| query.matches(record)).cloned().collect())
+    }
+
+    async fn remove_before(&self, cutoff: &Timestamp) -> DomainResult<()> {
+        self.records.lock().unwrap().retain(|record| &record.timestamp >= cutoff);
+        Ok(())
+    }
+}
+
+/// [`DisplayLayoutRepository`] keeping display layouts in memory, keyed by their connectors.
+#[derive(Debug, Default)]
+pub struct InMemoryDisplayLayoutRepository {
+    layouts: Mutex<BTreeMap<String, DisplayLayout>>,
+}
+
+impl InMemoryDisplayLayoutRepository {
+    /// Creates an empty repository.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DisplayLayoutRepository for InMemoryDisplayLayoutRepository {
+    async fn get_by_key(&self, key: &str) -> DomainResult<Option<DisplayLayout>> {
+        Ok(self.layouts.lock().unwrap().get(key).cloned())
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<DisplayLayout>> {
+        Ok(self.layouts.lock().unwrap().values().cloned().collect())
+    }
+
+    async fn save(&self, layout: &DisplayLayout) -> DomainResult<()> {
+        self.layouts.lock().unwrap().insert(layout.key(), layout.clone());
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> DomainResult<()> {
+        self.layouts.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use novade_domain::entities::{ApplicationType, AuditOperation, PreferenceValue};
+    use novade_domain::services::{ApplicationService, AuditService, DryRun, ImportConflictPolicy, WorkspaceService};
+    use std::sync::Arc;
+
+    fn app(name: &str) -> Application {
+        Application::new_desktop(name.to_string(), format!("/usr/bin/{}", name.to_lowercase()), None)
+    }
+
+    #[tokio::test]
+    async fn test_application_repository_through_service() {
+        let repository = Arc::new(InMemoryApplicationRepository::new());
+        let service = ApplicationService::new(repository.clone());
+        let firefox = service.register_application(app("Firefox")).await.unwrap();
+        service.register_application(app("Files")).await.unwrap();
+
+        assert_eq!(service.find_applications_by_name("fi").await.unwrap().len(), 2);
+        assert_eq!(service.find_applications_by_name("FIRE").await.unwrap(), vec![firefox.clone()]);
+        assert!(matches!(
+            service.register_application(firefox.clone()).await,
+            Err(DomainError::Conflict { .. })
+        ));
+
+        let mut renamed = firefox.clone();
+        renamed.display_name = Some("Web Browser".to_string());
+        repository.update(&renamed).await.unwrap();
+        assert_eq!(repository.find_by_name("browser").await.unwrap(), vec![renamed]);
+
+        repository.remove(&firefox.id).await.unwrap();
+        assert_eq!(repository.len(), 1);
+        assert!(matches!(repository.remove(&firefox.id).await, Err(DomainError::EntityNotFound { .. })));
+        assert!(matches!(repository.update(&firefox).await, Err(DomainError::EntityNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_archived_entities_are_left_out_of_get_all() {
+        let mut archived = app("Gimp");
+        archived.deleted_at = Some(Timestamp::now());
+        let files = app("Files");
+        let applications = InMemoryApplicationRepository::with_applications([archived.clone(), files.clone()]);
+        assert_eq!(applications.get_all().await.unwrap(), vec![files.clone()]);
+        assert_eq!(applications.get_archived().await.unwrap(), vec![archived.clone()]);
+        assert_eq!(applications.find(&ApplicationQuery::new().including_archived()).await.unwrap(), vec![archived, files]);
+
+        let mut old = Workspace::new("Alt".to_string(), None);
+        old.deleted_at = Some(Timestamp::now());
+        let work = Workspace::new("Arbeit".to_string(), None);
+        let workspaces = InMemoryWorkspaceRepository::with_workspaces([old.clone(), work.clone()]);
+        assert_eq!(workspaces.get_all().await.unwrap(), vec![work.clone()]);
+        assert_eq!(workspaces.get_archived().await.unwrap(), vec![old.clone()]);
+        assert_eq!(workspaces.get_all_including_archived().await.unwrap(), vec![old, work]);
+    }
+
+    #[tokio::test]
+    async fn test_applications_by_tag() {
+        let service = ApplicationService::new(Arc::new(InMemoryApplicationRepository::new()));
+        let gimp = service.register_application(app("Gimp")).await.unwrap();
+        let inkscape = service.register_application(app("Inkscape")).await.unwrap();
+        service.register_application(app("Files")).await.unwrap();
+
+        service.tag(&gimp.id, "Graphics").await.unwrap();
+        let inkscape = service.tag(&inkscape.id, "graphics").await.unwrap();
+        assert_eq!(service.list_by_tag("GRAPHICS").await.unwrap().len(), 2);
+
+        service.untag(&gimp.id, "Graphics").await.unwrap();
+        assert_eq!(service.list_by_tag("graphics").await.unwrap(), vec![inkscape]);
+    }
+
+    #[tokio::test]
+    async fn test_import_batch_is_all_or_nothing() {
+        let repository = Arc::new(InMemoryApplicationRepository::new());
+        let service = ApplicationService::new(repository.clone());
+        let mut editor = service.register_application(app("Editor")).await.unwrap();
+        editor.display_name = Some("Text Editor".to_string());
+        let report = service
+            .import_batch(vec![editor.clone(), app("Files"), app("Terminal")], ImportConflictPolicy::Replace, DryRun::No)
+            .await
+            .unwrap();
+        assert_eq!((report.added.len(), report.replaced.len()), (2, 1));
+        assert_eq!(repository.get_by_id(&editor.id).await.unwrap(), Some(editor.clone()));
+
+        let unknown = app("Unknown");
+        assert!(matches!(
+            repository.save_batch(&[app("Browser")], &[unknown]).await,
+            Err(DomainError::EntityNotFound { .. })
+        ));
+        assert_eq!(repository.len(), 3, "A failed batch adds nothing");
+    }
+
+    #[tokio::test]
+    async fn test_application_query() {
+        let mut gimp = app("Gimp");
+        gimp.categories = Some(vec!["Graphics".to_string()]);
+        gimp.keywords = Some(vec!["photo".to_string(), "paint".to_string()]);
+        gimp.icon_name = Some("gimp".to_string());
+        let mut shell = app("Shell");
+        shell.app_type = ApplicationType::Cli;
+        let repository = InMemoryApplicationRepository::with_applications([gimp.clone(), shell.clone(), app("Files")]);
+
+        let graphics = ApplicationQuery::new().category("graphics").keyword("PHOTO");
+        assert_eq!(repository.find(&graphics).await.unwrap(), vec![gimp]);
+        let without_icon = ApplicationQuery::new().has_icon(false).app_type_in([ApplicationType::Cli]);
+        assert_eq!(repository.find(&without_icon).await.unwrap(), vec![shell]);
+        assert_eq!(repository.find(&ApplicationQuery::new()).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_workspace_names_are_unique() {
+        let service = WorkspaceService::new(Arc::new(InMemoryWorkspaceRepository::new()));
+        let work = service.create_new_workspace("Work".to_string(), None).await.unwrap();
+        assert!(matches!(
+            service.create_new_workspace("Work".to_string(), None).await,
+            Err(DomainError::Conflict { .. })
+        ));
+        assert_eq!(service.get_workspace_details(&work.id).await.unwrap(), Some(work));
+
+        let repository = InMemoryWorkspaceRepository::with_workspaces([
+            Workspace::new("One".to_string(), None),
+            Workspace::new("Two".to_string(), None),
+        ]);
+        let mut two = repository.get_by_name("Two").await.unwrap().unwrap();
+        two.name = "One".to_string();
+        assert!(repository.update(&two).await.is_err(), "Renaming onto an existing name");
+    }
+
+    #[tokio::test]
+    async fn test_preferences_overwrite_by_key() {
+        let repository = InMemoryUserPreferenceRepository::new();
+        let user = Some(NovaId::new());
+        let dark_mode = |enabled| UserPreferenceSetting::new_boolean("theme.dark_mode", "Dark mode", enabled);
+        repository.set_preference(user.clone(), &dark_mode(false)).await.unwrap();
+        repository.set_preference(user.clone(), &dark_mode(true)).await.unwrap();
+        repository
+            .set_preference(user.clone(), &UserPreferenceSetting::new_string("keyboard.layout", "Layout", "de".to_string()))
+            .await
+            .unwrap();
+
+        let all = repository.get_all_preferences(user.clone()).await.unwrap();
+        assert_eq!(all.iter().map(|s| s.key.as_str()).collect::<Vec<_>>(), vec!["keyboard.layout", "theme.dark_mode"]);
+        let stored = repository.get_preference(user.clone(), "theme.dark_mode").await.unwrap().unwrap();
+        assert_eq!(stored.value, PreferenceValue::Boolean(true));
+        assert!(repository.get_preference(user.clone(), "missing").await.unwrap().is_none());
+
+        // Other users and the system-wide scope do not see the user's settings.
+        assert!(repository.get_preference(None, "theme.dark_mode").await.unwrap().is_none());
+        assert!(repository.get_all_preferences(Some(NovaId::new())).await.unwrap().is_empty());
+        repository.remove_preference(user.clone(), "theme.dark_mode").await.unwrap();
+        assert_eq!(repository.get_all_preferences(user).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_announced_as_changes() {
+        let applications = InMemoryApplicationRepository::new();
+        let application_changes = applications.changes().unwrap().subscribe();
+        let added = app("Editor");
+        applications.add(&added).await.unwrap();
+        let mut editor = added.clone();
+        editor.display_name = Some("Text Editor".to_string());
+        applications.update(&editor).await.unwrap();
+        assert!(applications.add(&editor).await.is_err());
+        applications.remove(&editor.id).await.unwrap();
+        assert_eq!(
+            application_changes.try_iter().collect::<Vec<_>>(),
+            vec![
+                RepositoryChange::Added(added),
+                RepositoryChange::Updated(editor.clone()),
+                RepositoryChange::Removed(editor.clone()),
+            ]
+        );
+
+        let workspaces = InMemoryWorkspaceRepository::new();
+        let workspace_changes = workspaces.changes().unwrap().subscribe();
+        let work = Workspace::new("Work".to_string(), None);
+        workspaces.add(&work).await.unwrap();
+        assert_eq!(workspace_changes.try_recv().unwrap(), RepositoryChange::Added(work));
+
+        let preferences = InMemoryUserPreferenceRepository::new();
+        let preference_changes = preferences.changes().unwrap().subscribe();
+        let user = Some(NovaId::new());
+        let layout = UserPreferenceSetting::new_string("keyboard.layout", "Layout", "de".to_string());
+        preferences.set_preference(user.clone(), &layout).await.unwrap();
+        preferences.set_preference(user.clone(), &layout).await.unwrap();
+        preferences.remove_preference(user.clone(), "keyboard.layout").await.unwrap();
+        preferences.remove_preference(user.clone(), "keyboard.layout").await.unwrap();
+        let changes: Vec<_> = preference_changes.try_iter().collect();
+        assert_eq!(
+            changes,
+            vec![
+                RepositoryChange::Added((user.clone(), layout.clone())),
+                RepositoryChange::Updated((user.clone(), layout.clone())),
+                RepositoryChange::Removed((user, layout)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_services_write_audit_log() {
+        let log = Arc::new(InMemoryAuditRepository::new());
+        let audit = Arc::new(AuditService::new(log.clone()));
+        audit.set_actor("alice");
+        let applications = ApplicationService::new(Arc::new(InMemoryApplicationRepository::new())).with_audit_log(audit.clone());
+        let workspaces = WorkspaceService::new(Arc::new(InMemoryWorkspaceRepository::new())).with_audit_log(audit.clone());
+
+        let gimp = applications.register_application(app("Gimp")).await.unwrap();
+        applications.tag(&gimp.id, "Graphics").await.unwrap();
+        applications.archive_application(&gimp.id).await.unwrap();
+        let work = workspaces.create_new_workspace("Work".to_string(), None).await.unwrap();
+        workspaces.rename_workspace(&work.id, "Office".to_string()).await.unwrap();
+        // Switching workspaces only updates usage statistics and is not audited.
+        workspaces.activate_workspace(&work.id).await.unwrap();
+
+        assert_eq!(log.len(), 5);
+        let history = audit.history_of("Application", &gimp.id.to_string()).await.unwrap();
+        let operations: Vec<AuditOperation> = history.iter().map(|record| record.operation).collect();
+        assert_eq!(operations, vec![AuditOperation::Archived, AuditOperation::Updated, AuditOperation::Created]);
+        assert_eq!(history[1].summary, r#"tags: [] → ["Graphics"]"#);
+        let renames = audit.query(&AuditQuery::new().entity_type("Workspace").operation(AuditOperation::Updated)).await.unwrap();
+        assert_eq!(renames[0].summary, r#"name: "Work" → "Office""#);
+        assert_eq!(audit.query(&AuditQuery::new().actor("alice")).await.unwrap().len(), 5);
+
+        log.remove_before(&Timestamp::now()).await.unwrap();
+        assert!(log.is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+// src/repositories/mime_apps.rs
+
+//! MIME associations from `mimeapps.list` files.
+//!
+//! Implements the storage side of the MIME Applications Associations Specification. The
+//! user's `$XDG_CONFIG_HOME/mimeapps.list` takes precedence over the system files in
+//! `$XDG_CONFIG_DIRS` and `$XDG_DATA_DIRS/applications`; changes are only ever written to
+//! the user's file. Desktop file IDs carry the `.desktop` suffix in the files and are
+//! stripped of it in the domain.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use novade_core::CoreError;
+use novade_domain::entities::MimeAssociation;
+use novade_domain::repositories::MimeAssociationRepository;
+use novade_domain::{DomainError, DomainResult};
+
+const DEFAULT_GROUP: &str = "Default Applications";
+const ADDED_GROUP: &str = "Added Associations";
+const REMOVED_GROUP: &str = "Removed Associations";
+
+/// The `mimeapps.list` files in order of decreasing importance; the first one is the user's.
+pub fn mimeapps_files() -> Vec<PathBuf> {
+    let non_empty = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    let mut files = Vec::new();
+    let user_file = match non_empty("XDG_CONFIG_HOME") {
+        Some(config_home) => Some(PathBuf::from(config_home).join("mimeapps.list")),
+        None => novade_core::utils::get_app_config_dir("mimeapps.list"),
+    };
+    files.extend(user_file);
+    let config_dirs = non_empty("XDG_CONFIG_DIRS").unwrap_or_else(|| "/etc/xdg".into());
+    files.extend(std::env::split_paths(&config_dirs).map(|dir| dir.join("mimeapps.list")));
+    let data_dirs = non_empty("XDG_DATA_DIRS").unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+    files.extend(std::env::split_paths(&data_dirs).map(|dir| dir.join("applications/mimeapps.list")));
+    files
+}
+
+/// The groups of a `mimeapps.list` file, keeping their order and unknown groups so the file
+/// can be written back without losing entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MimeAppsList {
+    groups: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl MimeAppsList {
+    /// Parses the contents of a `mimeapps.list` file. Comments are dropped.
+    pub fn parse(content: &str) -> Self {
+        let mut list = Self::default();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(group) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                list.groups.push((group.to_string(), Vec::new()));
+            } else if let (Some((key, value)), Some((_, entries))) = (line.split_once('='), list.groups.last_mut()) {
+                entries.push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        list
+    }
+
+    /// The desktop file IDs listed for `mime_type` in `group`, without the `.desktop` suffix.
+    pub fn applications(&self, group: &str, mime_type: &str) -> Vec<String> {
+        self.groups
+            .iter()
+            .filter(|(name, _)| name == group)
+            .flat_map(|(_, entries)| entries.iter().filter(|(key, _)| key == mime_type))
+            .flat_map(|(_, value)| value.split(';'))
+            .filter(|id| !id.is_empty())
+            .map(|id| id.strip_suffix(".desktop").unwrap_or(id).to_string())
+            .collect()
+    }
+
+    /// The MIME types with an entry in any of the association groups.
+    pub fn mime_types(&self) -> BTreeSet<String> {
+        self.groups
+            .iter()
+            .filter(|(name, _)| [DEFAULT_GROUP, ADDED_GROUP, REMOVED_GROUP].contains(&name.as_str()))
+            .flat_map(|(_, entries)| entries.iter().map(|(key, _)| key.clone()))
+            .collect()
+    }
+
+    /// Replaces the entry for `mime_type` in `group`; an empty list removes it.
+    pub fn set_applications(&mut self, group: &str, mime_type: &str, applications: &[String]) {
+        for (_, entries) in self.groups.iter_mut().filter(|(name, _)| name == group) {
+            entries.retain(|(key, _)| key != mime_type);
+        }
+        if applications.is_empty() {
+            return;
+        }
+        let value: String = applications.iter().map(|id| format!("{}.desktop;", id)).collect();
+        match self.groups.iter_mut().find(|(name, _)| name == group) {
+            Some((_, entries)) => entries.push((mime_type.to_string(), value)),
+            None => self.groups.push((group.to_string(), vec![(mime_type.to_string(), value)])),
+        }
+    }
+
+    /// Serializes the list in `mimeapps.list` format, omitting empty groups.
+    pub fn to_file_content(&self) -> String {
+        let mut content = String::new();
+        for (group, entries) in self.groups.iter().filter(|(_, entries)| !entries.is_empty()) {
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(&format!("[{}]\n", group));
+            for (key, value) in entries {
+                content.push_str(&format!("{}={}\n", key, value));
+            }
+        }
+        content
+    }
+}
+
+fn read_list(path: &PathBuf) -> MimeAppsList {
+    std::fs::read_to_string(path).map(|content| MimeAppsList::parse(&content)).unwrap_or_default()
+}
+
+/// [`MimeAssociationRepository`] over the user's and the system's `mimeapps.list` files.
+///
+/// The files are read when the repository is created and on [`MimeAppsListRepository::reload`].
+#[derive(Debug)]
+pub struct MimeAppsListRepository {
+    user_file: PathBuf,
+    /// The user's list followed by the system lists, in order of decreasing importance.
+    lists: Mutex<Vec<MimeAppsList>>,
+    system_files: Vec<PathBuf>,
+}
+
+impl MimeAppsListRepository {
+    /// Reads `user_file` and `system_files` (most important first).
+    pub fn new(user_file: PathBuf, system_files: Vec<PathBuf>) -> Self {
+        let repository = Self { user_file, lists: Mutex::new(Vec::new()), system_files };
+        repository.reload();
+        repository
+    }
+
+    /// Reads the files from [`mimeapps_files`].
+    ///
+    /// # Returns
+    /// `None` if no user configuration directory can be determined.
+    pub fn from_environment() -> Option<Self> {
+        let mut files = mimeapps_files().into_iter();
+        let user_file = files.next()?;
+        Some(Self::new(user_file, files.collect()))
+    }
+
+    /// Reads all files again, e.g. after another program changed them.
+    pub fn reload(&self) {
+        let lists = std::iter::once(&self.user_file).chain(&self.system_files).map(read_list).collect();
+        *self.lists.lock().unwrap() = lists;
+    }
+
+    fn merged(lists: &[MimeAppsList], mime_type: &str) -> Option<MimeAssociation> {
+        let mut association = MimeAssociation::new(mime_type);
+        for list in lists {
+            if association.default_application.is_none() {
+                association.default_application = list.applications(DEFAULT_GROUP, mime_type).into_iter().next();
+            }
+            for id in list.applications(REMOVED_GROUP, mime_type) {
+                if !association.removed_applications.contains(&id) {
+                    association.removed_applications.push(id);
+                }
+            }
+            for id in list.applications(ADDED_GROUP, mime_type) {
+                if !association.added_applications.contains(&id) && !association.removed_applications.contains(&id) {
+                    association.added_applications.push(id);
+                }
+            }
+        }
+        (association != MimeAssociation::new(mime_type)).then_some(association)
+    }
+}
+
+#[async_trait]
+impl MimeAssociationRepository for MimeAppsListRepository {
+    async fn get(&self, mime_type: &str) -> DomainResult<Option<MimeAssociation>> {
+        Ok(Self::merged(&self.lists.lock().unwrap(), mime_type))
+    }
+
+    async fn get_all(&self) -> DomainResult<Vec<MimeAssociation>> {
+        let lists = self.lists.lock().unwrap();
+        let mime_types: BTreeSet<String> = lists.iter().flat_map(MimeAppsList::mime_types).collect();
+        Ok(mime_types.iter().filter_map(|mime_type| Self::merged(&lists, mime_type)).collect())
+    }
+
+    /// Writes the association to the user's file, replacing its previous entries for the type.
+    async fn set(&self, association: &MimeAssociation) -> DomainResult<()> {
+        let mut lists = self.lists.lock().unwrap();
+        let mut user_list = lists.first().cloned().unwrap_or_default();
+        let mime_type = association.mime_type.as_str();
+        user_list.set_applications(DEFAULT_GROUP, mime_type, association.default_application.as_slice());
+        user_list.set_applications(ADDED_GROUP, mime_type, &association.added_applications);
+        user_list.set_applications(REMOVED_GROUP, mime_type, &association.removed_applications);
+
+        let io_error = |e: std::io::Error| {
+            DomainError::RepositoryError(CoreError::io(self.user_file.display(), e))
+        };
+        if let Some(parent) = self.user_file.parent() {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let temporary = self.user_file.with_extension("list.tmp");
+        std::fs::write(&temporary, user_list.to_file_content()).map_err(io_error)?;
+        std::fs::rename(&temporary, &self.user_file).map_err(io_error)?;
+        match lists.first_mut() {
+            Some(first) => *first = user_list,
+            None => lists.push(user_list),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("novade-mimeapps-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_parse_and_write_keep_unknown_groups() {
+        let mut list = MimeAppsList::parse(
+            "# comment\n[Default Applications]\ntext/plain=editor.desktop;other.desktop;\n\n[X-Vendor]\nkey=value\n",
+        );
+        assert_eq!(list.applications(DEFAULT_GROUP, "text/plain"), vec!["editor".to_string(), "other".to_string()]);
+
+        list.set_applications(DEFAULT_GROUP, "text/plain", &[]);
+        list.set_applications(ADDED_GROUP, "image/png", &["viewer".to_string()]);
+        assert_eq!(
+            list.to_file_content(),
+            "[X-Vendor]\nkey=value\n\n[Added Associations]\nimage/png=viewer.desktop;\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_user_file_overrides_system_file() {
+        let root = temp_root("merge");
+        let (user, system) = (root.join("user/mimeapps.list"), root.join("mimeapps.list"));
+        fs::write(
+            &system,
+            "[Default Applications]\ntext/plain=gedit.desktop;\nimage/png=eog.desktop;\n\
+             [Added Associations]\ntext/plain=kate.desktop;vim.desktop;\n",
+        )
+        .unwrap();
+        let repository = MimeAppsListRepository::new(user.clone(), vec![system]);
+
+        let mut text = repository.get("text/plain").await.unwrap().unwrap();
+        assert_eq!(text.default_application.as_deref(), Some("gedit"));
+        text.default_application = Some("kate".to_string());
+        text.removed_applications.push("vim".to_string());
+        repository.set(&text).await.unwrap();
+        assert!(fs::read_to_string(&user).unwrap().contains("text/plain=kate.desktop;"));
+
+        // A fresh repository sees the written file.
+        let reloaded = MimeAppsListRepository::new(user, vec![root.join("mimeapps.list")]);
+        let text = reloaded.get("text/plain").await.unwrap().unwrap();
+        assert_eq!(text.default_application.as_deref(), Some("kate"));
+        assert_eq!(text.added_applications, vec!["kate".to_string()]);
+        assert_eq!(text.removed_applications, vec!["vim".to_string()]);
+        assert_eq!(reloaded.get_all().await.unwrap().len(), 2);
+        assert!(reloaded.get("video/mp4").await.unwrap().is_none());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,43 @@
+// src/repositories/mod.rs
+
+//! Implementations of the repository traits defined in `novade_domain::repositories`.
+//!
+//! The [`memory`] repositories keep their entities (and an audit log) in process memory. They are meant for
+//! integration tests of domain services and UI code, and for running components that need a
+//! repository before persistent storage is available. The [`cached`] decorators add a read
+//! cache in front of any repository. The [`desktop_entries`] repository lists the installed
+//! applications from their XDG desktop entries, [`mime_apps`] stores the default
+//! applications in `mimeapps.list` files, [`icon_themes`] looks up icons in the
+//! installed icon themes, [`autostart`] manages the XDG autostart entries, [`upower`]
+//! switches power profiles and reads the battery state, [`pipewire`] controls audio devices
+//! and application volumes, and [`network_manager`] lists and (dis)connects network
+//! connections.
+
+pub mod autostart;
+pub mod cached;
+pub mod desktop_entries;
+pub mod icon_themes;
+pub mod memory;
+pub mod mime_apps;
+#[cfg(feature = "network-manager")]
+pub mod network_manager;
+#[cfg(feature = "audio-pipewire")]
+pub mod pipewire;
+#[cfg(feature = "power-upower")]
+pub mod upower;
+
+pub use self::autostart::XdgAutostartRepository;
+pub use self::cached::{CachedApplicationRepository, CachedWorkspaceRepository};
+pub use self::desktop_entries::{DesktopEntryRepository, SyncReport};
+pub use self::icon_themes::XdgIconThemeRepository;
+pub use self::memory::{
+    InMemoryApplicationRepository, InMemoryAuditRepository, InMemoryDisplayLayoutRepository, InMemoryUserPreferenceRepository,
+    InMemoryWorkspaceRepository,
+};
+pub use self::mime_apps::MimeAppsListRepository;
+#[cfg(feature = "network-manager")]
+pub use self::network_manager::NetworkManagerRepository;
+#[cfg(feature = "audio-pipewire")]
+pub use self::pipewire::PipeWireAudioRepository;
+#[cfg(feature = "power-upower")]
+pub use self::upower::UPowerRepository;
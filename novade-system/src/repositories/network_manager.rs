@@ -0,0 +1,243 @@
+// src/repositories/network_manager.rs
+
+//! Network connections through NetworkManager over D-Bus.
+//!
+//! The connections are NetworkManager's saved connection profiles of the supported types.
+//! Their state comes from the active connection objects, and the signal strength of Wi-Fi
+//! profiles from the strongest access point with the same SSID seen by any wireless device.
+//! Connections are identified by their profile UUID.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use novade_core::CoreError;
+use novade_domain::entities::{ConnectionState, ConnectionType, NetworkConnection};
+use novade_domain::repositories::NetworkRepository;
+use novade_domain::{DomainError, DomainResult};
+use zbus::proxy::{Builder, CacheProperties};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue};
+use zbus::{Connection, Proxy};
+
+const NM_DESTINATION: &str = "org.freedesktop.NetworkManager";
+const NM_PATH: &str = "/org/freedesktop/NetworkManager";
+const NM_INTERFACE: &str = "org.freedesktop.NetworkManager";
+const SETTINGS_PATH: &str = "/org/freedesktop/NetworkManager/Settings";
+const SETTINGS_INTERFACE: &str = "org.freedesktop.NetworkManager.Settings";
+const SETTINGS_CONNECTION_INTERFACE: &str = "org.freedesktop.NetworkManager.Settings.Connection";
+const ACTIVE_CONNECTION_INTERFACE: &str = "org.freedesktop.NetworkManager.Connection.Active";
+const DEVICE_INTERFACE: &str = "org.freedesktop.NetworkManager.Device";
+const WIRELESS_INTERFACE: &str = "org.freedesktop.NetworkManager.Device.Wireless";
+const ACCESS_POINT_INTERFACE: &str = "org.freedesktop.NetworkManager.AccessPoint";
+
+/// NetworkManager device type of Wi-Fi devices.
+const NM_DEVICE_TYPE_WIFI: u32 = 2;
+
+type ConnectionSettings = HashMap<String, HashMap<String, OwnedValue>>;
+
+fn bus_error(context: &str, error: impl Display) -> DomainError {
+    DomainError::RepositoryError(CoreError::IoError(std::io::Error::other(format!("{}: {}", context, error))))
+}
+
+/// Maps the `connection.type` setting of a profile to a [`ConnectionType`]; other types
+/// (bridges, loopback, ...) are not shown.
+pub fn connection_type_from_nm(connection_type: &str) -> Option<ConnectionType> {
+    match connection_type {
+        "802-3-ethernet" => Some(ConnectionType::Ethernet),
+        "802-11-wireless" => Some(ConnectionType::Wifi),
+        "vpn" | "wireguard" => Some(ConnectionType::Vpn),
+        _ => None,
+    }
+}
+
+/// Maps the `State` property of an active connection to a [`ConnectionState`].
+pub fn connection_state_from_nm(state: u32) -> ConnectionState {
+    match state {
+        1 => ConnectionState::Connecting,
+        2 => ConnectionState::Connected,
+        3 => ConnectionState::Disconnecting,
+        _ => ConnectionState::Disconnected,
+    }
+}
+
+fn setting_string(settings: &ConnectionSettings, group: &str, key: &str) -> Option<String> {
+    settings.get(group)?.get(key)?.downcast_ref::<&str>().ok().map(str::to_string)
+}
+
+fn setting_bytes(settings: &ConnectionSettings, group: &str, key: &str) -> Option<Vec<u8>> {
+    settings.get(group)?.get(key)?.try_clone().ok().and_then(|value| Vec::<u8>::try_from(value).ok())
+}
+
+/// The state of an active connection.
+struct ActiveConnection {
+    path: OwnedObjectPath,
+    state: ConnectionState,
+    interface: Option<String>,
+}
+
+/// [`NetworkRepository`] backed by NetworkManager on the system bus.
+#[derive(Debug, Clone)]
+pub struct NetworkManagerRepository {
+    connection: Connection,
+    network_manager: Proxy<'static>,
+    settings: Proxy<'static>,
+}
+
+impl NetworkManagerRepository {
+    /// Connects to the system bus.
+    pub async fn new() -> DomainResult<Self> {
+        let connection = Connection::system().await.map_err(|e| bus_error("Failed to connect to system bus", e))?;
+        let network_manager = uncached_proxy(&connection, ObjectPath::from_static_str_unchecked(NM_PATH), NM_INTERFACE).await?;
+        let settings =
+            uncached_proxy(&connection, ObjectPath::from_static_str_unchecked(SETTINGS_PATH), SETTINGS_INTERFACE).await?;
+        Ok(Self { connection, network_manager, settings })
+    }
+
+    async fn proxy(&self, path: &OwnedObjectPath, interface: &'static str) -> DomainResult<Proxy<'static>> {
+        uncached_proxy(&self.connection, path.clone().into(), interface).await
+    }
+
+    /// The active connections by profile UUID.
+    async fn active_connections(&self) -> DomainResult<HashMap<String, ActiveConnection>> {
+        let read_error = |e| bus_error("Failed to read active connections", e);
+        let paths: Vec<OwnedObjectPath> = self.network_manager.get_property("ActiveConnections").await.map_err(read_error)?;
+        let mut active = HashMap::new();
+        for path in paths {
+            let proxy = self.proxy(&path, ACTIVE_CONNECTION_INTERFACE).await?;
+            let uuid: String = proxy.get_property("Uuid").await.map_err(read_error)?;
+            let state: u32 = proxy.get_property("State").await.map_err(read_error)?;
+            let devices: Vec<OwnedObjectPath> = proxy.get_property("Devices").await.map_err(read_error)?;
+            let interface = match devices.first() {
+                Some(device) => self.proxy(device, DEVICE_INTERFACE).await?.get_property("Interface").await.ok(),
+                None => None,
+            };
+            active.insert(uuid, ActiveConnection { path, state: connection_state_from_nm(state), interface });
+        }
+        Ok(active)
+    }
+
+    /// The strongest signal per SSID over the access points of all Wi-Fi devices.
+    async fn signal_strengths(&self) -> DomainResult<HashMap<Vec<u8>, u8>> {
+        let read_error = |e| bus_error("Failed to read access points", e);
+        let devices: Vec<OwnedObjectPath> = self.network_manager.call("GetDevices", &()).await.map_err(read_error)?;
+        let mut strengths = HashMap::new();
+        for device in devices {
+            let device_type: u32 = self.proxy(&device, DEVICE_INTERFACE).await?.get_property("DeviceType").await.map_err(read_error)?;
+            if device_type != NM_DEVICE_TYPE_WIFI {
+                continue;
+            }
+            let access_points: Vec<OwnedObjectPath> =
+                self.proxy(&device, WIRELESS_INTERFACE).await?.get_property("AccessPoints").await.map_err(read_error)?;
+            for access_point in access_points {
+                let proxy = self.proxy(&access_point, ACCESS_POINT_INTERFACE).await?;
+                // Access points vanish while we iterate; skip those.
+                let (Ok(ssid), Ok(strength)) = (proxy.get_property::<Vec<u8>>("Ssid").await, proxy.get_property::<u8>("Strength").await)
+                else {
+                    continue;
+                };
+                let entry = strengths.entry(ssid).or_insert(strength);
+                *entry = (*entry).max(strength);
+            }
+        }
+        Ok(strengths)
+    }
+
+    async fn profile_path(&self, id: &str) -> DomainResult<OwnedObjectPath> {
+        self.settings.call("GetConnectionByUuid", &(id,)).await.map_err(|_| DomainError::not_found::<NetworkConnection>(id))
+    }
+}
+
+/// Property values change behind our back, so they are always read from the bus.
+async fn uncached_proxy(
+    connection: &Connection,
+    path: ObjectPath<'static>,
+    interface: &'static str,
+) -> DomainResult<Proxy<'static>> {
+    let context = "Failed to create D-Bus proxy";
+    Builder::new(connection)
+        .destination(NM_DESTINATION)
+        .and_then(|builder| builder.path(path))
+        .and_then(|builder| builder.interface(interface))
+        .map_err(|e| bus_error(context, e))?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await
+        .map_err(|e| bus_error(context, e))
+}
+
+#[async_trait]
+impl NetworkRepository for NetworkManagerRepository {
+    async fn get_connections(&self) -> DomainResult<Vec<NetworkConnection>> {
+        let read_error = |e| bus_error("Failed to read connection profiles", e);
+        let profiles: Vec<OwnedObjectPath> = self.settings.call("ListConnections", &()).await.map_err(read_error)?;
+        let mut active = self.active_connections().await?;
+        let strengths = self.signal_strengths().await?;
+        let mut connections = Vec::new();
+        for profile in profiles {
+            let settings: ConnectionSettings =
+                self.proxy(&profile, SETTINGS_CONNECTION_INTERFACE).await?.call("GetSettings", &()).await.map_err(read_error)?;
+            let Some(connection_type) =
+                setting_string(&settings, "connection", "type").as_deref().and_then(connection_type_from_nm)
+            else {
+                continue;
+            };
+            let (Some(uuid), Some(name)) = (setting_string(&settings, "connection", "uuid"), setting_string(&settings, "connection", "id"))
+            else {
+                continue;
+            };
+            let mut connection = NetworkConnection::new(&uuid, &name, connection_type);
+            if connection_type == ConnectionType::Wifi {
+                if let Some(ssid) = setting_bytes(&settings, "802-11-wireless", "ssid") {
+                    connection.name = String::from_utf8_lossy(&ssid).into_owned();
+                    connection.signal_strength = strengths.get(&ssid).copied();
+                }
+            }
+            if let Some(active) = active.remove(&uuid) {
+                connection.state = active.state;
+                connection.interface = active.interface;
+            }
+            connections.push(connection);
+        }
+        Ok(connections)
+    }
+
+    async fn connect(&self, id: &str) -> DomainResult<()> {
+        let profile = self.profile_path(id).await?;
+        // NetworkManager picks the device and access point for the root path "/".
+        let any = ObjectPath::from_static_str_unchecked("/");
+        let _: OwnedObjectPath = self
+            .network_manager
+            .call("ActivateConnection", &(profile, &any, &any))
+            .await
+            .map_err(|e| bus_error("Failed to activate connection", e))?;
+        Ok(())
+    }
+
+    async fn disconnect(&self, id: &str) -> DomainResult<()> {
+        let Some(active) = self.active_connections().await?.remove(id) else {
+            return Ok(());
+        };
+        self.network_manager
+            .call::<_, _, ()>("DeactivateConnection", &(active.path,))
+            .await
+            .map_err(|e| bus_error("Failed to deactivate connection", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_manager_values_are_mapped() {
+        assert_eq!(connection_type_from_nm("802-11-wireless"), Some(ConnectionType::Wifi));
+        assert_eq!(connection_type_from_nm("802-3-ethernet"), Some(ConnectionType::Ethernet));
+        assert_eq!(connection_type_from_nm("wireguard"), Some(ConnectionType::Vpn));
+        assert_eq!(connection_type_from_nm("loopback"), None);
+        assert_eq!(connection_state_from_nm(1), ConnectionState::Connecting);
+        assert_eq!(connection_state_from_nm(2), ConnectionState::Connected);
+        assert_eq!(connection_state_from_nm(3), ConnectionState::Disconnecting);
+        assert_eq!(connection_state_from_nm(4), ConnectionState::Disconnected);
+        assert_eq!(connection_state_from_nm(0), ConnectionState::Disconnected);
+    }
+}
@@ -0,0 +1,268 @@
+// src/repositories/pipewire.rs
+
+//! Audio devices and application streams through PipeWire.
+//!
+//! The state of the audio graph is read from `pw-dump`, which prints all PipeWire objects as
+//! JSON. Changes go through WirePlumber's `wpctl`, so that the session manager remembers the
+//! default devices and volumes across restarts like it does for any other client. Both tools
+//! are run once per call; the graph changes too often for a snapshot to stay valid.
+//!
+//! PipeWire stores linear channel volumes. The domain uses the perceived (cubic) scale, like
+//! `wpctl` and the usual volume sliders, so volumes are converted with a cube root when read.
+
+use std::fmt::Display;
+use std::process::Command;
+
+use async_trait::async_trait;
+use novade_core::CoreError;
+use novade_domain::entities::{AudioDevice, AudioDeviceKind, AudioStream, AudioStreamKind};
+use novade_domain::repositories::AudioRepository;
+use novade_domain::{DomainError, DomainResult};
+use serde_json::Value;
+
+const NODE_TYPE: &str = "PipeWire:Interface:Node";
+const METADATA_TYPE: &str = "PipeWire:Interface:Metadata";
+
+fn audio_error(context: &str, error: impl Display) -> DomainError {
+    DomainError::RepositoryError(CoreError::IoError(std::io::Error::other(format!("{}: {}", context, error))))
+}
+
+/// The audio nodes of one `pw-dump`, with their PipeWire object ids.
+#[derive(Debug, Default)]
+struct AudioGraph {
+    devices: Vec<(u32, AudioDevice)>,
+    streams: Vec<AudioStream>,
+}
+
+impl AudioGraph {
+    fn device_id(&self, name: &str) -> DomainResult<u32> {
+        self.devices
+            .iter()
+            .find(|(_, device)| device.name == name)
+            .map(|(id, _)| *id)
+            .ok_or_else(|| DomainError::not_found::<AudioDevice>(name))
+    }
+}
+
+/// Maps a node's `media.class` to the kind of device or stream it is.
+enum NodeClass {
+    Device(AudioDeviceKind),
+    Stream(AudioStreamKind),
+}
+
+fn node_class(media_class: &str) -> Option<NodeClass> {
+    match media_class {
+        "Audio/Sink" => Some(NodeClass::Device(AudioDeviceKind::Sink)),
+        "Audio/Source" => Some(NodeClass::Device(AudioDeviceKind::Source)),
+        "Stream/Output/Audio" => Some(NodeClass::Stream(AudioStreamKind::Playback)),
+        "Stream/Input/Audio" => Some(NodeClass::Stream(AudioStreamKind::Recording)),
+        _ => None,
+    }
+}
+
+/// Reads the names of the default sink and source from the `default` metadata object.
+fn default_device_names(objects: &[Value]) -> (Option<String>, Option<String>) {
+    let mut sink = None;
+    let mut source = None;
+    let metadata = objects.iter().filter(|object| {
+        object["type"] == METADATA_TYPE && object["props"]["metadata.name"] == "default"
+    });
+    for entry in metadata.flat_map(|object| object["metadata"].as_array().into_iter().flatten()) {
+        let name = entry["value"]["name"].as_str().map(str::to_string);
+        match entry["key"].as_str() {
+            Some("default.audio.sink") => sink = name,
+            Some("default.audio.source") => source = name,
+            _ => {}
+        }
+    }
+    (sink, source)
+}
+
+/// The volume and mute state of a node, from the first entry of its `Props` parameter.
+fn volume_and_mute(info: &Value) -> (f32, bool) {
+    let props = &info["params"]["Props"][0];
+    let linear = props["channelVolumes"]
+        .as_array()
+        .map(|volumes| volumes.iter().filter_map(Value::as_f64).fold(0.0, f64::max))
+        .unwrap_or(1.0);
+    (linear.cbrt() as f32, props["mute"].as_bool().unwrap_or(false))
+}
+
+/// Parses the output of `pw-dump`.
+fn parse_pw_dump(json: &str) -> DomainResult<AudioGraph> {
+    let objects: Vec<Value> = serde_json::from_str(json).map_err(|e| audio_error("Failed to parse pw-dump output", e))?;
+    let (default_sink, default_source) = default_device_names(&objects);
+    let mut graph = AudioGraph::default();
+    for object in objects.iter().filter(|object| object["type"] == NODE_TYPE) {
+        let Some(id) = object["id"].as_u64().and_then(|id| u32::try_from(id).ok()) else {
+            continue;
+        };
+        let info = &object["info"];
+        let props = &info["props"];
+        let Some(class) = props["media.class"].as_str().and_then(node_class) else {
+            continue;
+        };
+        let prop = |key: &str| props[key].as_str().map(str::to_string);
+        let (volume, muted) = volume_and_mute(info);
+        match class {
+            NodeClass::Device(kind) => {
+                let Some(name) = prop("node.name") else {
+                    continue;
+                };
+                let default = match kind {
+                    AudioDeviceKind::Sink => &default_sink,
+                    AudioDeviceKind::Source => &default_source,
+                };
+                let device = AudioDevice {
+                    description: prop("node.description").unwrap_or_else(|| name.clone()),
+                    is_default: default.as_deref() == Some(name.as_str()),
+                    name,
+                    kind,
+                    volume,
+                    muted,
+                };
+                graph.devices.push((id, device));
+            }
+            NodeClass::Stream(kind) => graph.streams.push(AudioStream {
+                id,
+                application: prop("application.name").or_else(|| prop("node.name")).unwrap_or_else(|| id.to_string()),
+                app_id: prop("pipewire.access.portal.app_id").or_else(|| prop("application.id")),
+                kind,
+                volume,
+                muted,
+            }),
+        }
+    }
+    Ok(graph)
+}
+
+/// [`AudioRepository`] backed by PipeWire, through `pw-dump` and WirePlumber's `wpctl`.
+#[derive(Debug, Clone, Default)]
+pub struct PipeWireAudioRepository;
+
+impl PipeWireAudioRepository {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn graph(&self) -> DomainResult<AudioGraph> {
+        let output = Command::new("pw-dump").output().map_err(|e| audio_error("Failed to run pw-dump", e))?;
+        if !output.status.success() {
+            return Err(audio_error("pw-dump failed", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        parse_pw_dump(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    fn wpctl(&self, args: &[&str]) -> DomainResult<()> {
+        let output = Command::new("wpctl").args(args).output().map_err(|e| audio_error("Failed to run wpctl", e))?;
+        if !output.status.success() {
+            return Err(audio_error(&format!("wpctl {} failed", args.join(" ")), String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(())
+    }
+}
+
+fn mute_arg(muted: bool) -> &'static str {
+    if muted {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+#[async_trait]
+impl AudioRepository for PipeWireAudioRepository {
+    async fn get_devices(&self) -> DomainResult<Vec<AudioDevice>> {
+        Ok(self.graph()?.devices.into_iter().map(|(_, device)| device).collect())
+    }
+
+    async fn get_streams(&self) -> DomainResult<Vec<AudioStream>> {
+        Ok(self.graph()?.streams)
+    }
+
+    async fn set_default_device(&self, _kind: AudioDeviceKind, name: &str) -> DomainResult<()> {
+        // The kind follows from the node's media class.
+        let id = self.graph()?.device_id(name)?;
+        self.wpctl(&["set-default", &id.to_string()])
+    }
+
+    async fn set_device_volume(&self, name: &str, volume: f32) -> DomainResult<()> {
+        let id = self.graph()?.device_id(name)?;
+        self.wpctl(&["set-volume", &id.to_string(), &format!("{:.3}", volume)])
+    }
+
+    async fn set_device_muted(&self, name: &str, muted: bool) -> DomainResult<()> {
+        let id = self.graph()?.device_id(name)?;
+        self.wpctl(&["set-mute", &id.to_string(), mute_arg(muted)])
+    }
+
+    async fn set_stream_volume(&self, id: u32, volume: f32) -> DomainResult<()> {
+        self.wpctl(&["set-volume", &id.to_string(), &format!("{:.3}", volume)])
+    }
+
+    async fn set_stream_muted(&self, id: u32, muted: bool) -> DomainResult<()> {
+        self.wpctl(&["set-mute", &id.to_string(), mute_arg(muted)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PW_DUMP: &str = r#"[
+        { "id": 0, "type": "PipeWire:Interface:Core", "info": { "props": {} } },
+        { "id": 35, "type": "PipeWire:Interface:Metadata", "props": { "metadata.name": "default" },
+          "metadata": [
+            { "subject": 0, "key": "default.audio.sink", "type": "Spa:String:JSON",
+              "value": { "name": "alsa_output.usb-headset.analog-stereo" } },
+            { "subject": 0, "key": "default.audio.source", "type": "Spa:String:JSON",
+              "value": { "name": "alsa_input.pci-0000_00_1f.3.analog-stereo" } }
+          ] },
+        { "id": 48, "type": "PipeWire:Interface:Node", "info": {
+            "props": { "media.class": "Audio/Sink", "node.name": "alsa_output.pci-0000_00_1f.3.analog-stereo",
+                       "node.description": "Built-in Audio Analog Stereo" },
+            "params": { "Props": [ { "channelVolumes": [0.125, 0.125], "mute": false } ] } } },
+        { "id": 52, "type": "PipeWire:Interface:Node", "info": {
+            "props": { "media.class": "Audio/Sink", "node.name": "alsa_output.usb-headset.analog-stereo",
+                       "node.description": "USB Headset" },
+            "params": { "Props": [ { "channelVolumes": [1.0, 1.0], "mute": true } ] } } },
+        { "id": 49, "type": "PipeWire:Interface:Node", "info": {
+            "props": { "media.class": "Audio/Source", "node.name": "alsa_input.pci-0000_00_1f.3.analog-stereo" },
+            "params": { "Props": [ { "channelVolumes": [0.512], "mute": false } ] } } },
+        { "id": 77, "type": "PipeWire:Interface:Node", "info": {
+            "props": { "media.class": "Stream/Output/Audio", "node.name": "Firefox", "application.name": "Firefox",
+                       "pipewire.access.portal.app_id": "org.mozilla.firefox" },
+            "params": { "Props": [ { "channelVolumes": [0.216, 0.216], "mute": false } ] } } },
+        { "id": 80, "type": "PipeWire:Interface:Node", "info": {
+            "props": { "media.class": "Video/Source", "node.name": "v4l2_input.camera" } } }
+    ]"#;
+
+    #[test]
+    fn test_pw_dump_is_parsed() {
+        let graph = parse_pw_dump(PW_DUMP).unwrap();
+        let devices: Vec<(u32, &str, AudioDeviceKind, bool, bool)> = graph
+            .devices
+            .iter()
+            .map(|(id, device)| (*id, device.description.as_str(), device.kind, device.is_default, device.muted))
+            .collect();
+        assert_eq!(
+            devices,
+            vec![
+                (48, "Built-in Audio Analog Stereo", AudioDeviceKind::Sink, false, false),
+                (52, "USB Headset", AudioDeviceKind::Sink, true, true),
+                (49, "alsa_input.pci-0000_00_1f.3.analog-stereo", AudioDeviceKind::Source, true, false),
+            ]
+        );
+        assert!((graph.devices[0].1.volume - 0.5).abs() < 1e-6);
+        assert!((graph.devices[2].1.volume - 0.8).abs() < 1e-6);
+        assert_eq!(graph.device_id("alsa_output.usb-headset.analog-stereo").unwrap(), 52);
+        assert!(graph.device_id("missing").is_err());
+
+        assert_eq!(graph.streams.len(), 1);
+        assert_eq!(graph.streams[0].application, "Firefox");
+        assert_eq!(graph.streams[0].app_id.as_deref(), Some("org.mozilla.firefox"));
+        assert_eq!(graph.streams[0].kind, AudioStreamKind::Playback);
+        assert!((graph.streams[0].volume - 0.6).abs() < 1e-6);
+        assert!(parse_pw_dump("not json").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+// src/repositories/upower.rs
+
+//! Power profiles and battery state over D-Bus.
+//!
+//! Profiles are read from and written to power-profiles-daemon, under its current bus name
+//! `org.freedesktop.UPower.PowerProfiles` or, for older versions, `net.hadess.PowerProfiles`.
+//! The battery state is that of UPower's display device, which combines all batteries of
+//! the system.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use novade_core::CoreError;
+use novade_domain::entities::{BatteryState, ChargingState, PowerProfile};
+use novade_domain::repositories::PowerRepository;
+use novade_domain::{DomainError, DomainResult};
+use zbus::proxy::{Builder, CacheProperties};
+use zbus::zvariant::OwnedValue;
+use zbus::{Connection, Proxy};
+
+/// Bus names, object paths and interfaces of power-profiles-daemon, newest first.
+const POWER_PROFILES_SERVICES: [(&str, &str, &str); 2] = [
+    ("org.freedesktop.UPower.PowerProfiles", "/org/freedesktop/UPower/PowerProfiles", "org.freedesktop.UPower.PowerProfiles"),
+    ("net.hadess.PowerProfiles", "/net/hadess/PowerProfiles", "net.hadess.PowerProfiles"),
+];
+
+const UPOWER_DESTINATION: &str = "org.freedesktop.UPower";
+const DISPLAY_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+const DEVICE_INTERFACE: &str = "org.freedesktop.UPower.Device";
+
+/// UPower device type of batteries.
+const UPOWER_TYPE_BATTERY: u32 = 2;
+
+fn bus_error(context: &str, error: impl Display) -> DomainError {
+    DomainError::RepositoryError(CoreError::IoError(std::io::Error::other(format!("{}: {}", context, error))))
+}
+
+/// Maps UPower's `State` property to a [`ChargingState`].
+pub fn charging_state_from_upower(state: u32) -> ChargingState {
+    match state {
+        1 => ChargingState::Charging,
+        // Empty and pending discharge are reported while running on battery.
+        2 | 3 | 6 => ChargingState::Discharging,
+        4 => ChargingState::FullyCharged,
+        5 => ChargingState::NotCharging,
+        _ => ChargingState::Unknown,
+    }
+}
+
+/// UPower reports unknown time estimates as 0.
+fn time_estimate(seconds: i64) -> Option<u64> {
+    u64::try_from(seconds).ok().filter(|seconds| *seconds > 0)
+}
+
+/// [`PowerRepository`] backed by power-profiles-daemon and UPower on the system bus.
+#[derive(Debug, Clone)]
+pub struct UPowerRepository {
+    profiles: Proxy<'static>,
+    display_device: Proxy<'static>,
+}
+
+impl UPowerRepository {
+    /// Connects to the system bus and looks up power-profiles-daemon under one of its bus
+    /// names.
+    pub async fn new() -> DomainResult<Self> {
+        let connection = Connection::system().await.map_err(|e| bus_error("Failed to connect to system bus", e))?;
+        let mut profiles = None;
+        for (destination, path, interface) in POWER_PROFILES_SERVICES {
+            let proxy = uncached_proxy(&connection, destination, path, interface).await?;
+            if proxy.get_property::<String>("ActiveProfile").await.is_ok() {
+                profiles = Some(proxy);
+                break;
+            }
+        }
+        let profiles = profiles.ok_or_else(|| bus_error("Failed to find power-profiles-daemon", "service not running"))?;
+        let display_device = uncached_proxy(&connection, UPOWER_DESTINATION, DISPLAY_DEVICE_PATH, DEVICE_INTERFACE).await?;
+        Ok(Self { profiles, display_device })
+    }
+}
+
+/// Property values change behind our back, so they are always read from the bus.
+async fn uncached_proxy(
+    connection: &Connection,
+    destination: &'static str,
+    path: &'static str,
+    interface: &'static str,
+) -> DomainResult<Proxy<'static>> {
+    let context = "Failed to create D-Bus proxy";
+    Builder::new(connection)
+        .destination(destination)
+        .and_then(|builder| builder.path(path))
+        .and_then(|builder| builder.interface(interface))
+        .map_err(|e| bus_error(context, e))?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await
+        .map_err(|e| bus_error(context, e))
+}
+
+#[async_trait]
+impl PowerRepository for UPowerRepository {
+    async fn active_profile(&self) -> DomainResult<PowerProfile> {
+        let id: String =
+            self.profiles.get_property("ActiveProfile").await.map_err(|e| bus_error("Failed to read active power profile", e))?;
+        PowerProfile::from_id(&id).ok_or_else(|| bus_error("Unknown power profile", id))
+    }
+
+    async fn available_profiles(&self) -> DomainResult<Vec<PowerProfile>> {
+        let profiles: Vec<HashMap<String, OwnedValue>> =
+            self.profiles.get_property("Profiles").await.map_err(|e| bus_error("Failed to read power profiles", e))?;
+        let mut available: Vec<PowerProfile> = profiles
+            .iter()
+            .filter_map(|profile| profile.get("Profile")?.downcast_ref::<&str>().ok().and_then(PowerProfile::from_id))
+            .collect();
+        available.sort();
+        available.dedup();
+        Ok(available)
+    }
+
+    async fn set_active_profile(&self, profile: PowerProfile) -> DomainResult<()> {
+        self.profiles
+            .set_property("ActiveProfile", profile.id())
+            .await
+            .map_err(|e| bus_error("Failed to set active power profile", e))
+    }
+
+    async fn battery_state(&self) -> DomainResult<Option<BatteryState>> {
+        let read_error = |e| bus_error("Failed to read battery state", e);
+        let device = &self.display_device;
+        let device_type: u32 = device.get_property("Type").await.map_err(read_error)?;
+        let present: bool = device.get_property("IsPresent").await.map_err(read_error)?;
+        if device_type != UPOWER_TYPE_BATTERY || !present {
+            return Ok(None);
+        }
+        let state: u32 = device.get_property("State").await.map_err(read_error)?;
+        Ok(Some(BatteryState {
+            percentage: device.get_property("Percentage").await.map_err(read_error)?,
+            charging_state: charging_state_from_upower(state),
+            time_to_empty_secs: time_estimate(device.get_property("TimeToEmpty").await.map_err(read_error)?),
+            time_to_full_secs: time_estimate(device.get_property("TimeToFull").await.map_err(read_error)?),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upower_states_are_mapped() {
+        assert_eq!(charging_state_from_upower(1), ChargingState::Charging);
+        assert_eq!(charging_state_from_upower(2), ChargingState::Discharging);
+        assert_eq!(charging_state_from_upower(4), ChargingState::FullyCharged);
+        assert_eq!(charging_state_from_upower(5), ChargingState::NotCharging);
+        assert_eq!(charging_state_from_upower(0), ChargingState::Unknown);
+        assert_eq!(time_estimate(0), None);
+        assert_eq!(time_estimate(-5), None);
+        assert_eq!(time_estimate(3600), Some(3600));
+    }
+}
@@ -1,9 +1,24 @@
 // src/server.rs
 
 use crate::clipboard::Clipboard;
-use crate::compositor::core::{CompositorState, Window}; // Window needs to be in scope
+use crate::compositor::backend::Backend;
+use crate::compositor::core::{CompositorState, IdleTracker, Output, Window}; // Window needs to be in scope
+use crate::compositor::render;
+use crate::compositor::{CompositorError, CompositorResult};
 use crate::input::{InputManager, InputEvent};
-use crate::client::{Client, ClientRequest, ServerEvent}; // ClientRequest, ServerEvent needed
+use crate::process_manager::DefaultProcessManager;
+use crate::client::{BackendRequest, Client, ClientRequest, ServerEvent}; // ClientRequest, ServerEvent needed
+use crate::session_management::{Session, SessionEvent};
+use novade_core::signal::Signal;
+use novade_domain::entities::display::layout_key;
+use novade_domain::entities::DisplayLayout;
+use novade_domain::services::DisplayService;
+use novade_domain::DomainResult;
+use std::time::{Duration, Instant};
+
+/// How long a launch may take to show its first window before the "launching" indicator
+/// is dropped.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(15);
 
 /// Represents the main server instance, orchestrating compositor and input logic.
 #[derive(Debug)]
@@ -18,6 +33,24 @@ pub struct Server {
     next_client_id: u32,
     /// The server's clipboard instance.
     pub clipboard: Clipboard,
+    /// Whether the session is switched away and the backend is paused.
+    session_paused: bool,
+    /// Detects user inactivity. Disabled (no timeout) by default.
+    pub idle_tracker: IdleTracker,
+    /// Whether the session is locked automatically once the user becomes idle.
+    pub auto_lock_on_idle: bool,
+    /// Whether all outputs are powered off while the user is idle (DPMS).
+    pub power_off_on_idle: bool,
+    /// The outputs as last reported by the backend, before the display layout is applied.
+    /// `None` until the server runs with a backend.
+    connected_outputs: Option<Vec<Output>>,
+    /// The display layout applied to the connected outputs while it covers exactly them.
+    display_layout: Option<DisplayLayout>,
+    /// The manager that launched the applications, if any; new windows are attached to
+    /// their process.
+    process_manager: Option<DefaultProcessManager>,
+    /// Every event the server produces, for clients other than the one that caused it.
+    events: Signal<ServerEvent>,
 }
 
 impl Server {
@@ -29,9 +62,24 @@ impl Server {
             clients: Vec::new(),
             next_client_id: 1,
             clipboard: Clipboard::new(),
+            session_paused: false,
+            idle_tracker: IdleTracker::default(),
+            auto_lock_on_idle: false,
+            power_off_on_idle: false,
+            connected_outputs: None,
+            display_layout: None,
+            process_manager: None,
+            events: Signal::new(),
         }
     }
 
+    /// Attaches the windows of applications launched by `manager` to their process: by the
+    /// client's PID when the window is created, or by the startup token when the window
+    /// activates itself with it.
+    pub fn set_process_manager(&mut self, manager: DefaultProcessManager) {
+        self.process_manager = Some(manager);
+    }
+
     /// Adds a new client to the server.
     ///
     /// A new client instance is created with a unique ID, added to the server's
@@ -48,6 +96,68 @@ impl Server {
         client_id
     }
 
+    /// Adds a new client whose process ID is known, e.g. from the socket credentials.
+    ///
+    /// # Returns
+    /// The ID of the newly added client.
+    pub fn add_client_with_pid(&mut self, pid: u32) -> u32 {
+        let client_id = self.add_client();
+        if let Some(client) = self.clients.iter_mut().find(|c| c.id == client_id) {
+            client.pid = Some(pid);
+        }
+        client_id
+    }
+
+    /// Removes a disconnected client together with its windows and idle inhibitors.
+    ///
+    /// # Returns
+    /// `true` if the client existed.
+    pub fn remove_client(&mut self, client_id: u32) -> bool {
+        let Some(index) = self.clients.iter().position(|c| c.id == client_id) else {
+            return false;
+        };
+        self.clients.remove(index);
+        self.compositor_state.remove_client_idle_inhibitors(client_id);
+        let window_ids: Vec<u32> = self.compositor_state.windows.iter()
+            .filter(|w| w.client_id == client_id)
+            .map(|w| w.id)
+            .collect();
+        for window_id in window_ids {
+            self.compositor_state.remove_window(window_id);
+            if let Some(manager) = &self.process_manager {
+                manager.detach_window(window_id);
+            }
+        }
+        println!("Server: Client {} removed.", client_id);
+        true
+    }
+
+    /// Adds a new privileged client (e.g. the lock screen) to the server.
+    ///
+    /// # Returns
+    /// The ID of the newly added client.
+    pub fn add_privileged_client(&mut self) -> u32 {
+        let client_id = self.next_client_id;
+        self.next_client_id += 1;
+        self.clients.push(Client::new_privileged(client_id));
+        println!("Server: Privileged client added with ID: {}", client_id);
+        client_id
+    }
+
+    /// The signal on which every event returned by the request handlers and idle checks is
+    /// broadcast.
+    pub fn events(&self) -> &Signal<ServerEvent> {
+        &self.events
+    }
+
+    /// Broadcasts `event` on [`Server::events`], if any, and hands it back.
+    fn broadcast(&self, event: Option<ServerEvent>) -> Option<ServerEvent> {
+        if let Some(event) = &event {
+            self.events.emit(event.clone());
+        }
+        event
+    }
+
     /// Processes a request from a client.
     ///
     /// # Arguments
@@ -55,9 +165,14 @@ impl Server {
     ///
     /// # Returns
     /// An `Option<ServerEvent>` which might contain an event to be sent back
-    /// to the client or broadcast, or `None` if the request generates no immediate event
-    /// or is invalid.
+    /// to the client, or `None` if the request generates no immediate event
+    /// or is invalid. The event is also broadcast on [`Server::events`].
     pub fn process_client_request(&mut self, request: ClientRequest) -> Option<ServerEvent> {
+        let event = self.handle_client_request(request);
+        self.broadcast(event)
+    }
+
+    fn handle_client_request(&mut self, request: ClientRequest) -> Option<ServerEvent> {
         println!("Server: Received client request: {:?}", request);
         match request {
             ClientRequest::CreateWindow { client_id, title, initial_width, initial_height } => {
@@ -84,9 +199,13 @@ impl Server {
                     initial_x,
                     initial_y,
                 );
-                let geometry = (new_window.x, new_window.y, new_window.width, new_window.height);
+                let geometry = (new_window.position.x, new_window.position.y, new_window.size.width, new_window.size.height);
                 
                 self.compositor_state.add_window(new_window);
+                let pid = self.clients.iter().find(|c| c.id == client_id).and_then(|c| c.pid);
+                if let (Some(manager), Some(pid)) = (&self.process_manager, pid) {
+                    manager.attach_window(pid, window_id);
+                }
                 println!("Server: Window {} created for client {} at ({},{}) size {}x{}",
                          window_id, client_id, geometry.0, geometry.1, geometry.2, geometry.3);
 
@@ -112,6 +231,58 @@ impl Server {
                 let text = self.get_clipboard_data();
                 Some(ServerEvent::PasteTextResponse { client_id, text })
             }
+            ClientRequest::LockSession { client_id } => {
+                if !self.clients.iter().any(|c| c.id == client_id && c.privileged) {
+                    eprintln!("Server Error: LockSession request from unknown or unprivileged client ID: {}", client_id);
+                    return None;
+                }
+                if self.compositor_state.lock_session(Some(client_id)) {
+                    Some(ServerEvent::SessionLocked { client_id })
+                } else {
+                    None
+                }
+            }
+            ClientRequest::CreateLockSurface { client_id, output_id } => {
+                let window_id = self.compositor_state.add_lock_surface(client_id, output_id)?;
+                let window = self.compositor_state.find_window(window_id)?;
+                Some(ServerEvent::LockSurfaceCreated {
+                    window_id,
+                    output_id,
+                    geometry: (window.position.x, window.position.y, window.size.width, window.size.height),
+                })
+            }
+            ClientRequest::UnlockSession { client_id } => {
+                if self.compositor_state.unlock_session(client_id) {
+                    Some(ServerEvent::SessionUnlocked { client_id })
+                } else {
+                    None
+                }
+            }
+            ClientRequest::InhibitIdle { client_id, window_id, reason } => {
+                if self.compositor_state.add_idle_inhibitor(client_id, window_id, reason) {
+                    Some(ServerEvent::IdleInhibited { window_id })
+                } else {
+                    None
+                }
+            }
+            ClientRequest::ReleaseIdleInhibit { client_id, window_id } => {
+                if self.compositor_state.remove_idle_inhibitor(client_id, window_id) {
+                    Some(ServerEvent::IdleInhibitReleased { window_id })
+                } else {
+                    None
+                }
+            }
+            ClientRequest::ActivateWindow { window_id, token } => {
+                // Unknown tokens are ignored so clients cannot steal focus.
+                let startup = self.compositor_state.complete_startup(&token, window_id)?;
+                if let Some(manager) = &self.process_manager {
+                    if let Some(pid) = manager.pid_for_startup_token(&token) {
+                        manager.attach_window(pid, window_id);
+                    }
+                }
+                self.compositor_state.set_focused_window_for_seat("seat0", Some(window_id));
+                Some(ServerEvent::WindowActivated { window_id, app_id: startup.app_id })
+            }
             // Handle other ClientRequest variants here in the future
             // _ => {
             //     println!("Server: Received unhandled client request type.");
@@ -120,6 +291,31 @@ impl Server {
         }
     }
     
+    /// Processes a request from a client that needs the backend.
+    ///
+    /// # Returns
+    /// The event to send back, as for [`process_client_request`](Self::process_client_request),
+    /// or an error if the backend failed to carry out the request.
+    pub fn process_backend_request<B: Backend + ?Sized>(
+        &mut self,
+        request: BackendRequest,
+        backend: &mut B,
+    ) -> CompositorResult<Option<ServerEvent>> {
+        match request {
+            BackendRequest::SetOutputPower { client_id, output_id, on } => {
+                if !self.clients.iter().any(|c| c.id == client_id) {
+                    eprintln!("Server Error: SetOutputPower request from non-existent client ID: {}", client_id);
+                    return Ok(None);
+                }
+                if self.set_output_power(backend, output_id, on)? {
+                    Ok(self.broadcast(Some(ServerEvent::OutputPowerChanged { output_id, on })))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
     /// Runs a single iteration of the main event loop.
     ///
     /// This method processes a batch of simulated input events, updates compositor state,
@@ -131,12 +327,20 @@ impl Server {
     pub fn run_loop_iteration(&mut self, simulated_events: Vec<InputEvent>) {
         println!("Server: Starting loop iteration with {} simulated events.", simulated_events.len());
 
+        if !simulated_events.is_empty() {
+            self.idle_tracker.notify_activity(Instant::now());
+        }
+
         // 1. Process all incoming simulated events
         for event in simulated_events {
             println!("Server: Processing raw event: {:?}", event);
             let processed_event = self.input_manager.process_simulated_raw_event(event);
 
             match processed_event {
+                InputEvent::CopyShortcut | InputEvent::PasteShortcut if self.compositor_state.is_locked() => {
+                    // The clipboard is not reachable from the lock screen.
+                    println!("Server: Ignoring clipboard shortcut while the session is locked.");
+                }
                 InputEvent::CopyShortcut => {
                     // In a real scenario, we'd try to get data from the "focused" window.
                     // For now, we simulate this with a predefined string.
@@ -178,6 +382,269 @@ impl Server {
         println!("Server: Loop iteration finished.");
     }
 
+    /// Drives the server from a backend until the backend or the compositor stops.
+    ///
+    /// The compositor's outputs are replaced by the ones the backend provides, arranged by the
+    /// display layout (and kept in sync, e.g. when a nested host window is resized). Each
+    /// batch of input events the
+    /// backend reports is handed to [`Server::run_loop_iteration`].
+    pub fn run_with_backend<B: Backend + ?Sized>(&mut self, backend: &mut B) -> CompositorResult<()> {
+        println!("Server: Running with '{}' backend.", backend.name());
+        while self.compositor_state.running && backend.is_running() {
+            self.backend_iteration(backend)?;
+        }
+        println!("Server: Backend '{}' stopped.", backend.name());
+        Ok(())
+    }
+
+    /// Like [`Server::run_with_backend`], but additionally reacts to session events:
+    /// the backend is paused while the session is switched away and resumed on switch-back.
+    pub fn run_with_session<S, B>(&mut self, session: &mut S, backend: &mut B) -> CompositorResult<()>
+    where
+        S: Session + ?Sized,
+        B: Backend + ?Sized,
+    {
+        println!("Server: Running with '{}' backend on seat '{}'.", backend.name(), session.seat());
+        while self.compositor_state.running && backend.is_running() {
+            for event in session.dispatch_events() {
+                self.handle_session_event(event, session, backend)?;
+            }
+            if self.session_paused {
+                // Nothing to draw and no devices to read; wait for the switch-back.
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                continue;
+            }
+            self.backend_iteration(backend)?;
+        }
+        println!("Server: Backend '{}' stopped.", backend.name());
+        Ok(())
+    }
+
+    /// Reacts to a single session event.
+    ///
+    /// Switching the session away, or pausing the backend's own device, pauses the whole
+    /// backend. Pauses and resumes of other devices (e.g. input devices) are forwarded to
+    /// the backend together with the descriptor the session reopened them with.
+    pub fn handle_session_event<S, B>(&mut self, event: SessionEvent, session: &mut S, backend: &mut B) -> CompositorResult<()>
+    where
+        S: Session + ?Sized,
+        B: Backend + ?Sized,
+    {
+        match event {
+            SessionEvent::ActiveChanged(false) => self.pause_backend(backend),
+            SessionEvent::ActiveChanged(true) => self.resume_backend(backend)?,
+            SessionEvent::DevicePaused { path, needs_ack } => {
+                if backend.device_path() == Some(path.as_path()) {
+                    self.pause_backend(backend);
+                } else {
+                    backend.device_paused(&path);
+                }
+                if needs_ack {
+                    session
+                        .acknowledge_pause(&path)
+                        .map_err(|e| CompositorError::BackendError(format!("Failed to acknowledge device pause: {:?}", e)))?;
+                }
+            }
+            SessionEvent::DeviceResumed { path, file } => {
+                let is_backend_device = backend.device_path() == Some(path.as_path());
+                backend.device_resumed(&path, file)?;
+                if is_backend_device {
+                    self.resume_backend(backend)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn pause_backend<B: Backend + ?Sized>(&mut self, backend: &mut B) {
+        if !self.session_paused {
+            println!("Server: Session deactivated, pausing backend '{}'.", backend.name());
+            self.session_paused = true;
+            backend.pause();
+        }
+    }
+
+    fn resume_backend<B: Backend + ?Sized>(&mut self, backend: &mut B) -> CompositorResult<()> {
+        if self.session_paused {
+            println!("Server: Session reactivated, resuming backend '{}'.", backend.name());
+            backend.resume()?;
+            self.session_paused = false;
+        }
+        Ok(())
+    }
+
+    /// Checks for user inactivity and locks the session if auto-lock is enabled.
+    ///
+    /// # Returns
+    /// `Some(ServerEvent::LockRequested)` if the session was locked because the user
+    /// became idle; the event should be forwarded to the lock client so it can claim
+    /// the lock and present its surfaces.
+    ///
+    /// While a visible window holds an idle inhibitor, the user never becomes idle.
+    pub fn check_idle(&mut self, now: Instant) -> Option<ServerEvent> {
+        self.compositor_state.prune_idle_inhibitors();
+        if self.compositor_state.is_idle_inhibited() {
+            self.idle_tracker.notify_activity(now);
+            return None;
+        }
+        if !self.idle_tracker.poll(now) {
+            return None;
+        }
+        println!("Server: User is idle.");
+        if self.auto_lock_on_idle && self.compositor_state.lock_session(None) {
+            return self.broadcast(Some(ServerEvent::LockRequested));
+        }
+        None
+    }
+
+    /// Turns an output's display on or off through the backend and records the new state.
+    ///
+    /// Used by the idle handling and by [`BackendRequest::SetOutputPower`].
+    ///
+    /// # Returns
+    /// `false` if the compositor does not know the output.
+    pub fn set_output_power<B: Backend + ?Sized>(&mut self, backend: &mut B, output_id: u32, on: bool) -> CompositorResult<bool> {
+        if !self.compositor_state.outputs.iter().any(|o| o.id == output_id) {
+            println!("Server: Cannot change power of unknown output {}.", output_id);
+            return Ok(false);
+        }
+        backend.set_output_power(output_id, on)?;
+        self.compositor_state.set_output_power(output_id, on);
+        println!("Server: Output {} powered {}.", output_id, if on { "on" } else { "off" });
+        Ok(true)
+    }
+
+    /// Switches all outputs on or off.
+    fn set_all_outputs_power<B: Backend + ?Sized>(&mut self, backend: &mut B, on: bool) -> CompositorResult<()> {
+        let output_ids: Vec<u32> = self.compositor_state.outputs.iter()
+            .filter(|o| o.is_powered_on != on)
+            .map(|o| o.id)
+            .collect();
+        for output_id in output_ids {
+            self.set_output_power(backend, output_id, on)?;
+        }
+        Ok(())
+    }
+
+    /// Announces an application launch with a startup notification token, typically right
+    /// after the process manager started it. Shows the "launching" indicator until a window
+    /// activates itself with the token or the launch times out.
+    pub fn begin_startup(&mut self, token: String, app_id: Option<String>) {
+        self.compositor_state.begin_startup(token, app_id, Instant::now());
+    }
+
+    /// The current display layout: the applied layout if it covers the connected outputs,
+    /// otherwise the outputs as they are.
+    pub fn display_layout(&self) -> DisplayLayout {
+        match &self.display_layout {
+            Some(layout) if self.display_layout_applies(layout) => layout.clone(),
+            _ => DisplayLayout::new(self.compositor_state.outputs.iter().map(Output::configuration).collect()),
+        }
+    }
+
+    /// Validates `layout` through the display service and arranges the outputs accordingly.
+    ///
+    /// The layout stays in effect while exactly its outputs are connected; see
+    /// [`Server::restore_display_layout`] for picking a layout after outputs changed.
+    pub fn apply_display_layout(&mut self, displays: &DisplayService, layout: DisplayLayout) -> DomainResult<()> {
+        displays.validate_layout(&layout)?;
+        println!("Server: Applying display layout '{}'.", layout.key());
+        self.display_layout = Some(layout);
+        self.arrange_outputs();
+        Ok(())
+    }
+
+    /// Applies the layout the display service saved for the connected outputs, if any.
+    ///
+    /// Meant to be called at startup and whenever outputs were connected or disconnected.
+    pub async fn restore_display_layout(&mut self, displays: &DisplayService) -> DomainResult<()> {
+        let layout = displays.resolve_layout(self.display_layout()).await?;
+        self.display_layout = Some(layout);
+        self.arrange_outputs();
+        Ok(())
+    }
+
+    /// Saves the current display layout through the display service, so it is restored the
+    /// next time the same outputs are connected.
+    pub async fn save_display_layout(&self, displays: &DisplayService) -> DomainResult<()> {
+        displays.save_layout(&self.display_layout()).await
+    }
+
+    fn display_layout_applies(&self, layout: &DisplayLayout) -> bool {
+        self.connected_outputs
+            .as_ref()
+            .is_some_and(|outputs| layout.key() == layout_key(outputs.iter().map(|output| output.name.as_str())))
+    }
+
+    /// Rebuilds the compositor's outputs from the connected outputs and the display layout.
+    /// Outputs the layout disables are left out.
+    fn arrange_outputs(&mut self) {
+        let Some(mut outputs) = self.connected_outputs.clone() else {
+            return;
+        };
+        if let Some(layout) = self.display_layout.as_ref().filter(|layout| self.display_layout_applies(layout)) {
+            outputs.retain(|output| layout.output(&output.name).is_some_and(|configuration| configuration.enabled));
+            for output in &mut outputs {
+                let Some(configuration) = layout.output(&output.name) else {
+                    continue;
+                };
+                if !output.apply_configuration(configuration) {
+                    println!("Server: Output '{}' keeps its mode and rotation; the backend cannot change them.", output.name);
+                }
+            }
+        }
+        self.compositor_state.outputs = outputs;
+    }
+
+    /// Whether rendering is paused because the session is inactive.
+    pub fn is_session_paused(&self) -> bool {
+        self.session_paused
+    }
+
+    /// Runs one backend round trip: dispatch input, sync outputs, process events.
+    ///
+    /// Also drives idle handling: outputs are powered off when the user becomes idle (if
+    /// `power_off_on_idle` is set) and back on at the next input. Finally every powered
+    /// output that can take a new frame is redrawn.
+    fn backend_iteration<B: Backend + ?Sized>(&mut self, backend: &mut B) -> CompositorResult<()> {
+        let events = backend.dispatch_input_events()?;
+
+        let outputs = backend.outputs();
+        if self.connected_outputs.as_ref() != Some(&outputs) {
+            println!("Server: Backend outputs changed, now {} output(s).", outputs.len());
+            self.connected_outputs = Some(outputs);
+            self.arrange_outputs();
+        }
+
+        let was_idle = self.idle_tracker.is_idle();
+        if !events.is_empty() {
+            self.run_loop_iteration(events);
+        }
+        let now = Instant::now();
+        if let Some(event) = self.check_idle(now) {
+            println!("Server: {:?}", event);
+        }
+        for startup in self.compositor_state.expire_startups(now, STARTUP_TIMEOUT) {
+            println!("Server: Startup '{}' timed out.", startup.token);
+        }
+        let is_idle = self.idle_tracker.is_idle();
+        if self.power_off_on_idle && was_idle != is_idle {
+            self.set_all_outputs_power(backend, !is_idle)?;
+        }
+        self.render_outputs(backend)
+    }
+
+    /// Draws the current state into every powered output of the backend.
+    fn render_outputs<B: Backend + ?Sized>(&self, backend: &mut B) -> CompositorResult<()> {
+        let state = &self.compositor_state;
+        for output in state.powered_outputs() {
+            backend.render_frame(output.id, &mut |pixels, width, height, pitch| {
+                render::draw_output(state, output, pixels, width, height, pitch)
+            })?;
+        }
+        Ok(())
+    }
+
     /// Sets the server's clipboard data.
     pub fn set_clipboard_data(&mut self, data: String) {
         self.clipboard.set_data(data);
@@ -216,6 +683,17 @@ mod tests {
         (server, client_id)
     }
 
+    #[test]
+    fn test_events_are_broadcast() {
+        let (mut server, client_id) = create_server_with_client();
+        let events = server.events().subscribe();
+
+        server.process_client_request(ClientRequest::CopyText { client_id, text: "Hello".to_string() });
+        // Requests without a response broadcast nothing.
+        server.process_client_request(ClientRequest::CopyText { client_id: 99, text: "Unknown".to_string() });
+        assert_eq!(events.try_iter().collect::<Vec<_>>(), vec![ServerEvent::TextCopied { client_id }]);
+    }
+
     #[test]
     fn test_server_handle_copy_text_request() {
         let (mut server, client_id) = create_server_with_client();
@@ -327,4 +805,517 @@ mod tests {
         // Further checks could involve capturing stdout if the test environment supports it
         // and looking for "Server: Detected PasteShortcut, data: 'Test paste data'..."
     }
+
+    #[test]
+    fn test_lock_requests_require_privileged_client() {
+        let (mut server, client_id) = create_server_with_client();
+        assert_eq!(server.process_client_request(ClientRequest::LockSession { client_id }), None);
+        assert!(!server.compositor_state.is_locked());
+
+        let locker_id = server.add_privileged_client();
+        assert_eq!(
+            server.process_client_request(ClientRequest::LockSession { client_id: locker_id }),
+            Some(ServerEvent::SessionLocked { client_id: locker_id })
+        );
+        let response = server.process_client_request(ClientRequest::CreateLockSurface { client_id: locker_id, output_id: 2 });
+        assert!(matches!(
+            response,
+            Some(ServerEvent::LockSurfaceCreated { output_id: 2, geometry: (1920, 0, 1280, 720), .. })
+        ));
+
+        assert_eq!(server.process_client_request(ClientRequest::UnlockSession { client_id }), None);
+        assert_eq!(
+            server.process_client_request(ClientRequest::UnlockSession { client_id: locker_id }),
+            Some(ServerEvent::SessionUnlocked { client_id: locker_id })
+        );
+        assert!(!server.compositor_state.is_locked());
+    }
+
+    #[test]
+    fn test_clipboard_shortcuts_ignored_while_locked() {
+        let mut server = Server::new();
+        server.compositor_state.lock_session(None);
+        let ctrl_c_event = InputEvent::Keyboard {
+            key_code: KEY_C,
+            state: KeyState::Pressed,
+            modifiers: Modifiers { ctrl: true, shift: false, alt: false, logo: false },
+        };
+
+        server.run_loop_iteration(vec![ctrl_c_event]);
+
+        assert_eq!(server.get_clipboard_data(), None);
+    }
+
+    #[test]
+    fn test_auto_lock_on_idle() {
+        let mut server = Server::new();
+        let start = std::time::Instant::now();
+        server.idle_tracker = IdleTracker::new(Some(std::time::Duration::from_secs(300)), start);
+        server.auto_lock_on_idle = true;
+
+        assert_eq!(server.check_idle(start + std::time::Duration::from_secs(10)), None);
+        assert_eq!(server.check_idle(start + std::time::Duration::from_secs(300)), Some(ServerEvent::LockRequested));
+        assert!(server.compositor_state.is_locked());
+        assert_eq!(server.compositor_state.session_lock.as_ref().unwrap().owner_client_id, None);
+    }
+
+    fn idle_server_with_video_window() -> (Server, u32, u32, std::time::Instant) {
+        let (mut server, client_id) = create_server_with_client();
+        let start = std::time::Instant::now();
+        server.idle_tracker = IdleTracker::new(Some(std::time::Duration::from_secs(300)), start);
+        server.auto_lock_on_idle = true;
+        let response = server.process_client_request(ClientRequest::CreateWindow {
+            client_id,
+            title: "Video".to_string(),
+            initial_width: 640,
+            initial_height: 480,
+        });
+        let Some(ServerEvent::WindowCreated { window_id, .. }) = response else {
+            panic!("Window creation failed");
+        };
+        server.compositor_state.find_window_mut(window_id).unwrap().map();
+        (server, client_id, window_id, start)
+    }
+
+    #[test]
+    fn test_idle_inhibitor_prevents_auto_lock() {
+        let (mut server, client_id, window_id, start) = idle_server_with_video_window();
+        let request = ClientRequest::InhibitIdle { client_id, window_id, reason: "Playing video".to_string() };
+        assert_eq!(server.process_client_request(request), Some(ServerEvent::IdleInhibited { window_id }));
+
+        assert_eq!(server.check_idle(start + std::time::Duration::from_secs(600)), None);
+        assert!(!server.compositor_state.is_locked());
+
+        // Once released, the idle timeout starts counting from the last inhibited check.
+        assert_eq!(
+            server.process_client_request(ClientRequest::ReleaseIdleInhibit { client_id, window_id }),
+            Some(ServerEvent::IdleInhibitReleased { window_id })
+        );
+        assert_eq!(server.check_idle(start + std::time::Duration::from_secs(900)), Some(ServerEvent::LockRequested));
+    }
+
+    #[test]
+    fn test_idle_inhibitor_released_on_unmap_and_disconnect() {
+        let (mut server, client_id, window_id, start) = idle_server_with_video_window();
+        server.process_client_request(ClientRequest::InhibitIdle { client_id, window_id, reason: "Playing video".to_string() });
+
+        server.compositor_state.find_window_mut(window_id).unwrap().unmap();
+        assert_eq!(server.check_idle(start + std::time::Duration::from_secs(600)), Some(ServerEvent::LockRequested));
+        assert!(server.compositor_state.idle_inhibitors.is_empty());
+
+        let (mut server, client_id, window_id, _) = idle_server_with_video_window();
+        server.process_client_request(ClientRequest::InhibitIdle { client_id, window_id, reason: "Playing video".to_string() });
+        assert!(server.remove_client(client_id));
+        assert!(server.compositor_state.idle_inhibitors.is_empty());
+        assert!(server.compositor_state.find_window(window_id).is_none());
+    }
+
+    #[test]
+    fn test_idle_inhibit_requires_mapped_window() {
+        let (mut server, client_id, window_id, _) = idle_server_with_video_window();
+        server.compositor_state.find_window_mut(window_id).unwrap().unmap();
+        let request = ClientRequest::InhibitIdle { client_id, window_id, reason: "Playing video".to_string() };
+        assert_eq!(server.process_client_request(request), None);
+        assert_eq!(
+            server.process_client_request(ClientRequest::InhibitIdle { client_id, window_id: 999, reason: String::new() }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_idle_inhibit_requires_window_owner() {
+        let (mut server, owner_id, window_id, _) = idle_server_with_video_window();
+        let other_id = server.add_client();
+        let request = ClientRequest::InhibitIdle { client_id: other_id, window_id, reason: "Keep awake".to_string() };
+        assert_eq!(server.process_client_request(request), None);
+
+        let request = ClientRequest::InhibitIdle { client_id: owner_id, window_id, reason: "Playing video".to_string() };
+        assert_eq!(server.process_client_request(request), Some(ServerEvent::IdleInhibited { window_id }));
+        assert_eq!(
+            server.process_client_request(ClientRequest::ReleaseIdleInhibit { client_id: other_id, window_id }),
+            None
+        );
+        assert_eq!(server.compositor_state.idle_inhibitors.len(), 1);
+
+        // Disconnecting another client leaves the inhibitor alone.
+        assert!(server.remove_client(other_id));
+        assert_eq!(server.compositor_state.idle_inhibitors.len(), 1);
+        assert_eq!(server.compositor_state.remove_client_idle_inhibitors(owner_id), 1);
+        assert!(server.compositor_state.idle_inhibitors.is_empty());
+    }
+
+    #[test]
+    fn test_windows_are_attached_to_launched_process() {
+        use crate::process_manager::{LaunchOptions, LaunchOutcome, LaunchPolicy, ProcessManager};
+        use novade_domain::entities::Application;
+
+        let manager = DefaultProcessManager::new();
+        let mut player = Application::new_desktop("sleep".to_string(), "sleep".to_string(), None);
+        player.arguments = Some(vec!["30".to_string()]);
+        let options = LaunchOptions { startup_token: Some("novade-player-1".to_string()), ..LaunchOptions::default() };
+        let LaunchOutcome::Launched(pids) =
+            manager.launch_application_with_policy(&player, &options, LaunchPolicy::MultipleInstances).unwrap()
+        else {
+            panic!("The player must be started");
+        };
+        let pid = pids[0];
+
+        let mut server = Server::new();
+        server.set_process_manager(manager.clone());
+        let create_window = |server: &mut Server, client_id| {
+            let request = ClientRequest::CreateWindow { client_id, title: "Player".to_string(), initial_width: 640, initial_height: 480 };
+            match server.process_client_request(request) {
+                Some(ServerEvent::WindowCreated { window_id, .. }) => window_id,
+                other => panic!("Window creation failed: {:?}", other),
+            }
+        };
+
+        // Matched by the client's PID.
+        let client_id = server.add_client_with_pid(pid);
+        let first = create_window(&mut server, client_id);
+        assert_eq!(manager.running_instances(&player.id)[0].window_ids, vec![first]);
+
+        // Matched by the startup token, for a client whose PID is unknown.
+        let other_client = server.add_client();
+        let second = create_window(&mut server, other_client);
+        assert_eq!(manager.running_instances(&player.id)[0].window_ids, vec![first]);
+        server.begin_startup("novade-player-1".to_string(), None);
+        let request = ClientRequest::ActivateWindow { window_id: second, token: "novade-player-1".to_string() };
+        assert!(server.process_client_request(request).is_some());
+        assert_eq!(manager.running_instances(&player.id)[0].window_ids, vec![first, second]);
+
+        assert!(server.remove_client(client_id));
+        assert_eq!(manager.running_instances(&player.id)[0].window_ids, vec![second]);
+        manager.terminate_process(pid).unwrap();
+    }
+
+    #[test]
+    fn test_activate_window_with_startup_token() {
+        let (mut server, _client_id, window_id, _) = idle_server_with_video_window();
+        server.begin_startup("novade-player-1".to_string(), Some("org.novade.Player".to_string()));
+        assert!(server.compositor_state.is_launching());
+
+        let forged = ClientRequest::ActivateWindow { window_id, token: "forged".to_string() };
+        assert_eq!(server.process_client_request(forged), None);
+        assert_eq!(server.compositor_state.seats[0].focused_window, None);
+
+        let request = ClientRequest::ActivateWindow { window_id, token: "novade-player-1".to_string() };
+        assert_eq!(
+            server.process_client_request(request),
+            Some(ServerEvent::WindowActivated { window_id, app_id: Some("org.novade.Player".to_string()) })
+        );
+        assert_eq!(server.compositor_state.seats[0].focused_window, Some(window_id));
+        assert!(!server.compositor_state.is_launching());
+    }
+
+    /// Backend that replays a fixed list of event batches and then stops.
+    #[derive(Default)]
+    struct ScriptedBackend {
+        batches: Vec<Vec<InputEvent>>,
+        outputs: Vec<crate::compositor::core::Output>,
+        pause_count: u32,
+        resume_count: u32,
+        device: Option<std::path::PathBuf>,
+        paused_devices: Vec<std::path::PathBuf>,
+        resumed_devices: Vec<(std::path::PathBuf, Option<std::fs::File>)>,
+        rendered: Vec<(u32, Vec<u8>)>,
+    }
+
+    impl Backend for ScriptedBackend {
+        fn name(&self) -> &'static str {
+            "scripted"
+        }
+
+        fn outputs(&self) -> Vec<crate::compositor::core::Output> {
+            self.outputs.clone()
+        }
+
+        fn dispatch_input_events(&mut self) -> CompositorResult<Vec<InputEvent>> {
+            Ok(self.batches.remove(0))
+        }
+
+        fn is_running(&self) -> bool {
+            !self.batches.is_empty()
+        }
+
+        fn pause(&mut self) {
+            self.pause_count += 1;
+        }
+
+        fn resume(&mut self) -> CompositorResult<()> {
+            self.resume_count += 1;
+            Ok(())
+        }
+
+        fn device_path(&self) -> Option<&std::path::Path> {
+            self.device.as_deref()
+        }
+
+        fn device_paused(&mut self, path: &std::path::Path) {
+            self.paused_devices.push(path.to_path_buf());
+        }
+
+        fn device_resumed(&mut self, path: &std::path::Path, file: Option<std::fs::File>) -> CompositorResult<()> {
+            self.resumed_devices.push((path.to_path_buf(), file));
+            Ok(())
+        }
+
+        fn set_output_power(&mut self, output_id: u32, on: bool) -> CompositorResult<()> {
+            let output = self.outputs.iter_mut().find(|o| o.id == output_id).expect("known output");
+            output.is_powered_on = on;
+            Ok(())
+        }
+
+        fn render_frame(&mut self, output_id: u32, draw: &mut crate::compositor::backend::DrawFrame<'_>) -> CompositorResult<bool> {
+            let output = self.outputs.iter().find(|o| o.id == output_id).expect("known output");
+            let (width, height) = (output.size.width, output.size.height);
+            let mut pixels = vec![0; (width * height * 4) as usize];
+            draw(&mut pixels, width, height, width * 4);
+            self.rendered.push((output_id, pixels));
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_display_layout_is_applied_and_restored() {
+        use crate::compositor::core::Output;
+        use crate::repositories::InMemoryDisplayLayoutRepository;
+        use std::sync::Arc;
+
+        let displays = DisplayService::new(Arc::new(InMemoryDisplayLayoutRepository::new()));
+        let outputs = vec![
+            Output::new(1, "eDP-1".to_string(), 1920, 1080, 0, 0, true),
+            Output::new(2, "HDMI-A-1".to_string(), 1920, 1080, 1920, 0, false),
+        ];
+        let mut backend = ScriptedBackend { batches: vec![Vec::new(); 2], outputs: outputs.clone(), ..Default::default() };
+        let mut server = Server::new();
+        server.backend_iteration(&mut backend).unwrap();
+        server.restore_display_layout(&displays).await.unwrap();
+        assert_eq!(server.compositor_state.outputs, outputs);
+
+        let mut layout = server.display_layout();
+        layout.outputs[1].x = -1920;
+        server.apply_display_layout(&displays, layout.clone()).unwrap();
+        assert_eq!(server.compositor_state.outputs[1].position.x, -1920);
+        let mut overlapping = layout.clone();
+        overlapping.outputs[1].x = 0;
+        assert!(server.apply_display_layout(&displays, overlapping).is_err());
+        server.save_display_layout(&displays).await.unwrap();
+
+        // After a restart the saved layout is picked up for the same outputs.
+        let mut server = Server::new();
+        server.backend_iteration(&mut backend).unwrap();
+        assert_eq!(server.compositor_state.outputs[1].position.x, 1920);
+        server.restore_display_layout(&displays).await.unwrap();
+        assert_eq!(server.compositor_state.outputs[1].position.x, -1920);
+        assert_eq!(server.display_layout(), layout);
+
+        // Disabled outputs are not part of the desktop.
+        layout.outputs[1].enabled = false;
+        server.apply_display_layout(&displays, layout).unwrap();
+        assert_eq!(server.compositor_state.outputs.len(), 1);
+    }
+
+    #[test]
+    fn test_backend_iteration_renders_powered_outputs() {
+        use crate::compositor::core::Output;
+
+        let mut first = Output::new(1, "eDP-1".to_string(), 40, 20, 0, 0, true);
+        first.is_powered_on = false;
+        let second = Output::new(2, "HDMI-A-1".to_string(), 40, 20, 40, 0, false);
+        let mut backend = ScriptedBackend { batches: vec![Vec::new()], outputs: vec![first, second], ..Default::default() };
+        let mut server = Server::new();
+        let mut window = Window::new(1, 1, "Editor".to_string(), 10, 10, 45, 5);
+        window.map();
+        server.compositor_state.add_window(window);
+
+        server.backend_iteration(&mut backend).unwrap();
+        assert_eq!(backend.rendered.len(), 1);
+        let (output_id, pixels) = &backend.rendered[0];
+        assert_eq!(*output_id, 2);
+        // The window starts at output-local (5, 5); the corner is background.
+        let offset = (5 * 40 + 5) * 4;
+        assert_ne!(pixels[offset..offset + 4], pixels[0..4]);
+    }
+
+    #[test]
+    fn test_set_output_power_updates_backend_and_state() {
+        let mut server = Server::new();
+        let output = crate::compositor::core::Output::new(1, "nested".to_string(), 800, 600, 0, 0, true);
+        server.compositor_state.outputs = vec![output.clone()];
+        let mut backend = ScriptedBackend { outputs: vec![output], ..Default::default() };
+
+        assert!(server.set_output_power(&mut backend, 1, false).unwrap());
+        assert!(!backend.outputs[0].is_powered_on);
+        assert_eq!(server.compositor_state.powered_outputs().count(), 0);
+        assert!(!server.set_output_power(&mut backend, 42, false).unwrap());
+    }
+
+    #[test]
+    fn test_set_output_power_request() {
+        let mut server = Server::new();
+        let client_id = server.add_client();
+        let output = crate::compositor::core::Output::new(1, "nested".to_string(), 800, 600, 0, 0, true);
+        server.compositor_state.outputs = vec![output.clone()];
+        let mut backend = ScriptedBackend { outputs: vec![output], ..Default::default() };
+
+        let request = BackendRequest::SetOutputPower { client_id, output_id: 1, on: false };
+        assert_eq!(
+            server.process_backend_request(request, &mut backend).unwrap(),
+            Some(ServerEvent::OutputPowerChanged { output_id: 1, on: false })
+        );
+        assert!(!backend.outputs[0].is_powered_on);
+        assert_eq!(server.compositor_state.powered_outputs().count(), 0);
+
+        let unknown_output = BackendRequest::SetOutputPower { client_id, output_id: 42, on: true };
+        assert_eq!(server.process_backend_request(unknown_output, &mut backend).unwrap(), None);
+        let unknown_client = BackendRequest::SetOutputPower { client_id: 99, output_id: 1, on: true };
+        assert_eq!(server.process_backend_request(unknown_client, &mut backend).unwrap(), None);
+        assert!(!backend.outputs[0].is_powered_on);
+    }
+
+    #[test]
+    fn test_outputs_powered_off_while_idle() {
+        let mut server = Server::new();
+        server.power_off_on_idle = true;
+        server.idle_tracker = IdleTracker::new(Some(std::time::Duration::ZERO), std::time::Instant::now());
+        let output = crate::compositor::core::Output::new(1, "nested".to_string(), 800, 600, 0, 0, true);
+        let key_event = InputEvent::Keyboard {
+            key_code: 65,
+            state: KeyState::Pressed,
+            modifiers: Modifiers::default(),
+        };
+        let mut backend = ScriptedBackend {
+            // Iteration 1 idles immediately (zero timeout), iteration 2 brings input.
+            batches: vec![Vec::new(), vec![key_event], Vec::new()],
+            outputs: vec![output],
+            ..Default::default()
+        };
+
+        server.backend_iteration(&mut backend).unwrap();
+        assert!(!backend.outputs[0].is_powered_on, "Idle powers outputs off");
+
+        server.idle_tracker.timeout = Some(std::time::Duration::from_secs(3600));
+        server.backend_iteration(&mut backend).unwrap();
+        assert!(backend.outputs[0].is_powered_on, "Input powers outputs back on");
+        assert!(server.compositor_state.outputs[0].is_powered_on);
+    }
+
+    /// Session that records acknowledged pauses and never reports events on its own.
+    #[derive(Default)]
+    struct FakeSession {
+        acknowledged: Vec<std::path::PathBuf>,
+    }
+
+    impl Session for FakeSession {
+        fn seat(&self) -> &str {
+            "seat0"
+        }
+
+        fn is_active(&self) -> bool {
+            true
+        }
+
+        fn open_device(&mut self, path: &std::path::Path) -> crate::session_management::SessionResult<std::fs::File> {
+            Err(crate::session_management::SessionError::DeviceAccess {
+                path: path.to_path_buf(),
+                message: "not supported".to_string(),
+            })
+        }
+
+        fn close_device(&mut self, _path: &std::path::Path) -> crate::session_management::SessionResult<()> {
+            Ok(())
+        }
+
+        fn acknowledge_pause(&mut self, path: &std::path::Path) -> crate::session_management::SessionResult<()> {
+            self.acknowledged.push(path.to_path_buf());
+            Ok(())
+        }
+
+        fn switch_vt(&mut self, _vt: u32) -> crate::session_management::SessionResult<()> {
+            Ok(())
+        }
+
+        fn dispatch_events(&mut self) -> Vec<SessionEvent> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_session_events_pause_and_resume_backend() {
+        let mut server = Server::new();
+        let mut session = FakeSession::default();
+        let card = std::path::PathBuf::from("/dev/dri/card0");
+        let mut backend = ScriptedBackend { device: Some(card.clone()), ..Default::default() };
+
+        server
+            .handle_session_event(SessionEvent::DevicePaused { path: card.clone(), needs_ack: true }, &mut session, &mut backend)
+            .unwrap();
+        // The session going inactive right after must not pause twice.
+        server.handle_session_event(SessionEvent::ActiveChanged(false), &mut session, &mut backend).unwrap();
+        assert!(server.is_session_paused());
+        assert_eq!(backend.pause_count, 1);
+        assert_eq!(session.acknowledged, vec![card.clone()]);
+
+        server
+            .handle_session_event(SessionEvent::DeviceResumed { path: card, file: None }, &mut session, &mut backend)
+            .unwrap();
+        server.handle_session_event(SessionEvent::ActiveChanged(true), &mut session, &mut backend).unwrap();
+        assert!(!server.is_session_paused());
+        assert_eq!(backend.resume_count, 1);
+    }
+
+    #[test]
+    fn test_input_device_events_do_not_pause_backend() {
+        let mut server = Server::new();
+        let mut session = FakeSession::default();
+        let mut backend =
+            ScriptedBackend { device: Some(std::path::PathBuf::from("/dev/dri/card0")), ..Default::default() };
+        let keyboard = std::path::PathBuf::from("/dev/input/event3");
+
+        server
+            .handle_session_event(SessionEvent::DevicePaused { path: keyboard.clone(), needs_ack: true }, &mut session, &mut backend)
+            .unwrap();
+        assert!(!server.is_session_paused());
+        assert_eq!(backend.pause_count, 0);
+        assert_eq!(backend.paused_devices, vec![keyboard.clone()]);
+        assert_eq!(session.acknowledged, vec![keyboard.clone()]);
+
+        let reopened = std::fs::File::open("/dev/null").unwrap();
+        server
+            .handle_session_event(
+                SessionEvent::DeviceResumed { path: keyboard.clone(), file: Some(reopened) },
+                &mut session,
+                &mut backend,
+            )
+            .unwrap();
+        assert_eq!(backend.resume_count, 0);
+        assert_eq!(backend.resumed_devices.len(), 1);
+        assert_eq!(backend.resumed_devices[0].0, keyboard);
+        assert!(backend.resumed_devices[0].1.is_some(), "the reopened fd must reach the backend");
+    }
+
+    #[test]
+    fn test_run_with_backend_uses_backend_outputs_and_events() {
+        let mut server = Server::new();
+        let output = crate::compositor::core::Output::new(1, "nested".to_string(), 800, 600, 0, 0, true);
+        let ctrl_c_event = InputEvent::Keyboard {
+            key_code: KEY_C,
+            state: KeyState::Pressed,
+            modifiers: Modifiers { ctrl: true, shift: false, alt: false, logo: false },
+        };
+        let mut backend = ScriptedBackend {
+            batches: vec![Vec::new(), vec![ctrl_c_event]],
+            outputs: vec![output.clone()],
+            ..Default::default()
+        };
+
+        server.run_with_backend(&mut backend).expect("Scripted backend never fails");
+
+        assert_eq!(server.compositor_state.outputs, vec![output]);
+        assert_eq!(
+            server.get_clipboard_data(),
+            Some("Simulated copied text from active window".to_string())
+        );
+    }
 }
@@ -0,0 +1,229 @@
+// src/session_management/logind.rs
+
+//! Session implementation backed by systemd-logind over D-Bus.
+//!
+//! Takes control of the caller's logind session, opens devices with `TakeDevice` and
+//! listens for `PauseDevice`/`ResumeDevice` signals and changes of the `Active` property
+//! on background threads, so [`Session::dispatch_events`] never blocks.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::{OwnedFd, OwnedObjectPath};
+
+use super::{device_numbers, Session, SessionError, SessionEvent, SessionResult};
+
+const LOGIND_DESTINATION: &str = "org.freedesktop.login1";
+const LOGIND_PATH: &str = "/org/freedesktop/login1";
+const MANAGER_INTERFACE: &str = "org.freedesktop.login1.Manager";
+const SESSION_INTERFACE: &str = "org.freedesktop.login1.Session";
+const SEAT_INTERFACE: &str = "org.freedesktop.login1.Seat";
+
+/// Major device number of DRM device nodes.
+const DRM_MAJOR: u32 = 226;
+
+/// Raw notifications forwarded from the signal threads.
+#[derive(Debug)]
+enum LogindSignal {
+    Pause { major: u32, minor: u32, kind: String },
+    Resume { major: u32, minor: u32, file: File },
+    Active(bool),
+}
+
+fn communication_error(context: &str, error: zbus::Error) -> SessionError {
+    SessionError::Communication(format!("{}: {}", context, error))
+}
+
+/// A logind session the compositor has taken control of.
+#[derive(Debug)]
+pub struct LogindSession {
+    session: Proxy<'static>,
+    seat_proxy: Proxy<'static>,
+    seat_name: String,
+    active: bool,
+    /// Devices opened through this session, keyed by (major, minor).
+    devices: HashMap<(u32, u32), PathBuf>,
+    signals: Receiver<LogindSignal>,
+}
+
+impl LogindSession {
+    /// Connects to logind, looks up the session of this process (`XDG_SESSION_ID` or the
+    /// caller's PID) and takes control of it.
+    pub fn new() -> SessionResult<Self> {
+        let acquisition_error = |context: &str, error: zbus::Error| {
+            SessionError::AcquisitionFailed(format!("{}: {}", context, error))
+        };
+
+        let connection = Connection::system().map_err(|e| acquisition_error("Failed to connect to system bus", e))?;
+        let manager = Proxy::new_owned(connection.clone(), LOGIND_DESTINATION, LOGIND_PATH, MANAGER_INTERFACE)
+            .map_err(|e| acquisition_error("Failed to create logind manager proxy", e))?;
+
+        let session_path: OwnedObjectPath = match std::env::var("XDG_SESSION_ID") {
+            Ok(id) => manager.call("GetSession", &(id,)),
+            Err(_) => manager.call("GetSessionByPID", &(std::process::id(),)),
+        }
+        .map_err(|e| acquisition_error("Failed to look up logind session", e))?;
+
+        let session = Proxy::new_owned(connection.clone(), LOGIND_DESTINATION, session_path, SESSION_INTERFACE)
+            .map_err(|e| acquisition_error("Failed to create session proxy", e))?;
+        let (seat_name, seat_path): (String, OwnedObjectPath) = session
+            .get_property("Seat")
+            .map_err(|e| acquisition_error("Failed to read session seat", e))?;
+        let seat_proxy = Proxy::new_owned(connection, LOGIND_DESTINATION, seat_path, SEAT_INTERFACE)
+            .map_err(|e| acquisition_error("Failed to create seat proxy", e))?;
+        let active: bool = session
+            .get_property("Active")
+            .map_err(|e| acquisition_error("Failed to read session state", e))?;
+
+        session
+            .call::<_, _, ()>("TakeControl", &(false,))
+            .map_err(|e| acquisition_error("Failed to take control of session", e))?;
+
+        let (sender, signals) = mpsc::channel();
+        spawn_signal_threads(&session, sender)?;
+
+        println!("LogindSession: Took control of session on seat '{}' (active: {}).", seat_name, active);
+        Ok(Self { session, seat_proxy, seat_name, active, devices: HashMap::new(), signals })
+    }
+
+    fn device_path(&self, major: u32, minor: u32) -> PathBuf {
+        self.devices
+            .get(&(major, minor))
+            .cloned()
+            .unwrap_or_else(|| PathBuf::from(format!("/dev/char/{}:{}", major, minor)))
+    }
+}
+
+fn numbers_for_path(path: &Path) -> SessionResult<(u32, u32)> {
+    let metadata = std::fs::metadata(path).map_err(|e| SessionError::DeviceAccess {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    Ok(device_numbers(metadata.rdev()))
+}
+
+fn spawn_signal_threads(session: &Proxy<'static>, sender: Sender<LogindSignal>) -> SessionResult<()> {
+    let pause_signals = session
+        .receive_signal("PauseDevice")
+        .map_err(|e| communication_error("Failed to subscribe to PauseDevice", e))?;
+    let pause_sender = sender.clone();
+    thread::spawn(move || {
+        for message in pause_signals {
+            if let Ok((major, minor, kind)) = message.body().deserialize::<(u32, u32, String)>() {
+                if pause_sender.send(LogindSignal::Pause { major, minor, kind }).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let resume_signals = session
+        .receive_signal("ResumeDevice")
+        .map_err(|e| communication_error("Failed to subscribe to ResumeDevice", e))?;
+    let resume_sender = sender.clone();
+    thread::spawn(move || {
+        for message in resume_signals {
+            if let Ok((major, minor, fd)) = message.body().deserialize::<(u32, u32, OwnedFd)>() {
+                let file = File::from(std::os::fd::OwnedFd::from(fd));
+                if resume_sender.send(LogindSignal::Resume { major, minor, file }).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let active_proxy = session.clone();
+    thread::spawn(move || {
+        for change in active_proxy.receive_property_changed::<bool>("Active") {
+            if let Ok(active) = change.get() {
+                if sender.send(LogindSignal::Active(active)).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+impl Session for LogindSession {
+    fn seat(&self) -> &str {
+        &self.seat_name
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn open_device(&mut self, path: &Path) -> SessionResult<File> {
+        let (major, minor) = numbers_for_path(path)?;
+        let (fd, _inactive): (OwnedFd, bool) =
+            self.session.call("TakeDevice", &(major, minor)).map_err(|e| SessionError::DeviceAccess {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+        self.devices.insert((major, minor), path.to_path_buf());
+        println!("LogindSession: Opened device {} ({}:{}).", path.display(), major, minor);
+        Ok(File::from(std::os::fd::OwnedFd::from(fd)))
+    }
+
+    fn close_device(&mut self, path: &Path) -> SessionResult<()> {
+        let (major, minor) = numbers_for_path(path)?;
+        self.devices.remove(&(major, minor));
+        self.session
+            .call::<_, _, ()>("ReleaseDevice", &(major, minor))
+            .map_err(|e| SessionError::DeviceAccess { path: path.to_path_buf(), message: e.to_string() })
+    }
+
+    fn acknowledge_pause(&mut self, path: &Path) -> SessionResult<()> {
+        let (major, minor) = numbers_for_path(path)?;
+        self.session
+            .call::<_, _, ()>("PauseDeviceComplete", &(major, minor))
+            .map_err(|e| communication_error("Failed to acknowledge device pause", e))
+    }
+
+    fn switch_vt(&mut self, vt: u32) -> SessionResult<()> {
+        self.seat_proxy
+            .call::<_, _, ()>("SwitchTo", &(vt,))
+            .map_err(|e| communication_error("Failed to switch VT", e))
+    }
+
+    fn dispatch_events(&mut self) -> Vec<SessionEvent> {
+        let mut events = Vec::new();
+        while let Ok(signal) = self.signals.try_recv() {
+            match signal {
+                LogindSignal::Pause { major, minor, kind } => {
+                    // "pause" expects an acknowledgement, "force" and "gone" do not.
+                    events.push(SessionEvent::DevicePaused {
+                        path: self.device_path(major, minor),
+                        needs_ack: kind == "pause",
+                    });
+                }
+                LogindSignal::Resume { major, minor, file } => {
+                    // DRM devices keep their descriptor across pauses (logind only drops
+                    // DRM master), so a new one is only meaningful for other devices.
+                    let file = if major == DRM_MAJOR { None } else { Some(file) };
+                    events.push(SessionEvent::DeviceResumed { path: self.device_path(major, minor), file });
+                }
+                LogindSignal::Active(active) if active != self.active => {
+                    self.active = active;
+                    println!("LogindSession: Session is now {}.", if active { "active" } else { "inactive" });
+                    events.push(SessionEvent::ActiveChanged(active));
+                }
+                LogindSignal::Active(_) => {}
+            }
+        }
+        events
+    }
+}
+
+impl Drop for LogindSession {
+    fn drop(&mut self) {
+        let _ = self.session.call::<_, _, ()>("ReleaseControl", &());
+    }
+}
@@ -0,0 +1,91 @@
+// src/session_management/mod.rs
+
+//! Session and seat management.
+//!
+//! A compositor running on real hardware must not open `/dev/dri/*` or `/dev/input/*`
+//! itself. Instead it asks the session manager (logind) for the devices, which hands out
+//! file descriptors and revokes them when the user switches to another VT. This module
+//! abstracts over that so the server can pause rendering while the session is inactive
+//! and resume cleanly on switch-back.
+
+#[cfg(feature = "session-logind")]
+pub mod logind;
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "session-logind")]
+pub use self::logind::LogindSession;
+
+/// Errors that can occur while talking to the session manager.
+#[derive(Debug)]
+pub enum SessionError {
+    /// No session could be acquired (no session manager, not running in a session, ...).
+    AcquisitionFailed(String),
+    /// The session manager refused to open or release a device.
+    DeviceAccess { path: PathBuf, message: String },
+    /// Any other failure while talking to the session manager.
+    Communication(String),
+}
+
+pub type SessionResult<T> = Result<T, SessionError>;
+
+/// Events reported by a [`Session`].
+#[derive(Debug)]
+pub enum SessionEvent {
+    /// A device opened through the session has been paused, usually because the user
+    /// switched to another VT. Stop using it; if `needs_ack` is set, call
+    /// [`Session::acknowledge_pause`] once done.
+    DevicePaused { path: PathBuf, needs_ack: bool },
+    /// A paused device is usable again. `file` holds a new descriptor if the old one was
+    /// revoked (input devices); DRM devices keep their descriptor and report `None`.
+    DeviceResumed { path: PathBuf, file: Option<File> },
+    /// The session as a whole became active (`true`) or inactive (`false`).
+    ActiveChanged(bool),
+}
+
+/// Interface to the session manager owning the seat the compositor runs on.
+pub trait Session {
+    /// Name of the seat this session is attached to (e.g., "seat0").
+    fn seat(&self) -> &str;
+
+    /// Whether the session is currently in the foreground.
+    fn is_active(&self) -> bool;
+
+    /// Opens a device node through the session manager.
+    fn open_device(&mut self, path: &Path) -> SessionResult<File>;
+
+    /// Releases a device previously opened with [`Session::open_device`].
+    fn close_device(&mut self, path: &Path) -> SessionResult<()>;
+
+    /// Confirms that the compositor has stopped using a paused device.
+    fn acknowledge_pause(&mut self, path: &Path) -> SessionResult<()>;
+
+    /// Switches to the given virtual terminal.
+    fn switch_vt(&mut self, vt: u32) -> SessionResult<()>;
+
+    /// Returns the session events received since the last call, without blocking.
+    fn dispatch_events(&mut self) -> Vec<SessionEvent>;
+}
+
+/// Splits a Linux `dev_t` (as returned by `MetadataExt::rdev`) into major and minor numbers.
+pub fn device_numbers(rdev: u64) -> (u32, u32) {
+    let major = ((rdev >> 8) & 0xfff) | ((rdev >> 32) & !0xfff);
+    let minor = (rdev & 0xff) | ((rdev >> 12) & !0xff);
+    (major as u32, minor as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_numbers() {
+        // /dev/dri/card0 is 226:0
+        assert_eq!(device_numbers(0xe200), (226, 0));
+        // /dev/input/event3 is 13:67
+        assert_eq!(device_numbers((13 << 8) | 67), (13, 67));
+        // Large minor numbers are split across the encoding
+        assert_eq!(device_numbers((0x12345 & 0xff) | ((0x12345 & !0xff) << 12) | (4 << 8)), (4, 0x12345));
+    }
+}